@@ -1,14 +1,19 @@
 use super::entry::BannerEntry;
 use crate::{
-    client,
+    client::{self, TlsBackend},
     config::Configuration,
     event_handlers::Handles,
+    notify::redact_webhook,
+    progress::DEFAULT_BAR_REFRESH_HZ,
+    scan_manager::DEFAULT_MAX_DIR_REQUESTS,
+    theme::themed,
     utils::{make_request, parse_url_with_raw_path, status_colorizer},
+    wordlists::SECLISTS_REVISION,
     DEFAULT_IGNORED_EXTENSIONS, DEFAULT_METHOD, DEFAULT_STATUS_CODES, VERSION,
 };
 use anyhow::{bail, Result};
 use console::{style, Emoji};
-use serde_json::Value;
+use serde_json::{json, Value};
 use std::collections::HashMap;
 use std::{io::Write, sync::Arc};
 
@@ -33,9 +38,15 @@ pub struct Banner {
     /// all live targets
     targets: Vec<BannerEntry>,
 
+    /// represents Configuration.run_id
+    run_id: BannerEntry,
+
     /// represents Configuration.status_codes
     status_codes: BannerEntry,
 
+    /// represents Configuration.status_groups
+    status_groups: Vec<BannerEntry>,
+
     /// represents Configuration.filter_status
     filter_status: BannerEntry,
 
@@ -45,21 +56,30 @@ pub struct Banner {
     /// represents Configuration.wordlist
     wordlist: BannerEntry,
 
+    /// represents Configuration.archive_seed
+    archive_seed: BannerEntry,
+
     /// represents Configuration.timeout
     timeout: BannerEntry,
 
+    /// represents Configuration.dns_cache_size and Configuration.dns_cache_ttl
+    dns_cache: BannerEntry,
+
     /// represents Configuration.user_agent
     user_agent: BannerEntry,
 
     /// represents Configuration.random_agent
     random_agent: BannerEntry,
 
-    /// represents Configuration.config
-    config: BannerEntry,
+    /// represents Configuration.config_files
+    config: Vec<BannerEntry>,
 
     /// represents Configuration.proxy
     proxy: BannerEntry,
 
+    /// represents Configuration.proxy_check
+    proxy_check: BannerEntry,
+
     /// represents Configuration.client_key
     client_key: BannerEntry,
 
@@ -78,6 +98,9 @@ pub struct Banner {
     /// represents Configuration.headers
     headers: Vec<BannerEntry>,
 
+    /// represents Configuration.interstitial_bypass
+    interstitial_bypass: Vec<BannerEntry>,
+
     /// represents Configuration.filter_size
     filter_size: Vec<BannerEntry>,
 
@@ -96,18 +119,42 @@ pub struct Banner {
     /// represents Configuration.extract_links
     extract_links: BannerEntry,
 
+    /// represents Configuration.extract_priority
+    extract_priority: BannerEntry,
+
     /// represents Configuration.json
     json: BannerEntry,
 
+    /// represents Configuration.sarif
+    sarif: BannerEntry,
+
+    /// represents Configuration.csv and Configuration.csv_columns
+    csv: BannerEntry,
+
+    /// represents Configuration.json_include_body
+    json_include_body: BannerEntry,
+
     /// represents Configuration.output
     output: BannerEntry,
 
+    /// represents Configuration.output_dir
+    output_dir: BannerEntry,
+
+    /// represents Configuration.output_format
+    output_format: BannerEntry,
+
     /// represents Configuration.debug_log
     debug_log: BannerEntry,
 
     /// represents Configuration.extensions
     extensions: BannerEntry,
 
+    /// represents Configuration.extension_wordlists
+    extension_wordlists: Vec<BannerEntry>,
+
+    /// represents Configuration.adaptive_extensions
+    adaptive_extensions: BannerEntry,
+
     /// represents Configuration.methods
     methods: BannerEntry,
 
@@ -117,6 +164,9 @@ pub struct Banner {
     /// represents Configuration.insecure
     insecure: BannerEntry,
 
+    /// represents Configuration.tls_backend
+    tls_backend: BannerEntry,
+
     /// represents Configuration.redirects
     redirects: BannerEntry,
 
@@ -126,15 +176,30 @@ pub struct Banner {
     /// represents Configuration.queries
     queries: Vec<BannerEntry>,
 
+    /// represents Configuration.meta
+    meta: Vec<BannerEntry>,
+
     /// represents Configuration.verbosity
     verbosity: BannerEntry,
 
     /// represents Configuration.add_slash
     add_slash: BannerEntry,
 
+    /// represents Configuration.auto_slash
+    auto_slash: BannerEntry,
+
+    /// represents Configuration.case_insensitive
+    case_insensitive: BannerEntry,
+
+    /// represents Configuration.retry_truncated
+    retry_truncated: BannerEntry,
+
     /// represents Configuration.no_recursion
     no_recursion: BannerEntry,
 
+    /// represents Configuration.recurse_threshold
+    recurse_threshold: BannerEntry,
+
     /// represents Configuration.scan_limit
     scan_limit: BannerEntry,
 
@@ -144,6 +209,9 @@ pub struct Banner {
     /// represents Configuration.rate_limit
     rate_limit: BannerEntry,
 
+    /// represents Configuration.inflight_per_dir
+    inflight_per_dir: BannerEntry,
+
     /// represents Configuration.parallel
     parallel: BannerEntry,
 
@@ -156,12 +224,18 @@ pub struct Banner {
     /// represents Configuration.url_denylist
     url_denylist: Vec<BannerEntry>,
 
+    /// represents Configuration.dont_scan_file
+    dont_scan_file: BannerEntry,
+
     /// current version of feroxbuster
     pub(super) version: String,
 
     /// whether or not there is a known new version
     pub(super) update_status: UpdateStatus,
 
+    /// shown when `update_status` is `OutOfDate`
+    update: BannerEntry,
+
     /// represents Configuration.collect_extensions
     collect_extensions: BannerEntry,
 
@@ -174,17 +248,146 @@ pub struct Banner {
     /// represents Configuration.collect_words
     collect_words: BannerEntry,
 
+    /// represents Configuration.word_languages
+    word_languages: BannerEntry,
+
+    /// represents Configuration.transliterate
+    transliterate: BannerEntry,
+
+    /// represents Configuration.collected_word_min_len
+    collected_word_min_len: BannerEntry,
+
+    /// represents Configuration.collected_word_max_len
+    collected_word_max_len: BannerEntry,
+
+    /// represents Configuration.collected_word_charset
+    collected_word_charset: BannerEntry,
+
+    /// represents Configuration.collected_word_limit
+    collected_word_limit: BannerEntry,
+
+    /// represents Configuration.save_collected_words
+    save_collected_words: BannerEntry,
+
     /// represents Configuration.collect_words
     force_recursion: BannerEntry,
 
     /// represents Configuration.protocol
     protocol: BannerEntry,
 
+    /// represents Configuration.both_schemes
+    both_schemes: BannerEntry,
+
+    /// represents Configuration.smart_schemes
+    smart_schemes: BannerEntry,
+
+    /// represents Configuration.no_scheme_probe
+    no_scheme_probe: BannerEntry,
+
     /// represents Configuration.scan_dir_listings
     scan_dir_listings: BannerEntry,
 
+    /// represents Configuration.scan_discovered_hosts
+    scan_discovered_hosts: BannerEntry,
+
+    /// represents Configuration.spa_mode
+    spa_mode: BannerEntry,
+
+    /// represents Configuration.survey_headers
+    survey_headers: BannerEntry,
+
+    /// represents Configuration.recon_banner
+    recon_banner: BannerEntry,
+
+    /// represents Configuration.known_urls
+    known_urls: BannerEntry,
+
+    /// represents Configuration.openapi
+    openapi: BannerEntry,
+
+    /// represents Configuration.graphql
+    graphql: BannerEntry,
+
+    /// represents Configuration.import_burp
+    import_burp: BannerEntry,
+
+    /// represents Configuration.changed_since
+    changed_since: BannerEntry,
+
+    /// represents Configuration.nmap_xml
+    nmap_xml: BannerEntry,
+
+    /// represents Configuration.import_crawl
+    import_crawl: BannerEntry,
+
     /// represents Configuration.limit_bars
     limit_bars: BannerEntry,
+
+    /// represents Configuration.bar_refresh_hz
+    bar_refresh_hz: BannerEntry,
+
+    /// represents Configuration.no_bars
+    no_bars: BannerEntry,
+
+    /// represents Configuration.ascii
+    ascii: BannerEntry,
+
+    /// represents Configuration.tree
+    tree: BannerEntry,
+
+    /// represents Configuration.error_events
+    error_events: BannerEntry,
+
+    /// represents Configuration.skip_events
+    skip_events: BannerEntry,
+
+    /// represents Configuration.detect_language
+    detect_language: BannerEntry,
+
+    /// represents Configuration.tag_findings
+    tag_findings: BannerEntry,
+
+    /// represents Configuration.tag_rules
+    tag_rules: Vec<BannerEntry>,
+
+    /// represents Configuration.flush_every
+    flush_every: BannerEntry,
+
+    /// represents Configuration.max_dir_requests
+    max_dir_requests: BannerEntry,
+
+    /// represents Configuration.export_urls
+    export_urls: BannerEntry,
+
+    /// represents Configuration.nuclei_targets
+    nuclei_targets: BannerEntry,
+
+    /// represents Configuration.report_html
+    report_html: BannerEntry,
+
+    /// represents Configuration.report_markdown
+    report_markdown: BannerEntry,
+
+    /// represents Configuration.report_xml
+    report_xml: BannerEntry,
+
+    /// represents Configuration.emit_ffuf_json
+    emit_ffuf_json: BannerEntry,
+
+    /// represents Configuration.campaign
+    campaign: BannerEntry,
+
+    /// represents Configuration.notify_webhook and the other --notify-* options
+    notify_webhook: BannerEntry,
+
+    /// represents Configuration.zap_api_url and Configuration.zap_api_key
+    zap_api: BannerEntry,
+
+    /// represents Configuration.preflight and Configuration.preflight_retries
+    preflight: BannerEntry,
+
+    /// represents Configuration.preflight_report
+    preflight_report: BannerEntry,
 }
 
 /// implementation of Banner
@@ -196,17 +399,21 @@ impl Banner {
         let mut code_filters = Vec::new();
         let mut replay_codes = Vec::new();
         let mut headers = Vec::new();
+        let mut interstitial_bypass = Vec::new();
         let mut filter_size = Vec::new();
         let mut filter_similar = Vec::new();
         let mut filter_word_count = Vec::new();
         let mut filter_line_count = Vec::new();
         let mut filter_regex = Vec::new();
         let mut queries = Vec::new();
+        let mut meta = Vec::new();
 
         for target in tgts {
             targets.push(BannerEntry::new("🎯", "Target Url", target));
         }
 
+        let run_id = BannerEntry::new("🆔", "Run ID", &config.run_id);
+
         for denied_url in &config.url_denylist {
             url_denylist.push(BannerEntry::new(
                 "🚫",
@@ -223,6 +430,8 @@ impl Banner {
             ));
         }
 
+        let dont_scan_file = BannerEntry::new("🚫", "Don't Scan File", &config.dont_scan_file);
+
         // the +2 is for the 2 experimental status codes we add to the default list manually
         let status_codes = if config.status_codes.len() == DEFAULT_STATUS_CODES.len() + 2 {
             let all_str = format!(
@@ -243,6 +452,24 @@ impl Banner {
             BannerEntry::new("👌", "Status Codes", &format!("[{}]", codes.join(", ")))
         };
 
+        let mut groups: Vec<_> = config.status_groups.iter().collect();
+        groups.sort();
+        let status_groups = groups
+            .into_iter()
+            .map(|(name, codes)| {
+                let codes: Vec<_> = codes
+                    .iter()
+                    .map(|code| status_colorizer(&code.to_string()))
+                    .collect();
+
+                BannerEntry::new(
+                    "🗂",
+                    "Status Group",
+                    &format!("@{name}: [{}]", codes.join(", ")),
+                )
+            })
+            .collect();
+
         for code in &config.filter_status {
             code_filters.push(status_colorizer(&code.to_string()))
         }
@@ -269,6 +496,14 @@ impl Banner {
             ));
         }
 
+        for (name, value) in &config.interstitial_bypass {
+            interstitial_bypass.push(BannerEntry::new(
+                "🍪",
+                "Interstitial Bypass",
+                &format!("{name}: {value}"),
+            ));
+        }
+
         for filter in &config.filter_size {
             filter_size.push(BannerEntry::new("💢", "Size Filter", &filter.to_string()));
         }
@@ -305,6 +540,13 @@ impl Banner {
             ));
         }
 
+        let mut pairs: Vec<_> = config.meta.iter().collect();
+        pairs.sort();
+
+        for (key, value) in pairs {
+            meta.push(BannerEntry::new("🏷", "Metadata", &format!("{key}={value}")));
+        }
+
         let volume = ["🔈", "🔉", "🔊", "📢"];
         let verbosity = if let 1..=4 = config.verbosity {
             //speaker medium volume (increasing with verbosity to loudspeaker)
@@ -329,21 +571,45 @@ impl Banner {
             BannerEntry::new("🚫", "Do Not Recurse", &config.no_recursion.to_string())
         };
 
+        let recurse_threshold = BannerEntry::new(
+            "🪜",
+            "Recursion Threshold",
+            &config.recurse_threshold.to_string(),
+        );
+
         let protocol = if config.protocol.to_lowercase() == "http" {
             BannerEntry::new("🔓", "Default Protocol", &config.protocol)
         } else {
             BannerEntry::new("🔒", "Default Protocol", &config.protocol)
         };
 
-        let scan_limit = BannerEntry::new(
-            "🦥",
-            "Concurrent Scan Limit",
-            &config.scan_limit.to_string(),
-        );
+        let both_schemes = BannerEntry::new("🔀", "Both Schemes", &config.both_schemes.to_string());
+        let smart_schemes =
+            BannerEntry::new("🔌", "Smart Schemes", &config.smart_schemes.to_string());
+        let no_scheme_probe =
+            BannerEntry::new("🚫", "No Scheme Probe", &config.no_scheme_probe.to_string());
+
+        let scan_limit = if config.interleave_targets {
+            BannerEntry::new(
+                "🦥",
+                "Concurrent Scan Limit",
+                &format!("{} (round-robin across targets)", config.scan_limit),
+            )
+        } else {
+            BannerEntry::new(
+                "🦥",
+                "Concurrent Scan Limit",
+                &config.scan_limit.to_string(),
+            )
+        };
 
         let force_recursion =
             BannerEntry::new("🤘", "Force Recursion", &config.force_recursion.to_string());
-        let replay_proxy = BannerEntry::new("🎥", "Replay Proxy", &config.replay_proxy);
+        let replay_proxy = BannerEntry::new(
+            "🎥",
+            "Replay Proxy",
+            &client::describe_proxy(&config.replay_proxy),
+        );
         let auto_tune = BannerEntry::new("🎶", "Auto Tune", &config.auto_tune.to_string());
         let auto_bail = BannerEntry::new("🙅", "Auto Bail", &config.auto_bail.to_string());
         let scan_dir_listings = BannerEntry::new(
@@ -351,8 +617,66 @@ impl Banner {
             "Scan Dir Listings",
             &config.scan_dir_listings.to_string(),
         );
-        let cfg = BannerEntry::new("💉", "Config File", &config.config);
-        let proxy = BannerEntry::new("💎", "Proxy", &config.proxy);
+        let scan_discovered_hosts = BannerEntry::new(
+            "🏘",
+            "Scan Discovered Hosts",
+            &config.scan_discovered_hosts.to_string(),
+        );
+        let spa_mode = BannerEntry::new(
+            "🧭",
+            "SPA Mode",
+            "auto-detect (filter app shell, api paths first, recursion depth <= 2)",
+        );
+        let survey_headers =
+            BannerEntry::new("🩺", "Survey Headers", &config.survey_headers.to_string());
+        let recon_banner = BannerEntry::new("🛰", "Recon Banner", &config.recon_banner.to_string());
+        let known_urls = BannerEntry::new("🌱", "Known Urls", &config.known_urls);
+        let openapi = BannerEntry::new("🧭", "OpenAPI Spec", &config.openapi);
+        let graphql = BannerEntry::new("🕸", "GraphQL Discovery", &config.graphql.to_string());
+        let import_burp = BannerEntry::new(
+            "📥",
+            "Burp Import",
+            &format!(
+                "{} ({} known url(s))",
+                config.import_burp,
+                config.imported_urls.len()
+            ),
+        );
+        let import_crawl = BannerEntry::new(
+            "🕷",
+            "Crawl Import",
+            &format!(
+                "{} ({} url(s))",
+                config.import_crawl,
+                config.crawled_urls.len()
+            ),
+        );
+        let changed_since = BannerEntry::new(
+            "📆",
+            "Changed Since",
+            &format!(
+                "{} ({} url(s))",
+                config.changed_since,
+                config.validators.len()
+            ),
+        );
+        let nmap_xml = BannerEntry::new(
+            "📡",
+            "Nmap Import",
+            &format!(
+                "{} ({} target(s))",
+                config.nmap_xml,
+                config.imported_targets.len()
+            ),
+        );
+        // each loaded config file is shown, in the order in which they were loaded
+        let cfg = config
+            .config_files
+            .iter()
+            .map(|config_file| BannerEntry::new("💉", "Config File", config_file))
+            .collect();
+        let proxy = BannerEntry::new("💎", "Proxy", &client::describe_proxy(&config.proxy));
+        let proxy_check = BannerEntry::new("🧦", "Proxy Check", &config.proxy_check.to_string());
         let server_certs = BannerEntry::new(
             "🏅",
             "Server Certificates",
@@ -363,20 +687,180 @@ impl Banner {
         let threads = BannerEntry::new("🚀", "Threads", &config.threads.to_string());
         let limit_bars =
             BannerEntry::new("📊", "Limit Dir Scan Bars", &config.limit_bars.to_string());
-        let wordlist = BannerEntry::new("📖", "Wordlist", &config.wordlist);
+        let error_events = BannerEntry::new("🧯", "Error Events", &config.error_events.to_string());
+        let skip_events = BannerEntry::new("🙈", "Skip Events", &config.skip_events.to_string());
+        let detect_language =
+            BannerEntry::new("🗣", "Detect Language", &config.detect_language.to_string());
+        let tag_findings = BannerEntry::new("🔖", "Tag Findings", "true");
+        let tag_rules = config
+            .tag_rules
+            .iter()
+            .map(|rule| BannerEntry::new("🔖", "Tag Rule", rule))
+            .collect();
+        let flush_every = BannerEntry::new(
+            "🚽",
+            "Flush Every (findings)",
+            &config.flush_every.to_string(),
+        );
+        let max_dir_requests = if config.max_dir_requests == 0 {
+            BannerEntry::new("🧮", "Max Requests Per Dir", "∞")
+        } else {
+            BannerEntry::new(
+                "🧮",
+                "Max Requests Per Dir",
+                &config.max_dir_requests.to_string(),
+            )
+        };
+        let export_urls = if config.export_status.is_empty() {
+            BannerEntry::new(
+                "📤",
+                "Export Urls",
+                &format!("{} ({})", config.export_urls, config.export_format),
+            )
+        } else {
+            let codes: Vec<_> = config
+                .export_status
+                .iter()
+                .map(|code| status_colorizer(&code.to_string()))
+                .collect();
+
+            BannerEntry::new(
+                "📤",
+                "Export Urls",
+                &format!(
+                    "{} ({}) [{}]",
+                    config.export_urls,
+                    config.export_format,
+                    codes.join(", ")
+                ),
+            )
+        };
+        let nuclei_targets = BannerEntry::new("🔬", "Nuclei Targets", &config.nuclei_targets);
+        let report_html = BannerEntry::new("📈", "HTML Report", &config.report_html);
+        let report_markdown = BannerEntry::new("📝", "Markdown Report", &config.report_markdown);
+        let report_xml = BannerEntry::new("🧬", "XML Report", &config.report_xml);
+        let emit_ffuf_json = BannerEntry::new("🪶", "ffuf JSON", &config.emit_ffuf_json);
+        let campaign = BannerEntry::new("📒", "Campaign Ledger", &config.campaign);
+        let notify_webhook = {
+            let mut details = Vec::new();
+
+            if config.notify_format != "generic" {
+                details.push(config.notify_format.clone());
+            }
+
+            if config.notify_min_status > 0 {
+                details.push(format!("status >= {}", config.notify_min_status));
+            }
+
+            details.push(match config.notify_rate_limit {
+                0 => String::from("no rate limit"),
+                limit => format!("{limit} per minute"),
+            });
+
+            BannerEntry::new(
+                "🔔",
+                "Notify Webhook",
+                // the url itself is a secret, only its host is shown
+                &format!(
+                    "{} ({})",
+                    redact_webhook(&config.notify_webhook),
+                    details.join(", ")
+                ),
+            )
+        };
+        let zap_api = BannerEntry::new(
+            "⚡",
+            "ZAP API",
+            &if config.zap_api_key.is_empty() {
+                config.zap_api_url.clone()
+            } else {
+                // the key itself stays out of the banner
+                format!("{} (with api key)", config.zap_api_url)
+            },
+        );
+        let preflight = BannerEntry::new(
+            "🛫",
+            "Preflight",
+            &format!(
+                "{} ({} retries)",
+                config.preflight, config.preflight_retries
+            ),
+        );
+        let preflight_report = BannerEntry::new("🛬", "Preflight Report", &config.preflight_report);
+        let no_bars = BannerEntry::new("🔕", "No Progress Bars", &config.no_bars.to_string());
+        let ascii = BannerEntry::new("🔠", "ASCII Output", &config.ascii.to_string());
+        let tree = BannerEntry::new("🌳", "Tree View", &config.tree.to_string());
+        let bar_refresh_hz = BannerEntry::new(
+            "🖌",
+            "Bar Refresh Rate (Hz)",
+            &config.bar_refresh_hz.to_string(),
+        );
+        let wordlist = if config.fetch_wordlist.is_empty() {
+            BannerEntry::new("📖", "Wordlist", &config.wordlist)
+        } else {
+            BannerEntry::new(
+                "📖",
+                "Wordlist",
+                &format!("{} (SecLists {})", config.fetch_wordlist, SECLISTS_REVISION),
+            )
+        };
+        let archive_seed = BannerEntry::new("🏛", "Archive Seed", &config.archive_seed.join(", "));
         let timeout = BannerEntry::new("💥", "Timeout (secs)", &config.timeout.to_string());
+        let dns_cache = BannerEntry::new(
+            "📇",
+            "DNS Cache",
+            &format!(
+                "{} hosts, {}s ttl",
+                config.dns_cache_size, config.dns_cache_ttl
+            ),
+        );
         let user_agent = BannerEntry::new("🦡", "User-Agent", &config.user_agent);
         let random_agent = BannerEntry::new("🦡", "User-Agent", "Random");
         let extract_links =
             BannerEntry::new("🔎", "Extract Links", &config.extract_links.to_string());
+        let extract_priority = BannerEntry::new("🐌", "Extract Priority", &config.extract_priority);
         let json = BannerEntry::new("🧔", "JSON Output", &config.json.to_string());
-        let output = BannerEntry::new("💾", "Output File", &config.output);
+        let sarif = BannerEntry::new("🧾", "SARIF Output", &config.sarif.to_string());
+        let csv = BannerEntry::new("📑", "CSV Columns", &config.csv_columns.join(","));
+        let json_include_body = if config.json_include_body == usize::MAX {
+            BannerEntry::new("📦", "JSON Include Body", "full body")
+        } else {
+            BannerEntry::new(
+                "📦",
+                "JSON Include Body",
+                &format!("{} bytes", config.json_include_body),
+            )
+        };
+        let output = if config.output_upload.is_empty() {
+            BannerEntry::new("💾", "Output File", &config.output)
+        } else {
+            BannerEntry::new(
+                "💾",
+                "Output File",
+                &format!("{} (staged at {})", config.output_upload, config.output),
+            )
+        };
+        let output_dir = BannerEntry::new("🗄", "Output Directory", &config.output_dir);
+        let output_format = BannerEntry::new("🧩", "Output Format", &config.output_format);
         let debug_log = BannerEntry::new("🪲", "Debugging Log", &config.debug_log);
         let extensions = BannerEntry::new(
             "💲",
             "Extensions",
             &format!("[{}]", config.extensions.join(", ")),
         );
+        let mut paired: Vec<_> = config.extension_wordlists.iter().collect();
+        paired.sort();
+        let extension_wordlists = paired
+            .into_iter()
+            .map(|(ext, wordlist)| {
+                BannerEntry::new("💲", "Extension Wordlist", &format!("{ext}: {wordlist}"))
+            })
+            .collect();
+        let adaptive_extensions = BannerEntry::new(
+            "🧪",
+            "Adaptive Extensions",
+            &format!("sample {} per directory", config.adaptive_extensions),
+        );
         let methods = BannerEntry::new(
             "🏁",
             "HTTP methods",
@@ -410,14 +894,28 @@ impl Banner {
             .replace('\r', "");
         let data = BannerEntry::new("💣", "HTTP Body", &data);
         let insecure = BannerEntry::new("🔓", "Insecure", &config.insecure.to_string());
+        let tls_backend = BannerEntry::new("🔐", "TLS Backend", &config.tls_backend.to_string());
         let redirects = BannerEntry::new("📍", "Follow Redirects", &config.redirects.to_string());
         let dont_filter =
             BannerEntry::new("🤪", "Filter Wildcards", &(!config.dont_filter).to_string());
         let add_slash = BannerEntry::new("🪓", "Add Slash", &config.add_slash.to_string());
+        let auto_slash = BannerEntry::new("🪓", "Auto Slash", &config.auto_slash.to_string());
+        let case_insensitive = BannerEntry::new(
+            "🔡",
+            "Case Insensitive",
+            &config.case_insensitive.to_string(),
+        );
+        let retry_truncated =
+            BannerEntry::new("🔁", "Retry Truncated", &config.retry_truncated.to_string());
         let time_limit = BannerEntry::new("🕖", "Time Limit", &config.time_limit);
         let parallel = BannerEntry::new("🛤", "Parallel Scans", &config.parallel.to_string());
         let rate_limit =
             BannerEntry::new("🚧", "Requests per Second", &config.rate_limit.to_string());
+        let inflight_per_dir = BannerEntry::new(
+            "🛩",
+            "In-flight Requests (per dir)",
+            &config.inflight_per_dir.to_string(),
+        );
         let collect_extensions = BannerEntry::new(
             "💰",
             "Collect Extensions",
@@ -428,60 +926,156 @@ impl Banner {
 
         let collect_words =
             BannerEntry::new("🤑", "Collect Words", &config.collect_words.to_string());
+        let word_languages =
+            BannerEntry::new("🈂", "Word Languages", &config.word_languages.join(", "));
+        let transliterate =
+            BannerEntry::new("🔤", "Transliterate", &config.transliterate.to_string());
+        let collected_word_min_len = BannerEntry::new(
+            "📏",
+            "Collected Word Min Length",
+            &config.collected_word_min_len.to_string(),
+        );
+        let collected_word_max_len = BannerEntry::new(
+            "📐",
+            "Collected Word Max Length",
+            &config.collected_word_max_len.to_string(),
+        );
+        let collected_word_charset = BannerEntry::new(
+            "🔡",
+            "Collected Word Charset",
+            &config.collected_word_charset,
+        );
+        let collected_word_limit = BannerEntry::new(
+            "🔢",
+            "Collected Word Limit",
+            &config.collected_word_limit.to_string(),
+        );
+        let save_collected_words =
+            BannerEntry::new("🗃", "Save Collected Words", &config.save_collected_words);
 
         Self {
             targets,
+            run_id,
             status_codes,
+            status_groups,
             threads,
             wordlist,
+            archive_seed,
             filter_status,
             timeout,
+            dns_cache,
             user_agent,
             random_agent,
             auto_bail,
             auto_tune,
             proxy,
+            proxy_check,
             client_cert,
             client_key,
             server_certs,
             replay_codes,
             replay_proxy,
             headers,
+            interstitial_bypass,
             filter_size,
             filter_similar,
             filter_word_count,
             filter_line_count,
             filter_regex,
             extract_links,
+            extract_priority,
             parallel,
             json,
+            sarif,
+            csv,
+            json_include_body,
             queries,
+            meta,
             output,
+            output_dir,
+            output_format,
             debug_log,
             extensions,
+            extension_wordlists,
+            adaptive_extensions,
             methods,
             data,
             insecure,
+            tls_backend,
             dont_filter,
             redirects,
             verbosity,
             add_slash,
+            auto_slash,
+            case_insensitive,
+            retry_truncated,
             no_recursion,
+            recurse_threshold,
             rate_limit,
+            inflight_per_dir,
             scan_limit,
             force_recursion,
             time_limit,
             url_denylist,
+            dont_scan_file,
             collect_extensions,
             collect_backups,
             collect_words,
+            word_languages,
+            transliterate,
+            collected_word_min_len,
+            collected_word_max_len,
+            collected_word_charset,
+            collected_word_limit,
+            save_collected_words,
             dont_collect,
             config: cfg,
             scan_dir_listings,
+            scan_discovered_hosts,
+            spa_mode,
+            survey_headers,
+            recon_banner,
+            known_urls,
+            openapi,
+            graphql,
+            import_burp,
+            changed_since,
+            nmap_xml,
+            import_crawl,
             protocol,
+            both_schemes,
+            smart_schemes,
+            no_scheme_probe,
             limit_bars,
+            bar_refresh_hz,
+            no_bars,
+            ascii,
+            tree,
+            error_events,
+            skip_events,
+            detect_language,
+            tag_findings,
+            tag_rules,
+            flush_every,
+            max_dir_requests,
+            export_urls,
+            nuclei_targets,
+            report_html,
+            report_markdown,
+            report_xml,
+            emit_ffuf_json,
+            campaign,
+            notify_webhook,
+            zap_api,
+            preflight,
+            preflight_report,
             version: VERSION.to_string(),
             update_status: UpdateStatus::Unknown,
+            update: BannerEntry::new(
+                "🎉",
+                "New Version Available",
+                "https://github.com/epi052/feroxbuster/releases/latest",
+            ),
         }
     }
 
@@ -533,6 +1127,7 @@ by Ben "epi" Risher {}                 ver: {}"#,
             "feroxbuster-update-check",
             handles.config.redirects,
             handles.config.insecure,
+            handles.config.tls_backend,
             &HashMap::new(),
             Some(&handles.config.proxy),
             &handles.config.server_certs,
@@ -589,206 +1184,501 @@ by Ben "epi" Risher {}                 ver: {}"#,
     where
         W: Write,
     {
-        writeln!(&mut writer, "{}", self.header())?;
+        writeln!(&mut writer, "{}", themed(&self.header()))?;
+
+        for entry in self.entries(&config) {
+            writeln!(&mut writer, "{entry}")?;
+        }
+
+        writeln!(&mut writer, "{}", themed(&self.footer()))?;
+
+        Ok(())
+    }
+
+    /// write the same entries shown by `print_to` as a single line of JSON, for wrappers that
+    /// want to know what settings a scan ran with (--banner-json)
+    ///
+    /// ex (expanded for clarity):
+    /// {
+    ///    "type":"banner",
+    ///    "version":"2.11.0",
+    ///    "entries":[
+    ///       {"title":"Target Url","value":"http://localhost"},
+    ///       {"title":"Threads","value":"50"}
+    ///    ]
+    /// }
+    pub fn print_json_to<W>(&self, mut writer: W, config: Arc<Configuration>) -> Result<()>
+    where
+        W: Write,
+    {
+        let json = json!({
+            "type": "banner",
+            "version": self.version,
+            "entries": self.entries(&config),
+        });
+
+        writeln!(&mut writer, "{json}")?;
+
+        Ok(())
+    }
+
+    /// every entry to be displayed, given the current configuration, in the order they're shown
+    fn entries(&self, config: &Configuration) -> Vec<&BannerEntry> {
+        let mut entries = Vec::new();
 
         // begin with always printed items
         for target in &self.targets {
-            writeln!(&mut writer, "{target}")?;
+            entries.push(target);
+        }
+
+        if !config.run_id.is_empty() {
+            // empty only when a Configuration was built by hand, rather than at startup
+            entries.push(&self.run_id);
         }
 
         for denied_url in &self.url_denylist {
-            writeln!(&mut writer, "{denied_url}")?;
+            entries.push(denied_url);
+        }
+
+        if !config.dont_scan_file.is_empty() {
+            entries.push(&self.dont_scan_file);
         }
 
-        writeln!(&mut writer, "{}", self.threads)?;
-        writeln!(&mut writer, "{}", self.wordlist)?;
+        entries.push(&self.threads);
+        entries.push(&self.wordlist);
+
+        if !config.archive_seed.is_empty() {
+            entries.push(&self.archive_seed);
+        }
 
         if config.filter_status.is_empty() {
             // -C and -s are mutually exclusive, and -s meaning changes when -C is used
             // so only print one or the other
-            writeln!(&mut writer, "{}", self.status_codes)?;
+            entries.push(&self.status_codes);
         } else {
-            writeln!(&mut writer, "{}", self.filter_status)?;
+            entries.push(&self.filter_status);
+        }
+
+        for group in &self.status_groups {
+            entries.push(group);
         }
 
-        writeln!(&mut writer, "{}", self.timeout)?;
+        entries.push(&self.timeout);
+
+        if config.dns_cache_size > 0 {
+            entries.push(&self.dns_cache);
+        }
 
         if config.random_agent {
-            writeln!(&mut writer, "{}", self.random_agent)?;
+            entries.push(&self.random_agent);
         } else {
-            writeln!(&mut writer, "{}", self.user_agent)?;
+            entries.push(&self.user_agent);
         }
 
         // followed by the maybe printed or variably displayed values
         if !config.request_file.is_empty() || !config.target_url.starts_with("http") {
-            writeln!(&mut writer, "{}", self.protocol)?;
+            entries.push(&self.protocol);
+        }
+
+        if config.both_schemes {
+            entries.push(&self.both_schemes);
+        }
+
+        if config.smart_schemes {
+            entries.push(&self.smart_schemes);
+        }
+
+        if config.no_scheme_probe {
+            entries.push(&self.no_scheme_probe);
         }
 
         if config.limit_bars > 0 {
-            writeln!(&mut writer, "{}", self.limit_bars)?;
+            entries.push(&self.limit_bars);
+        }
+
+        if config.bar_refresh_hz != DEFAULT_BAR_REFRESH_HZ {
+            entries.push(&self.bar_refresh_hz);
+        }
+
+        if config.no_bars {
+            entries.push(&self.no_bars);
+        }
+
+        if config.ascii {
+            entries.push(&self.ascii);
+        }
+
+        if config.tree {
+            entries.push(&self.tree);
+        }
+
+        if config.error_events {
+            entries.push(&self.error_events);
         }
 
-        if !config.config.is_empty() {
-            writeln!(&mut writer, "{}", self.config)?;
+        if config.skip_events {
+            entries.push(&self.skip_events);
+        }
+
+        if config.detect_language {
+            entries.push(&self.detect_language);
+        }
+
+        if config.tag_findings || !config.tag_rules.is_empty() {
+            entries.push(&self.tag_findings);
+        }
+
+        for tag_rule in &self.tag_rules {
+            entries.push(tag_rule);
+        }
+
+        if config.flush_every > 1 {
+            entries.push(&self.flush_every);
+        }
+
+        if config.max_dir_requests != DEFAULT_MAX_DIR_REQUESTS {
+            entries.push(&self.max_dir_requests);
+        }
+
+        if !config.export_urls.is_empty() {
+            entries.push(&self.export_urls);
+        }
+
+        if !config.nuclei_targets.is_empty() {
+            entries.push(&self.nuclei_targets);
+        }
+
+        if !config.report_html.is_empty() {
+            entries.push(&self.report_html);
+        }
+
+        if !config.report_markdown.is_empty() {
+            entries.push(&self.report_markdown);
+        }
+
+        if !config.report_xml.is_empty() {
+            entries.push(&self.report_xml);
+        }
+
+        if !config.emit_ffuf_json.is_empty() {
+            entries.push(&self.emit_ffuf_json);
+        }
+
+        if !config.campaign.is_empty() {
+            entries.push(&self.campaign);
+        }
+
+        if !config.notify_webhook.is_empty() {
+            entries.push(&self.notify_webhook);
+        }
+
+        if !config.zap_api_url.is_empty() {
+            entries.push(&self.zap_api);
+        }
+
+        if config.preflight != "warn" || config.preflight_retries > 0 {
+            entries.push(&self.preflight);
+        }
+
+        if !config.preflight_report.is_empty() {
+            entries.push(&self.preflight_report);
+        }
+
+        for config_file in &self.config {
+            entries.push(config_file);
         }
 
         if !config.proxy.is_empty() {
-            writeln!(&mut writer, "{}", self.proxy)?;
+            entries.push(&self.proxy);
+        }
+
+        if config.proxy_check {
+            entries.push(&self.proxy_check);
         }
 
         if !config.client_cert.is_empty() {
-            writeln!(&mut writer, "{}", self.client_cert)?;
+            entries.push(&self.client_cert);
         }
 
         if !config.client_key.is_empty() {
-            writeln!(&mut writer, "{}", self.client_key)?;
+            entries.push(&self.client_key);
         }
 
         if !config.server_certs.is_empty() {
-            writeln!(&mut writer, "{}", self.server_certs)?;
+            entries.push(&self.server_certs);
         }
 
         if !config.replay_proxy.is_empty() {
             // i include replay codes logic here because in config.rs, replay codes are set to the
             // value in status codes, meaning it's never empty
-            writeln!(&mut writer, "{}", self.replay_proxy)?;
-            writeln!(&mut writer, "{}", self.replay_codes)?;
+            entries.push(&self.replay_proxy);
+            entries.push(&self.replay_codes);
         }
 
         for header in &self.headers {
-            writeln!(&mut writer, "{header}")?;
+            entries.push(header);
+        }
+
+        for header in &self.interstitial_bypass {
+            entries.push(header);
         }
 
         for filter in &self.filter_size {
-            writeln!(&mut writer, "{filter}")?;
+            entries.push(filter);
         }
 
         for filter in &self.filter_similar {
-            writeln!(&mut writer, "{filter}")?;
+            entries.push(filter);
         }
 
         for filter in &self.filter_word_count {
-            writeln!(&mut writer, "{filter}")?;
+            entries.push(filter);
         }
 
         for filter in &self.filter_line_count {
-            writeln!(&mut writer, "{filter}")?;
+            entries.push(filter);
         }
 
         for filter in &self.filter_regex {
-            writeln!(&mut writer, "{filter}")?;
+            entries.push(filter);
         }
 
         if config.extract_links {
-            writeln!(&mut writer, "{}", self.extract_links)?;
+            entries.push(&self.extract_links);
+
+            if config.extract_priority != "normal" {
+                entries.push(&self.extract_priority);
+            }
         }
 
         if config.json {
-            writeln!(&mut writer, "{}", self.json)?;
+            entries.push(&self.json);
+        }
+
+        if config.sarif {
+            entries.push(&self.sarif);
+        }
+
+        if config.csv {
+            entries.push(&self.csv);
+        }
+
+        if config.json_include_body > 0 {
+            entries.push(&self.json_include_body);
         }
 
         for query in &self.queries {
-            writeln!(&mut writer, "{query}")?;
+            entries.push(query);
         }
 
         if !config.output.is_empty() {
-            writeln!(&mut writer, "{}", self.output)?;
+            entries.push(&self.output);
+        }
+
+        if !config.output_dir.is_empty() {
+            entries.push(&self.output_dir);
+        }
+
+        if !config.output_format.is_empty() {
+            entries.push(&self.output_format);
+        }
+
+        for pair in &self.meta {
+            entries.push(pair);
         }
 
         if config.scan_dir_listings {
-            writeln!(&mut writer, "{}", self.scan_dir_listings)?;
+            entries.push(&self.scan_dir_listings);
+        }
+
+        if config.scan_discovered_hosts {
+            entries.push(&self.scan_discovered_hosts);
+        }
+
+        if config.spa_mode {
+            entries.push(&self.spa_mode);
+        }
+
+        if config.survey_headers {
+            entries.push(&self.survey_headers);
+        }
+
+        if config.recon_banner {
+            entries.push(&self.recon_banner);
+        }
+
+        if !config.known_urls.is_empty() {
+            entries.push(&self.known_urls);
+        }
+
+        if !config.openapi.is_empty() {
+            entries.push(&self.openapi);
+        }
+
+        if config.graphql {
+            entries.push(&self.graphql);
+        }
+
+        if !config.import_burp.is_empty() {
+            entries.push(&self.import_burp);
+        }
+
+        if !config.changed_since.is_empty() {
+            entries.push(&self.changed_since);
+        }
+
+        if !config.nmap_xml.is_empty() {
+            entries.push(&self.nmap_xml);
+        }
+
+        if !config.import_crawl.is_empty() {
+            entries.push(&self.import_crawl);
         }
 
         if !config.debug_log.is_empty() {
-            writeln!(&mut writer, "{}", self.debug_log)?;
+            entries.push(&self.debug_log);
         }
 
         if !config.extensions.is_empty() {
-            writeln!(&mut writer, "{}", self.extensions)?;
+            entries.push(&self.extensions);
+        }
+
+        for paired in &self.extension_wordlists {
+            entries.push(paired);
+        }
+
+        if config.adaptive_extensions > 0 {
+            entries.push(&self.adaptive_extensions);
         }
 
         if config.collect_extensions {
             // dont-collect is active only when collect-extensions is used
-            writeln!(&mut writer, "{}", self.collect_extensions)?;
-            writeln!(&mut writer, "{}", self.dont_collect)?;
+            entries.push(&self.collect_extensions);
+            entries.push(&self.dont_collect);
         }
 
         if config.collect_backups {
-            writeln!(&mut writer, "{}", self.collect_backups)?;
+            entries.push(&self.collect_backups);
         }
 
         if config.collect_words {
-            writeln!(&mut writer, "{}", self.collect_words)?;
+            entries.push(&self.collect_words);
+
+            // word languages and transliteration only matter when words are collected
+            if config.word_languages != ["en"] {
+                entries.push(&self.word_languages);
+            }
+
+            if config.transliterate {
+                entries.push(&self.transliterate);
+            }
+
+            if config.collected_word_min_len > 0 {
+                entries.push(&self.collected_word_min_len);
+            }
+
+            if config.collected_word_max_len > 0 {
+                entries.push(&self.collected_word_max_len);
+            }
+
+            if config.collected_word_charset != "any" {
+                entries.push(&self.collected_word_charset);
+            }
+
+            if config.collected_word_limit > 0 {
+                entries.push(&self.collected_word_limit);
+            }
+
+            if !config.save_collected_words.is_empty() {
+                entries.push(&self.save_collected_words);
+            }
         }
 
         if !config.methods.is_empty() {
-            writeln!(&mut writer, "{}", self.methods)?;
+            entries.push(&self.methods);
         }
 
         if !config.data.is_empty() {
-            writeln!(&mut writer, "{}", self.data)?;
+            entries.push(&self.data);
         }
 
         if config.insecure {
-            writeln!(&mut writer, "{}", self.insecure)?;
+            entries.push(&self.insecure);
+        }
+
+        if config.tls_backend != TlsBackend::default() {
+            entries.push(&self.tls_backend);
         }
 
         if config.auto_bail {
-            writeln!(&mut writer, "{}", self.auto_bail)?;
+            entries.push(&self.auto_bail);
         }
         if config.auto_tune {
-            writeln!(&mut writer, "{}", self.auto_tune)?;
+            entries.push(&self.auto_tune);
         }
 
         if config.redirects {
-            writeln!(&mut writer, "{}", self.redirects)?;
+            entries.push(&self.redirects);
         }
 
         if config.dont_filter {
-            writeln!(&mut writer, "{}", self.dont_filter)?;
+            entries.push(&self.dont_filter);
         }
 
         if let 1..=4 = config.verbosity {
-            writeln!(&mut writer, "{}", self.verbosity)?;
+            entries.push(&self.verbosity);
         }
 
         if config.add_slash {
-            writeln!(&mut writer, "{}", self.add_slash)?;
+            entries.push(&self.add_slash);
         }
 
-        writeln!(&mut writer, "{}", self.no_recursion)?;
+        if config.auto_slash {
+            entries.push(&self.auto_slash);
+        }
+
+        if config.case_insensitive {
+            entries.push(&self.case_insensitive);
+        }
+
+        if config.retry_truncated {
+            entries.push(&self.retry_truncated);
+        }
+
+        entries.push(&self.no_recursion);
+
+        if !config.no_recursion && config.recurse_threshold > 0 {
+            entries.push(&self.recurse_threshold);
+        }
 
         if config.force_recursion {
-            writeln!(&mut writer, "{}", self.force_recursion)?;
+            entries.push(&self.force_recursion);
         }
 
         if config.scan_limit > 0 {
-            writeln!(&mut writer, "{}", self.scan_limit)?;
+            entries.push(&self.scan_limit);
         }
 
         if config.parallel > 0 {
-            writeln!(&mut writer, "{}", self.parallel)?;
+            entries.push(&self.parallel);
         }
 
         if config.rate_limit > 0 {
-            writeln!(&mut writer, "{}", self.rate_limit)?;
+            entries.push(&self.rate_limit);
+        }
+
+        if config.inflight_per_dir > 0 {
+            entries.push(&self.inflight_per_dir);
         }
 
         if !config.time_limit.is_empty() {
-            writeln!(&mut writer, "{}", self.time_limit)?;
+            entries.push(&self.time_limit);
         }
 
         if matches!(self.update_status, UpdateStatus::OutOfDate) {
-            let update = BannerEntry::new(
-                "🎉",
-                "New Version Available",
-                "https://github.com/epi052/feroxbuster/releases/latest",
-            );
-            writeln!(&mut writer, "{update}")?;
+            entries.push(&self.update);
         }
 
-        writeln!(&mut writer, "{}", self.footer())?;
-
-        Ok(())
+        entries
     }
 }
@@ -1,6 +1,9 @@
-use console::{measure_text_width, Emoji};
+use console::{measure_text_width, strip_ansi_codes, Emoji};
+use serde::{ser::SerializeStruct, Serialize, Serializer};
 use std::fmt;
 
+use crate::theme::{ascii, glyph};
+
 /// Initial visual indentation size used in formatting banner entries
 const INDENT: usize = 3;
 
@@ -36,19 +39,44 @@ impl BannerEntry {
         let width = measure_text_width(&self.emoji);
         let pad_len = width * width;
         let pad = format!("{:<pad_len$}", "\u{0020}", pad_len = pad_len);
+
+        if ascii() {
+            // --ascii, or a terminal that can't draw emoji; same fallback console would use
+            return pad;
+        }
+
         Emoji(&self.emoji, &pad).to_string()
     }
 }
 
+/// Serialize implementation for a banner entry
+///
+/// the emoji is purely decorative and left out, as is any styling applied to the value
+impl Serialize for BannerEntry {
+    /// Function that handles serialization of a banner entry to JSON
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut state = serializer.serialize_struct("BannerEntry", 2)?;
+
+        state.serialize_field("title", &self.title)?;
+        state.serialize_field("value", &strip_ansi_codes(&self.value))?;
+
+        state.end()
+    }
+}
+
 /// Display implementation for a banner entry
 impl fmt::Display for BannerEntry {
     /// Display formatter for the given banner entry
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(
             f,
-            "\u{0020}{:\u{0020}<indent$}{:\u{0020}<width$}\u{2502}\u{0020}{}",
+            "\u{0020}{:\u{0020}<indent$}{:\u{0020}<width$}{}\u{0020}{}",
             self.format_emoji(),
             self.title,
+            glyph("\u{2502}", "|"),
             self.value,
             indent = INDENT,
             width = COL_WIDTH
@@ -1,8 +1,10 @@
 //! all logic related to building/printing the banner seen when scans start
 mod container;
 mod entry;
+mod recon;
 
 #[cfg(test)]
 mod tests;
 
 pub use self::container::{Banner, UPDATE_URL};
+pub use self::recon::TargetRecon;
@@ -0,0 +1,308 @@
+use std::{collections::BTreeSet, io::Write, net::IpAddr};
+
+use anyhow::Result;
+use console::style;
+use reqwest::{
+    header::{HeaderMap, SERVER, SET_COOKIE, VIA},
+    Response, Url,
+};
+
+// only needed to read the target's certificate
+#[cfg(feature = "native-tls")]
+use openssl::{nid::Nid, x509::X509};
+#[cfg(feature = "native-tls")]
+use reqwest::{redirect::Policy, tls::TlsInfo, Client, Proxy};
+#[cfg(feature = "native-tls")]
+use std::time::Duration;
+
+use super::entry::BannerEntry;
+use crate::{config::Configuration, theme::themed};
+
+/// cookie name prefixes that give away the technology behind a target
+const COOKIE_SIGNATURES: [(&str, &str); 8] = [
+    ("phpsessid", "PHP"),
+    ("jsessionid", "Java"),
+    ("asp.net_sessionid", "ASP.NET"),
+    ("aspsessionid", "Classic ASP"),
+    ("laravel_session", "Laravel"),
+    ("ci_session", "CodeIgniter"),
+    ("connect.sid", "Express"),
+    ("wordpress_", "WordPress"),
+];
+
+/// Summary of what could be learned about a target before scanning it (--recon-banner)
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct TargetRecon {
+    /// the target url
+    pub(crate) target: String,
+
+    /// value of the `Server` header, if present
+    pub(crate) server: Option<String>,
+
+    /// technologies given away by the target's headers and cookies
+    pub(crate) technologies: Vec<String>,
+
+    /// common name of the target's TLS certificate, if served over https
+    pub(crate) certificate_cn: Option<String>,
+
+    /// addresses the target's hostname resolves to
+    pub(crate) addresses: Vec<IpAddr>,
+
+    /// best guess at a CDN or WAF sitting in front of the target
+    pub(crate) cdn_waf: Option<String>,
+}
+
+/// implementation of TargetRecon
+impl TargetRecon {
+    /// gather target intelligence from the response to the connectivity check, plus a dns
+    /// lookup and, for https targets, a look at the certificate
+    pub(crate) async fn gather(target: &str, response: &Response, config: &Configuration) -> Self {
+        log::trace!("enter: gather({})", target);
+
+        let headers = response.headers();
+
+        let server = headers
+            .get(SERVER)
+            .map(|value| String::from_utf8_lossy(value.as_bytes()).to_string());
+
+        let url = response.url().clone();
+
+        let recon = Self {
+            target: target.to_string(),
+            server,
+            technologies: detect_technologies(headers),
+            certificate_cn: certificate_common_name(&url, config).await,
+            addresses: resolve_addresses(&url).await,
+            cdn_waf: guess_cdn_waf(headers).map(String::from),
+        };
+
+        log::trace!("exit: gather -> {:?}", recon);
+        recon
+    }
+
+    /// print the intelligence section to the given writer; meant to sit directly beneath the
+    /// banner
+    pub fn print_to<W>(&self, mut writer: W) -> Result<()>
+    where
+        W: Write,
+    {
+        let unknown = style("unknown").dim().to_string();
+
+        let header = format!(
+            " 🛰   Target Intelligence: {}",
+            style(&self.target).bright().yellow()
+        );
+        writeln!(&mut writer, "{}", themed(&header))?;
+
+        let server = BannerEntry::new("🖥", "Server", self.server.as_deref().unwrap_or(&unknown));
+        writeln!(&mut writer, "{server}")?;
+
+        let technologies = if self.technologies.is_empty() {
+            unknown.clone()
+        } else {
+            self.technologies.join(", ")
+        };
+        writeln!(
+            &mut writer,
+            "{}",
+            BannerEntry::new("🧩", "Technologies", &technologies)
+        )?;
+
+        if let Some(common_name) = &self.certificate_cn {
+            writeln!(
+                &mut writer,
+                "{}",
+                BannerEntry::new("📜", "Certificate CN", common_name)
+            )?;
+        }
+
+        let addresses = if self.addresses.is_empty() {
+            unknown.clone()
+        } else {
+            self.addresses
+                .iter()
+                .map(|address| address.to_string())
+                .collect::<Vec<_>>()
+                .join(", ")
+        };
+        writeln!(
+            &mut writer,
+            "{}",
+            BannerEntry::new("📡", "IP Addresses", &addresses)
+        )?;
+
+        let cdn_waf = BannerEntry::new("🛡", "CDN/WAF", self.cdn_waf.as_deref().unwrap_or(&unknown));
+        writeln!(&mut writer, "{cdn_waf}")?;
+
+        writeln!(
+            &mut writer,
+            "{}",
+            themed("──────────────────────────────────────────────────")
+        )?;
+
+        Ok(())
+    }
+}
+
+/// names of cookies set by the target
+fn cookie_names(headers: &HeaderMap) -> Vec<String> {
+    headers
+        .get_all(SET_COOKIE)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .filter_map(|cookie| cookie.split('=').next())
+        .map(|name| name.trim().to_lowercase())
+        .collect()
+}
+
+/// lowercased value of the given header, or an empty string when it's missing
+fn header_lowercase(headers: &HeaderMap, name: &str) -> String {
+    headers
+        .get(name)
+        .and_then(|value| value.to_str().ok())
+        .unwrap_or_default()
+        .to_lowercase()
+}
+
+/// technologies given away by well-known headers and session cookies
+pub(super) fn detect_technologies(headers: &HeaderMap) -> Vec<String> {
+    let mut technologies = BTreeSet::new();
+
+    for name in ["x-powered-by", "x-generator"] {
+        if let Some(value) = headers.get(name).and_then(|value| value.to_str().ok()) {
+            technologies.insert(value.trim().to_string());
+        }
+    }
+
+    if let Some(version) = headers
+        .get("x-aspnet-version")
+        .and_then(|value| value.to_str().ok())
+    {
+        technologies.insert(format!("ASP.NET {}", version.trim()));
+    }
+
+    if headers.contains_key("x-drupal-cache") || headers.contains_key("x-drupal-dynamic-cache") {
+        technologies.insert(String::from("Drupal"));
+    }
+
+    for cookie in cookie_names(headers) {
+        for (prefix, technology) in COOKIE_SIGNATURES {
+            if cookie.starts_with(prefix) {
+                technologies.insert(technology.to_string());
+            }
+        }
+    }
+
+    technologies.into_iter().collect()
+}
+
+/// best guess at a CDN or WAF, based on the headers and cookies they're known to add
+pub(super) fn guess_cdn_waf(headers: &HeaderMap) -> Option<&'static str> {
+    let server = header_lowercase(headers, SERVER.as_str());
+    let via = header_lowercase(headers, VIA.as_str());
+    let cookies = cookie_names(headers);
+
+    let has_cookie = |prefix: &str| cookies.iter().any(|name| name.starts_with(prefix));
+
+    if headers.contains_key("cf-ray") || server.contains("cloudflare") {
+        Some("Cloudflare")
+    } else if headers.contains_key("x-amz-cf-id") || via.contains("cloudfront") {
+        Some("Amazon CloudFront")
+    } else if server.contains("akamaighost") || headers.contains_key("x-akamai-transformed") {
+        Some("Akamai")
+    } else if headers.contains_key("x-sucuri-id") || server.contains("sucuri") {
+        Some("Sucuri")
+    } else if headers.contains_key("x-fastly-request-id")
+        || header_lowercase(headers, "x-served-by").starts_with("cache-")
+    {
+        Some("Fastly")
+    } else if headers.contains_key("x-iinfo")
+        || has_cookie("incap_ses")
+        || has_cookie("visid_incap")
+    {
+        Some("Imperva Incapsula")
+    } else if has_cookie("bigipserver") || server.contains("big-ip") {
+        Some("F5 BIG-IP")
+    } else if headers.contains_key("x-azure-ref") {
+        Some("Azure Front Door")
+    } else if server.contains("awselb") {
+        Some("AWS Elastic Load Balancer")
+    } else {
+        None
+    }
+}
+
+/// addresses the url's host resolves to
+async fn resolve_addresses(url: &Url) -> Vec<IpAddr> {
+    let (Some(host), Some(port)) = (url.host_str(), url.port_or_known_default()) else {
+        return Vec::new();
+    };
+
+    // ipv6 hosts are bracketed in urls, but not when being resolved
+    let host = host.trim_start_matches('[').trim_end_matches(']');
+
+    let addresses: BTreeSet<_> = match tokio::net::lookup_host((host, port)).await {
+        Ok(resolved) => resolved.map(|address| address.ip()).collect(),
+        Err(e) => {
+            log::warn!("Could not resolve {}: {}", host, e);
+            BTreeSet::new()
+        }
+    };
+
+    addresses.into_iter().collect()
+}
+
+/// common name of the certificate presented by an https url
+///
+/// the scan's client doesn't retain tls details, so a dedicated client is used. Certificate
+/// errors are ignored, since the goal is to see the certificate, not to trust it
+#[cfg(feature = "native-tls")]
+async fn certificate_common_name(url: &Url, config: &Configuration) -> Option<String> {
+    if url.scheme() != "https" {
+        return None;
+    }
+
+    let mut builder = config
+        .tls_backend
+        .configure(Client::builder())
+        .ok()?
+        .timeout(Duration::new(config.timeout, 0))
+        .user_agent(&config.user_agent)
+        .danger_accept_invalid_certs(true)
+        .redirect(Policy::none())
+        .tls_info(true);
+
+    if !config.proxy.is_empty() {
+        builder = builder.proxy(Proxy::all(&config.proxy).ok()?);
+    }
+
+    let response = builder.build().ok()?.get(url.clone()).send().await.ok()?;
+
+    let der = response.extensions().get::<TlsInfo>()?.peer_certificate()?;
+
+    common_name_from_der(der)
+}
+
+/// certificates are read with openssl, which only comes with the native-tls feature; without
+/// it, the common name is left out
+#[cfg(not(feature = "native-tls"))]
+async fn certificate_common_name(_url: &Url, _config: &Configuration) -> Option<String> {
+    None
+}
+
+/// pull the subject's common name out of a der-encoded certificate
+#[cfg(feature = "native-tls")]
+pub(super) fn common_name_from_der(der: &[u8]) -> Option<String> {
+    let certificate = X509::from_der(der).ok()?;
+
+    let common_name = certificate
+        .subject_name()
+        .entries_by_nid(Nid::COMMONNAME)
+        .next()?
+        .data()
+        .as_utf8()
+        .ok()?
+        .to_string();
+
+    Some(common_name)
+}
@@ -3,6 +3,7 @@ use super::*;
 use crate::{config::Configuration, event_handlers::Handles, scan_manager::FeroxScans};
 use httpmock::Method::GET;
 use httpmock::MockServer;
+use reqwest::header::{HeaderMap, HeaderValue, SET_COOKIE};
 use std::{io::stderr, sync::Arc, time::Duration};
 
 #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
@@ -172,3 +173,136 @@ async fn banner_needs_update_returns_unknown_on_json_without_correct_tag() {
     assert_eq!(mock.hits(), 1);
     assert!(matches!(banner.update_status, UpdateStatus::Unknown));
 }
+
+#[test]
+/// technologies are picked up from headers and session cookies
+fn recon_detects_technologies_from_headers_and_cookies() {
+    let mut headers = HeaderMap::new();
+    headers.insert("x-powered-by", HeaderValue::from_static("PHP/8.1.2"));
+    headers.insert("x-aspnet-version", HeaderValue::from_static("4.0.30319"));
+    headers.append(
+        SET_COOKIE,
+        HeaderValue::from_static("PHPSESSID=abc123; path=/"),
+    );
+    headers.append(
+        SET_COOKIE,
+        HeaderValue::from_static("wordpress_logged_in_1=x; path=/"),
+    );
+
+    assert_eq!(
+        super::recon::detect_technologies(&headers),
+        vec!["ASP.NET 4.0.30319", "PHP", "PHP/8.1.2", "WordPress"]
+    );
+}
+
+#[test]
+/// cdn/waf guesses are based on well-known headers and cookies
+fn recon_guesses_cdn_waf() {
+    let mut cloudflare = HeaderMap::new();
+    cloudflare.insert("cf-ray", HeaderValue::from_static("7d0c5b3b9a1c2e3f-IAD"));
+    assert_eq!(super::recon::guess_cdn_waf(&cloudflare), Some("Cloudflare"));
+
+    let mut big_ip = HeaderMap::new();
+    big_ip.insert(
+        SET_COOKIE,
+        HeaderValue::from_static("BIGipServerpool=1677787402.36895.0000"),
+    );
+    assert_eq!(super::recon::guess_cdn_waf(&big_ip), Some("F5 BIG-IP"));
+
+    assert_eq!(super::recon::guess_cdn_waf(&HeaderMap::new()), None);
+}
+
+#[test]
+#[cfg(feature = "native-tls")]
+/// the common name is pulled from a certificate's subject
+fn recon_reads_certificate_common_name() {
+    use openssl::{
+        ec::{EcGroup, EcKey},
+        hash::MessageDigest,
+        nid::Nid,
+        pkey::PKey,
+        x509::{X509NameBuilder, X509},
+    };
+
+    let group = EcGroup::from_curve_name(Nid::X9_62_PRIME256V1).unwrap();
+    let key = PKey::from_ec_key(EcKey::generate(&group).unwrap()).unwrap();
+
+    let mut name = X509NameBuilder::new().unwrap();
+    name.append_entry_by_nid(Nid::COMMONNAME, "example.com")
+        .unwrap();
+    let name = name.build();
+
+    let mut builder = X509::builder().unwrap();
+    builder.set_subject_name(&name).unwrap();
+    builder.set_issuer_name(&name).unwrap();
+    builder.set_pubkey(&key).unwrap();
+    builder.sign(&key, MessageDigest::sha256()).unwrap();
+
+    let der = builder.build().to_der().unwrap();
+
+    assert_eq!(
+        super::recon::common_name_from_der(&der),
+        Some(String::from("example.com"))
+    );
+    assert_eq!(super::recon::common_name_from_der(b"not a cert"), None);
+}
+
+#[test]
+/// the recon section prints every field, falling back to unknown
+fn recon_prints_all_fields() {
+    let recon = TargetRecon {
+        target: String::from("https://localhost"),
+        server: Some(String::from("nginx")),
+        technologies: vec![String::from("PHP")],
+        certificate_cn: Some(String::from("localhost")),
+        addresses: vec!["127.0.0.1".parse().unwrap()],
+        cdn_waf: None,
+    };
+
+    let mut output = Vec::new();
+    recon.print_to(&mut output).unwrap();
+
+    let output = console::strip_ansi_codes(&String::from_utf8(output).unwrap()).to_string();
+
+    assert!(output.contains("Target Intelligence: https://localhost"));
+    assert!(output.contains("nginx"));
+    assert!(output.contains("PHP"));
+    assert!(output.contains("Certificate CN"));
+    assert!(output.contains("127.0.0.1"));
+    assert!(output.contains("unknown"));
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+/// the json banner holds the same entries as the pretty one, minus emoji and styling
+async fn banner_print_json_to_matches_print_to() {
+    let config = Arc::new(Configuration::new().unwrap());
+    let banner = Banner::new(&[String::from("http://localhost")], &config);
+
+    let mut pretty = Vec::new();
+    banner.print_to(&mut pretty, config.clone()).unwrap();
+    let pretty = console::strip_ansi_codes(&String::from_utf8(pretty).unwrap()).to_string();
+
+    let mut json = Vec::new();
+    banner.print_json_to(&mut json, config).unwrap();
+    let json = String::from_utf8(json).unwrap();
+
+    assert_eq!(json.lines().count(), 1);
+    assert!(!json.contains('\u{1b}'));
+
+    let parsed: serde_json::Value = serde_json::from_str(&json).unwrap();
+    assert_eq!(parsed["type"], "banner");
+    assert_eq!(parsed["version"], crate::VERSION);
+
+    let entries = parsed["entries"].as_array().unwrap();
+    assert_eq!(entries[0]["title"], "Target Url");
+    assert_eq!(entries[0]["value"], "http://localhost");
+
+    for entry in entries {
+        let line = format!(
+            "{:<22}│ {}",
+            entry["title"].as_str().unwrap(),
+            entry["value"].as_str().unwrap()
+        );
+        assert!(pretty.contains(&line), "{line}");
+    }
+}
@@ -0,0 +1,247 @@
+use std::{
+    fs::OpenOptions,
+    io::Write,
+    path::Path,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    config::Configuration,
+    statistics::Stats,
+    updater::sha256_hex,
+    utils::{csv_field, fmt_err},
+};
+
+/// configuration fields that change from one run to the next without changing what was scanned,
+/// left out of `config_hash`
+const VOLATILE_FIELDS: [&str; 10] = [
+    "type",
+    "run_id",
+    "target_url",
+    "output",
+    "output_upload",
+    "debug_log",
+    "resume_from",
+    "campaign",
+    "time_limit",
+    "meta",
+];
+
+/// columns of a csv campaign ledger, in the order they're written
+const CSV_HEADER: &str = "timestamp,run_id,targets,duration,requests,findings,errors,config_hash";
+
+/// one run's summary, as appended to the --campaign ledger
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct CampaignEntry {
+    /// unix timestamp of when the run finished
+    pub timestamp: u64,
+
+    /// id of the run
+    pub run_id: String,
+
+    /// the run's initial targets
+    pub targets: Vec<String>,
+
+    /// seconds the run took
+    pub duration: f64,
+
+    /// requests sent during the run
+    pub requests: usize,
+
+    /// resources discovered during the run
+    pub findings: usize,
+
+    /// failed requests during the run
+    pub errors: usize,
+
+    /// sha256 of the run's configuration, minus `VOLATILE_FIELDS`; runs with the same hash were
+    /// made with the same settings
+    pub config_hash: String,
+}
+
+/// implementation of CampaignEntry
+impl CampaignEntry {
+    /// summarize a finished run
+    pub fn new(config: &Configuration, stats: &Stats) -> Result<Self> {
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|elapsed| elapsed.as_secs())
+            .unwrap_or_default();
+
+        let mut targets = stats.targets();
+
+        if targets.is_empty() && !config.target_url.is_empty() {
+            targets.push(config.target_url.clone());
+        }
+
+        Ok(Self {
+            timestamp,
+            run_id: config.run_id.clone(),
+            targets,
+            duration: stats.total_runtime(),
+            requests: stats.requests(),
+            findings: stats.resources_discovered(),
+            errors: stats.errors(),
+            config_hash: config_hash(config)?,
+        })
+    }
+
+    /// a single csv row, targets are joined by spaces
+    fn as_csv(&self) -> String {
+        format!(
+            "{},{},{},{:.3},{},{},{},{}\n",
+            self.timestamp,
+            csv_field(&self.run_id),
+            csv_field(&self.targets.join(" ")),
+            self.duration,
+            self.requests,
+            self.findings,
+            self.errors,
+            self.config_hash
+        )
+    }
+
+    /// a single line of json
+    fn as_json(&self) -> Result<String> {
+        let mut json = serde_json::to_string(self)?;
+        json.push('\n');
+        Ok(json)
+    }
+}
+
+/// sha256 of the configuration, with the fields that differ between otherwise identical runs
+/// left out
+fn config_hash(config: &Configuration) -> Result<String> {
+    let mut value = serde_json::to_value(config)?;
+
+    if let Some(fields) = value.as_object_mut() {
+        for field in VOLATILE_FIELDS {
+            fields.remove(field);
+        }
+    }
+
+    // serde_json's map is ordered by key, so equal configurations always serialize the same way
+    Ok(sha256_hex(serde_json::to_string(&value)?.as_bytes()))
+}
+
+/// append a summary of the finished run to --campaign
+///
+/// the ledger is csv when its name ends in .csv, and newline delimited json otherwise; a new
+/// csv ledger gets a header row first
+pub fn record_run(config: &Configuration, stats: &Stats) -> Result<()> {
+    log::trace!("enter: record_run({})", config.campaign);
+
+    if config.campaign.is_empty() {
+        log::trace!("exit: record_run");
+        return Ok(());
+    }
+
+    let entry = CampaignEntry::new(config, stats)?;
+
+    let is_csv = Path::new(&config.campaign)
+        .extension()
+        .is_some_and(|extension| extension.eq_ignore_ascii_case("csv"));
+
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&config.campaign)
+        .with_context(|| fmt_err(&format!("Could not open {}", config.campaign)))?;
+
+    let needs_header = is_csv && file.metadata().map(|meta| meta.len() == 0)?;
+
+    let mut contents = String::new();
+
+    if needs_header {
+        contents.push_str(CSV_HEADER);
+        contents.push('\n');
+    }
+
+    if is_csv {
+        contents.push_str(&entry.as_csv());
+    } else {
+        contents.push_str(&entry.as_json()?);
+    }
+
+    file.write_all(contents.as_bytes())
+        .with_context(|| fmt_err(&format!("Could not write to {}", config.campaign)))?;
+
+    log::info!("recorded run {} in {}", entry.run_id, config.campaign);
+
+    log::trace!("exit: record_run");
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::NamedTempFile;
+
+    /// configuration writing to the given campaign ledger
+    fn config(campaign: &str, run_id: &str) -> Configuration {
+        Configuration {
+            campaign: campaign.to_string(),
+            run_id: run_id.to_string(),
+            target_url: String::from("http://localhost"),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    /// runs with the same settings share a hash, no matter their id or target
+    fn config_hash_ignores_volatile_fields() {
+        let first = config("", "abc");
+        let mut second = config("", "def");
+        second.target_url = String::from("http://other.local");
+
+        assert_eq!(config_hash(&first).unwrap(), config_hash(&second).unwrap());
+
+        second.threads = 3;
+        assert_ne!(config_hash(&first).unwrap(), config_hash(&second).unwrap());
+    }
+
+    #[test]
+    /// csv ledgers get a single header row, followed by a row per run
+    fn record_run_appends_csv_rows() {
+        let file = NamedTempFile::with_suffix(".csv").unwrap();
+        let path = file.path().to_str().unwrap();
+        let stats = Stats::new(false);
+
+        record_run(&config(path, "abc"), &stats).unwrap();
+        record_run(&config(path, "def"), &stats).unwrap();
+
+        let contents = fs::read_to_string(path).unwrap();
+        let lines = contents.lines().collect::<Vec<_>>();
+
+        assert_eq!(lines.len(), 3);
+        assert_eq!(lines[0], CSV_HEADER);
+        assert!(lines[1].contains(",abc,http://localhost,"));
+        assert!(lines[2].contains(",def,http://localhost,"));
+    }
+
+    #[test]
+    /// anything other than csv is newline delimited json
+    fn record_run_appends_json_lines() {
+        let file = NamedTempFile::new().unwrap();
+        let path = file.path().to_str().unwrap();
+        let stats = Stats::new(false);
+
+        record_run(&config(path, "abc"), &stats).unwrap();
+        record_run(&config(path, "def"), &stats).unwrap();
+
+        let contents = fs::read_to_string(path).unwrap();
+        let entries = contents
+            .lines()
+            .map(|line| serde_json::from_str::<CampaignEntry>(line).unwrap())
+            .collect::<Vec<_>>();
+
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].run_id, "abc");
+        assert_eq!(entries[1].targets, vec![String::from("http://localhost")]);
+        assert_eq!(entries[0].config_hash, entries[1].config_hash);
+    }
+}
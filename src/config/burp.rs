@@ -0,0 +1,442 @@
+//! seeding a scan from a Burp Suite xml export (Proxy history or Target site map, "Save
+//! selected items"), see --import-burp
+use std::collections::BTreeSet;
+
+use anyhow::{bail, Context, Result};
+use base64::{engine::general_purpose::STANDARD, Engine};
+use reqwest::StatusCode;
+
+use super::{
+    utils::{combine_cookies, split_header, split_raw_request, unfold_header_lines, user_agent},
+    Configuration,
+};
+use crate::{
+    utils::parse_url_with_raw_path,
+    xml::{attribute, decode_entities},
+};
+
+/// request headers that only make sense for the request they were captured from, and so aren't
+/// carried over to every request the scan makes
+const SKIPPED_HEADERS: [&str; 12] = [
+    "host",
+    "content-length",
+    "content-type",
+    "transfer-encoding",
+    "connection",
+    "keep-alive",
+    "proxy-connection",
+    "te",
+    "upgrade",
+    "if-none-match",
+    "if-modified-since",
+    "cookie", // merged across every item instead, see `import_burp`
+];
+
+/// a single <item> from a burp export
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub(super) struct BurpItem {
+    /// full url of the request
+    pub(super) url: String,
+
+    /// status code of the response, if burp saw one
+    pub(super) status: Option<u16>,
+
+    /// the raw request, decoded from base64 when needed
+    pub(super) request: Vec<u8>,
+}
+
+/// read the export at --import-burp and fold what it knows into the configuration
+///
+/// - targets: when --url isn't used, each scheme://host:port found becomes a target; otherwise
+///   only items belonging to the --url target are used
+/// - known-good paths: urls of items whose response wasn't a 404/410 are seeded like --known-urls
+/// - headers: the first value seen for each header is used, unless it was given on the cli
+/// - cookies: merged across every item, later items win; cookies given on the cli win over all
+pub fn import_burp(config: &mut Configuration) -> Result<()> {
+    log::trace!("enter: import_burp({})", config.import_burp);
+
+    let contents = std::fs::read_to_string(&config.import_burp)
+        .with_context(|| format!("Could not read {}", config.import_burp))?;
+
+    let mut items = parse_burp_items(&contents)?;
+
+    if !config.target_url.is_empty() {
+        // targets given on the cli limit what's taken from the export
+        let origin = parse_url_with_raw_path(&config.target_url)
+            .map(|url| url.origin().ascii_serialization())
+            .unwrap_or_default();
+
+        items.retain(|item| origin_of(&item.url).as_deref() == Some(origin.as_str()));
+    } else {
+        let origins: BTreeSet<_> = items
+            .iter()
+            .filter_map(|item| origin_of(&item.url))
+            .collect();
+
+        config.imported_targets = origins.into_iter().collect();
+
+        if config.imported_targets.len() == 1 {
+            config.target_url = config.imported_targets[0].clone();
+        }
+    }
+
+    if items.is_empty() {
+        bail!("Did not find any usable items in {}", config.import_burp);
+    }
+
+    for item in &items {
+        let known_good = item.status.is_some_and(|status| {
+            status != StatusCode::NOT_FOUND.as_u16() && status != StatusCode::GONE.as_u16()
+        });
+
+        if known_good && !config.imported_urls.contains(&item.url) {
+            config.imported_urls.push(item.url.clone());
+        }
+    }
+
+    // headers given on the cli, as opposed to those found in the export
+    let cli_headers: Vec<String> = config.headers.keys().map(|k| k.to_lowercase()).collect();
+
+    let mut cookies = String::new();
+
+    for item in &items {
+        let (head, _) = split_raw_request(&item.request);
+        let head = String::from_utf8_lossy(head);
+
+        // the first line is the request line
+        for line in unfold_header_lines(&head).iter().skip(1) {
+            let Ok((name, value)) = split_header(line) else {
+                continue;
+            };
+
+            let lowered = name.to_lowercase();
+
+            if lowered == "cookie" {
+                cookies = combine_cookies(&cookies, &value);
+                continue;
+            }
+
+            if lowered == "user-agent" {
+                if config.user_agent == user_agent() {
+                    config.user_agent = value;
+                }
+                continue;
+            }
+
+            if SKIPPED_HEADERS.contains(&lowered.as_str())
+                || config
+                    .headers
+                    .keys()
+                    .any(|key| key.to_lowercase() == lowered)
+            {
+                continue;
+            }
+
+            config.headers.insert(name, value);
+        }
+    }
+
+    if !cookies.is_empty() {
+        let existing = config
+            .headers
+            .keys()
+            .find(|key| key.to_lowercase() == "cookie")
+            .cloned();
+
+        match existing {
+            Some(key) if cli_headers.contains(&String::from("cookie")) => {
+                let current = config.headers.get_mut(&key).unwrap();
+                *current = combine_cookies(&cookies, current);
+            }
+            Some(key) => {
+                let current = config.headers.get_mut(&key).unwrap();
+                *current = combine_cookies(current, &cookies);
+            }
+            None => {
+                config.headers.insert(String::from("Cookie"), cookies);
+            }
+        }
+    }
+
+    log::info!(
+        "imported {} item(s) from {}: {} target(s), {} known url(s)",
+        items.len(),
+        config.import_burp,
+        config.imported_targets.len().max(1),
+        config.imported_urls.len()
+    );
+
+    log::trace!("exit: import_burp");
+    Ok(())
+}
+
+/// pull each <item> out of a burp export; items without a url are skipped
+pub(super) fn parse_burp_items(xml: &str) -> Result<Vec<BurpItem>> {
+    if !xml.contains("<items") {
+        bail!("Expected a Burp Suite xml export, i.e. <items burpVersion=...>");
+    }
+
+    let mut items = Vec::new();
+    let mut rest = xml;
+
+    while let Some(start) = rest.find("<item>") {
+        let Some(end) = rest[start..].find("</item>") else {
+            bail!("Unterminated <item> in Burp Suite export");
+        };
+
+        let item = &rest[start + "<item>".len()..start + end];
+        rest = &rest[start + end + "</item>".len()..];
+
+        let Some((_, url)) = element(item, "url") else {
+            continue;
+        };
+
+        let status = element(item, "status").and_then(|(_, status)| status.trim().parse().ok());
+
+        let request = match element(item, "request") {
+            Some((attributes, request))
+                if attribute(&attributes, "base64").as_deref() == Some("true") =>
+            {
+                STANDARD
+                    .decode(request.trim())
+                    .context("Could not decode base64 request in Burp Suite export")?
+            }
+            Some((_, request)) => request.into_bytes(),
+            None => Vec::new(),
+        };
+
+        items.push(BurpItem {
+            url: url.trim().to_string(),
+            status,
+            request,
+        });
+    }
+
+    Ok(items)
+}
+
+/// attributes and text of the first <name> element within the given xml, with CDATA sections
+/// unwrapped and entities decoded
+fn element(xml: &str, name: &str) -> Option<(String, String)> {
+    let open = format!("<{name}");
+    let close = format!("</{name}>");
+
+    let mut search = 0;
+
+    // skip over elements whose name only starts with `name`, i.e. <requestx>
+    let start = loop {
+        let found = search + xml[search..].find(&open)?;
+        let after = xml[found + open.len()..].chars().next()?;
+
+        if after == '>' || after.is_whitespace() || after == '/' {
+            break found;
+        }
+
+        search = found + open.len();
+    };
+
+    let tag_end = start + xml[start..].find('>')?;
+    let attributes = xml[start + open.len()..tag_end].trim();
+
+    if attributes.ends_with('/') {
+        // self-closing, i.e. <comment/>
+        return Some((
+            attributes.trim_end_matches('/').trim().to_string(),
+            String::new(),
+        ));
+    }
+
+    let content_end = tag_end + xml[tag_end..].find(&close)?;
+
+    Some((attributes.to_string(), text(&xml[tag_end + 1..content_end])))
+}
+
+/// the text of an element's content; CDATA sections are taken as-is (burp splits content that
+/// contains `]]>` into several of them), everything else has its entities decoded
+fn text(content: &str) -> String {
+    let mut text = String::new();
+    let mut rest = content;
+
+    while let Some(start) = rest.find("<![CDATA[") {
+        text.push_str(&decode_entities(&rest[..start]));
+
+        let data = &rest[start + "<![CDATA[".len()..];
+
+        match data.find("]]>") {
+            Some(end) => {
+                text.push_str(&data[..end]);
+                rest = &data[end + "]]>".len()..];
+            }
+            None => {
+                text.push_str(data);
+                rest = "";
+            }
+        }
+    }
+
+    text.push_str(&decode_entities(rest));
+    text
+}
+
+/// scheme://host:port of the given url, if it can be parsed
+fn origin_of(url: &str) -> Option<String> {
+    parse_url_with_raw_path(url)
+        .ok()
+        .map(|url| url.origin().ascii_serialization())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+    use tempfile::NamedTempFile;
+
+    /// an export with two hosts: a login page on localhost (with cookies and a stale cache
+    /// validator), a 404 on localhost, and an api endpoint on another host
+    const EXPORT: &str = r#"<?xml version="1.0"?>
+<!DOCTYPE items [
+<!ELEMENT items (item*)>
+]>
+<items burpVersion="2024.3.1" exportTime="Mon Apr 01 12:00:00 UTC 2024">
+  <item>
+    <time>Mon Apr 01 11:59:00 UTC 2024</time>
+    <url><![CDATA[http://localhost/admin/login?next=%2F]]></url>
+    <host ip="127.0.0.1">localhost</host>
+    <port>80</port>
+    <protocol>http</protocol>
+    <method><![CDATA[GET]]></method>
+    <path><![CDATA[/admin/login?next=%2F]]></path>
+    <extension>null</extension>
+    <request base64="true"><![CDATA[R0VUIC9hZG1pbi9sb2dpbj9uZXh0PSUyRiBIVFRQLzEuMQ0KSG9zdDogbG9jYWxob3N0DQpVc2VyLUFnZW50OiBNb3ppbGxhLzUuMA0KQXV0aG9yaXphdGlvbjogQmVhcmVyIGFiYw0KQ29va2llOiBzZXNzaW9uPW9uZTsgdGhlbWU9ZGFyaw0KSWYtTm9uZS1NYXRjaDogIngiDQoNCg==]]></request>
+    <status>200</status>
+    <responselength>10</responselength>
+    <mimetype>HTML</mimetype>
+    <response base64="true"><![CDATA[SFRUUC8xLjEgMjAwIE9LDQoNCg==]]></response>
+    <comment/>
+  </item>
+  <item>
+    <url><![CDATA[http://localhost/missing]]></url>
+    <method><![CDATA[GET]]></method>
+    <request base64="false"><![CDATA[GET /missing HTTP/1.1
+Host: localhost
+Cookie: session=two
+
+]]></request>
+    <status>404</status>
+  </item>
+  <item>
+    <url>https://api.local:8443/v1/users?a=1&amp;b=2</url>
+    <request base64="false"><![CDATA[GET /v1/users HTTP/1.1
+Host: api.local:8443
+X-Api-Key: secret
+
+]]></request>
+    <status>200</status>
+  </item>
+</items>
+"#;
+
+    /// write the given export to disk and point a fresh configuration at it
+    fn config_for(export: &str, target_url: &str) -> (NamedTempFile, Configuration) {
+        let mut file = NamedTempFile::new().unwrap();
+        write!(file, "{export}").unwrap();
+
+        let config = Configuration {
+            import_burp: file.path().to_string_lossy().to_string(),
+            target_url: target_url.to_string(),
+            ..Default::default()
+        };
+
+        (file, config)
+    }
+
+    #[test]
+    /// urls, statuses, and requests (plain and base64) are pulled out of each item
+    fn parse_burp_items_reads_each_item() {
+        let items = parse_burp_items(EXPORT).unwrap();
+
+        assert_eq!(items.len(), 3);
+        assert_eq!(items[0].url, "http://localhost/admin/login?next=%2F");
+        assert_eq!(items[0].status, Some(200));
+        assert!(items[0]
+            .request
+            .starts_with(b"GET /admin/login?next=%2F HTTP/1.1\r\n"));
+        assert_eq!(items[1].status, Some(404));
+        assert!(items[1].request.starts_with(b"GET /missing HTTP/1.1\n"));
+        assert_eq!(items[2].url, "https://api.local:8443/v1/users?a=1&b=2");
+
+        assert!(parse_burp_items("<html></html>").is_err());
+        assert!(parse_burp_items("<items><item><url>x").is_err());
+
+        // single quotes are just as valid as double quotes
+        let items = parse_burp_items(
+            "<items><item><url>http://localhost/</url><request base64='true'>R0VUIC8=</request></item></items>",
+        )
+        .unwrap();
+        assert_eq!(items[0].request, b"GET /");
+    }
+
+    #[test]
+    /// without --url, every host becomes a target, and headers and cookies from all of them
+    /// are used
+    fn import_burp_seeds_targets_urls_and_headers() {
+        let (_file, mut config) = config_for(EXPORT, "");
+
+        import_burp(&mut config).unwrap();
+
+        assert_eq!(
+            config.imported_targets,
+            vec![
+                String::from("http://localhost"),
+                String::from("https://api.local:8443")
+            ]
+        );
+        assert!(config.target_url.is_empty());
+        assert_eq!(
+            config.imported_urls,
+            vec![
+                String::from("http://localhost/admin/login?next=%2F"),
+                String::from("https://api.local:8443/v1/users?a=1&b=2")
+            ]
+        );
+        assert_eq!(config.user_agent, "Mozilla/5.0");
+        assert_eq!(config.headers.get("Authorization").unwrap(), "Bearer abc");
+        assert_eq!(config.headers.get("X-Api-Key").unwrap(), "secret");
+        assert!(!config.headers.contains_key("Host"));
+        assert!(!config.headers.contains_key("If-None-Match"));
+
+        let cookie = config.headers.get("Cookie").unwrap();
+        assert!(cookie.contains("session=two"));
+        assert!(cookie.contains("theme=dark"));
+    }
+
+    #[test]
+    /// with --url, only that target's items are used, and cli headers and cookies win
+    fn import_burp_limits_items_to_target_url() {
+        let (_file, mut config) = config_for(EXPORT, "http://localhost/");
+        config
+            .headers
+            .insert(String::from("Authorization"), String::from("Bearer cli"));
+        config
+            .headers
+            .insert(String::from("Cookie"), String::from("session=cli"));
+
+        import_burp(&mut config).unwrap();
+
+        assert!(config.imported_targets.is_empty());
+        assert_eq!(config.target_url, "http://localhost/");
+        assert_eq!(
+            config.imported_urls,
+            vec![String::from("http://localhost/admin/login?next=%2F")]
+        );
+        assert_eq!(config.headers.get("Authorization").unwrap(), "Bearer cli");
+        assert!(!config.headers.contains_key("X-Api-Key"));
+
+        let cookie = config.headers.get("Cookie").unwrap();
+        assert!(cookie.contains("session=cli"));
+        assert!(cookie.contains("theme=dark"));
+
+        let (_file, mut config) = config_for(EXPORT, "http://elsewhere.local");
+        assert!(import_burp(&mut config).is_err());
+    }
+}
@@ -0,0 +1,119 @@
+//! conditional requests against a previous run's findings, see --changed-since
+//!
+//! the ETag and Last-Modified headers of each finding in a previous run's --json output are
+//! sent back as If-None-Match and If-Modified-Since, so content that hasn't changed since that
+//! run comes back as a cheap 304
+use std::collections::HashMap;
+
+use anyhow::{Context, Result};
+use serde_json::Value;
+
+use super::Configuration;
+
+/// validators of a single url from a previous run
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Validators {
+    /// ETag response header, sent back as If-None-Match
+    pub etag: Option<String>,
+
+    /// Last-Modified response header, sent back as If-Modified-Since
+    pub last_modified: Option<String>,
+}
+
+/// read the previous run's NDJSON output at --changed-since and store the validators of each
+/// non-wildcard finding that had any
+///
+/// lines that aren't response records (or aren't json at all) are skipped, so the output of
+/// any version that wrote headers can be used
+pub fn load_validators(config: &mut Configuration) -> Result<()> {
+    log::trace!("enter: load_validators({})", config.changed_since);
+
+    let contents = std::fs::read_to_string(&config.changed_since)
+        .with_context(|| format!("Could not read {}", config.changed_since))?;
+
+    config.validators = parse_validators(&contents);
+
+    log::info!(
+        "loaded validators for {} url(s) from {}",
+        config.validators.len(),
+        config.changed_since
+    );
+
+    log::trace!("exit: load_validators");
+    Ok(())
+}
+
+/// validators for each url found in the given NDJSON, keyed by url
+fn parse_validators(contents: &str) -> HashMap<String, Validators> {
+    let mut validators = HashMap::new();
+
+    for line in contents.lines() {
+        let Ok(record) = serde_json::from_str::<Value>(line) else {
+            continue;
+        };
+
+        if record["type"] != "response" || record["wildcard"] == true {
+            continue;
+        }
+
+        let Some(url) = record["url"].as_str() else {
+            continue;
+        };
+
+        // header names are serialized in lowercase
+        let header = |name: &str| {
+            record["headers"][name]
+                .as_str()
+                .filter(|value| !value.is_empty())
+                .map(String::from)
+        };
+
+        let found = Validators {
+            etag: header("etag"),
+            last_modified: header("last-modified"),
+        };
+
+        if found != Validators::default() {
+            // later records win, same as the server would've answered most recently
+            validators.insert(url.to_string(), found);
+        }
+    }
+
+    validators
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    /// etags and last-modified dates are read from response records; everything else is skipped
+    fn parse_validators_reads_response_records() {
+        let contents = r#"{"type":"metadata","schema_version":1}
+{"type":"configuration","target_url":"http://localhost"}
+{"type":"response","url":"http://localhost/a","wildcard":false,"headers":{"etag":"\"abc\"","last-modified":"Wed, 21 Oct 2015 07:28:00 GMT"}}
+{"type":"response","url":"http://localhost/b","wildcard":false,"headers":{"etag":"W/\"def\""}}
+{"type":"response","url":"http://localhost/c","wildcard":false,"headers":{"server":"nginx"}}
+{"type":"response","url":"http://localhost/d","wildcard":true,"headers":{"etag":"\"wild\""}}
+not json at all
+"#;
+
+        let validators = parse_validators(contents);
+
+        assert_eq!(validators.len(), 2);
+        assert_eq!(
+            validators["http://localhost/a"],
+            Validators {
+                etag: Some(String::from("\"abc\"")),
+                last_modified: Some(String::from("Wed, 21 Oct 2015 07:28:00 GMT")),
+            }
+        );
+        assert_eq!(
+            validators["http://localhost/b"],
+            Validators {
+                etag: Some(String::from("W/\"def\"")),
+                last_modified: None,
+            }
+        );
+    }
+}
@@ -1,29 +1,43 @@
+use super::burp::import_burp;
+use super::conditional::{load_validators, Validators};
+use super::crawl::import_crawl;
+use super::nmap::import_nmap;
 use super::utils::{
-    backup_extensions, depth, determine_requester_policy, extract_links, ignored_extensions,
-    methods, parse_request_file, report_and_exit, request_protocol, save_state, serialized_type,
-    split_header, split_query, status_codes, threads, timeout, user_agent, wordlist, OutputLevel,
-    RequesterPolicy,
+    backup_extensions, bar_refresh_hz, bypass_headers, collected_word_charset, csv_columns, depth,
+    determine_requester_policy, dns_cache_ttl, export_format, extract_links, extract_priority,
+    flush_every, ignored_extensions, max_dir_requests, methods, notify_format, notify_rate_limit,
+    notify_template, parse_request_file, parse_status_codes, preflight, report_and_exit,
+    request_protocol, save_state, serialized_type, split_header, split_query, status_codes,
+    threads, timeout, user_agent, word_languages, wordlist, Denier, OutputLevel, RequesterPolicy,
 };
 
 use crate::config::determine_output_level;
 use crate::{
-    client, parser,
-    scan_manager::resume_scan,
+    client::{self, TlsBackend},
+    dns::DNS_CACHE,
+    parser,
+    scan_manager::{confirm_resume, find_state_file, resume_scan},
+    tags::TagRule,
+    template::OutputTemplate,
     traits::FeroxSerialize,
-    utils::{fmt_err, module_colorizer, parse_url_with_raw_path, status_colorizer},
-    DEFAULT_CONFIG_NAME,
+    upload::{self, S3Location},
+    utils::{
+        fmt_err, is_output_template, module_colorizer, render_output_template, status_colorizer,
+    },
+    DEFAULT_CONFIG_NAME, DEFAULT_EXTENSION_SAMPLE, DEFAULT_JSON_CONFIG_NAME, RECENT_STATE_FILE_AGE,
 };
-use anyhow::{anyhow, Context, Result};
+use anyhow::{anyhow, bail, Context, Result};
 use clap::{parser::ValueSource, ArgMatches};
 use regex::Regex;
-use reqwest::{Client, Method, StatusCode, Url};
+use reqwest::{Client, Method, Url};
 use serde::{Deserialize, Serialize};
 use std::{
     collections::HashMap,
-    env::{current_dir, current_exe},
+    env::{current_dir, current_exe, temp_dir},
     fs::read_to_string,
     path::{Path, PathBuf},
 };
+use uuid::Uuid;
 
 /// macro helper to abstract away repetitive configuration updates
 macro_rules! update_config_if_present {
@@ -97,10 +111,18 @@ pub struct Configuration {
     #[serde(default)]
     pub config: String,
 
+    /// Paths to all config files that were loaded, in the order they were loaded
+    #[serde(default)]
+    pub config_files: Vec<String>,
+
     /// Proxy to use for requests (ex: http(s)://host:port, socks5(h)://host:port)
     #[serde(default)]
     pub proxy: String,
 
+    /// Make sure the proxy works, and report its exit ip, before scanning
+    #[serde(default)]
+    pub proxy_check: bool,
+
     /// Replay Proxy to use for requests (ex: http(s)://host:port, socks5(h)://host:port)
     #[serde(default)]
     pub replay_proxy: String,
@@ -133,6 +155,11 @@ pub struct Configuration {
     #[serde(default)]
     pub filter_status: Vec<u16>,
 
+    /// Named groups of status codes, referenced on the command line as `@name`
+    /// (ex: `-s @interesting`)
+    #[serde(default)]
+    pub status_groups: HashMap<String, Vec<u16>>,
+
     /// Instance of [reqwest::Client](https://docs.rs/reqwest/latest/reqwest/struct.Client.html)
     #[serde(skip)]
     pub client: Client,
@@ -141,6 +168,12 @@ pub struct Configuration {
     #[serde(skip)]
     pub replay_client: Option<Client>,
 
+    /// Instance of [reqwest::Client](https://docs.rs/reqwest/latest/reqwest/struct.Client.html)
+    /// that also sends the --interstitial-bypass headers; used for hosts that were found to be
+    /// fronted by an interstitial page
+    #[serde(skip)]
+    pub interstitial_client: Option<Client>,
+
     /// Number of concurrent threads (default: 50)
     #[serde(default = "threads")]
     pub threads: usize,
@@ -149,6 +182,14 @@ pub struct Configuration {
     #[serde(default = "timeout")]
     pub timeout: u64,
 
+    /// Most hostnames kept in the in-process dns cache; 0 leaves resolution to the os alone
+    #[serde(default)]
+    pub dns_cache_size: usize,
+
+    /// Number of seconds a hostname is kept in the dns cache (default: 300)
+    #[serde(default = "dns_cache_ttl")]
+    pub dns_cache_ttl: u64,
+
     /// Level of verbosity, equates to log level
     #[serde(default)]
     pub verbosity: u8,
@@ -181,10 +222,48 @@ pub struct Configuration {
     #[serde(default)]
     pub json: bool,
 
+    /// Write findings to --output as a SARIF 2.1.0 log
+    #[serde(default)]
+    pub sarif: bool,
+
+    /// Write findings to --output as CSV
+    #[serde(default)]
+    pub csv: bool,
+
+    /// Columns written by --csv, in order
+    #[serde(default = "csv_columns")]
+    pub csv_columns: Vec<String>,
+
+    /// Include up to this many bytes of each response's body in NDJSON output; 0 leaves bodies
+    /// out entirely. Bodies never make it into state files, and the values of --headers,
+    /// --cookies, and --query are redacted from them
+    #[serde(default)]
+    pub json_include_body: usize,
+
     /// Output file to write results to (default: stdout)
     #[serde(default)]
     pub output: String,
 
+    /// s3://bucket/key that --output is uploaded to once the scan completes (or is
+    /// interrupted with ctrl+c); when set, `output` is a local staging file
+    #[serde(default)]
+    pub output_upload: String,
+
+    /// directory in which results are split into a file per target, instead of a single
+    /// --output file
+    #[serde(default)]
+    pub output_dir: String,
+
+    /// file the banner's settings are written to as JSON; - writes them to stderr in place of
+    /// the banner
+    #[serde(default)]
+    pub banner_json: String,
+
+    /// template used in place of the normal line for each finding written to the terminal or a
+    /// non-JSON --output file, i.e. `{{status}} {{url}}`
+    #[serde(default)]
+    pub output_format: String,
+
     /// File in which to store debug output, used in conjunction with verbosity to dictate which
     /// logs are written
     #[serde(default)]
@@ -206,10 +285,25 @@ pub struct Configuration {
     #[serde(default)]
     pub insecure: bool,
 
+    /// TLS implementation used by the client
+    #[serde(default)]
+    pub tls_backend: TlsBackend,
+
     /// File extension(s) to search for
     #[serde(default)]
     pub extensions: Vec<String>,
 
+    /// File extension(s) paired with their own wordlist; words from the paired wordlist are only
+    /// requested with that extension, and the extension isn't applied to the main wordlist
+    #[serde(default)]
+    pub extension_wordlists: HashMap<String, String>,
+
+    /// number of requests made with each extension in a directory before the extension is
+    /// dropped for that directory (and those beneath it) if it hasn't turned anything up;
+    /// 0 means every extension is always requested
+    #[serde(default)]
+    pub adaptive_extensions: usize,
+
     /// HTTP requests methods(s) to search for
     #[serde(default = "methods")]
     pub methods: Vec<String>,
@@ -222,10 +316,20 @@ pub struct Configuration {
     #[serde(default)]
     pub headers: HashMap<String, String>,
 
+    /// HTTP headers (typically a cookie) sent to hosts once they're found to be fronted by an
+    /// interstitial page, such as a cookie consent or anti-bot challenge
+    #[serde(default)]
+    pub interstitial_bypass: HashMap<String, String>,
+
     /// URL query parameters
     #[serde(default)]
     pub queries: Vec<(String, String)>,
 
+    /// user-supplied key/value pairs (engagement id, ticket number, operator, etc...) that are
+    /// embedded in the scan's outputs
+    #[serde(default)]
+    pub meta: HashMap<String, String>,
+
     /// Do not scan recursively
     #[serde(default)]
     pub no_recursion: bool,
@@ -234,10 +338,29 @@ pub struct Configuration {
     #[serde(default = "extract_links")]
     pub extract_links: bool,
 
+    /// when links found via extraction are requested: `normal` requests them as they're found,
+    /// alongside the wordlist; `low` holds them until the directory's wordlist is done
+    #[serde(default = "extract_priority")]
+    pub extract_priority: String,
+
     /// Append / to each request
     #[serde(default)]
     pub add_slash: bool,
 
+    /// Decide per-directory whether to append / to each request, based on whether the server
+    /// redirects slashless directory requests
+    #[serde(default)]
+    pub auto_slash: bool,
+
+    /// Treat urls that differ only by case as the same directory when deciding whether or not
+    /// to scan it (i.e. for case-insensitive servers such as IIS)
+    #[serde(default)]
+    pub case_insensitive: bool,
+
+    /// Retry a request once when its response body was cut short or didn't match its Content-Length
+    #[serde(default)]
+    pub retry_truncated: bool,
+
     /// Read url(s) from STDIN
     #[serde(default)]
     pub stdin: bool,
@@ -246,10 +369,21 @@ pub struct Configuration {
     #[serde(default = "depth")]
     pub depth: usize,
 
+    /// Minimum number of findings a directory's parent scan must have produced before recursion
+    /// into the directory is attempted, decided once the parent scan finishes; a threshold of 0
+    /// means no threshold is imposed
+    #[serde(default)]
+    pub recurse_threshold: usize,
+
     /// Number of concurrent scans permitted; a limit of 0 means no limit is imposed
     #[serde(default)]
     pub scan_limit: usize,
 
+    /// when scans are waiting on --scan-limit, start them round-robin across targets instead
+    /// of in the order they were found
+    #[serde(default)]
+    pub interleave_targets: bool,
+
     /// Number of parallel scans permitted; a limit of 0 means no limit is imposed
     #[serde(default)]
     pub parallel: usize,
@@ -258,6 +392,11 @@ pub struct Configuration {
     #[serde(default)]
     pub rate_limit: usize,
 
+    /// Number of requests allowed to be in-flight at once (per directory); a limit of 0 means
+    /// no limit is imposed
+    #[serde(default)]
+    pub inflight_per_dir: usize,
+
     /// Filter out messages of a particular size
     #[serde(default)]
     pub filter_size: Vec<u64>,
@@ -282,6 +421,15 @@ pub struct Configuration {
     #[serde(default)]
     pub resumed: bool,
 
+    /// Unique identifier of this invocation, generated at startup; shown in the banner and
+    /// embedded in output, log, and state files so artifacts from the same run can be matched up
+    #[serde(default)]
+    pub run_id: String,
+
+    /// resume from a recent state file matching the target without prompting
+    #[serde(default)]
+    pub auto_resume: bool,
+
     /// Resume scan from this file
     #[serde(default)]
     pub resume_from: String,
@@ -307,6 +455,10 @@ pub struct Configuration {
     #[serde(with = "serde_regex", default)]
     pub regex_denylist: Vec<Regex>,
 
+    /// File of --dont-scan values that's re-read during the scan; new entries cancel matching scans
+    #[serde(default)]
+    pub dont_scan_file: String,
+
     /// Automatically discover extensions and add them to --extensions (unless they're in --dont-collect)
     #[serde(default)]
     pub collect_extensions: bool,
@@ -326,6 +478,35 @@ pub struct Configuration {
     #[serde(default)]
     pub collect_words: bool,
 
+    /// languages whose stop words are removed from collected words (ISO 639-1 codes)
+    #[serde(default = "word_languages")]
+    pub word_languages: Vec<String>,
+
+    /// transliterate collected cyrillic and greek words to latin, and strip diacritics
+    #[serde(default)]
+    pub transliterate: bool,
+
+    /// collected words shorter than this are discarded; a limit of 0 means no limit is imposed
+    #[serde(default)]
+    pub collected_word_min_len: usize,
+
+    /// collected words longer than this are discarded; a limit of 0 means no limit is imposed
+    #[serde(default)]
+    pub collected_word_max_len: usize,
+
+    /// characters collected words may contain: alpha, alnum, or any
+    #[serde(default = "collected_word_charset")]
+    pub collected_word_charset: String,
+
+    /// maximum number of collected words (highest scoring first) added to each directory's
+    /// scan; a limit of 0 means no limit is imposed
+    #[serde(default)]
+    pub collected_word_limit: usize,
+
+    /// directory to which the words collected from each target are saved at the end of the run
+    #[serde(default)]
+    pub save_collected_words: String,
+
     /// override recursion logic to always attempt recursion, still respects --depth
     #[serde(default)]
     pub force_recursion: bool,
@@ -334,21 +515,254 @@ pub struct Configuration {
     #[serde(skip)]
     pub update_app: bool,
 
+    /// scan a built-in mock site and check the results, instead of scanning a target
+    #[serde(skip)]
+    pub self_test: bool,
+
+    /// path to a locally provided release archive to update from (offline update)
+    #[serde(skip)]
+    pub update_from: String,
+
+    /// previous run's --json output or state file to apply filters to, instead of scanning
+    #[serde(skip)]
+    pub refilter: String,
+
     /// whether to recurse into directory listings or not
     #[serde(default)]
     pub scan_dir_listings: bool,
 
+    /// scan hosts within a target's domain that were found via extraction
+    #[serde(default)]
+    pub scan_discovered_hosts: bool,
+
+    /// detect single-page applications and adjust the scan strategy when one is found
+    #[serde(default)]
+    pub spa_mode: bool,
+
+    /// probe each host once for security headers and TRACE availability
+    #[serde(default)]
+    pub survey_headers: bool,
+
+    /// print a summary of what could be learned about each target beneath the banner
+    #[serde(default)]
+    pub recon_banner: bool,
+
+    /// path to a file of previously discovered urls that are requested before the wordlist
+    #[serde(default)]
+    pub known_urls: String,
+
+    /// OpenAPI / Swagger spec (json file or url) whose documented operations are requested
+    /// before the wordlist; `auto` looks for a spec on each target instead
+    #[serde(default)]
+    pub openapi: String,
+
+    /// look for GraphQL endpoints on each target and report the operations found through
+    /// introspection
+    #[serde(default)]
+    pub graphql: bool,
+
+    /// path to a previous run's --json output; its findings are requested with
+    /// If-None-Match/If-Modified-Since, and 304s are reported as unchanged
+    #[serde(default)]
+    pub changed_since: String,
+
+    /// ETag/Last-Modified values read from --changed-since, keyed by url
+    #[serde(skip)]
+    pub validators: HashMap<String, Validators>,
+
+    /// name of a well-known SecLists wordlist to download (and cache) instead of using --wordlist
+    #[serde(default)]
+    pub fetch_wordlist: String,
+
+    /// web archives (wayback, commoncrawl) whose historical paths for the target are merged into
+    /// the wordlist before scanning
+    #[serde(default)]
+    pub archive_seed: Vec<String>,
+
     /// path to a raw request file generated by burp or similar
     #[serde(skip)]
     pub request_file: String,
 
+    /// path to a Burp Suite xml export used to seed targets, known urls, headers, and cookies
+    #[serde(skip)]
+    pub import_burp: String,
+
+    /// path to nmap xml output (-oX) whose open http(s) services become targets
+    #[serde(skip)]
+    pub nmap_xml: String,
+
+    /// targets found by --import-burp or --nmap-xml, used when --url isn't given
+    #[serde(skip)]
+    pub imported_targets: Vec<String>,
+
+    /// known-good urls found by --import-burp, requested ahead of the wordlist like --known-urls
+    #[serde(skip)]
+    pub imported_urls: Vec<String>,
+
+    /// path to katana (-jsonl) or gospider (--json) output from a crawl of the target
+    #[serde(skip)]
+    pub import_crawl: String,
+
+    /// urls found by --import-crawl, marked as already seen and used to seed recursion
+    #[serde(skip)]
+    pub crawled_urls: Vec<String>,
+
     /// default request protocol
     #[serde(default = "request_protocol")]
     pub protocol: String,
 
+    /// Scan every live scheme of a bare hostname, instead of picking one
+    #[serde(default)]
+    pub both_schemes: bool,
+
+    /// Probe a bare hostname over the scheme its well-known port speaks first, and reuse what
+    /// was learned during the connectivity check
+    #[serde(default)]
+    pub smart_schemes: bool,
+
+    /// Skip requesting a bare hostname over both schemes, and use --protocol for it instead
+    #[serde(default)]
+    pub no_scheme_probe: bool,
+
     /// number of directory scan bars to show at any given time, 0 is no limit
     #[serde(default)]
     pub limit_bars: usize,
+
+    /// write an error event (url, method, error class) to the output file for each failed request
+    #[serde(default)]
+    pub error_events: bool,
+
+    /// write a skip event (url, reason) to the output file for each directory that recursion
+    /// deliberately passed over (--depth, --dont-scan, wildcards, etc)
+    #[serde(default)]
+    pub skip_events: bool,
+
+    /// annotate each finding with a best guess at the human language of its body
+    #[serde(default)]
+    pub detect_language: bool,
+
+    /// annotate findings worth a closer look with built-in tags (ex: backup-file, 5xx)
+    #[serde(default)]
+    pub tag_findings: bool,
+
+    /// NAME=REGEX rules; findings whose url or body match REGEX are tagged with NAME
+    #[serde(default)]
+    pub tag_rules: Vec<String>,
+
+    /// number of findings written to the output file between flushes to disk
+    #[serde(default = "flush_every")]
+    pub flush_every: usize,
+
+    /// number of requests a single directory may take before the user is warned (and asked
+    /// whether or not to continue, when stdin is a terminal); 0 turns the check off
+    #[serde(default = "max_dir_requests")]
+    pub max_dir_requests: usize,
+
+    /// disable progress bars entirely, printing a periodic status line instead
+    #[serde(default)]
+    pub no_bars: bool,
+
+    /// use ascii in place of emoji and box-drawing characters in terminal output; also turned on
+    /// automatically for terminals that can't display them
+    #[serde(default)]
+    pub ascii: bool,
+
+    /// print the findings as a tree, grouped by directory, once the scan completes
+    #[serde(default)]
+    pub tree: bool,
+
+    /// maximum number of times per second that progress bars are redrawn
+    #[serde(default = "bar_refresh_hz")]
+    pub bar_refresh_hz: u8,
+
+    /// path to a file that discovered urls are written to at the end of the scan
+    #[serde(default)]
+    pub export_urls: String,
+
+    /// only export urls whose responses have one of these status codes (default: all)
+    #[serde(default)]
+    pub export_status: Vec<u16>,
+
+    /// format of the --export-urls file (plain, ffuf, gobuster)
+    #[serde(default = "export_format")]
+    pub export_format: String,
+
+    /// path to a file that discovered urls are appended to as they're found, one per line, so
+    /// nuclei (-list FILE) can pick them up while the scan is still running
+    #[serde(default)]
+    pub nuclei_targets: String,
+
+    /// path to a self-contained html summary of the scan, written at the end of the scan
+    #[serde(default)]
+    pub report_html: String,
+
+    /// path to a markdown summary of the scan, grouped by target, written at the end of the scan
+    #[serde(default)]
+    pub report_markdown: String,
+
+    /// path to an xml report of the scan's findings, in DirBuster's schema, written at the end
+    /// of the scan
+    #[serde(default)]
+    pub report_xml: String,
+
+    /// path to which findings are written once the scan is over, in the layout of ffuf's json
+    /// output (-of json)
+    #[serde(default)]
+    pub emit_ffuf_json: String,
+
+    /// path to a ledger (csv or json lines) that a summary of each run is appended to
+    #[serde(default)]
+    pub campaign: String,
+
+    /// url that a json payload is POSTed to for each finding, and once the scan is over
+    ///
+    /// never serialized, so it stays out of state files and json output; a resumed scan needs
+    /// it passed again
+    #[serde(default, skip_serializing)]
+    pub notify_webhook: String,
+
+    /// findings with a lower status code than this don't fire a --notify-webhook notification
+    #[serde(default)]
+    pub notify_min_status: u16,
+
+    /// shape of --notify-webhook payloads (generic, slack, discord, teams)
+    #[serde(default = "notify_format")]
+    pub notify_format: String,
+
+    /// message sent to chat --notify-format webhooks for each finding; {url}, {status},
+    /// {method}, {lines}, {words}, {chars}, and {run_id} are filled in
+    #[serde(default = "notify_template")]
+    pub notify_template: String,
+
+    /// most --notify-webhook findings sent per minute, the rest are rolled up into a count
+    /// (0 is unlimited)
+    #[serde(default = "notify_rate_limit")]
+    pub notify_rate_limit: usize,
+
+    /// base url of a running OWASP ZAP instance's api; each finding is handed to ZAP so its
+    /// passive and active scanners can pick it up
+    #[serde(default)]
+    pub zap_api_url: String,
+
+    /// key sent to the --zap-api-url api, when ZAP requires one
+    ///
+    /// never serialized, so it stays out of state files and json output; a resumed scan needs
+    /// it passed again
+    #[serde(default, skip_serializing)]
+    pub zap_api_key: String,
+
+    /// how unreachable targets are handled by the pre-scan connectivity check (warn, strict,
+    /// skip-tls-errors)
+    #[serde(default = "preflight")]
+    pub preflight: String,
+
+    /// number of times an unreachable target is retried by the pre-scan connectivity check
+    #[serde(default)]
+    pub preflight_retries: usize,
+
+    /// path to a file that the pre-scan connectivity check's results are written to (NDJSON)
+    #[serde(default)]
+    pub preflight_report: String,
 }
 
 impl Default for Configuration {
@@ -361,6 +775,7 @@ impl Default for Configuration {
             &user_agent,
             false,
             false,
+            TlsBackend::default(),
             &HashMap::new(),
             None,
             Vec::<String>::new(),
@@ -369,6 +784,7 @@ impl Default for Configuration {
         )
         .expect("Could not build client");
         let replay_client = None;
+        let interstitial_client = None;
         let status_codes = status_codes();
         let replay_codes = status_codes.clone();
         let kind = serialized_type();
@@ -380,11 +796,15 @@ impl Default for Configuration {
             kind,
             client,
             timeout,
+            dns_cache_size: 0,
+            dns_cache_ttl: dns_cache_ttl(),
             user_agent,
             replay_codes,
             status_codes,
             extract_links,
+            extract_priority: extract_priority(),
             replay_client,
+            interstitial_client,
             requester_policy,
             dont_filter: false,
             auto_bail: false,
@@ -393,52 +813,137 @@ impl Default for Configuration {
             quiet: false,
             output_level,
             resumed: false,
+            run_id: String::new(),
+            auto_resume: false,
             stdin: false,
             json: false,
+            sarif: false,
+            csv: false,
+            csv_columns: csv_columns(),
+            json_include_body: 0,
+            banner_json: String::new(),
+            output_format: String::new(),
             scan_dir_listings: false,
+            scan_discovered_hosts: false,
+            spa_mode: false,
+            survey_headers: false,
+            recon_banner: false,
+            known_urls: String::new(),
+            openapi: String::new(),
+            graphql: false,
+            changed_since: String::new(),
+            validators: HashMap::new(),
+            fetch_wordlist: String::new(),
+            archive_seed: Vec::new(),
             verbosity: 0,
             scan_limit: 0,
+            interleave_targets: false,
             parallel: 0,
             rate_limit: 0,
+            inflight_per_dir: 0,
             limit_bars: 0,
+            no_bars: false,
+            ascii: false,
+            tree: false,
+            error_events: false,
+            skip_events: false,
+            detect_language: false,
+            tag_findings: false,
+            tag_rules: Vec::new(),
+            flush_every: flush_every(),
+            max_dir_requests: max_dir_requests(),
+            bar_refresh_hz: bar_refresh_hz(),
+            export_urls: String::new(),
+            export_status: Vec::new(),
+            export_format: export_format(),
+            nuclei_targets: String::new(),
+            report_html: String::new(),
+            report_markdown: String::new(),
+            report_xml: String::new(),
+            emit_ffuf_json: String::new(),
+            campaign: String::new(),
+            notify_webhook: String::new(),
+            notify_min_status: 0,
+            notify_format: notify_format(),
+            notify_template: notify_template(),
+            notify_rate_limit: notify_rate_limit(),
+            zap_api_url: String::new(),
+            zap_api_key: String::new(),
+            preflight: preflight(),
+            preflight_retries: 0,
+            preflight_report: String::new(),
             add_slash: false,
+            auto_slash: false,
+            case_insensitive: false,
+            retry_truncated: false,
             insecure: false,
+            tls_backend: TlsBackend::default(),
             redirects: false,
             no_recursion: false,
             random_agent: false,
             collect_extensions: false,
             collect_backups: false,
             collect_words: false,
+            word_languages: word_languages(),
+            transliterate: false,
+            collected_word_min_len: 0,
+            collected_word_max_len: 0,
+            collected_word_charset: collected_word_charset(),
+            collected_word_limit: 0,
+            save_collected_words: String::new(),
             save_state: true,
             force_recursion: false,
             update_app: false,
+            self_test: false,
+            update_from: String::new(),
+            refilter: String::new(),
             proxy: String::new(),
+            proxy_check: false,
             client_cert: String::new(),
             client_key: String::new(),
             config: String::new(),
+            config_files: Vec::new(),
             output: String::new(),
+            output_upload: String::new(),
+            output_dir: String::new(),
             debug_log: String::new(),
             target_url: String::new(),
             time_limit: String::new(),
             resume_from: String::new(),
             replay_proxy: String::new(),
             request_file: String::new(),
+            import_burp: String::new(),
+            nmap_xml: String::new(),
+            imported_targets: Vec::new(),
+            imported_urls: Vec::new(),
+            import_crawl: String::new(),
+            crawled_urls: Vec::new(),
             protocol: request_protocol(),
+            both_schemes: false,
+            smart_schemes: false,
+            no_scheme_probe: false,
             server_certs: Vec::new(),
             queries: Vec::new(),
+            meta: HashMap::new(),
             extensions: Vec::new(),
+            extension_wordlists: HashMap::new(),
+            adaptive_extensions: 0,
             methods: methods(),
             data: Vec::new(),
             filter_size: Vec::new(),
             filter_regex: Vec::new(),
             url_denylist: Vec::new(),
             regex_denylist: Vec::new(),
+            dont_scan_file: String::new(),
             filter_line_count: Vec::new(),
             filter_word_count: Vec::new(),
             filter_status: Vec::new(),
+            status_groups: HashMap::new(),
             filter_similar: Vec::new(),
             headers: HashMap::new(),
+            interstitial_bypass: HashMap::new(),
             depth: depth(),
+            recurse_threshold: 0,
             threads: threads(),
             wordlist: wordlist(),
             dont_collect: ignored_extensions(),
@@ -454,15 +959,23 @@ impl Configuration {
     /// - **timeout**: `5` seconds
     /// - **redirects**: `false`
     /// - **extract_links**: `true`
+    /// - **extract_priority**: `normal`
     /// - **wordlist**: [`DEFAULT_WORDLIST`](constant.DEFAULT_WORDLIST.html)
     /// - **config**: `None`
+    /// - **config_files**: `[]`
     /// - **threads**: `50`
     /// - **timeout**: `7` seconds
+    /// - **dns_cache_size**: `0` (no dns cache)
+    /// - **dns_cache_ttl**: `300` seconds
     /// - **verbosity**: `0` (no logging enabled)
     /// - **proxy**: `None`
+    /// - **proxy_check**: `false`
     /// - **status_codes**: [`DEFAULT_RESPONSE_CODES`](constant.DEFAULT_RESPONSE_CODES.html)
     /// - **filter_status**: `None`
+    /// - **status_groups**: `None`
     /// - **output**: `None` (print to stdout)
+    /// - **output_upload**: `None`
+    /// - **output_dir**: `None`
     /// - **debug_log**: `None`
     /// - **quiet**: `false`
     /// - **silent**: `false`
@@ -472,41 +985,121 @@ impl Configuration {
     /// - **user_agent**: `feroxbuster/VERSION`
     /// - **random_agent**: `false`
     /// - **insecure**: `false` (don't be insecure, i.e. don't allow invalid certs)
+    /// - **tls_backend**: `native-tls` (`rustls` when built without the `native-tls` feature)
     /// - **extensions**: `None`
+    /// - **extension_wordlists**: `None`
+    /// - **adaptive_extensions**: `0` (every extension is requested everywhere)
     /// - **collect_extensions**: `false`
     /// - **collect_backups**: `false`
     /// - **backup_extensions**: [`DEFAULT_BACKUP_EXTENSIONS`](constant.DEFAULT_BACKUP_EXTENSIONS.html)
     /// - **collect_words**: `false`
+    /// - **word_languages**: `["en"]`
+    /// - **transliterate**: `false`
+    /// - **collected_word_min_len**: `0` (no minimum length imposed)
+    /// - **collected_word_max_len**: `0` (no maximum length imposed)
+    /// - **collected_word_charset**: `any`
+    /// - **collected_word_limit**: `0` (no limit on number of collected words imposed)
+    /// - **save_collected_words**: `None`
     /// - **dont_collect**: [`DEFAULT_IGNORED_EXTENSIONS`](constant.DEFAULT_RESPONSE_CODES.html)
     /// - **methods**: [`DEFAULT_METHOD`](constant.DEFAULT_METHOD.html)
     /// - **data**: `None`
     /// - **url_denylist**: `None`
     /// - **regex_denylist**: `None`
+    /// - **dont_scan_file**: `None`
     /// - **filter_size**: `None`
     /// - **filter_similar**: `None`
     /// - **filter_regex**: `None`
     /// - **filter_word_count**: `None`
     /// - **filter_line_count**: `None`
     /// - **headers**: `None`
+    /// - **interstitial_bypass**: `None`
     /// - **queries**: `None`
+    /// - **meta**: `None`
     /// - **no_recursion**: `false` (recursively scan enumerated sub-directories)
     /// - **add_slash**: `false`
+    /// - **auto_slash**: `false`
+    /// - **case_insensitive**: `false`
+    /// - **retry_truncated**: `false`
+    /// - **run_id**: a new v4 uuid for each invocation
     /// - **stdin**: `false`
+    /// - **auto_resume**: `false` (prompt before resuming from a recent state file)
     /// - **json**: `false`
+    /// - **sarif**: `false`
+    /// - **csv**: `false`
+    /// - **csv_columns**: `url`, `status`, `method`, `lines`, `words`, `chars`, `location`
+    /// - **json_include_body**: `0` (response bodies aren't included in NDJSON output)
+    /// - **banner_json**: `None`
+    /// - **output_format**: `None` (findings use the normal report line)
     /// - **dont_filter**: `false` (auto filter wildcard responses)
     /// - **depth**: `4` (maximum recursion depth)
+    /// - **recurse_threshold**: `0` (recursion isn't limited by the number of findings)
     /// - **force_recursion**: `false` (still respects recursion depth)
     /// - **scan_limit**: `0` (no limit on concurrent scans imposed)
+    /// - **interleave_targets**: `false`
     /// - **limit_bars**: `0` (no limit on number of directory scan bars shown)
+    /// - **no_bars**: `false`
+    /// - **ascii**: `false` (unless the terminal can't display emoji)
+    /// - **tree**: `false`
+    /// - **error_events**: `false`
+    /// - **skip_events**: `false`
+    /// - **detect_language**: `false`
+    /// - **tag_findings**: `false`
+    /// - **tag_rules**: `None`
+    /// - **flush_every**: `1` (flush after every finding)
+    /// - **max_dir_requests**: `1_000_000` (warn when a directory takes more requests than this)
+    /// - **bar_refresh_hz**: `20` (progress bar redraws per second)
     /// - **parallel**: `0` (no limit on parallel scans imposed)
     /// - **rate_limit**: `0` (no limit on requests per second imposed)
+    /// - **inflight_per_dir**: `0` (no limit on in-flight requests per directory imposed)
     /// - **time_limit**: `None` (no limit on length of scan imposed)
     /// - **replay_proxy**: `None` (no limit on concurrent scans imposed)
     /// - **replay_codes**: [`DEFAULT_RESPONSE_CODES`](constant.DEFAULT_RESPONSE_CODES.html)
     /// - **update_app**: `false`
+    /// - **self_test**: `false`
+    /// - **update_from**: `None`
+    /// - **refilter**: `None`
     /// - **scan_dir_listings**: `false`
+    /// - **scan_discovered_hosts**: `false`
+    /// - **spa_mode**: `false`
+    /// - **survey_headers**: `false`
+    /// - **recon_banner**: `false`
+    /// - **known_urls**: `None`
+    /// - **openapi**: `None`
+    /// - **graphql**: `false`
+    /// - **changed_since**: `None`
+    /// - **validators**: `{}`
+    /// - **fetch_wordlist**: `None`
+    /// - **archive_seed**: `[]`
+    /// - **export_urls**: `None`
+    /// - **export_status**: `None` (export all discovered urls)
+    /// - **export_format**: `plain`
+    /// - **nuclei_targets**: `None`
+    /// - **report_html**: `None`
+    /// - **report_markdown**: `None`
+    /// - **report_xml**: `None`
+    /// - **emit_ffuf_json**: `None`
+    /// - **campaign**: `None`
+    /// - **notify_webhook**: `None`
+    /// - **notify_min_status**: `0` (notify on every finding)
+    /// - **notify_format**: `generic`
+    /// - **notify_template**: `{status} {method} {url} ({lines}l {words}w {chars}c)`
+    /// - **notify_rate_limit**: `20` (per minute)
+    /// - **zap_api_url**: `None`
+    /// - **zap_api_key**: `None`
+    /// - **preflight**: `warn`
+    /// - **preflight_retries**: `0`
+    /// - **preflight_report**: `None`
     /// - **request_file**: `None`
+    /// - **import_burp**: `None`
+    /// - **nmap_xml**: `None`
+    /// - **imported_targets**: `[]`
+    /// - **imported_urls**: `[]`
+    /// - **import_crawl**: `None`
+    /// - **crawled_urls**: `[]`
     /// - **protocol**: `https`
+    /// - **both_schemes**: `false`
+    /// - **smart_schemes**: `false`
+    /// - **no_scheme_probe**: `false`
     ///
     /// After which, any values defined in a
     /// [ferox-config.toml](constant.DEFAULT_CONFIG_NAME.html) config file will override the
@@ -518,6 +1111,12 @@ impl Configuration {
     /// - The same directory as the `feroxbuster` executable
     /// - The user's current working directory
     ///
+    /// A `ferox-config.json` using the same keys is also accepted in each location, and is read
+    /// after the `ferox-config.toml` in the same directory (if any).
+    ///
+    /// When a config file is passed via `--config` (either format), none of the locations above
+    /// are searched; only the given file is read.
+    ///
     /// If more than one valid configuration file is found, each one overwrites the values found previously.
     ///
     /// Finally, any options/arguments given on the commandline will override both built-in and
@@ -541,30 +1140,56 @@ impl Configuration {
         // else is specified.
         let mut config = Configuration::default();
 
-        // read in all config files
-        Self::parse_config_files(&mut config)?;
+        if let Some(config_file) = args.get_one::<String>("config") {
+            // an explicitly given config file is the only one read
+            let config_file = PathBuf::from(config_file);
+
+            if !config_file.exists() {
+                bail!("Could not find config file: {}", config_file.display());
+            }
+
+            Self::parse_and_merge_config(config_file, &mut config)?;
+        } else {
+            // read in all config files
+            Self::parse_config_files(&mut config)?;
+        }
 
         // read in the user provided options, this produces a separate instance of Configuration
         // in order to allow for potentially merging into a --resume-from Configuration
-        let cli_config = Self::parse_cli_args(&args);
+        //
+        // status groups (-s @name) can only be defined in config files, so those are handed over
+        // to resolve any groups referenced on the command line
+        let cli_config = Self::parse_cli_args(&args, &config.status_groups);
+
+        // --resume-from used, or a recent state file for the target was found, need to first
+        // read the Configuration from disk, and then merge the cli_config into the resumed config
+        let resume_from = match args.get_one::<String>("resume_from") {
+            Some(filename) => Some(filename.to_owned()),
+            None => Self::discover_state_file(&config, &cli_config),
+        };
 
-        // --resume-from used, need to first read the Configuration from disk, and then
-        // merge the cli_config into the resumed config
-        if let Some(filename) = args.get_one::<String>("resume_from") {
+        if let Some(filename) = resume_from {
             // when resuming a scan, instead of normal configuration loading, we just
             // load the config from disk by calling resume_scan
-            let mut previous_config = resume_scan(filename);
+            let mut previous_config = resume_scan(&filename);
 
             // if any other arguments were passed on the command line, the theory is that the
             // user meant to modify the previously cancelled/saved scan in some way that we
             // should take into account
             Self::merge_config(&mut previous_config, cli_config);
 
+            // a discovered state file wasn't passed via --resume-from, but is where previously
+            // seen scans and statistics are loaded from
+            previous_config.resume_from = filename;
+
             // the resumed flag isn't printed in the banner and really has no business being
             // serialized or included in much of the usual config logic; simply setting it to true
             // here and being done with it
             previous_config.resumed = true;
 
+            // a resumed scan is still a new invocation, and gets its own id
+            previous_config.run_id = Uuid::new_v4().to_string();
+
             // if the user used --stdin, we already have all the scans started (or complete), we
             // need to flip stdin to false so that the 'read from stdin' logic doesn't fire (if
             // not flipped to false, the program hangs waiting for input from stdin again)
@@ -592,14 +1217,53 @@ impl Configuration {
             parse_request_file(&mut config)?;
         }
 
+        // same reasoning as the raw request file above; headers and cookies from the cli take
+        // precedence over those found in the export
+        if !config.import_burp.is_empty() {
+            import_burp(&mut config)?;
+        }
+
+        if !config.nmap_xml.is_empty() {
+            import_nmap(&mut config)?;
+        }
+
+        if !config.import_crawl.is_empty() {
+            import_crawl(&mut config)?;
+        }
+
+        if !config.changed_since.is_empty() {
+            load_validators(&mut config)?;
+        }
+
+        config.run_id = Uuid::new_v4().to_string();
+
+        if is_output_template(&config.output) {
+            // resolved once per run; --parallel children are handed the template and resolve
+            // it for their own target
+            let target = if config.target_url.is_empty() {
+                "stdin"
+            } else {
+                config.target_url.as_str()
+            };
+
+            config.output = render_output_template(&config.output, target, &config.run_id);
+        }
+
+        if upload::is_s3_url(&config.output) {
+            // results are written locally as usual, then uploaded once the scan completes
+            S3Location::parse(&config.output)?;
+            config.output_upload = std::mem::take(&mut config.output);
+            config.output = upload::staging_path(&config.output_upload, &config.run_id);
+        }
+
         // rebuild clients is the last step in either code branch
         Self::try_rebuild_clients(&mut config);
 
         Ok(config)
     }
 
-    /// Parse all possible versions of the ferox-config.toml file, adhering to the order of
-    /// precedence outlined above
+    /// Parse all possible versions of the ferox-config.toml (or ferox-config.json) file, adhering
+    /// to the order of precedence outlined above
     fn parse_config_files(config: &mut Self) -> Result<()> {
         // Next, we parse the ferox-config.toml file, if present and set the values
         // therein to overwrite our default values. Deserialized defaults are specified
@@ -613,8 +1277,7 @@ impl Configuration {
         //   - current directory
 
         // merge a config found at /etc/feroxbuster/ferox-config.toml
-        let config_file = Path::new("/etc/feroxbuster").join(DEFAULT_CONFIG_NAME);
-        Self::parse_and_merge_config(config_file, config)?;
+        Self::parse_and_merge_config_dir(Path::new("/etc/feroxbuster"), config)?;
 
         // merge a config found at ~/.config/feroxbuster/ferox-config.toml
         // config_dir() resolves to one of the following
@@ -622,41 +1285,163 @@ impl Configuration {
         //   - macOS: $HOME/Library/Application Support
         //   - windows: {FOLDERID_RoamingAppData}
         let config_dir = dirs::config_dir().ok_or_else(|| anyhow!("Couldn't load config"))?;
-        let config_file = config_dir.join("feroxbuster").join(DEFAULT_CONFIG_NAME);
-        Self::parse_and_merge_config(config_file, config)?;
+        Self::parse_and_merge_config_dir(&config_dir.join("feroxbuster"), config)?;
 
         // merge a config found in same the directory as feroxbuster executable
         let exe_path = current_exe()?;
         let bin_dir = exe_path
             .parent()
             .ok_or_else(|| anyhow!("Couldn't load config"))?;
-        let config_file = bin_dir.join(DEFAULT_CONFIG_NAME);
-        Self::parse_and_merge_config(config_file, config)?;
+        Self::parse_and_merge_config_dir(bin_dir, config)?;
 
         // merge a config found in the user's current working directory
         let cwd = current_dir()?;
-        let config_file = cwd.join(DEFAULT_CONFIG_NAME);
-        Self::parse_and_merge_config(config_file, config)?;
+        Self::parse_and_merge_config_dir(&cwd, config)?;
+
+        Ok(())
+    }
 
+    /// Given a directory, read in the toml and json config files found there (in that order)
+    fn parse_and_merge_config_dir(directory: &Path, config: &mut Self) -> Result<()> {
+        for name in [DEFAULT_CONFIG_NAME, DEFAULT_JSON_CONFIG_NAME] {
+            Self::parse_and_merge_config(directory.join(name), config)?;
+        }
         Ok(())
     }
 
     /// Given a set of ArgMatches read from the CLI, update and return the default Configuration
     /// settings
-    fn parse_cli_args(args: &ArgMatches) -> Self {
+    ///
+    /// `status_groups` are the groups defined in config files, used to expand `@name` wherever
+    /// status codes are accepted
+    fn parse_cli_args(args: &ArgMatches, status_groups: &HashMap<String, Vec<u16>>) -> Self {
         let mut config = Configuration::default();
 
         update_config_with_num_type_if_present!(&mut config.threads, args, "threads", usize);
         update_config_with_num_type_if_present!(&mut config.parallel, args, "parallel", usize);
         update_config_with_num_type_if_present!(&mut config.depth, args, "depth", usize);
+        update_config_with_num_type_if_present!(
+            &mut config.recurse_threshold,
+            args,
+            "recurse_threshold",
+            usize
+        );
         update_config_with_num_type_if_present!(&mut config.scan_limit, args, "scan_limit", usize);
+        update_config_with_num_type_if_present!(
+            &mut config.max_dir_requests,
+            args,
+            "max_dir_requests",
+            usize
+        );
+        update_config_with_num_type_if_present!(
+            &mut config.flush_every,
+            args,
+            "flush_every",
+            usize
+        );
         update_config_with_num_type_if_present!(&mut config.rate_limit, args, "rate_limit", usize);
+        update_config_with_num_type_if_present!(
+            &mut config.collected_word_min_len,
+            args,
+            "collected_word_min_len",
+            usize
+        );
+        update_config_with_num_type_if_present!(
+            &mut config.collected_word_max_len,
+            args,
+            "collected_word_max_len",
+            usize
+        );
+        update_config_with_num_type_if_present!(
+            &mut config.collected_word_limit,
+            args,
+            "collected_word_limit",
+            usize
+        );
+        update_config_if_present!(
+            &mut config.save_collected_words,
+            args,
+            "save_collected_words",
+            String
+        );
+        update_config_with_num_type_if_present!(
+            &mut config.inflight_per_dir,
+            args,
+            "inflight_per_dir",
+            usize
+        );
         update_config_with_num_type_if_present!(&mut config.limit_bars, args, "limit_bars", usize);
+        update_config_with_num_type_if_present!(
+            &mut config.bar_refresh_hz,
+            args,
+            "bar_refresh_hz",
+            u8
+        );
         update_config_if_present!(&mut config.wordlist, args, "wordlist", String);
         update_config_if_present!(&mut config.output, args, "output", String);
+        update_config_if_present!(&mut config.output_dir, args, "output_dir", String);
         update_config_if_present!(&mut config.debug_log, args, "debug_log", String);
         update_config_if_present!(&mut config.resume_from, args, "resume_from", String);
         update_config_if_present!(&mut config.request_file, args, "request_file", String);
+        update_config_if_present!(&mut config.import_burp, args, "import_burp", String);
+        update_config_if_present!(&mut config.nmap_xml, args, "nmap_xml", String);
+        update_config_if_present!(&mut config.import_crawl, args, "import_crawl", String);
+        update_config_if_present!(&mut config.update_from, args, "update_from", String);
+        update_config_if_present!(&mut config.refilter, args, "refilter", String);
+        update_config_if_present!(&mut config.known_urls, args, "known_urls", String);
+        update_config_if_present!(&mut config.openapi, args, "openapi", String);
+        update_config_if_present!(&mut config.changed_since, args, "changed_since", String);
+        update_config_if_present!(&mut config.fetch_wordlist, args, "fetch_wordlist", String);
+        update_config_if_present!(&mut config.export_urls, args, "export_urls", String);
+        update_config_if_present!(&mut config.export_format, args, "export_format", String);
+        update_config_if_present!(
+            &mut config.extract_priority,
+            args,
+            "extract_priority",
+            String
+        );
+        update_config_if_present!(&mut config.nuclei_targets, args, "nuclei_targets", String);
+        update_config_if_present!(&mut config.report_html, args, "report_html", String);
+        update_config_if_present!(&mut config.report_markdown, args, "report_markdown", String);
+        update_config_if_present!(&mut config.report_xml, args, "report_xml", String);
+        update_config_if_present!(&mut config.emit_ffuf_json, args, "emit_ffuf_json", String);
+        update_config_if_present!(&mut config.campaign, args, "campaign", String);
+        update_config_if_present!(&mut config.notify_webhook, args, "notify_webhook", String);
+        update_config_with_num_type_if_present!(
+            &mut config.notify_min_status,
+            args,
+            "notify_min_status",
+            u16
+        );
+        update_config_if_present!(&mut config.notify_format, args, "notify_format", String);
+        update_config_if_present!(&mut config.notify_template, args, "notify_template", String);
+        update_config_with_num_type_if_present!(
+            &mut config.notify_rate_limit,
+            args,
+            "notify_rate_limit",
+            usize
+        );
+        update_config_if_present!(&mut config.zap_api_url, args, "zap_api_url", String);
+        update_config_if_present!(&mut config.zap_api_key, args, "zap_api_key", String);
+        update_config_if_present!(&mut config.preflight, args, "preflight", String);
+        update_config_if_present!(
+            &mut config.collected_word_charset,
+            args,
+            "collected_word_charset",
+            String
+        );
+        update_config_with_num_type_if_present!(
+            &mut config.preflight_retries,
+            args,
+            "preflight_retries",
+            usize
+        );
+        update_config_if_present!(
+            &mut config.preflight_report,
+            args,
+            "preflight_report",
+            String
+        );
         update_config_if_present!(&mut config.protocol, args, "protocol", String);
 
         if let Ok(Some(inner)) = args.try_get_one::<String>("time_limit") {
@@ -664,37 +1449,23 @@ impl Configuration {
         }
 
         if let Some(arg) = args.get_many::<String>("status_codes") {
-            config.status_codes = arg
-                .map(|code| {
-                    StatusCode::from_bytes(code.as_bytes())
-                        .unwrap_or_else(|e| report_and_exit(&e.to_string()))
-                        .as_u16()
-                })
-                .collect();
+            config.status_codes = parse_status_codes(arg, status_groups);
         }
 
         if let Some(arg) = args.get_many::<String>("replay_codes") {
             // replay codes passed in by the user
-            config.replay_codes = arg
-                .map(|code| {
-                    StatusCode::from_bytes(code.as_bytes())
-                        .unwrap_or_else(|e| report_and_exit(&e.to_string()))
-                        .as_u16()
-                })
-                .collect();
+            config.replay_codes = parse_status_codes(arg, status_groups);
         } else {
             // not passed in by the user, use whatever value is held in status_codes
             config.replay_codes.clone_from(&config.status_codes);
         }
 
         if let Some(arg) = args.get_many::<String>("filter_status") {
-            config.filter_status = arg
-                .map(|code| {
-                    StatusCode::from_bytes(code.as_bytes())
-                        .unwrap_or_else(|e| report_and_exit(&e.to_string()))
-                        .as_u16()
-                })
-                .collect();
+            config.filter_status = parse_status_codes(arg, status_groups);
+        }
+
+        if let Some(arg) = args.get_many::<String>("export_status") {
+            config.export_status = parse_status_codes(arg, status_groups);
         }
 
         if let Some(arg) = args.get_many::<String>("extensions") {
@@ -714,6 +1485,12 @@ impl Configuration {
                     });
 
                     extensions.extend(exts_from_file);
+                } else if let Some((ext, wordlist)) = ext.split_once(':') {
+                    // ext:wordlist pairs the extension with its own wordlist
+                    config.extension_wordlists.insert(
+                        ext.trim().trim_start_matches('.').to_string(),
+                        wordlist.trim().to_string(),
+                    );
                 } else {
                     extensions.push(ext.trim().trim_start_matches('.').to_string());
                 }
@@ -721,6 +1498,26 @@ impl Configuration {
             config.extensions = extensions;
         }
 
+        if came_from_cli!(args, "adaptive_extensions") {
+            // --adaptive-extensions without a value uses the default sample size
+            config.adaptive_extensions = DEFAULT_EXTENSION_SAMPLE;
+
+            update_config_with_num_type_if_present!(
+                &mut config.adaptive_extensions,
+                args,
+                "adaptive_extensions",
+                usize
+            );
+        }
+
+        if let Some(arg) = args.get_many::<String>("archive_seed") {
+            config.archive_seed = arg.map(|val| val.to_string()).collect();
+        }
+
+        if let Some(arg) = args.get_many::<String>("word_languages") {
+            config.word_languages = arg.map(|val| val.to_string()).collect();
+        }
+
         if let Some(arg) = args.get_many::<String>("dont_collect") {
             config.dont_collect = arg.map(|val| val.to_string()).collect();
         }
@@ -751,6 +1548,10 @@ impl Configuration {
             }
         }
 
+        if came_from_cli!(args, "auto_resume") {
+            config.auto_resume = true;
+        }
+
         if came_from_cli!(args, "stdin") {
             config.stdin = true;
         } else if let Some(url) = args.get_one::<String>("url") {
@@ -767,40 +1568,15 @@ impl Configuration {
             for denier in arg {
                 // could be an absolute url or a regex, need to determine which and populate the
                 // appropriate vector
-                match parse_url_with_raw_path(denier.trim_end_matches('/')) {
-                    Ok(absolute) => {
-                        // denier is an absolute url and can be parsed as such
-                        config.url_denylist.push(absolute);
-                    }
-                    Err(err) => {
-                        // there are some expected errors that happen when we try to parse a url
-                        //     ex: Url::parse("/login") -> Err("relative URL without a base")
-                        //     ex: Url::parse("http:") -> Err("empty host")
-                        //
-                        // these are known errors and are used to determine a valid value to
-                        // --dont-scan, when it's not an absolute url
-                        //
-                        // when expected errors are encountered, we're going to assume
-                        // that the input is a regular expression to be parsed. The possibility
-                        // exists that the user rolled their face across the keyboard and we're
-                        // dealing with the results, in which case we'll report it as an error and
-                        // give up
-                        if err.to_string().contains("relative URL without a base")
-                            || err.to_string().contains("empty host")
-                        {
-                            let regex = Regex::new(denier)
-                                .unwrap_or_else(|e| report_and_exit(&e.to_string()));
-
-                            config.regex_denylist.push(regex);
-                        } else {
-                            // unexpected error has occurred; bail
-                            report_and_exit(&err.to_string());
-                        }
-                    }
+                match Denier::parse(denier).unwrap_or_else(|e| report_and_exit(&e.to_string())) {
+                    Denier::Url(absolute) => config.url_denylist.push(absolute),
+                    Denier::Regex(regex) => config.regex_denylist.push(regex),
                 }
             }
         }
 
+        update_config_if_present!(&mut config.dont_scan_file, args, "dont_scan_file", String);
+
         if let Some(arg) = args.get_many::<String>("filter_regex") {
             config.filter_regex = arg.map(|val| val.to_string()).collect();
         }
@@ -875,6 +1651,66 @@ impl Configuration {
             config.scan_dir_listings = true;
         }
 
+        if came_from_cli!(args, "scan_discovered_hosts") {
+            config.scan_discovered_hosts = true;
+        }
+
+        if came_from_cli!(args, "error_events") {
+            config.error_events = true;
+        }
+
+        if came_from_cli!(args, "skip_events") {
+            config.skip_events = true;
+        }
+
+        if came_from_cli!(args, "detect_language") {
+            config.detect_language = true;
+        }
+
+        if came_from_cli!(args, "tag_findings") {
+            config.tag_findings = true;
+        }
+
+        if let Some(arg) = args.get_many::<String>("tag_rules") {
+            config.tag_rules = arg.map(|val| val.to_string()).collect();
+
+            // rules are compiled by the output handler, but a bad one should be reported up front
+            // rather than silently dropped mid-scan
+            for rule in &config.tag_rules {
+                TagRule::parse(rule).unwrap_or_else(|e| {
+                    report_and_exit(&format!("Invalid --tag-rule {rule}: {e}"))
+                });
+            }
+        }
+
+        if came_from_cli!(args, "no_bars") {
+            config.no_bars = true;
+        }
+
+        if came_from_cli!(args, "ascii") {
+            config.ascii = true;
+        }
+
+        if came_from_cli!(args, "tree") {
+            config.tree = true;
+        }
+
+        if came_from_cli!(args, "spa_mode") {
+            config.spa_mode = true;
+        }
+
+        if came_from_cli!(args, "survey_headers") {
+            config.survey_headers = true;
+        }
+
+        if came_from_cli!(args, "recon_banner") {
+            config.recon_banner = true;
+        }
+
+        if came_from_cli!(args, "graphql") {
+            config.graphql = true;
+        }
+
         if came_from_cli!(args, "dont_filter") {
             config.dont_filter = true;
         }
@@ -911,6 +1747,10 @@ impl Configuration {
             config.collect_words = true;
         }
 
+        if came_from_cli!(args, "transliterate") {
+            config.transliterate = true;
+        }
+
         if args.get_count("verbosity") > 0 {
             // occurrences_of returns 0 if none are found; this is protected in
             // an if block for the same reason as the quiet option
@@ -944,22 +1784,94 @@ impl Configuration {
             config.add_slash = true;
         }
 
+        if came_from_cli!(args, "auto_slash") {
+            config.auto_slash = true;
+        }
+
+        if came_from_cli!(args, "case_insensitive") {
+            config.case_insensitive = true;
+        }
+
+        if came_from_cli!(args, "retry_truncated") {
+            config.retry_truncated = true;
+        }
+
+        if came_from_cli!(args, "both_schemes") {
+            config.both_schemes = true;
+        }
+
+        if came_from_cli!(args, "smart_schemes") {
+            config.smart_schemes = true;
+        }
+
+        if came_from_cli!(args, "no_scheme_probe") {
+            config.no_scheme_probe = true;
+        }
+
         if came_from_cli!(args, "dont_extract_links") {
             config.extract_links = false;
         }
 
+        if came_from_cli!(args, "json_include_body") {
+            // --json-include-body without a value includes bodies in full
+            config.json_include_body = usize::MAX;
+
+            update_config_with_num_type_if_present!(
+                &mut config.json_include_body,
+                args,
+                "json_include_body",
+                usize
+            );
+        }
+
+        if came_from_cli!(args, "banner_json") {
+            // --banner-json without a value writes to stderr
+            config.banner_json = String::from("-");
+
+            update_config_if_present!(&mut config.banner_json, args, "banner_json", String);
+        }
+
+        if let Some(template) = args.get_one::<String>("output_format") {
+            // a typo'd placeholder would otherwise only show up once findings start printing
+            OutputTemplate::parse(template).unwrap_or_else(|e| {
+                report_and_exit(&format!("Invalid --output-format {template}: {e}"))
+            });
+
+            config.output_format = template.to_string();
+        }
+
         if came_from_cli!(args, "json") {
             config.json = true;
         }
 
+        if came_from_cli!(args, "sarif") {
+            config.sarif = true;
+        }
+
+        if came_from_cli!(args, "csv") {
+            config.csv = true;
+        }
+
+        if let Some(arg) = args.get_many::<String>("csv_columns") {
+            config.csv_columns = arg.map(|column| column.to_lowercase()).collect();
+        }
+
         if came_from_cli!(args, "force_recursion") {
             config.force_recursion = true;
         }
 
+        if came_from_cli!(args, "interleave_targets") {
+            config.interleave_targets = true;
+        }
+
         if came_from_cli!(args, "update_app") {
             config.update_app = true;
         }
 
+        if came_from_cli!(args, "self_test") {
+            config.self_test = true;
+        }
+
         ////
         // organizational breakpoint; all options below alter the Client configuration
         ////
@@ -969,11 +1881,27 @@ impl Configuration {
         update_config_if_present!(&mut config.replay_proxy, args, "replay_proxy", String);
         update_config_if_present!(&mut config.user_agent, args, "user_agent", String);
         update_config_with_num_type_if_present!(&mut config.timeout, args, "timeout", u64);
+        update_config_with_num_type_if_present!(
+            &mut config.dns_cache_size,
+            args,
+            "dns_cache_size",
+            usize
+        );
+        update_config_with_num_type_if_present!(
+            &mut config.dns_cache_ttl,
+            args,
+            "dns_cache_ttl",
+            u64
+        );
 
         if came_from_cli!(args, "burp") {
             config.proxy = String::from("http://127.0.0.1:8080");
         }
 
+        if came_from_cli!(args, "proxy_check") {
+            config.proxy_check = true;
+        }
+
         if came_from_cli!(args, "burp_replay") {
             config.replay_proxy = String::from("http://127.0.0.1:8080");
         }
@@ -993,6 +1921,12 @@ impl Configuration {
             config.insecure = true;
         }
 
+        if let Some(arg) = args.get_one::<String>("tls_backend") {
+            config.tls_backend = arg
+                .parse()
+                .unwrap_or_else(|err: anyhow::Error| report_and_exit(&err.to_string()));
+        }
+
         if let Some(headers) = args.get_many::<String>("headers") {
             for val in headers {
                 let Ok((name, value)) = split_header(val) else {
@@ -1003,6 +1937,16 @@ impl Configuration {
             }
         }
 
+        if let Some(headers) = args.get_many::<String>("interstitial_bypass") {
+            for val in headers {
+                let Ok((name, value)) = split_header(val) else {
+                    log::warn!("Invalid header: {}", val);
+                    continue;
+                };
+                config.interstitial_bypass.insert(name, value);
+            }
+        }
+
         if let Some(cookies) = args.get_many::<String>("cookies") {
             config.headers.insert(
                 // we know the header name is always "cookie"
@@ -1041,6 +1985,16 @@ impl Configuration {
             }
         }
 
+        if let Some(pairs) = args.get_many::<String>("meta") {
+            for val in pairs {
+                let Ok((key, value)) = split_query(val) else {
+                    log::warn!("Invalid metadata: {}", val);
+                    continue;
+                };
+                config.meta.insert(key, value);
+            }
+        }
+
         if let Some(certs) = args.get_many::<String>("server_certs") {
             for val in certs {
                 config.server_certs.push(val.to_string());
@@ -1078,8 +2032,21 @@ impl Configuration {
             Some(configuration.client_key.as_str())
         };
 
+        // every client built from here on resolves hostnames through the cache, when enabled
+        DNS_CACHE.configure(configuration.dns_cache_size, configuration.dns_cache_ttl);
+
+        if !configuration.tls_backend.is_available() {
+            // only reachable via a config file, --tls-backend is checked while parsing
+            report_and_exit(&format!(
+                "feroxbuster was built without {} support",
+                configuration.tls_backend
+            ));
+        }
+
         if proxy.is_some()
+            || configuration.tls_backend != TlsBackend::default()
             || configuration.timeout != timeout()
+            || configuration.dns_cache_size > 0
             || configuration.user_agent != user_agent()
             || configuration.redirects
             || configuration.insecure
@@ -1094,6 +2061,7 @@ impl Configuration {
                 &configuration.user_agent,
                 configuration.redirects,
                 configuration.insecure,
+                configuration.tls_backend,
                 &configuration.headers,
                 proxy,
                 server_certs,
@@ -1111,6 +2079,7 @@ impl Configuration {
                     &configuration.user_agent,
                     configuration.redirects,
                     configuration.insecure,
+                    configuration.tls_backend,
                     &configuration.headers,
                     Some(&configuration.replay_proxy),
                     server_certs,
@@ -1120,6 +2089,28 @@ impl Configuration {
                 .expect("Could not rebuild client"),
             );
         }
+
+        if !configuration.interstitial_bypass.is_empty() {
+            // only set interstitial_client when interstitial_bypass is set
+            let headers =
+                bypass_headers(&configuration.headers, &configuration.interstitial_bypass);
+
+            configuration.interstitial_client = Some(
+                client::initialize(
+                    configuration.timeout,
+                    &configuration.user_agent,
+                    configuration.redirects,
+                    configuration.insecure,
+                    configuration.tls_backend,
+                    &headers,
+                    proxy,
+                    server_certs,
+                    client_cert,
+                    client_key,
+                )
+                .expect("Could not rebuild client"),
+            );
+        }
     }
 
     /// Given a configuration file's location and an instance of `Configuration`, read in
@@ -1131,7 +2122,8 @@ impl Configuration {
             let settings = Self::parse_config(config_file)?;
 
             // set the config used for viewing in the banner
-            config.config = conf_str;
+            config.config = conf_str.clone();
+            config.config_files.push(conf_str);
 
             // update the settings
             Self::merge_config(config, settings);
@@ -1139,27 +2131,96 @@ impl Configuration {
         Ok(())
     }
 
+    /// look for a recent state file left behind by a previous scan of the target, and determine
+    /// whether or not it should be resumed (prompting the user, unless --auto-resume was used)
+    ///
+    /// state files are written to the current directory, or the temp directory as a fallback;
+    /// the --output directory is searched as well
+    fn discover_state_file(config: &Self, cli_config: &Self) -> Option<String> {
+        // cli values haven't been merged into the config file's values at this point
+        let pick = |cli: &String, file: &String| {
+            if cli.is_empty() {
+                file.to_owned()
+            } else {
+                cli.to_owned()
+            }
+        };
+
+        let target_url = pick(&cli_config.target_url, &config.target_url);
+
+        if target_url.is_empty() || cli_config.stdin {
+            // nothing to match against, or targets aren't known until read from stdin
+            return None;
+        }
+
+        let output = pick(&cli_config.output, &config.output);
+
+        let mut dirs = vec![PathBuf::from(".")];
+
+        if let Some(parent) = Path::new(&output).parent() {
+            if !parent.as_os_str().is_empty() {
+                dirs.push(parent.to_path_buf());
+            }
+        }
+
+        dirs.push(temp_dir());
+
+        let max_age = std::time::Duration::from_secs(RECENT_STATE_FILE_AGE);
+        let path = find_state_file(&target_url, &dirs, max_age)?;
+
+        if cli_config.auto_resume || config.auto_resume || confirm_resume(&target_url, &path) {
+            log::info!("resuming scan of {} from {}", target_url, path.display());
+            return Some(path.to_string_lossy().to_string());
+        }
+
+        None
+    }
+
     /// Given two Configurations, overwrite `settings` with the fields found in `settings_to_merge`
     fn merge_config(conf: &mut Self, new: Self) {
         // does not include the following Configuration fields, as they don't make sense here
         //  - kind
         //  - client
         //  - replay_client
+        //  - interstitial_client
         //  - resumed
         //  - config
         update_if_not_default!(&mut conf.target_url, new.target_url, "");
         update_if_not_default!(&mut conf.time_limit, new.time_limit, "");
         update_if_not_default!(&mut conf.proxy, new.proxy, "");
+        update_if_not_default!(&mut conf.proxy_check, new.proxy_check, false);
         update_if_not_default!(
             &mut conf.server_certs,
             new.server_certs,
             Vec::<String>::new()
         );
         update_if_not_default!(&mut conf.json, new.json, false);
+        update_if_not_default!(&mut conf.sarif, new.sarif, false);
+        update_if_not_default!(&mut conf.csv, new.csv, false);
+        update_if_not_default!(&mut conf.csv_columns, new.csv_columns, csv_columns());
+        update_if_not_default!(&mut conf.json_include_body, new.json_include_body, 0);
+        update_if_not_default!(&mut conf.banner_json, new.banner_json, "");
+        update_if_not_default!(&mut conf.output_format, new.output_format, "");
         update_if_not_default!(&mut conf.client_cert, new.client_cert, "");
         update_if_not_default!(&mut conf.client_key, new.client_key, "");
         update_if_not_default!(&mut conf.verbosity, new.verbosity, 0);
         update_if_not_default!(&mut conf.limit_bars, new.limit_bars, 0);
+        update_if_not_default!(&mut conf.ascii, new.ascii, false);
+        update_if_not_default!(&mut conf.tree, new.tree, false);
+        update_if_not_default!(&mut conf.flush_every, new.flush_every, flush_every());
+        update_if_not_default!(
+            &mut conf.max_dir_requests,
+            new.max_dir_requests,
+            max_dir_requests()
+        );
+        update_if_not_default!(&mut conf.detect_language, new.detect_language, false);
+        update_if_not_default!(&mut conf.tag_findings, new.tag_findings, false);
+        update_if_not_default!(&mut conf.tag_rules, new.tag_rules, Vec::<String>::new());
+        update_if_not_default!(
+            &mut conf.bar_refresh_hz,
+            new.bar_refresh_hz,
+            bar_refresh_hz()
+        );
         update_if_not_default!(&mut conf.silent, new.silent, false);
         update_if_not_default!(&mut conf.quiet, new.quiet, false);
         update_if_not_default!(&mut conf.auto_bail, new.auto_bail, false);
@@ -1167,19 +2228,57 @@ impl Configuration {
         update_if_not_default!(&mut conf.collect_extensions, new.collect_extensions, false);
         update_if_not_default!(&mut conf.collect_backups, new.collect_backups, false);
         update_if_not_default!(&mut conf.collect_words, new.collect_words, false);
+        update_if_not_default!(&mut conf.transliterate, new.transliterate, false);
+        update_if_not_default!(
+            &mut conf.collected_word_min_len,
+            new.collected_word_min_len,
+            0
+        );
+        update_if_not_default!(
+            &mut conf.collected_word_max_len,
+            new.collected_word_max_len,
+            0
+        );
+        update_if_not_default!(
+            &mut conf.collected_word_charset,
+            new.collected_word_charset,
+            collected_word_charset()
+        );
+        update_if_not_default!(&mut conf.collected_word_limit, new.collected_word_limit, 0);
+        update_if_not_default!(&mut conf.save_collected_words, new.save_collected_words, "");
         // use updated quiet/silent values to determine output level; same for requester policy
         conf.output_level = determine_output_level(conf.quiet, conf.silent, conf.json);
         conf.requester_policy = determine_requester_policy(conf.auto_tune, conf.auto_bail);
         update_if_not_default!(&mut conf.output, new.output, "");
+        update_if_not_default!(&mut conf.output_dir, new.output_dir, "");
         update_if_not_default!(&mut conf.redirects, new.redirects, false);
         update_if_not_default!(&mut conf.insecure, new.insecure, false);
+        update_if_not_default!(
+            &mut conf.tls_backend,
+            new.tls_backend,
+            TlsBackend::default()
+        );
         update_if_not_default!(&mut conf.force_recursion, new.force_recursion, false);
         update_if_not_default!(&mut conf.extract_links, new.extract_links, extract_links());
+        update_if_not_default!(
+            &mut conf.extract_priority,
+            new.extract_priority,
+            extract_priority()
+        );
         update_if_not_default!(&mut conf.extensions, new.extensions, Vec::<String>::new());
+        update_if_not_default!(
+            &mut conf.extension_wordlists,
+            new.extension_wordlists,
+            HashMap::new()
+        );
+        update_if_not_default!(&mut conf.adaptive_extensions, new.adaptive_extensions, 0);
         update_if_not_default!(&mut conf.methods, new.methods, methods());
         update_if_not_default!(&mut conf.data, new.data, Vec::<u8>::new());
         update_if_not_default!(&mut conf.url_denylist, new.url_denylist, Vec::<Url>::new());
         update_if_not_default!(&mut conf.update_app, new.update_app, false);
+        update_if_not_default!(&mut conf.self_test, new.self_test, false);
+        update_if_not_default!(&mut conf.update_from, new.update_from, "");
+        update_if_not_default!(&mut conf.refilter, new.refilter, "");
         if !new.regex_denylist.is_empty() {
             // cant use the update_if_not_default macro due to the following error
             //
@@ -1188,11 +2287,23 @@ impl Configuration {
             // if we get a non-empty list of regex in the new config, override the old
             conf.regex_denylist = new.regex_denylist;
         }
+        update_if_not_default!(&mut conf.dont_scan_file, new.dont_scan_file, "");
         update_if_not_default!(&mut conf.headers, new.headers, HashMap::new());
+        update_if_not_default!(
+            &mut conf.interstitial_bypass,
+            new.interstitial_bypass,
+            HashMap::new()
+        );
         update_if_not_default!(&mut conf.queries, new.queries, Vec::new());
+        update_if_not_default!(&mut conf.meta, new.meta, HashMap::new());
+        update_if_not_default!(&mut conf.status_groups, new.status_groups, HashMap::new());
         update_if_not_default!(&mut conf.no_recursion, new.no_recursion, false);
         update_if_not_default!(&mut conf.add_slash, new.add_slash, false);
+        update_if_not_default!(&mut conf.auto_slash, new.auto_slash, false);
+        update_if_not_default!(&mut conf.case_insensitive, new.case_insensitive, false);
+        update_if_not_default!(&mut conf.retry_truncated, new.retry_truncated, false);
         update_if_not_default!(&mut conf.stdin, new.stdin, false);
+        update_if_not_default!(&mut conf.auto_resume, new.auto_resume, false);
         update_if_not_default!(&mut conf.filter_size, new.filter_size, Vec::<u64>::new());
         update_if_not_default!(
             &mut conf.filter_regex,
@@ -1221,16 +2332,75 @@ impl Configuration {
         );
         update_if_not_default!(&mut conf.dont_filter, new.dont_filter, false);
         update_if_not_default!(&mut conf.scan_dir_listings, new.scan_dir_listings, false);
+        update_if_not_default!(
+            &mut conf.scan_discovered_hosts,
+            new.scan_discovered_hosts,
+            false
+        );
+        update_if_not_default!(&mut conf.spa_mode, new.spa_mode, false);
+        update_if_not_default!(&mut conf.survey_headers, new.survey_headers, false);
+        update_if_not_default!(&mut conf.recon_banner, new.recon_banner, false);
+        update_if_not_default!(&mut conf.known_urls, new.known_urls, "");
+        update_if_not_default!(&mut conf.openapi, new.openapi, "");
+        update_if_not_default!(&mut conf.graphql, new.graphql, false);
+        update_if_not_default!(&mut conf.changed_since, new.changed_since, "");
+        update_if_not_default!(&mut conf.fetch_wordlist, new.fetch_wordlist, "");
+        update_if_not_default!(
+            &mut conf.archive_seed,
+            new.archive_seed,
+            Vec::<String>::new()
+        );
+        update_if_not_default!(&mut conf.export_urls, new.export_urls, "");
+        update_if_not_default!(&mut conf.export_format, new.export_format, export_format());
+        update_if_not_default!(&mut conf.nuclei_targets, new.nuclei_targets, "");
+        update_if_not_default!(&mut conf.report_html, new.report_html, "");
+        update_if_not_default!(&mut conf.report_markdown, new.report_markdown, "");
+        update_if_not_default!(&mut conf.report_xml, new.report_xml, "");
+        update_if_not_default!(&mut conf.emit_ffuf_json, new.emit_ffuf_json, "");
+        update_if_not_default!(&mut conf.campaign, new.campaign, "");
+        update_if_not_default!(&mut conf.notify_webhook, new.notify_webhook, "");
+        update_if_not_default!(&mut conf.notify_min_status, new.notify_min_status, 0);
+        update_if_not_default!(&mut conf.notify_format, new.notify_format, notify_format());
+        update_if_not_default!(
+            &mut conf.notify_template,
+            new.notify_template,
+            notify_template()
+        );
+        update_if_not_default!(
+            &mut conf.notify_rate_limit,
+            new.notify_rate_limit,
+            notify_rate_limit()
+        );
+        update_if_not_default!(&mut conf.zap_api_url, new.zap_api_url, "");
+        update_if_not_default!(&mut conf.zap_api_key, new.zap_api_key, "");
+        update_if_not_default!(&mut conf.preflight, new.preflight, preflight());
+        update_if_not_default!(&mut conf.preflight_retries, new.preflight_retries, 0);
+        update_if_not_default!(&mut conf.preflight_report, new.preflight_report, "");
+        update_if_not_default!(
+            &mut conf.export_status,
+            new.export_status,
+            Vec::<u16>::new()
+        );
         update_if_not_default!(&mut conf.scan_limit, new.scan_limit, 0);
+        update_if_not_default!(&mut conf.interleave_targets, new.interleave_targets, false);
         update_if_not_default!(&mut conf.parallel, new.parallel, 0);
         update_if_not_default!(&mut conf.rate_limit, new.rate_limit, 0);
+        update_if_not_default!(&mut conf.inflight_per_dir, new.inflight_per_dir, 0);
         update_if_not_default!(&mut conf.replay_proxy, new.replay_proxy, "");
         update_if_not_default!(&mut conf.debug_log, new.debug_log, "");
         update_if_not_default!(&mut conf.resume_from, new.resume_from, "");
         update_if_not_default!(&mut conf.request_file, new.request_file, "");
+        update_if_not_default!(&mut conf.import_burp, new.import_burp, "");
+        update_if_not_default!(&mut conf.nmap_xml, new.nmap_xml, "");
+        update_if_not_default!(&mut conf.import_crawl, new.import_crawl, "");
         update_if_not_default!(&mut conf.protocol, new.protocol, request_protocol());
+        update_if_not_default!(&mut conf.both_schemes, new.both_schemes, false);
+        update_if_not_default!(&mut conf.smart_schemes, new.smart_schemes, false);
+        update_if_not_default!(&mut conf.no_scheme_probe, new.no_scheme_probe, false);
 
         update_if_not_default!(&mut conf.timeout, new.timeout, timeout());
+        update_if_not_default!(&mut conf.dns_cache_size, new.dns_cache_size, 0);
+        update_if_not_default!(&mut conf.dns_cache_ttl, new.dns_cache_ttl, dns_cache_ttl());
         update_if_not_default!(&mut conf.user_agent, new.user_agent, user_agent());
         update_if_not_default!(
             &mut conf.backup_extensions,
@@ -1240,6 +2410,7 @@ impl Configuration {
         update_if_not_default!(&mut conf.random_agent, new.random_agent, false);
         update_if_not_default!(&mut conf.threads, new.threads, threads());
         update_if_not_default!(&mut conf.depth, new.depth, depth());
+        update_if_not_default!(&mut conf.recurse_threshold, new.recurse_threshold, 0);
         update_if_not_default!(&mut conf.wordlist, new.wordlist, wordlist());
         update_if_not_default!(&mut conf.status_codes, new.status_codes, status_codes());
         // status_codes() is the default for replay_codes, if they're not provided
@@ -1250,14 +2421,29 @@ impl Configuration {
             new.dont_collect,
             ignored_extensions()
         );
+        update_if_not_default!(
+            &mut conf.word_languages,
+            new.word_languages,
+            word_languages()
+        );
     }
 
     /// If present, read in `DEFAULT_CONFIG_NAME` and deserialize the specified values
     ///
-    /// uses serde to deserialize the toml into a `Configuration` struct
+    /// uses serde to deserialize the toml (or json, based on the file's extension) into a
+    /// `Configuration` struct
     pub(super) fn parse_config(config_file: PathBuf) -> Result<Self> {
+        let is_json = config_file
+            .extension()
+            .is_some_and(|ext| ext.eq_ignore_ascii_case("json"));
+
         let content = read_to_string(config_file)?;
-        let mut config: Self = toml::from_str(content.as_str())?;
+
+        let mut config: Self = if is_json {
+            serde_json::from_str(content.as_str())?
+        } else {
+            toml::from_str(content.as_str())?
+        };
 
         if !config.extensions.is_empty() {
             // remove leading periods, if any are found
@@ -0,0 +1,167 @@
+//! reading the jsonl output of a crawler that already ran against the target (katana -jsonl,
+//! gospider --json), see --import-crawl
+use std::collections::HashSet;
+
+use anyhow::{bail, Context, Result};
+use reqwest::StatusCode;
+use serde_json::Value;
+
+use super::Configuration;
+use crate::utils::parse_url_with_raw_path;
+
+/// gospider output types that aren't urls on the crawled site
+const SKIPPED_GOSPIDER_TYPES: [&str; 2] = ["subdomains", "aws-s3"];
+
+/// read the crawl at --import-crawl into Configuration.crawled_urls
+///
+/// lines that aren't json, aren't from a known crawler, or whose response was a 404/410 are
+/// skipped; which of the remaining urls belong to a target is decided once targets are known
+pub fn import_crawl(config: &mut Configuration) -> Result<()> {
+    log::trace!("enter: import_crawl({})", config.import_crawl);
+
+    let contents = std::fs::read_to_string(&config.import_crawl)
+        .with_context(|| format!("Could not read {}", config.import_crawl))?;
+
+    let mut seen = HashSet::new();
+
+    for line in contents.lines() {
+        let Some(url) = parse_crawl_line(line) else {
+            continue;
+        };
+
+        if seen.insert(url.clone()) {
+            config.crawled_urls.push(url);
+        }
+    }
+
+    if config.crawled_urls.is_empty() {
+        bail!(
+            "Did not find any katana or gospider results in {}",
+            config.import_crawl
+        );
+    }
+
+    log::info!(
+        "imported {} crawled url(s) from {}",
+        config.crawled_urls.len(),
+        config.import_crawl
+    );
+
+    log::trace!("exit: import_crawl");
+    Ok(())
+}
+
+/// the url from a single line of crawler output, unless the crawler saw it no longer exists
+///
+/// - katana: `{"request": {"endpoint": ...}, "response": {"status_code": ...}}`, older releases
+///   put `endpoint` at the top level
+/// - gospider: `{"type": "url", "output": ..., "status": ...}`
+pub(super) fn parse_crawl_line(line: &str) -> Option<String> {
+    let entry: Value = serde_json::from_str(line.trim()).ok()?;
+
+    let endpoint = entry
+        .pointer("/request/endpoint")
+        .or_else(|| entry.get("endpoint"));
+
+    let (url, status) = match endpoint {
+        Some(endpoint) => (endpoint.as_str()?, entry.pointer("/response/status_code")),
+        None => {
+            let kind = entry.get("type").and_then(Value::as_str).unwrap_or("url");
+
+            if SKIPPED_GOSPIDER_TYPES.contains(&kind) {
+                return None;
+            }
+
+            (entry.get("output")?.as_str()?, entry.get("status"))
+        }
+    };
+
+    let gone = status.and_then(Value::as_u64).is_some_and(|status| {
+        status == u64::from(StatusCode::NOT_FOUND.as_u16())
+            || status == u64::from(StatusCode::GONE.as_u16())
+    });
+
+    if gone {
+        return None;
+    }
+
+    let url = parse_url_with_raw_path(url.trim()).ok()?;
+
+    if !matches!(url.scheme(), "http" | "https") {
+        return None;
+    }
+
+    Some(url.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+    use tempfile::NamedTempFile;
+
+    #[test]
+    /// both katana formats and gospider's are understood; missing and non-url entries aren't
+    fn parse_crawl_line_reads_katana_and_gospider() {
+        let katana = r#"{"timestamp":"2024-01-01T00:00:00Z","request":{"method":"GET","endpoint":"https://example.com/js/app.js","tag":"script","attribute":"src"},"response":{"status_code":200}}"#;
+        let old_katana = r#"{"timestamp":"2024-01-01T00:00:00Z","endpoint":"https://example.com/about","source":"https://example.com/"}"#;
+        let gospider = r#"{"input":"https://example.com","source":"body","type":"url","output":"https://example.com/login","status":200,"length":512}"#;
+        let missing =
+            r#"{"request":{"endpoint":"https://example.com/old"},"response":{"status_code":404}}"#;
+        let subdomain = r#"{"input":"https://example.com","source":"body","type":"subdomains","output":"https://api.example.com","status":0}"#;
+
+        assert_eq!(
+            parse_crawl_line(katana).as_deref(),
+            Some("https://example.com/js/app.js")
+        );
+        assert_eq!(
+            parse_crawl_line(old_katana).as_deref(),
+            Some("https://example.com/about")
+        );
+        assert_eq!(
+            parse_crawl_line(gospider).as_deref(),
+            Some("https://example.com/login")
+        );
+        assert_eq!(parse_crawl_line(missing), None);
+        assert_eq!(parse_crawl_line(subdomain), None);
+        assert_eq!(parse_crawl_line("[INFO] crawling"), None);
+        assert_eq!(parse_crawl_line(r#"{"output":"/relative"}"#), None);
+    }
+
+    #[test]
+    /// duplicate urls are imported once, and a crawl without any results is an error
+    fn import_crawl_dedupes_and_rejects_empty_crawls() {
+        let mut file = NamedTempFile::new().unwrap();
+        writeln!(file, r#"{{"endpoint":"https://example.com/a"}}"#).unwrap();
+        writeln!(file, r#"{{"type":"url","output":"https://example.com/a"}}"#).unwrap();
+        writeln!(
+            file,
+            r#"{{"type":"form","output":"https://example.com/b/submit"}}"#
+        )
+        .unwrap();
+
+        let mut config = Configuration {
+            import_crawl: file.path().to_string_lossy().to_string(),
+            ..Default::default()
+        };
+
+        import_crawl(&mut config).unwrap();
+
+        assert_eq!(
+            config.crawled_urls,
+            vec![
+                String::from("https://example.com/a"),
+                String::from("https://example.com/b/submit")
+            ]
+        );
+
+        let empty = NamedTempFile::new().unwrap();
+
+        let mut config = Configuration {
+            import_crawl: empty.path().to_string_lossy().to_string(),
+            ..Default::default()
+        };
+
+        assert!(import_crawl(&mut config).is_err());
+    }
+}
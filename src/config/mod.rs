@@ -1,9 +1,17 @@
 //! all logic related to instantiating a running configuration
 
+mod burp;
+mod conditional;
 mod container;
+mod crawl;
+mod nmap;
 mod utils;
 #[cfg(test)]
 mod tests;
 
+pub use self::conditional::Validators;
 pub use self::container::Configuration;
-pub use self::utils::{determine_output_level, OutputLevel, RequesterPolicy};
+pub use self::utils::{
+    determine_output_level, parse_denylist, split_target_output_level, Denier, OutputLevel,
+    RequesterPolicy,
+};
@@ -0,0 +1,321 @@
+//! turning the open web services of an nmap scan (-oX) into scan targets, see --nmap-xml
+use std::collections::BTreeSet;
+
+use anyhow::{bail, Context, Result};
+use lazy_static::lazy_static;
+use regex::Regex;
+
+use super::Configuration;
+use crate::{utils::parse_url_with_raw_path, xml::attribute};
+
+lazy_static! {
+    /// names from certificates in ssl-cert script output, i.e. commonName=example.com and
+    /// DNS:www.example.com
+    static ref CERT_NAME: Regex = Regex::new(r"(?:commonName=|DNS:)([A-Za-z0-9.-]+)").unwrap();
+
+    /// where http-title saw a redirect go, i.e. Did not follow redirect to https://example.com/
+    static ref REDIRECT_URL: Regex = Regex::new(r"redirect to (\S+)").unwrap();
+}
+
+/// read the nmap xml at --nmap-xml and add a target for each open http(s) service
+///
+/// each service becomes one target per hostname known for its host (from nmap's hostnames and
+/// the port's ssl-cert / http-title script results), or a single target using the host's address
+/// when no hostnames are known
+pub fn import_nmap(config: &mut Configuration) -> Result<()> {
+    log::trace!("enter: import_nmap({})", config.nmap_xml);
+
+    let contents = std::fs::read_to_string(&config.nmap_xml)
+        .with_context(|| format!("Could not read {}", config.nmap_xml))?;
+
+    let targets = parse_nmap_targets(&contents)?;
+
+    if targets.is_empty() {
+        bail!(
+            "Did not find any open http(s) services in {}",
+            config.nmap_xml
+        );
+    }
+
+    config.imported_targets = targets.into_iter().collect();
+
+    if config.imported_targets.len() == 1 {
+        config.target_url = config.imported_targets[0].clone();
+    }
+
+    log::info!(
+        "imported {} target(s) from {}",
+        config.imported_targets.len(),
+        config.nmap_xml
+    );
+
+    log::trace!("exit: import_nmap");
+    Ok(())
+}
+
+/// targets for every open http(s) service in the given nmap xml, sorted and deduplicated
+pub(super) fn parse_nmap_targets(xml: &str) -> Result<BTreeSet<String>> {
+    if !xml.contains("<nmaprun") {
+        bail!("Expected nmap xml output, i.e. <nmaprun scanner=\"nmap\" ...>");
+    }
+
+    let mut targets = BTreeSet::new();
+
+    for (_, host) in elements(xml, "host") {
+        let address = elements(host, "address")
+            .into_iter()
+            .find_map(
+                |(attributes, _)| match attribute(attributes, "addrtype").as_deref() {
+                    Some("ipv4") => attribute(attributes, "addr"),
+                    Some("ipv6") => attribute(attributes, "addr").map(|addr| format!("[{addr}]")),
+                    _ => None,
+                },
+            );
+
+        let Some(address) = address else {
+            continue;
+        };
+
+        let hostnames: BTreeSet<_> = elements(host, "hostname")
+            .into_iter()
+            .filter_map(|(attributes, _)| attribute(attributes, "name"))
+            .collect();
+
+        for (attributes, port) in elements(host, "port") {
+            if attribute(attributes, "protocol").as_deref() != Some("tcp") {
+                continue;
+            }
+
+            let open = elements(port, "state")
+                .into_iter()
+                .any(|(attributes, _)| attribute(attributes, "state").as_deref() == Some("open"));
+
+            if !open {
+                continue;
+            }
+
+            let Some(number) =
+                attribute(attributes, "portid").and_then(|id| id.parse::<u16>().ok())
+            else {
+                continue;
+            };
+
+            let Some(scheme) = web_scheme(port) else {
+                continue;
+            };
+
+            let mut names = hostnames.clone();
+            names.extend(script_hostnames(port));
+
+            if names.is_empty() {
+                names.insert(address.clone());
+            }
+
+            let default_port =
+                (scheme == "http" && number == 80) || (scheme == "https" && number == 443);
+
+            for name in names {
+                if default_port {
+                    targets.insert(format!("{scheme}://{name}"));
+                } else {
+                    targets.insert(format!("{scheme}://{name}:{number}"));
+                }
+            }
+        }
+    }
+
+    Ok(targets)
+}
+
+/// http or https, depending on the port's <service>; None when it isn't a web service
+fn web_scheme(port: &str) -> Option<&'static str> {
+    let (attributes, _) = elements(port, "service").into_iter().next()?;
+
+    let name = attribute(attributes, "name")?;
+
+    // i.e. http, https, http-alt, http-proxy, https-alt; http-rpc-epmap is windows rpc
+    if !name.starts_with("http") || name == "http-rpc-epmap" {
+        return None;
+    }
+
+    if name.starts_with("https") || attribute(attributes, "tunnel").as_deref() == Some("ssl") {
+        Some("https")
+    } else {
+        Some("http")
+    }
+}
+
+/// hostnames found by the port's ssl-cert and http-title scripts; wildcard certificate names
+/// can't be scanned and are skipped
+fn script_hostnames(port: &str) -> BTreeSet<String> {
+    let mut names = BTreeSet::new();
+
+    for (attributes, _) in elements(port, "script") {
+        let output = attribute(attributes, "output").unwrap_or_default();
+
+        match attribute(attributes, "id").as_deref() {
+            Some("ssl-cert") => {
+                for captures in CERT_NAME.captures_iter(&output) {
+                    names.insert(captures[1].to_lowercase());
+                }
+            }
+            Some("http-title") => {
+                let host = REDIRECT_URL
+                    .captures(&output)
+                    .and_then(|captures| parse_url_with_raw_path(&captures[1]).ok())
+                    .and_then(|url| url.host_str().map(String::from));
+
+                if let Some(host) = host {
+                    names.insert(host.to_lowercase());
+                }
+            }
+            _ => {}
+        }
+    }
+
+    // an ip address in a cert or redirect adds nothing over the host's address
+    names.retain(|name| !name.starts_with('*') && name.parse::<std::net::IpAddr>().is_err());
+    names
+}
+
+/// attributes and raw content of each <name> element within the given xml, in order;
+/// self-closing elements have no content, and elements of the same name aren't expected to nest
+fn elements<'a>(xml: &'a str, name: &str) -> Vec<(&'a str, &'a str)> {
+    let open = format!("<{name}");
+    let close = format!("</{name}>");
+
+    let mut found = Vec::new();
+    let mut rest = xml;
+
+    while let Some(start) = rest.find(&open) {
+        let after_name = &rest[start + open.len()..];
+
+        // skip over elements whose name only starts with `name`, i.e. <hostnames>
+        if !after_name.starts_with(|c: char| c == '>' || c == '/' || c.is_whitespace()) {
+            rest = after_name;
+            continue;
+        }
+
+        let Some(tag_end) = after_name.find('>') else {
+            break;
+        };
+
+        let attributes = &after_name[..tag_end];
+        rest = &after_name[tag_end + 1..];
+
+        if attributes.ends_with('/') {
+            found.push((attributes.trim_end_matches('/').trim(), ""));
+            continue;
+        }
+
+        match rest.find(&close) {
+            Some(end) => {
+                found.push((attributes.trim(), &rest[..end]));
+                rest = &rest[end + close.len()..];
+            }
+            None => {
+                found.push((attributes.trim(), rest));
+                break;
+            }
+        }
+    }
+
+    found
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+    use tempfile::NamedTempFile;
+
+    /// a scan with three hosts: one with a hostname, a web server on a high port, a closed
+    /// port, and ssh; one known only by address; and one that's down
+    const SCAN: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<!DOCTYPE nmaprun>
+<nmaprun scanner="nmap" args="nmap -sV -sC -oX scan.xml 10.0.0.0/24" start="1711972800" version="7.94">
+<host starttime="1711972800" endtime="1711972900"><status state="up" reason="syn-ack"/>
+<address addr="10.0.0.5" addrtype="ipv4"/>
+<address addr="00:11:22:33:44:55" addrtype="mac"/>
+<hostnames>
+<hostname name="intranet.corp.local" type="PTR"/>
+</hostnames>
+<ports><extraports state="closed" count="995"/>
+<port protocol="tcp" portid="22"><state state="open" reason="syn-ack"/><service name="ssh" product="OpenSSH"/></port>
+<port protocol="tcp" portid="80"><state state="open" reason="syn-ack"/><service name="http" product="nginx"/><script id="http-title" output="Did not follow redirect to https://portal.corp.local/"/></port>
+<port protocol="tcp" portid="443"><state state="open" reason="syn-ack"/><service name="http" tunnel="ssl" product="nginx"/><script id="ssl-cert" output="Subject: commonName=portal.corp.local&#xa;Subject Alternative Name: DNS:portal.corp.local, DNS:*.corp.local, IP Address:10.0.0.5&#xa;Not valid before: 2024-01-01T00:00:00"><table key="subject"><elem key="commonName">portal.corp.local</elem></table></script></port>
+<port protocol="tcp" portid="8443"><state state="closed" reason="reset"/><service name="https-alt"/></port>
+<port protocol="tcp" portid="9090"><state state="open" reason="syn-ack"/><service name="http-proxy"/></port>
+</ports>
+</host>
+<host><status state="up" reason="echo-reply"/>
+<address addr="10.0.0.7" addrtype="ipv4"/>
+<hostnames/>
+<ports>
+<port protocol="tcp" portid="8443"><state state="open" reason="syn-ack"/><service name="https-alt"/></port>
+<port protocol="udp" portid="80"><state state="open" reason="udp-response"/><service name="http"/></port>
+<port protocol="tcp" portid="135"><state state="open" reason="syn-ack"/><service name="msrpc"/></port>
+<port protocol="tcp" portid="593"><state state="open" reason="syn-ack"/><service name="http-rpc-epmap"/></port>
+</ports>
+</host>
+<host><status state="down" reason="no-response"/>
+<address addr="10.0.0.9" addrtype="ipv4"/>
+</host>
+<runstats><finished time="1711972900"/><hosts up="2" down="1" total="3"/></runstats>
+</nmaprun>
+"#;
+
+    #[test]
+    /// open http(s) services become targets, named by every hostname known for them
+    fn parse_nmap_targets_finds_web_services() {
+        let targets: Vec<_> = parse_nmap_targets(SCAN).unwrap().into_iter().collect();
+
+        assert_eq!(
+            targets,
+            vec![
+                "http://intranet.corp.local",
+                "http://intranet.corp.local:9090",
+                "http://portal.corp.local",
+                "https://10.0.0.7:8443",
+                "https://intranet.corp.local",
+                "https://portal.corp.local",
+            ]
+        );
+
+        assert!(parse_nmap_targets("<items></items>").is_err());
+
+        // single-quoted attributes are read the same as double-quoted ones
+        let targets: Vec<_> = parse_nmap_targets(
+            "<nmaprun><host><address addr='10.0.0.9' addrtype='ipv4'/><ports><port protocol='tcp' portid='80'><state state='open'/><service name='http'/></port></ports></host></nmaprun>",
+        )
+        .unwrap()
+        .into_iter()
+        .collect();
+
+        assert_eq!(targets, ["http://10.0.0.9"]);
+    }
+
+    #[test]
+    /// a single target is used as the target url
+    fn import_nmap_sets_single_target() {
+        let mut file = NamedTempFile::new().unwrap();
+        write!(
+            file,
+            r#"<nmaprun scanner="nmap"><host><address addr="::1" addrtype="ipv6"/><ports><port protocol="tcp" portid="8000"><state state="open"/><service name="http-alt"/></port></ports></host></nmaprun>"#
+        )
+        .unwrap();
+
+        let mut config = Configuration {
+            nmap_xml: file.path().to_string_lossy().to_string(),
+            ..Default::default()
+        };
+
+        import_nmap(&mut config).unwrap();
+
+        assert_eq!(
+            config.imported_targets,
+            vec![String::from("http://[::1]:8000")]
+        );
+        assert_eq!(config.target_url, "http://[::1]:8000");
+    }
+}
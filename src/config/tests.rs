@@ -1,6 +1,6 @@
 use super::utils::*;
 use super::*;
-use crate::{traits::FeroxSerialize, DEFAULT_CONFIG_NAME};
+use crate::{traits::FeroxSerialize, DEFAULT_CONFIG_NAME, DEFAULT_JSON_CONFIG_NAME};
 use regex::Regex;
 use reqwest::Url;
 use std::{collections::HashMap, fs::write};
@@ -14,7 +14,10 @@ fn setup_config_test() -> Configuration {
             replay_codes = [201, 301]
             threads = 40
             timeout = 5
+            dns_cache_size = 512
+            dns_cache_ttl = 60
             proxy = "http://127.0.0.1:8080"
+            proxy_check = true
             replay_proxy = "http://127.0.0.1:8081"
             quiet = true
             silent = true
@@ -22,37 +25,109 @@ fn setup_config_test() -> Configuration {
             auto_bail = true
             verbosity = 1
             scan_limit = 6
+            interleave_targets = true
             parallel = 14
             rate_limit = 250
+            inflight_per_dir = 4
             time_limit = "10m"
             output = "/some/otherpath"
+            output_dir = "/some/otherdir"
             debug_log = "/yet/anotherpath"
             resume_from = "/some/state/file"
             redirects = true
             insecure = true
+            tls_backend = "rustls"
             collect_backups = true
             collect_extensions = true
             collect_words = true
+            word_languages = ["en", "ru"]
+            transliterate = true
+            collected_word_min_len = 3
+            collected_word_max_len = 24
+            collected_word_charset = "alpha"
+            collected_word_limit = 200
+            save_collected_words = "/some/words"
             extensions = ["html", "php", "js"]
+            extension_wordlists = {asp = "/some/asp/words"}
+            adaptive_extensions = 25
             dont_collect = ["png", "gif", "jpg", "jpeg"]
             methods = ["GET", "PUT", "DELETE"]
             data = [31, 32, 33, 34]
             url_denylist = ["http://dont-scan.me", "https://also-not.me"]
             regex_denylist = ["/deny.*"]
+            dont_scan_file = "/some/deny.txt"
             headers = {stuff = "things", mostuff = "mothings"}
+            interstitial_bypass = {Cookie = "consent=yes"}
             queries = [["name","value"], ["rick", "astley"]]
             no_recursion = true
             add_slash = true
+            auto_slash = true
+            case_insensitive = true
+            retry_truncated = true
             stdin = true
             dont_filter = true
+            auto_resume = true
             extract_links = false
+            extract_priority = "low"
             json = true
+            sarif = true
+            csv = true
+            csv_columns = ["url", "status"]
+            json_include_body = 4096
+            banner_json = "/some/banner.json"
+            output_format = "{{status}} {{url}}"
             save_state = false
             depth = 1
+            recurse_threshold = 3
             limit_bars = 3
+            bar_refresh_hz = 5
+            no_bars = true
+            ascii = true
+            tree = true
+            error_events = true
+            skip_events = true
+            detect_language = true
+            tag_findings = true
+            tag_rules = ["secret=(?i)api[_-]?key"]
+            flush_every = 25
+            max_dir_requests = 5000
             protocol = "http"
+            both_schemes = true
+            smart_schemes = true
+            no_scheme_probe = true
             request_file = "/some/request/file"
+            update_from = "/some/release/archive.tar.gz"
             scan_dir_listings = true
+            scan_discovered_hosts = true
+            spa_mode = true
+            survey_headers = true
+            recon_banner = true
+            known_urls = "/some/known/urls"
+            changed_since = "/some/previous/run.json"
+            openapi = "/some/openapi.json"
+            graphql = true
+            fetch_wordlist = "raft-small-words"
+            archive_seed = ["wayback", "commoncrawl"]
+            export_urls = "/some/export/file"
+            export_status = [200, 301]
+            export_format = "ffuf"
+            nuclei_targets = "/some/nuclei/targets"
+            report_html = "/some/report.html"
+            report_markdown = "/some/report.md"
+            report_xml = "/some/report.xml"
+            emit_ffuf_json = "/some/ffuf.json"
+            campaign = "/some/campaign.csv"
+            notify_webhook = "https://hooks.example.com/ferox"
+            notify_min_status = 200
+            notify_format = "discord"
+            notify_template = "{url} ({status})"
+            notify_rate_limit = 5
+            zap_api_url = "http://127.0.0.1:8080"
+            zap_api_key = "changeme"
+            meta = {engagement = "1337"}
+            preflight = "strict"
+            preflight_retries = 2
+            preflight_report = "/some/preflight/report"
             force_recursion = true
             filter_size = [4120]
             filter_regex = ["^ignore me$"]
@@ -60,6 +135,7 @@ fn setup_config_test() -> Configuration {
             filter_word_count = [994, 992]
             filter_line_count = [34]
             filter_status = [201]
+            status_groups = {interesting = [200, 301, 403]}
             server_certs = ["/some/cert.pem", "/some/other/cert.pem"]
             client_cert = "/some/client/cert.pem"
             client_key = "/some/client/key.pem"
@@ -71,52 +147,148 @@ fn setup_config_test() -> Configuration {
     Configuration::parse_config(file).unwrap()
 }
 
+#[test]
+/// a json config file is deserialized with the same model as a toml one
+fn config_reads_json_config_file() {
+    let data = r#"{"threads": 40, "extensions": [".php", "html"], "headers": {"stuff": "things"}}"#;
+    let tmp_dir = TempDir::new().unwrap();
+    let file = tmp_dir.path().join(DEFAULT_JSON_CONFIG_NAME);
+    write(&file, data).unwrap();
+
+    let config = Configuration::parse_config(file).unwrap();
+
+    assert_eq!(config.threads, 40);
+    assert_eq!(config.extensions, vec!["php", "html"]);
+    assert_eq!(config.headers.get("stuff").unwrap(), "things");
+    // values not present in the file keep their defaults
+    assert_eq!(config.depth, depth());
+}
+
 #[test]
 /// test that all default config values meet expectations
 fn default_configuration() {
     let config = Configuration::default();
     assert_eq!(config.wordlist, wordlist());
     assert_eq!(config.proxy, String::new());
+    assert!(!config.proxy_check);
     assert_eq!(config.target_url, String::new());
     assert_eq!(config.time_limit, String::new());
     assert_eq!(config.resume_from, String::new());
     assert_eq!(config.debug_log, String::new());
     assert_eq!(config.config, String::new());
+    assert!(config.config_files.is_empty());
     assert_eq!(config.replay_proxy, String::new());
     assert_eq!(config.status_codes, status_codes());
     assert_eq!(config.replay_codes, config.status_codes);
     assert!(config.replay_client.is_none());
+    assert!(config.interstitial_client.is_none());
     assert_eq!(config.threads, threads());
     assert_eq!(config.depth, depth());
+    assert_eq!(config.recurse_threshold, 0);
     assert_eq!(config.timeout, timeout());
+    assert_eq!(config.dns_cache_size, 0);
+    assert_eq!(config.dns_cache_ttl, dns_cache_ttl());
     assert_eq!(config.verbosity, 0);
     assert_eq!(config.scan_limit, 0);
+    assert!(!config.interleave_targets);
+    assert!(!config.self_test);
+    assert_eq!(config.nmap_xml, String::new());
+    assert_eq!(config.inflight_per_dir, 0);
     assert_eq!(config.limit_bars, 0);
+    assert_eq!(config.bar_refresh_hz, bar_refresh_hz());
+    assert!(!config.no_bars);
+    assert!(!config.ascii);
+    assert!(!config.tree);
+    assert!(!config.error_events);
+    assert!(!config.skip_events);
+    assert!(!config.detect_language);
+    assert!(!config.tag_findings);
+    assert!(config.tag_rules.is_empty());
+    assert_eq!(config.flush_every, 1);
+    assert_eq!(config.max_dir_requests, max_dir_requests());
     assert!(!config.silent);
     assert!(!config.quiet);
     assert_eq!(config.output_level, OutputLevel::Default);
+    assert_eq!(config.output_upload, String::new());
+    assert_eq!(config.output_dir, String::new());
     assert!(!config.dont_filter);
+    assert!(!config.auto_resume);
     assert!(!config.auto_tune);
     assert!(!config.auto_bail);
     assert_eq!(config.requester_policy, RequesterPolicy::Default);
     assert!(!config.no_recursion);
     assert!(!config.random_agent);
     assert!(!config.json);
+    assert!(!config.sarif);
+    assert!(!config.csv);
+    assert_eq!(config.csv_columns, csv_columns());
+    assert_eq!(config.json_include_body, 0);
+    assert_eq!(config.banner_json, String::new());
+    assert_eq!(config.output_format, String::new());
     assert!(config.save_state);
     assert!(!config.stdin);
     assert!(!config.add_slash);
+    assert!(!config.auto_slash);
+    assert!(!config.case_insensitive);
+    assert!(!config.retry_truncated);
     assert!(!config.force_recursion);
     assert!(!config.redirects);
     assert!(config.extract_links);
+    assert_eq!(config.extract_priority, "normal");
     assert!(!config.insecure);
+    assert_eq!(config.tls_backend, TlsBackend::default());
     assert!(!config.collect_extensions);
     assert!(!config.collect_backups);
     assert!(!config.collect_words);
+    assert_eq!(config.word_languages, word_languages());
+    assert!(!config.transliterate);
+    assert_eq!(config.collected_word_min_len, 0);
+    assert_eq!(config.collected_word_max_len, 0);
+    assert_eq!(config.collected_word_charset, collected_word_charset());
+    assert_eq!(config.collected_word_limit, 0);
+    assert_eq!(config.save_collected_words, String::new());
     assert!(!config.scan_dir_listings);
+    assert!(!config.scan_discovered_hosts);
+    assert!(!config.spa_mode);
+    assert!(!config.survey_headers);
+    assert!(!config.recon_banner);
+    assert_eq!(config.known_urls, String::new());
+    assert_eq!(config.changed_since, String::new());
+    assert_eq!(config.openapi, String::new());
+    assert!(!config.graphql);
+    assert!(config.validators.is_empty());
+    assert_eq!(config.fetch_wordlist, String::new());
+    assert!(config.archive_seed.is_empty());
+    assert_eq!(config.export_urls, String::new());
+    assert_eq!(config.nuclei_targets, String::new());
+    assert_eq!(config.report_html, String::new());
+    assert_eq!(config.report_markdown, String::new());
+    assert_eq!(config.report_xml, String::new());
+    assert_eq!(config.emit_ffuf_json, String::new());
+    assert_eq!(config.campaign, String::new());
+    assert_eq!(config.notify_webhook, String::new());
+    assert_eq!(config.zap_api_url, String::new());
+    assert_eq!(config.zap_api_key, String::new());
+    assert_eq!(config.notify_min_status, 0);
+    assert_eq!(config.notify_format, "generic");
+    assert_eq!(
+        config.notify_template,
+        "{status} {method} {url} ({lines}l {words}w {chars}c)"
+    );
+    assert_eq!(config.notify_rate_limit, 20);
+    assert_eq!(config.export_status, Vec::<u16>::new());
+    assert_eq!(config.export_format, "plain");
+    assert_eq!(config.meta, HashMap::new());
+    assert_eq!(config.preflight, "warn");
+    assert_eq!(config.preflight_retries, 0);
+    assert_eq!(config.preflight_report, String::new());
     assert!(config.regex_denylist.is_empty());
+    assert_eq!(config.dont_scan_file, String::new());
     assert_eq!(config.queries, Vec::new());
     assert_eq!(config.filter_size, Vec::<u64>::new());
     assert_eq!(config.extensions, Vec::<String>::new());
+    assert!(config.extension_wordlists.is_empty());
+    assert_eq!(config.adaptive_extensions, 0);
     assert_eq!(config.methods, vec!["GET"]);
     assert_eq!(config.data, Vec::<u8>::new());
     assert_eq!(config.url_denylist, Vec::<Url>::new());
@@ -126,13 +298,22 @@ fn default_configuration() {
     assert_eq!(config.filter_word_count, Vec::<usize>::new());
     assert_eq!(config.filter_line_count, Vec::<usize>::new());
     assert_eq!(config.filter_status, Vec::<u16>::new());
+    assert_eq!(config.status_groups, HashMap::new());
     assert_eq!(config.headers, HashMap::new());
+    assert_eq!(config.interstitial_bypass, HashMap::new());
     assert_eq!(config.server_certs, Vec::<String>::new());
     assert_eq!(config.client_cert, String::new());
     assert_eq!(config.client_key, String::new());
     assert_eq!(config.backup_extensions, backup_extensions());
     assert_eq!(config.protocol, request_protocol());
+    assert!(!config.both_schemes);
+    assert!(!config.smart_schemes);
+    assert!(!config.no_scheme_probe);
     assert_eq!(config.request_file, String::new());
+    assert_eq!(config.import_burp, String::new());
+    assert!(config.imported_targets.is_empty());
+    assert!(config.imported_urls.is_empty());
+    assert_eq!(config.update_from, String::new());
 }
 
 #[test]
@@ -177,6 +358,13 @@ fn config_reads_depth() {
     assert_eq!(config.depth, 1);
 }
 
+#[test]
+/// parse the test config and see that the value parsed is correct
+fn config_reads_recurse_threshold() {
+    let config = setup_config_test();
+    assert_eq!(config.recurse_threshold, 3);
+}
+
 #[test]
 /// parse the test config and see that the value parsed is correct
 fn config_reads_scan_limit() {
@@ -184,6 +372,13 @@ fn config_reads_scan_limit() {
     assert_eq!(config.scan_limit, 6);
 }
 
+#[test]
+/// parse the test config and see that the value parsed is correct
+fn config_reads_interleave_targets() {
+    let config = setup_config_test();
+    assert!(config.interleave_targets);
+}
+
 #[test]
 /// parse the test config and see that the value parsed is correct
 fn config_reads_parallel() {
@@ -198,6 +393,13 @@ fn config_reads_rate_limit() {
     assert_eq!(config.rate_limit, 250);
 }
 
+#[test]
+/// parse the test config and see that the value parsed is correct
+fn config_reads_inflight_per_dir() {
+    let config = setup_config_test();
+    assert_eq!(config.inflight_per_dir, 4);
+}
+
 #[test]
 /// parse the test config and see that the value parsed is correct
 fn config_reads_timeout() {
@@ -205,6 +407,14 @@ fn config_reads_timeout() {
     assert_eq!(config.timeout, 5);
 }
 
+#[test]
+/// parse the test config and see that the values parsed are correct
+fn config_reads_dns_cache() {
+    let config = setup_config_test();
+    assert_eq!(config.dns_cache_size, 512);
+    assert_eq!(config.dns_cache_ttl, 60);
+}
+
 #[test]
 /// parse the test config and see that the value parsed is correct
 fn config_reads_proxy() {
@@ -212,6 +422,13 @@ fn config_reads_proxy() {
     assert_eq!(config.proxy, "http://127.0.0.1:8080");
 }
 
+#[test]
+/// parse the test config and see that the value parsed is correct
+fn config_reads_proxy_check() {
+    let config = setup_config_test();
+    assert!(config.proxy_check);
+}
+
 #[test]
 /// parse the test config and see that the value parsed is correct
 fn config_reads_replay_proxy() {
@@ -247,6 +464,48 @@ fn config_reads_json() {
     assert!(config.json);
 }
 
+#[test]
+/// parse the test config and see that the value parsed is correct
+fn config_reads_sarif() {
+    let config = setup_config_test();
+    assert!(config.sarif);
+}
+
+#[test]
+/// parse the test config and see that the value parsed is correct
+fn config_reads_csv() {
+    let config = setup_config_test();
+    assert!(config.csv);
+}
+
+#[test]
+/// parse the test config and see that the value parsed is correct
+fn config_reads_csv_columns() {
+    let config = setup_config_test();
+    assert_eq!(config.csv_columns, vec!["url", "status"]);
+}
+
+#[test]
+/// parse the test config and see that the value parsed is correct
+fn config_reads_json_include_body() {
+    let config = setup_config_test();
+    assert_eq!(config.json_include_body, 4096);
+}
+
+#[test]
+/// parse the test config and see that the value parsed is correct
+fn config_reads_banner_json() {
+    let config = setup_config_test();
+    assert_eq!(config.banner_json, "/some/banner.json");
+}
+
+#[test]
+/// parse the test config and see that the value parsed is correct
+fn config_reads_output_format() {
+    let config = setup_config_test();
+    assert_eq!(config.output_format, "{{status}} {{url}}");
+}
+
 #[test]
 /// parse the test config and see that the value parsed is correct
 fn config_reads_auto_bail() {
@@ -275,6 +534,83 @@ fn config_reads_limit_bars() {
     assert_eq!(config.limit_bars, 3);
 }
 
+#[test]
+/// parse the test config and see that the value parsed is correct
+fn config_reads_bar_refresh_hz() {
+    let config = setup_config_test();
+    assert_eq!(config.bar_refresh_hz, 5);
+}
+
+#[test]
+/// parse the test config and see that the value parsed is correct
+fn config_reads_no_bars() {
+    let config = setup_config_test();
+    assert!(config.no_bars);
+}
+
+#[test]
+/// parse the test config and see that the value parsed is correct
+fn config_reads_ascii() {
+    let config = setup_config_test();
+    assert!(config.ascii);
+}
+
+#[test]
+/// parse the test config and see that the value parsed is correct
+fn config_reads_tree() {
+    let config = setup_config_test();
+    assert!(config.tree);
+}
+
+#[test]
+/// parse the test config and see that the value parsed is correct
+fn config_reads_error_events() {
+    let config = setup_config_test();
+    assert!(config.error_events);
+}
+
+#[test]
+/// parse the test config and see that the value parsed is correct
+fn config_reads_skip_events() {
+    let config = setup_config_test();
+    assert!(config.skip_events);
+}
+
+#[test]
+/// parse the test config and see that the value parsed is correct
+fn config_reads_detect_language() {
+    let config = setup_config_test();
+    assert!(config.detect_language);
+}
+
+#[test]
+/// parse the test config and see that the value parsed is correct
+fn config_reads_tag_findings() {
+    let config = setup_config_test();
+    assert!(config.tag_findings);
+}
+
+#[test]
+/// parse the test config and see that the value parsed is correct
+fn config_reads_tag_rules() {
+    let config = setup_config_test();
+    assert_eq!(config.tag_rules, vec!["secret=(?i)api[_-]?key"]);
+}
+
+#[test]
+/// parse the test config and see that the value parsed is correct
+fn config_reads_flush_every() {
+    let config = setup_config_test();
+    assert_eq!(config.flush_every, 25);
+}
+
+#[test]
+/// parse the test config and see that the value parsed is correct
+fn config_reads_max_dir_requests() {
+    let config = setup_config_test();
+    assert_eq!(config.max_dir_requests, 5000);
+}
+
 #[test]
 /// parse the test config and see that the value parsed is correct
 fn config_reads_output() {
@@ -282,6 +618,13 @@ fn config_reads_output() {
     assert_eq!(config.output, "/some/otherpath");
 }
 
+#[test]
+/// parse the test config and see that the value parsed is correct
+fn config_reads_output_dir() {
+    let config = setup_config_test();
+    assert_eq!(config.output_dir, "/some/otherdir");
+}
+
 #[test]
 /// parse the test config and see that the value parsed is correct
 fn config_reads_redirects() {
@@ -296,6 +639,13 @@ fn config_reads_insecure() {
     assert!(config.insecure);
 }
 
+#[test]
+/// parse the test config and see that the value parsed is correct
+fn config_reads_tls_backend() {
+    let config = setup_config_test();
+    assert_eq!(config.tls_backend, TlsBackend::Rustls);
+}
+
 #[test]
 /// parse the test config and see that the value parsed is correct
 fn config_reads_no_recursion() {
@@ -317,6 +667,13 @@ fn config_reads_dont_filter() {
     assert!(config.dont_filter);
 }
 
+#[test]
+/// parse the test config and see that the value parsed is correct
+fn config_reads_auto_resume() {
+    let config = setup_config_test();
+    assert!(config.auto_resume);
+}
+
 #[test]
 /// parse the test config and see that the value parsed is correct
 fn config_reads_add_slash() {
@@ -324,6 +681,27 @@ fn config_reads_add_slash() {
     assert!(config.add_slash);
 }
 
+#[test]
+/// parse the test config and see that the value parsed is correct
+fn config_reads_auto_slash() {
+    let config = setup_config_test();
+    assert!(config.auto_slash);
+}
+
+#[test]
+/// parse the test config and see that the value parsed is correct
+fn config_reads_case_insensitive() {
+    let config = setup_config_test();
+    assert!(config.case_insensitive);
+}
+
+#[test]
+/// parse the test config and see that the value parsed is correct
+fn config_reads_retry_truncated() {
+    let config = setup_config_test();
+    assert!(config.retry_truncated);
+}
+
 #[test]
 /// parse the test config and see that the value parsed is correct
 fn config_reads_extract_links() {
@@ -331,6 +709,13 @@ fn config_reads_extract_links() {
     assert!(!config.extract_links);
 }
 
+#[test]
+/// parse the test config and see that the value parsed is correct
+fn config_reads_extract_priority() {
+    let config = setup_config_test();
+    assert_eq!(config.extract_priority, "low");
+}
+
 #[test]
 /// parse the test config and see that the value parsed is correct
 fn config_reads_collect_extensions() {
@@ -352,6 +737,37 @@ fn config_reads_collect_words() {
     assert!(config.collect_words);
 }
 
+#[test]
+/// parse the test config and see that the value parsed is correct
+fn config_reads_word_languages() {
+    let config = setup_config_test();
+    assert_eq!(config.word_languages, vec!["en", "ru"]);
+}
+
+#[test]
+/// parse the test config and see that the value parsed is correct
+fn config_reads_transliterate() {
+    let config = setup_config_test();
+    assert!(config.transliterate);
+}
+
+#[test]
+/// parse the test config and see that the value parsed is correct
+fn config_reads_collected_word_constraints() {
+    let config = setup_config_test();
+    assert_eq!(config.collected_word_min_len, 3);
+    assert_eq!(config.collected_word_max_len, 24);
+    assert_eq!(config.collected_word_charset, "alpha");
+    assert_eq!(config.collected_word_limit, 200);
+}
+
+#[test]
+/// parse the test config and see that the value parsed is correct
+fn config_reads_save_collected_words() {
+    let config = setup_config_test();
+    assert_eq!(config.save_collected_words, "/some/words");
+}
+
 #[test]
 /// parse the test config and see that the value parsed is correct
 fn config_reads_extensions() {
@@ -359,6 +775,22 @@ fn config_reads_extensions() {
     assert_eq!(config.extensions, vec!["html", "php", "js"]);
 }
 
+#[test]
+/// parse the test config and see that the value parsed is correct
+fn config_reads_extension_wordlists() {
+    let config = setup_config_test();
+    let mut expected = HashMap::new();
+    expected.insert("asp".to_string(), "/some/asp/words".to_string());
+    assert_eq!(config.extension_wordlists, expected);
+}
+
+#[test]
+/// parse the test config and see that the value parsed is correct
+fn config_reads_adaptive_extensions() {
+    let config = setup_config_test();
+    assert_eq!(config.adaptive_extensions, 25);
+}
+
 #[test]
 /// parse the test config and see that the value parsed is correct
 fn config_reads_dont_collect() {
@@ -390,6 +822,13 @@ fn config_reads_regex_denylist() {
     );
 }
 
+#[test]
+/// parse the test config and see that the value parsed is correct
+fn config_reads_dont_scan_file() {
+    let config = setup_config_test();
+    assert_eq!(config.dont_scan_file, "/some/deny.txt");
+}
+
 #[test]
 /// parse the test config and see that the value parsed is correct
 fn config_reads_url_denylist() {
@@ -445,6 +884,15 @@ fn config_reads_filter_status() {
     assert_eq!(config.filter_status, vec![201]);
 }
 
+#[test]
+/// parse the test config and see that the values parsed are correct
+fn config_reads_status_groups() {
+    let config = setup_config_test();
+    let mut groups = HashMap::new();
+    groups.insert("interesting".to_string(), vec![200, 301, 403]);
+    assert_eq!(config.status_groups, groups);
+}
+
 #[test]
 /// parse the test config and see that the value parsed is correct
 fn config_reads_save_state() {
@@ -466,6 +914,182 @@ fn config_reads_scan_dir_listings() {
     assert!(config.scan_dir_listings);
 }
 
+#[test]
+/// parse the test config and see that the value parsed is correct
+fn config_reads_scan_discovered_hosts() {
+    let config = setup_config_test();
+    assert!(config.scan_discovered_hosts);
+}
+
+#[test]
+/// parse the test config and see that the value parsed is correct
+fn config_reads_spa_mode() {
+    let config = setup_config_test();
+    assert!(config.spa_mode);
+}
+
+#[test]
+/// parse the test config and see that the value parsed is correct
+fn config_reads_survey_headers() {
+    let config = setup_config_test();
+    assert!(config.survey_headers);
+}
+
+#[test]
+/// parse the test config and see that the value parsed is correct
+fn config_reads_recon_banner() {
+    let config = setup_config_test();
+    assert!(config.recon_banner);
+}
+
+#[test]
+/// parse the test config and see that the value parsed is correct
+fn config_reads_known_urls() {
+    let config = setup_config_test();
+    assert_eq!(config.known_urls, "/some/known/urls");
+}
+
+#[test]
+/// parse the test config and see that the value parsed is correct
+fn config_reads_changed_since() {
+    let config = setup_config_test();
+    assert_eq!(config.changed_since, "/some/previous/run.json");
+}
+
+#[test]
+/// parse the test config and see that the value parsed is correct
+fn config_reads_openapi() {
+    let config = setup_config_test();
+    assert_eq!(config.openapi, "/some/openapi.json");
+}
+
+#[test]
+/// parse the test config and see that the value parsed is correct
+fn config_reads_graphql() {
+    let config = setup_config_test();
+    assert!(config.graphql);
+}
+
+#[test]
+/// parse the test config and see that the value parsed is correct
+fn config_reads_fetch_wordlist() {
+    let config = setup_config_test();
+    assert_eq!(config.fetch_wordlist, "raft-small-words");
+}
+
+#[test]
+/// parse the test config and see that the value parsed is correct
+fn config_reads_archive_seed() {
+    let config = setup_config_test();
+    assert_eq!(config.archive_seed, vec!["wayback", "commoncrawl"]);
+}
+
+#[test]
+/// parse the test config and see that the value parsed is correct
+fn config_reads_export_urls() {
+    let config = setup_config_test();
+    assert_eq!(config.export_urls, "/some/export/file");
+}
+
+#[test]
+/// parse the test config and see that the value parsed is correct
+fn config_reads_nuclei_targets() {
+    let config = setup_config_test();
+    assert_eq!(config.nuclei_targets, "/some/nuclei/targets");
+}
+
+#[test]
+/// parse the test config and see that the value parsed is correct
+fn config_reads_report_html() {
+    let config = setup_config_test();
+    assert_eq!(config.report_html, "/some/report.html");
+}
+
+#[test]
+/// parse the test config and see that the value parsed is correct
+fn config_reads_report_markdown() {
+    let config = setup_config_test();
+    assert_eq!(config.report_markdown, "/some/report.md");
+}
+
+#[test]
+/// parse the test config and see that the value parsed is correct
+fn config_reads_report_xml() {
+    let config = setup_config_test();
+    assert_eq!(config.report_xml, "/some/report.xml");
+}
+
+#[test]
+/// parse the test config and see that the value parsed is correct
+fn config_reads_emit_ffuf_json() {
+    let config = setup_config_test();
+    assert_eq!(config.emit_ffuf_json, "/some/ffuf.json");
+}
+
+#[test]
+/// parse the test config and see that the value parsed is correct
+fn config_reads_campaign() {
+    let config = setup_config_test();
+    assert_eq!(config.campaign, "/some/campaign.csv");
+}
+
+#[test]
+/// parse the test config and see that the values parsed are correct
+fn config_reads_notify_webhook() {
+    let config = setup_config_test();
+    assert_eq!(config.notify_webhook, "https://hooks.example.com/ferox");
+    assert_eq!(config.notify_min_status, 200);
+}
+
+#[test]
+/// parse the test config and see that the values parsed are correct
+fn config_reads_notify_chat_settings() {
+    let config = setup_config_test();
+    assert_eq!(config.notify_format, "discord");
+    assert_eq!(config.notify_template, "{url} ({status})");
+    assert_eq!(config.notify_rate_limit, 5);
+}
+
+#[test]
+/// parse the test config and see that the values parsed are correct
+fn config_reads_zap_api() {
+    let config = setup_config_test();
+    assert_eq!(config.zap_api_url, "http://127.0.0.1:8080");
+    assert_eq!(config.zap_api_key, "changeme");
+}
+
+#[test]
+/// parse the test config and see that the value parsed is correct
+fn config_reads_export_status() {
+    let config = setup_config_test();
+    assert_eq!(config.export_status, vec![200, 301]);
+}
+
+#[test]
+/// parse the test config and see that the value parsed is correct
+fn config_reads_export_format() {
+    let config = setup_config_test();
+    assert_eq!(config.export_format, "ffuf");
+}
+
+#[test]
+/// parse the test config and see that the values parsed are correct
+fn config_reads_preflight() {
+    let config = setup_config_test();
+    assert_eq!(config.preflight, "strict");
+    assert_eq!(config.preflight_retries, 2);
+    assert_eq!(config.preflight_report, "/some/preflight/report");
+}
+
+#[test]
+/// parse the test config and see that the values parsed are correct
+fn config_reads_meta() {
+    let config = setup_config_test();
+    let mut meta = HashMap::new();
+    meta.insert("engagement".to_string(), "1337".to_string());
+    assert_eq!(config.meta, meta);
+}
+
 #[test]
 /// parse the test config and see that the value parsed is correct
 fn config_reads_protocol() {
@@ -473,6 +1097,27 @@ fn config_reads_protocol() {
     assert_eq!(config.protocol, "http");
 }
 
+#[test]
+/// parse the test config and see that the value parsed is correct
+fn config_reads_both_schemes() {
+    let config = setup_config_test();
+    assert!(config.both_schemes);
+}
+
+#[test]
+/// parse the test config and see that the value parsed is correct
+fn config_reads_smart_schemes() {
+    let config = setup_config_test();
+    assert!(config.smart_schemes);
+}
+
+#[test]
+/// parse the test config and see that the value parsed is correct
+fn config_reads_no_scheme_probe() {
+    let config = setup_config_test();
+    assert!(config.no_scheme_probe);
+}
+
 #[test]
 /// parse the test config and see that the value parsed is correct
 fn config_reads_request_file() {
@@ -480,6 +1125,13 @@ fn config_reads_request_file() {
     assert_eq!(config.request_file, String::new());
 }
 
+#[test]
+/// parse the test config and see that the value parsed is correct
+fn config_reads_update_from() {
+    let config = setup_config_test();
+    assert_eq!(config.update_from, String::new());
+}
+
 #[test]
 /// parse the test config and see that the value parsed is correct
 fn config_reads_resume_from() {
@@ -528,6 +1180,15 @@ fn config_reads_headers() {
     assert_eq!(config.headers, headers);
 }
 
+#[test]
+/// parse the test config and see that the values parsed are correct
+fn config_reads_interstitial_bypass() {
+    let config = setup_config_test();
+    let mut headers = HashMap::new();
+    headers.insert("Cookie".to_string(), "consent=yes".to_string());
+    assert_eq!(config.interstitial_bypass, headers);
+}
+
 #[test]
 /// parse the test config and see that the values parsed are correct
 fn config_reads_queries() {
@@ -579,3 +1240,52 @@ fn as_json_returns_json_representation_of_configuration_with_newline() {
     assert_eq!(json.timeout, config.timeout);
     assert_eq!(json.depth, config.depth);
 }
+
+#[test]
+/// the zap api key is left out of serialized configurations, the url isn't
+fn as_json_omits_zap_api_key() {
+    let mut config = Configuration::new().unwrap();
+    config.zap_api_url = String::from("http://127.0.0.1:8080");
+    config.zap_api_key = String::from("super-secret-key");
+
+    let config_str = config.as_json().unwrap();
+    let json: serde_json::Value = serde_json::from_str(&config_str).unwrap();
+
+    assert!(!config_str.contains("super-secret-key"));
+    assert!(json.get("zap_api_key").is_none());
+    assert_eq!(json["zap_api_url"], "http://127.0.0.1:8080");
+}
+
+#[test]
+/// the notify webhook is left out of serialized configurations
+fn as_json_omits_notify_webhook() {
+    let mut config = Configuration::new().unwrap();
+    config.notify_webhook = String::from("https://hooks.example.com/super-secret-path");
+
+    let config_str = config.as_json().unwrap();
+    let json: serde_json::Value = serde_json::from_str(&config_str).unwrap();
+
+    assert!(!config_str.contains("super-secret-path"));
+    assert!(json.get("notify_webhook").is_none());
+}
+
+#[test]
+/// bypass headers override normal headers, case-insensitively, while cookies are combined
+fn bypass_headers_merges_into_normal_headers() {
+    let mut headers = HashMap::new();
+    headers.insert("cookie".to_string(), "session=abc".to_string());
+    headers.insert("x-stuff".to_string(), "things".to_string());
+
+    let mut bypass = HashMap::new();
+    bypass.insert("Cookie".to_string(), "consent=yes".to_string());
+    bypass.insert("X-Stuff".to_string(), "mothings".to_string());
+
+    let merged = bypass_headers(&headers, &bypass);
+
+    assert_eq!(merged.len(), 2);
+    assert_eq!(merged["X-Stuff"], "mothings");
+
+    let mut cookies: Vec<_> = merged["cookie"].split("; ").collect();
+    cookies.sort();
+    assert_eq!(cookies, vec!["consent=yes", "session=abc"]);
+}
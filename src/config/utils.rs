@@ -1,11 +1,16 @@
 use super::Configuration;
 use crate::{
+    progress::DEFAULT_BAR_REFRESH_HZ,
+    scan_manager::DEFAULT_MAX_DIR_REQUESTS,
     utils::{module_colorizer, parse_url_with_raw_path, status_colorizer},
-    DEFAULT_BACKUP_EXTENSIONS, DEFAULT_IGNORED_EXTENSIONS, DEFAULT_METHOD, DEFAULT_STATUS_CODES,
-    DEFAULT_WORDLIST, VERSION,
+    CSV_COLUMNS, DEFAULT_BACKUP_EXTENSIONS, DEFAULT_IGNORED_EXTENSIONS, DEFAULT_METHOD,
+    DEFAULT_STATUS_CODES, DEFAULT_WORDLIST, VERSION,
 };
-use anyhow::{bail, Result};
-use std::collections::HashMap;
+use anyhow::{bail, Context, Result};
+use regex::Regex;
+use reqwest::{StatusCode, Url};
+use std::collections::{HashMap, HashSet};
+use std::fmt;
 
 #[cfg(not(test))]
 use std::process::exit;
@@ -39,6 +44,16 @@ pub(super) fn timeout() -> u64 {
     7
 }
 
+/// default number of seconds a hostname is kept in the dns cache
+pub(super) fn dns_cache_ttl() -> u64 {
+    300
+}
+
+/// default number of progress bar redraws per second
+pub(super) fn bar_refresh_hz() -> u8 {
+    DEFAULT_BAR_REFRESH_HZ
+}
+
 /// default save_state value
 pub(super) fn save_state() -> bool {
     true
@@ -54,6 +69,56 @@ pub(super) fn request_protocol() -> String {
     String::from("https")
 }
 
+/// default priority of requests for links found via extraction
+pub(super) fn extract_priority() -> String {
+    String::from("normal")
+}
+
+/// default format of the --export-urls file
+pub(super) fn export_format() -> String {
+    String::from("plain")
+}
+
+/// default format of --notify-webhook payloads
+pub(super) fn notify_format() -> String {
+    String::from("generic")
+}
+
+/// default message sent to chat --notify-format webhooks for each finding
+pub(super) fn notify_template() -> String {
+    String::from("{status} {method} {url} ({lines}l {words}w {chars}c)")
+}
+
+/// default number of --notify-webhook notifications sent per minute
+pub(super) fn notify_rate_limit() -> usize {
+    20
+}
+
+/// default number of findings written to the output file between flushes
+pub(super) fn flush_every() -> usize {
+    1
+}
+
+/// default number of requests a single directory may take before the user is warned
+pub(super) fn max_dir_requests() -> usize {
+    DEFAULT_MAX_DIR_REQUESTS
+}
+
+/// default languages whose stop words are removed from collected words
+pub(super) fn word_languages() -> Vec<String> {
+    vec![String::from("en")]
+}
+
+/// default set of characters collected words may contain
+pub(super) fn collected_word_charset() -> String {
+    String::from("any")
+}
+
+/// default mode of the pre-scan connectivity check
+pub(super) fn preflight() -> String {
+    String::from("warn")
+}
+
 /// default status codes
 pub(super) fn status_codes() -> Vec<u16> {
     DEFAULT_STATUS_CODES
@@ -66,11 +131,53 @@ pub(super) fn status_codes() -> Vec<u16> {
         .collect()
 }
 
+/// parse status codes given on the command line, expanding each `@name` into the codes of the
+/// status group by that name (see `status_groups` in ferox-config.toml)
+///
+/// ex: `-s @interesting 418` with `interesting = [200, 301]` -> `[200, 301, 418]`
+///
+/// exits on an unknown group or an invalid status code
+pub(super) fn parse_status_codes<'a, I>(values: I, groups: &HashMap<String, Vec<u16>>) -> Vec<u16>
+where
+    I: IntoIterator<Item = &'a String>,
+{
+    let mut codes = Vec::new();
+
+    for value in values {
+        if let Some(name) = value.strip_prefix('@') {
+            match groups.get(name) {
+                Some(group) => codes.extend(group),
+                None => report_and_exit(&format!(
+                    "Unknown status group @{name}; define it under status_groups in ferox-config.toml"
+                )),
+            }
+        } else {
+            let code = StatusCode::from_bytes(value.as_bytes())
+                .unwrap_or_else(|e| report_and_exit(&e.to_string()));
+            codes.push(code.as_u16());
+        }
+    }
+
+    // groups can overlap each other, or codes given alongside them
+    let mut seen = HashSet::new();
+    codes.retain(|code| seen.insert(*code));
+
+    codes
+}
+
 /// default HTTP Method
 pub(super) fn methods() -> Vec<String> {
     vec![DEFAULT_METHOD.to_owned()]
 }
 
+/// default columns written by --csv
+pub(super) fn csv_columns() -> Vec<String> {
+    CSV_COLUMNS
+        .iter()
+        .map(|column| column.to_string())
+        .collect()
+}
+
 /// default extensions to ignore while auto-collecting
 pub(super) fn ignored_extensions() -> Vec<String> {
     DEFAULT_IGNORED_EXTENSIONS
@@ -153,6 +260,27 @@ pub fn determine_output_level(quiet: bool, silent: bool, json: bool) -> OutputLe
     }
 }
 
+/// split an optional output level prefix from a target read from stdin, i.e.
+/// `quiet http://noisy.example`; recognized prefixes are `default`, `quiet`, and `silent`
+///
+/// lines without a recognized prefix are returned as-is, with no level
+pub fn split_target_output_level(line: &str, json: bool) -> (Option<OutputLevel>, &str) {
+    let line = line.trim();
+
+    let Some((prefix, target)) = line.split_once(char::is_whitespace) else {
+        return (None, line);
+    };
+
+    let level = match prefix.to_ascii_lowercase().as_str() {
+        "default" => determine_output_level(false, false, json),
+        "quiet" => determine_output_level(true, false, json),
+        "silent" => determine_output_level(false, true, json),
+        _ => return (None, line),
+    };
+
+    (Some(level), target.trim())
+}
+
 /// represents actions the Requester should take in certain situations
 #[derive(Debug, PartialEq, Eq, Copy, Clone)]
 pub enum RequesterPolicy {
@@ -339,7 +467,7 @@ pub fn split_header(header: &str) -> Result<(String, String)> {
 ///
 /// The output string will contain all unique keys from both input strings, with the value
 /// from the second string taking precedence in the case of key collisions.
-fn combine_cookies(cookie1: &str, cookie2: &str) -> String {
+pub(super) fn combine_cookies(cookie1: &str, cookie2: &str) -> String {
     let mut cookie_map = HashMap::new();
 
     // Helper function to parse a cookie string and insert it into the map
@@ -364,6 +492,40 @@ fn combine_cookies(cookie1: &str, cookie2: &str) -> String {
         .join("; ")
 }
 
+/// Merges the `--interstitial-bypass` headers into a copy of the normal request headers.
+///
+/// Bypass headers win on name collisions, with the exception of `Cookie`, where the two
+/// values are combined via [`combine_cookies`] so that neither set of cookies is lost.
+pub(super) fn bypass_headers(
+    headers: &HashMap<String, String>,
+    bypass: &HashMap<String, String>,
+) -> HashMap<String, String> {
+    let mut merged = headers.clone();
+
+    for (name, value) in bypass {
+        let existing = merged
+            .keys()
+            .find(|key| key.eq_ignore_ascii_case(name))
+            .cloned();
+
+        match existing {
+            Some(key) if name.eq_ignore_ascii_case("cookie") => {
+                let combined = combine_cookies(&merged[&key], value);
+                merged.insert(key, combined);
+            }
+            Some(key) => {
+                merged.remove(&key);
+                merged.insert(name.clone(), value.clone());
+            }
+            None => {
+                merged.insert(name.clone(), value.clone());
+            }
+        }
+    }
+
+    merged
+}
+
 /// Parses a raw HTTP request from a file and updates the provided configuration.
 ///
 /// This function reads an HTTP request from the file specified by `config.request_file`,
@@ -387,14 +549,23 @@ fn combine_cookies(cookie1: &str, cookie2: &str) -> String {
 /// * The file specified in `config.request_file` is empty.
 /// * The request is malformed (e.g., missing the request line, method, or URI).
 /// * Required headers are missing (e.g., `Host` when the request line URI is not a full URL).
+/// * The body claims to be chunked, but isn't.
 ///
 /// # Details
 ///
-/// * The request body is only set if it hasn't been overridden by the CLI options.
+/// * The file may be a raw request (Burp's "Copy to file", ZAP's "Save Raw", etc...) using
+///   CRLF or bare LF line endings, or a `curl --trace`/`--trace-ascii` dump, from which the
+///   sent request is reassembled.
+/// * The request body is only set if it hasn't been overridden by the CLI options. Bodies are
+///   treated as bytes, so binary bodies survive intact. When a `Content-Length` header is
+///   present, the body is cut to that length (dropping trailing newlines added by editors);
+///   chunked bodies are decoded.
 /// * The request line method is added to `config.methods` if it's not already present.
+/// * Folded header lines (starting with a space or tab) are joined to the header above them,
+///   and repeated headers are combined into a single header.
 /// * Headers from the raw request are added to `config.headers`, unless overridden
 ///   by CLI options. Special handling is applied to `User-Agent`, `Content-Length`,
-///   and `Cookie` headers.
+///   `Transfer-Encoding`, and `Cookie` headers.
 /// * The request URI is validated and parsed. If it's not a full URL, it will be
 ///   combined with the `Host` header to form a full target URL.
 /// * Query parameters are extracted from the URI and added to `config.queries`,
@@ -414,36 +585,27 @@ fn combine_cookies(cookie1: &str, cookie2: &str) -> String {
 /// assert_eq!(config.data, b"key=value".to_vec());
 /// ```
 pub fn parse_request_file(config: &mut Configuration) -> Result<()> {
-    // read in the file located at config.request_file
-    // parse the file into a Request struct
-    let contents = std::fs::read_to_string(&config.request_file)?;
+    // read in the file located at config.request_file; bytes, not a string, as the body may
+    // be binary
+    let contents = std::fs::read(&config.request_file)?;
 
     if contents.is_empty() {
         bail!("Empty --request-file file provided");
     }
 
-    // this should split the body from the request line and headers
-    let lines = contents.split("\r\n\r\n").collect::<Vec<&str>>();
+    let raw = curl_trace_to_raw_request(&contents).unwrap_or(contents);
 
-    if lines.len() < 2 {
-        bail!("Invalid request: Missing head/body CRLF separator");
-    }
+    // this should split the body from the request line and headers
+    let (head, body) = split_raw_request(&raw);
 
-    let head = lines[0];
-    let body = lines[1].as_bytes().to_vec();
-
-    // we only want to use the request's body if the user hasn't
-    // overridden it on the cli
-    if config.data.is_empty() {
-        config.data = body;
-    }
+    // headers are ascii; anything else is replaced rather than rejected
+    let head = String::from_utf8_lossy(head);
 
     // begin parsing the request line and headers
-    let mut head_parts = head.split("\r\n");
+    let mut head_parts = unfold_header_lines(&head).into_iter();
 
-    let Some(request_line) = head_parts.next() else {
-        bail!("Invalid request: Missing request line");
-    };
+    // blank lines are skipped, so a missing request line shows up as an empty head
+    let request_line = head_parts.next().unwrap_or_default();
 
     if request_line.is_empty() {
         bail!("Invalid request: Empty request line");
@@ -473,14 +635,14 @@ pub fn parse_request_file(config: &mut Configuration) -> Result<()> {
         bail!("Invalid request: Empty request line URI");
     }
 
-    for mut line in head_parts {
-        line = line.trim();
+    // headers given on the cli, as opposed to those found further down in the request
+    let cli_headers: Vec<String> = config.headers.keys().map(|k| k.to_lowercase()).collect();
 
-        if line.is_empty() {
-            break; // Empty line signals the end of headers
-        }
+    let mut content_length = None;
+    let mut chunked = false;
 
-        let Ok((name, value)) = split_header(line) else {
+    for line in head_parts {
+        let Ok((name, value)) = split_header(&line) else {
             log::warn!("Invalid header: {}", line);
             continue;
         };
@@ -490,46 +652,101 @@ pub fn parse_request_file(config: &mut Configuration) -> Result<()> {
             continue;
         }
 
-        if name.to_lowercase() == "user-agent" {
+        let lowered = name.to_lowercase();
+
+        if lowered == "user-agent" {
             if config.user_agent == user_agent() {
                 config.user_agent = value;
             }
             continue;
         }
 
-        if name.to_lowercase() == "content-length" {
+        if lowered == "content-length" {
             log::debug!("Skipping content-length header, a new one will be created");
+            content_length = value.trim().parse::<usize>().ok();
             continue;
         }
 
-        if config.headers.contains_key(&name) {
-            if name.to_lowercase() == "cookie" {
-                // the cookie header already exists, so we need to extend it with
-                // our values and ensure cli-provided cookie values override those
-                // from the request
-                let existing = config.headers.get_mut(&name).unwrap();
+        if lowered == "transfer-encoding" {
+            log::debug!("Skipping transfer-encoding header, the body will be sent as-is");
+            chunked = value.to_lowercase().contains("chunked");
+            continue;
+        }
+
+        let existing = config
+            .headers
+            .keys()
+            .find(|key| key.to_lowercase() == lowered)
+            .cloned();
+
+        let Some(existing) = existing else {
+            config.headers.insert(name, value);
+            continue;
+        };
+
+        let current = config.headers.get_mut(&existing).unwrap();
+
+        if lowered == "cookie" {
+            // the cookie header already exists, so we need to extend it with
+            // our values and ensure cli-provided cookie values override those
+            // from the request
+            *current = if cli_headers.contains(&lowered) {
                 // second param takes precedence over first
-                let combined = combine_cookies(&value, existing);
-                *existing = combined;
-                continue;
-            }
+                combine_cookies(&value, current)
+            } else {
+                combine_cookies(current, &value)
+            };
+            continue;
+        }
+
+        if cli_headers.contains(&lowered) {
             log::debug!("Found header from cli, overriding raw request with cli entry: {name}");
             continue;
         }
 
-        config.headers.insert(name, value);
+        // repeated header within the request itself, which is equivalent to a single header
+        // with comma-separated values (RFC 9110 5.3)
+        current.push_str(", ");
+        current.push_str(&value);
+    }
+
+    let body = if chunked {
+        decode_chunked_body(body)?
+    } else {
+        match content_length {
+            Some(length) if length <= body.len() => body[..length].to_vec(),
+            Some(length) => {
+                log::warn!(
+                    "Content-Length of {} is larger than the request's body ({} bytes), using the body as-is",
+                    length,
+                    body.len()
+                );
+                body.to_vec()
+            }
+            None if body.iter().all(|b| *b == b'\r' || *b == b'\n') => Vec::new(),
+            None => body.to_vec(),
+        }
+    };
+
+    // we only want to use the request's body if the user hasn't
+    // overridden it on the cli
+    if config.data.is_empty() {
+        config.data = body;
     }
 
+    let host = config
+        .headers
+        .iter()
+        .find(|(key, _)| key.to_lowercase() == "host")
+        .map(|(_, value)| value.clone());
+
     let url = parse_url_with_raw_path(uri);
 
     if url.is_err() {
         // uri in request line is not a valid URL, so it's most likely a path/relative url
         // we need to combine it with the host header
-        for (key, value) in &config.headers {
-            if key.to_lowercase() == "host" {
-                config.target_url = format!("{}{}", value, uri);
-                break;
-            }
+        if let Some(host) = &host {
+            config.target_url = format!("{}{}", host, uri);
         }
 
         if config.target_url.is_empty() {
@@ -562,8 +779,20 @@ pub fn parse_request_file(config: &mut Configuration) -> Result<()> {
     } else {
         let mut url = url.unwrap();
 
-        if let Some(host) = config.headers.get("Host") {
-            url.set_host(Some(host)).unwrap();
+        if let Some(host) = &host {
+            // the host header may carry a port (ex: example.com:8443)
+            let (name, port) = match host.rsplit_once(':') {
+                Some((name, port)) if port.parse::<u16>().is_ok() => {
+                    (name, port.parse::<u16>().ok())
+                }
+                _ => (host.as_str(), None),
+            };
+
+            if url.set_host(Some(name)).is_err() {
+                log::warn!("Invalid Host header: {}", host);
+            } else if port.is_some() {
+                url.set_port(port).unwrap_or_default();
+            }
         }
 
         url.query_pairs().for_each(|(key, value)| {
@@ -586,6 +815,267 @@ pub fn parse_request_file(config: &mut Configuration) -> Result<()> {
     Ok(())
 }
 
+/// Splits a raw request into its head (request line and headers) and its body
+///
+/// The head ends at the first blank line, whether lines end in CRLF or a bare LF. Everything
+/// after that blank line is the body, blank lines included. A request without a blank line is
+/// all head.
+pub(super) fn split_raw_request(raw: &[u8]) -> (&[u8], &[u8]) {
+    let crlf = raw.windows(4).position(|window| window == b"\r\n\r\n");
+    let lf = raw.windows(2).position(|window| window == b"\n\n");
+
+    match (crlf, lf) {
+        (Some(crlf), Some(lf)) if lf < crlf => (&raw[..lf], &raw[lf + 2..]),
+        (Some(crlf), _) => (&raw[..crlf], &raw[crlf + 4..]),
+        (None, Some(lf)) => (&raw[..lf], &raw[lf + 2..]),
+        (None, None) => (raw, &[]),
+    }
+}
+
+/// Splits a request's head into lines, joining folded header lines (those starting with a
+/// space or tab, RFC 9112 5.2) onto the line above them
+///
+/// Leading blank lines and a leading byte order mark are skipped
+pub(super) fn unfold_header_lines(head: &str) -> Vec<String> {
+    let mut lines: Vec<String> = Vec::new();
+
+    for line in head.trim_start_matches('\u{feff}').split('\n') {
+        let line = line.strip_suffix('\r').unwrap_or(line);
+
+        if lines.is_empty() && line.trim().is_empty() {
+            continue;
+        }
+
+        match lines.last_mut() {
+            Some(previous) if lines.len() > 1 && line.starts_with([' ', '\t']) => {
+                previous.push(' ');
+                previous.push_str(line.trim());
+            }
+            _ if line.trim().is_empty() => {}
+            _ => lines.push(line.trim().to_string()),
+        }
+    }
+
+    lines
+}
+
+/// Decodes a body sent with `Transfer-Encoding: chunked`, ignoring chunk extensions and
+/// trailers
+fn decode_chunked_body(body: &[u8]) -> Result<Vec<u8>> {
+    let mut decoded = Vec::new();
+    let mut remaining = body;
+
+    loop {
+        let Some(end) = remaining.windows(2).position(|window| window == b"\r\n") else {
+            bail!("Invalid request: chunked body is missing a chunk size");
+        };
+
+        let size_line = String::from_utf8_lossy(&remaining[..end]);
+        let size = size_line.split(';').next().unwrap_or_default().trim();
+
+        let Ok(size) = usize::from_str_radix(size, 16) else {
+            bail!("Invalid request: bad chunk size {:?}", size_line);
+        };
+
+        remaining = &remaining[end + 2..];
+
+        if size == 0 {
+            break;
+        }
+
+        if remaining.len() < size {
+            bail!("Invalid request: chunked body ends mid-chunk");
+        }
+
+        decoded.extend_from_slice(&remaining[..size]);
+        remaining = remaining[size..]
+            .strip_prefix(b"\r\n")
+            .unwrap_or(&remaining[size..]);
+    }
+
+    Ok(decoded)
+}
+
+/// Reassembles the request sent by curl from a `curl --trace` or `curl --trace-ascii` dump
+///
+/// Only the `=> Send header` and `=> Send data` sections are used. `--trace` dumps are exact;
+/// `--trace-ascii` dumps show non-printable bytes as `.` and don't show line endings, so
+/// header lines are rejoined with CRLF and body lines are concatenated. Returns None when the
+/// given contents aren't a curl trace.
+fn curl_trace_to_raw_request(contents: &[u8]) -> Option<Vec<u8>> {
+    let text = String::from_utf8_lossy(contents);
+
+    if !text.lines().any(|line| line.starts_with("=> Send header")) {
+        return None;
+    }
+
+    let mut header_lines = Vec::new();
+    let mut header_bytes = Vec::new();
+    let mut body = Vec::new();
+    let mut is_hex = None;
+
+    // which section of the dump is being read: None for anything that isn't sent data
+    let mut section = None;
+
+    for line in text.lines() {
+        if line.starts_with("=> Send header") {
+            section = Some(true);
+            continue;
+        } else if line.starts_with("=> Send data") {
+            section = Some(false);
+            continue;
+        } else if line.starts_with("==") || line.starts_with("<=") || line.starts_with("=>") {
+            section = None;
+            continue;
+        }
+
+        let Some(is_header) = section else {
+            continue;
+        };
+
+        // data lines look like `0000: <data>`
+        let Some((offset, data)) = line.split_once(": ") else {
+            continue;
+        };
+
+        if u32::from_str_radix(offset.trim(), 16).is_err() {
+            continue;
+        }
+
+        // --trace shows 16 hex bytes in a fixed-width column, followed by their ascii
+        let is_hex = *is_hex.get_or_insert_with(|| {
+            let column = data.get(..data.len().min(48)).unwrap_or_default();
+            !column.is_empty()
+                && column
+                    .split_whitespace()
+                    .all(|byte| byte.len() == 2 && u8::from_str_radix(byte, 16).is_ok())
+        });
+
+        if is_hex {
+            let column = data.get(..data.len().min(48)).unwrap_or_default();
+
+            let bytes = column
+                .split_whitespace()
+                .filter_map(|byte| u8::from_str_radix(byte, 16).ok());
+
+            if is_header {
+                header_bytes.extend(bytes);
+            } else {
+                body.extend(bytes);
+            }
+        } else if is_header {
+            header_lines.push(data.to_string());
+        } else {
+            body.extend_from_slice(data.as_bytes());
+        }
+    }
+
+    let mut raw = if is_hex == Some(true) {
+        header_bytes
+    } else {
+        let mut head = header_lines.join("\r\n").trim_end().to_string();
+        head.push_str("\r\n\r\n");
+        head.into_bytes()
+    };
+
+    raw.extend(body);
+    Some(raw)
+}
+
+/// a single --dont-scan entry; either an absolute url or a regular expression
+#[derive(Debug, Clone)]
+pub enum Denier {
+    /// absolute url whose children are denied
+    Url(Url),
+
+    /// regular expression matched against the full url
+    Regex(Regex),
+}
+
+/// implementation of Denier
+impl Denier {
+    /// determine whether the given value is an absolute url or a regular expression, and parse
+    /// it as such
+    pub fn parse(denier: &str) -> Result<Self> {
+        // could be an absolute url or a regex, need to determine which
+        match parse_url_with_raw_path(denier.trim_end_matches('/')) {
+            // denier is an absolute url and can be parsed as such
+            Ok(absolute) => Ok(Self::Url(absolute)),
+            Err(err) => {
+                // there are some expected errors that happen when we try to parse a url
+                //     ex: Url::parse("/login") -> Err("relative URL without a base")
+                //     ex: Url::parse("http:") -> Err("empty host")
+                //
+                // these are known errors and are used to determine a valid value to
+                // --dont-scan, when it's not an absolute url
+                //
+                // when expected errors are encountered, we're going to assume
+                // that the input is a regular expression to be parsed. The possibility
+                // exists that the user rolled their face across the keyboard and we're
+                // dealing with the results, in which case we'll report it as an error and
+                // give up
+                if err.to_string().contains("relative URL without a base")
+                    || err.to_string().contains("empty host")
+                {
+                    Ok(Self::Regex(Regex::new(denier)?))
+                } else {
+                    // unexpected error has occurred; bail
+                    Err(err)
+                }
+            }
+        }
+    }
+
+    /// determine whether the given url falls under this entry
+    ///
+    /// unlike `should_deny_url`, no exception is made for children of scans that are already
+    /// running; this is what's used to cancel scans that were added to the denylist after they
+    /// started
+    pub fn matches(&self, url: &Url) -> bool {
+        match self {
+            Self::Regex(regex) => regex.is_match(url.as_str()),
+            Self::Url(denied) => {
+                let path = url.path().trim_end_matches('/');
+
+                url.host().is_some()
+                    && url.host() == denied.host()
+                    && path.starts_with(denied.path().trim_end_matches('/'))
+            }
+        }
+    }
+}
+
+/// Display implementation for Denier; shows the value as it was given
+impl fmt::Display for Denier {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Url(url) => write!(f, "{url}"),
+            Self::Regex(regex) => write!(f, "{regex}"),
+        }
+    }
+}
+
+/// parse the contents of a --dont-scan-file; one --dont-scan value per line, blank lines and
+/// lines starting with # are ignored
+pub fn parse_denylist(contents: &str) -> Result<Vec<Denier>> {
+    let mut deniers = Vec::new();
+
+    for (number, line) in contents.lines().enumerate() {
+        let line = line.trim();
+
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let denier =
+            Denier::parse(line).with_context(|| format!("invalid entry on line {}", number + 1))?;
+
+        deniers.push(denier);
+    }
+
+    Ok(deniers)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -660,6 +1150,31 @@ mod tests {
         assert_eq!(level, OutputLevel::Quiet);
     }
 
+    #[test]
+    /// test split_target_output_level only strips recognized prefixes
+    fn split_target_output_level_returns_correct_results() {
+        assert_eq!(
+            split_target_output_level("quiet http://noisy.example", false),
+            (Some(OutputLevel::Quiet), "http://noisy.example")
+        );
+        assert_eq!(
+            split_target_output_level("SILENT \thttp://noisy.example ", true),
+            (Some(OutputLevel::SilentJSON), "http://noisy.example")
+        );
+        assert_eq!(
+            split_target_output_level("default http://localhost", false),
+            (Some(OutputLevel::Default), "http://localhost")
+        );
+        assert_eq!(
+            split_target_output_level("http://localhost", false),
+            (None, "http://localhost")
+        );
+        assert_eq!(
+            split_target_output_level("loud http://localhost", false),
+            (None, "loud http://localhost")
+        );
+    }
+
     #[test]
     /// test determine_requester_policy returns higher of the two levels if both given values are true
     fn determine_requester_policy_returns_correct_results() {
@@ -683,6 +1198,31 @@ mod tests {
         report_and_exit("test");
     }
 
+    #[test]
+    /// status groups are expanded in place, and repeated codes are dropped
+    fn parse_status_codes_expands_groups() {
+        let mut groups = HashMap::new();
+        groups.insert(String::from("interesting"), vec![200, 301, 403]);
+        groups.insert(String::from("auth"), vec![401, 403]);
+
+        let values: Vec<String> = ["@interesting", "418", "@auth"]
+            .iter()
+            .map(|value| value.to_string())
+            .collect();
+
+        assert_eq!(
+            parse_status_codes(&values, &groups),
+            vec![200, 301, 403, 418, 401]
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    /// referencing a group that isn't defined is an error
+    fn parse_status_codes_panics_on_unknown_group() {
+        parse_status_codes(&[String::from("@nope")], &HashMap::new());
+    }
+
     #[test]
     fn test_split_query_simple() {
         let query = "name=value";
@@ -901,15 +1441,14 @@ mod tests {
     fn test_parse_raw_without_head_body_crlf() -> io::Result<()> {
         let mut tmp = TempSetup::new();
 
-        write!(tmp.file, "GET / HTTP/1.1\r\n")?;
+        write!(tmp.file, "GET /srv HTTP/1.1\r\nHost: example.com\r\n")?;
 
+        // a request without a blank line is all head, with an empty body
         let result = parse_request_file(&mut tmp.config);
 
-        assert!(result.is_err());
-        assert_eq!(
-            result.unwrap_err().to_string(),
-            "Invalid request: Missing head/body CRLF separator"
-        );
+        assert!(result.is_ok());
+        assert_eq!(tmp.config.target_url, "example.com/srv");
+        assert!(tmp.config.data.is_empty());
 
         tmp.cleanup();
         Ok(())
@@ -1287,4 +1826,305 @@ mod tests {
         tmp.cleanup();
         Ok(())
     }
+
+    /// Configuration pointed at one of the raw requests in tests/requests
+    fn fixture_config(name: &str) -> Configuration {
+        Configuration {
+            request_file: format!("{}/tests/requests/{}", env!("CARGO_MANIFEST_DIR"), name),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_parse_raw_with_lf_line_endings() -> io::Result<()> {
+        let mut tmp: TempSetup = TempSetup::new();
+
+        write!(
+            tmp.file,
+            "POST /srv HTTP/1.1\nHost: example.com\nX-Stuff: things\n\nkey=value\n"
+        )?;
+
+        let result = parse_request_file(&mut tmp.config);
+
+        assert!(result.is_ok());
+        assert_eq!(tmp.config.target_url, "example.com/srv");
+        assert_eq!(tmp.config.headers.get("X-Stuff").unwrap(), "things");
+        assert_eq!(tmp.config.data, b"key=value\n".to_vec());
+
+        tmp.cleanup();
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_raw_with_folded_header() -> io::Result<()> {
+        let mut tmp: TempSetup = TempSetup::new();
+
+        write!(
+            tmp.file,
+            "GET /srv HTTP/1.1\r\nHost: example.com\r\nX-Stuff: things\r\n\tand\r\n  more things\r\n\r\n"
+        )?;
+
+        let result = parse_request_file(&mut tmp.config);
+
+        assert!(result.is_ok());
+        assert_eq!(
+            tmp.config.headers.get("X-Stuff").unwrap(),
+            "things and more things"
+        );
+
+        tmp.cleanup();
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_raw_repeated_headers_are_joined() -> io::Result<()> {
+        let mut tmp: TempSetup = TempSetup::new();
+
+        write!(
+            tmp.file,
+            "GET /srv HTTP/1.1\r\nHost: example.com\r\nX-Stuff: things\r\nx-stuff: more things\r\n\r\n"
+        )?;
+
+        let result = parse_request_file(&mut tmp.config);
+
+        assert!(result.is_ok());
+        assert_eq!(
+            tmp.config.headers.get("X-Stuff").unwrap(),
+            "things, more things"
+        );
+
+        tmp.cleanup();
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_raw_body_is_cut_at_content_length() -> io::Result<()> {
+        let mut tmp: TempSetup = TempSetup::new();
+
+        write!(
+            tmp.file,
+            "POST /srv HTTP/1.1\r\nHost: example.com\r\nContent-Length: 9\r\n\r\nkey=value\r\n"
+        )?;
+
+        let result = parse_request_file(&mut tmp.config);
+
+        assert!(result.is_ok());
+        assert_eq!(tmp.config.data, b"key=value".to_vec());
+
+        tmp.cleanup();
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_raw_with_oversized_content_length_keeps_body() -> io::Result<()> {
+        let mut tmp: TempSetup = TempSetup::new();
+
+        write!(
+            tmp.file,
+            "POST /srv HTTP/1.1\r\nHost: example.com\r\nContent-Length: 900\r\n\r\nkey=value"
+        )?;
+
+        let result = parse_request_file(&mut tmp.config);
+
+        assert!(result.is_ok());
+        assert_eq!(tmp.config.data, b"key=value".to_vec());
+
+        tmp.cleanup();
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_raw_with_bad_chunked_body() -> io::Result<()> {
+        let mut tmp: TempSetup = TempSetup::new();
+
+        write!(
+            tmp.file,
+            "POST /srv HTTP/1.1\r\nHost: example.com\r\nTransfer-Encoding: chunked\r\n\r\nzz\r\nhello\r\n0\r\n\r\n"
+        )?;
+
+        let result = parse_request_file(&mut tmp.config);
+
+        assert!(result.is_err());
+
+        tmp.cleanup();
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_raw_with_full_url_and_host_header_with_port() -> io::Result<()> {
+        let mut tmp: TempSetup = TempSetup::new();
+
+        write!(
+            tmp.file,
+            "GET http://localhost/srv HTTP/1.1\r\nHost: example.com:8443\r\n\r\n"
+        )?;
+
+        let result = parse_request_file(&mut tmp.config);
+
+        assert!(result.is_ok());
+        assert_eq!(tmp.config.target_url, "http://example.com:8443/srv");
+
+        tmp.cleanup();
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_raw_with_full_url_and_invalid_host_header() -> io::Result<()> {
+        let mut tmp: TempSetup = TempSetup::new();
+
+        write!(
+            tmp.file,
+            "GET http://localhost/srv HTTP/1.1\r\nHost: exa mple.com\r\n\r\n"
+        )?;
+
+        let result = parse_request_file(&mut tmp.config);
+
+        assert!(result.is_ok());
+        assert_eq!(tmp.config.target_url, "http://localhost/srv");
+
+        tmp.cleanup();
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_raw_corpus_burp_post() {
+        let mut config = fixture_config("burp-post.req");
+
+        parse_request_file(&mut config).unwrap();
+
+        assert_eq!(config.methods, vec!["GET", "POST"]);
+        assert_eq!(config.target_url, "example.com:8443/api/login?debug=true");
+        assert_eq!(
+            config.queries,
+            vec![(String::from("debug"), String::from("true"))]
+        );
+        assert_eq!(config.headers.get("Cookie").unwrap(), "session=abc123");
+        assert_eq!(
+            config.headers.get("Content-Type").unwrap(),
+            "application/json"
+        );
+        assert!(config.user_agent.starts_with("Mozilla/5.0"));
+        // the blank line within the body is kept, the editor's trailing newline is not
+        assert_eq!(
+            config.data,
+            b"{\"user\":\"admin\",\r\n\r\n\"pass\":\"hunter2\"}".to_vec()
+        );
+    }
+
+    #[test]
+    fn test_parse_raw_corpus_burp_binary() {
+        let mut config = fixture_config("burp-binary.req");
+
+        parse_request_file(&mut config).unwrap();
+
+        assert_eq!(config.target_url, "example.com/upload");
+        assert_eq!(
+            config.data,
+            vec![
+                0x89, 0x50, 0x4e, 0x47, 0x0d, 0x0a, 0x1a, 0x0a, 0x00, 0x00, 0xff, 0xfe, 0x0d, 0x0a,
+                0x0d, 0x0a, 0x41
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_raw_corpus_zap_folded() {
+        let mut config = fixture_config("zap-folded.raw");
+
+        parse_request_file(&mut config).unwrap();
+
+        assert_eq!(config.target_url, "https://example.com/search");
+        assert_eq!(
+            config.queries,
+            vec![(String::from("q"), String::from("ferox"))]
+        );
+        assert_eq!(
+            config.headers.get("Accept").unwrap(),
+            "text/html, application/xhtml+xml"
+        );
+        assert_eq!(
+            config.headers.get("X-Forwarded-For").unwrap(),
+            "10.0.0.1, 10.0.0.2"
+        );
+
+        let cookies = config.headers.get("Cookie").unwrap();
+        assert!(cookies.contains("a=1"));
+        assert!(cookies.contains("b=2"));
+
+        assert!(config.data.is_empty());
+    }
+
+    #[test]
+    fn test_parse_raw_corpus_chunked() {
+        let mut config = fixture_config("chunked.req");
+
+        parse_request_file(&mut config).unwrap();
+
+        assert_eq!(config.data, b"hello world".to_vec());
+        assert!(!config.headers.contains_key("Transfer-Encoding"));
+    }
+
+    #[test]
+    fn test_parse_raw_corpus_curl_trace() {
+        let mut config = fixture_config("curl-trace.txt");
+
+        parse_request_file(&mut config).unwrap();
+
+        assert_eq!(config.methods, vec!["GET", "POST"]);
+        assert_eq!(config.target_url, "localhost:8080/upload");
+        assert_eq!(config.user_agent, "curl/8.5.0");
+        assert_eq!(
+            config.headers.get("Content-Type").unwrap(),
+            "application/octet-stream"
+        );
+        assert_eq!(config.data, vec![0x00, 0x01, 0x02, 0xff, 0xfe, 0x41]);
+    }
+
+    #[test]
+    fn test_parse_raw_corpus_curl_trace_ascii() {
+        let mut config = fixture_config("curl-trace-ascii.txt");
+
+        parse_request_file(&mut config).unwrap();
+
+        assert_eq!(config.methods, vec!["GET", "PUT"]);
+        assert_eq!(config.target_url, "localhost:8080/notes.txt");
+        assert_eq!(config.headers.get("Accept").unwrap(), "*/*");
+        assert_eq!(config.data, b"a=1&b=two".to_vec());
+    }
+
+    #[test]
+    /// absolute urls and regular expressions are told apart, and url entries deny their children
+    fn denier_parses_and_matches_urls_and_regexes() {
+        let url = Denier::parse("http://localhost/admin/").unwrap();
+        let regex = Denier::parse("/logout$").unwrap();
+
+        assert!(matches!(url, Denier::Url(_)));
+        assert!(matches!(regex, Denier::Regex(_)));
+
+        let child = Url::parse("http://localhost/admin/users").unwrap();
+        let other_host = Url::parse("http://otherhost/admin/users").unwrap();
+        let logout = Url::parse("http://localhost/api/logout").unwrap();
+
+        assert!(url.matches(&child));
+        assert!(!url.matches(&other_host));
+        assert!(!url.matches(&logout));
+        assert!(regex.matches(&logout));
+        assert!(!regex.matches(&child));
+
+        assert_eq!(url.to_string(), "http://localhost/admin");
+        assert_eq!(regex.to_string(), "/logout$");
+    }
+
+    #[test]
+    /// comments and blank lines are skipped, and a bad entry reports its line number
+    fn parse_denylist_skips_comments_and_reports_bad_lines() {
+        let deniers =
+            parse_denylist("# out of scope\n\nhttp://localhost/admin\n  /logout$  \n").unwrap();
+
+        assert_eq!(deniers.len(), 2);
+
+        let err = parse_denylist("http://localhost/admin\n/[unclosed\n").unwrap_err();
+
+        assert!(err.to_string().contains("line 2"));
+    }
 }
@@ -0,0 +1,209 @@
+//! in-process cache of hostname lookups shared by every client, see --dns-cache-size
+use std::{
+    collections::HashMap,
+    net::SocketAddr,
+    sync::{
+        atomic::{AtomicU64, AtomicUsize, Ordering},
+        Mutex,
+    },
+    time::{Duration, Instant},
+};
+
+use lazy_static::lazy_static;
+use reqwest::dns::{Addrs, Name, Resolve, Resolving};
+
+use crate::{
+    event_handlers::{Command::AddToUsizeField, Handles},
+    statistics::StatField::{DnsCacheHits, DnsLookups},
+};
+
+lazy_static! {
+    /// lookups shared by every client; unused until `configure`d with a non-zero size
+    pub static ref DNS_CACHE: DnsCache = DnsCache::default();
+}
+
+/// hostnames mapped to the addresses they resolved to, kept for a fixed number of seconds
+/// regardless of the record's own ttl, which the os resolver doesn't expose
+#[derive(Debug, Default)]
+pub struct DnsCache {
+    /// most hostnames kept at once; 0 disables the cache
+    capacity: AtomicUsize,
+
+    /// number of seconds a lookup is kept
+    ttl: AtomicU64,
+
+    /// hostname -> (when it was resolved, its addresses)
+    entries: Mutex<HashMap<String, (Instant, Vec<SocketAddr>)>>,
+
+    /// number of lookups that went to the os resolver
+    lookups: AtomicUsize,
+
+    /// number of lookups answered from the cache
+    hits: AtomicUsize,
+}
+
+/// implementation of DnsCache
+impl DnsCache {
+    /// set the size and ttl of the cache; must happen before any client is built
+    pub fn configure(&self, capacity: usize, ttl: u64) {
+        self.capacity.store(capacity, Ordering::Relaxed);
+        self.ttl.store(ttl, Ordering::Relaxed);
+    }
+
+    /// whether or not clients should resolve hostnames through the cache
+    pub fn is_enabled(&self) -> bool {
+        self.capacity.load(Ordering::Relaxed) > 0
+    }
+
+    /// number of lookups that went to the os resolver
+    pub fn lookups(&self) -> usize {
+        self.lookups.load(Ordering::Relaxed)
+    }
+
+    /// number of lookups answered from the cache
+    pub fn hits(&self) -> usize {
+        self.hits.load(Ordering::Relaxed)
+    }
+
+    /// addresses of the given host, if they were resolved less than ttl seconds ago
+    fn get(&self, host: &str) -> Option<Vec<SocketAddr>> {
+        let ttl = Duration::from_secs(self.ttl.load(Ordering::Relaxed));
+        let entries = self.entries.lock().ok()?;
+
+        let (resolved, addrs) = entries.get(host)?;
+
+        if resolved.elapsed() >= ttl {
+            return None;
+        }
+
+        self.hits.fetch_add(1, Ordering::Relaxed);
+        Some(addrs.clone())
+    }
+
+    /// keep the addresses of the given host; when the cache is full, expired entries are
+    /// dropped first, then the oldest
+    fn insert(&self, host: String, addrs: Vec<SocketAddr>) {
+        let capacity = self.capacity.load(Ordering::Relaxed);
+        let ttl = Duration::from_secs(self.ttl.load(Ordering::Relaxed));
+
+        let Ok(mut entries) = self.entries.lock() else {
+            return;
+        };
+
+        if !entries.contains_key(&host) && entries.len() >= capacity {
+            entries.retain(|_, (resolved, _)| resolved.elapsed() < ttl);
+
+            if entries.len() >= capacity {
+                let oldest = entries
+                    .iter()
+                    .min_by_key(|(_, (resolved, _))| *resolved)
+                    .map(|(host, _)| host.clone());
+
+                if let Some(oldest) = oldest {
+                    entries.remove(&oldest);
+                }
+            }
+        }
+
+        entries.insert(host, (Instant::now(), addrs));
+    }
+}
+
+/// resolver handed to each client when the cache is enabled; misses are resolved by the os,
+/// the same as they would be without the cache
+#[derive(Debug, Default, Clone, Copy)]
+pub(crate) struct CachingResolver;
+
+/// implementation of reqwest's Resolve for CachingResolver
+impl Resolve for CachingResolver {
+    fn resolve(&self, name: Name) -> Resolving {
+        Box::pin(async move {
+            let host = name.as_str().to_owned();
+
+            if let Some(addrs) = DNS_CACHE.get(&host) {
+                let addrs: Addrs = Box::new(addrs.into_iter());
+                return Ok(addrs);
+            }
+
+            DNS_CACHE.lookups.fetch_add(1, Ordering::Relaxed);
+
+            // the port is replaced by the client with the one from the url
+            let addrs: Vec<SocketAddr> =
+                tokio::net::lookup_host((host.as_str(), 0)).await?.collect();
+
+            DNS_CACHE.insert(host, addrs.clone());
+
+            let addrs: Addrs = Box::new(addrs.into_iter());
+            Ok(addrs)
+        })
+    }
+}
+
+/// lookups happen inside the clients, which can't reach the stats handler; their totals are
+/// added to the statistics once the scan is over
+pub fn record_lookup_stats(handles: &Handles) {
+    if !DNS_CACHE.is_enabled() {
+        return;
+    }
+
+    handles
+        .stats
+        .send(AddToUsizeField(DnsLookups, DNS_CACHE.lookups()))
+        .unwrap_or_default();
+
+    handles
+        .stats
+        .send(AddToUsizeField(DnsCacheHits, DNS_CACHE.hits()))
+        .unwrap_or_default();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// cache of the given size and ttl, separate from the global one
+    fn cache(capacity: usize, ttl: u64) -> DnsCache {
+        let cache = DnsCache::default();
+        cache.configure(capacity, ttl);
+        cache
+    }
+
+    #[test]
+    /// fresh entries are answered from the cache and counted, stale ones aren't
+    fn dns_cache_honors_ttl() {
+        let addr: SocketAddr = "127.0.0.1:0".parse().unwrap();
+
+        let fresh = cache(10, 300);
+        assert!(fresh.is_enabled());
+        assert!(fresh.get("localhost").is_none());
+
+        fresh.insert(String::from("localhost"), vec![addr]);
+        assert_eq!(fresh.get("localhost"), Some(vec![addr]));
+        assert_eq!(fresh.hits(), 1);
+
+        let stale = cache(10, 0);
+        stale.insert(String::from("localhost"), vec![addr]);
+        assert!(stale.get("localhost").is_none());
+        assert_eq!(stale.hits(), 0);
+
+        assert!(!cache(0, 300).is_enabled());
+    }
+
+    #[test]
+    /// a full cache makes room by dropping its oldest entry
+    fn dns_cache_evicts_oldest_entry() {
+        let addr: SocketAddr = "127.0.0.1:0".parse().unwrap();
+        let full = cache(2, 300);
+
+        // entries are told apart by when they were resolved
+        for host in ["one.local", "two.local", "three.local"] {
+            full.insert(String::from(host), vec![addr]);
+            std::thread::sleep(Duration::from_millis(5));
+        }
+
+        assert!(full.get("one.local").is_none());
+        assert!(full.get("two.local").is_some());
+        assert!(full.get("three.local").is_some());
+        assert_eq!(full.entries.lock().unwrap().len(), 2);
+    }
+}
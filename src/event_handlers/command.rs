@@ -6,10 +6,15 @@ use tokio::sync::oneshot::Sender;
 
 use crate::response::FeroxResponse;
 use crate::{
+    config::Denier,
     event_handlers::Handles,
-    message::FeroxMessage,
-    statistics::{StatError, StatField},
+    extractor::{DiscoveredHost, ServiceDescription},
+    graphql::GraphqlSchema,
+    message::{FeroxMessage, SkipEvent},
+    statistics::{ErrorEvent, StatError, StatField, StatsBatch},
+    survey::HeaderSurvey,
     traits::FeroxFilter,
+    wordlists::Wordlist,
 };
 
 /// Protocol definition for updating an event handler via mpsc
@@ -21,9 +26,15 @@ pub enum Command {
     /// Add one to the proper field(s) based on the given `StatError`
     AddError(StatError),
 
+    /// Record the given `StatError` against the given target (scheme://host:port)
+    AddTargetError(String, StatError),
+
     /// Add one to the proper field(s) based on the given `StatusCode`
     AddStatus(StatusCode),
 
+    /// Add every request outcome in the given `StatsBatch` (see `StatsBatches`)
+    AddBatch(Box<StatsBatch>),
+
     /// Create the progress bar (`BarType::Total`) that is updated from the stats thread
     ///
     /// the u64 value is the offset at which to start the progress bar (can be 0)
@@ -62,8 +73,15 @@ pub enum Command {
     /// Determine whether or not recursion is appropriate, given a FeroxResponse, if so start a scan
     TryRecursion(Box<FeroxResponse>),
 
+    /// Let the ScanHandler know that the scan of the given url has made all of its requests, so
+    /// that directories held back by --recurse-threshold can be decided on
+    ScanFinished(String),
+
     /// Send a pointer to the wordlist to the recursion handler
-    UpdateWordlist(Arc<Vec<String>>),
+    UpdateWordlist(Arc<Wordlist>),
+
+    /// Replace the --dont-scan-file entries and cancel any scans they now deny
+    UpdateDenylist(Vec<Denier>),
 
     /// Instruct the ScanHandler to join on all known scans, use sender to notify main when done
     JoinTasks(Sender<bool>),
@@ -80,6 +98,24 @@ pub enum Command {
     /// Write an arbitrary string to disk
     WriteToDisk(Box<FeroxMessage>),
 
+    /// Write a `HeaderSurvey` to disk
+    WriteSurvey(Box<HeaderSurvey>),
+
+    /// Write a `GraphqlSchema` to disk
+    WriteGraphql(Box<GraphqlSchema>),
+
+    /// Write a `DiscoveredHost` to disk
+    WriteDiscoveredHost(Box<DiscoveredHost>),
+
+    /// Write a `ServiceDescription` to disk
+    WriteServiceDescription(Box<ServiceDescription>),
+
+    /// Write an `ErrorEvent` to disk
+    WriteError(Box<ErrorEvent>),
+
+    /// Write a `SkipEvent` to disk
+    WriteSkip(Box<SkipEvent>),
+
     /// Break out of the (infinite) mpsc receive loop
     Exit,
 
@@ -92,4 +128,7 @@ pub enum Command {
 
     /// query the Stats handler about the position of the overall progress bar
     QueryOverallBarEta(Sender<Duration>),
+
+    /// query the Stats handler about how long the scan has been running
+    QueryElapsed(Sender<Duration>),
 }
@@ -1,7 +1,9 @@
 use super::*;
 use crate::config::Configuration;
 use crate::event_handlers::scans::ScanHandle;
+use crate::extractor::{DiscoveredHosts, SeenLinks};
 use crate::scan_manager::FeroxScans;
+use crate::wordlists::Wordlist;
 use crate::Joiner;
 #[cfg(test)]
 use crate::{filters::FeroxFilters, statistics::Stats, Command};
@@ -10,6 +12,7 @@ use std::collections::HashSet;
 use std::sync::{Arc, RwLock};
 #[cfg(test)]
 use tokio::sync::mpsc::{self, UnboundedReceiver};
+use tokio_util::sync::CancellationToken;
 
 #[derive(Debug)]
 /// Simple container for multiple JoinHandles
@@ -59,7 +62,19 @@ pub struct Handles {
     pub scans: RwLock<Option<ScanHandle>>,
 
     /// Pointer to the list of words generated from reading in the wordlist
-    pub wordlist: Arc<Vec<String>>,
+    pub wordlist: Arc<Wordlist>,
+
+    /// extensions paired with their own wordlist (-x ext:FILE), sorted by extension
+    pub extension_wordlists: Vec<(String, Arc<Wordlist>)>,
+
+    /// links found via extraction by any scan, see `SeenLinks`
+    pub seen_links: SeenLinks,
+
+    /// hosts within a target's domain found via extraction by any scan, see `DiscoveredHosts`
+    pub discovered_hosts: DiscoveredHosts,
+
+    /// cancelled to stop the run early; see `Handles::cancel`
+    cancellation: CancellationToken,
 }
 
 /// implementation of Handles
@@ -70,7 +85,8 @@ impl Handles {
         filters: FiltersHandle,
         output: TermOutHandle,
         config: Arc<Configuration>,
-        wordlist: Arc<Vec<String>>,
+        wordlist: Arc<Wordlist>,
+        extension_wordlists: Vec<(String, Arc<Wordlist>)>,
     ) -> Self {
         Self {
             stats,
@@ -79,6 +95,10 @@ impl Handles {
             config,
             scans: RwLock::new(None),
             wordlist,
+            extension_wordlists,
+            seen_links: SeenLinks::new(),
+            discovered_hosts: DiscoveredHosts::new(),
+            cancellation: CancellationToken::new(),
         }
     }
 
@@ -93,13 +113,14 @@ impl Handles {
         let terminal_handle = TermOutHandle::new(tx.clone(), tx.clone());
         let stats_handle = StatsHandle::new(Arc::new(Stats::new(configuration.json)), tx.clone());
         let filters_handle = FiltersHandle::new(Arc::new(FeroxFilters::default()), tx.clone());
-        let wordlist = Arc::new(vec![String::from("this_is_a_test")]);
+        let wordlist = Arc::new(Wordlist::from(vec![String::from("this_is_a_test")]));
         let handles = Self::new(
             stats_handle,
             filters_handle,
             terminal_handle,
             configuration,
             wordlist,
+            Vec::new(),
         );
         if let Some(sh) = scanned_urls {
             let scan_handle = ScanHandle::new(sh, tx);
@@ -150,11 +171,24 @@ impl Handles {
         HashSet::new()
     }
 
-    /// number of words in the wordlist, multiplied by `expected_num_requests_multiplier`
+    /// number of words in the wordlist, multiplied by `expected_num_requests_multiplier`, plus
+    /// the requests made for words paired with a single extension
     pub fn expected_num_requests_per_dir(&self) -> usize {
         let num_words = self.wordlist.len();
         let multiplier = self.expected_num_requests_multiplier();
-        multiplier * num_words
+        multiplier * num_words + self.expected_num_paired_requests()
+    }
+
+    /// number of requests made per directory for words in the extension-paired wordlists; each
+    /// of those words is only requested with its paired extension (once per method)
+    pub fn expected_num_paired_requests(&self) -> usize {
+        let num_words: usize = self
+            .extension_wordlists
+            .iter()
+            .map(|(_, words)| words.len())
+            .sum();
+
+        num_words * self.config.methods.len().max(1)
     }
 
     /// number of extensions plus the number of request method types plus any dynamically collected
@@ -173,6 +207,50 @@ impl Handles {
         multiplier
     }
 
+    /// push findings that are still making their way through the output handlers into the
+    /// --output file and flush it to disk, logging a confirmation once done
+    ///
+    /// `reason` is included in the confirmation (ex: "auto-bail on http://localhost/")
+    pub async fn flush_output(&self, reason: &str) -> Result<()> {
+        let location = if self.config.output_dir.is_empty() {
+            &self.config.output
+        } else {
+            &self.config.output_dir
+        };
+
+        if location.is_empty() {
+            return Ok(());
+        }
+
+        self.output.sync(true).await?;
+
+        log::info!("flushed findings to {} ({})", location, reason);
+        Ok(())
+    }
+
+    /// stop the run early, saving its state the same way ctrl+c does (unless --no-state was used)
+    ///
+    /// scans stop making new requests, requests already in flight are allowed to finish, and
+    /// scans that haven't started yet are left that way, so the saved state can be resumed with
+    /// --resume-from. Unlike ctrl+c, the process isn't exited; the scan's entry point returns
+    /// once everything has wound down
+    pub fn cancel(&self) {
+        log::info!("cancellation requested, winding down scans");
+        self.cancellation.cancel();
+    }
+
+    /// whether `cancel` has been called, either directly or through a token obtained from
+    /// `cancellation_token`
+    pub fn is_cancelled(&self) -> bool {
+        self.cancellation.is_cancelled()
+    }
+
+    /// a token that cancels the run when its `cancel` is called; useful for handing to code that
+    /// shouldn't hold on to the `Handles` themselves
+    pub fn cancellation_token(&self) -> CancellationToken {
+        self.cancellation.clone()
+    }
+
     /// Helper to easily get the (locked) underlying FeroxScans object
     pub fn ferox_scans(&self) -> Result<Arc<FeroxScans>> {
         if let Ok(guard) = self.scans.read().as_ref() {
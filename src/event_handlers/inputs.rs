@@ -1,18 +1,21 @@
 use super::*;
 use crate::{
-    progress::PROGRESS_PRINTER,
+    progress::print_above_bars,
     scan_manager::{FeroxState, PAUSE_SCAN},
     scanner::RESPONSES,
     statistics::StatError,
-    utils::slugify_filename,
-    utils::{open_file, write_to},
+    upload,
+    utils::state_filename,
+    utils::{fmt_err, write_to},
     SLEEP_DURATION,
 };
-use anyhow::Result;
+use anyhow::{anyhow, bail, Context, Result};
 use console::style;
 use crossterm::event::{self, Event, KeyCode};
 use std::{
     env::temp_dir,
+    fs::File,
+    io::BufWriter,
     sync::{
         atomic::{AtomicBool, Ordering},
         Arc,
@@ -24,6 +27,17 @@ use std::{
 /// Atomic boolean flag, used to determine whether or not the terminal input handler should exit
 pub static SCAN_COMPLETE: AtomicBool = AtomicBool::new(false);
 
+/// how long the sigint handler waits for buffered findings to be flushed before giving up
+const FLUSH_TIMEOUT: Duration = Duration::from_secs(3);
+
+/// open the given state file for writing, replacing whatever an earlier save left in it; the
+/// state is saved more than once per run when scans bail or are cancelled
+fn create_state_file(filename: &str) -> Result<BufWriter<File>> {
+    let file =
+        File::create(filename).with_context(|| fmt_err(&format!("Could not open {filename}")))?;
+    Ok(BufWriter::new(file))
+}
+
 /// Container for filters transmitter and FeroxFilters object
 pub struct TermInputHandler {
     /// handles to other handlers
@@ -74,25 +88,54 @@ impl TermInputHandler {
         }
     }
 
-    /// Writes the current state of the program to disk (if save_state is true) and then exits
-    pub fn sigint_handler(handles: Arc<Handles>) -> Result<()> {
-        log::trace!("enter: sigint_handler({:?})", handles);
+    /// blocks until findings still making their way through the output handlers are flushed to
+    /// --output, or FLUSH_TIMEOUT elapses; returns whether or not the flush completed
+    ///
+    /// the sigint handler can be called from the ctrl+c handler's thread or from within the
+    /// async runtime (--time-limit), so the flush is driven from a thread of its own
+    fn flush_output(handles: Arc<Handles>) -> bool {
+        let (tx, rx) = std::sync::mpsc::channel();
+
+        std::thread::spawn(move || {
+            let result = futures::executor::block_on(handles.flush_output("ctrl+c"));
+            tx.send(result.is_ok()).unwrap_or_default();
+        });
+
+        rx.recv_timeout(FLUSH_TIMEOUT).unwrap_or(false)
+    }
 
-        let filename = if !handles.config.target_url.is_empty() {
+    /// uploads an s3:// --output, so that interrupted scans still reach object storage
+    ///
+    /// like `flush_output`, the upload is driven from a thread of its own, with a runtime of its
+    /// own, since the sigint handler may or may not be running within the async runtime
+    fn upload_output(handles: Arc<Handles>) -> Result<()> {
+        std::thread::spawn(move || {
+            tokio::runtime::Builder::new_current_thread()
+                .enable_all()
+                .build()?
+                .block_on(upload::upload_output(&handles.config))
+        })
+        .join()
+        .unwrap_or_else(|_| Err(anyhow!("the upload thread panicked")))
+    }
+
+    /// name of the file the scan's state is saved to, based on the target (or stdin) and run id
+    fn state_file(handles: &Handles) -> String {
+        if !handles.config.target_url.is_empty() {
             // target url populated
-            slugify_filename(&handles.config.target_url, "ferox", "state")
+            state_filename(&handles.config.target_url, &handles.config.run_id)
         } else {
             // stdin used
-            slugify_filename("stdin", "ferox", "state")
-        };
+            state_filename("stdin", &handles.config.run_id)
+        }
+    }
 
-        let warning = format!(
-            "🚨 Caught {} 🚨 saving scan state to {} ...",
-            style("ctrl+c").yellow(),
-            filename
-        );
+    /// Writes the current state of the scan to disk, falling back to the temp directory when the
+    /// state file can't be opened; returns the path that was written
+    pub fn save_state(handles: Arc<Handles>) -> Result<String> {
+        log::trace!("enter: save_state({:?})", handles);
 
-        PROGRESS_PRINTER.println(warning);
+        let filename = Self::state_file(&handles);
 
         let state = FeroxState::new(
             handles.ferox_scans()?,
@@ -102,38 +145,113 @@ impl TermInputHandler {
             handles.filters.data.clone(),
         );
 
-        // User didn't set the --no-state flag (so saved_state is still the default true)
-        if handles.config.save_state {
-            let Ok(mut state_file) = open_file(&filename) else {
-                // couldn't open the file, let the user know we're going to try again
-                let error = format!(
-                    "❌ Could not save {}, falling back to {}",
-                    filename,
-                    temp_dir().to_string_lossy()
-                );
-                PROGRESS_PRINTER.println(error);
-
-                let temp_filename = temp_dir().join(&filename);
-
-                let Ok(mut state_file) = open_file(&temp_filename.to_string_lossy()) else {
-                    // couldn't open the fallback file, let the user know
-                    let error = format!("❌❌ Could not save {:?}, giving up...", temp_filename);
-                    PROGRESS_PRINTER.println(error);
+        if let Ok(mut state_file) = create_state_file(&filename) {
+            write_to(&state, &mut state_file, true)?;
 
-                    log::trace!("exit: sigint_handler (failed to write)");
-                    std::process::exit(1);
-                };
+            log::trace!("exit: save_state -> {}", filename);
+            return Ok(filename);
+        }
 
-                write_to(&state, &mut state_file, true)?;
+        // couldn't open the file, let the user know we're going to try again
+        let error = format!(
+            "❌ Could not save {}, falling back to {}",
+            filename,
+            temp_dir().to_string_lossy()
+        );
+        print_above_bars(&error);
 
-                let msg = format!("✅ Saved scan state to {:?}", temp_filename);
-                PROGRESS_PRINTER.println(msg);
+        let temp_filename = temp_dir().join(&filename).to_string_lossy().to_string();
 
-                log::trace!("exit: sigint_handler (saved to temp folder)");
-                std::process::exit(1);
+        let Ok(mut state_file) = create_state_file(&temp_filename) else {
+            bail!("Could not save {:?}", temp_filename);
+        };
+
+        write_to(&state, &mut state_file, true)?;
+
+        log::trace!("exit: save_state -> {}", temp_filename);
+        Ok(temp_filename)
+    }
+
+    /// save the scan's state while the run carries on (auto-bail, cancelled scans), so that the
+    /// process dying soon after doesn't take it along; does nothing when --no-state was used
+    ///
+    /// returns the path that was written, if any
+    pub async fn checkpoint_state(handles: Arc<Handles>, reason: &str) -> Option<String> {
+        if !handles.config.save_state {
+            return None;
+        }
+
+        match tokio::task::spawn_blocking(move || Self::save_state(handles)).await {
+            Ok(Ok(saved)) => {
+                log::info!("saved scan state to {} ({})", saved, reason);
+                Some(saved)
+            }
+            Ok(Err(e)) => {
+                log::warn!("Could not save scan state ({}): {}", reason, e);
+                None
+            }
+            Err(e) => {
+                log::warn!("Could not save scan state ({}): {}", reason, e);
+                None
+            }
+        }
+    }
+
+    /// Writes the current state of the program to disk (if save_state is true) and then exits
+    pub fn sigint_handler(handles: Arc<Handles>) -> Result<()> {
+        log::trace!("enter: sigint_handler({:?})", handles);
+
+        let filename = Self::state_file(&handles);
+
+        let warning = format!(
+            "🚨 Caught {} 🚨 saving scan state to {} ...",
+            style("ctrl+c").yellow(),
+            filename
+        );
+
+        print_above_bars(&warning);
+
+        let location = if handles.config.output_dir.is_empty() {
+            &handles.config.output
+        } else {
+            &handles.config.output_dir
+        };
+
+        if !location.is_empty() {
+            let flushed = Self::flush_output(handles.clone());
+
+            let msg = if flushed {
+                format!("✅ Flushed findings to {}", location)
+            } else {
+                format!("❌ Could not flush findings to {}", location)
             };
 
-            write_to(&state, &mut state_file, true)?;
+            print_above_bars(&msg);
+
+            if flushed && !handles.config.output_upload.is_empty() {
+                let msg = match Self::upload_output(handles.clone()) {
+                    Ok(_) => format!("✅ Uploaded findings to {}", handles.config.output_upload),
+                    Err(e) => format!("❌ {}", e),
+                };
+
+                print_above_bars(&msg);
+            }
+        }
+
+        // User didn't set the --no-state flag (so saved_state is still the default true)
+        if handles.config.save_state {
+            match Self::save_state(handles.clone()) {
+                Ok(saved) if saved != filename => {
+                    let msg = format!("✅ Saved scan state to {:?}", saved);
+                    print_above_bars(&msg);
+                }
+                Ok(_) => {}
+                Err(e) => {
+                    // couldn't open the fallback file, let the user know
+                    let error = format!("❌❌ {}, giving up...", e);
+                    print_above_bars(&error);
+                }
+            }
         }
 
         log::trace!("exit: sigint_handler (end of program)");
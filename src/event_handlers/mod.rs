@@ -6,11 +6,15 @@ mod command;
 mod outputs;
 mod scans;
 mod inputs;
+mod notifications;
+mod zap;
 
 pub use self::command::Command;
 pub use self::container::{Handles, Tasks};
 pub use self::filters::{FiltersHandle, FiltersHandler};
 pub use self::inputs::{TermInputHandler, SCAN_COMPLETE};
+pub use self::notifications::NotificationHandler;
 pub use self::outputs::{TermOutHandle, TermOutHandler};
 pub use self::scans::{ScanHandle, ScanHandler};
 pub use self::statistics::{StatsHandle, StatsHandler};
+pub use self::zap::ZapHandler;
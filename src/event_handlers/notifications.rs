@@ -0,0 +1,200 @@
+use super::*;
+
+use anyhow::Result;
+use std::time::Instant;
+use tokio::{sync::mpsc, time::Duration};
+
+use crate::{
+    config::Configuration,
+    notify::{redact_webhook, Webhook},
+    CommandReceiver, CommandSender, Joiner,
+};
+
+/// --notify-rate-limit is the number of notifications allowed per window of this length
+const NOTIFY_WINDOW: Duration = Duration::from_secs(60);
+
+#[derive(Debug)]
+/// Event handler that sends findings to --notify-webhook
+///
+/// findings are sent one at a time, in the order they were found, so a slow webhook only ever
+/// holds up this handler and never the scan itself
+pub struct NotificationHandler {
+    /// notification handler's receiver
+    receiver: CommandReceiver,
+
+    /// where findings are sent
+    webhook: Webhook,
+
+    /// most findings sent per `NOTIFY_WINDOW`, 0 is unlimited
+    rate_limit: usize,
+
+    /// when the current window started
+    window_start: Instant,
+
+    /// notifications sent during the current window
+    sent: usize,
+
+    /// findings held back during the current window
+    suppressed: usize,
+}
+
+/// implementation of NotificationHandler
+impl NotificationHandler {
+    /// Given a receiver and the webhook to notify, create a NotificationHandler
+    fn new(receiver: CommandReceiver, webhook: Webhook, rate_limit: usize) -> Self {
+        Self {
+            receiver,
+            webhook,
+            rate_limit,
+            window_start: Instant::now(),
+            sent: 0,
+            suppressed: 0,
+        }
+    }
+
+    /// Spawn the notification handler, if --notify-webhook was used
+    pub fn initialize(config: &Configuration) -> Option<(Joiner, CommandSender)> {
+        log::trace!(
+            "enter: initialize({})",
+            redact_webhook(&config.notify_webhook)
+        );
+
+        let webhook = Webhook::new(config).unwrap_or_else(|e| {
+            log::warn!("{:#}; findings will not be sent to the webhook", e);
+            None
+        })?;
+
+        let (tx, rx) = mpsc::unbounded_channel::<Command>();
+
+        let mut handler = Self::new(rx, webhook, config.notify_rate_limit);
+        let task = tokio::spawn(async move { handler.start().await });
+
+        log::trace!("exit: initialize -> {:?}", task);
+        Some((task, tx))
+    }
+
+    /// Start a single consumer task (sc side of mpsc)
+    ///
+    /// findings arrive as `Command::Report`; whatever --notify-rate-limit held back during the
+    /// final window is reported on `Command::Exit`
+    async fn start(&mut self) -> Result<()> {
+        log::trace!("enter: start");
+
+        while let Some(command) = self.receiver.recv().await {
+            match command {
+                Command::Report(response) => {
+                    if !self.webhook.wants(&response) {
+                        continue;
+                    }
+
+                    let (allowed, held_back) = self.admit(Instant::now());
+
+                    if held_back > 0 {
+                        self.report_suppressed(held_back).await;
+                    }
+
+                    if allowed {
+                        if let Err(e) = self.webhook.finding(&response).await {
+                            log::warn!("{:#}", e);
+                        }
+                    }
+                }
+                Command::Sync(sender) => {
+                    sender.send(true).unwrap_or_default();
+                }
+                Command::Exit => break,
+                _ => {} // no more commands needed
+            }
+        }
+
+        let held_back = std::mem::take(&mut self.suppressed);
+
+        if held_back > 0 {
+            self.report_suppressed(held_back).await;
+        }
+
+        log::trace!("exit: start");
+        Ok(())
+    }
+
+    /// whether a finding seen at `now` may be sent; when `now` starts a new window, the number
+    /// of findings held back during the last one is returned as well, so it can be reported
+    fn admit(&mut self, now: Instant) -> (bool, usize) {
+        if self.rate_limit == 0 {
+            return (true, 0);
+        }
+
+        let mut held_back = 0;
+
+        if now.duration_since(self.window_start) >= NOTIFY_WINDOW {
+            held_back = std::mem::take(&mut self.suppressed);
+            self.window_start = now;
+            // the held back count is a notification of its own
+            self.sent = usize::from(held_back > 0);
+        }
+
+        if self.sent < self.rate_limit {
+            self.sent += 1;
+            (true, held_back)
+        } else {
+            self.suppressed += 1;
+            (false, held_back)
+        }
+    }
+
+    /// let the webhook know how many findings weren't sent
+    async fn report_suppressed(&self, count: usize) {
+        log::info!("{} finding(s) held back by --notify-rate-limit", count);
+
+        if let Err(e) = self.webhook.suppressed(count).await {
+            log::warn!("{:#}", e);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// handler with the given rate limit and a webhook that's never actually called
+    fn handler(rate_limit: usize) -> NotificationHandler {
+        let config = Configuration {
+            notify_webhook: String::from("http://localhost/hook"),
+            ..Default::default()
+        };
+
+        let (_, rx) = mpsc::unbounded_channel::<Command>();
+
+        NotificationHandler::new(rx, Webhook::new(&config).unwrap().unwrap(), rate_limit)
+    }
+
+    #[test]
+    /// findings past the limit are held back until the next window, which reports them
+    fn admit_holds_back_findings_past_rate_limit() {
+        let mut handler = handler(2);
+        let start = handler.window_start;
+
+        assert_eq!(handler.admit(start), (true, 0));
+        assert_eq!(handler.admit(start), (true, 0));
+        assert_eq!(handler.admit(start), (false, 0));
+        assert_eq!(handler.admit(start), (false, 0));
+
+        let next_window = start + NOTIFY_WINDOW;
+
+        // the held back count takes one of the new window's slots
+        assert_eq!(handler.admit(next_window), (true, 2));
+        assert_eq!(handler.admit(next_window), (false, 0));
+        assert_eq!(handler.suppressed, 1);
+    }
+
+    #[test]
+    /// a rate limit of 0 sends everything
+    fn admit_without_rate_limit() {
+        let mut handler = handler(0);
+        let start = handler.window_start;
+
+        for _ in 0..100 {
+            assert_eq!(handler.admit(start), (true, 0));
+        }
+    }
+}
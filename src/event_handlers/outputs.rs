@@ -1,24 +1,86 @@
+use super::statistics::DiscardStats;
 use super::Command::AddToUsizeField;
 use super::*;
 
 use anyhow::{Context, Result};
 use futures::future::{BoxFuture, FutureExt};
-use tokio::sync::{mpsc, oneshot};
+use tokio::{
+    net::TcpStream,
+    sync::{mpsc, oneshot},
+    time::{self, Duration, MissedTickBehavior},
+};
 
 use crate::{
     config::Configuration,
+    export::format_meta,
+    metadata::RunMetadata,
+    nuclei::NucleiTargets,
     progress::PROGRESS_PRINTER,
-    response::FeroxResponse,
+    response::{body_redactions, FeroxResponse, JSON_BODY_LIMIT, JSON_BODY_REDACTIONS, SHOW_WORD},
+    sarif::SarifLog,
     scanner::RESPONSES,
     send_command, skip_fail,
-    statistics::StatField::{ResourcesDiscovered, TotalExpected},
+    statistics::StatField::{ReplayErrors, ResourcesDiscovered, TotalExpected},
+    tags::Tagger,
+    template::{OutputTemplate, OUTPUT_TEMPLATE},
     traits::FeroxSerialize,
-    utils::{ferox_print, fmt_err, make_request, open_file, write_to},
-    CommandReceiver, CommandSender, Joiner,
+    utils::{
+        buffer_to, ferox_print, fmt_err, make_request, make_traced_request, open_file,
+        slugify_filename, write_to,
+    },
+    CommandReceiver, CommandSender, Joiner, REPLAY_BUFFER_SIZE, REPLAY_HEALTH_INTERVAL,
+};
+use std::{
+    collections::{hash_map::Entry, HashMap, VecDeque},
+    fs::File,
+    io::{BufWriter, Write},
+    path::Path,
+    sync::{atomic::Ordering, Arc, Mutex},
 };
-use std::sync::Arc;
 use url::Url;
 
+/// file within --output-dir that holds everything not tied to a single target: the
+/// configuration, log messages, and the statistics shared by all targets
+const RUN_FILE_NAME: &str = "ferox-run.log";
+
+/// file to which the configuration and statistics are written; --output, or the run file
+/// within --output-dir
+pub(super) fn run_file(config: &Configuration) -> String {
+    if config.output_dir.is_empty() {
+        return config.output.clone();
+    }
+
+    Path::new(&config.output_dir)
+        .join(RUN_FILE_NAME)
+        .to_string_lossy()
+        .to_string()
+}
+
+#[derive(Debug, Clone)]
+/// A finding that still needs to be sent through the replay proxy
+struct PendingReplay {
+    /// url of the finding
+    url: Url,
+
+    /// HTTP method used to request the finding
+    method: String,
+
+    /// trace id of the original request, empty if unknown
+    trace_id: String,
+}
+
+/// implementation of PendingReplay
+impl PendingReplay {
+    /// capture the parts of a `FeroxResponse` needed to replay its request
+    fn from(response: &FeroxResponse) -> Self {
+        Self {
+            url: response.url().to_owned(),
+            method: response.method().to_string(),
+            trace_id: response.trace_id().to_string(),
+        }
+    }
+}
+
 #[derive(Debug, Copy, Clone)]
 /// Simple enum for semantic clarity around calling expectations for `process_response`
 enum ProcessResponseCall {
@@ -53,9 +115,13 @@ impl TermOutHandle {
     }
 
     /// Sync the handle with the handler
+    ///
+    /// the terminal handler is synced first, so that any findings it was still processing have
+    /// been forwarded to the file handler by the time the file handler is synced (and flushed)
     pub async fn sync(&self, send_to_file: bool) -> Result<()> {
         let (tx, rx) = oneshot::channel::<bool>();
         self.send(Command::Sync(tx))?;
+        rx.await?;
 
         if send_to_file {
             let (tx, rx) = oneshot::channel::<bool>();
@@ -63,7 +129,6 @@ impl TermOutHandle {
             rx.await?;
         }
 
-        rx.await?;
         Ok(())
     }
 }
@@ -76,6 +141,10 @@ pub struct FileOutHandler {
 
     /// pointer to "global" configuration struct
     config: Arc<Configuration>,
+
+    /// details about the run, written ahead of everything else when --json is used; only
+    /// gathered once the handler starts writing json
+    metadata: Option<RunMetadata>,
 }
 
 impl FileOutHandler {
@@ -85,7 +154,18 @@ impl FileOutHandler {
         Self {
             receiver: rx,
             config,
+            metadata: None,
+        }
+    }
+
+    /// start off a newly opened output file with the run's metadata (json only), followed by
+    /// the configuration
+    fn write_preamble(&self, file: &mut BufWriter<File>) -> Result<()> {
+        if let Some(metadata) = &self.metadata {
+            write_to(metadata, file, true)?;
         }
+
+        write_to(&*self.config, file, self.config.json)
     }
 
     /// Spawn a single consumer task (sc side of mpsc)
@@ -94,16 +174,42 @@ impl FileOutHandler {
     async fn start(&mut self, tx_stats: CommandSender) -> Result<()> {
         log::trace!("enter: start_file_handler({:?})", tx_stats);
 
+        if self.config.sarif {
+            return self.start_sarif().await;
+        }
+
+        if self.config.csv {
+            return self.start_csv().await;
+        }
+
+        if self.config.json {
+            self.metadata = Some(RunMetadata::new(&self.config).await);
+        }
+
+        if !self.config.output_dir.is_empty() {
+            return self.start_per_target(tx_stats).await;
+        }
+
         let mut file = open_file(&self.config.output)?;
 
         log::info!("Writing scan results to {}", self.config.output);
 
-        write_to(&*self.config, &mut file, self.config.json)?;
+        self.write_preamble(&mut file)?;
+
+        // findings written since the last flush; flushed every --flush-every findings, or
+        // whenever something else is written or a sync is requested
+        let mut unflushed = 0;
 
         while let Some(command) = self.receiver.recv().await {
             match command {
                 Command::Report(response) => {
-                    skip_fail!(write_to(&*response, &mut file, self.config.json));
+                    skip_fail!(buffer_to(&*response, &mut file, self.config.json));
+                    unflushed += 1;
+
+                    if unflushed >= self.config.flush_every.max(1) {
+                        skip_fail!(file.flush());
+                        unflushed = 0;
+                    }
                 }
                 Command::WriteToDisk(message) => {
                     // todo consider making report accept dyn FeroxSerialize; would mean adding
@@ -111,10 +217,30 @@ impl FileOutHandler {
                     //  implementing structs
                     skip_fail!(write_to(&*message, &mut file, self.config.json));
                 }
+                Command::WriteSurvey(survey) => {
+                    skip_fail!(write_to(&*survey, &mut file, self.config.json));
+                }
+                Command::WriteGraphql(schema) => {
+                    skip_fail!(write_to(&*schema, &mut file, self.config.json));
+                }
+                Command::WriteDiscoveredHost(host) => {
+                    skip_fail!(write_to(&*host, &mut file, self.config.json));
+                }
+                Command::WriteServiceDescription(description) => {
+                    skip_fail!(write_to(&*description, &mut file, self.config.json));
+                }
+                Command::WriteError(event) => {
+                    skip_fail!(write_to(&*event, &mut file, self.config.json));
+                }
+                Command::WriteSkip(event) => {
+                    skip_fail!(write_to(&*event, &mut file, self.config.json));
+                }
                 Command::Exit => {
                     break;
                 }
                 Command::Sync(sender) => {
+                    skip_fail!(file.flush());
+                    unflushed = 0;
                     skip_fail!(sender.send(true));
                 }
                 _ => {} // no more needed
@@ -129,6 +255,217 @@ impl FileOutHandler {
         log::trace!("exit: start_file_handler");
         Ok(())
     }
+
+    /// Consumer used in place of the normal one when --sarif is used
+    ///
+    /// a SARIF log is a single json document, so findings are collected until the scan ends,
+    /// then written out all at once. Nothing else (config, statistics, etc) is written, since
+    /// it would make the document invalid
+    async fn start_sarif(&mut self) -> Result<()> {
+        log::trace!("enter: start_sarif");
+
+        // created up front so that an unwritable location is reported before scanning starts
+        let mut file = std::io::BufWriter::new(
+            std::fs::File::create(&self.config.output)
+                .with_context(|| fmt_err(&format!("Could not open {}", self.config.output)))?,
+        );
+
+        log::info!("Writing SARIF results to {}", self.config.output);
+
+        let mut log = SarifLog::new(&self.config.run_id, &self.config.meta);
+
+        while let Some(command) = self.receiver.recv().await {
+            match command {
+                Command::Report(response) => log.add(&response),
+                Command::Sync(sender) => {
+                    skip_fail!(sender.send(true));
+                }
+                Command::Exit => break,
+                _ => {} // only findings are part of the log
+            }
+        }
+
+        file.write_all(log.to_json()?.as_bytes())?;
+        file.flush()?;
+
+        log::info!(
+            "wrote {} SARIF result(s) to {}",
+            log.len(),
+            self.config.output
+        );
+
+        log::trace!("exit: start_sarif");
+        Ok(())
+    }
+
+    /// Consumer used in place of the normal one when --csv is used
+    ///
+    /// a header row is written first, followed by a row per finding. Nothing else (config,
+    /// statistics, etc) is written, since it wouldn't fit the columns
+    async fn start_csv(&mut self) -> Result<()> {
+        log::trace!("enter: start_csv");
+
+        // truncated rather than appended to, so the header only ever shows up on the first line
+        let mut file = std::io::BufWriter::new(
+            std::fs::File::create(&self.config.output)
+                .with_context(|| fmt_err(&format!("Could not open {}", self.config.output)))?,
+        );
+
+        log::info!("Writing CSV results to {}", self.config.output);
+
+        // --meta pairs lead the file as comment lines, which csv readers can be told to skip
+        file.write_all(format_meta(&self.config.meta).as_bytes())?;
+        file.write_all(format!("{}\n", self.config.csv_columns.join(",")).as_bytes())?;
+        file.flush()?;
+
+        let mut unflushed = 0;
+
+        while let Some(command) = self.receiver.recv().await {
+            match command {
+                Command::Report(response) => {
+                    if let Some(row) = response.as_csv(&self.config.csv_columns) {
+                        skip_fail!(file.write_all(row.as_bytes()));
+                        unflushed += 1;
+                    }
+
+                    if unflushed >= self.config.flush_every.max(1) {
+                        skip_fail!(file.flush());
+                        unflushed = 0;
+                    }
+                }
+                Command::Sync(sender) => {
+                    skip_fail!(file.flush());
+                    unflushed = 0;
+                    skip_fail!(sender.send(true));
+                }
+                Command::Exit => break,
+                _ => {} // only findings are written as rows
+            }
+        }
+
+        file.flush()?;
+
+        log::trace!("exit: start_csv");
+        Ok(())
+    }
+
+    /// Consumer used in place of the normal one when --output-dir is used
+    ///
+    /// findings, header surveys, and error/skip events are split into a file per target, named
+    /// after the target's origin the same way --parallel names its files. Everything else goes
+    /// to the run file, since it isn't tied to a single target
+    async fn start_per_target(&mut self, tx_stats: CommandSender) -> Result<()> {
+        log::trace!("enter: start_per_target");
+
+        std::fs::create_dir_all(&self.config.output_dir)
+            .with_context(|| fmt_err(&format!("Could not create {}", self.config.output_dir)))?;
+
+        let mut run_file = open_file(&run_file(&self.config))?;
+
+        log::info!("Writing scan results to {}", self.config.output_dir);
+
+        self.write_preamble(&mut run_file)?;
+
+        let mut files = HashMap::new();
+        let mut unflushed = 0;
+
+        while let Some(command) = self.receiver.recv().await {
+            match command {
+                Command::Report(response) => {
+                    let file = skip_fail!(self.target_file(&mut files, response.url().as_str()));
+                    skip_fail!(buffer_to(&*response, file, self.config.json));
+                    unflushed += 1;
+
+                    if unflushed >= self.config.flush_every.max(1) {
+                        skip_fail!(file.flush());
+                        unflushed = 0;
+                    }
+                }
+                Command::WriteToDisk(message) => {
+                    skip_fail!(write_to(&*message, &mut run_file, self.config.json));
+                }
+                Command::WriteSurvey(survey) => {
+                    let file = skip_fail!(self.target_file(&mut files, &survey.url));
+                    skip_fail!(write_to(&*survey, file, self.config.json));
+                }
+                Command::WriteGraphql(schema) => {
+                    let file = skip_fail!(self.target_file(&mut files, &schema.url));
+                    skip_fail!(write_to(&*schema, file, self.config.json));
+                }
+                Command::WriteDiscoveredHost(host) => {
+                    // the host isn't a target, it goes with the page it was found on
+                    let file = skip_fail!(self.target_file(&mut files, &host.found_on));
+                    skip_fail!(write_to(&*host, file, self.config.json));
+                }
+                Command::WriteServiceDescription(description) => {
+                    let file = skip_fail!(self.target_file(&mut files, &description.url));
+                    skip_fail!(write_to(&*description, file, self.config.json));
+                }
+                Command::WriteError(event) => {
+                    let file = skip_fail!(self.target_file(&mut files, &event.url));
+                    skip_fail!(write_to(&*event, file, self.config.json));
+                }
+                Command::WriteSkip(event) => {
+                    let file = skip_fail!(self.target_file(&mut files, &event.url));
+                    skip_fail!(write_to(&*event, file, self.config.json));
+                }
+                Command::Exit => {
+                    break;
+                }
+                Command::Sync(sender) => {
+                    for file in files.values_mut() {
+                        skip_fail!(file.flush());
+                    }
+                    unflushed = 0;
+                    skip_fail!(sender.send(true));
+                }
+                _ => {} // no more needed
+            }
+        }
+
+        for file in files.values_mut() {
+            file.flush()?;
+        }
+
+        // close the run file before we tell statistics to save current data to the same file
+        drop(run_file);
+
+        send_command!(tx_stats, Command::Save);
+
+        log::trace!("exit: start_per_target");
+        Ok(())
+    }
+
+    /// file for the target the given url belongs to, opened (and started off with the
+    /// preamble, like any other output file) the first time the target is seen
+    fn target_file<'a>(
+        &self,
+        files: &'a mut HashMap<String, BufWriter<File>>,
+        url: &str,
+    ) -> Result<&'a mut BufWriter<File>> {
+        let origin = Url::parse(url)
+            .map(|parsed| parsed.origin().ascii_serialization())
+            .unwrap_or_else(|_| url.to_string());
+
+        match files.entry(origin) {
+            Entry::Occupied(entry) => Ok(entry.into_mut()),
+            Entry::Vacant(entry) => {
+                let filename = slugify_filename(entry.key(), "ferox", "log");
+                let path = Path::new(&self.config.output_dir).join(filename);
+
+                let mut file = open_file(&path.to_string_lossy())?;
+                self.write_preamble(&mut file)?;
+
+                log::info!(
+                    "Writing scan results for {} to {}",
+                    entry.key(),
+                    path.display()
+                );
+
+                Ok(entry.insert(file))
+            }
+        }
+    }
 }
 
 #[derive(Debug)]
@@ -148,6 +485,28 @@ pub struct TermOutHandler {
 
     /// handles instance
     handles: Option<Arc<Handles>>,
+
+    /// replays that couldn't be sent because the replay proxy was unreachable; retried once
+    /// the proxy passes a health check
+    replay_buffer: Mutex<VecDeque<PendingReplay>>,
+
+    /// notification handler, when --notify-webhook was used
+    tx_notify: Option<CommandSender>,
+
+    /// optional notification handler task
+    notify_task: Option<Joiner>,
+
+    /// zap handler, when --zap-api-url was used
+    tx_zap: Option<CommandSender>,
+
+    /// optional zap handler task
+    zap_task: Option<Joiner>,
+
+    /// applies --tag-findings / --tag-rule tags to findings, when either was used
+    tagger: Option<Tagger>,
+
+    /// appends findings to --nuclei-targets as they're reported, when it was used
+    nuclei_targets: Option<NucleiTargets>,
 }
 
 /// implementation of TermOutHandler
@@ -160,12 +519,32 @@ impl TermOutHandler {
         file_task: Option<Joiner>,
         config: Arc<Configuration>,
     ) -> Self {
+        let (notify_task, tx_notify) = match NotificationHandler::initialize(&config) {
+            Some((task, tx)) => (Some(task), Some(tx)),
+            None => (None, None),
+        };
+
+        let (zap_task, tx_zap) = match ZapHandler::initialize(&config) {
+            Some((task, tx)) => (Some(task), Some(tx)),
+            None => (None, None),
+        };
+
+        let tagger = Tagger::new(&config);
+        let nuclei_targets = NucleiTargets::new(&config);
+
         Self {
             receiver,
             tx_file,
             file_task,
             config,
             handles: None,
+            replay_buffer: Mutex::new(VecDeque::new()),
+            tx_notify,
+            notify_task,
+            tx_zap,
+            zap_task,
+            tagger,
+            nuclei_targets,
         }
     }
 
@@ -179,12 +558,40 @@ impl TermOutHandler {
         let (tx_term, rx_term) = mpsc::unbounded_channel::<Command>();
         let (tx_file, rx_file) = mpsc::unbounded_channel::<Command>();
 
+        if config.json {
+            // bodies are only captured when they'll end up in NDJSON output
+            JSON_BODY_LIMIT.store(config.json_include_body, Ordering::Relaxed);
+
+            if config.json_include_body > 0 {
+                if let Ok(mut secrets) = JSON_BODY_REDACTIONS.write() {
+                    *secrets = body_redactions(&config);
+                }
+            }
+        }
+
+        if config.verbosity > 0 {
+            // the wordlist entry behind each finding is always in NDJSON output, but only
+            // clutters the terminal when asked for
+            SHOW_WORD.store(true, Ordering::Relaxed);
+        }
+
+        if !config.output_format.is_empty() {
+            // templates from the cli were already checked, only a bad one from a config file can
+            // fail here
+            match OutputTemplate::parse(&config.output_format) {
+                Ok(template) => {
+                    let _ = OUTPUT_TEMPLATE.set(template);
+                }
+                Err(e) => log::warn!("Ignoring --output-format {}: {}", config.output_format, e),
+            }
+        }
+
         let mut file_handler = FileOutHandler::new(rx_file, config.clone());
 
         let tx_stats_clone = tx_stats.clone();
 
-        let file_task = if !config.output.is_empty() {
-            // -o used, need to spawn the thread for writing to disk
+        let file_task = if !config.output.is_empty() || !config.output_dir.is_empty() {
+            // -o or --output-dir used, need to spawn the thread for writing to disk
             Some(tokio::spawn(async move {
                 file_handler.start(tx_stats_clone).await
             }))
@@ -208,7 +615,24 @@ impl TermOutHandler {
     async fn start(&mut self, tx_stats: CommandSender) -> Result<()> {
         log::trace!("enter: start({:?})", tx_stats);
 
-        while let Some(command) = self.receiver.recv().await {
+        // buffered replays are retried on this interval, as long as the replay proxy is healthy
+        let mut ticker = time::interval(Duration::from_secs(REPLAY_HEALTH_INTERVAL));
+        ticker.set_missed_tick_behavior(MissedTickBehavior::Delay);
+
+        loop {
+            let command = tokio::select! {
+                command = self.receiver.recv() => match command {
+                    Some(command) => command,
+                    None => break,
+                },
+                _ = ticker.tick() => {
+                    if self.buffered_replays() > 0 && self.replay_proxy_is_healthy().await {
+                        self.flush_replays(tx_stats.clone()).await;
+                    }
+                    continue;
+                }
+            };
+
             match command {
                 Command::Report(resp) => {
                     if let Err(err) = self
@@ -225,9 +649,41 @@ impl TermOutHandler {
                     self.handles = Some(handles);
                 }
                 Command::Exit => {
+                    if self.buffered_replays() > 0 && self.replay_proxy_is_healthy().await {
+                        // one last shot at getting buffered findings to the replay proxy
+                        self.flush_replays(tx_stats.clone()).await;
+                    }
+
+                    let remaining = self.buffered_replays();
+
+                    if remaining > 0 {
+                        log::warn!(
+                            "{} finding(s) never reached the replay proxy ({})",
+                            remaining,
+                            self.config.replay_proxy
+                        );
+                    }
+
                     if self.file_task.is_some() && self.tx_file.send(Command::Exit).is_ok() {
                         self.file_task.as_mut().unwrap().await??; // wait for death
                     }
+
+                    if let (Some(tx_notify), Some(notify_task)) =
+                        (self.tx_notify.as_ref(), self.notify_task.as_mut())
+                    {
+                        // pending notifications go out before the end of scan summary
+                        if tx_notify.send(Command::Exit).is_ok() {
+                            notify_task.await??;
+                        }
+                    }
+
+                    if let (Some(tx_zap), Some(zap_task)) =
+                        (self.tx_zap.as_ref(), self.zap_task.as_mut())
+                    {
+                        if tx_zap.send(Command::Exit).is_ok() {
+                            zap_task.await??;
+                        }
+                    }
                     break;
                 }
                 _ => {} // no more commands needed
@@ -262,6 +718,19 @@ impl TermOutHandler {
             let should_process_response = contains_sentry && unknown_sentry;
 
             if should_process_response {
+                if let Some(tagger) = self.tagger.as_ref() {
+                    // tags go on before the finding is printed, written, or otherwise passed along
+                    let tags = tagger.tags(&resp);
+                    resp.set_tags(tags);
+                }
+
+                if let Some(handles) = self.handles.as_ref() {
+                    // targets read from stdin may override --quiet|--silent for their findings
+                    if let Ok(scans) = handles.ferox_scans() {
+                        resp.output_level = scans.output_level_for(resp.url().as_str());
+                    }
+                }
+
                 // print to stdout
                 ferox_print(&resp.as_str(), &PROGRESS_PRINTER);
 
@@ -278,26 +747,50 @@ impl TermOutHandler {
             }
             log::trace!("report complete: {}", resp.url());
 
+            if let Some(nuclei_targets) = self.nuclei_targets.as_ref() {
+                if should_process_response {
+                    if let Err(e) = nuclei_targets.add(&resp) {
+                        log::warn!("{:#}", e);
+                    }
+                }
+            }
+
+            if let Some(tx_notify) = self.tx_notify.as_ref() {
+                if should_process_response {
+                    // the notification handler applies --notify-min-status/--notify-rate-limit
+                    tx_notify
+                        .send(Command::Report(resp.clone()))
+                        .with_context(|| {
+                            fmt_err(&format!("Could not send {resp} to notification handler"))
+                        })?;
+                }
+            }
+
+            if let Some(tx_zap) = self.tx_zap.as_ref() {
+                if should_process_response {
+                    tx_zap.send(Command::Report(resp.clone())).with_context(|| {
+                        fmt_err(&format!("Could not send {resp} to zap handler"))
+                    })?;
+                }
+            }
+
             if self.config.replay_client.is_some() && should_process_response {
                 // replay proxy specified/client created and this response's status code is one that
-                // should be replayed; not using logged_request due to replay proxy client
-                let data = if self.config.data.is_empty() {
-                    None
-                } else {
-                    Some(self.config.data.as_slice())
-                };
-
-                make_request(
-                    self.config.replay_client.as_ref().unwrap(),
-                    resp.url(),
-                    resp.method().as_str(),
-                    data,
-                    self.config.output_level,
-                    &self.config,
-                    tx_stats.clone(),
-                )
-                .await
-                .with_context(|| "Could not replay request through replay proxy")?;
+                // should be replayed
+                let pending = PendingReplay::from(&resp);
+
+                if self.buffered_replays() > 0 {
+                    // the proxy was unreachable recently; get in line behind the other findings
+                    // so they reach the proxy in the order they were found
+                    self.buffer_replay(pending);
+                } else if let Err(e) = self.replay(&pending).await {
+                    log::warn!(
+                        "Could not replay request through replay proxy, buffering until it's reachable: {:#}",
+                        e
+                    );
+                    send_command!(tx_stats, AddToUsizeField(ReplayErrors, 1));
+                    self.buffer_replay(pending);
+                }
             }
 
             if self.config.collect_backups
@@ -375,6 +868,126 @@ impl TermOutHandler {
         .boxed()
     }
 
+    /// send a single finding through the replay proxy; not using logged_request due to the
+    /// replay proxy client
+    ///
+    /// replayed requests aren't part of the scan, so their outcomes stay out of its statistics;
+    /// callers count failures as ReplayErrors instead
+    async fn replay(&self, pending: &PendingReplay) -> Result<()> {
+        let Some(replay_client) = self.config.replay_client.as_ref() else {
+            return Ok(());
+        };
+
+        let data = if self.config.data.is_empty() {
+            None
+        } else {
+            Some(self.config.data.as_slice())
+        };
+
+        // reuse the original request's trace id, so the replayed request can be tied
+        // back to the finding (sent via the X-Ferox-Trace header)
+        let trace = if pending.trace_id.is_empty() {
+            None
+        } else {
+            Some(pending.trace_id.as_str())
+        };
+
+        make_traced_request(
+            replay_client,
+            &pending.url,
+            &pending.method,
+            data,
+            self.config.output_level,
+            &self.config,
+            DiscardStats,
+            trace,
+        )
+        .await
+        .with_context(|| "Could not replay request through replay proxy")?;
+
+        Ok(())
+    }
+
+    /// number of findings waiting to be sent through the replay proxy
+    fn buffered_replays(&self) -> usize {
+        self.replay_buffer
+            .lock()
+            .map(|buffer| buffer.len())
+            .unwrap_or_default()
+    }
+
+    /// add a finding to the back of the replay buffer, dropping the oldest finding if the buffer
+    /// is full
+    fn buffer_replay(&self, pending: PendingReplay) {
+        let Ok(mut buffer) = self.replay_buffer.lock() else {
+            log::warn!("Could not buffer {} for the replay proxy", pending.url);
+            return;
+        };
+
+        if buffer.len() >= REPLAY_BUFFER_SIZE {
+            if let Some(dropped) = buffer.pop_front() {
+                log::warn!(
+                    "replay buffer is full, {} will not be sent to the replay proxy",
+                    dropped.url
+                );
+            }
+        }
+
+        buffer.push_back(pending);
+    }
+
+    /// check that the replay proxy is accepting connections
+    async fn replay_proxy_is_healthy(&self) -> bool {
+        let Ok(proxy) = Url::parse(&self.config.replay_proxy) else {
+            return false;
+        };
+
+        let (Some(host), Some(port)) = (proxy.host_str(), proxy.port_or_known_default()) else {
+            return false;
+        };
+
+        let connection = time::timeout(
+            Duration::from_secs(REPLAY_HEALTH_INTERVAL),
+            TcpStream::connect((host, port)),
+        )
+        .await;
+
+        matches!(connection, Ok(Ok(_)))
+    }
+
+    /// send buffered findings through the replay proxy, in the order they were found; stops at
+    /// the first failure, leaving the rest in the buffer
+    async fn flush_replays(&self, tx_stats: CommandSender) {
+        let mut replayed = 0;
+
+        loop {
+            let pending = match self.replay_buffer.lock() {
+                Ok(mut buffer) => buffer.pop_front(),
+                Err(_) => None,
+            };
+
+            let Some(pending) = pending else {
+                break;
+            };
+
+            if let Err(e) = self.replay(&pending).await {
+                log::warn!("replay proxy is still unreachable: {:#}", e);
+                send_command!(tx_stats, AddToUsizeField(ReplayErrors, 1));
+
+                if let Ok(mut buffer) = self.replay_buffer.lock() {
+                    buffer.push_front(pending);
+                }
+                break;
+            }
+
+            replayed += 1;
+        }
+
+        if replayed > 0 {
+            log::info!("sent {} buffered finding(s) to the replay proxy", replayed);
+        }
+    }
+
     /// internal helper to stay DRY
     fn add_new_url_to_vec(&self, url: &Url, new_name: &str, urls: &mut Vec<Url>) {
         if let Ok(joined) = url.join(new_name) {
@@ -442,12 +1055,108 @@ mod tests {
         let (_, rx) = mpsc::unbounded_channel::<Command>();
         let config = Arc::new(Configuration::new().unwrap());
         let foh = FileOutHandler {
+            metadata: None,
             config,
             receiver: rx,
         };
         println!("{foh:?}");
     }
 
+    #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+    /// --output-dir splits findings into a file per target, with everything else in the run file
+    async fn file_out_handler_splits_findings_per_target() {
+        let directory = tempfile::tempdir().unwrap();
+
+        let config = Arc::new(Configuration {
+            output_dir: directory.path().to_string_lossy().to_string(),
+            ..Default::default()
+        });
+
+        let (tx, rx) = mpsc::unbounded_channel::<Command>();
+        let (tx_stats, mut rx_stats) = mpsc::unbounded_channel::<Command>();
+
+        let mut handler = FileOutHandler::new(rx, config.clone());
+
+        for url in [
+            "http://one.local/admin",
+            "http://two.local:8080/login",
+            "http://one.local/backup",
+        ] {
+            let mut response = FeroxResponse::default();
+            response.set_url(url);
+            tx.send(Command::Report(Box::new(response))).unwrap();
+        }
+
+        tx.send(Command::Exit).unwrap();
+        handler.start(tx_stats).await.unwrap();
+
+        assert!(matches!(rx_stats.recv().await, Some(Command::Save)));
+
+        let mut contents = std::fs::read_dir(directory.path())
+            .unwrap()
+            .map(|entry| {
+                let path = entry.unwrap().path();
+                let name = path.file_name().unwrap().to_string_lossy().to_string();
+                (name, std::fs::read_to_string(&path).unwrap())
+            })
+            .collect::<Vec<_>>();
+
+        contents.sort();
+
+        assert_eq!(contents.len(), 3);
+        assert!(contents[0].0.starts_with("ferox-http_one_local-"));
+        assert!(contents[0].1.contains("http://one.local/admin"));
+        assert!(contents[0].1.contains("http://one.local/backup"));
+        assert!(!contents[0].1.contains("two.local"));
+        assert!(contents[1].0.starts_with("ferox-http_two_local:8080-"));
+        assert!(contents[1].1.contains("http://two.local:8080/login"));
+        assert_eq!(contents[2].0, RUN_FILE_NAME);
+        assert_eq!(
+            run_file(&config),
+            directory.path().join(RUN_FILE_NAME).to_string_lossy()
+        );
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+    /// json output leads with the run's metadata, ahead of the configuration and any findings
+    async fn file_out_handler_leads_json_output_with_metadata() {
+        let directory = tempfile::tempdir().unwrap();
+        let output = directory.path().join("results.json");
+
+        let config = Arc::new(Configuration {
+            output: output.to_string_lossy().to_string(),
+            target_url: String::from("http://one.local"),
+            json: true,
+            ..Default::default()
+        });
+
+        let (tx, rx) = mpsc::unbounded_channel::<Command>();
+        let (tx_stats, _rx_stats) = mpsc::unbounded_channel::<Command>();
+
+        let mut handler = FileOutHandler::new(rx, config);
+
+        let mut response = FeroxResponse::default();
+        response.set_url("http://one.local/admin");
+        tx.send(Command::Report(Box::new(response))).unwrap();
+        tx.send(Command::Exit).unwrap();
+
+        handler.start(tx_stats).await.unwrap();
+
+        let contents = std::fs::read_to_string(&output).unwrap();
+        let records = contents
+            .lines()
+            .map(|line| serde_json::from_str::<serde_json::Value>(line).unwrap())
+            .collect::<Vec<_>>();
+
+        assert_eq!(records.len(), 3);
+        assert_eq!(records[0]["type"], "metadata");
+        assert_eq!(records[0]["schema_version"], crate::OUTPUT_SCHEMA_VERSION);
+        assert_eq!(records[0]["targets"][0], "http://one.local");
+        assert_eq!(records[1]["type"], "configuration");
+        assert_eq!(records[2]["type"], "response");
+        assert_eq!(records[2]["schema_version"], crate::OUTPUT_SCHEMA_VERSION);
+    }
+
     #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
     /// try to hit struct field coverage of TermOutHandler
     async fn struct_fields_of_term_out_handler() {
@@ -462,12 +1171,41 @@ mod tests {
             receiver: rx,
             tx_file,
             handles: Some(handles),
+            replay_buffer: Mutex::new(VecDeque::new()),
+            tx_notify: None,
+            notify_task: None,
+            tagger: None,
+            nuclei_targets: None,
         };
 
         println!("{toh:?}");
         tx.send(Command::Exit).unwrap();
     }
 
+    #[test]
+    /// the replay buffer is bounded, dropping the oldest findings first
+    fn replay_buffer_drops_oldest_when_full() {
+        let (_, rx) = mpsc::unbounded_channel::<Command>();
+        let (tx_file, _) = mpsc::unbounded_channel::<Command>();
+        let config = Arc::new(Configuration::new().unwrap());
+        let toh = TermOutHandler::new(rx, tx_file, None, config);
+
+        for i in 0..=REPLAY_BUFFER_SIZE {
+            let mut fr = FeroxResponse::default();
+            fr.set_url(&format!("http://localhost/{i}"));
+            toh.buffer_replay(PendingReplay::from(&fr));
+        }
+
+        assert_eq!(toh.buffered_replays(), REPLAY_BUFFER_SIZE);
+
+        let buffer = toh.replay_buffer.lock().unwrap();
+        assert_eq!(buffer.front().unwrap().url.path(), "/1");
+        assert_eq!(
+            buffer.back().unwrap().url.path(),
+            format!("/{REPLAY_BUFFER_SIZE}")
+        );
+    }
+
     #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
     /// when the feroxresponse's url contains an extension, there should be 7 urls returned
     async fn generate_backup_urls_creates_correct_urls_when_extension_present() {
@@ -482,6 +1220,11 @@ mod tests {
             receiver: rx,
             tx_file,
             handles: Some(handles),
+            replay_buffer: Mutex::new(VecDeque::new()),
+            tx_notify: None,
+            notify_task: None,
+            tagger: None,
+            nuclei_targets: None,
         };
 
         let expected: Vec<_> = vec![
@@ -527,6 +1270,11 @@ mod tests {
             receiver: rx,
             tx_file,
             handles: Some(handles),
+            replay_buffer: Mutex::new(VecDeque::new()),
+            tx_notify: None,
+            notify_task: None,
+            tagger: None,
+            nuclei_targets: None,
         };
 
         let expected: Vec<_> = vec![
@@ -572,6 +1320,11 @@ mod tests {
             receiver: rx,
             tx_file,
             handles: Some(handles),
+            replay_buffer: Mutex::new(VecDeque::new()),
+            tx_notify: None,
+            notify_task: None,
+            tagger: None,
+            nuclei_targets: None,
         };
 
         let expected: Vec<_> = vec![
@@ -1,19 +1,25 @@
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 
 use anyhow::{bail, Result};
-use tokio::sync::{mpsc, Semaphore};
+use tokio::sync::mpsc;
 
 use crate::{
+    config::Denier,
+    message::SkipEvent,
+    progress::print_above_bars,
     response::FeroxResponse,
     scan_manager::{FeroxScan, FeroxScans, ScanOrder},
     scanner::{FeroxScanner, RESPONSES},
+    semaphore::ScanLimiter,
     statistics::StatField::TotalScans,
     url::FeroxUrl,
-    utils::should_deny_url,
-    CommandReceiver, CommandSender, FeroxChannel, Joiner, SLEEP_DURATION,
+    utils::{has_denylist, should_deny_url},
+    wordlists::Wordlist,
+    CommandReceiver, CommandSender, FeroxChannel, Joiner, SLEEP_DURATION, SPA_MAX_DEPTH,
 };
 
-use super::command::Command::AddToUsizeField;
+use super::command::Command::{AddToUsizeField, SubtractFromUsizeField};
 use super::*;
 use crate::statistics::StatField;
 use crate::utils::parse_url_with_raw_path;
@@ -56,7 +62,7 @@ pub struct ScanHandler {
     receiver: CommandReceiver,
 
     /// wordlist (re)used for each scan
-    wordlist: std::sync::Mutex<Option<Arc<Vec<String>>>>,
+    wordlist: std::sync::Mutex<Option<Arc<Wordlist>>>,
 
     /// group of scans that need to be joined
     tasks: Vec<Arc<FeroxScan>>,
@@ -67,8 +73,15 @@ pub struct ScanHandler {
     /// depths associated with the initial targets provided by the user
     depths: Vec<(String, usize)>,
 
-    /// Bounded semaphore used as a barrier to limit concurrent scans
-    limiter: Arc<Semaphore>,
+    /// Bounded limiter used as a barrier to limit concurrent scans
+    limiter: Arc<ScanLimiter>,
+
+    /// directories held back by --recurse-threshold until their parent scan finishes, keyed by
+    /// the parent scan's url
+    deferred: HashMap<String, Vec<Box<FeroxResponse>>>,
+
+    /// urls of parent scans whose deferred directories have already been decided on
+    decided: HashSet<String>,
 }
 
 /// implementation of event handler for filters
@@ -80,19 +93,8 @@ impl ScanHandler {
         max_depth: usize,
         receiver: CommandReceiver,
     ) -> Self {
-        let limit = handles.config.scan_limit;
-        let limiter = Semaphore::new(limit);
-
-        if limit == 0 {
-            // scan_limit == 0 means no limit should be imposed... however, scoping the Semaphore
-            // permit is tricky, so as a workaround, we'll add a ridiculous number of permits to
-            // the semaphore (1,152,921,504,606,846,975 to be exact) and call that 'unlimited'
-
-            // note to self: the docs say max is usize::MAX >> 3, however, threads will panic if
-            // that value is used (says adding (1) will overflow the semaphore, even though none
-            // are being added...)
-            limiter.add_permits(usize::MAX >> 4);
-        }
+        // scan_limit == 0 means no limit should be imposed
+        let limiter = ScanLimiter::new(handles.config.scan_limit);
 
         Self {
             data,
@@ -103,11 +105,13 @@ impl ScanHandler {
             depths: Vec::new(),
             limiter: Arc::new(limiter),
             wordlist: std::sync::Mutex::new(None),
+            deferred: HashMap::new(),
+            decided: HashSet::new(),
         }
     }
 
     /// Set the wordlist
-    fn wordlist(&self, wordlist: Arc<Vec<String>>) {
+    fn wordlist(&self, wordlist: Arc<Wordlist>) {
         if let Ok(mut guard) = self.wordlist.lock() {
             if guard.is_none() {
                 let _ = std::mem::replace(&mut *guard, Some(wordlist));
@@ -123,6 +127,7 @@ impl ScanHandler {
         let data = Arc::new(FeroxScans::new(
             handles.config.output_level,
             handles.config.limit_bars,
+            handles.config.case_insensitive,
         ));
         let (tx, rx): FeroxChannel<Command> = mpsc::unbounded_channel();
 
@@ -162,21 +167,35 @@ impl ScanHandler {
                 Command::UpdateWordlist(wordlist) => {
                     self.wordlist(wordlist);
                 }
+                Command::UpdateDenylist(deniers) => {
+                    self.update_denylist(deniers).await;
+                }
                 Command::JoinTasks(sender) => {
                     let ferox_scans = self.handles.ferox_scans().unwrap_or_default();
                     let limiter_clone = self.limiter.clone();
+                    let handles = self.handles.clone();
 
                     tokio::spawn(async move {
                         while ferox_scans.has_active_scans() {
                             tokio::time::sleep(Duration::from_millis(SLEEP_DURATION + 250)).await;
                         }
                         limiter_clone.close();
+
+                        if handles.is_cancelled() {
+                            // cancelled runs are saved here, rather than by whatever is driving
+                            // the scan, so that embedders get a resumable state file for free
+                            save_cancelled_run(handles).await;
+                        }
+
                         sender.send(true).expect("oneshot channel failed");
                     });
                 }
                 Command::TryRecursion(response) => {
                     self.try_recursion(response).await?;
                 }
+                Command::ScanFinished(url) => {
+                    self.release_deferred(&url).await?;
+                }
                 Command::Sync(sender) => {
                     sender.send(true).unwrap_or_default();
                 }
@@ -293,11 +312,11 @@ impl ScanHandler {
     }
 
     /// Helper to easily get the (locked) underlying wordlist
-    pub fn get_wordlist(&self, offset: usize) -> Result<Arc<Vec<String>>> {
+    pub fn get_wordlist(&self, offset: usize) -> Result<Arc<Wordlist>> {
         if let Ok(guard) = self.wordlist.lock().as_ref() {
             if let Some(list) = guard.as_ref() {
                 return if offset > 0 {
-                    Ok(Arc::new(list[offset..].to_vec()))
+                    Ok(Arc::new(list.skip(offset)))
                 } else {
                     Ok(list.clone())
                 };
@@ -310,8 +329,7 @@ impl ScanHandler {
     /// wrapper around scanning a url to stay DRY
     async fn ordered_scan_url(&mut self, targets: Vec<String>, order: ScanOrder) -> Result<()> {
         log::trace!("enter: ordered_scan_url({:?}, {:?})", targets, order);
-        let should_test_deny = !self.handles.config.url_denylist.is_empty()
-            || !self.handles.config.regex_denylist.is_empty();
+        let should_test_deny = has_denylist(&self.handles);
 
         for target in targets {
             if self.data.contains(&target) && matches!(order, ScanOrder::Latest) {
@@ -336,22 +354,29 @@ impl ScanHandler {
                 // response was caught by a user-provided deny list
                 // checking this last, since it's most susceptible to longer runtimes due to what
                 // input is received
+                self.skipped(&target, "denied", "matched --dont-scan");
                 continue;
             }
 
             let divisor = self.handles.expected_num_requests_multiplier();
 
-            let list = if divisor > 1 && scan.requests() > 0 {
+            let offset = if let Some(word_index) = scan.word_index() {
+                // the state file recorded exactly how far through the wordlist the scan got
+                word_index as usize
+            } else if divisor > 1 && scan.requests() > 0 {
                 // if there were extensions provided and/or more than a single method used, and some
                 // number of requests have already been sent, we need to adjust the offset into the
                 // wordlist to ensure we don't index out of bounds
 
                 let adjusted = scan.requests_made_so_far() as f64 / (divisor as f64 - 1.0).max(1.0);
-                self.get_wordlist(adjusted as usize)?
+                adjusted as usize
             } else {
-                self.get_wordlist(scan.requests_made_so_far() as usize)?
+                scan.requests_made_so_far() as usize
             };
 
+            let list = self.get_wordlist(offset)?;
+            scan.start_checkpoint(offset);
+
             log::info!("scan handler received {} - beginning scan", target);
 
             if matches!(order, ScanOrder::Initial) {
@@ -387,6 +412,53 @@ impl ScanHandler {
         Ok(())
     }
 
+    /// swap in the latest --dont-scan-file entries, then cancel running and queued scans that
+    /// are now out of scope
+    async fn update_denylist(&self, deniers: Vec<Denier>) {
+        log::trace!("enter: update_denylist({:?})", deniers);
+
+        self.data.set_file_denylist(deniers.clone());
+
+        let (cancelled, num_cancelled) = self.data.cancel_denied_scans(&deniers).await;
+
+        if num_cancelled > 0 {
+            // the same as cancelling from the scan management menu, the requests these scans
+            // won't be making come off of the overall progress bar
+            self.handles
+                .stats
+                .send(SubtractFromUsizeField(
+                    StatField::TotalExpected,
+                    num_cancelled,
+                ))
+                .unwrap_or_else(|e| log::warn!("Could not update overall scan bar: {}", e));
+        }
+
+        for url in cancelled {
+            log::info!("cancelled scan of {} after --dont-scan-file changed", url);
+            self.skipped(&url, "denied", "matched --dont-scan-file");
+        }
+
+        log::trace!("exit: update_denylist");
+    }
+
+    /// write a skip event for a url that recursion deliberately passed over, when --skip-events
+    /// was used
+    fn skipped(&self, url: &str, reason: &str, detail: &str) {
+        if !self.handles.config.skip_events {
+            return;
+        }
+
+        log::debug!("{} skipped ({}): {}", url, reason, detail);
+
+        let event = SkipEvent::new(url, reason, detail);
+
+        self.handles
+            .output
+            .tx_file
+            .send(Command::WriteSkip(Box::new(event)))
+            .unwrap_or_default();
+    }
+
     async fn try_recursion(&mut self, response: Box<FeroxResponse>) -> Result<()> {
         log::trace!("enter: try_recursion({:?})", response,);
 
@@ -395,6 +467,16 @@ impl ScanHandler {
             return Ok(());
         }
 
+        if self.data.is_host_cancelled(response.url().as_str()) {
+            // all scans against this host were cancelled from the menu
+            self.skipped(
+                response.url().as_str(),
+                "host-cancelled",
+                "scans against this host were cancelled",
+            );
+            return Ok(());
+        }
+
         let mut base_depth = 1_usize;
 
         for (base_url, base_url_depth) in &self.depths {
@@ -403,11 +485,114 @@ impl ScanHandler {
             }
         }
 
-        if response.reached_max_depth(base_depth, self.max_depth, self.handles.clone()) {
+        let max_depth = if self.data.is_spa_url(response.url().as_str()) {
+            // single-page applications serve their app shell for (nearly) every path, recursing
+            // deeply into them rarely yields anything but noise
+            match self.max_depth {
+                0 => SPA_MAX_DEPTH,
+                depth => depth.min(SPA_MAX_DEPTH),
+            }
+        } else {
+            self.max_depth
+        };
+
+        if response.reached_max_depth(base_depth, max_depth, self.handles.clone()) {
             // at or past recursion depth
+            self.skipped(
+                response.url().as_str(),
+                "max-depth",
+                &format!("--depth of {max_depth} reached"),
+            );
+            return Ok(());
+        }
+
+        if self.handles.config.recurse_threshold > 0 {
+            if let Some(parent) = self.parent_scan(&response) {
+                if !parent.is_complete() && !self.decided.contains(parent.url()) {
+                    // the parent scan's findings aren't all in yet, the decision waits for
+                    // Command::ScanFinished
+                    self.deferred
+                        .entry(parent.url().to_string())
+                        .or_default()
+                        .push(response);
+                    return Ok(());
+                }
+
+                if self.below_threshold(&response, parent.findings()) {
+                    return Ok(());
+                }
+            }
+        }
+
+        self.recurse(response).await?;
+
+        log::trace!("exit: try_recursion");
+        Ok(())
+    }
+
+    /// the directory scan that the given response's parent directory is (or was) scanned by
+    fn parent_scan(&self, response: &FeroxResponse) -> Option<Arc<FeroxScan>> {
+        let parent = if response.url().path().ends_with('/') {
+            response.url().join("..")
+        } else {
+            response.url().join(".")
+        };
+
+        self.data.get_scan_by_url(parent.ok()?.as_str())
+    }
+
+    /// whether or not the given parent scan's findings fall short of --recurse-threshold; the
+    /// directory is reported as skipped when they do
+    fn below_threshold(&self, response: &FeroxResponse, findings: usize) -> bool {
+        let threshold = self.handles.config.recurse_threshold;
+
+        if findings >= threshold {
+            return false;
+        }
+
+        log::debug!(
+            "{} not recursed into; parent scan has {} finding(s), threshold is {}",
+            response.url(),
+            findings,
+            threshold
+        );
+        self.skipped(
+            response.url().as_str(),
+            "recurse-threshold",
+            &format!("parent scan has {findings} finding(s), --recurse-threshold is {threshold}"),
+        );
+
+        true
+    }
+
+    /// decide on the directories held back by --recurse-threshold now that the scan of the given
+    /// url has all of its findings
+    async fn release_deferred(&mut self, url: &str) -> Result<()> {
+        log::trace!("enter: release_deferred({})", url);
+
+        let Some(parent) = self.data.get_scan_by_url(url) else {
+            log::trace!("exit: release_deferred (unknown scan)");
             return Ok(());
+        };
+
+        self.decided.insert(parent.url().to_string());
+
+        let findings = parent.findings();
+
+        for response in self.deferred.remove(parent.url()).unwrap_or_default() {
+            if !self.below_threshold(&response, findings) {
+                self.recurse(response).await?;
+            }
         }
 
+        log::trace!("exit: release_deferred");
+        Ok(())
+    }
+
+    /// start a scan of the given directory, unless it's a wildcard directory
+    async fn recurse(&mut self, response: Box<FeroxResponse>) -> Result<()> {
+        log::trace!("enter: recurse({:?})", response);
+
         if let Ok(responses) = RESPONSES.responses.read() {
             for maybe_wild in responses.iter() {
                 if !maybe_wild.wildcard() || !maybe_wild.is_directory() {
@@ -443,6 +628,11 @@ impl ScanHandler {
                             (Some(maybe_wild_parent), Some(response_parent)) => {
                                 if maybe_wild_parent == response_parent {
                                     // the parent directories match, so we need to prevent recursion
+                                    self.skipped(
+                                        response.url().as_str(),
+                                        "wildcard",
+                                        &format!("wildcard directory {}", maybe_wild.url()),
+                                    );
                                     return Ok(());
                                 }
                             }
@@ -465,7 +655,23 @@ impl ScanHandler {
 
         log::info!("Added new directory to recursive scan: {}", response.url());
 
-        log::trace!("exit: try_recursion");
+        log::trace!("exit: recurse");
         Ok(())
     }
 }
+
+/// flush a cancelled run's findings and save its state, the same way ctrl+c does (unless
+/// --no-state was used)
+///
+/// called once every scan has wound down; findings the output handlers haven't gotten to yet
+/// are pushed through first, so that they're part of the saved state
+async fn save_cancelled_run(handles: Arc<Handles>) {
+    handles
+        .flush_output("run cancelled")
+        .await
+        .unwrap_or_else(|e| log::warn!("Could not flush output file: {}", e));
+
+    if let Some(saved) = TermInputHandler::checkpoint_state(handles, "run cancelled").await {
+        print_above_bars(&format!("✅ Saved scan state to {saved}"));
+    }
+}
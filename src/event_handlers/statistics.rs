@@ -1,18 +1,30 @@
+use super::outputs::run_file;
 use super::*;
 use crate::{
-    config::Configuration,
+    config::{Configuration, OutputLevel},
     progress::{add_bar, BarType},
-    statistics::{StatField, Stats},
+    statistics::{StatError, StatField, Stats, StatsBatch, StatsBatches},
+    traits::StatsRecorder,
     CommandSender, FeroxChannel, Joiner,
 };
 use anyhow::Result;
 use console::style;
 use indicatif::ProgressBar;
-use std::{sync::Arc, time::Instant};
-use tokio::sync::{
-    mpsc::{self, UnboundedReceiver},
-    oneshot,
+use reqwest::StatusCode;
+use std::{
+    sync::Arc,
+    time::{Duration, Instant},
 };
+use tokio::{
+    sync::{
+        mpsc::{self, UnboundedReceiver},
+        oneshot,
+    },
+    time::{self, MissedTickBehavior},
+};
+
+/// how often the single-line status is printed when progress bars are disabled
+const STATUS_LINE_INTERVAL: Duration = Duration::from_secs(10);
 
 #[derive(Debug)]
 /// Container for statistics transmitter and Stats object
@@ -22,13 +34,35 @@ pub struct StatsHandle {
 
     /// transmitter used to update `data`
     pub tx: CommandSender,
+
+    /// request outcomes waiting to be sent to the handler; when `None`, each outcome is sent
+    /// as soon as it's recorded
+    batches: Option<Arc<StatsBatches>>,
 }
 
 /// implementation of StatsHandle
 impl StatsHandle {
     /// Given an Arc-wrapped Stats and CommandSender, create a new StatsHandle
     pub fn new(data: Arc<Stats>, tx: CommandSender) -> Self {
-        Self { data, tx }
+        Self {
+            data,
+            tx,
+            batches: None,
+        }
+    }
+
+    /// Tally request outcomes recorded through this handle in the given `StatsBatches`
+    pub fn with_batches(mut self, batches: Arc<StatsBatches>) -> Self {
+        self.batches = Some(batches);
+        self
+    }
+
+    /// Send a full batch, if there is one
+    fn send_batch(&self, batch: Option<StatsBatch>) {
+        if let Some(batch) = batch {
+            self.send(Command::AddBatch(Box::new(batch)))
+                .unwrap_or_default();
+        }
     }
 
     /// Send the given Command over `tx`
@@ -46,6 +80,58 @@ impl StatsHandle {
     }
 }
 
+/// implementation of StatsRecorder for StatsHandle; outcomes are batched when `batches` is set
+impl StatsRecorder for StatsHandle {
+    fn add_status(&self, status: StatusCode) {
+        match &self.batches {
+            Some(batches) => self.send_batch(batches.add_status(status)),
+            None => self.send(Command::AddStatus(status)).unwrap_or_default(),
+        }
+    }
+
+    fn add_error(&self, error: StatError) {
+        match &self.batches {
+            Some(batches) => self.send_batch(batches.add_error(error)),
+            None => self.send(Command::AddError(error)).unwrap_or_default(),
+        }
+    }
+
+    fn add_target_error(&self, target: String, error: StatError) {
+        self.send(Command::AddTargetError(target, error))
+            .unwrap_or_default();
+    }
+}
+
+/// implementation of StatsRecorder for a bare transmitter; outcomes are sent right away
+impl StatsRecorder for CommandSender {
+    fn add_status(&self, status: StatusCode) {
+        self.send(Command::AddStatus(status)).unwrap_or_default();
+    }
+
+    fn add_error(&self, error: StatError) {
+        self.send(Command::AddError(error)).unwrap_or_default();
+    }
+
+    fn add_target_error(&self, target: String, error: StatError) {
+        self.send(Command::AddTargetError(target, error))
+            .unwrap_or_default();
+    }
+}
+
+/// StatsRecorder that records nothing, for requests that aren't part of the scan itself (i.e.
+/// findings sent through the replay proxy)
+#[derive(Debug, Default, Clone, Copy)]
+pub(crate) struct DiscardStats;
+
+/// implementation of StatsRecorder for DiscardStats; every outcome is dropped
+impl StatsRecorder for DiscardStats {
+    fn add_status(&self, _status: StatusCode) {}
+
+    fn add_error(&self, _error: StatError) {}
+
+    fn add_target_error(&self, _target: String, _error: StatError) {}
+}
+
 /// event handler struct for updating statistics
 #[derive(Debug)]
 pub struct StatsHandler {
@@ -57,19 +143,43 @@ pub struct StatsHandler {
 
     /// data class that stores all statistics updates
     stats: Arc<Stats>,
+
+    /// request outcomes tallied by workers that haven't been sent yet
+    batches: Arc<StatsBatches>,
+
+    /// number of bar increments received since the overall bar was last redrawn
+    pending: u64,
+
+    /// minimum amount of time between updates to the overall bar, derived from --bar-refresh-hz
+    refresh_interval: Duration,
+
+    /// whether or not to print a periodic status line in place of the progress bars (--no-bars)
+    status_line: bool,
 }
 
 /// implementation of event handler for statistics
 impl StatsHandler {
     /// create new event handler
-    fn new(stats: Arc<Stats>, rx_stats: UnboundedReceiver<Command>) -> Self {
+    fn new(
+        stats: Arc<Stats>,
+        rx_stats: UnboundedReceiver<Command>,
+        batches: Arc<StatsBatches>,
+        refresh_hz: u8,
+        status_line: bool,
+    ) -> Self {
         // will be updated later via StatCommand; delay is for banner to print first
         let bar = ProgressBar::hidden();
 
+        let refresh_interval = Duration::from_secs_f64(1.0 / f64::from(refresh_hz.max(1)));
+
         Self {
             bar,
             stats,
+            batches,
             receiver: rx_stats,
+            pending: 0,
+            refresh_interval,
+            status_line,
         }
     }
 
@@ -80,13 +190,40 @@ impl StatsHandler {
         log::trace!("enter: start({:?})", self);
 
         let start = Instant::now();
+        let mut last_status = Instant::now();
+
+        // requests are tallied as they come in and applied to the overall bar at most once per
+        // refresh interval, instead of redrawing the bar on every single request
+        let mut ticker = time::interval(self.refresh_interval);
+        ticker.set_missed_tick_behavior(MissedTickBehavior::Skip);
+
+        loop {
+            let command = tokio::select! {
+                command = self.receiver.recv() => match command {
+                    Some(command) => command,
+                    None => break,
+                },
+                _ = ticker.tick() => {
+                    self.drain_batches();
+                    self.flush_bar();
+
+                    if self.status_line && last_status.elapsed() >= STATUS_LINE_INTERVAL {
+                        self.print_status_line(start.elapsed());
+                        last_status = Instant::now();
+                    }
+
+                    continue;
+                }
+            };
 
-        while let Some(command) = self.receiver.recv().await {
             match command as Command {
                 Command::AddError(err) => {
                     self.stats.add_error(err);
                     self.increment_bar();
                 }
+                Command::AddTargetError(target, err) => {
+                    self.stats.add_target_error(&target, err);
+                }
                 Command::AddStatus(status) => {
                     self.stats.add_status_code(status);
 
@@ -96,7 +233,9 @@ impl StatsHandler {
                     self.stats.add_request();
                     self.increment_bar();
                 }
+                Command::AddBatch(batch) => self.add_batch(&batch),
                 Command::Save => {
+                    self.drain_batches();
                     self.stats
                         .save(start.elapsed().as_secs_f64(), output_file)?;
                 }
@@ -106,6 +245,12 @@ impl StatsHandler {
                     if matches!(field, StatField::TotalScans | StatField::TotalExpected) {
                         self.bar.set_length(self.stats.total_expected() as u64);
                     }
+
+                    if matches!(field, StatField::QueuedScans) {
+                        // no requests are made while scans are queued, so the message can't
+                        // wait for the next flush
+                        self.update_message();
+                    }
                 }
                 Command::SubtractFromUsizeField(field, value) => {
                     self.stats.subtract_from_usize_field(field, value);
@@ -113,9 +258,14 @@ impl StatsHandler {
                     if matches!(field, StatField::TotalExpected) {
                         self.bar.set_length(self.stats.total_expected() as u64);
                     }
+
+                    if matches!(field, StatField::QueuedScans) {
+                        self.update_message();
+                    }
                 }
                 Command::AddToF64Field(field, value) => self.stats.update_f64_field(field, value),
                 Command::CreateBar(offset) => {
+                    self.pending = 0;
                     self.bar = add_bar("", self.stats.total_expected() as u64, BarType::Total);
                     self.bar.set_position(offset);
                 }
@@ -123,11 +273,18 @@ impl StatsHandler {
                     self.stats.merge_from(&filename)?;
                 }
                 Command::Sync(sender) => {
+                    self.drain_batches();
+                    self.flush_bar();
                     sender.send(true).unwrap_or_default();
                 }
                 Command::QueryOverallBarEta(sender) => {
+                    self.drain_batches();
+                    self.flush_bar();
                     sender.send(self.bar.eta()).unwrap_or_default();
                 }
+                Command::QueryElapsed(sender) => {
+                    sender.send(start.elapsed()).unwrap_or_default();
+                }
                 Command::UpdateTargets(targets) => {
                     self.stats.update_targets(targets);
                 }
@@ -136,16 +293,58 @@ impl StatsHandler {
             }
         }
 
+        self.drain_batches();
+        self.flush_bar();
         self.bar.finish();
 
+        // final runtime, for anything that reads stats once the handler's gone (--campaign)
+        self.stats.update_runtime(start.elapsed().as_secs_f64());
+
         log::info!("{:#?}", *self.stats);
         log::trace!("exit: start");
         Ok(())
     }
 
-    /// Wrapper around incrementing the overall scan's progress bar
-    fn increment_bar(&self) {
-        let msg = format!(
+    /// Apply a batch of request outcomes to the stats, and the overall bar
+    fn add_batch(&mut self, batch: &StatsBatch) {
+        self.stats.add_batch(batch);
+        self.pending += batch.len() as u64;
+    }
+
+    /// Apply whatever request outcomes workers have tallied but not yet sent
+    fn drain_batches(&mut self) {
+        let batch = self.batches.drain();
+
+        if !batch.is_empty() {
+            self.add_batch(&batch);
+        }
+    }
+
+    /// Record an increment of the overall scan's progress bar, to be applied on the next flush
+    fn increment_bar(&mut self) {
+        self.pending += 1;
+    }
+
+    /// Apply any pending increments to the overall scan's progress bar
+    fn flush_bar(&mut self) {
+        if self.pending == 0 {
+            return;
+        }
+
+        let pending = std::mem::take(&mut self.pending);
+
+        self.update_message();
+
+        // don't run off the end when we're a few requests over the expected total
+        // due to the heuristics tests
+        let remaining = (self.stats.total_expected() as u64).saturating_sub(self.bar.position());
+
+        self.bar.inc(pending.min(remaining));
+    }
+
+    /// Update the overall scan's progress bar message with the current totals
+    fn update_message(&self) {
+        let mut msg = format!(
             "{}:{:<7} {}:{:<7}",
             style("found").green(),
             self.stats.resources_discovered(),
@@ -153,13 +352,32 @@ impl StatsHandler {
             self.stats.errors(),
         );
 
-        self.bar.set_message(msg);
+        let queued = self.stats.queued_scans();
 
-        if self.bar.position() < self.stats.total_expected() as u64 {
-            // don't run off the end when we're a few requests over the expected total
-            // due to the heuristics tests
-            self.bar.inc(1);
+        if queued > 0 {
+            // directories waiting on --scan-limit explain an otherwise idle looking scan
+            msg.push_str(&format!(" {}:{:<7}", style("queued").yellow(), queued));
         }
+
+        self.bar.set_message(msg);
+    }
+
+    /// Print a single line summarizing the overall scan's progress to stderr; used in place of
+    /// the progress bars when --no-bars is used
+    fn print_status_line(&self, elapsed: Duration) {
+        let requests = self.stats.requests();
+        let seconds = elapsed.as_secs().max(1);
+
+        eprintln!(
+            "[status] {}s elapsed | {}/{} requests ({}/s) | found: {} | errors: {} | queued: {}",
+            elapsed.as_secs(),
+            requests,
+            self.stats.total_expected(),
+            requests as u64 / seconds,
+            self.stats.resources_discovered(),
+            self.stats.errors(),
+            self.stats.queued_scans(),
+        );
     }
 
     /// Initialize new `Stats` object and the sc side of an mpsc channel that is responsible for
@@ -170,11 +388,25 @@ impl StatsHandler {
         let data = Arc::new(Stats::new(config.json));
         let (tx, rx): FeroxChannel<Command> = mpsc::unbounded_channel();
 
-        let mut handler = StatsHandler::new(data.clone(), rx);
+        let status_line = config.no_bars
+            && matches!(
+                config.output_level,
+                OutputLevel::Default | OutputLevel::Quiet
+            );
+
+        let batches = Arc::new(StatsBatches::new());
+
+        let mut handler = StatsHandler::new(
+            data.clone(),
+            rx,
+            batches.clone(),
+            config.bar_refresh_hz,
+            status_line,
+        );
 
-        let task = tokio::spawn(async move { handler.start(&config.output).await });
+        let task = tokio::spawn(async move { handler.start(&run_file(&config)).await });
 
-        let event_handle = StatsHandle::new(data, tx);
+        let event_handle = StatsHandle::new(data, tx).with_batches(batches);
 
         log::trace!("exit: initialize -> ({:?}, {:?})", task, event_handle);
 
@@ -0,0 +1,81 @@
+use super::*;
+
+use anyhow::Result;
+use tokio::sync::mpsc;
+
+use crate::{config::Configuration, zap::Zap, CommandReceiver, CommandSender, Joiner};
+
+#[derive(Debug)]
+/// Event handler that hands findings to the ZAP instance at --zap-api-url
+///
+/// findings are sent one at a time, in the order they were found, so a slow ZAP only ever
+/// holds up this handler and never the scan itself
+pub struct ZapHandler {
+    /// zap handler's receiver
+    receiver: CommandReceiver,
+
+    /// where findings are sent
+    zap: Zap,
+}
+
+/// implementation of ZapHandler
+impl ZapHandler {
+    /// Spawn the zap handler, if --zap-api-url was used
+    pub fn initialize(config: &Configuration) -> Option<(Joiner, CommandSender)> {
+        log::trace!("enter: initialize({})", config.zap_api_url);
+
+        let zap = Zap::new(config).unwrap_or_else(|e| {
+            log::warn!("{:#}; findings will not be sent to ZAP", e);
+            None
+        })?;
+
+        let (tx, rx) = mpsc::unbounded_channel::<Command>();
+
+        let mut handler = Self { receiver: rx, zap };
+        let task = tokio::spawn(async move { handler.start().await });
+
+        log::trace!("exit: initialize -> {:?}", task);
+        Some((task, tx))
+    }
+
+    /// Start a single consumer task (sc side of mpsc)
+    ///
+    /// findings arrive as `Command::Report`; when ZAP can't be reached at startup, findings
+    /// are drained without being sent, rather than warning about each one
+    async fn start(&mut self) -> Result<()> {
+        log::trace!("enter: start");
+
+        let reachable = match self.zap.version().await {
+            Ok(version) => {
+                log::info!("sending findings to ZAP {}", version);
+                true
+            }
+            Err(e) => {
+                log::warn!("{:#}; findings will not be sent to ZAP", e);
+                false
+            }
+        };
+
+        while let Some(command) = self.receiver.recv().await {
+            match command {
+                Command::Report(response) => {
+                    if !reachable {
+                        continue;
+                    }
+
+                    if let Err(e) = self.zap.access_url(response.url().as_str()).await {
+                        log::warn!("{:#}", e);
+                    }
+                }
+                Command::Sync(sender) => {
+                    sender.send(true).unwrap_or_default();
+                }
+                Command::Exit => break,
+                _ => {} // no more commands needed
+            }
+        }
+
+        log::trace!("exit: start");
+        Ok(())
+    }
+}
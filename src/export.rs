@@ -0,0 +1,411 @@
+use std::{
+    collections::{HashMap, HashSet},
+    env,
+    fs::File,
+    io::{BufWriter, Write},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use anyhow::{bail, Context, Result};
+use reqwest::header::{CONTENT_TYPE, LOCATION};
+use serde_json::{json, Value};
+
+use crate::{
+    config::Configuration,
+    response::FeroxResponse,
+    scanner::RESPONSES,
+    utils::{csv_field, fmt_err, utc_rfc3339},
+    DEFAULT_METHOD,
+};
+
+/// formats understood by --export-format
+pub const EXPORT_FORMATS: [&str; 3] = ["plain", "ffuf", "gobuster"];
+
+/// columns written by ffuf's csv output (-of csv), following the FUZZ keyword column
+const FFUF_CSV_HEADER: &str = "FUZZ,url,redirectlocation,position,status_code,content_length,content_words,content_lines,content_type,duration,resultfile,Ffufhash";
+
+/// write every discovered url to --export-urls, in the format given by --export-format
+///
+/// called once all scans are complete and the output handlers have exited, so that all
+/// responses have made their way into `RESPONSES`
+pub fn export_urls(config: &Configuration) -> Result<()> {
+    log::trace!("enter: export_urls({})", config.export_urls);
+
+    if config.export_urls.is_empty() {
+        log::trace!("exit: export_urls");
+        return Ok(());
+    }
+
+    let responses = match RESPONSES.responses.read() {
+        Ok(guard) => guard
+            .iter()
+            .filter(|response| {
+                config.export_status.is_empty()
+                    || config.export_status.contains(&response.status().as_u16())
+            })
+            .cloned()
+            .collect::<Vec<_>>(),
+        Err(_) => bail!(fmt_err("Could not read discovered responses for export")),
+    };
+
+    let file = File::create(&config.export_urls)
+        .with_context(|| fmt_err(&format!("Could not create {}", config.export_urls)))?;
+
+    let mut writer = BufWriter::new(file);
+
+    let contents = match config.export_format.as_str() {
+        // ffuf's csv has no room for comments without breaking its parsers
+        "ffuf" => format_ffuf(&responses),
+        "gobuster" => format_meta(&config.meta) + &format_gobuster(&responses),
+        _ => format_meta(&config.meta) + &format_plain(&responses),
+    };
+
+    writer
+        .write_all(contents.as_bytes())
+        .and_then(|_| writer.flush())
+        .with_context(|| fmt_err(&format!("Could not write to {}", config.export_urls)))?;
+
+    log::info!(
+        "exported {} url(s) to {}",
+        responses.len(),
+        config.export_urls
+    );
+
+    log::trace!("exit: export_urls");
+    Ok(())
+}
+
+/// write every discovered url to --emit-ffuf-json, in the same structure as ffuf's `-of json`
+///
+/// like --export-urls, this is called once all scans are complete; unlike it, --export-status
+/// doesn't apply, ffuf's json holds every result
+pub fn emit_ffuf_json(config: &Configuration) -> Result<()> {
+    log::trace!("enter: emit_ffuf_json({})", config.emit_ffuf_json);
+
+    if config.emit_ffuf_json.is_empty() {
+        log::trace!("exit: emit_ffuf_json");
+        return Ok(());
+    }
+
+    let responses = match RESPONSES.responses.read() {
+        Ok(guard) => guard.iter().cloned().collect::<Vec<_>>(),
+        Err(_) => bail!(fmt_err("Could not read discovered responses for export")),
+    };
+
+    let commandline = env::args().collect::<Vec<_>>().join(" ");
+    let document = format_ffuf_json(&responses, &commandline, &utc_now(), config);
+
+    let file = File::create(&config.emit_ffuf_json)
+        .with_context(|| fmt_err(&format!("Could not create {}", config.emit_ffuf_json)))?;
+
+    let mut writer = BufWriter::new(file);
+
+    let contents = serde_json::to_string(&document)?;
+
+    writer
+        .write_all(contents.as_bytes())
+        .and_then(|_| writer.flush())
+        .with_context(|| fmt_err(&format!("Could not write to {}", config.emit_ffuf_json)))?;
+
+    log::info!(
+        "wrote {} result(s) to {}",
+        responses.len(),
+        config.emit_ffuf_json
+    );
+
+    log::trace!("exit: emit_ffuf_json");
+    Ok(())
+}
+
+/// --meta pairs as comment lines, sorted by key
+///
+/// ex:
+/// # engagement=1337
+/// # operator=epi
+pub(crate) fn format_meta(meta: &HashMap<String, String>) -> String {
+    let mut pairs: Vec<_> = meta.iter().collect();
+    pairs.sort();
+
+    pairs
+        .into_iter()
+        .map(|(key, value)| format!("# {key}={value}\n"))
+        .collect()
+}
+
+/// one url per line, each url listed only once
+fn format_plain(responses: &[FeroxResponse]) -> String {
+    let mut seen = HashSet::new();
+    let mut contents = String::new();
+
+    for response in responses {
+        let url = response.url().as_str();
+
+        if seen.insert(url) {
+            contents.push_str(url);
+            contents.push('\n');
+        }
+    }
+
+    contents
+}
+
+/// csv in the same layout as ffuf's `-of csv`, so existing parsers can consume it
+fn format_ffuf(responses: &[FeroxResponse]) -> String {
+    let mut contents = format!("{FFUF_CSV_HEADER}\n");
+
+    for (position, response) in responses.iter().enumerate() {
+        let fuzz = response.url().path().trim_start_matches('/');
+
+        let row = [
+            csv_field(fuzz),
+            csv_field(response.url().as_str()),
+            csv_field(header_value(response, LOCATION.as_str())),
+            (position + 1).to_string(),
+            response.status().as_u16().to_string(),
+            response.content_length().to_string(),
+            response.word_count().to_string(),
+            response.line_count().to_string(),
+            csv_field(header_value(response, CONTENT_TYPE.as_str())),
+            // request durations aren't tracked per-response
+            String::from("0"),
+            String::new(),
+            String::new(),
+        ];
+
+        contents.push_str(&row.join(","));
+        contents.push('\n');
+    }
+
+    contents
+}
+
+/// json in the same structure as ffuf's `-of json`; each response is a result whose FUZZ
+/// keyword is its path, and `config` holds the handful of ffuf settings with a feroxbuster
+/// equivalent
+///
+/// ex:
+/// {
+///   "commandline": "feroxbuster -u http://localhost",
+///   "time": "2024-06-30T12:00:00Z",
+///   "results": [{"input": {"FUZZ": "admin"}, "position": 1, "status": 301, ...}],
+///   "config": {"url": "http://localhost/FUZZ", "method": "GET", ...}
+/// }
+fn format_ffuf_json(
+    responses: &[FeroxResponse],
+    commandline: &str,
+    time: &str,
+    config: &Configuration,
+) -> Value {
+    let results: Vec<_> = responses
+        .iter()
+        .enumerate()
+        .map(|(position, response)| {
+            let url = response.url();
+
+            let host = match (url.host_str(), url.port()) {
+                (Some(host), Some(port)) => format!("{host}:{port}"),
+                (Some(host), None) => host.to_string(),
+                _ => String::new(),
+            };
+
+            json!({
+                "input": {"FUZZ": url.path().trim_start_matches('/')},
+                "position": position + 1,
+                "status": response.status().as_u16(),
+                "length": response.content_length(),
+                "words": response.word_count(),
+                "lines": response.line_count(),
+                "content-type": header_value(response, CONTENT_TYPE.as_str()),
+                "redirectlocation": header_value(response, LOCATION.as_str()),
+                "scraper": {},
+                // request durations aren't tracked per-response
+                "duration": 0,
+                "resultfile": "",
+                "url": url.as_str(),
+                "host": host,
+            })
+        })
+        .collect();
+
+    let url = if config.target_url.is_empty() {
+        String::new()
+    } else {
+        format!("{}/FUZZ", config.target_url.trim_end_matches('/'))
+    };
+
+    json!({
+        "commandline": commandline,
+        "time": time,
+        "results": results,
+        "config": {
+            "url": url,
+            "method": config.methods.first().map_or(DEFAULT_METHOD, String::as_str),
+            "wordlists": [config.wordlist],
+            "outputfile": config.emit_ffuf_json,
+            "outputformat": "json",
+        },
+    })
+}
+
+/// the current time, as rfc3339 in utc, i.e. 2024-06-30T12:00:00Z
+fn utc_now() -> String {
+    let secs = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_secs())
+        .unwrap_or_default();
+
+    utc_rfc3339(secs)
+}
+
+/// lines in the same layout as gobuster's dir mode output
+///
+/// ex:
+/// /admin                (Status: 301) [Size: 178] [--> http://localhost/admin/]
+fn format_gobuster(responses: &[FeroxResponse]) -> String {
+    let mut contents = String::new();
+
+    for response in responses {
+        let mut path = response.url().path().to_string();
+
+        if let Some(query) = response.url().query() {
+            path.push('?');
+            path.push_str(query);
+        }
+
+        let mut line = format!(
+            "{:<20} (Status: {}) [Size: {}]",
+            path,
+            response.status().as_u16(),
+            response.content_length()
+        );
+
+        let location = header_value(response, LOCATION.as_str());
+
+        if !location.is_empty() {
+            line.push_str(&format!(" [--> {location}]"));
+        }
+
+        contents.push_str(&line);
+        contents.push('\n');
+    }
+
+    contents
+}
+
+/// value of the given header, or an empty string if it's missing or not valid utf-8
+fn header_value<'a>(response: &'a FeroxResponse, name: &str) -> &'a str {
+    response
+        .headers()
+        .get(name)
+        .and_then(|value| value.to_str().ok())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::response::fixtures::response;
+    use reqwest::header::HeaderValue;
+
+    #[test]
+    /// metadata is written as sorted comment lines, and not at all when there is none
+    fn format_meta_writes_sorted_comments() {
+        let mut meta = HashMap::new();
+        meta.insert(String::from("operator"), String::from("epi"));
+        meta.insert(String::from("engagement"), String::from("1337"));
+
+        assert_eq!(format_meta(&meta), "# engagement=1337\n# operator=epi\n");
+        assert_eq!(format_meta(&HashMap::new()), "");
+    }
+
+    #[test]
+    /// plain format lists each url once
+    fn format_plain_deduplicates_urls() {
+        let responses = [
+            response("http://localhost/admin", 200),
+            response("http://localhost/admin", 200),
+            response("http://localhost/login", 403),
+        ];
+
+        assert_eq!(
+            format_plain(&responses),
+            "http://localhost/admin\nhttp://localhost/login\n"
+        );
+    }
+
+    #[test]
+    /// ffuf format writes a header row and quotes fields that need it
+    fn format_ffuf_writes_header_and_rows() {
+        let mut redirect = response("http://localhost/a,b", 301);
+        redirect
+            .headers_mut()
+            .insert(LOCATION, HeaderValue::from_static("http://localhost/a,b/"));
+
+        let contents = format_ffuf(&[redirect]);
+        let lines: Vec<_> = contents.lines().collect();
+
+        assert_eq!(lines[0], FFUF_CSV_HEADER);
+        assert!(lines[1]
+            .starts_with("\"a,b\",\"http://localhost/a,b\",\"http://localhost/a,b/\",1,301,"));
+        assert_eq!(lines[1].split(',').count(), 15);
+    }
+
+    #[test]
+    /// ffuf json holds one result per response, in ffuf's field names, along with the command
+    /// line and settings
+    fn format_ffuf_json_matches_ffuf_structure() {
+        let mut redirect = response("http://localhost:8080/admin", 301);
+        redirect
+            .headers_mut()
+            .insert(LOCATION, HeaderValue::from_static("/admin/"));
+
+        let config = Configuration {
+            target_url: String::from("http://localhost:8080/"),
+            emit_ffuf_json: String::from("/some/ffuf.json"),
+            ..Default::default()
+        };
+
+        let document = format_ffuf_json(
+            &[redirect, response("http://localhost:8080/login", 200)],
+            "feroxbuster -u http://localhost:8080/",
+            "2024-06-30T12:00:00Z",
+            &config,
+        );
+
+        assert_eq!(
+            document["commandline"],
+            "feroxbuster -u http://localhost:8080/"
+        );
+        assert_eq!(document["time"], "2024-06-30T12:00:00Z");
+        assert_eq!(document["config"]["url"], "http://localhost:8080/FUZZ");
+        assert_eq!(document["config"]["method"], "GET");
+        assert_eq!(document["config"]["outputformat"], "json");
+
+        let results = document["results"].as_array().unwrap();
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0]["input"]["FUZZ"], "admin");
+        assert_eq!(results[0]["position"], 1);
+        assert_eq!(results[0]["status"], 301);
+        assert_eq!(results[0]["redirectlocation"], "/admin/");
+        assert_eq!(results[0]["host"], "localhost:8080");
+        assert_eq!(results[1]["url"], "http://localhost:8080/login");
+        assert_eq!(results[1]["position"], 2);
+        assert!(results[1]["scraper"].as_object().unwrap().is_empty());
+    }
+
+    #[test]
+    /// gobuster format shows path, status, size, and redirect target
+    fn format_gobuster_matches_dir_mode_output() {
+        let mut redirect = response("http://localhost/admin?x=1", 301);
+        redirect
+            .headers_mut()
+            .insert(LOCATION, HeaderValue::from_static("/admin/"));
+
+        let contents = format_gobuster(&[redirect, response("http://localhost/login", 200)]);
+
+        assert_eq!(
+            contents,
+            "/admin?x=1           (Status: 301) [Size: 0] [--> /admin/]\n/login               (Status: 200) [Size: 0]\n"
+        );
+    }
+}
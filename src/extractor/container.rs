@@ -2,25 +2,25 @@ use super::*;
 use crate::{
     client,
     event_handlers::{
-        Command::{AddError, AddToUsizeField},
+        Command::{AddError, AddToUsizeField, ScanNewUrl},
         Handles,
     },
     scan_manager::ScanOrder,
     statistics::{
         StatError::Other,
-        StatField::{LinksExtracted, TotalExpected},
+        StatField::{ExtractedRequests, LinksExtracted, TotalExpected},
     },
     url::FeroxUrl,
     utils::{
-        logged_request, make_request, parse_url_with_raw_path, send_try_recursion_command,
-        should_deny_url,
+        has_denylist, logged_request, make_request, parse_url_with_raw_path,
+        send_try_recursion_command, should_deny_url,
     },
     ExtractionResult, DEFAULT_METHOD,
 };
 use anyhow::{bail, Context, Result};
 use futures::StreamExt;
 use reqwest::{Client, Response, StatusCode, Url};
-use scraper::{Html, Selector};
+use scraper::{Html, Node, Selector};
 use std::{borrow::Cow, collections::HashSet};
 
 /// Wrapper around link extraction logic
@@ -43,9 +43,13 @@ pub(super) async fn request_link(url: &str, handles: Arc<Handles>) -> Result<Res
         bail!("previously seen url");
     }
 
-    if (!handles.config.url_denylist.is_empty() || !handles.config.regex_denylist.is_empty())
-        && should_deny_url(&new_url, handles.clone())?
-    {
+    if scanned_urls.is_host_cancelled(new_url.as_str()) {
+        // scans against this host were cancelled from the menu
+        log::trace!("exit: request_link -> None");
+        bail!("host was cancelled");
+    }
+
+    if has_denylist(&handles) && should_deny_url(&new_url, handles.clone())? {
         // can't allow a denied url to be requested
         bail!(
             "prevented request to {} due to {:?} || {:?}",
@@ -55,6 +59,9 @@ pub(super) async fn request_link(url: &str, handles: Arc<Handles>) -> Result<Res
         );
     }
 
+    // kept apart from the total, so crawling and the wordlist can be told apart in the stats
+    handles.stats.send(AddToUsizeField(ExtractedRequests, 1))?;
+
     // make the request and store the response
     let new_response = logged_request(&new_url, DEFAULT_METHOD, None, handles.clone()).await?;
 
@@ -63,6 +70,22 @@ pub(super) async fn request_link(url: &str, handles: Arc<Handles>) -> Result<Res
     Ok(new_response)
 }
 
+/// determine whether a value pulled from somewhere other than a link-bearing attribute looks
+/// like a path (/admin) or an absolute url (https://localhost/admin)
+pub(super) fn looks_like_link(value: &str) -> bool {
+    let lowered = value.to_lowercase();
+
+    if lowered.starts_with("http://") || lowered.starts_with("https://") {
+        return true;
+    }
+
+    // protocol-relative urls and comment markers (//, /*) aren't paths
+    value.starts_with('/')
+        && !value.starts_with("//")
+        && !value.starts_with("/*")
+        && value.chars().any(|c| c.is_ascii_alphanumeric())
+}
+
 /// Whether an active scan is recursive or not
 #[derive(Debug, Copy, Clone)]
 enum RecursionStatus {
@@ -132,6 +155,7 @@ impl<'a> Extractor<'a> {
                 {
                     // domains/ips are not the same, don't scan things that aren't part of the original
                     // target url
+                    self.record_discovered_host(&absolute, original_url);
                     bail!("parsed url does not belong to original domain/host");
                 }
 
@@ -239,6 +263,10 @@ impl<'a> Extractor<'a> {
                                         resp.parse_extension(c_handles.clone()).unwrap();
                                     }
 
+                                    if c_handles.config.detect_language {
+                                        resp.detect_language();
+                                    }
+
                                     if let Err(e) = resp.send_report(c_handles.output.tx.clone()) {
                                         log::warn!(
                                             "Could not send FeroxResponse to output handler: {}",
@@ -320,6 +348,136 @@ impl<'a> Extractor<'a> {
         self.extract_links_by_attr(resp_url, links, html, "frame", "src");
         self.extract_links_by_attr(resp_url, links, html, "embed", "src");
         self.extract_links_by_attr(resp_url, links, html, "link", "href");
+        self.extract_links_from_meta_refresh(resp_url, links, html);
+        self.extract_links_from_data_attrs(resp_url, links, html);
+        self.extract_links_from_comments(resp_url, links, html);
+    }
+
+    /// pull the redirect target out of `<meta http-equiv="refresh" content="0; url=/new">` tags
+    fn extract_links_from_meta_refresh(
+        &self,
+        resp_url: &Url,
+        links: &mut HashSet<String>,
+        html: &Html,
+    ) {
+        log::trace!("enter: extract_links_from_meta_refresh");
+
+        let selector = Selector::parse("meta[http-equiv][content]").unwrap();
+
+        for tag in html.select(&selector) {
+            let is_refresh = tag
+                .value()
+                .attr("http-equiv")
+                .is_some_and(|value| value.eq_ignore_ascii_case("refresh"));
+
+            let Some(content) = tag.value().attr("content") else {
+                continue;
+            };
+
+            if !is_refresh {
+                continue;
+            }
+
+            // content is a delay, optionally followed by ;url=<target>
+            let Some(position) = content.to_lowercase().find("url=") else {
+                continue;
+            };
+
+            let link = content[position + 4..]
+                .trim()
+                .trim_matches(|c| c == '\'' || c == '"');
+
+            if link.is_empty() {
+                continue;
+            }
+
+            log::debug!("Parsed link \"{}\" from {}", link, resp_url.as_str());
+
+            if self
+                .parse_url_and_add_subpaths(link, resp_url, links)
+                .is_err()
+            {
+                log::debug!("link didn't belong to the target domain/host: {}", link);
+            }
+        }
+
+        log::trace!("exit: extract_links_from_meta_refresh");
+    }
+
+    /// pull paths out of `data-*` attributes (ex: `<div data-endpoint="/api/v2/users">`)
+    ///
+    /// data attributes hold all sorts of values, so only those that look like a path or an
+    /// absolute url are kept
+    fn extract_links_from_data_attrs(
+        &self,
+        resp_url: &Url,
+        links: &mut HashSet<String>,
+        html: &Html,
+    ) {
+        log::trace!("enter: extract_links_from_data_attrs");
+
+        let selector = Selector::parse("*").unwrap();
+
+        for tag in html.select(&selector) {
+            for (name, value) in tag.value().attrs() {
+                if !name.starts_with("data-") || !looks_like_link(value) {
+                    continue;
+                }
+
+                log::debug!("Parsed link \"{}\" from {}", value, resp_url.as_str());
+
+                if self
+                    .parse_url_and_add_subpaths(value, resp_url, links)
+                    .is_err()
+                {
+                    log::debug!("link didn't belong to the target domain/host: {}", value);
+                }
+            }
+        }
+
+        log::trace!("exit: extract_links_from_data_attrs");
+    }
+
+    /// pull paths out of html comments (ex: `<!-- /old-admin -->`)
+    ///
+    /// the linkfinder regex only matches quoted strings, which commented-out paths usually
+    /// aren't, so each whitespace-separated word of a comment that looks like a path or an
+    /// absolute url is kept
+    fn extract_links_from_comments(
+        &self,
+        resp_url: &Url,
+        links: &mut HashSet<String>,
+        html: &Html,
+    ) {
+        log::trace!("enter: extract_links_from_comments");
+
+        for node in html.tree.values() {
+            let Node::Comment(comment) = node else {
+                continue;
+            };
+
+            for word in comment.split_whitespace() {
+                // strip surrounding quotes, brackets, and trailing punctuation
+                let link = word
+                    .trim_start_matches(['\'', '"', '(', '[', '<'])
+                    .trim_end_matches(['\'', '"', ')', ']', '>', ',', '.', ';', ':']);
+
+                if !looks_like_link(link) {
+                    continue;
+                }
+
+                log::debug!("Parsed link \"{}\" from {}", link, resp_url.as_str());
+
+                if self
+                    .parse_url_and_add_subpaths(link, resp_url, links)
+                    .is_err()
+                {
+                    log::debug!("link didn't belong to the target domain/host: {}", link);
+                }
+            }
+        }
+
+        log::trace!("exit: extract_links_from_comments");
     }
 
     /// Given the body of a `reqwest::Response`, perform the following actions
@@ -469,6 +627,25 @@ impl<'a> Extractor<'a> {
         paths
     }
 
+    /// keep track of a link to another host within the same domain (see `DiscoveredHosts`);
+    /// newly discovered hosts are scanned when --scan-discovered-hosts is used
+    fn record_discovered_host(&self, link: &Url, found_on: &Url) {
+        let Some(origin) = self.handles.discovered_hosts.insert(link, found_on) else {
+            return;
+        };
+
+        log::info!("discovered a new host: {} (found on {})", origin, found_on);
+
+        if self.handles.config.scan_discovered_hosts {
+            if let Err(e) = self
+                .handles
+                .send_scan_command(ScanNewUrl(format!("{origin}/")))
+            {
+                log::warn!("Could not queue a scan of {}: {}", origin, e);
+            }
+        }
+    }
+
     /// simple helper to stay DRY, tries to join a url + fragment and add it to the `links` HashSet
     pub(super) fn add_link_to_set_of_links(
         &self,
@@ -500,6 +677,7 @@ impl<'a> Extractor<'a> {
                 "Skipping {} because it's not part of the original target",
                 new_url
             );
+            self.record_discovered_host(&new_url, &old_url);
             log::trace!("exit: add_link_to_set_of_links");
             return Ok(());
         }
@@ -564,10 +742,47 @@ impl<'a> Extractor<'a> {
         self.extract_all_links_from_html_tags(resp_url, &mut result, &html);
         self.extract_all_links_from_javascript(body, resp_url, &mut result);
 
+        // wsdl/wadl documents declare their endpoints outright
+        if let Some(description) = ServiceDescription::parse(resp_url, body) {
+            self.extract_from_service_description(&description, resp_url, &mut result);
+        }
+
         log::trace!("exit: extract_from_body -> {:?}", result);
         Ok(result)
     }
 
+    /// add the endpoints declared by a wsdl/wadl document to `links` and report the service's
+    /// endpoints and operations
+    fn extract_from_service_description(
+        &self,
+        description: &ServiceDescription,
+        resp_url: &Url,
+        links: &mut HashSet<String>,
+    ) {
+        log::trace!(
+            "enter: extract_from_service_description({}, {:?})",
+            description.url,
+            links
+        );
+
+        for endpoint in description.requestable_endpoints() {
+            // endpoints on other hosts are recorded as discovered hosts, not requested
+            if self
+                .parse_url_and_add_subpaths(endpoint, resp_url, links)
+                .is_err()
+            {
+                log::debug!(
+                    "Skipping {} because it's not part of the original target",
+                    endpoint
+                );
+            }
+        }
+
+        description.report(&self.handles);
+
+        log::trace!("exit: extract_from_service_description");
+    }
+
     /// parses html response bodies in search of <a> tags.
     ///
     /// the assumption is that directory listing is turned on and this extraction target simply
@@ -663,6 +878,7 @@ impl<'a> Extractor<'a> {
                 &self.handles.config.user_agent,
                 follow_redirects,
                 self.handles.config.insecure,
+                self.handles.config.tls_backend,
                 &self.handles.config.headers,
                 proxy,
                 server_certs,
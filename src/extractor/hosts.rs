@@ -0,0 +1,145 @@
+use crate::{traits::FeroxSerialize, utils::fmt_err};
+use anyhow::{Context, Result};
+use console::style;
+use reqwest::Url;
+use serde::{Deserialize, Serialize};
+use std::{collections::BTreeMap, sync::Mutex};
+
+/// hosts related to a target that were found via extraction, shared by every scan
+///
+/// links to other hosts are never requested as part of the scan they were found in; instead,
+/// hosts that share a domain with the page they were found on (i.e. api.example.com found on
+/// www.example.com) are kept here, reported at the end of the scan, and only scanned when
+/// --scan-discovered-hosts is used
+#[derive(Debug, Default)]
+pub struct DiscoveredHosts {
+    /// origin (scheme://host:port) of each discovered host, mapped to the url of the page it
+    /// was first found on
+    hosts: Mutex<BTreeMap<String, String>>,
+}
+
+/// implementation of DiscoveredHosts
+impl DiscoveredHosts {
+    /// create an empty collection
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// record the host of `link` if it's related to, but not the same as, the host of
+    /// `found_on`; returns the host's origin when it hadn't been seen before
+    pub fn insert(&self, link: &Url, found_on: &Url) -> Option<String> {
+        if !is_related_host(link, found_on) {
+            return None;
+        }
+
+        let origin = link.origin().ascii_serialization();
+
+        let mut hosts = self.hosts.lock().ok()?;
+
+        if hosts.contains_key(&origin) {
+            return None;
+        }
+
+        hosts.insert(origin.clone(), found_on.to_string());
+
+        Some(origin)
+    }
+
+    /// every host discovered so far, ordered by origin
+    pub fn entries(&self) -> Vec<DiscoveredHost> {
+        let Ok(hosts) = self.hosts.lock() else {
+            return Vec::new();
+        };
+
+        hosts
+            .iter()
+            .map(|(host, found_on)| DiscoveredHost {
+                kind: String::from("discovered_host"),
+                host: host.clone(),
+                found_on: found_on.clone(),
+            })
+            .collect()
+    }
+
+    /// number of hosts discovered so far
+    pub fn len(&self) -> usize {
+        self.hosts
+            .lock()
+            .map(|hosts| hosts.len())
+            .unwrap_or_default()
+    }
+
+    /// whether or not any hosts have been discovered
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// whether `link` is on a different host than `found_on`, but within the same domain
+///
+/// the domain of `found_on` is its host without the first label, when it has more than two
+/// (www.example.com -> example.com); ip addresses and other schemes are never related
+fn is_related_host(link: &Url, found_on: &Url) -> bool {
+    if !matches!(link.scheme(), "http" | "https") {
+        return false;
+    }
+
+    let (Some(host), Some(original)) = (link.domain(), found_on.domain()) else {
+        return false;
+    };
+
+    let host = host.trim_end_matches('.').to_ascii_lowercase();
+    let original = original.trim_end_matches('.').to_ascii_lowercase();
+
+    if host == original {
+        return false;
+    }
+
+    let domain = match original.split_once('.') {
+        Some((_, rest)) if rest.contains('.') => rest,
+        _ => original.as_str(),
+    };
+
+    host == domain || host.ends_with(&format!(".{domain}"))
+}
+
+#[derive(Serialize, Deserialize, Default, Debug, Clone, PartialEq, Eq)]
+/// A host found via extraction, reported at the end of the scan
+pub struct DiscoveredHost {
+    #[serde(rename = "type")]
+    /// Name of this type of struct, used for serialization, i.e. `{"type":"discovered_host"}`
+    pub(crate) kind: String,
+
+    /// origin of the discovered host, i.e. https://api.example.com
+    pub(crate) host: String,
+
+    /// url of the page the host was first found on
+    pub(crate) found_on: String,
+}
+
+/// Implementation of DiscoveredHost
+impl FeroxSerialize for DiscoveredHost {
+    /// Create a string representation of the host
+    ///
+    /// ex:
+    /// HST https://api.example.com (found on https://www.example.com/js/app.js)
+    fn as_str(&self) -> String {
+        format!(
+            "{} {} (found on {})\n",
+            style("HST").bg(console::Color::Cyan).black(),
+            self.host,
+            self.found_on
+        )
+    }
+
+    /// Create an NDJSON representation of the host
+    ///
+    /// ex:
+    /// {"type":"discovered_host","host":"https://api.example.com","found_on":"https://www.example.com/js/app.js"}\n
+    fn as_json(&self) -> Result<String> {
+        let mut json = serde_json::to_string(&self)
+            .with_context(|| fmt_err(&format!("Could not convert {} to JSON", self.host)))?;
+        json.push('\n');
+        Ok(json)
+    }
+}
@@ -1,13 +1,22 @@
 //! extract links from html source and robots.txt
 mod builder;
 mod container;
+mod hosts;
+mod seen;
+mod services;
 #[cfg(test)]
 mod tests;
 
 pub use self::builder::ExtractionTarget;
 pub use self::builder::ExtractorBuilder;
 pub use self::container::Extractor;
+pub use self::hosts::{DiscoveredHost, DiscoveredHosts};
+pub use self::seen::SeenLinks;
+pub use self::services::ServiceDescription;
 
 use crate::response::FeroxResponse;
 use regex::Regex;
 use std::sync::Arc;
+
+/// accepted values of --extract-priority
+pub const EXTRACT_PRIORITIES: [&str; 2] = ["normal", "low"];
@@ -0,0 +1,77 @@
+use std::{
+    collections::{hash_map::DefaultHasher, HashSet},
+    hash::{Hash, Hasher},
+    sync::Mutex,
+};
+
+/// number of independently locked sets that links are spread across
+const SHARDS: usize = 16;
+
+/// links found via extraction, shared by every scan
+///
+/// heavily cross-linked sites hand back the same links from nearly every directory; keeping a
+/// single record of what's already been extracted means each link is only requested once,
+/// regardless of how many scans come across it. Links are spread across `SHARDS` sets by hash,
+/// so scans rarely wait on one another
+#[derive(Debug)]
+pub struct SeenLinks {
+    /// one set per shard, a link's shard is picked by its hash
+    shards: Vec<Mutex<HashSet<String>>>,
+}
+
+/// implementation of SeenLinks
+impl SeenLinks {
+    /// create an empty collection
+    pub fn new() -> Self {
+        Self {
+            shards: (0..SHARDS).map(|_| Mutex::new(HashSet::new())).collect(),
+        }
+    }
+
+    /// the shard responsible for the given link
+    fn shard(&self, link: &str) -> &Mutex<HashSet<String>> {
+        let mut hasher = DefaultHasher::new();
+        link.hash(&mut hasher);
+
+        &self.shards[hasher.finish() as usize % self.shards.len()]
+    }
+
+    /// record the given links, returning only those that hadn't been seen before
+    ///
+    /// returns None when a shard can't be locked, in which case the caller is left to
+    /// deduplicate the links on its own
+    pub fn insert_new(&self, links: &HashSet<String>) -> Option<HashSet<String>> {
+        let mut new_links = HashSet::new();
+
+        for link in links {
+            let mut shard = self.shard(link).lock().ok()?;
+
+            if shard.insert(link.to_owned()) {
+                new_links.insert(link.to_owned());
+            }
+        }
+
+        Some(new_links)
+    }
+
+    /// number of links seen across every scan
+    pub fn len(&self) -> usize {
+        self.shards
+            .iter()
+            .filter_map(|shard| shard.lock().ok().map(|links| links.len()))
+            .sum()
+    }
+
+    /// whether or not any links have been seen
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// implementation of Default for SeenLinks
+impl Default for SeenLinks {
+    /// see `SeenLinks::new`
+    fn default() -> Self {
+        Self::new()
+    }
+}
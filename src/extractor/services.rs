@@ -0,0 +1,420 @@
+use crate::{
+    config::OutputLevel,
+    event_handlers::{Command::WriteServiceDescription, Handles},
+    progress::PROGRESS_PRINTER,
+    traits::FeroxSerialize,
+    utils::{ferox_print, fmt_err},
+    xml::attribute,
+};
+use anyhow::{Context, Result};
+use console::style;
+use lazy_static::lazy_static;
+use regex::Regex;
+use reqwest::Url;
+use serde::{Deserialize, Serialize};
+
+/// namespaces of WSDL 1.1 and 2.0 documents
+const WSDL_NAMESPACES: [&str; 2] = [
+    "http://schemas.xmlsoap.org/wsdl/",
+    "http://www.w3.org/ns/wsdl",
+];
+
+/// namespaces of WADL documents, current and pre-2009
+const WADL_NAMESPACES: [&str; 2] = [
+    "http://wadl.dev.java.net/2009/02",
+    "http://research.sun.com/wadl/2006/10",
+];
+
+lazy_static! {
+    /// an opening, closing, or self-closing xml tag; the namespace prefix is dropped, so
+    /// soap:address, soap12:address, and address are all `address`
+    static ref TAG: Regex =
+        Regex::new(r#"<(/?)(?:[A-Za-z0-9_.-]+:)?([A-Za-z0-9_.-]+)([^>]*?)(/?)>"#).unwrap();
+}
+
+/// which kind of service description a document is
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+enum DescriptionFormat {
+    /// SOAP services, i.e. service.asmx?wsdl
+    Wsdl,
+
+    /// REST services, i.e. application.wadl
+    Wadl,
+}
+
+#[derive(Serialize, Deserialize, Default, Debug, Clone, PartialEq, Eq)]
+/// The endpoints and operations declared by a WSDL or WADL document found during extraction
+pub struct ServiceDescription {
+    #[serde(rename = "type")]
+    /// Name of this type of struct, used for serialization, i.e. `{"type":"service_description"}`
+    pub(crate) kind: String,
+
+    /// url of the document
+    pub(crate) url: String,
+
+    /// `wsdl` or `wadl`
+    pub(crate) format: String,
+
+    /// urls the document says the service is reachable at; wadl resources keep their templated
+    /// segments, i.e. http://localhost/api/users/{id}
+    pub(crate) endpoints: Vec<String>,
+
+    /// wsdl operation names, or the method and path of each wadl method, i.e. `GET /users/{id}`
+    pub(crate) operations: Vec<String>,
+}
+
+/// implementation of ServiceDescription
+impl ServiceDescription {
+    /// the service described by `body`, if it's a WSDL or WADL document that declares at least
+    /// one endpoint or operation
+    pub fn parse(url: &Url, body: &str) -> Option<Self> {
+        let format = if WSDL_NAMESPACES.iter().any(|ns| body.contains(ns)) {
+            DescriptionFormat::Wsdl
+        } else if WADL_NAMESPACES.iter().any(|ns| body.contains(ns)) {
+            DescriptionFormat::Wadl
+        } else {
+            return None;
+        };
+
+        let (endpoints, operations) = match format {
+            DescriptionFormat::Wsdl => parse_wsdl(url, body),
+            DescriptionFormat::Wadl => parse_wadl(url, body),
+        };
+
+        if endpoints.is_empty() && operations.is_empty() {
+            return None;
+        }
+
+        Some(Self {
+            kind: String::from("service_description"),
+            url: url.to_string(),
+            format: match format {
+                DescriptionFormat::Wsdl => String::from("wsdl"),
+                DescriptionFormat::Wadl => String::from("wadl"),
+            },
+            endpoints,
+            operations,
+        })
+    }
+
+    /// endpoints that can be requested as-is; templated endpoints are cut short at their first
+    /// templated segment, i.e. http://localhost/api/users/{id} -> http://localhost/api/users
+    pub fn requestable_endpoints(&self) -> impl Iterator<Item = &str> {
+        self.endpoints.iter().map(|endpoint| {
+            endpoint
+                .split('{')
+                .next()
+                .unwrap_or(endpoint)
+                .trim_end_matches('/')
+        })
+    }
+
+    /// show the service's endpoints and operations, and write them to --output
+    pub fn report(&self, handles: &Handles) {
+        if matches!(
+            handles.config.output_level,
+            OutputLevel::Default | OutputLevel::Quiet
+        ) {
+            ferox_print(&self.as_str(), &PROGRESS_PRINTER);
+        }
+
+        handles
+            .output
+            .tx_file
+            .send(WriteServiceDescription(Box::new(self.clone())))
+            .unwrap_or_default();
+    }
+}
+
+/// endpoints from each service port's address, and the name of each operation
+///
+/// - wsdl 1.1: `<soap:address location="..."/>` (also soap12: and http:), `<operation name="..."/>`
+/// - wsdl 2.0: `<endpoint address="..."/>`, `<operation name="..."/>`
+fn parse_wsdl(url: &Url, body: &str) -> (Vec<String>, Vec<String>) {
+    let mut endpoints = Vec::new();
+    let mut operations = Vec::new();
+
+    for tag in TAG.captures_iter(body) {
+        if &tag[1] == "/" {
+            continue;
+        }
+
+        let attributes = &tag[3];
+
+        match &tag[2] {
+            "address" => {
+                if let Some(location) = attribute(attributes, "location") {
+                    if let Ok(location) = url.join(&location) {
+                        push_unique(&mut endpoints, location.to_string());
+                    }
+                }
+            }
+            "endpoint" => {
+                if let Some(address) = attribute(attributes, "address") {
+                    if let Ok(address) = url.join(&address) {
+                        push_unique(&mut endpoints, address.to_string());
+                    }
+                }
+            }
+            "operation" => {
+                // operations are declared in both the portType and the binding
+                if let Some(name) = attribute(attributes, "name") {
+                    push_unique(&mut operations, name);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    (endpoints, operations)
+}
+
+/// the full path of every resource, joined to the base of its `<resources>`, and the method
+/// and path of every `<method>` within them
+fn parse_wadl(url: &Url, body: &str) -> (Vec<String>, Vec<String>) {
+    let mut endpoints = Vec::new();
+    let mut operations = Vec::new();
+
+    // resources without a base are relative to the document
+    let directory = url.join("./").unwrap_or_else(|_| url.clone());
+    let mut base = directory.clone();
+
+    // paths of the resources the current tag is nested in
+    let mut resources: Vec<String> = Vec::new();
+
+    for tag in TAG.captures_iter(body) {
+        let closing = &tag[1] == "/";
+        let self_closing = &tag[4] == "/";
+        let attributes = &tag[3];
+
+        match (&tag[2], closing) {
+            ("resources", false) => {
+                base = match attribute(attributes, "base").and_then(|base| url.join(&base).ok()) {
+                    Some(mut base) => {
+                        if !base.path().ends_with('/') {
+                            // resource paths are relative to all of base, not its parent
+                            let path = format!("{}/", base.path());
+                            base.set_path(&path);
+                        }
+                        base
+                    }
+                    None => directory.clone(),
+                };
+            }
+            ("resource", false) => {
+                let path = attribute(attributes, "path").unwrap_or_default();
+                resources.push(path.trim_matches('/').to_string());
+
+                // templated segments are kept as-is; they'd be percent-encoded by a join
+                push_unique(&mut endpoints, format!("{base}{}", nested_path(&resources)));
+
+                if self_closing {
+                    resources.pop();
+                }
+            }
+            ("resource", true) => {
+                resources.pop();
+            }
+            ("method", false) if !resources.is_empty() => {
+                // methods outside of a resource are only referenced from elsewhere
+                let Some(method) = attribute(attributes, "name") else {
+                    continue;
+                };
+
+                let path = nested_path(&resources);
+                push_unique(
+                    &mut operations,
+                    format!("{} /{path}", method.to_uppercase()),
+                );
+            }
+            _ => {}
+        }
+    }
+
+    (endpoints, operations)
+}
+
+/// path of the innermost resource, relative to the base of its `<resources>`
+fn nested_path(resources: &[String]) -> String {
+    resources
+        .iter()
+        .filter(|path| !path.is_empty())
+        .cloned()
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+/// add `value` to `values`, unless it's already there; order of appearance is kept
+fn push_unique(values: &mut Vec<String>, value: String) {
+    if !values.contains(&value) {
+        values.push(value);
+    }
+}
+
+/// Implementation of ServiceDescription
+impl FeroxSerialize for ServiceDescription {
+    /// Create a string representation of the service description
+    ///
+    /// ex:
+    /// SVC https://localhost/users.asmx?wsdl (wsdl) endpoints: https://localhost/users.asmx | operations: GetUser, ListUsers
+    fn as_str(&self) -> String {
+        let mut parts = Vec::new();
+
+        if !self.endpoints.is_empty() {
+            parts.push(format!("endpoints: {}", self.endpoints.join(", ")));
+        }
+
+        if !self.operations.is_empty() {
+            parts.push(format!("operations: {}", self.operations.join(", ")));
+        }
+
+        format!(
+            "{} {} ({}) {}\n",
+            style("SVC").bg(console::Color::Blue).black(),
+            self.url,
+            self.format,
+            parts.join(" | ")
+        )
+    }
+
+    /// Create an NDJSON representation of the service description
+    ///
+    /// (expanded for clarity)
+    /// ex:
+    /// {
+    ///   "type": "service_description",
+    ///   "url": "https://localhost/application.wadl",
+    ///   "format": "wadl",
+    ///   "endpoints": ["https://localhost/api/users", "https://localhost/api/users/{id}"],
+    ///   "operations": ["GET /users", "GET /users/{id}"]
+    /// }\n
+    fn as_json(&self) -> Result<String> {
+        let mut json = serde_json::to_string(&self)
+            .with_context(|| fmt_err(&format!("Could not convert {} to JSON", self.url)))?;
+        json.push('\n');
+        Ok(json)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// trimmed down wsdl 1.1 document, as served by an asmx service
+    const WSDL: &str = r#"<?xml version="1.0" encoding="utf-8"?>
+<wsdl:definitions xmlns:soap="http://schemas.xmlsoap.org/wsdl/soap/" xmlns:wsdl="http://schemas.xmlsoap.org/wsdl/">
+  <wsdl:portType name="UsersSoap">
+    <wsdl:operation name="GetUser"><wsdl:input message="tns:GetUserSoapIn" /></wsdl:operation>
+    <wsdl:operation name="ListUsers"><wsdl:input message="tns:ListUsersSoapIn" /></wsdl:operation>
+  </wsdl:portType>
+  <wsdl:binding name="UsersSoap" type="tns:UsersSoap">
+    <wsdl:operation name="GetUser"><soap:operation soapAction="urn:GetUser" /></wsdl:operation>
+  </wsdl:binding>
+  <wsdl:service name="Users">
+    <wsdl:port name="UsersSoap" binding="tns:UsersSoap">
+      <soap:address location="http://localhost/services/users.asmx" />
+    </wsdl:port>
+  </wsdl:service>
+</wsdl:definitions>"#;
+
+    /// trimmed down wadl document, as served by jersey
+    const WADL: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<application xmlns="http://wadl.dev.java.net/2009/02">
+  <resources base="http://localhost/api">
+    <resource path="users">
+      <method name="GET" id="listUsers" />
+      <resource path="{id}">
+        <method name="GET" id="getUser" />
+        <method name="delete" id="deleteUser" />
+      </resource>
+    </resource>
+    <resource path="/health" />
+  </resources>
+</application>"#;
+
+    #[test]
+    /// addresses and deduplicated operation names are read from a wsdl
+    fn parse_reads_wsdl_addresses_and_operations() {
+        let url = Url::parse("http://localhost/services/users.asmx?wsdl").unwrap();
+        let description = ServiceDescription::parse(&url, WSDL).unwrap();
+
+        assert_eq!(description.format, "wsdl");
+        assert_eq!(
+            description.endpoints,
+            vec!["http://localhost/services/users.asmx"]
+        );
+        assert_eq!(description.operations, vec!["GetUser", "ListUsers"]);
+    }
+
+    #[test]
+    /// nested wadl resources are joined to their base, and their methods reported with them
+    fn parse_reads_wadl_resources_and_methods() {
+        let url = Url::parse("http://localhost/application.wadl").unwrap();
+        let description = ServiceDescription::parse(&url, WADL).unwrap();
+
+        assert_eq!(description.format, "wadl");
+        assert_eq!(
+            description.endpoints,
+            vec![
+                "http://localhost/api/users",
+                "http://localhost/api/users/{id}",
+                "http://localhost/api/health"
+            ]
+        );
+        assert_eq!(
+            description.operations,
+            vec!["GET /users", "GET /users/{id}", "DELETE /users/{id}"]
+        );
+        assert_eq!(
+            description.requestable_endpoints().collect::<Vec<_>>(),
+            vec![
+                "http://localhost/api/users",
+                "http://localhost/api/users",
+                "http://localhost/api/health"
+            ]
+        );
+    }
+
+    #[test]
+    /// single-quoted attributes are read, and entities within them decoded
+    fn parse_reads_single_quoted_attributes_with_entities() {
+        let url = Url::parse("http://localhost/users.svc?wsdl").unwrap();
+        let body = r#"<definitions xmlns='http://www.w3.org/ns/wsdl'><endpoint name='users' address='http://localhost/users.svc?v=2&amp;format=soap'/></definitions>"#;
+        let description = ServiceDescription::parse(&url, body).unwrap();
+
+        assert_eq!(
+            description.endpoints,
+            vec!["http://localhost/users.svc?v=2&format=soap"]
+        );
+    }
+
+    #[test]
+    /// documents that aren't service descriptions, or that declare nothing, are ignored
+    fn parse_ignores_other_documents() {
+        let url = Url::parse("http://localhost/index.html").unwrap();
+
+        assert!(ServiceDescription::parse(&url, "<html><a href=\"/login\"></a></html>").is_none());
+        assert!(ServiceDescription::parse(
+            &url,
+            r#"<definitions xmlns="http://schemas.xmlsoap.org/wsdl/"></definitions>"#
+        )
+        .is_none());
+    }
+
+    #[test]
+    /// test as_str and as_json methods of ServiceDescription
+    fn service_description_serializes() {
+        let url = Url::parse("http://localhost/services/users.asmx?wsdl").unwrap();
+        let description = ServiceDescription::parse(&url, WSDL).unwrap();
+
+        let description_str = console::strip_ansi_codes(&description.as_str()).to_string();
+        assert_eq!(
+            description_str,
+            "SVC http://localhost/services/users.asmx?wsdl (wsdl) endpoints: http://localhost/services/users.asmx | operations: GetUser, ListUsers\n"
+        );
+
+        let json = description.as_json().unwrap();
+        assert!(json.starts_with(r#"{"type":"service_description","#));
+        assert!(json.ends_with('\n'));
+    }
+}
@@ -1,8 +1,9 @@
 use super::builder::{LINKFINDER_REGEX, ROBOTS_TXT_REGEX, URL_CHARS_REGEX};
-use super::container::request_link;
+use super::container::{looks_like_link, request_link};
 use super::*;
 use crate::config::{Configuration, OutputLevel};
 use crate::scan_manager::ScanOrder;
+use crate::traits::FeroxSerialize;
 use crate::{
     event_handlers::Handles, scan_manager::FeroxScans, utils::make_request, Command, FeroxChannel,
     DEFAULT_METHOD,
@@ -234,6 +235,60 @@ fn normalize_url_path_filters_queries_and_fragments() {
     });
 }
 
+#[test]
+/// only paths and absolute urls are considered links when pulled from comments/data attributes
+fn looks_like_link_accepts_paths_and_urls() {
+    assert!(looks_like_link("/old-admin"));
+    assert!(looks_like_link("https://localhost/api"));
+    assert!(!looks_like_link("//cdn.example.com/lib.js"));
+    assert!(!looks_like_link("/*"));
+    assert!(!looks_like_link("/"));
+    assert!(!looks_like_link("42"));
+    assert!(!looks_like_link("TODO"));
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+/// paths hidden in html comments, meta refresh tags, and data-* attributes are extracted
+async fn extractor_extracts_links_from_comments_meta_refresh_and_data_attrs() -> Result<()> {
+    let mut response = FeroxResponse::default();
+    response.set_url("http://localhost/index.html");
+    response.set_text(
+        r#"<html><head><meta http-equiv="Refresh" content="5; URL='/moved/here'"></head>
+        <body>
+        <!-- TODO: remove /old-admin before go-live, see http://localhost/backup/db.sql. -->
+        <div data-endpoint="/api/v2/users" data-count="42"></div>
+        <!-- https://elsewhere.com/not-ours -->
+        </body></html>"#,
+    );
+
+    let (handles, _rx) = Handles::for_testing(None, None);
+
+    let extractor = Extractor {
+        links_regex: Regex::new(LINKFINDER_REGEX).unwrap(),
+        robots_regex: Regex::new(ROBOTS_TXT_REGEX).unwrap(),
+        url_regex: Regex::new(URL_CHARS_REGEX).unwrap(),
+        response: Some(&response),
+        url: String::new(),
+        target: ExtractionTarget::ResponseBody,
+        handles: Arc::new(handles),
+    };
+
+    let links = extractor.extract_from_body().await?;
+
+    for expected in [
+        "http://localhost/moved/here",
+        "http://localhost/old-admin",
+        "http://localhost/backup/db.sql",
+        "http://localhost/api/v2/users",
+    ] {
+        assert!(links.contains(expected), "missing {expected}: {links:?}");
+    }
+
+    assert!(!links.iter().any(|link| link.contains("not-ours")));
+    assert!(!links.iter().any(|link| link.ends_with("/42")));
+    Ok(())
+}
+
 #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
 /// use make_request to generate a Response, and use the Response to test get_links;
 /// the response will contain an absolute path to a domain that is not part of the scanned
@@ -403,3 +458,75 @@ async fn request_link_bails_on_seen_url() -> Result<()> {
     assert_eq!(mock.hits(), 0); // function exits before requests can happen
     Ok(())
 }
+
+#[test]
+/// links seen by one scan aren't handed back to another
+fn seen_links_only_returns_new_links() {
+    let seen = SeenLinks::new();
+    assert!(seen.is_empty());
+
+    let first: HashSet<String> = ["http://localhost/a", "http://localhost/b"]
+        .iter()
+        .map(|link| link.to_string())
+        .collect();
+    assert_eq!(seen.insert_new(&first).unwrap(), first);
+
+    let second: HashSet<String> = ["http://localhost/b", "http://localhost/c"]
+        .iter()
+        .map(|link| link.to_string())
+        .collect();
+    let new_links = seen.insert_new(&second).unwrap();
+
+    assert_eq!(new_links.len(), 1);
+    assert!(new_links.contains("http://localhost/c"));
+    assert_eq!(seen.len(), 3);
+}
+
+#[test]
+/// only other hosts within the same domain are kept, and each only once
+fn discovered_hosts_only_records_related_hosts() {
+    let hosts = DiscoveredHosts::new();
+    assert!(hosts.is_empty());
+
+    let found_on = Url::parse("https://www.example.com/js/app.js").unwrap();
+    let link = |url: &str| Url::parse(url).unwrap();
+
+    assert_eq!(
+        hosts.insert(&link("https://api.example.com/v1/users"), &found_on),
+        Some(String::from("https://api.example.com"))
+    );
+    assert_eq!(
+        hosts.insert(&link("https://api.example.com/v2/users"), &found_on),
+        None
+    );
+    assert_eq!(
+        hosts.insert(&link("http://example.com:8080/"), &found_on),
+        Some(String::from("http://example.com:8080"))
+    );
+
+    // same host, other domains, ip addresses, and other schemes are ignored
+    assert!(hosts
+        .insert(&link("https://www.example.com/admin"), &found_on)
+        .is_none());
+    assert!(hosts
+        .insert(&link("https://cdn.jsdelivr.net/npm/vue"), &found_on)
+        .is_none());
+    assert!(hosts
+        .insert(&link("https://notexample.com/"), &found_on)
+        .is_none());
+    assert!(hosts.insert(&link("http://10.0.0.1/"), &found_on).is_none());
+    assert!(hosts
+        .insert(&link("ftp://files.example.com/"), &found_on)
+        .is_none());
+
+    let entries = hosts.entries();
+
+    assert_eq!(hosts.len(), 2);
+    assert_eq!(entries[0].host, "http://example.com:8080");
+    assert_eq!(entries[1].host, "https://api.example.com");
+    assert_eq!(entries[1].found_on, "https://www.example.com/js/app.js");
+    assert!(entries[1]
+        .as_json()
+        .unwrap()
+        .contains(r#""type":"discovered_host""#));
+}
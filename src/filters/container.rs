@@ -1,5 +1,7 @@
-use std::sync::RwLock;
+use std::collections::HashMap;
+use std::sync::{Mutex, RwLock};
 
+use ::regex::RegexSet;
 use anyhow::Result;
 use serde::{ser::SerializeSeq, Serialize, Serializer};
 
@@ -10,14 +12,39 @@ use super::{
     WildcardFilter, WordsFilter,
 };
 use crate::{
-    event_handlers::Command::AddToUsizeField, statistics::StatField::WildcardsFiltered,
-    CommandSender,
+    event_handlers::Command::AddToUsizeField,
+    statistics::StatField::WildcardsFiltered,
+    utils::{parent_directory, status_colorizer},
+    CommandSender, WILDCARD_WARN_MIN_HITS, WILDCARD_WARN_RATIO,
 };
+
+/// responses seen within a single directory, and how many of them a `WildcardFilter` suppressed
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+struct WildcardTally {
+    /// responses that were either suppressed as wildcards or made it through every filter
+    hits: usize,
+
+    /// responses suppressed by a `WildcardFilter`
+    suppressed: usize,
+
+    /// whether or not the user has already been warned about this directory
+    warned: bool,
+}
+
 /// Container around a collection of `FeroxFilters`s
 #[derive(Debug, Default)]
 pub struct FeroxFilters {
-    /// collection of `FeroxFilters`
+    /// collection of `FeroxFilters`, ordered by their cost
     pub filters: RwLock<Vec<Box<dyn FeroxFilter>>>,
+
+    /// every `RegexFilter`'s pattern, compiled into a single set (in the same order as the
+    /// `RegexFilter`s appear in `filters`) so that a response is only searched once, regardless
+    /// of the number of regex filters
+    regex_set: RwLock<Option<RegexSet>>,
+
+    /// per-directory tallies of wildcard suppression, used to warn when wildcard filtering
+    /// hides nearly everything in a directory
+    wildcard_tallies: Mutex<HashMap<String, WildcardTally>>,
 }
 
 /// implementation of FeroxFilter collection
@@ -29,11 +56,68 @@ impl FeroxFilters {
                 return Ok(());
             }
 
-            guard.push(filter)
+            let is_regex = filter.as_any().downcast_ref::<RegexFilter>().is_some();
+
+            guard.push(filter);
+
+            // cheapest filters are evaluated first; the sort is stable, so filters of the same
+            // cost keep their insertion order
+            guard.sort_by_key(|filter| filter.cost());
+
+            if is_regex {
+                self.rebuild_regex_set(&guard);
+            }
         }
         Ok(())
     }
 
+    /// recompile the regex set from the `RegexFilter`s currently in the collection
+    fn rebuild_regex_set(&self, filters: &[Box<dyn FeroxFilter>]) {
+        let patterns = filters
+            .iter()
+            .filter_map(|filter| filter.as_any().downcast_ref::<RegexFilter>())
+            .map(|filter| filter.compiled.as_str());
+
+        // each pattern already compiled on its own, so the set should as well; if it somehow
+        // doesn't, the filters fall back to evaluating their own regex
+        let set = RegexSet::new(patterns)
+            .map_err(|e| log::warn!("Could not compile regex filters into a set: {}", e))
+            .ok()
+            .filter(|set| !set.is_empty());
+
+        if let Ok(mut guard) = self.regex_set.write() {
+            *guard = set;
+        }
+    }
+
+    /// which patterns in the regex set match the response's body, and which match any of its
+    /// header names/values, respectively
+    fn regex_set_matches(set: &RegexSet, response: &FeroxResponse) -> (Vec<bool>, Vec<bool>) {
+        let mut body = vec![false; set.len()];
+        let mut headers = vec![false; set.len()];
+
+        for index in set.matches(response.text()).iter() {
+            body[index] = true;
+        }
+
+        let haystacks = response
+            .headers()
+            .iter()
+            .flat_map(|(k, v)| [k.as_str(), v.to_str().unwrap_or("")]);
+
+        for haystack in haystacks {
+            for index in set.matches(haystack).iter() {
+                headers[index] = true;
+            }
+
+            if headers.iter().all(|is_match| *is_match) {
+                break;
+            }
+        }
+
+        (body, headers)
+    }
+
     /// remove items from the underlying collection by their index
     ///
     /// note: indexes passed in should be index-to-remove+1. This is built for the scan mgt menu
@@ -62,6 +146,8 @@ impl FeroxFilters {
 
                 guard.remove(reduced_idx);
             }
+
+            self.rebuild_regex_set(&guard);
         }
     }
 
@@ -73,7 +159,38 @@ impl FeroxFilters {
         tx_stats: CommandSender,
     ) -> bool {
         if let Ok(filters) = self.filters.read() {
+            let has_wildcard = filters
+                .iter()
+                .any(|filter| filter.as_any().downcast_ref::<WildcardFilter>().is_some());
+
+            let regex_set = self.regex_set.read().ok();
+            let regex_set = regex_set.as_ref().and_then(|guard| (**guard).as_ref());
+
+            // results from the regex set are computed once, the first time a RegexFilter is seen
+            let mut regex_matches: Option<(Vec<bool>, Vec<bool>)> = None;
+            let mut regex_index = 0;
+
             for filter in filters.iter() {
+                if let (Some(set), Some(regex_filter)) =
+                    (regex_set, filter.as_any().downcast_ref::<RegexFilter>())
+                {
+                    let (body, headers) =
+                        regex_matches.get_or_insert_with(|| Self::regex_set_matches(set, response));
+
+                    let scope = regex_filter.scope;
+                    let matched = |matches: &[bool]| matches.get(regex_index).copied();
+                    let is_match = (scope.includes_body() && matched(body).unwrap_or(false))
+                        || (scope.includes_headers() && matched(headers).unwrap_or(false));
+                    regex_index += 1;
+
+                    if is_match {
+                        log::debug!("filtering response due to: {:?}", regex_filter);
+                        return true;
+                    }
+
+                    continue;
+                }
+
                 // wildcard.should_filter goes here
                 if filter.should_filter_response(response) {
                     log::debug!("filtering response due to: {:?}", filter);
@@ -81,13 +198,127 @@ impl FeroxFilters {
                         tx_stats
                             .send(AddToUsizeField(WildcardsFiltered, 1))
                             .unwrap_or_default();
+                        self.tally_wildcard(response, true);
                     }
                     return true;
                 }
             }
+
+            if has_wildcard {
+                self.tally_wildcard(response, false);
+            }
         }
         false
     }
+
+    /// every filter that would suppress the given response, as (filter id, filter, matched
+    /// value); ids match those shown in the scan management menu
+    ///
+    /// unlike `should_filter_response`, every filter is checked, and nothing is counted toward
+    /// statistics or wildcard tallies
+    pub fn explain(&self, response: &FeroxResponse) -> Vec<(usize, String, String)> {
+        let mut matches = Vec::new();
+
+        if let Ok(filters) = self.filters.read() {
+            for (index, filter) in filters.iter().enumerate() {
+                if filter.should_filter_response(response) {
+                    matches.push((
+                        index + 1,
+                        filter.to_string(),
+                        Self::matched_value(filter.as_ref(), response),
+                    ));
+                }
+            }
+        }
+
+        matches
+    }
+
+    /// the part of the response that the given (matching) filter matched on
+    fn matched_value(filter: &dyn FeroxFilter, response: &FeroxResponse) -> String {
+        let filter = filter.as_any();
+
+        if filter.is::<LinesFilter>() {
+            format!("{} lines", response.line_count())
+        } else if filter.is::<WordsFilter>() {
+            format!("{} words", response.word_count())
+        } else if filter.is::<SizeFilter>() {
+            format!("{} bytes", response.content_length())
+        } else if filter.is::<StatusCodeFilter>() {
+            format!("status {}", response.status().as_u16())
+        } else if let Some(regex) = filter.downcast_ref::<RegexFilter>() {
+            let in_body = regex
+                .scope
+                .includes_body()
+                .then(|| regex.compiled.find(response.text()))
+                .flatten()
+                .map(|found| format!("body contains {:?}", found.as_str()));
+
+            let in_headers = || {
+                response
+                    .headers()
+                    .iter()
+                    .map(|(k, v)| (k.as_str(), v.to_str().unwrap_or("")))
+                    .filter(|_| regex.scope.includes_headers())
+                    .find(|(k, v)| regex.compiled.is_match(k) || regex.compiled.is_match(v))
+                    .map(|(k, v)| format!("header {k}: {v}"))
+            };
+
+            in_body.or_else(in_headers).unwrap_or_default()
+        } else if let Some(similarity) = filter.downcast_ref::<SimilarityFilter>() {
+            format!("body similar to {}", similarity.original_url)
+        } else if filter.is::<WildcardFilter>() {
+            format!(
+                "{} {}, {} lines, {} words, {} bytes",
+                response.method(),
+                response.status().as_u16(),
+                response.line_count(),
+                response.word_count(),
+                response.content_length()
+            )
+        } else {
+            String::new()
+        }
+    }
+
+    /// count a response against its directory's wildcard tally
+    fn tally_wildcard(&self, response: &FeroxResponse, suppressed: bool) {
+        if let Ok(mut tallies) = self.wildcard_tallies.lock() {
+            let tally = tallies.entry(parent_directory(response.url())).or_default();
+
+            tally.hits += 1;
+            tally.suppressed += usize::from(suppressed);
+        }
+    }
+
+    /// a warning for the directory the response was found in, when wildcard filters have
+    /// suppressed more than `WILDCARD_WARN_RATIO` of its responses
+    ///
+    /// over-eager wildcard detection can hide real content, so each directory is only warned
+    /// about once, after `WILDCARD_WARN_MIN_HITS` responses have been seen in it
+    pub fn wildcard_warning(&self, response: &FeroxResponse) -> Option<String> {
+        let directory = parent_directory(response.url());
+
+        let mut tallies = self.wildcard_tallies.lock().ok()?;
+        let tally = tallies.get_mut(&directory)?;
+
+        let ratio = tally.suppressed as f64 / tally.hits as f64;
+
+        if tally.warned || tally.hits < WILDCARD_WARN_MIN_HITS || ratio <= WILDCARD_WARN_RATIO {
+            return None;
+        }
+
+        tally.warned = true;
+
+        Some(format!(
+            "{} Wildcard filters suppressed {} of {} responses ({:.1}%) under {}; real content may be hidden, consider --dont-filter or reviewing it manually",
+            status_colorizer("WRN"),
+            tally.suppressed,
+            tally.hits,
+            ratio * 100.0,
+            directory
+        ))
+    }
 }
 
 impl Serialize for FeroxFilters {
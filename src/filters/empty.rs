@@ -19,4 +19,9 @@ impl FeroxFilter for EmptyFilter {
     fn as_any(&self) -> &dyn Any {
         self
     }
+
+    /// never filters anything, so there's nothing to compute
+    fn cost(&self) -> FilterCost {
+        FilterCost::Cheap
+    }
 }
@@ -4,7 +4,6 @@ use super::{
 };
 use crate::{event_handlers::Handles, skip_fail, utils::fmt_err, Command::AddFilter};
 use anyhow::Result;
-use regex::Regex;
 use std::sync::Arc;
 
 /// add all user-supplied filters to the (already started) filters handler
@@ -47,13 +46,9 @@ pub async fn initialize(handles: Arc<Handles>) -> Result<()> {
 
     // add any regex filters to filters handler's FeroxFilters  (-X|--filter-regex)
     for regex_filter in &handles.config.filter_regex {
-        let raw = regex_filter;
-        let compiled = skip_fail!(Regex::new(raw));
+        // an optional body:|headers:|all: prefix limits what the regex is matched against
+        let filter = skip_fail!(RegexFilter::new(regex_filter));
 
-        let filter = RegexFilter {
-            raw_string: raw.to_owned(),
-            compiled,
-        };
         let boxed_filter = Box::new(filter);
         skip_fail!(handles.filters.send(AddFilter(boxed_filter)));
     }
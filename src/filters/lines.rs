@@ -30,4 +30,9 @@ impl FeroxFilter for LinesFilter {
     fn as_any(&self) -> &dyn Any {
         self
     }
+
+    /// compares against the line count counted when the response was read
+    fn cost(&self) -> FilterCost {
+        FilterCost::Cheap
+    }
 }
@@ -4,13 +4,13 @@ use std::any::Any;
 use std::fmt::Debug;
 
 use crate::response::FeroxResponse;
-use crate::traits::FeroxFilter;
+use crate::traits::{FeroxFilter, FilterCost};
 
 pub use self::container::FeroxFilters;
 pub(crate) use self::empty::EmptyFilter;
 pub use self::init::initialize;
 pub use self::lines::LinesFilter;
-pub use self::regex::RegexFilter;
+pub use self::regex::{RegexFilter, RegexScope};
 pub use self::similarity::{SimilarityFilter, SIM_HASHER};
 pub use self::size::SizeFilter;
 pub use self::status_code::StatusCodeFilter;
@@ -1,6 +1,40 @@
 use super::*;
 use ::regex::Regex;
 
+/// which parts of a response a `RegexFilter` is matched against
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RegexScope {
+    /// only the response body
+    Body,
+
+    /// only the response's header names and values (i.e. Set-Cookie values)
+    Headers,
+
+    /// both the body and the headers
+    #[default]
+    All,
+}
+
+/// implementation of RegexScope
+impl RegexScope {
+    /// whether or not the response body should be searched
+    pub fn includes_body(&self) -> bool {
+        matches!(self, Self::Body | Self::All)
+    }
+
+    /// whether or not the response headers should be searched
+    pub fn includes_headers(&self) -> bool {
+        matches!(self, Self::Headers | Self::All)
+    }
+
+    /// whether or not this is the default scope; used to keep the default out of serialized
+    /// filters
+    fn is_all(&self) -> bool {
+        matches!(self, Self::All)
+    }
+}
+
 /// Simple implementor of FeroxFilter; used to filter out responses based on a given regular
 /// expression; specified using -X|--filter-regex
 #[derive(Debug, Serialize, Deserialize)]
@@ -11,6 +45,10 @@ pub struct RegexFilter {
 
     /// Regular expression as passed in on the command line, not compiled
     pub raw_string: String,
+
+    /// which parts of the response are searched
+    #[serde(default, skip_serializing_if = "RegexScope::is_all")]
+    pub scope: RegexScope,
 }
 
 impl Default for RegexFilter {
@@ -18,10 +56,33 @@ impl Default for RegexFilter {
         Self {
             compiled: Regex::new("").unwrap(),
             raw_string: String::new(),
+            scope: RegexScope::default(),
         }
     }
 }
 
+/// implementation of RegexFilter
+impl RegexFilter {
+    /// create a RegexFilter from the value given to --filter-regex
+    ///
+    /// the value may be prefixed with a scope, i.e. `headers:.*cloudflare.*`, `body:^ignore me$`,
+    /// or `all:...`; without a prefix, both the body and headers are searched
+    pub fn new(raw: &str) -> Result<Self, ::regex::Error> {
+        let (scope, expression) = match raw.split_once(':') {
+            Some(("body", expression)) => (RegexScope::Body, expression),
+            Some(("headers", expression)) => (RegexScope::Headers, expression),
+            Some(("all", expression)) => (RegexScope::All, expression),
+            _ => (RegexScope::All, raw),
+        };
+
+        Ok(Self {
+            compiled: Regex::new(expression)?,
+            raw_string: raw.to_string(),
+            scope,
+        })
+    }
+}
+
 /// implementation of FeroxFilter for RegexFilter
 impl FeroxFilter for RegexFilter {
     /// Check `expression` against the response body, if the expression matches, the response
@@ -29,10 +90,12 @@ impl FeroxFilter for RegexFilter {
     fn should_filter_response(&self, response: &FeroxResponse) -> bool {
         log::trace!("enter: should_filter_response({:?} {})", self, response);
 
-        let result = self.compiled.is_match(response.text());
-        let other = response.headers().iter().any(|(k, v)| {
-            self.compiled.is_match(k.as_str()) || self.compiled.is_match(v.to_str().unwrap_or(""))
-        });
+        let result = self.scope.includes_body() && self.compiled.is_match(response.text());
+        let other = self.scope.includes_headers()
+            && response.headers().iter().any(|(k, v)| {
+                self.compiled.is_match(k.as_str())
+                    || self.compiled.is_match(v.to_str().unwrap_or(""))
+            });
 
         log::trace!("exit: should_filter_response -> {}", result || other);
 
@@ -48,6 +111,11 @@ impl FeroxFilter for RegexFilter {
     fn as_any(&self) -> &dyn Any {
         self
     }
+
+    /// runs the regex over the body and/or headers, depending on its scope
+    fn cost(&self) -> FilterCost {
+        FilterCost::Expensive
+    }
 }
 
 /// PartialEq implementation for RegexFilter
@@ -46,4 +46,9 @@ impl FeroxFilter for SimilarityFilter {
     fn as_any(&self) -> &dyn Any {
         self
     }
+
+    /// hashes the entire (preprocessed) response body
+    fn cost(&self) -> FilterCost {
+        FilterCost::Expensive
+    }
 }
@@ -30,4 +30,9 @@ impl FeroxFilter for SizeFilter {
     fn as_any(&self) -> &dyn Any {
         self
     }
+
+    /// compares against the content length counted when the response was read
+    fn cost(&self) -> FilterCost {
+        FilterCost::Cheap
+    }
 }
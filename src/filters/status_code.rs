@@ -37,4 +37,9 @@ impl FeroxFilter for StatusCodeFilter {
     fn as_any(&self) -> &dyn Any {
         self
     }
+
+    /// a single comparison against the response's status code
+    fn cost(&self) -> FilterCost {
+        FilterCost::Cheap
+    }
 }
@@ -1,7 +1,9 @@
 use super::*;
 use crate::nlp::preprocess;
-use crate::DEFAULT_METHOD;
+use crate::{DEFAULT_METHOD, WILDCARD_WARN_MIN_HITS};
 use ::regex::Regex;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
 
 #[test]
 /// simply test the default values for wildcardfilter
@@ -105,10 +107,12 @@ fn regex_filter_as_any() {
     let filter = RegexFilter {
         compiled,
         raw_string: raw.to_string(),
+        ..Default::default()
     };
     let filter2 = RegexFilter {
         compiled: compiled2,
         raw_string: raw.to_string(),
+        ..Default::default()
     };
 
     assert!(filter.box_eq(filter2.as_any()));
@@ -194,11 +198,97 @@ fn regexfilter_should_filter_when_regex_matches_on_response_body() {
     let filter = RegexFilter {
         raw_string: raw.to_string(),
         compiled: Regex::new(raw).unwrap(),
+        ..Default::default()
     };
 
     assert!(filter.should_filter_response(&resp));
 }
 
+#[test]
+/// regex filters evaluated as a set stay aligned with their filters as filters are added/removed
+fn ferox_filters_regex_set_tracks_regex_filters() {
+    let (tx, _rx) = tokio::sync::mpsc::unbounded_channel();
+
+    let mut resp = FeroxResponse::default();
+    resp.set_url("http://localhost/stuff");
+    resp.set_text("im a body response hurr durr!");
+
+    let regex = |raw: &str| {
+        Box::new(RegexFilter {
+            raw_string: raw.to_string(),
+            compiled: Regex::new(raw).unwrap(),
+            ..Default::default()
+        })
+    };
+
+    let data = FeroxFilters::default();
+    data.push(regex("doesnt-match")).unwrap();
+    data.push(Box::new(WordsFilter { word_count: 100 }))
+        .unwrap();
+    data.push(regex("hurr")).unwrap();
+    data.push(regex("also-doesnt-match")).unwrap();
+
+    assert!(data.should_filter_response(&resp, tx.clone()));
+
+    // removing the matching regex filter leaves nothing that matches
+    data.remove(&mut [3]);
+    assert!(!data.should_filter_response(&resp, tx.clone()));
+
+    // a filter matching on a header value is still found through the set
+    resp.headers_mut()
+        .insert("server", "nginx-hurr".parse().unwrap());
+    data.push(regex("nginx")).unwrap();
+    data.remove(&mut [1]);
+    assert!(data.should_filter_response(&resp, tx));
+}
+
+#[test]
+/// a directory whose responses are nearly all wildcards is warned about exactly once, and only
+/// after enough responses have been seen
+fn ferox_filters_warn_when_wildcards_suppress_most_hits() {
+    let (tx, _rx) = tokio::sync::mpsc::unbounded_channel();
+
+    let data = FeroxFilters::default();
+    data.push(Box::new(WildcardFilter {
+        content_length: Some(42),
+        status_code: 200,
+        ..Default::default()
+    }))
+    .unwrap();
+
+    let mut wildcard = FeroxResponse::default();
+    wildcard.set_url("http://localhost/api/stuff");
+    wildcard.set_text(&"x".repeat(42));
+
+    let mut real = FeroxResponse::default();
+    real.set_url("http://localhost/api/things");
+
+    assert!(!data.should_filter_response(&real, tx.clone()));
+
+    for _ in 0..WILDCARD_WARN_MIN_HITS - 2 {
+        assert!(data.should_filter_response(&wildcard, tx.clone()));
+    }
+
+    // 48 of 49; not enough responses seen yet
+    assert!(data.wildcard_warning(&wildcard).is_none());
+
+    assert!(data.should_filter_response(&wildcard, tx.clone()));
+
+    let warning = data.wildcard_warning(&wildcard).unwrap();
+    assert!(warning.contains("49 of 50"));
+    assert!(warning.contains("http://localhost/api/"));
+    assert!(warning.contains("--dont-filter"));
+
+    // only warned once per directory
+    assert!(data.should_filter_response(&wildcard, tx));
+    assert!(data.wildcard_warning(&wildcard).is_none());
+
+    // other directories are tallied separately
+    let mut elsewhere = FeroxResponse::default();
+    elsewhere.set_url("http://localhost/admin/stuff");
+    assert!(data.wildcard_warning(&elsewhere).is_none());
+}
+
 #[test]
 /// a few simple tests for similarity filter
 fn similarity_filter_is_accurate() {
@@ -284,3 +374,188 @@ fn remove_function_works_as_expected() {
         assert!(expected.contains(downcast));
     }
 }
+
+#[test]
+/// filters are kept sorted by their cost, cheapest first, regardless of insertion order
+fn ferox_filters_ordered_by_cost() {
+    let data = FeroxFilters::default();
+
+    data.push(Box::new(RegexFilter {
+        raw_string: "stuff".to_string(),
+        compiled: Regex::new("stuff").unwrap(),
+        ..Default::default()
+    }))
+    .unwrap();
+    data.push(Box::new(SimilarityFilter::default())).unwrap();
+    data.push(Box::new(StatusCodeFilter { filter_code: 404 }))
+        .unwrap();
+    data.push(Box::new(WordsFilter { word_count: 1 })).unwrap();
+
+    let costs: Vec<_> = data
+        .filters
+        .read()
+        .unwrap()
+        .iter()
+        .map(|filter| filter.cost())
+        .collect();
+
+    assert_eq!(
+        costs,
+        [
+            FilterCost::Cheap,
+            FilterCost::Cheap,
+            FilterCost::Expensive,
+            FilterCost::Expensive
+        ]
+    );
+
+    // same-cost filters keep their insertion order
+    let guard = data.filters.read().unwrap();
+    assert!(guard[0]
+        .as_any()
+        .downcast_ref::<StatusCodeFilter>()
+        .is_some());
+    assert!(guard[2].as_any().downcast_ref::<RegexFilter>().is_some());
+}
+
+/// filter with a fixed cost and outcome that counts how often it's evaluated
+#[derive(Debug)]
+struct CountingFilter {
+    cost: FilterCost,
+    matches: bool,
+    calls: Arc<AtomicUsize>,
+}
+
+impl FeroxFilter for CountingFilter {
+    fn should_filter_response(&self, _response: &FeroxResponse) -> bool {
+        self.calls.fetch_add(1, Ordering::Relaxed);
+        self.matches
+    }
+
+    fn box_eq(&self, other: &dyn Any) -> bool {
+        other
+            .downcast_ref::<Self>()
+            .map_or(false, |other| Arc::ptr_eq(&self.calls, &other.calls))
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn cost(&self) -> FilterCost {
+        self.cost
+    }
+}
+
+#[test]
+/// a response rejected by a cheap filter never reaches the more expensive ones, regardless of
+/// the order the filters were added in, and a response that passes runs each filter once
+fn filter_cost_ordering_skips_expensive_filters() {
+    let (tx, _rx) = tokio::sync::mpsc::unbounded_channel();
+    let resp = FeroxResponse::default();
+
+    let counter = |cost, matches| {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let filter = CountingFilter {
+            cost,
+            matches,
+            calls: calls.clone(),
+        };
+        (Box::new(filter) as Box<dyn FeroxFilter>, calls)
+    };
+
+    let (expensive, expensive_calls) = counter(FilterCost::Expensive, true);
+    let (moderate, moderate_calls) = counter(FilterCost::Moderate, true);
+    let (cheap, cheap_calls) = counter(FilterCost::Cheap, true);
+
+    let filters = FeroxFilters::default();
+    filters.push(expensive).unwrap();
+    filters.push(moderate).unwrap();
+    filters.push(cheap).unwrap();
+
+    assert!(filters.should_filter_response(&resp, tx.clone()));
+    assert_eq!(cheap_calls.load(Ordering::Relaxed), 1);
+    assert_eq!(moderate_calls.load(Ordering::Relaxed), 0);
+    assert_eq!(expensive_calls.load(Ordering::Relaxed), 0);
+
+    let (expensive, expensive_calls) = counter(FilterCost::Expensive, false);
+    let (cheap, cheap_calls) = counter(FilterCost::Cheap, false);
+
+    let filters = FeroxFilters::default();
+    filters.push(expensive).unwrap();
+    filters.push(cheap).unwrap();
+
+    assert!(!filters.should_filter_response(&resp, tx));
+    assert_eq!(cheap_calls.load(Ordering::Relaxed), 1);
+    assert_eq!(expensive_calls.load(Ordering::Relaxed), 1);
+}
+
+#[test]
+/// a scope prefix limits what a regex filter is matched against, both on its own and as part of
+/// the regex set
+fn regexfilter_scope_limits_matching() {
+    let (tx, _rx) = tokio::sync::mpsc::unbounded_channel();
+
+    let mut resp = FeroxResponse::default();
+    resp.set_url("http://localhost/stuff");
+    resp.set_text("served by nginx");
+    resp.headers_mut()
+        .insert("set-cookie", "__cf_bm=abc; path=/".parse().unwrap());
+
+    let headers = RegexFilter::new("headers:__cf_bm").unwrap();
+    assert_eq!(headers.scope, RegexScope::Headers);
+    assert_eq!(headers.raw_string, "headers:__cf_bm");
+    assert!(headers.should_filter_response(&resp));
+
+    let body_only = RegexFilter::new("body:__cf_bm").unwrap();
+    assert_eq!(body_only.scope, RegexScope::Body);
+    assert!(!body_only.should_filter_response(&resp));
+
+    let unscoped = RegexFilter::new("nginx").unwrap();
+    assert_eq!(unscoped.scope, RegexScope::All);
+    assert!(unscoped.should_filter_response(&resp));
+
+    // unknown prefixes are part of the expression
+    let unknown = RegexFilter::new("cookie:abc").unwrap();
+    assert_eq!(unknown.scope, RegexScope::All);
+    assert!(!unknown.should_filter_response(&resp));
+
+    let data = FeroxFilters::default();
+    data.push(Box::new(RegexFilter::new("headers:nginx").unwrap()))
+        .unwrap();
+    data.push(Box::new(RegexFilter::new("body:__cf_bm").unwrap()))
+        .unwrap();
+    assert!(!data.should_filter_response(&resp, tx.clone()));
+
+    data.push(Box::new(RegexFilter::new("all:path=/").unwrap()))
+        .unwrap();
+    assert!(data.should_filter_response(&resp, tx));
+}
+
+#[test]
+/// explain reports every matching filter, by menu id, along with the value it matched
+fn ferox_filters_explain_reports_matching_filters() {
+    let mut resp = FeroxResponse::default();
+    resp.set_url("http://localhost/stuff");
+    resp.set_text("im a body response hurr durr!");
+
+    let data = FeroxFilters::default();
+    data.push(Box::new(StatusCodeFilter { filter_code: 200 }))
+        .unwrap();
+    data.push(Box::new(WordsFilter { word_count: 100 }))
+        .unwrap();
+    data.push(Box::new(RegexFilter {
+        raw_string: String::from("hu+rr"),
+        compiled: Regex::new("hu+rr").unwrap(),
+        ..Default::default()
+    }))
+    .unwrap();
+
+    let explained = data.explain(&resp);
+
+    assert_eq!(explained.len(), 2);
+    assert_eq!(explained[0].0, 1);
+    assert_eq!(explained[0].2, "status 200");
+    assert_eq!(explained[1].0, 3);
+    assert_eq!(explained[1].2, "body contains \"hurr\"");
+}
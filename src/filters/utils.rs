@@ -7,7 +7,6 @@ use crate::response::FeroxResponse;
 use crate::utils::{logged_request, parse_url_with_raw_path};
 use crate::DEFAULT_METHOD;
 use anyhow::Result;
-use regex::Regex;
 use std::sync::Arc;
 
 /// wrapper around logic necessary to create a SimilarityFilter
@@ -84,11 +83,8 @@ pub(crate) fn filter_lookup(filter_type: &str, filter_value: &str) -> Option<Box
             }
         }
         "regex" => {
-            if let Ok(parsed) = Regex::new(filter_value) {
-                return Some(Box::new(super::RegexFilter {
-                    compiled: parsed,
-                    raw_string: filter_value.to_string(),
-                }));
+            if let Ok(parsed) = super::RegexFilter::new(filter_value) {
+                return Some(Box::new(parsed));
             }
         }
         "similarity" => {
@@ -111,6 +107,7 @@ mod tests {
     use crate::scan_manager::FeroxScans;
     use httpmock::Method::GET;
     use httpmock::MockServer;
+    use regex::Regex;
 
     #[test]
     /// filter_lookup returns correct filters
@@ -146,7 +143,8 @@ mod tests {
             filter.as_any().downcast_ref::<RegexFilter>().unwrap(),
             &RegexFilter {
                 compiled,
-                raw_string
+                raw_string,
+                ..Default::default()
             }
         );
 
@@ -151,6 +151,11 @@ impl FeroxFilter for WildcardFilter {
     fn as_any(&self) -> &dyn Any {
         self
     }
+
+    /// compares the response's method, status code, and counts, all known before filtering
+    fn cost(&self) -> FilterCost {
+        FilterCost::Cheap
+    }
 }
 
 impl std::fmt::Display for WildcardFilter {
@@ -0,0 +1,244 @@
+//! discovery of GraphQL endpoints and the operations they expose through introspection, see
+//! --graphql
+use anyhow::{bail, Context, Result};
+use console::style;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::traits::FeroxSerialize;
+use crate::utils::fmt_err;
+
+/// where GraphQL endpoints are commonly served from, tried in order against each target
+pub const GRAPHQL_LOCATIONS: [&str; 8] = [
+    "graphql",
+    "api/graphql",
+    "graphql/v1",
+    "v1/graphql",
+    "api/v1/graphql",
+    "graphiql",
+    "gql",
+    "query",
+];
+
+/// smallest possible query; any GraphQL server answers it, regardless of its schema
+pub const PROBE_QUERY: &str = r#"{"query":"{__typename}"}"#;
+
+/// asks for the root operation types and the fields of every type, which is all that's needed
+/// to list the available queries, mutations, and subscriptions
+pub const INTROSPECTION_QUERY: &str = r#"{"query":"query IntrospectionQuery { __schema { queryType { name } mutationType { name } subscriptionType { name } types { name fields { name } } } }"}"#;
+
+/// whether the given body is a GraphQL response, i.e. a json object with `data` or `errors`
+pub fn is_graphql_response(body: &str) -> bool {
+    match serde_json::from_str::<Value>(body) {
+        Ok(Value::Object(object)) => object.contains_key("data") || object.contains_key("errors"),
+        _ => false,
+    }
+}
+
+#[derive(Serialize, Deserialize, Default, Debug, Clone, PartialEq, Eq)]
+/// A discovered GraphQL endpoint and the operations its schema exposes
+pub struct GraphqlSchema {
+    #[serde(rename = "type")]
+    /// Name of this type of struct, used for serialization, i.e. `{"type":"graphql_schema"}`
+    pub(crate) kind: String,
+
+    /// The url of the endpoint
+    pub(crate) url: String,
+
+    /// whether or not the server answered the introspection query
+    pub(crate) introspection: bool,
+
+    /// names of the fields of the root query type
+    pub(crate) queries: Vec<String>,
+
+    /// names of the fields of the root mutation type
+    pub(crate) mutations: Vec<String>,
+
+    /// names of the fields of the root subscription type
+    pub(crate) subscriptions: Vec<String>,
+}
+
+impl GraphqlSchema {
+    /// a schema for an endpoint that refused introspection
+    pub fn without_introspection(url: &str) -> Self {
+        Self {
+            kind: String::from("graphql_schema"),
+            url: url.to_string(),
+            ..Default::default()
+        }
+    }
+
+    /// parse the response to `INTROSPECTION_QUERY`; fails when introspection was refused
+    pub fn from_introspection(url: &str, body: &str) -> Result<Self> {
+        let Ok(response) = serde_json::from_str::<Value>(body) else {
+            bail!("Expected a json response to the introspection query");
+        };
+
+        let Some(schema) = response.pointer("/data/__schema") else {
+            bail!("Introspection is disabled on {url}");
+        };
+
+        let types = schema
+            .get("types")
+            .and_then(Value::as_array)
+            .cloned()
+            .unwrap_or_default();
+
+        // root operation types are referred to by name, their fields live with the other types
+        let fields_of = |root: &str| -> Vec<String> {
+            let Some(name) = schema
+                .pointer(&format!("/{root}/name"))
+                .and_then(Value::as_str)
+            else {
+                return Vec::new();
+            };
+
+            types
+                .iter()
+                .find(|kind| kind.get("name").and_then(Value::as_str) == Some(name))
+                .and_then(|kind| kind.get("fields"))
+                .and_then(Value::as_array)
+                .into_iter()
+                .flatten()
+                .filter_map(|field| field.get("name").and_then(Value::as_str))
+                .map(String::from)
+                .collect()
+        };
+
+        Ok(Self {
+            kind: String::from("graphql_schema"),
+            url: url.to_string(),
+            introspection: true,
+            queries: fields_of("queryType"),
+            mutations: fields_of("mutationType"),
+            subscriptions: fields_of("subscriptionType"),
+        })
+    }
+
+    /// every operation name, regardless of its type
+    pub fn operations(&self) -> impl Iterator<Item = &String> {
+        self.queries
+            .iter()
+            .chain(&self.mutations)
+            .chain(&self.subscriptions)
+    }
+}
+
+/// Implementation of GraphqlSchema
+impl FeroxSerialize for GraphqlSchema {
+    /// Create a string representation of the schema
+    ///
+    /// ex:
+    /// GQL https://localhost/graphql queries: user, users | mutations: login
+    fn as_str(&self) -> String {
+        let mut parts = Vec::new();
+
+        for (name, operations) in [
+            ("queries", &self.queries),
+            ("mutations", &self.mutations),
+            ("subscriptions", &self.subscriptions),
+        ] {
+            if !operations.is_empty() {
+                parts.push(format!("{name}: {}", operations.join(", ")));
+            }
+        }
+
+        if !self.introspection {
+            parts.push(style("introspection disabled").yellow().to_string());
+        }
+
+        format!(
+            "{} {} {}\n",
+            style("GQL").bg(console::Color::Magenta).black(),
+            self.url,
+            parts.join(" | ")
+        )
+    }
+
+    /// Create an NDJSON representation of the schema
+    ///
+    /// (expanded for clarity)
+    /// ex:
+    /// {
+    ///   "type": "graphql_schema",
+    ///   "url": "https://localhost/graphql",
+    ///   "introspection": true,
+    ///   "queries": ["user", "users"],
+    ///   "mutations": ["login"],
+    ///   "subscriptions": []
+    /// }\n
+    fn as_json(&self) -> Result<String> {
+        let mut json = serde_json::to_string(&self)
+            .with_context(|| fmt_err(&format!("Could not convert {} to JSON", self.url)))?;
+        json.push('\n');
+        Ok(json)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// trimmed down response to `INTROSPECTION_QUERY`
+    const INTROSPECTION: &str = r#"{
+  "data": {
+    "__schema": {
+      "queryType": {"name": "Query"},
+      "mutationType": {"name": "Mutation"},
+      "subscriptionType": null,
+      "types": [
+        {"name": "Query", "fields": [{"name": "user"}, {"name": "users"}]},
+        {"name": "Mutation", "fields": [{"name": "login"}]},
+        {"name": "User", "fields": [{"name": "id"}, {"name": "email"}]},
+        {"name": "String", "fields": null}
+      ]
+    }
+  }
+}"#;
+
+    #[test]
+    /// fields of the root operation types are reported, fields of other types aren't
+    fn from_introspection_reads_root_operations() {
+        let schema =
+            GraphqlSchema::from_introspection("http://localhost/graphql", INTROSPECTION).unwrap();
+
+        assert!(schema.introspection);
+        assert_eq!(schema.queries, vec!["user", "users"]);
+        assert_eq!(schema.mutations, vec!["login"]);
+        assert!(schema.subscriptions.is_empty());
+        assert_eq!(schema.operations().count(), 3);
+    }
+
+    #[test]
+    /// a refused introspection query is an error
+    fn from_introspection_rejects_disabled_introspection() {
+        let refused = r#"{"errors": [{"message": "GraphQL introspection is not allowed"}]}"#;
+
+        assert!(GraphqlSchema::from_introspection("http://localhost/graphql", refused).is_err());
+        assert!(is_graphql_response(refused));
+        assert!(!is_graphql_response("<html></html>"));
+        assert!(!is_graphql_response(r#"["data"]"#));
+    }
+
+    #[test]
+    /// test as_str and as_json methods of GraphqlSchema
+    fn graphql_schema_serializes() {
+        let schema =
+            GraphqlSchema::from_introspection("http://localhost/graphql", INTROSPECTION).unwrap();
+
+        let schema_str = console::strip_ansi_codes(&schema.as_str()).to_string();
+        assert_eq!(
+            schema_str,
+            "GQL http://localhost/graphql queries: user, users | mutations: login\n"
+        );
+
+        let disabled = GraphqlSchema::without_introspection("http://localhost/graphql");
+        assert!(disabled.as_str().contains("introspection disabled"));
+
+        let json = schema.as_json().unwrap();
+        assert!(json.contains(r#""type":"graphql_schema""#));
+
+        let deserialized: GraphqlSchema = serde_json::from_str(&json).unwrap();
+        assert_eq!(deserialized, schema);
+    }
+}
@@ -1,16 +1,24 @@
-use std::collections::HashMap;
-use std::sync::Arc;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::io::stderr;
+use std::sync::{Arc, Mutex};
 
 use anyhow::{bail, Result};
 use futures::future;
+use reqwest::{header::LOCATION, Url};
 use scraper::{Html, Selector};
 use uuid::Uuid;
 
 use crate::filters::{SimilarityFilter, WildcardFilter, SIM_HASHER};
 use crate::message::FeroxMessage;
 use crate::nlp::preprocess;
+use crate::preflight::{self, PreflightResult};
 use crate::scanner::RESPONSES;
+use crate::schemes::{preferred_scheme, SchemeProbe};
+use crate::statistics::StatError;
+use crate::survey::{HeaderSurvey, SECURITY_HEADERS};
+use crate::traits::{FeroxFilter, FeroxSerialize};
 use crate::{
+    banner::TargetRecon,
     config::OutputLevel,
     event_handlers::{Command, Handles},
     progress::PROGRESS_PRINTER,
@@ -18,7 +26,7 @@ use crate::{
     skip_fail,
     url::FeroxUrl,
     utils::{ferox_print, fmt_err, logged_request},
-    DEFAULT_METHOD,
+    DEFAULT_METHOD, PROXY_CHECK_URL,
 };
 
 /// enum representing the different servers that `parse_html` can detect when directory listing is
@@ -40,6 +48,41 @@ pub enum DirListingType {
     None,
 }
 
+/// Directory listing heuristic detection, uses <title> tag to make its determination. When
+/// the inner html of <title> matches one of the following, a `DirListingType` is returned.
+/// - apache: `Index of /`
+/// - tomcat/python: `Directory Listing for /`
+/// - ASP.NET: `Directory Listing -- /`
+/// - <host> - /: iis, azure, skipping due to loose heuristic
+pub(crate) fn directory_listing_type(html: &Html) -> Option<DirListingType> {
+    log::trace!("enter: directory_listing_type(html body...)");
+
+    let title_selector = Selector::parse("title").expect("couldn't parse title selector");
+
+    for t in html.select(&title_selector) {
+        let title = t.inner_html().to_lowercase();
+
+        let dirlist_type = if title.contains("directory listing for /") {
+            Some(DirListingType::TomCatOrPython)
+        } else if title.contains("index of /") {
+            Some(DirListingType::Apache)
+        } else if title.contains("directory listing -- /") {
+            Some(DirListingType::AspDotNet)
+        } else {
+            // IIS_AZURE purposely skipped for now
+            None
+        };
+
+        if dirlist_type.is_some() {
+            log::trace!("exit: directory_listing_type -> {:?}", dirlist_type);
+            return dirlist_type;
+        }
+    }
+
+    log::trace!("exit: directory_listing_type -> None");
+    None
+}
+
 /// Wrapper around the results of running a directory listing detection against a target web page
 #[derive(Debug, Clone)]
 pub struct DirListingResult {
@@ -61,17 +104,52 @@ pub enum WildcardResult {
     FourOhFourLike(usize),
 }
 
+/// lowercased snippets found in common cookie consent and anti-bot interstitial pages
+const INTERSTITIAL_MARKERS: [&str; 14] = [
+    "cf-browser-verification",
+    "challenge-platform",
+    "just a moment...",
+    "checking your browser",
+    "_incapsula_resource",
+    "ddos-guard",
+    "please enable javascript",
+    "enable javascript and cookies",
+    "captcha-delivery",
+    "cookieconsent",
+    "onetrust-consent",
+    "cookiebot",
+    "consent-banner",
+    "__cf_chl_",
+];
+
+/// determine whether the given response body looks like a cookie consent or anti-bot
+/// interstitial page
+fn looks_like_interstitial(text: &str) -> bool {
+    let text = text.to_lowercase();
+
+    INTERSTITIAL_MARKERS
+        .iter()
+        .any(|marker| text.contains(marker))
+}
+
 /// container for heuristics related info
 pub struct HeuristicTests {
     /// Handles object for event handler interaction
     handles: Arc<Handles>,
+
+    /// status code (or `None` when unreachable) of each url requested by `probe_schemes`, so
+    /// that `connectivity` doesn't need to request it again (--smart-schemes)
+    scheme_cache: Mutex<HashMap<String, Option<u16>>>,
 }
 
 /// HeuristicTests implementation
 impl HeuristicTests {
     /// create a new HeuristicTests struct
     pub fn new(handles: Arc<Handles>) -> Self {
-        Self { handles }
+        Self {
+            handles,
+            scheme_cache: Mutex::new(HashMap::new()),
+        }
     }
 
     /// Simple helper to return a uuid, formatted as lowercase without hyphens
@@ -93,27 +171,269 @@ impl HeuristicTests {
         unique_id
     }
 
+    /// Request each bare hostname (i.e. --url example.com) over both http and https, and pick
+    /// which scheme(s) to scan based on which are live and whether http redirects to https
+    ///
+    /// targets that already have a scheme are passed through untouched. Returns the targets to
+    /// scan, along with what was learned about each bare hostname
+    ///
+    /// with --smart-schemes, a hostname whose port conventionally speaks one scheme (i.e. :8080
+    /// or :8443) is requested over that scheme first, and the other scheme is only tried when
+    /// the first doesn't answer or redirects elsewhere
+    ///
+    /// with --no-scheme-probe, nothing is requested and bare hostnames are scanned over
+    /// --protocol
+    pub async fn probe_schemes(&self, targets: &[String]) -> (Vec<String>, Vec<SchemeProbe>) {
+        log::trace!("enter: probe_schemes({:?})", targets);
+
+        let mut resolved = Vec::new();
+        let mut probes = Vec::new();
+
+        for target in targets {
+            if target.contains("://") {
+                resolved.push(target.to_owned());
+                continue;
+            }
+
+            if self.handles.config.no_scheme_probe {
+                let probed = vec![format!("{}://{target}", self.handles.config.protocol)];
+
+                if let Ok(scans) = self.handles.ferox_scans() {
+                    scans.rekey_target_output_level(target, &probed);
+                }
+
+                resolved.extend(probed);
+                continue;
+            }
+
+            let preferred = if self.handles.config.smart_schemes {
+                preferred_scheme(target)
+            } else {
+                None
+            };
+
+            let probe = match preferred {
+                Some(first) => self.probe_preferred_scheme(first, target).await,
+                None => {
+                    let (http, https) = future::join(
+                        self.probe_scheme("http", target),
+                        self.probe_scheme("https", target),
+                    )
+                    .await;
+
+                    SchemeProbe::new(target, http.as_ref(), https.as_ref())
+                }
+            };
+
+            log::info!("{}", console::strip_ansi_codes(&probe.to_string()));
+
+            let probed = probe.targets(
+                &self.handles.config.protocol,
+                self.handles.config.both_schemes,
+            );
+
+            if let Ok(scans) = self.handles.ferox_scans() {
+                // a level read from stdin (quiet example.com) was keyed by the bare hostname
+                scans.rekey_target_output_level(target, &probed);
+            }
+
+            resolved.extend(probed);
+
+            probes.push(probe);
+        }
+
+        log::trace!("exit: probe_schemes -> {:?} {:?}", resolved, probes);
+        (resolved, probes)
+    }
+
+    /// request the given host over the scheme its port conventionally speaks, only falling back
+    /// to the other scheme when that's needed to pick what to scan
+    async fn probe_preferred_scheme(&self, first: &'static str, host: &str) -> SchemeProbe {
+        let second = if first == "http" { "https" } else { "http" };
+
+        let first_response = self.probe_scheme(first, host).await;
+
+        let (http, https) = if first == "http" {
+            (first_response.as_ref(), None)
+        } else {
+            (None, first_response.as_ref())
+        };
+
+        let probe = SchemeProbe::new(host, http, https);
+
+        // a live scheme settles things, unless every scheme is wanted, or http says to use https
+        let settled = first_response.is_some()
+            && !self.handles.config.both_schemes
+            && !probe.upgrades_to_https;
+
+        if settled {
+            log::debug!("skipping {second}://{host}, its port speaks {first}");
+
+            return SchemeProbe {
+                skipped: Some(second),
+                ..probe
+            };
+        }
+
+        let second_response = self.probe_scheme(second, host).await;
+
+        if first == "http" {
+            SchemeProbe::new(host, first_response.as_ref(), second_response.as_ref())
+        } else {
+            SchemeProbe::new(host, second_response.as_ref(), first_response.as_ref())
+        }
+    }
+
+    /// request the given host over the given scheme, returning the response if there was one
+    async fn probe_scheme(&self, scheme: &str, host: &str) -> Option<reqwest::Response> {
+        let target = format!("{scheme}://{host}");
+        let url = FeroxUrl::from_string(&target, self.handles.clone());
+        let request = url.format("", None).ok()?;
+
+        let response = logged_request(&request, DEFAULT_METHOD, None, self.handles.clone())
+            .await
+            .ok();
+
+        if let Ok(mut cache) = self.scheme_cache.lock() {
+            cache.insert(
+                request.to_string(),
+                response.as_ref().map(|r| r.status().as_u16()),
+            );
+        }
+
+        response
+    }
+
+    /// status code seen for the given url while probing schemes, if it answered
+    ///
+    /// only consulted with --smart-schemes, and never when the recon banner needs the full
+    /// response
+    fn cached_probe(&self, url: &Url) -> Option<u16> {
+        if !self.handles.config.smart_schemes || self.handles.config.recon_banner {
+            return None;
+        }
+
+        self.scheme_cache
+            .lock()
+            .ok()
+            .and_then(|cache| cache.get(url.as_str()).copied().flatten())
+    }
+
+    /// Make sure --proxy works before scanning through it, returning the proxy's exit ip
+    ///
+    /// the request goes to a public ip echo service by hostname, so for socks5h proxies a
+    /// successful check also shows that names are resolved on the proxy's side
+    pub async fn proxy_check(&self) -> Result<String> {
+        log::trace!("enter: proxy_check({})", self.handles.config.proxy);
+
+        if self.handles.config.proxy.is_empty() {
+            bail!("--proxy-check was given without a proxy to check");
+        }
+
+        let exit_ip = match self
+            .handles
+            .config
+            .client
+            .get(PROXY_CHECK_URL)
+            .send()
+            .await
+            .and_then(|response| response.error_for_status())
+        {
+            Ok(response) => response.text().await?.trim().to_string(),
+            Err(e) => bail!(
+                "Could not reach {} through {}\n  => {}",
+                PROXY_CHECK_URL,
+                self.handles.config.proxy,
+                e
+            ),
+        };
+
+        log::info!("{} exits from {}", self.handles.config.proxy, exit_ip);
+
+        log::trace!("exit: proxy_check -> {}", exit_ip);
+        Ok(exit_ip)
+    }
+
     /// Simply tries to connect to all given sites before starting to scan
     ///
-    /// In the event that no sites can be reached, the program will exit.
+    /// Unreachable targets are retried --preflight-retries times. What happens to targets that
+    /// still can't be reached depends on --preflight; by default, they're skipped. Each target's
+    /// result is written to --preflight-report, when given. In the event that no sites can be
+    /// reached, or --preflight deems a failure fatal, the program will exit.
     ///
     /// Any urls that are found to be alive are returned to the caller.
     pub async fn connectivity(&self, target_urls: &[String]) -> Result<Vec<String>> {
         log::trace!("enter: connectivity_test({:?})", target_urls);
 
         let mut good_urls = vec![];
+        let mut results = vec![];
 
         for target_url in target_urls {
             let url = FeroxUrl::from_string(target_url, self.handles.clone());
             let request = skip_fail!(url.format("", None));
 
-            let result = logged_request(&request, DEFAULT_METHOD, None, self.handles.clone()).await;
+            if let Some(status) = self.cached_probe(&request) {
+                // already answered while probing schemes, no need to ask again
+                results.push(PreflightResult::live(request.as_str(), status, 1));
+                good_urls.push(target_url.to_owned());
+                continue;
+            }
+
+            let mut attempts = 0;
+
+            let result = loop {
+                attempts += 1;
+
+                let result =
+                    logged_request(&request, DEFAULT_METHOD, None, self.handles.clone()).await;
+
+                if result.is_ok() || attempts > self.handles.config.preflight_retries {
+                    break result;
+                }
+
+                log::info!(
+                    "Could not connect to {}, retrying ({}/{})",
+                    target_url,
+                    attempts,
+                    self.handles.config.preflight_retries
+                );
+            };
 
             match result {
-                Ok(_) => {
+                Ok(response) => {
+                    results.push(PreflightResult::live(
+                        request.as_str(),
+                        response.status().as_u16(),
+                        attempts,
+                    ));
+
+                    if self.handles.config.recon_banner
+                        && matches!(self.handles.config.output_level, OutputLevel::Default)
+                    {
+                        // pre-scan intelligence is printed beneath the banner, so it's only shown
+                        // when the banner is
+                        let recon =
+                            TargetRecon::gather(target_url, &response, &self.handles.config).await;
+
+                        recon
+                            .print_to(stderr())
+                            .unwrap_or_else(|e| log::warn!("Could not print recon banner: {}", e));
+                    }
+
                     good_urls.push(target_url.to_owned());
                 }
                 Err(e) => {
+                    let error = e
+                        .downcast_ref::<reqwest::Error>()
+                        .map_or(StatError::Other, StatError::from);
+
+                    results.push(PreflightResult::dead(
+                        request.as_str(),
+                        error,
+                        &format!("{e:#}"),
+                        attempts,
+                    ));
+
                     if matches!(
                         self.handles.config.output_level,
                         OutputLevel::Default | OutputLevel::Quiet
@@ -138,6 +458,24 @@ impl HeuristicTests {
             }
         }
 
+        if !self.handles.config.preflight_report.is_empty() {
+            preflight::write_report(&self.handles.config.preflight_report, &results)?;
+        }
+
+        let fatal: Vec<_> = results
+            .iter()
+            .filter(|result| result.is_fatal(&self.handles.config.preflight))
+            .map(|result| result.url.as_str())
+            .collect();
+
+        if !fatal.is_empty() {
+            bail!(
+                "Could not connect to {} (--preflight {})",
+                fatal.join(", "),
+                self.handles.config.preflight
+            );
+        }
+
         if good_urls.is_empty() {
             bail!("Could not connect to any target provided");
         }
@@ -146,6 +484,102 @@ impl HeuristicTests {
         Ok(good_urls)
     }
 
+    /// one-shot, per-host probe (--survey-headers) that collects security-relevant response
+    /// headers, the methods advertised via OPTIONS, and whether TRACE is reflected
+    ///
+    /// results are printed and sent to the file handler; failures are logged and skipped
+    pub async fn survey_headers(&self, target_urls: &[String]) -> Result<()> {
+        log::trace!("enter: survey_headers({:?})", target_urls);
+
+        if !self.handles.config.survey_headers {
+            log::trace!("exit: survey_headers -> survey_headers is false");
+            return Ok(());
+        }
+
+        let mut surveyed_hosts = HashSet::new();
+
+        for target_url in target_urls {
+            let url = FeroxUrl::from_string(target_url, self.handles.clone());
+            let request = skip_fail!(url.format("", None));
+
+            // only the first target for a given scheme/host/port combination is surveyed
+            if !surveyed_hosts.insert(request.origin().ascii_serialization()) {
+                continue;
+            }
+
+            let response = skip_fail!(
+                logged_request(&request, DEFAULT_METHOD, None, self.handles.clone()).await
+            );
+
+            let mut present = BTreeMap::new();
+            let mut missing = Vec::new();
+
+            for header in SECURITY_HEADERS {
+                match response.headers().get(header) {
+                    Some(value) => {
+                        present.insert(
+                            header.to_string(),
+                            String::from_utf8_lossy(value.as_bytes()).to_string(),
+                        );
+                    }
+                    None => missing.push(header.to_string()),
+                }
+            }
+
+            let allowed_methods =
+                match logged_request(&request, "OPTIONS", None, self.handles.clone()).await {
+                    Ok(options) => options
+                        .headers()
+                        .get("allow")
+                        .and_then(|value| value.to_str().ok())
+                        .map(|value| {
+                            value
+                                .split(',')
+                                .map(|method| method.trim().to_uppercase())
+                                .filter(|method| !method.is_empty())
+                                .collect()
+                        })
+                        .unwrap_or_default(),
+                    Err(_) => Vec::new(),
+                };
+
+            // a server with TRACE enabled echoes the request back as the response body
+            let trace_enabled =
+                match logged_request(&request, "TRACE", None, self.handles.clone()).await {
+                    Ok(trace) if trace.status().is_success() => trace
+                        .text()
+                        .await
+                        .map_or(false, |body| body.starts_with("TRACE ")),
+                    _ => false,
+                };
+
+            let survey = HeaderSurvey {
+                kind: "header_survey".to_string(),
+                url: request.to_string(),
+                present,
+                missing,
+                allowed_methods,
+                trace_enabled,
+            };
+
+            if matches!(
+                self.handles.config.output_level,
+                OutputLevel::Default | OutputLevel::Quiet
+            ) {
+                ferox_print(&survey.as_str(), &PROGRESS_PRINTER);
+            }
+
+            self.handles
+                .output
+                .tx_file
+                .send(Command::WriteSurvey(Box::new(survey)))
+                .unwrap_or_default();
+        }
+
+        log::trace!("exit: survey_headers");
+        Ok(())
+    }
+
     /// heuristic designed to detect when a server has directory listing enabled
     pub async fn directory_listing(&self, target_url: &str) -> Result<Option<DirListingResult>> {
         log::trace!("enter: directory_listing({})", target_url);
@@ -216,39 +650,262 @@ impl HeuristicTests {
         Ok(None)
     }
 
-    /// Directory listing heuristic detection, uses <title> tag to make its determination. When
-    /// the inner html of <title> matches one of the following, a `DirListingType` is returned.
-    /// - apache: `Index of /`
-    /// - tomcat/python: `Directory Listing for /`
-    /// - ASP.NET: `Directory Listing -- /`
-    /// - <host> - /: iis, azure, skipping due to loose heuristic
-    fn detect_directory_listing(&self, html: &Html) -> Option<DirListingType> {
-        log::trace!("enter: detect_directory_listing(html body...)");
+    /// heuristic designed to detect single-page applications, i.e. servers that respond to every
+    /// path with the same catch-all `index.html` (the app shell)
+    ///
+    /// the target is requested along with two nonexistent paths (one nested); if all three are
+    /// 2xx html responses whose bodies are similar to one another, the target's response is
+    /// returned so that it can be used as the basis for a similarity filter
+    pub async fn detect_spa(&self, target_url: &str) -> Result<Option<FeroxResponse>> {
+        log::trace!("enter: detect_spa({})", target_url);
+
+        if !self.handles.config.spa_mode {
+            log::trace!("exit: detect_spa -> spa_mode is false");
+            return Ok(None);
+        }
 
-        let title_selector = Selector::parse("title").expect("couldn't parse title selector");
+        let url = FeroxUrl::from_string(target_url, self.handles.clone());
 
-        for t in html.select(&title_selector) {
-            let title = t.inner_html().to_lowercase();
+        let paths = [
+            String::new(),
+            self.unique_string(1),
+            format!("{}/{}", self.unique_string(1), self.unique_string(1)),
+        ];
 
-            let dirlist_type = if title.contains("directory listing for /") {
-                Some(DirListingType::TomCatOrPython)
-            } else if title.contains("index of /") {
-                Some(DirListingType::Apache)
-            } else if title.contains("directory listing -- /") {
-                Some(DirListingType::AspDotNet)
-            } else {
-                // IIS_AZURE purposely skipped for now
-                None
-            };
+        let mut responses = Vec::with_capacity(paths.len());
+
+        for path in paths.iter() {
+            let request = url.format(path, None)?;
+
+            let response =
+                logged_request(&request, DEFAULT_METHOD, None, self.handles.clone()).await?;
 
-            if dirlist_type.is_some() {
-                log::trace!("exit: detect_directory_listing -> {:?}", dirlist_type);
-                return dirlist_type;
+            let ferox_response = FeroxResponse::from(
+                response,
+                &url.target,
+                DEFAULT_METHOD,
+                self.handles.config.output_level,
+            )
+            .await;
+
+            let is_html = ferox_response
+                .headers()
+                .get("content-type")
+                .and_then(|value| value.to_str().ok())
+                .map_or(false, |value| value.contains("text/html"));
+
+            if !ferox_response.status().is_success() || !is_html {
+                // a catch-all app shell is always served as a 2xx html page
+                log::trace!("exit: detect_spa -> None");
+                return Ok(None);
             }
+
+            responses.push(ferox_response);
+        }
+
+        let app_shell = responses.remove(0);
+
+        let shell_filter = SimilarityFilter {
+            hash: SIM_HASHER.create_signature(preprocess(app_shell.text()).iter()),
+            original_url: app_shell.url().to_string(),
+        };
+
+        if !responses
+            .iter()
+            .all(|resp| shell_filter.should_filter_response(resp))
+        {
+            log::trace!("exit: detect_spa -> None");
+            return Ok(None);
+        }
+
+        let msg = format!("detected single-page application: {target_url}");
+
+        let ferox_msg = FeroxMessage {
+            kind: "log".to_string(),
+            message: msg.clone(),
+            level: "MSG".to_string(),
+            time_offset: 0.0,
+            module: "feroxbuster::heuristics".to_string(),
+        };
+
+        self.handles
+            .output
+            .tx_file
+            .send(Command::WriteToDisk(Box::new(ferox_msg)))
+            .unwrap_or_default();
+
+        log::info!("{}", msg);
+
+        log::trace!("exit: detect_spa -> {}", app_shell);
+        Ok(Some(app_shell))
+    }
+
+    /// heuristic designed to detect targets that front every response with the same
+    /// interstitial page (cookie consent, anti-bot javascript challenge, etc...)
+    ///
+    /// only runs when --interstitial-bypass is used. The target is requested along with two
+    /// nonexistent paths (one nested); if all three responses are similar to one another and
+    /// the target's response looks like an interstitial, the target is fronted by one. Its
+    /// origin is then recorded so that all further requests to it (including the 404
+    /// baselines that follow this test) carry the bypass headers
+    pub async fn detect_interstitial(&self, target_url: &str) -> Result<bool> {
+        log::trace!("enter: detect_interstitial({})", target_url);
+
+        if self.handles.config.interstitial_client.is_none() {
+            log::trace!("exit: detect_interstitial -> no bypass configured");
+            return Ok(false);
+        }
+
+        let url = FeroxUrl::from_string(target_url, self.handles.clone());
+
+        let paths = [
+            String::new(),
+            self.unique_string(1),
+            format!("{}/{}", self.unique_string(1), self.unique_string(1)),
+        ];
+
+        let mut responses = Vec::with_capacity(paths.len());
+
+        for path in paths.iter() {
+            let request = url.format(path, None)?;
+
+            let response =
+                logged_request(&request, DEFAULT_METHOD, None, self.handles.clone()).await?;
+
+            let ferox_response = FeroxResponse::from(
+                response,
+                &url.target,
+                DEFAULT_METHOD,
+                self.handles.config.output_level,
+            )
+            .await;
+
+            responses.push(ferox_response);
+        }
+
+        let front = responses.remove(0);
+
+        if !looks_like_interstitial(front.text()) {
+            log::trace!("exit: detect_interstitial -> false");
+            return Ok(false);
+        }
+
+        let front_filter = SimilarityFilter {
+            hash: SIM_HASHER.create_signature(preprocess(front.text()).iter()),
+            original_url: front.url().to_string(),
+        };
+
+        if !responses
+            .iter()
+            .all(|resp| front_filter.should_filter_response(resp))
+        {
+            log::trace!("exit: detect_interstitial -> false");
+            return Ok(false);
+        }
+
+        self.handles
+            .ferox_scans()?
+            .add_interstitial_origin(target_url);
+
+        let msg = format!("detected interstitial page: {target_url} (sending bypass headers)");
+
+        let ferox_msg = FeroxMessage {
+            kind: "log".to_string(),
+            message: msg.clone(),
+            level: "MSG".to_string(),
+            time_offset: 0.0,
+            module: "feroxbuster::heuristics".to_string(),
+        };
+
+        self.handles
+            .output
+            .tx_file
+            .send(Command::WriteToDisk(Box::new(ferox_msg)))
+            .unwrap_or_default();
+
+        log::info!("{}", msg);
+
+        log::trace!("exit: detect_interstitial -> true");
+        Ok(true)
+    }
+
+    /// heuristic used by --auto-slash to decide whether requests beneath the given directory
+    /// should have a / appended
+    ///
+    /// the directory is requested without its trailing slash; when the server redirects to the
+    /// slashed variant, slashless requests are enough to find directories (the redirect gives
+    /// them away), so no slash is appended. Otherwise, directories can only be found by asking
+    /// for them with a slash. The decision is recorded for the directory and inherited by its
+    /// children until they're tested themselves.
+    ///
+    /// the root of a target can't be requested without a slash, so roots aren't tested
+    pub async fn detect_slash_redirects(&self, target_url: &str) -> Result<Option<bool>> {
+        log::trace!("enter: detect_slash_redirects({})", target_url);
+
+        if !self.handles.config.auto_slash || self.handles.config.add_slash {
+            log::trace!("exit: detect_slash_redirects -> auto_slash is false");
+            return Ok(None);
+        }
+
+        let directory = Url::parse(&format!("{}/", target_url.trim_end_matches('/')))?;
+
+        if directory.path() == "/" {
+            log::trace!("exit: detect_slash_redirects -> nothing to test at the root");
+            return Ok(None);
         }
 
-        log::trace!("exit: detect_directory_listing -> None");
-        None
+        let slashless = Url::parse(directory.as_str().trim_end_matches('/'))?;
+
+        let data = if self.handles.config.data.is_empty() {
+            None
+        } else {
+            Some(self.handles.config.data.as_slice())
+        };
+
+        let response =
+            logged_request(&slashless, DEFAULT_METHOD, data, self.handles.clone()).await?;
+
+        let redirected = if response.status().is_redirection() {
+            // Location may be relative, i.e. `/admin/`, so it's resolved against the request url
+            response
+                .headers()
+                .get(LOCATION)
+                .and_then(|location| location.to_str().ok())
+                .and_then(|location| slashless.join(location).ok())
+                .is_some_and(|location| location.path() == directory.path())
+        } else {
+            // when --redirects is used, the redirect was already followed
+            self.handles.config.redirects && response.url().path() == directory.path()
+        };
+
+        let add_slash = !redirected;
+
+        self.handles
+            .ferox_scans()?
+            .set_add_slash(directory.as_str(), add_slash);
+
+        log::info!(
+            "{} {} slashless requests; {} / to requests beneath it",
+            directory,
+            if redirected {
+                "redirects"
+            } else {
+                "doesn't redirect"
+            },
+            if add_slash {
+                "appending"
+            } else {
+                "not appending"
+            }
+        );
+
+        log::trace!("exit: detect_slash_redirects -> {}", add_slash);
+        Ok(Some(add_slash))
+    }
+
+    /// Directory listing heuristic detection, see `directory_listing_type`
+    fn detect_directory_listing(&self, html: &Html) -> Option<DirListingType> {
+        directory_listing_type(html)
     }
 
     /// given a target's base url, attempt to automatically detect its 404 response
@@ -275,7 +932,7 @@ impl HeuristicTests {
         };
 
         // To take care of slash when needed
-        let slash = if self.handles.config.add_slash {
+        let slash = if FeroxUrl::from_string(target_url, self.handles.clone()).should_add_slash() {
             Some("/")
         } else {
             None
@@ -619,6 +1276,20 @@ mod tests {
         }
     }
 
+    #[test]
+    /// common interstitial pages are recognized, regular pages aren't
+    fn looks_like_interstitial_matches_known_markers() {
+        assert!(looks_like_interstitial(
+            "<title>Just a moment...</title><script src=\"/cdn-cgi/challenge-platform/h/b\">"
+        ));
+        assert!(looks_like_interstitial(
+            "<div id=\"CookieConsent\">we use cookies</div>"
+        ));
+        assert!(!looks_like_interstitial(
+            "<html><body><h1>Welcome!</h1></body></html>"
+        ));
+    }
+
     #[test]
     /// `detect_directory_listing` correctly identifies tomcat/python instances
     fn detect_directory_listing_finds_tomcat_python() {
@@ -10,26 +10,54 @@ use tokio::{
 
 use crate::event_handlers::Command;
 
+#[cfg(not(any(feature = "native-tls", feature = "rustls")))]
+compile_error!("at least one of the native-tls or rustls features must be enabled");
+
 pub mod banner;
+pub mod campaign;
 pub mod config;
 mod client;
+pub mod dns;
 pub mod event_handlers;
+pub mod export;
 pub mod filters;
 pub mod heuristics;
 pub mod logger;
+pub mod notify;
 mod parser;
+pub mod preflight;
 pub mod progress;
+mod proxy_auth;
+pub mod refilter;
+pub mod report;
 pub mod scan_manager;
 pub mod scanner;
+pub mod schemes;
+pub mod selftest;
+mod sarif;
+mod semaphore;
 pub mod statistics;
+mod survey;
+pub mod theme;
 mod traits;
+pub mod upload;
+pub mod updater;
 pub mod utils;
+pub mod wordlists;
 mod extractor;
 mod macros;
 mod url;
 mod response;
 mod message;
 mod nlp;
+mod tags;
+mod template;
+mod metadata;
+mod nuclei;
+mod openapi;
+mod graphql;
+mod xml;
+mod zap;
 
 /// Alias for tokio::sync::mpsc::UnboundedSender<Command>
 pub(crate) type CommandSender = UnboundedSender<Command>;
@@ -49,6 +77,10 @@ pub(crate) type ExtractionResult = HashSet<String>;
 /// Version pulled from Cargo.toml at compile time
 pub(crate) const VERSION: &str = env!("CARGO_PKG_VERSION");
 
+/// Version of the NDJSON record schemas written by --json; bumped whenever a field is removed or
+/// changes meaning, see `metadata`
+pub(crate) const OUTPUT_SCHEMA_VERSION: u32 = 1;
+
 /// Maximum number of file descriptors that can be opened during a scan
 pub const DEFAULT_OPEN_FILE_LIMIT: u64 = 8192;
 
@@ -82,9 +114,69 @@ pub const SECONDARY_WORDLIST: &str =
 /// Number of milliseconds to wait between polls of `PAUSE_SCAN` when user pauses a scan
 pub(crate) const SLEEP_DURATION: u64 = 500;
 
+/// Number of request outcomes a worker tallies locally before sending them to the stats handler
+pub(crate) const STATS_BATCH_SIZE: usize = 64;
+
+/// Maximum number of replayed requests held in memory while the replay proxy is unreachable
+pub(crate) const REPLAY_BUFFER_SIZE: usize = 1000;
+
+/// Number of seconds to wait between health checks of an unreachable replay proxy
+pub(crate) const REPLAY_HEALTH_INTERVAL: u64 = 5;
+
+/// Number of seconds after which a state file is too old to be offered for automatic resumption
+pub(crate) const RECENT_STATE_FILE_AGE: u64 = 60 * 60 * 24 * 7;
+
+/// Requests per second below which a scan's progress bar shows the word currently being requested
+pub(crate) const SLOW_SCAN_RATE: f64 = 5.0;
+
+/// Number of milliseconds between updates of the word shown in a slow scan's progress bar
+pub(crate) const CURRENT_WORD_INTERVAL: u64 = 1000;
+
+/// Service that echoes back the caller's ip, used by --proxy-check to find the proxy's exit ip
+pub(crate) const PROXY_CHECK_URL: &str = "https://api.ipify.org";
+
+/// Share of a directory's responses that, once filtered as wildcards, triggers a warning that
+/// wildcard detection may be hiding real content
+pub(crate) const WILDCARD_WARN_RATIO: f64 = 0.95;
+
+/// Minimum number of responses seen in a directory before `WILDCARD_WARN_RATIO` is checked
+pub(crate) const WILDCARD_WARN_MIN_HITS: usize = 50;
+
+/// Number of requests made with each extension in a directory before --adaptive-extensions
+/// drops an extension that hasn't turned anything up, when no sample size is given
+pub(crate) const DEFAULT_EXTENSION_SAMPLE: usize = 50;
+
 /// The percentage of requests as errors it takes to be deemed too high
 pub const HIGH_ERROR_RATIO: f64 = 0.90;
 
+/// API-centric paths requested ahead of the wordlist when a single-page application is detected
+pub(crate) const SPA_API_WORDS: [&str; 20] = [
+    "api",
+    "api/v1",
+    "api/v2",
+    "api/v3",
+    "api/graphql",
+    "graphql",
+    "rest",
+    "v1",
+    "v2",
+    "swagger",
+    "swagger-ui",
+    "swagger.json",
+    "openapi.json",
+    "api-docs",
+    "v2/api-docs",
+    "v3/api-docs",
+    "actuator",
+    "health",
+    "config.json",
+    "env.js",
+];
+
+/// Maximum recursion depth (relative to the initial target) used once a single-page application
+/// is detected
+pub(crate) const SPA_MAX_DEPTH: usize = 2;
+
 /// Default list of status codes to report (all of them)
 pub const DEFAULT_STATUS_CODES: [StatusCode; 60] = [
     // all 1XX response codes
@@ -157,10 +249,21 @@ pub const DEFAULT_STATUS_CODES: [StatusCode; 60] = [
 /// Default method for requests
 pub(crate) const DEFAULT_METHOD: &str = "GET";
 
+/// columns that can be written by --csv, in their default order
+pub const CSV_COLUMNS: [&str; 7] = [
+    "url", "status", "method", "lines", "words", "chars", "location",
+];
+
 /// Default filename for config file settings
 ///
 /// Expected location is in the same directory as the feroxbuster binary.
 pub const DEFAULT_CONFIG_NAME: &str = "ferox-config.toml";
+
+/// Default filename for config file settings, when written as JSON
+///
+/// Searched for in the same locations as [DEFAULT_CONFIG_NAME](constant.DEFAULT_CONFIG_NAME.html)
+pub const DEFAULT_JSON_CONFIG_NAME: &str = "ferox-config.json";
+
 /// User agents to select from when random agent is being used
 pub const USER_AGENTS: [&str; 12] = [
     "Mozilla/5.0 (Linux; Android 8.0.0; SM-G960F Build/R16NW) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/62.0.3202.84 Mobile Safari/537.36",
@@ -187,6 +290,12 @@ mod tests {
         assert_eq!(DEFAULT_CONFIG_NAME, "ferox-config.toml");
     }
 
+    #[test]
+    /// asserts default json config name is correct
+    fn default_json_config_name() {
+        assert_eq!(DEFAULT_JSON_CONFIG_NAME, "ferox-config.json");
+    }
+
     #[test]
     /// asserts default wordlist is correct
     fn default_wordlist() {
@@ -10,7 +10,7 @@ use env_logger::Builder;
 use crate::{
     config::Configuration,
     message::FeroxMessage,
-    progress::PROGRESS_PRINTER,
+    progress::print_above_bars,
     traits::FeroxSerialize,
     utils::{fmt_err, write_to},
 };
@@ -65,7 +65,7 @@ pub fn initialize(config: Arc<Configuration>) -> Result<()> {
                 kind: "log".to_string(),
             };
 
-            PROGRESS_PRINTER.println(log_entry.as_str());
+            print_above_bars(&log_entry.as_str());
 
             if let Some(buffered_file) = file.clone() {
                 if let Ok(mut unlocked) = buffered_file.write() {
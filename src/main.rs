@@ -1,15 +1,12 @@
 use std::io::stdin;
 use std::{
-    env::{
-        args,
-        consts::{ARCH, OS},
-    },
+    env::args,
     fs::{create_dir, remove_file, File},
-    io::{stderr, BufRead, BufReader},
+    io::{stderr, BufRead, BufReader, Write},
     ops::Index,
     path::Path,
-    process::{exit, Command, Stdio},
-    sync::{atomic::Ordering, Arc},
+    process::{exit, Command, ExitStatus, Stdio},
+    sync::{atomic::Ordering, Arc, Mutex},
 };
 
 use anyhow::{bail, Context, Result};
@@ -22,7 +19,9 @@ use tokio_util::codec::{FramedRead, LinesCodec};
 
 use feroxbuster::{
     banner::{Banner, UPDATE_URL},
-    config::{Configuration, OutputLevel},
+    campaign,
+    config::{split_target_output_level, Configuration, Denier, OutputLevel},
+    dns,
     event_handlers::{
         Command::{
             AddHandles, CreateBar, Exit, JoinTasks, LoadStats, ScanInitialUrls, UpdateTargets,
@@ -31,55 +30,52 @@ use feroxbuster::{
         FiltersHandler, Handles, ScanHandler, StatsHandler, Tasks, TermInputHandler,
         TermOutHandler, SCAN_COMPLETE,
     },
-    filters, heuristics, logger,
-    progress::PROGRESS_PRINTER,
+    export, filters, heuristics, logger, notify,
+    progress::{disable_bars, redraw_bars_on_resize, set_refresh_rate, PROGRESS_PRINTER},
+    refilter, report,
     scan_manager::{self, ScanType},
-    scanner,
-    utils::{fmt_err, slugify_filename},
+    scanner, selftest, theme, updater, upload,
+    utils::{fmt_err, is_output_template, slugify_filename},
+    wordlists::{self, Wordlist},
     SECONDARY_WORDLIST,
 };
 #[cfg(not(target_os = "windows"))]
 use feroxbuster::{utils::set_open_file_limit, DEFAULT_OPEN_FILE_LIMIT};
 use lazy_static::lazy_static;
 use regex::Regex;
-use self_update::cargo_crate_version;
+use serde_json::json;
 
 lazy_static! {
     /// Limits the number of parallel scans active at any given time when using --parallel
     static ref PARALLEL_LIMITER: Semaphore = Semaphore::new(0);
 }
 
-/// Create a Vec of Strings from the given wordlist then stores it inside an Arc
-fn get_unique_words_from_wordlist(path: &str) -> Result<Arc<Vec<String>>> {
-    log::trace!("enter: get_unique_words_from_wordlist({})", path);
-    let mut trimmed_word = false;
+/// name of the file, within --parallel's logs directory, that maps each child to its target
+const PARALLEL_MANIFEST: &str = "manifest.json";
 
-    let file = File::open(path).with_context(|| format!("Could not open {path}"))?;
+/// Create a Wordlist from the given wordlist file then stores it inside an Arc, along with the
+/// number of duplicate words that were removed from it
+///
+/// the file is read in a single pass and its words are kept in one shared buffer, which keeps
+/// startup fast and memory use low for multi-million line wordlists
+fn get_unique_words_from_wordlist(path: &str) -> Result<(Arc<Wordlist>, usize)> {
+    log::trace!("enter: get_unique_words_from_wordlist({})", path);
 
-    let reader = BufReader::new(file);
+    let contents = std::fs::read(path).with_context(|| format!("Could not open {path}"))?;
 
-    // this empty string ensures that we call Requester::request with the base url, i.e.
-    // `http://localhost/` instead of going straight into `http://localhost/WORD.EXT`.
-    // for vanilla scans, it doesn't matter all that much, but it can be a significant difference
-    // when `-e` is used, depending on the content at the base url.
-    let mut words = vec![String::from("")];
+    let (words, summary) = Wordlist::from_bytes(&contents);
 
-    for line in reader.lines() {
-        line.map(|result| {
-            if !result.starts_with('#') && !result.is_empty() {
-                if result.starts_with('/') {
-                    words.push(result.trim_start_matches('/').to_string());
-                    trimmed_word = true;
-                } else {
-                    words.push(result);
-                }
-            }
-        })
-        .ok();
+    if summary.trimmed_word {
+        log::warn!("Some words in the wordlist started with a leading forward-slash; those words were trimmed (i.e. /word -> word)");
     }
 
-    if trimmed_word {
-        log::warn!("Some words in the wordlist started with a leading forward-slash; those words were trimmed (i.e. /word -> word)");
+    if summary.duplicates > 0 {
+        // expected request totals are derived from the deduplicated wordlist's length
+        log::warn!(
+            "Removed {} duplicate word(s) from the wordlist ({} unique words remain)",
+            summary.duplicates,
+            words.len() - 1
+        );
     }
 
     log::trace!(
@@ -87,7 +83,7 @@ fn get_unique_words_from_wordlist(path: &str) -> Result<Arc<Vec<String>>> {
         words.len()
     );
 
-    Ok(Arc::new(words))
+    Ok((Arc::new(words), summary.duplicates))
 }
 
 /// Determine whether it's a single url scan or urls are coming from stdin, then scan as needed
@@ -98,7 +94,16 @@ async fn scan(targets: Vec<String>, handles: Arc<Handles>) -> Result<()> {
 
     handles.send_scan_command(UpdateWordlist(handles.wordlist.clone()))?;
 
-    scanner::initialize(handles.wordlist.len(), handles.clone()).await?;
+    let mut known_parents =
+        scanner::initialize(handles.wordlist.len(), &targets, handles.clone()).await?;
+
+    // documented endpoints are requested once per target, the same as --known-urls
+    known_parents.extend(scanner::seed_openapi(&targets, handles.clone()).await?);
+
+    // directories a crawler already found are recursed into without requesting their pages again
+    known_parents.extend(scanner::seed_crawl(&targets, handles.clone()));
+
+    scanner::discover_graphql(&targets, handles.clone()).await?;
 
     // at this point, the stat thread's progress bar can be created; things that needed to happen
     // first:
@@ -133,7 +138,11 @@ async fn scan(targets: Vec<String>, handles: Arc<Handles>) -> Result<()> {
     }
 
     log::debug!("sending {:?} to be scanned as initial targets", targets);
-    handles.send_scan_command(ScanInitialUrls(targets))?;
+    handles.send_scan_command(ScanInitialUrls(targets.clone()))?;
+
+    // parent directories of confirmed --known-urls are sent after the initial targets, so
+    // that the scan handler can enforce recursion depth and skip already-known scans
+    scanner::queue_known_parents(known_parents, &targets, handles.clone()).await?;
 
     log::trace!("exit: scan");
 
@@ -153,7 +162,19 @@ async fn get_targets(handles: Arc<Handles>) -> Result<Vec<String>> {
         let mut reader = FramedRead::new(stdin, LinesCodec::new());
 
         while let Some(line) = reader.next().await {
-            targets.push(line?);
+            let line = line?;
+
+            // a target may be prefixed with the output level to use for it, i.e.
+            // quiet http://noisy.example
+            let (output_level, target) = split_target_output_level(&line, handles.config.json);
+
+            if let Some(output_level) = output_level {
+                handles
+                    .ferox_scans()?
+                    .set_target_output_level(target, output_level);
+            }
+
+            targets.push(target.to_string());
         }
     } else if handles.config.resumed {
         // resume-from can't be used with --url, and --stdin is marked false for every resumed
@@ -172,6 +193,10 @@ async fn get_targets(handles: Arc<Handles>) -> Result<Vec<String>> {
                 targets.push(scan.url().to_owned());
             }
         };
+    } else if handles.config.target_url.is_empty() && !handles.config.imported_targets.is_empty() {
+        // more than one host was found in --import-burp's export (and --url wasn't used to pick
+        // one of them), or in --nmap-xml's scan
+        targets.extend(handles.config.imported_targets.iter().cloned());
     } else {
         targets.push(handles.config.target_url.clone());
     }
@@ -196,10 +221,21 @@ async fn get_targets(handles: Arc<Handles>) -> Result<Vec<String>> {
                 );
             }
         }
+        for denier in handles.ferox_scans()?.file_denylist() {
+            let matched = match &denier {
+                Denier::Regex(regex) => regex.is_match(target),
+                Denier::Url(url) => {
+                    url.as_str().trim_end_matches('/') == target.trim_end_matches('/')
+                }
+            };
 
-        if !target.starts_with("http") {
-            // --url hackerone.com
-            *target = format!("{}://{target}", handles.config.protocol);
+            if matched {
+                bail!(
+                    "The --dont-scan-file entry '{}' matches {}; the scan will never start",
+                    denier,
+                    target
+                );
+            }
         }
     }
 
@@ -208,9 +244,84 @@ async fn get_targets(handles: Arc<Handles>) -> Result<Vec<String>> {
     Ok(targets)
 }
 
+/// append a --parallel child's details to the manifest, one line of JSON per child
+///
+/// ex: {"type":"child","pid":1234,"target":"http://localhost","output":"/tmp/x.logs/ferox-http_localhost-1627845741.log","exit_status":0}
+///
+/// `exit_status` is null when the child was killed by a signal or couldn't be waited on
+fn record_child(
+    manifest: &Mutex<File>,
+    pid: u32,
+    target: &str,
+    output: &str,
+    status: std::io::Result<ExitStatus>,
+) {
+    log::trace!("enter: record_child({}, {}, {:?})", pid, target, status);
+
+    let exit_status = status.ok().and_then(|status| status.code());
+
+    let entry = json!({
+        "type": "child",
+        "pid": pid,
+        "target": target,
+        "output": output,
+        "exit_status": exit_status,
+    });
+
+    match manifest.lock() {
+        Ok(mut file) => {
+            if let Err(e) = writeln!(file, "{entry}").and_then(|_| file.flush()) {
+                log::warn!(
+                    "Could not record {} in the parallel manifest: {}",
+                    target,
+                    e
+                );
+            }
+        }
+        Err(e) => log::warn!(
+            "Could not record {} in the parallel manifest: {}",
+            target,
+            e
+        ),
+    }
+
+    log::trace!("exit: record_child");
+}
+
+/// print the banner to stderr and/or write its settings as JSON when --banner-json is used
+fn print_banner(banner: &Banner, show_banner: bool, config: Arc<Configuration>) -> Result<()> {
+    match config.banner_json.as_str() {
+        "" => {}
+        "-" => {
+            // --banner-json without a file writes the JSON to stderr in place of the banner
+            banner.print_json_to(stderr(), config.clone())?;
+        }
+        path => {
+            let file = File::create(path).with_context(|| format!("Could not create {path}"))?;
+            banner.print_json_to(file, config.clone())?;
+        }
+    }
+
+    if show_banner && config.banner_json != "-" {
+        banner.print_to(stderr(), config)?;
+    }
+
+    Ok(())
+}
+
 /// async main called from real main, broken out in this way to allow for some synchronous code
 /// to be executed before bringing the tokio runtime online
 async fn wrapped_main(config: Arc<Configuration>) -> Result<()> {
+    // set before anything is printed, so the banner, bars, and messages all agree
+    theme::set_ascii(config.ascii || theme::terminal_lacks_unicode());
+
+    if config.no_bars {
+        disable_bars();
+    } else {
+        set_refresh_rate(config.bar_refresh_hz);
+        tokio::spawn(redraw_bars_on_resize());
+    }
+
     // join can only be called once, otherwise it causes the thread to panic
     tokio::task::spawn_blocking(move || {
         // ok, lazy_static! uses (unsurprisingly in retrospect) a lazy loading model where the
@@ -237,7 +348,32 @@ async fn wrapped_main(config: Arc<Configuration>) -> Result<()> {
         exit(0);
     }
 
-    let words = if config.wordlist.starts_with("http") {
+    if !config.update_from.is_empty() {
+        let archive = config.update_from.clone();
+
+        match tokio::task::spawn_blocking(move || updater::update_from_archive(&archive)).await? {
+            Err(e) => eprintln!("\n[ERROR] {:#}", e),
+            Ok(()) => eprintln!("\nFeroxbuster updated from {}", config.update_from),
+        }
+        exit(0);
+    }
+
+    if !config.refilter.is_empty() {
+        // nothing is scanned; the previous run's responses are filtered and written back out
+        let (kept, total) = refilter::refilter(&config)?;
+        eprintln!(
+            "Kept {kept} of {total} response(s) from {}",
+            config.refilter
+        );
+        exit(0);
+    }
+
+    let (words, duplicates) = if !config.fetch_wordlist.is_empty() {
+        // well-known wordlist requested by name; downloaded once and then served from cache
+        let path = wordlists::fetch_wordlist(&config.fetch_wordlist, &config).await?;
+
+        get_unique_words_from_wordlist(&path.to_string_lossy())?
+    } else if config.wordlist.starts_with("http") {
         // found a url scheme, attempt to download the wordlist
         let response = config
             .client
@@ -301,6 +437,30 @@ async fn wrapped_main(config: Arc<Configuration>) -> Result<()> {
         bail!("Did not find any words in {}", config.wordlist);
     }
 
+    // historical paths are merged in before anything is scanned, so every directory gets them
+    let words = if config.archive_seed.is_empty() {
+        words
+    } else {
+        wordlists::seed_from_archives(words, &config).await
+    };
+
+    // wordlists paired with a single extension (-x php:admin-words.txt); the base url's empty
+    // word is dropped, since it'd only produce requests for /.php and the like
+    let mut extension_wordlists = Vec::new();
+
+    for (extension, path) in &config.extension_wordlists {
+        let (paired, _) = get_unique_words_from_wordlist(path)?;
+        let paired = paired.skip(1);
+
+        if paired.is_empty() {
+            bail!("Did not find any words in {}", path);
+        }
+
+        extension_wordlists.push((extension.to_owned(), Arc::new(paired)));
+    }
+
+    extension_wordlists.sort_by(|(a, _), (b, _)| a.cmp(b));
+
     // spawn all event handlers, expect back a JoinHandle and a *Handle to the specific event
     let (stats_task, stats_handle) = StatsHandler::initialize(config.clone());
     let (filters_task, filters_handle) = FiltersHandler::initialize();
@@ -314,6 +474,7 @@ async fn wrapped_main(config: Arc<Configuration>) -> Result<()> {
         out_handle,
         config.clone(),
         words,
+        extension_wordlists,
     ));
 
     let (scan_task, scan_handle) = ScanHandler::initialize(handles.clone());
@@ -339,6 +500,15 @@ async fn wrapped_main(config: Arc<Configuration>) -> Result<()> {
         tokio::spawn(async move { scan_manager::start_max_time_thread(time_handles).await });
     }
 
+    if !config.dont_scan_file.is_empty() {
+        // entries are loaded up front so they apply to the initial targets, after which the
+        // file is watched for changes for the rest of the scan
+        scan_manager::load_denylist_file(handles.clone())?;
+
+        let watcher_handles = handles.clone();
+        tokio::spawn(async move { scan_manager::start_denylist_watcher(watcher_handles).await });
+    }
+
     // can't trace main until after logger is initialized and the above task is started
     log::trace!("enter: main");
 
@@ -422,10 +592,30 @@ async fn wrapped_main(config: Arc<Configuration>) -> Result<()> {
             String::new()
         };
 
+        // each child's pid, target, output file, and exit status are appended to a manifest in
+        // the logs directory as the child exits, so post-processing doesn't have to guess which
+        // log belongs to which target
+        let manifest = if !out_dir.is_empty() {
+            let path = Path::new(&out_dir).join(PARALLEL_MANIFEST);
+
+            match File::create(&path) {
+                Ok(file) => Some(Arc::new(Mutex::new(file))),
+                Err(e) => {
+                    log::warn!("Could not create {}: {}", path.display(), e);
+                    None
+                }
+            }
+        } else {
+            None
+        };
+
+        let mut children = Vec::new();
+
         // unvalidated targets fresh from stdin, just spawn children and let them do all checks
         for target in targets {
             // add the current target to the provided command
             let mut cloned = original.clone();
+            let mut output_file = String::new();
 
             if !out_dir.is_empty() {
                 // output directory value is not empty, need to join output directory with
@@ -437,20 +627,49 @@ async fn wrapped_main(config: Arc<Configuration>) -> Result<()> {
                     .position(|s| *s == "--output" || *s == "-o")
                     .unwrap();
 
-                let filename = slugify_filename(&target, "ferox", "log");
+                // a templated --output is passed along as-is, so each child resolves it for its
+                // own target; otherwise, each child gets a file named after its target
+                let filename = match Path::new(&original[out_idx + 1]).file_name() {
+                    Some(name) if is_output_template(&name.to_string_lossy()) => {
+                        name.to_string_lossy().to_string()
+                    }
+                    _ => slugify_filename(&target, "ferox", "log"),
+                };
 
                 let full_path = Path::new(&out_dir)
                     .join(filename)
                     .to_string_lossy()
                     .to_string();
 
+                output_file.clone_from(&full_path);
+
                 // a +1 to the index is fine here, as clap has already validated that
                 // -o|--output has a value associated with it
                 cloned[out_idx + 1] = full_path;
             }
 
+            if let Some(output_level) = handles.ferox_scans()?.target_output_level(&target) {
+                // the target's own level replaces whatever --quiet|--silent was given
+                cloned.retain(|arg| arg != "-q" && arg != "--quiet" && arg != "--silent");
+
+                match output_level {
+                    OutputLevel::Quiet => cloned.push("--quiet".to_string()),
+                    OutputLevel::Silent | OutputLevel::SilentJSON => {
+                        // --silent conflicts with -v|--verbosity
+                        cloned.retain(|arg| {
+                            arg != "--verbosity"
+                                && !(arg.len() > 1
+                                    && arg.starts_with('-')
+                                    && arg[1..].chars().all(|c| c == 'v'))
+                        });
+                        cloned.push("--silent".to_string());
+                    }
+                    OutputLevel::Default => {}
+                }
+            }
+
             cloned.push("-u".to_string());
-            cloned.push(target);
+            cloned.push(target.clone());
 
             let bin = cloned.index(0).to_owned(); // user's path to feroxbuster
             let args = cloned.index(1..).to_vec(); // and args
@@ -459,13 +678,17 @@ async fn wrapped_main(config: Arc<Configuration>) -> Result<()> {
 
             log::debug!("parallel exec: {} {}", bin, args.join(" "));
 
-            tokio::task::spawn(async move {
+            let manifest = manifest.clone();
+
+            children.push(tokio::task::spawn(async move {
                 let mut output = Command::new(bin)
                     .args(&args)
                     .stdout(Stdio::piped())
                     .spawn()
                     .expect("failed to spawn a child process");
 
+                let pid = output.id();
+
                 let stdout = output.stdout.take().unwrap();
 
                 let mut bufread = BufReader::new(stdout);
@@ -484,8 +707,21 @@ async fn wrapped_main(config: Arc<Configuration>) -> Result<()> {
                         break;
                     }
                 }
+
+                // stdout closing means the child is done (or about to be)
+                let status = output.wait();
+
+                if let Some(manifest) = manifest {
+                    record_child(&manifest, pid, &target, &output_file, status);
+                }
+
                 drop(permit);
-            });
+            }));
+        }
+
+        // wait on the children, so the manifest is complete by the time we exit
+        for child in children {
+            child.await.unwrap_or_default();
         }
 
         // the output handler creates an empty file to which it will try to write, because
@@ -508,6 +744,15 @@ async fn wrapped_main(config: Arc<Configuration>) -> Result<()> {
         return Ok(());
     }
 
+    // bare hostnames (i.e. --url hackerone.com) are requested over both http and https to
+    // decide which scheme(s) to scan. This happens after the parallel branch so that each child
+    // process probes its own target
+    //
+    // the same tester runs the connectivity check below, so that urls already requested while
+    // probing schemes aren't requested again (--smart-schemes)
+    let tester = heuristics::HeuristicTests::new(handles.clone());
+    let (targets, scheme_probes) = tester.probe_schemes(&targets).await;
+
     // in order for the Stats object to know about which targets are being scanned, we need to
     // wait until the parallel branch has been handled before sending the UpdateTargets command
     // this ensures that only the targets being scanned are sent to the Stats object
@@ -516,23 +761,51 @@ async fn wrapped_main(config: Arc<Configuration>) -> Result<()> {
     // targets
     handles.stats.send(UpdateTargets(targets.clone()))?;
 
-    if matches!(config.output_level, OutputLevel::Default) {
-        // only print banner if output level is default (no banner on --quiet|--silent)
-        let std_stderr = stderr(); // std::io::stderr
+    // only print banner if output level is default (no banner on --quiet|--silent); the
+    // --banner-json settings are written regardless, since that's what wrappers are after
+    let show_banner = matches!(config.output_level, OutputLevel::Default);
 
+    if show_banner || !config.banner_json.is_empty() {
         let mut banner = Banner::new(&targets, &config);
 
-        // only interested in the side-effect that sets banner.update_status
-        let _ = banner.check_for_updates(UPDATE_URL, handles.clone()).await;
+        if show_banner {
+            // only interested in the side-effect that sets banner.update_status
+            let _ = banner.check_for_updates(UPDATE_URL, handles.clone()).await;
+        }
 
-        if banner.print_to(std_stderr, config.clone()).is_err() {
+        if let Err(e) = print_banner(&banner, show_banner, config.clone()) {
             clean_up(handles, tasks).await?;
-            bail!(fmt_err("Could not print banner"));
+            bail!(fmt_err(&format!("Could not print banner: {e:#}")));
+        }
+
+        if show_banner && config.banner_json != "-" {
+            // which schemes answered for bare hostnames, printed beneath the banner
+            for probe in &scheme_probes {
+                eprintln!("{}", theme::themed(&format!(" 🔀  {probe}")));
+            }
+
+            if duplicates > 0 {
+                eprintln!(
+                    "{}",
+                    theme::themed(&format!(
+                        " 🧹  Removed {duplicates} duplicate word(s) from the wordlist"
+                    ))
+                );
+            }
         }
     }
 
+    // a handful of extensions and methods can turn each directory into millions of requests;
+    // that's worth knowing (and, interactively, agreeing to) before any of them are sent
+    if !scan_manager::confirm_request_volume(handles.clone()) {
+        clean_up(handles, tasks).await?;
+        bail!(fmt_err(
+            "Scan cancelled; each directory would take more than --max-dir-requests requests"
+        ));
+    }
+
     {
-        let send_to_file = !config.output.is_empty();
+        let send_to_file = !config.output.is_empty() || !config.output_dir.is_empty();
 
         // The TermOutHandler spawns a FileOutHandler, so errors in the FileOutHandler never bubble
         // up due to the TermOutHandler never awaiting the result of FileOutHandler::start (that's
@@ -540,15 +813,37 @@ async fn wrapped_main(config: Arc<Configuration>) -> Result<()> {
         if send_to_file && handles.output.sync(send_to_file).await.is_err() {
             // output file specified and file handler could not initialize
             clean_up(handles, tasks).await?;
-            let msg = format!("Couldn't start {} file handler", config.output);
+            let location = if config.output_dir.is_empty() {
+                &config.output
+            } else {
+                &config.output_dir
+            };
+            let msg = format!("Couldn't start {} file handler", location);
             bail!(fmt_err(&msg));
         }
     }
 
+    if handles.config.proxy_check {
+        // make sure the proxy works before sending the scan through it
+        match tester.proxy_check().await {
+            Ok(exit_ip) => {
+                if matches!(config.output_level, OutputLevel::Default) {
+                    eprintln!(
+                        "{}",
+                        theme::themed(&format!(" 🧦  Proxy exit ip: {exit_ip}"))
+                    );
+                }
+            }
+            Err(e) => {
+                clean_up(handles, tasks).await?;
+                bail!(fmt_err(&format!("Proxy check failed: {e}")));
+            }
+        }
+    }
+
     // discard non-responsive targets
     let live_targets = {
-        let test = heuristics::HeuristicTests::new(handles.clone());
-        let result = test.connectivity(&targets).await;
+        let result = tester.connectivity(&targets).await;
         if result.is_err() {
             clean_up(handles, tasks).await?;
             bail!(fmt_err(&result.unwrap_err().to_string()));
@@ -561,6 +856,12 @@ async fn wrapped_main(config: Arc<Configuration>) -> Result<()> {
         bail!(fmt_err("Could not find any live targets to scan"));
     }
 
+    if handles.config.survey_headers {
+        // one-shot security header / TRACE probe per live host
+        let test = heuristics::HeuristicTests::new(handles.clone());
+        test.survey_headers(&live_targets).await?;
+    }
+
     // kick off a scan against any targets determined to be responsive
     match scan(live_targets, handles.clone()).await {
         Ok(_) => {}
@@ -587,6 +888,12 @@ async fn clean_up(handles: Arc<Handles>, tasks: Tasks) -> Result<()> {
 
     log::info!("All scans complete!");
 
+    // sent ahead of Exit, so they reach the file handler before it's closed
+    report::report_discovered_hosts(&handles);
+
+    // also ahead of Exit, since the stats are saved to the output file once it closes
+    dns::record_lookup_stats(&handles);
+
     // terminal handler closes file handler if one is in use
     handles.output.send(Exit)?;
     tasks.terminal.await??;
@@ -600,6 +907,22 @@ async fn clean_up(handles: Arc<Handles>, tasks: Tasks) -> Result<()> {
     tasks.stats.await??;
     log::trace!("stats handler closed");
 
+    // all responses have been processed by the terminal handler at this point
+    export::export_urls(&handles.config)?;
+    export::emit_ffuf_json(&handles.config)?;
+    report::print_tree(&handles.config)?;
+    report::write_html_report(&handles.config, &handles.stats.data)?;
+    report::write_markdown_report(&handles.config, &handles.stats.data)?;
+    report::write_xml_report(&handles.config)?;
+    wordlists::save_collected_words(&handles.config)?;
+    campaign::record_run(&handles.config, &handles.stats.data)?;
+    upload::upload_output(&handles.config)
+        .await
+        .unwrap_or_else(|e| log::warn!("{:#}", e));
+    notify::notify_summary(&handles.config, &handles.stats.data)
+        .await
+        .unwrap_or_else(|e| log::warn!("{:#}", e));
+
     // mark all scans complete so the terminal input handler will exit cleanly
     SCAN_COMPLETE.store(true, Ordering::Relaxed);
 
@@ -611,20 +934,25 @@ async fn clean_up(handles: Arc<Handles>, tasks: Tasks) -> Result<()> {
     Ok(())
 }
 
-async fn update_app() -> Result<self_update::Status, Box<dyn ::std::error::Error>> {
-    let target_os = format!("{}-{}", ARCH, OS);
-    let status = tokio::task::spawn_blocking(move || {
-        self_update::backends::github::Update::configure()
-            .repo_owner("epi052")
-            .repo_name("feroxbuster")
-            .bin_name("feroxbuster")
-            .target(target_os.as_str())
-            .show_download_progress(true)
-            .current_version(cargo_crate_version!())
-            .build()?
-            .update()
-    })
-    .await??;
+/// --self-test; scan the built-in mock site using the rest of the user's flags, then check what
+/// was reported against what's actually there
+async fn self_test(config: Arc<Configuration>) -> Result<()> {
+    log::trace!("enter: self_test");
+
+    let site = selftest::MockSite::start().await?;
+
+    wrapped_main(site.configure(&config)).await?;
+
+    let result = site.verify();
+
+    log::trace!("exit: self_test -> {:?}", result);
+    result
+}
+
+async fn update_app() -> Result<self_update::Status> {
+    // downloads are verified against the release's published SHA256SUMS (and its signature,
+    // when a signing key is embedded) before the running binary is replaced
+    let status = tokio::task::spawn_blocking(updater::update_app).await??;
 
     Ok(status)
 }
@@ -649,8 +977,13 @@ fn main() -> Result<()> {
         .enable_all()
         .build()
     {
-        let future = wrapped_main(config.clone());
-        if let Err(e) = runtime.block_on(future) {
+        let result = if config.self_test {
+            runtime.block_on(self_test(config.clone()))
+        } else {
+            runtime.block_on(wrapped_main(config.clone()))
+        };
+
+        if let Err(e) = result {
             eprintln!("{e}");
 
             // the code below is to facilitate testing tests/test_banner entries. Since it's an
@@ -672,16 +1005,19 @@ fn main() -> Result<()> {
                 };
 
                 // print the banner to stderr
-                let std_stderr = stderr(); // std::io::stderr
                 let banner = Banner::new(&targets, &config);
-                if (!config.quiet && !config.silent) || config.parallel != 0 {
-                    banner.print_to(std_stderr, config).unwrap();
-                }
+                let show_banner = (!config.quiet && !config.silent) || config.parallel != 0;
+                print_banner(&banner, show_banner, config).unwrap();
             }
 
             // if we've encountered an error before clean_up can be called (i.e. a wordlist error)
             // we need to at least spin-down the progress bar
             PROGRESS_PRINTER.finish();
+
+            if config.self_test {
+                // scripts checking an installation need to be able to tell that it failed
+                exit(1);
+            }
         };
     }
 
@@ -73,10 +73,96 @@ impl FeroxSerialize for FeroxMessage {
     }
 }
 
+#[derive(Serialize, Deserialize, Default, Debug, Clone, PartialEq, Eq)]
+/// A url that was deliberately not scanned, written to the output file when --skip-events is used
+pub struct SkipEvent {
+    #[serde(rename = "type")]
+    /// Name of this type of struct, used for serialization, i.e. `{"type":"skipped"}`
+    pub(crate) kind: String,
+
+    /// The url that wasn't scanned
+    pub(crate) url: String,
+
+    /// why the url wasn't scanned (max-depth, denied, wildcard, recurse-threshold,
+    /// host-cancelled)
+    pub(crate) reason: String,
+
+    /// human readable explanation of the reason
+    pub(crate) detail: String,
+}
+
+/// implementation of SkipEvent
+impl SkipEvent {
+    /// create a new SkipEvent from the given url, reason, and detail
+    pub fn new(url: &str, reason: &str, detail: &str) -> Self {
+        Self {
+            kind: String::from("skipped"),
+            url: url.to_string(),
+            reason: reason.to_string(),
+            detail: detail.to_string(),
+        }
+    }
+}
+
+/// Implementation of SkipEvent
+impl FeroxSerialize for SkipEvent {
+    /// Create a string representation of the skip event
+    ///
+    /// ex:
+    /// SKP max-depth https://localhost/a/b/c/ (--depth of 2 reached)
+    fn as_str(&self) -> String {
+        format!(
+            "{} {} {} ({})\n",
+            style("SKP").bg(Color::Yellow).black(),
+            style(&self.reason).yellow(),
+            self.url,
+            style(&self.detail).dim(),
+        )
+    }
+
+    /// Create an NDJSON representation of the skip event
+    ///
+    /// (expanded for clarity)
+    /// ex:
+    /// {
+    ///   "type": "skipped",
+    ///   "url": "https://localhost/a/b/c/",
+    ///   "reason": "max-depth",
+    ///   "detail": "--depth of 2 reached"
+    /// }\n
+    fn as_json(&self) -> anyhow::Result<String> {
+        let mut json = serde_json::to_string(&self)
+            .with_context(|| fmt_err(&format!("Could not convert {} to JSON", self.url)))?;
+        json.push('\n');
+        Ok(json)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    /// test as_str and as_json methods of SkipEvent
+    fn skip_event_serializes() {
+        let event = SkipEvent::new(
+            "https://localhost/a/b/c/",
+            "max-depth",
+            "--depth of 2 reached",
+        );
+
+        let event_str = console::strip_ansi_codes(&event.as_str()).to_string();
+        assert!(event_str.contains("max-depth https://localhost/a/b/c/"));
+        assert!(event_str.ends_with('\n'));
+
+        let json = event.as_json().unwrap();
+        assert!(json.contains(r#""type":"skipped""#));
+        assert!(json.contains(r#""reason":"max-depth""#));
+
+        let deserialized: SkipEvent = serde_json::from_str(&json).unwrap();
+        assert_eq!(deserialized, event);
+    }
+
     #[test]
     /// test as_str method of FeroxMessage
     fn ferox_message_as_str_returns_string_with_newline() {
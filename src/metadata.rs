@@ -0,0 +1,191 @@
+//! leading record of --json output files, describing the run that produced them
+//!
+//! downstream parsers should read `schema_version` from this record before anything else; it's
+//! bumped whenever a field is removed or changes meaning in any record type. Fields that are only
+//! added don't bump it, so parsers should ignore keys they don't recognize
+use std::{collections::BTreeMap, fs::File, io};
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::{
+    config::Configuration,
+    traits::FeroxSerialize,
+    utils::{fmt_err, timestamp},
+    OUTPUT_SCHEMA_VERSION, VERSION,
+};
+
+#[derive(Serialize, Deserialize, Default, Debug, Clone, PartialEq)]
+/// Details about the run, written as the first line of NDJSON output
+pub struct RunMetadata {
+    #[serde(rename = "type")]
+    /// Name of this type of struct, used for serialization, i.e. `{"type":"metadata"}`
+    pub(crate) kind: String,
+
+    /// version of the NDJSON record schemas, see `OUTPUT_SCHEMA_VERSION`
+    pub(crate) schema_version: u32,
+
+    /// version of feroxbuster that wrote the output
+    pub(crate) version: String,
+
+    /// unique id of this run, the same one --output templates use
+    pub(crate) run_id: String,
+
+    /// when the output was started, in seconds since the unix epoch
+    pub(crate) start_time: f64,
+
+    /// targets known at startup; empty when they're read from stdin or a state file
+    pub(crate) targets: Vec<String>,
+
+    /// the wordlist, as given to --wordlist
+    pub(crate) wordlist: String,
+
+    /// sha256 of the wordlist's contents; null when it isn't a readable local file
+    pub(crate) wordlist_sha256: Option<String>,
+
+    /// --meta pairs, sorted by key
+    #[serde(default)]
+    pub(crate) meta: BTreeMap<String, String>,
+}
+
+/// Implementation of RunMetadata
+impl RunMetadata {
+    /// gather the run's details from the given configuration
+    ///
+    /// wordlists can be large, so the hash is computed off of the runtime's worker threads
+    pub async fn new(config: &Configuration) -> Self {
+        log::trace!("enter: RunMetadata::new");
+
+        let targets = if config.target_url.is_empty() {
+            Vec::new()
+        } else {
+            vec![config.target_url.clone()]
+        };
+
+        let wordlist_sha256 = if config.wordlist.starts_with("http") {
+            None
+        } else {
+            let wordlist = config.wordlist.clone();
+
+            tokio::task::spawn_blocking(move || sha256_file(&wordlist).ok())
+                .await
+                .ok()
+                .flatten()
+        };
+
+        let metadata = Self {
+            kind: String::from("metadata"),
+            schema_version: OUTPUT_SCHEMA_VERSION,
+            version: VERSION.to_string(),
+            run_id: config.run_id.clone(),
+            start_time: timestamp(),
+            targets,
+            wordlist: config.wordlist.clone(),
+            wordlist_sha256,
+            meta: config.meta.clone().into_iter().collect(),
+        };
+
+        log::trace!("exit: RunMetadata::new -> {:?}", metadata);
+        metadata
+    }
+}
+
+/// sha256 of the file at the given path, read a buffer at a time rather than all at once
+fn sha256_file(path: &str) -> io::Result<String> {
+    let mut hasher = Sha256::new();
+    io::copy(&mut File::open(path)?, &mut hasher)?;
+
+    Ok(hasher
+        .finalize()
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect())
+}
+
+/// Implementation of FeroxSerialize for RunMetadata
+impl FeroxSerialize for RunMetadata {
+    /// metadata is only useful to parsers, so it's never part of plain-text output
+    fn as_str(&self) -> String {
+        String::new()
+    }
+
+    /// Create an NDJSON representation of the run's metadata
+    ///
+    /// (expanded for clarity)
+    /// ex:
+    /// {
+    ///   "type": "metadata",
+    ///   "schema_version": 1,
+    ///   "version": "2.11.0",
+    ///   "run_id": "0cd7fed0-47f4-4b18-a1b0-ac39708c1676",
+    ///   "start_time": 1711796681.3455093,
+    ///   "targets": ["https://localhost"],
+    ///   "wordlist": "/wordlists/raft-medium-directories.txt",
+    ///   "wordlist_sha256": "9f86d081884c7d659a2feaa0c55ad015a3bf4f1b2b0b822cd15d6c15b0f00a08",
+    ///   "meta": {"engagement": "1337", "operator": "epi"}
+    /// }\n
+    fn as_json(&self) -> Result<String> {
+        let mut json = serde_json::to_string(&self)
+            .with_context(|| fmt_err("Could not convert run metadata to JSON"))?;
+        json.push('\n');
+        Ok(json)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+    use tempfile::NamedTempFile;
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+    /// a local wordlist is hashed, and the record is tagged with the current schema version
+    async fn run_metadata_hashes_local_wordlist() {
+        let mut wordlist = NamedTempFile::new().unwrap();
+        write!(wordlist, "test").unwrap();
+
+        let config = Configuration {
+            target_url: String::from("http://localhost"),
+            wordlist: wordlist.path().to_string_lossy().to_string(),
+            meta: [(String::from("engagement"), String::from("1337"))].into(),
+            ..Default::default()
+        };
+
+        let metadata = RunMetadata::new(&config).await;
+
+        assert_eq!(metadata.kind, "metadata");
+        assert_eq!(metadata.schema_version, OUTPUT_SCHEMA_VERSION);
+        assert_eq!(metadata.targets, vec![String::from("http://localhost")]);
+        assert_eq!(
+            metadata.wordlist_sha256.as_deref(),
+            Some("9f86d081884c7d659a2feaa0c55ad015a3bf4f1b2b0b822cd15d6c15b0f00a08")
+        );
+        assert_eq!(metadata.meta["engagement"], "1337");
+        assert!(metadata.as_str().is_empty());
+        assert!(metadata
+            .as_json()
+            .unwrap()
+            .starts_with(r#"{"type":"metadata","schema_version":1,"#));
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+    /// remote and missing wordlists have no hash, and stdin runs have no known targets
+    async fn run_metadata_without_hash_or_targets() {
+        let config = Configuration {
+            wordlist: String::from("https://localhost/words.txt"),
+            ..Default::default()
+        };
+
+        let metadata = RunMetadata::new(&config).await;
+        assert!(metadata.targets.is_empty());
+        assert!(metadata.wordlist_sha256.is_none());
+
+        let config = Configuration {
+            wordlist: String::from("/definitely/doesnt/exist/0cd7fed0-47f4-4b18-a1b0-ac39708c1676"),
+            ..Default::default()
+        };
+
+        assert!(RunMetadata::new(&config).await.wordlist_sha256.is_none());
+    }
+}
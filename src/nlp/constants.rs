@@ -6,7 +6,11 @@ lazy_static! {
     pub(super) static ref BOUNDED_WORD_REGEX: Regex = Regex::new(r"\b[a-zA-Z0-9_-]+\b").unwrap();
 }
 
-/// collection of stop words from spaCy with small modifications
+/// languages with stop word lists, by ISO 639-1 code (used by --word-languages)
+pub(crate) const STOP_WORD_LANGUAGES: [&str; 9] =
+    ["en", "de", "fr", "es", "pt", "it", "ru", "zh", "ja"];
+
+/// collection of english stop words from spaCy with small modifications
 pub(super) static STOP_WORDS: [&str; 323] = [
     "'d",
     "'ll",
@@ -332,3 +336,488 @@ pub(super) static STOP_WORDS: [&str; 323] = [
     "\u{2019}s",
     "\u{2019}ve",
 ];
+
+/// collection of german stop words
+pub(super) static GERMAN_STOP_WORDS: [&str; 221] = [
+    "aber",
+    "alle",
+    "allem",
+    "allen",
+    "aller",
+    "alles",
+    "als",
+    "also",
+    "am",
+    "an",
+    "ander",
+    "andere",
+    "anderem",
+    "anderen",
+    "anderer",
+    "anderes",
+    "anderm",
+    "andern",
+    "anders",
+    "auch",
+    "auf",
+    "aus",
+    "bei",
+    "bin",
+    "bis",
+    "bist",
+    "da",
+    "damit",
+    "dann",
+    "das",
+    "dass",
+    "dasselbe",
+    "dazu",
+    "dein",
+    "deine",
+    "deinem",
+    "deinen",
+    "deiner",
+    "dem",
+    "demselben",
+    "den",
+    "denn",
+    "denselben",
+    "der",
+    "derer",
+    "derselbe",
+    "derselben",
+    "des",
+    "desselben",
+    "dessen",
+    "dich",
+    "die",
+    "dies",
+    "diese",
+    "dieselbe",
+    "dieselben",
+    "diesem",
+    "diesen",
+    "dieser",
+    "dieses",
+    "dir",
+    "doch",
+    "dort",
+    "du",
+    "durch",
+    "ein",
+    "eine",
+    "einem",
+    "einen",
+    "einer",
+    "eines",
+    "einig",
+    "einige",
+    "einigem",
+    "einigen",
+    "einiger",
+    "einiges",
+    "einmal",
+    "er",
+    "es",
+    "etwas",
+    "euch",
+    "euer",
+    "eure",
+    "eurem",
+    "euren",
+    "eurer",
+    "für",
+    "gegen",
+    "gewesen",
+    "hab",
+    "habe",
+    "haben",
+    "hat",
+    "hatte",
+    "hatten",
+    "hier",
+    "hin",
+    "hinter",
+    "ich",
+    "ihm",
+    "ihn",
+    "ihnen",
+    "ihr",
+    "ihre",
+    "ihrem",
+    "ihren",
+    "ihrer",
+    "ihres",
+    "im",
+    "in",
+    "indem",
+    "ins",
+    "ist",
+    "jede",
+    "jedem",
+    "jeden",
+    "jeder",
+    "jedes",
+    "jene",
+    "jenem",
+    "jenen",
+    "jener",
+    "jenes",
+    "jetzt",
+    "kann",
+    "kein",
+    "keine",
+    "keinem",
+    "keinen",
+    "keiner",
+    "keines",
+    "können",
+    "könnte",
+    "machen",
+    "man",
+    "manche",
+    "manchem",
+    "manchen",
+    "mancher",
+    "manches",
+    "mein",
+    "meine",
+    "meinem",
+    "meinen",
+    "meiner",
+    "mich",
+    "mir",
+    "mit",
+    "muss",
+    "musste",
+    "nach",
+    "nicht",
+    "nichts",
+    "noch",
+    "nun",
+    "nur",
+    "ob",
+    "oder",
+    "ohne",
+    "sehr",
+    "sein",
+    "seine",
+    "seinem",
+    "seinen",
+    "seiner",
+    "seit",
+    "sich",
+    "sie",
+    "sind",
+    "so",
+    "solche",
+    "solchem",
+    "solchen",
+    "solcher",
+    "soll",
+    "sollte",
+    "sondern",
+    "sonst",
+    "um",
+    "und",
+    "uns",
+    "unser",
+    "unsere",
+    "unter",
+    "viel",
+    "vom",
+    "von",
+    "vor",
+    "während",
+    "war",
+    "waren",
+    "warst",
+    "was",
+    "weg",
+    "weil",
+    "weiter",
+    "welche",
+    "welchem",
+    "welchen",
+    "welcher",
+    "welches",
+    "wenn",
+    "werde",
+    "werden",
+    "wie",
+    "wieder",
+    "will",
+    "wir",
+    "wird",
+    "wirst",
+    "wo",
+    "wollen",
+    "wollte",
+    "würde",
+    "würden",
+    "zu",
+    "zum",
+    "zur",
+    "zwar",
+    "zwischen",
+];
+
+/// collection of french stop words
+pub(super) static FRENCH_STOP_WORDS: [&str; 145] = [
+    "au", "aux", "avec", "ce", "ces", "dans", "de", "des", "du", "elle", "en", "et", "eux", "il",
+    "ils", "je", "la", "le", "les", "leur", "leurs", "lui", "ma", "mais", "me", "même", "mes",
+    "moi", "mon", "ne", "nos", "notre", "nous", "on", "ou", "où", "par", "pas", "pour", "qu",
+    "que", "qui", "sa", "se", "ses", "son", "sur", "ta", "te", "tes", "toi", "ton", "tu", "un",
+    "une", "vos", "votre", "vous", "c", "d", "j", "l", "à", "m", "n", "s", "t", "y", "été", "étée",
+    "étées", "étés", "étant", "suis", "es", "est", "sommes", "êtes", "sont", "serai", "seras",
+    "sera", "serons", "serez", "seront", "serais", "serait", "serions", "seriez", "seraient",
+    "étais", "était", "étions", "étiez", "étaient", "fus", "fut", "fûmes", "fûtes", "furent",
+    "sois", "soit", "soyons", "soyez", "soient", "ai", "as", "avons", "avez", "ont", "aurai",
+    "aura", "aurons", "aurez", "auront", "aurais", "aurait", "avais", "avait", "avions", "aviez",
+    "avaient", "eu", "eut", "eurent", "cette", "cet", "ceci", "cela", "ça", "comme", "aussi",
+    "plus", "tout", "tous", "toute", "toutes", "très", "sans", "sous", "entre", "alors", "donc",
+    "car", "si",
+];
+
+/// collection of spanish stop words
+pub(super) static SPANISH_STOP_WORDS: [&str; 126] = [
+    "a", "al", "algo", "algunas", "algunos", "ante", "antes", "como", "con", "contra", "cual",
+    "cuando", "de", "del", "desde", "donde", "durante", "e", "el", "ella", "ellas", "ellos", "en",
+    "entre", "era", "erais", "eran", "eras", "eres", "es", "esa", "esas", "ese", "eso", "esos",
+    "esta", "estaba", "estado", "estáis", "están", "estar", "estas", "este", "esto", "estos",
+    "estoy", "fue", "fueron", "fui", "ha", "había", "habían", "han", "has", "hasta", "hay", "la",
+    "las", "le", "les", "lo", "los", "más", "me", "mi", "mis", "mucho", "muy", "nada", "ni", "no",
+    "nos", "nosotros", "o", "os", "otra", "otras", "otro", "otros", "para", "pero", "poco", "por",
+    "porque", "que", "quien", "quienes", "qué", "se", "sea", "sean", "ser", "si", "sido", "sin",
+    "sobre", "sois", "somos", "son", "soy", "su", "sus", "suyo", "también", "tanto", "te",
+    "tenemos", "tener", "tengo", "ti", "tiene", "tienen", "todo", "todos", "tu", "tus", "tú", "un",
+    "una", "uno", "unos", "vosotros", "y", "ya", "yo", "él",
+];
+
+/// collection of portuguese stop words
+pub(super) static PORTUGUESE_STOP_WORDS: [&str; 110] = [
+    "a", "ao", "aos", "aquela", "aquelas", "aquele", "aqueles", "aquilo", "as", "até", "com",
+    "como", "da", "das", "de", "dela", "delas", "dele", "deles", "depois", "do", "dos", "e", "ela",
+    "elas", "ele", "eles", "em", "entre", "era", "eram", "essa", "essas", "esse", "esses", "esta",
+    "estas", "este", "estes", "eu", "foi", "for", "foram", "há", "isso", "isto", "já", "lhe",
+    "lhes", "mais", "mas", "me", "mesmo", "meu", "meus", "minha", "minhas", "muito", "na", "nas",
+    "não", "nem", "no", "nos", "nós", "nossa", "nossas", "nosso", "nossos", "num", "numa", "o",
+    "os", "ou", "para", "pela", "pelas", "pelo", "pelos", "por", "qual", "quando", "que", "quem",
+    "se", "sem", "ser", "seu", "seus", "só", "sua", "suas", "também", "te", "tem", "têm", "teu",
+    "tu", "tua", "tuas", "um", "uma", "umas", "uns", "você", "vocês", "vos", "é", "à", "às",
+];
+
+/// collection of italian stop words
+pub(super) static ITALIAN_STOP_WORDS: [&str; 115] = [
+    "a", "ad", "agli", "ai", "al", "alla", "alle", "allo", "anche", "che", "chi", "ci", "come",
+    "con", "contro", "cui", "da", "dagli", "dai", "dal", "dalla", "dalle", "dallo", "degli", "dei",
+    "del", "della", "delle", "dello", "di", "dove", "e", "ed", "era", "erano", "essere", "gli",
+    "ha", "hai", "hanno", "ho", "i", "il", "in", "io", "la", "le", "lei", "lo", "loro", "lui",
+    "ma", "mi", "mia", "mie", "miei", "mio", "ne", "negli", "nei", "nel", "nella", "nelle",
+    "nello", "noi", "non", "nostra", "nostre", "nostri", "nostro", "o", "per", "perché", "più",
+    "quale", "quando", "quella", "quelle", "quelli", "quello", "questa", "queste", "questi",
+    "questo", "se", "sei", "si", "sia", "siamo", "siete", "sono", "su", "sua", "sue", "sui", "sul",
+    "sulla", "sulle", "suo", "suoi", "ti", "tra", "tu", "tua", "tue", "tuo", "tuoi", "tutti",
+    "tutto", "un", "una", "uno", "vi", "voi", "è",
+];
+
+/// collection of russian stop words
+pub(super) static RUSSIAN_STOP_WORDS: [&str; 103] = [
+    "а",
+    "без",
+    "более",
+    "бы",
+    "был",
+    "была",
+    "были",
+    "было",
+    "быть",
+    "в",
+    "вам",
+    "вас",
+    "весь",
+    "во",
+    "вот",
+    "все",
+    "всего",
+    "всех",
+    "вы",
+    "где",
+    "да",
+    "даже",
+    "для",
+    "до",
+    "его",
+    "ее",
+    "ей",
+    "ему",
+    "если",
+    "есть",
+    "еще",
+    "же",
+    "за",
+    "здесь",
+    "и",
+    "из",
+    "или",
+    "им",
+    "их",
+    "к",
+    "как",
+    "какая",
+    "какой",
+    "когда",
+    "кто",
+    "ли",
+    "либо",
+    "мне",
+    "может",
+    "мы",
+    "на",
+    "над",
+    "надо",
+    "наш",
+    "не",
+    "него",
+    "нее",
+    "нет",
+    "ни",
+    "них",
+    "но",
+    "ну",
+    "о",
+    "об",
+    "однако",
+    "он",
+    "она",
+    "они",
+    "оно",
+    "от",
+    "очень",
+    "по",
+    "под",
+    "при",
+    "с",
+    "со",
+    "так",
+    "также",
+    "такой",
+    "там",
+    "те",
+    "тем",
+    "то",
+    "того",
+    "тоже",
+    "той",
+    "только",
+    "том",
+    "ты",
+    "у",
+    "уже",
+    "хотя",
+    "чего",
+    "чей",
+    "чем",
+    "что",
+    "чтобы",
+    "чье",
+    "чья",
+    "эта",
+    "эти",
+    "это",
+    "я",
+];
+
+/// collection of chinese stop words
+pub(super) static CHINESE_STOP_WORDS: [&str; 55] = [
+    "的", "了", "和", "是", "在", "我", "有", "他", "这", "中", "也", "就", "人", "都", "一", "个",
+    "上", "们", "为", "到", "说", "要", "以", "会", "与", "及", "或", "而", "被", "把", "等", "对",
+    "从", "之", "其", "你", "她", "它", "那", "些", "吗", "吧", "呢", "啊", "着", "过", "还", "很",
+    "又", "所", "并", "但", "由", "于", "此",
+];
+
+/// collection of japanese stop words
+pub(super) static JAPANESE_STOP_WORDS: [&str; 82] = [
+    "の",
+    "に",
+    "は",
+    "を",
+    "た",
+    "が",
+    "で",
+    "て",
+    "と",
+    "し",
+    "れ",
+    "さ",
+    "ある",
+    "いる",
+    "も",
+    "する",
+    "から",
+    "な",
+    "こと",
+    "として",
+    "い",
+    "や",
+    "れる",
+    "など",
+    "なっ",
+    "ない",
+    "この",
+    "ため",
+    "その",
+    "あっ",
+    "よう",
+    "また",
+    "もの",
+    "という",
+    "あり",
+    "まで",
+    "られ",
+    "なる",
+    "へ",
+    "か",
+    "だ",
+    "これ",
+    "によって",
+    "により",
+    "おり",
+    "より",
+    "による",
+    "ず",
+    "なり",
+    "られる",
+    "において",
+    "ば",
+    "なかっ",
+    "なく",
+    "しかし",
+    "について",
+    "せ",
+    "だっ",
+    "その後",
+    "できる",
+    "それ",
+    "う",
+    "ので",
+    "なお",
+    "のみ",
+    "でき",
+    "き",
+    "つ",
+    "における",
+    "および",
+    "いう",
+    "さらに",
+    "でも",
+    "ら",
+    "たり",
+    "その他",
+    "に関する",
+    "たち",
+    "ます",
+    "ん",
+    "なら",
+    "です",
+];
@@ -1,5 +1,5 @@
 use super::term::{Term, TermMetaData};
-use super::utils::preprocess;
+use super::utils::{preprocess_with, PreprocessOptions};
 use scraper::{Html, Node, Selector};
 use std::collections::HashMap;
 
@@ -15,15 +15,15 @@ pub(crate) struct Document {
 
 impl Document {
     /// create a new `Document` from the given string
-    pub(super) fn new(text: &str) -> Self {
+    pub(crate) fn new(text: &str, options: &PreprocessOptions) -> Self {
         let mut document = Self::default();
 
-        let processed = preprocess(text);
+        let processed = preprocess_with(text, options);
 
         document.number_of_terms += processed.len();
 
         for normalized in processed {
-            if normalized.len() >= 2 {
+            if normalized.chars().count() >= 2 {
                 document.add_term(&normalized)
             }
         }
@@ -40,61 +40,12 @@ impl Document {
     }
 
     /// create a new `Document` from the given HTML string
-    pub(crate) fn from_html(raw_html: &str) -> Option<Self> {
-        let selector = Selector::parse("body").unwrap();
-
-        let html = Html::parse_document(raw_html);
-
-        let element = html.select(&selector).next()?;
-
-        let text = element
-            .descendants()
-            .filter_map(|node| {
-                if !node.value().is_text() && !node.value().is_comment() {
-                    return None;
-                }
-
-                // have a Text||Comment node, trim whitespace to test for all whitespace stuff
-                let trimmed = if node.value().is_text() {
-                    node.value().as_text().unwrap().text.trim()
-                } else {
-                    node.value().as_comment().unwrap().comment.trim()
-                };
-
-                if trimmed.is_empty() {
-                    return None;
-                }
-
-                // found a non-empty Text||Comment node, need to check its parent to determine if
-                // it's a <script>||<style> tag. We're assuming text within a script||style tag is
-                // uninteresting
-
-                let parent = node.parent().unwrap().value();
-
-                if !parent.is_element() {
-                    return None;
-                }
-
-                // parent is an Element node, see if it's a <script> or <style>
-
-                if let Node::Element(element) = parent {
-                    if element.name() == "script" || element.name() == "style" {
-                        return None;
-                    }
-
-                    // at this point, we have a non-empty Text element with a non-script|style parent;
-                    // now we can return the trimmed up string
-                    return Some(format!("{trimmed} "));
-                }
-
-                // not an Element node
-                None
-            })
-            .collect::<String>();
+    pub(crate) fn from_html(raw_html: &str, options: &PreprocessOptions) -> Option<Self> {
+        let text = visible_text(raw_html)?;
 
         // call `new` to push the parsed html through the pre-processing pipeline and process all
         // the words
-        Some(Self::new(&text))
+        Some(Self::new(&text, options))
     }
 
     /// Log normalized weighting scheme for term frequency
@@ -117,6 +68,62 @@ impl Document {
     }
 }
 
+/// text of the given HTML's `<body>` that a reader would see; scripts and styles are left out
+pub(super) fn visible_text(raw_html: &str) -> Option<String> {
+    let selector = Selector::parse("body").unwrap();
+
+    let html = Html::parse_document(raw_html);
+
+    let element = html.select(&selector).next()?;
+
+    let text = element
+        .descendants()
+        .filter_map(|node| {
+            if !node.value().is_text() && !node.value().is_comment() {
+                return None;
+            }
+
+            // have a Text||Comment node, trim whitespace to test for all whitespace stuff
+            let trimmed = if node.value().is_text() {
+                node.value().as_text().unwrap().text.trim()
+            } else {
+                node.value().as_comment().unwrap().comment.trim()
+            };
+
+            if trimmed.is_empty() {
+                return None;
+            }
+
+            // found a non-empty Text||Comment node, need to check its parent to determine if
+            // it's a <script>||<style> tag. We're assuming text within a script||style tag is
+            // uninteresting
+
+            let parent = node.parent().unwrap().value();
+
+            if !parent.is_element() {
+                return None;
+            }
+
+            // parent is an Element node, see if it's a <script> or <style>
+
+            if let Node::Element(element) = parent {
+                if element.name() == "script" || element.name() == "style" {
+                    return None;
+                }
+
+                // at this point, we have a non-empty Text element with a non-script|style parent;
+                // now we can return the trimmed up string
+                return Some(format!("{trimmed} "));
+            }
+
+            // not an Element node
+            None
+        })
+        .collect::<String>();
+
+    Some(text)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -124,7 +131,10 @@ mod tests {
     #[test]
     /// `Document::new` should preprocess text and generate a hashmap of `Term, TermMetadata`
     fn nlp_document_creation_from_text() {
-        let doc = Document::new("The air quality in Singapore got worse on Wednesday.");
+        let doc = Document::new(
+            "The air quality in Singapore got worse on Wednesday.",
+            &PreprocessOptions::default(),
+        );
 
         let expected_terms = ["air", "quality", "singapore", "worse", "wednesday"];
 
@@ -145,20 +155,25 @@ mod tests {
     #[test]
     /// `Document::new` should preprocess html and generate a hashmap of `Term, TermMetadata`
     fn nlp_document_creation_from_html() {
-        let empty = Document::from_html("<html></html>").unwrap();
+        let empty = Document::from_html("<html></html>", &PreprocessOptions::default()).unwrap();
         assert_eq!(empty.number_of_terms, 0);
 
-        let other_empty = Document::from_html("<html><body><p></p></body></html>").unwrap();
+        let other_empty = Document::from_html(
+            "<html><body><p></p></body></html>",
+            &PreprocessOptions::default(),
+        )
+        .unwrap();
         assert_eq!(other_empty.number_of_terms, 0);
 
-        let third_empty =
-            Document::from_html("<!DOCTYPE html><html><!DOCTYPE html><p></p></html>").unwrap();
+        let third_empty = Document::from_html(
+            "<!DOCTYPE html><html><!DOCTYPE html><p></p></html>",
+            &PreprocessOptions::default(),
+        )
+        .unwrap();
         assert_eq!(third_empty.number_of_terms, 0);
 
         // p tag for is_text check and comment for is_comment
-        let doc = Document::from_html(
-            "<html><body><p>The air quality in Singapore.</p><!--got worse on Wednesday--></body></html>",
-        ).unwrap();
+        let doc = Document::from_html("<html><body><p>The air quality in Singapore.</p><!--got worse on Wednesday--></body></html>", &PreprocessOptions::default()).unwrap();
 
         let expected_terms = ["air", "quality", "singapore", "worse", "wednesday"];
 
@@ -179,7 +194,10 @@ mod tests {
     #[test]
     /// simple check of the `term_frequency` function's return value
     fn term_frequency_validation() {
-        let doc = Document::new("The air quality in Singapore got worse on Wednesday. Air Jordan.");
+        let doc = Document::new(
+            "The air quality in Singapore got worse on Wednesday. Air Jordan.",
+            &PreprocessOptions::default(),
+        );
 
         let air_freq = doc.term_frequency(&Term::new("air"));
 
@@ -193,7 +211,10 @@ mod tests {
     #[test]
     /// test accessors for correctness
     fn document_accessor_test() {
-        let doc = Document::new("The air quality in Singapore got worse on Wednesday.");
+        let doc = Document::new(
+            "The air quality in Singapore got worse on Wednesday.",
+            &PreprocessOptions::default(),
+        );
         let keys = doc.terms().keys().map(|key| key.raw()).collect::<Vec<_>>();
 
         let expected = ["air", "quality", "singapore", "worse", "wednesday"];
@@ -205,11 +226,32 @@ mod tests {
         }
     }
 
+    #[test]
+    /// non-english documents keep their words whole, minus the given languages' stop words
+    fn document_creation_with_options() {
+        let html = "<body><p>Новости и Контакты</p><p>Новости</p></body>";
+
+        let doc = Document::from_html(html, &PreprocessOptions::default()).unwrap();
+        assert_eq!(doc.number_of_terms(), 4);
+        assert_eq!(doc.terms().get(&Term::new("новости")).unwrap().count(), 2);
+
+        let options = PreprocessOptions {
+            languages: vec![String::from("ru")],
+            transliterate: true,
+        };
+
+        let doc = Document::from_html(html, &options).unwrap();
+        assert_eq!(doc.number_of_terms(), 3);
+        assert_eq!(doc.terms().get(&Term::new("novosti")).unwrap().count(), 2);
+        assert!(doc.terms().contains_key(&Term::new("kontakty")));
+        assert!(!doc.terms().contains_key(&Term::new("i")));
+    }
+
     #[test]
     /// ensure words in script/style tags aren't processed
     fn document_creation_skips_script_and_style_tags() {
         let html = "<body><script>The air quality</script><style>in Singapore</style><p>got worse on Wednesday.</p></body>";
-        let doc = Document::from_html(html).unwrap();
+        let doc = Document::from_html(html, &PreprocessOptions::default()).unwrap();
         let keys = doc.terms().keys().map(|key| key.raw()).collect::<Vec<_>>();
 
         let expected = ["worse", "wednesday"];
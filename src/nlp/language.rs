@@ -0,0 +1,197 @@
+use super::document::visible_text;
+use super::utils::{cjk_script, normalize_case, remove_punctuation, stop_words, CjkScript};
+
+/// languages written in latin script that can be told apart by `guess_language`
+const LATIN_LANGUAGES: [&str; 6] = ["en", "de", "fr", "es", "pt", "it"];
+
+/// character trigrams that are common in one language's text but not the others'; words are
+/// padded with a space on either side, so a trigram can mark the start or end of a word
+const TRIGRAMS: [(&str, [&str; 12]); 6] = [
+    (
+        "en",
+        [
+            " th", "the", "he ", "ing", "ng ", "and", " wh", "ly ", " yo", "you", "ith", "ght",
+        ],
+    ),
+    (
+        "de",
+        [
+            "sch", "ich", "ch ", "cht", "ein", "ung", "und", " ge", "gen", "ür ", "eit", "ß",
+        ],
+    ),
+    (
+        "fr",
+        [
+            "les", "eur", "ux ", "ait", "ons", "ée ", "és ", " qu", "ais", "oi", "tre", "è",
+        ],
+    ),
+    (
+        "es",
+        [
+            "ión", "ón ", "ado", "dad", "ñ", "os ", "ía ", " el", "ued", "cia", "ar ", "¿",
+        ],
+    ),
+    (
+        "pt",
+        [
+            "ão ", "ção", "ões", "nh", "lh", "ã", "õ", "ç", "em ", " nã", "ade", "ês ",
+        ],
+    ),
+    (
+        "it",
+        [
+            "che", "zio", "gli", "ell", "lla", "tà ", "zz", "tto", "ere", "ett", "cc", "one",
+        ],
+    ),
+];
+
+/// weight of a stop word hit relative to a trigram hit; a whole word is stronger evidence than
+/// part of one
+const STOP_WORD_WEIGHT: f32 = 4.0;
+
+/// least amount of evidence (weighted stop word and trigram hits) needed before a guess is made
+const MIN_EVIDENCE: f32 = 20.0;
+
+/// fewest characters of a non-latin script needed before that script decides the language
+const MIN_SCRIPT_CHARS: usize = 10;
+
+/// best guess at the human language of the given response body, by ISO 639-1 code; None when
+/// the body doesn't have enough text to make a confident guess
+///
+/// only the visible text of html is considered, so markup, scripts, and styles don't skew the
+/// guess toward english
+pub(crate) fn detect_language(body: &str) -> Option<&'static str> {
+    let text = visible_text(body)?;
+    guess_language(&text)
+}
+
+/// guess the language of plain text; non-latin scripts are identified by script alone, while
+/// latin languages are scored on stop words and character trigrams
+pub(super) fn guess_language(text: &str) -> Option<&'static str> {
+    let mut cyrillic = 0;
+    let mut kana = 0;
+    let mut han = 0;
+    let mut latin = 0;
+
+    for c in text.chars() {
+        match cjk_script(c) {
+            Some(CjkScript::Hiragana | CjkScript::Katakana) => kana += 1,
+            Some(CjkScript::Han) => han += 1,
+            None if matches!(c, '\u{0400}'..='\u{04ff}') => cyrillic += 1,
+            None if c.is_alphabetic() => latin += 1,
+            None => {}
+        }
+    }
+
+    // japanese mixes kana with kanji, so any real amount of kana is enough to call it japanese,
+    // even when there's more han
+    if kana >= MIN_SCRIPT_CHARS && kana + han > latin {
+        return Some("ja");
+    }
+
+    if han >= MIN_SCRIPT_CHARS && han > latin {
+        return Some("zh");
+    }
+
+    if cyrillic >= MIN_SCRIPT_CHARS && cyrillic > latin {
+        return Some("ru");
+    }
+
+    guess_latin_language(text)
+}
+
+/// score each latin language on the given text; the highest score wins, provided there's
+/// enough evidence behind it and it's clear of the runner up
+fn guess_latin_language(text: &str) -> Option<&'static str> {
+    let text = remove_punctuation(text);
+    let text = normalize_case(text.as_str());
+
+    let mut scores = [0.0_f32; LATIN_LANGUAGES.len()];
+
+    for word in text.split_whitespace() {
+        // words shared by several languages' stop word lists (ex: "de") are split between them
+        let hits: Vec<_> = LATIN_LANGUAGES
+            .iter()
+            .map(|language| stop_words(language).contains(&word))
+            .collect();
+
+        let shared = hits.iter().filter(|hit| **hit).count();
+
+        for (score, hit) in scores.iter_mut().zip(&hits) {
+            if *hit {
+                *score += STOP_WORD_WEIGHT / shared as f32;
+            }
+        }
+
+        let padded = format!(" {word} ");
+
+        for (score, (_, trigrams)) in scores.iter_mut().zip(&TRIGRAMS) {
+            *score += trigrams
+                .iter()
+                .filter(|trigram| padded.contains(*trigram))
+                .count() as f32;
+        }
+    }
+
+    let mut ranked: Vec<_> = LATIN_LANGUAGES.iter().zip(scores).collect();
+    ranked.sort_by(|a, b| b.1.total_cmp(&a.1));
+
+    let (language, best) = ranked[0];
+    let runner_up = ranked[1].1;
+
+    if best < MIN_EVIDENCE || best < runner_up * 1.25 {
+        return None;
+    }
+
+    Some(language)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    /// a few sentences are enough to tell the latin languages apart
+    fn guess_language_identifies_latin_languages() {
+        let samples = [
+            ("en", "The quick brown fox jumps over the lazy dog. You should have seen what happened when they went to the store and bought everything that was on sale."),
+            ("de", "Die Mitarbeiter haben sich entschieden, dass wir die Sitzung auf nächste Woche verschieben. Ich glaube, das ist eine gute Entscheidung für unser Unternehmen."),
+            ("fr", "Les utilisateurs peuvent modifier leurs paramètres à tout moment. Nous avons mis à jour notre politique de confidentialité pour vous informer de nos pratiques."),
+            ("es", "Los usuarios pueden modificar su configuración en cualquier momento. Hemos actualizado nuestra política de privacidad para que la información sea más clara."),
+            ("pt", "Os usuários podem alterar as suas configurações a qualquer momento. Não se esqueça de que a nossa política de privacidade também foi atualizada."),
+            ("it", "Gli utenti possono modificare le proprie impostazioni in qualsiasi momento. Abbiamo aggiornato la nostra politica sulla riservatezza per renderla più chiara."),
+        ];
+
+        for (expected, text) in samples {
+            assert_eq!(guess_language(text), Some(expected), "{text}");
+        }
+    }
+
+    #[test]
+    /// non-latin scripts are identified by script
+    fn guess_language_identifies_non_latin_scripts() {
+        assert_eq!(
+            guess_language("Пользователи могут изменить свои настройки в любое время."),
+            Some("ru")
+        );
+        assert_eq!(
+            guess_language("ユーザーはいつでも設定を変更することができます。"),
+            Some("ja")
+        );
+        assert_eq!(
+            guess_language("用户可以随时更改他们的设置，我们已经更新了隐私政策。"),
+            Some("zh")
+        );
+    }
+
+    #[test]
+    /// too little text, or text that isn't prose, gets no guess
+    fn guess_language_needs_enough_evidence() {
+        assert_eq!(guess_language(""), None);
+        assert_eq!(guess_language("Not Found"), None);
+        assert_eq!(
+            guess_language("{\"id\":12,\"token\":\"a8f3e1\",\"ok\":true}"),
+            None
+        );
+    }
+}
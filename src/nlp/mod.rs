@@ -2,10 +2,13 @@
 
 mod constants;
 mod document;
+mod language;
 mod model;
 mod term;
 mod utils;
 
+pub(crate) use self::constants::STOP_WORD_LANGUAGES;
 pub(crate) use self::document::Document;
+pub(crate) use self::language::detect_language;
 pub(crate) use self::model::TfIdf;
-pub(crate) use self::utils::preprocess;
+pub(crate) use self::utils::{preprocess, PreprocessOptions};
@@ -1,8 +1,8 @@
 use super::document::Document;
 use super::term::{Term, TermMetaData};
-use super::utils::{inverse_document_frequency, tf_idf_score};
+use super::utils::{inverse_document_frequency, tf_idf_score, PreprocessOptions};
 use std::borrow::{Borrow, BorrowMut};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 /// data container for the TF-IDF model
 #[derive(Debug, Default)]
@@ -12,6 +12,13 @@ pub(crate) struct TfIdf {
 
     /// number of documents processed by the model
     num_documents: usize,
+
+    /// how documents added to the model should be pre-processed
+    options: PreprocessOptions,
+
+    /// number of documents, and the terms they contained, for each origin (scheme, host, and
+    /// port) that documents came from
+    origins: HashMap<String, (usize, HashSet<Term>)>,
 }
 
 impl TfIdf {
@@ -25,6 +32,16 @@ impl TfIdf {
         self.terms.borrow()
     }
 
+    /// accessor method for the options used to pre-process documents added to the model
+    pub(crate) fn options(&self) -> &PreprocessOptions {
+        &self.options
+    }
+
+    /// set the options used to pre-process documents added to the model
+    pub(crate) fn set_options(&mut self, options: PreprocessOptions) {
+        self.options = options;
+    }
+
     /// accessor method for the number of `Document`s the model has processed
     pub(crate) fn num_documents(&self) -> usize {
         self.num_documents
@@ -51,6 +68,17 @@ impl TfIdf {
         }
     }
 
+    /// add a `Document` to the model, remembering the origin (scheme, host, and port) it came
+    /// from so that words can later be selected per origin
+    pub(crate) fn add_document_from(&mut self, document: Document, origin: &str) {
+        let (documents, terms) = self.origins.entry(origin.to_owned()).or_default();
+
+        *documents += 1;
+        terms.extend(document.terms().keys().cloned());
+
+        self.add_document(document);
+    }
+
     /// (re)-calculate tf-idf scores for all terms, given the current number of documents
     ///
     /// # Notes
@@ -79,14 +107,54 @@ impl TfIdf {
         }
     }
 
-    /// select all terms with a non-zero tf-idf score
+    /// select all terms with a non-zero tf-idf score, highest scoring first
     pub(crate) fn all_words(&self) -> Vec<String> {
-        self.terms()
-            .iter()
-            .filter(|(_, metadata)| metadata.tf_idf_score() > 0.0)
-            .map(|(term, _)| term.raw().to_owned())
+        rank(self.terms().iter())
+            .into_iter()
+            .map(|(word, _)| word)
             .collect()
     }
+
+    /// origins that documents were added from via `add_document_from`, along with the number
+    /// of documents each contributed, sorted by origin
+    pub(crate) fn origins(&self) -> Vec<(&str, usize)> {
+        let mut origins = self
+            .origins
+            .iter()
+            .map(|(origin, (documents, _))| (origin.as_str(), *documents))
+            .collect::<Vec<_>>();
+
+        origins.sort();
+        origins
+    }
+
+    /// select the terms seen in documents from the given origin that have a non-zero tf-idf
+    /// score, along with that score, highest scoring first
+    ///
+    /// scores come from the whole model, so a word common to every origin scores low for each
+    pub(crate) fn scored_words_from(&self, origin: &str) -> Vec<(String, f32)> {
+        let Some((_, seen)) = self.origins.get(origin) else {
+            return Vec::new();
+        };
+
+        rank(self.terms().iter().filter(|(term, _)| seen.contains(*term)))
+    }
+}
+
+/// the given terms that have a non-zero tf-idf score, highest scoring first
+fn rank<'a>(terms: impl Iterator<Item = (&'a Term, &'a TermMetaData)>) -> Vec<(String, f32)> {
+    let mut scored = terms
+        .filter(|(_, metadata)| metadata.tf_idf_score() > 0.0)
+        .map(|(term, metadata)| (term.raw(), metadata.tf_idf_score()))
+        .collect::<Vec<_>>();
+
+    // ties are broken alphabetically, so the order doesn't depend on the hashmap's
+    scored.sort_by(|a, b| b.1.total_cmp(&a.1).then_with(|| a.0.cmp(b.0)));
+
+    scored
+        .into_iter()
+        .map(|(word, score)| (word.to_owned(), score))
+        .collect()
 }
 
 #[cfg(test)]
@@ -112,7 +180,7 @@ mod tests {
         let mut model = TfIdf::new();
 
         for doc in docs.iter() {
-            let d = Document::new(doc);
+            let d = Document::new(doc, &PreprocessOptions::default());
             model.add_document(d);
         }
 
@@ -148,7 +216,7 @@ mod tests {
         let mut model = TfIdf::new();
 
         for doc in docs.iter() {
-            let d = Document::new(doc);
+            let d = Document::new(doc, &PreprocessOptions::default());
             model.add_document(d);
         }
 
@@ -181,5 +249,60 @@ mod tests {
         .for_each(|word| {
             assert!(non_zero_words.contains(&word.to_string()));
         });
+
+        // highest scoring first, gradually/improved/sunny tie and are sorted alphabetically
+        assert_eq!(non_zero_words[..3], ["gradually", "improved", "sunny"]);
+        assert!(non_zero_words
+            .windows(2)
+            .all(|pair| { get_score(&pair[0], &model) >= get_score(&pair[1], &model) }));
+    }
+
+    #[test]
+    /// words are selected per origin, but scored across the whole model
+    fn scored_words_from_selects_words_by_origin() {
+        let mut model = TfIdf::new();
+
+        let docs = [
+            (
+                "http://one.local",
+                "Air quality in the sunny island improved gradually.",
+            ),
+            (
+                "http://one.local",
+                "Air quality in Singapore got worse as haze hit.",
+            ),
+            (
+                "http://two.local",
+                "The air in Singapore is monitored through stations.",
+            ),
+        ];
+
+        for (origin, doc) in docs {
+            let d = Document::new(doc, &PreprocessOptions::default());
+            model.add_document_from(d, origin);
+        }
+
+        model.calculate_tf_idf_scores();
+
+        assert_eq!(
+            model.origins(),
+            [("http://one.local", 2), ("http://two.local", 1)]
+        );
+
+        let one = model.scored_words_from("http://one.local");
+        let two = model.scored_words_from("http://two.local");
+
+        assert!(one.iter().any(|(word, _)| word == "haze"));
+        assert!(!one.iter().any(|(word, _)| word == "stations"));
+        assert!(two.iter().any(|(word, _)| word == "stations"));
+
+        // seen in every document, so it doesn't score
+        assert!(!two.iter().any(|(word, _)| word == "air"));
+
+        assert_eq!(
+            two.iter().find(|(word, _)| word == "singapore").unwrap().1,
+            get_score("singapore", &model)
+        );
+        assert!(model.scored_words_from("http://three.local").is_empty());
     }
 }
@@ -1,21 +1,79 @@
-use super::constants::{BOUNDED_WORD_REGEX, STOP_WORDS};
+use super::constants::{
+    BOUNDED_WORD_REGEX, CHINESE_STOP_WORDS, FRENCH_STOP_WORDS, GERMAN_STOP_WORDS,
+    ITALIAN_STOP_WORDS, JAPANESE_STOP_WORDS, PORTUGUESE_STOP_WORDS, RUSSIAN_STOP_WORDS,
+    SPANISH_STOP_WORDS, STOP_WORDS,
+};
 use regex::Captures;
 use std::borrow::Cow;
 
-/// pre-processing pipeline wrapper that removes punctuation, normalizes word case (utf-8 included)
-/// to lowercase, and remove stop words
+/// options controlling how text is turned into words, set via --word-languages and
+/// --transliterate
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct PreprocessOptions {
+    /// languages whose stop words are removed, by ISO 639-1 code
+    pub(crate) languages: Vec<String>,
+
+    /// whether or not cyrillic and greek words are transliterated to latin (and diacritics
+    /// stripped from latin words)
+    pub(crate) transliterate: bool,
+}
+
+/// default implementation for PreprocessOptions
+impl Default for PreprocessOptions {
+    /// english stop words, no transliteration
+    fn default() -> Self {
+        Self {
+            languages: vec![String::from("en")],
+            transliterate: false,
+        }
+    }
+}
+
+/// which of the scripts that aren't space-delimited a character belongs to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum CjkScript {
+    /// chinese characters (kanji in japanese)
+    Han,
+
+    /// japanese hiragana
+    Hiragana,
+
+    /// japanese katakana, including the prolonged sound mark and half-width forms
+    Katakana,
+}
+
+/// pre-processing pipeline wrapper that uses the default `PreprocessOptions`
 pub(crate) fn preprocess(text: &str) -> Vec<String> {
+    preprocess_with(text, &PreprocessOptions::default())
+}
+
+/// pre-processing pipeline that removes punctuation, normalizes word case (utf-8 included) to
+/// lowercase, splits the text into words (segmenting CJK text), removes stop words for the
+/// given languages, and optionally transliterates what's left
+pub(crate) fn preprocess_with(text: &str, options: &PreprocessOptions) -> Vec<String> {
     let text = remove_punctuation(text);
     let text = normalize_case(text);
-    let text = remove_stop_words(&text);
+    let text = if options.languages.iter().any(|language| language == "en") {
+        Cow::Owned(remove_stop_words(&text))
+    } else {
+        text
+    };
+    let words = tokenize(&text);
+    let words = remove_language_stop_words(words, &options.languages);
+
+    if !options.transliterate {
+        return words;
+    }
 
-    text.split_whitespace()
-        .map(|word| word.to_string())
-        .collect::<Vec<_>>()
+    words
+        .iter()
+        .map(|word| transliterate(word).into_owned())
+        .filter(|word| !word.is_empty())
+        .collect()
 }
 
 /// optimized version of `str::to_lowercase`
-fn normalize_case<'a, S: Into<Cow<'a, str>>>(input: S) -> Cow<'a, str> {
+pub(super) fn normalize_case<'a, S: Into<Cow<'a, str>>>(input: S) -> Cow<'a, str> {
     let input = input.into();
 
     let first = input.find(char::is_uppercase);
@@ -39,18 +97,101 @@ fn normalize_case<'a, S: Into<Cow<'a, str>>>(input: S) -> Cow<'a, str> {
 }
 
 /// replace ascii and some utf-8 punctuation characters with ' ' (space) in the given string
-fn remove_punctuation(text: &str) -> String {
+pub(super) fn remove_punctuation(text: &str) -> String {
     text.replace(
         [
             '!', '\\', '"', '#', '$', '%', '&', '(', ')', '*', '+', ':', ';', '<', '=', '>', '?',
             '@', '[', ']', '^', '{', '}', '|', '~', ',', '\'', '“', '”', '’', '‘', '’', '‘', '/',
-            '–', '—', '.',
+            '–', '—', '.', '«', '»', '„', '¿', '¡', '…', '。', '、', '，', '．', '：', '；', '！',
+            '？', '「', '」', '『', '』', '（', '）', '【', '】', '《', '》', '〈', '〉', '・',
+            '〜', '～',
         ],
         " ",
     )
 }
 
-/// remove stop words from the given string
+/// split text into words on whitespace, then segment any CJK text found within those words
+fn tokenize(text: &str) -> Vec<String> {
+    text.split_whitespace().flat_map(segment_cjk).collect()
+}
+
+/// determine which CJK script, if any, the given character belongs to
+pub(super) fn cjk_script(c: char) -> Option<CjkScript> {
+    match c {
+        '\u{3005}'
+        | '\u{3400}'..='\u{4dbf}'
+        | '\u{4e00}'..='\u{9fff}'
+        | '\u{f900}'..='\u{faff}'
+        | '\u{20000}'..='\u{2fa1f}' => Some(CjkScript::Han),
+        '\u{3040}'..='\u{309f}' => Some(CjkScript::Hiragana),
+        '\u{30a0}'..='\u{30ff}' | '\u{31f0}'..='\u{31ff}' | '\u{ff66}'..='\u{ff9f}' => {
+            Some(CjkScript::Katakana)
+        }
+        _ => None,
+    }
+}
+
+/// CJK text isn't space-delimited, so a word containing CJK characters is split wherever the
+/// script changes (ex: kanji followed by hiragana), and runs of more than two han characters
+/// are broken into overlapping bigrams
+fn segment_cjk(word: &str) -> Vec<String> {
+    if !word.chars().any(|c| cjk_script(c).is_some()) {
+        return vec![word.to_string()];
+    }
+
+    let mut segments = Vec::new();
+    let mut current = String::new();
+    let mut current_script = None;
+
+    for c in word.chars() {
+        let script = cjk_script(c);
+
+        if !current.is_empty() && script != current_script {
+            push_segment(&mut segments, std::mem::take(&mut current), current_script);
+        }
+
+        current_script = script;
+        current.push(c);
+    }
+
+    push_segment(&mut segments, current, current_script);
+
+    segments
+}
+
+/// add a single-script run of characters to the given segments, breaking long han runs into
+/// bigrams
+fn push_segment(segments: &mut Vec<String>, segment: String, script: Option<CjkScript>) {
+    if segment.is_empty() {
+        return;
+    }
+
+    let chars: Vec<char> = segment.chars().collect();
+
+    if script == Some(CjkScript::Han) && chars.len() > 2 {
+        segments.extend(chars.windows(2).map(|pair| pair.iter().collect::<String>()));
+    } else {
+        segments.push(segment);
+    }
+}
+
+/// stop words for the given ISO 639-1 language code; unknown languages have none
+pub(super) fn stop_words(language: &str) -> &'static [&'static str] {
+    match language {
+        "en" => &STOP_WORDS,
+        "de" => &GERMAN_STOP_WORDS,
+        "fr" => &FRENCH_STOP_WORDS,
+        "es" => &SPANISH_STOP_WORDS,
+        "pt" => &PORTUGUESE_STOP_WORDS,
+        "it" => &ITALIAN_STOP_WORDS,
+        "ru" => &RUSSIAN_STOP_WORDS,
+        "zh" => &CHINESE_STOP_WORDS,
+        "ja" => &JAPANESE_STOP_WORDS,
+        _ => &[],
+    }
+}
+
+/// remove english stop words from the given string
 fn remove_stop_words(text: &str) -> String {
     BOUNDED_WORD_REGEX
         .replace_all(text, |caps: &Captures| {
@@ -64,6 +205,124 @@ fn remove_stop_words(text: &str) -> String {
         .into()
 }
 
+/// remove stop words, for each of the given non-english languages, from the given words;
+/// english is handled by `remove_stop_words` before the text is split into words
+fn remove_language_stop_words(words: Vec<String>, languages: &[String]) -> Vec<String> {
+    let lists: Vec<_> = languages
+        .iter()
+        .filter(|language| language.as_str() != "en")
+        .map(|language| stop_words(language))
+        .collect();
+
+    words
+        .into_iter()
+        .filter(|word| !lists.iter().any(|list| list.contains(&word.as_str())))
+        .collect()
+}
+
+/// latin replacement for a single lowercase cyrillic, greek, or accented latin character;
+/// None when the character should be kept as-is
+fn transliterate_char(c: char) -> Option<&'static str> {
+    let replacement = match c {
+        // cyrillic (russian, ukrainian, belarusian)
+        'а' => "a",
+        'б' => "b",
+        'в' => "v",
+        'г' | 'ґ' => "g",
+        'д' => "d",
+        'е' | 'ё' | 'э' => "e",
+        'є' => "ye",
+        'ж' => "zh",
+        'з' => "z",
+        'и' | 'і' => "i",
+        'ї' => "yi",
+        'й' | 'ы' => "y",
+        'к' => "k",
+        'л' => "l",
+        'м' => "m",
+        'н' => "n",
+        'о' => "o",
+        'п' => "p",
+        'р' => "r",
+        'с' => "s",
+        'т' => "t",
+        'у' | 'ў' => "u",
+        'ф' => "f",
+        'х' => "kh",
+        'ц' => "ts",
+        'ч' => "ch",
+        'ш' => "sh",
+        'щ' => "shch",
+        'ъ' | 'ь' => "",
+        'ю' => "yu",
+        'я' => "ya",
+        // greek
+        'α' | 'ά' => "a",
+        'β' => "v",
+        'γ' => "g",
+        'δ' => "d",
+        'ε' | 'έ' => "e",
+        'ζ' => "z",
+        'η' | 'ή' | 'ι' | 'ί' | 'ϊ' | 'ΐ' => "i",
+        'θ' => "th",
+        'κ' => "k",
+        'λ' => "l",
+        'μ' => "m",
+        'ν' => "n",
+        'ξ' => "x",
+        'ο' | 'ό' | 'ω' | 'ώ' => "o",
+        'π' => "p",
+        'ρ' => "r",
+        'σ' | 'ς' => "s",
+        'τ' => "t",
+        'υ' | 'ύ' | 'ϋ' | 'ΰ' => "y",
+        'φ' => "f",
+        'χ' => "ch",
+        'ψ' => "ps",
+        // latin with diacritics
+        'à' | 'á' | 'â' | 'ã' | 'ä' | 'å' | 'ā' | 'ă' | 'ą' => "a",
+        'æ' => "ae",
+        'ç' | 'ć' | 'č' => "c",
+        'ď' | 'đ' => "d",
+        'è' | 'é' | 'ê' | 'ë' | 'ē' | 'ė' | 'ę' | 'ě' => "e",
+        'ğ' => "g",
+        'ì' | 'í' | 'î' | 'ï' | 'ī' | 'ı' => "i",
+        'ł' | 'ľ' => "l",
+        'ñ' | 'ń' | 'ň' => "n",
+        'ò' | 'ó' | 'ô' | 'õ' | 'ö' | 'ø' | 'ō' | 'ő' => "o",
+        'œ' => "oe",
+        'ř' => "r",
+        'ś' | 'š' | 'ş' | 'ș' => "s",
+        'ß' => "ss",
+        'ť' | 'ţ' | 'ț' => "t",
+        'ù' | 'ú' | 'û' | 'ü' | 'ū' | 'ů' | 'ű' => "u",
+        'ý' | 'ÿ' => "y",
+        'ź' | 'ż' | 'ž' => "z",
+        _ => return None,
+    };
+
+    Some(replacement)
+}
+
+/// transliterate cyrillic and greek to latin, and strip diacritics from latin characters;
+/// anything else (ex: CJK) is left as-is
+fn transliterate(word: &str) -> Cow<'_, str> {
+    if word.is_ascii() {
+        return Cow::Borrowed(word);
+    }
+
+    let mut output = String::with_capacity(word.len());
+
+    for c in word.chars() {
+        match transliterate_char(c) {
+            Some(replacement) => output.push_str(replacement),
+            None => output.push(c),
+        }
+    }
+
+    Cow::Owned(output)
+}
+
 /// calculate inverse document frequency
 pub(super) fn inverse_document_frequency(num_docs: f32, doc_frequency: f32) -> f32 {
     f32::log10(num_docs / doc_frequency)
@@ -76,6 +335,7 @@ pub(super) fn tf_idf_score(term_frequency: f32, idf: f32) -> f32 {
 
 #[cfg(test)]
 mod tests {
+    use super::super::constants::STOP_WORD_LANGUAGES;
     use super::*;
 
     #[test]
@@ -111,6 +371,29 @@ mod tests {
         assert_eq!(removed, "'d'll'm''s'ven'tn‘tn’t‘d‘ll‘m‘‘s‘ve’d’ll’m’’s’ve");
     }
 
+    #[test]
+    /// ensure stop words are only removed for the requested languages
+    fn test_remove_stopwords_per_language() {
+        let words = ["the", "der", "и", "的", "server"]
+            .iter()
+            .map(|word| word.to_string())
+            .collect::<Vec<_>>();
+
+        let languages = [String::from("de"), String::from("ru")];
+
+        assert_eq!(
+            remove_language_stop_words(words.clone(), &languages),
+            ["the", "的", "server"]
+        );
+        assert_eq!(remove_language_stop_words(words.clone(), &[]), words);
+
+        // every language has a list, and nothing outside of a list is removed
+        for language in STOP_WORD_LANGUAGES {
+            assert!(!stop_words(language).is_empty());
+        }
+        assert!(stop_words("xx").is_empty());
+    }
+
     #[test]
     /// ensure preprocess
     fn test_preprocess_results() {
@@ -118,6 +401,68 @@ mod tests {
         assert_eq!(&preprocess(tester), &["y", "all", "yelling"]);
     }
 
+    #[test]
+    /// cyrillic words are kept whole and lowercased, cjk text is segmented by script with han
+    /// runs broken into bigrams
+    fn test_tokenize_non_english_text() {
+        assert_eq!(
+            &preprocess("Новости, Контакты и Вход"),
+            &["новости", "контакты", "и", "вход"]
+        );
+
+        // 東京 (han), の (hiragana), ログイン (katakana), 管理画面 (han, 4 chars)
+        assert_eq!(
+            &preprocess("東京のログイン。管理画面"),
+            &["東京", "の", "ログイン", "管理", "理画", "画面"]
+        );
+        assert_eq!(&segment_cjk("用户"), &["用户"]);
+        assert_eq!(&segment_cjk("v2版本"), &["v2", "版本"]);
+    }
+
+    #[test]
+    /// preprocess_with should apply each requested language's stop words and transliterate
+    /// when asked
+    fn test_preprocess_with_options() {
+        let options = PreprocessOptions {
+            languages: vec![String::from("ru"), String::from("ja")],
+            transliterate: false,
+        };
+
+        assert_eq!(
+            &preprocess_with("Новости и Контакты", &options),
+            &["новости", "контакты"]
+        );
+        assert_eq!(
+            &preprocess_with("東京のログイン", &options),
+            &["東京", "ログイン"]
+        );
+
+        let options = PreprocessOptions {
+            languages: vec![String::from("ru"), String::from("de")],
+            transliterate: true,
+        };
+
+        assert_eq!(
+            &preprocess_with("Новости и Контакты; Größe der Übersicht", &options),
+            &["novosti", "kontakty", "grosse", "ubersicht"]
+        );
+    }
+
+    #[test]
+    /// ensure transliteration covers cyrillic, greek, and latin diacritics, and leaves
+    /// everything else alone
+    fn test_transliterate() {
+        assert_eq!(transliterate("щука"), "shchuka");
+        assert_eq!(transliterate("объявления"), "obyavleniya");
+        assert_eq!(transliterate("їжак"), "yizhak");
+        assert_eq!(transliterate("λογαριασμός"), "logariasmos");
+        assert_eq!(transliterate("café"), "cafe");
+        assert_eq!(transliterate("łódź"), "lodz");
+        assert_eq!(transliterate("東京"), "東京");
+        assert_eq!(transliterate("ascii"), "ascii");
+        assert_eq!(transliterate("ь"), "");
+    }
+
     #[test]
     /// ensure our calculations conform to the example provided at the link below
     ///
@@ -0,0 +1,124 @@
+//! live list of discovered urls for nuclei (--nuclei-targets)
+//!
+//! every finding is appended to the file as soon as it's reported, one url per line, so that
+//! nuclei can be pointed at the file (nuclei -list FILE) while discovery is still running
+use std::{
+    collections::HashSet,
+    fs::File,
+    io::{BufWriter, Write},
+    sync::Mutex,
+};
+
+use anyhow::{bail, Context, Result};
+
+use crate::{
+    config::Configuration,
+    response::FeroxResponse,
+    utils::{fmt_err, open_file},
+};
+
+/// appends discovered urls to --nuclei-targets
+#[derive(Debug)]
+pub(crate) struct NucleiTargets {
+    /// path given to --nuclei-targets, used in error messages
+    path: String,
+
+    /// the open --nuclei-targets file, along with the urls already written to it
+    state: Mutex<(BufWriter<File>, HashSet<String>)>,
+}
+
+/// implementation of NucleiTargets
+impl NucleiTargets {
+    /// open the --nuclei-targets file for appending; None when the option wasn't used or the
+    /// file couldn't be opened
+    pub(crate) fn new(config: &Configuration) -> Option<Self> {
+        if config.nuclei_targets.is_empty() {
+            return None;
+        }
+
+        match open_file(&config.nuclei_targets) {
+            Ok(writer) => Some(Self {
+                path: config.nuclei_targets.clone(),
+                state: Mutex::new((writer, HashSet::new())),
+            }),
+            Err(e) => {
+                log::warn!("Not writing nuclei targets: {:#}", e);
+                None
+            }
+        }
+    }
+
+    /// append the response's url, unless it's a wildcard or was already written (i.e. the same
+    /// url found with another method); flushed right away so nuclei sees it
+    pub(crate) fn add(&self, response: &FeroxResponse) -> Result<()> {
+        if response.wildcard() {
+            return Ok(());
+        }
+
+        let Ok(mut guard) = self.state.lock() else {
+            bail!(fmt_err(&format!("Could not lock {}", self.path)));
+        };
+
+        let (writer, written) = &mut *guard;
+        let url = response.url().as_str();
+
+        if !written.insert(url.to_string()) {
+            return Ok(());
+        }
+
+        writeln!(writer, "{url}")
+            .and_then(|_| writer.flush())
+            .with_context(|| fmt_err(&format!("Could not write to {}", self.path)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::response::fixtures::response;
+    use tempfile::NamedTempFile;
+
+    #[test]
+    /// urls are written as they're added, each only once, and wildcards are left out
+    fn nuclei_targets_appends_unique_urls() {
+        let file = NamedTempFile::new().unwrap();
+
+        let config = Configuration {
+            nuclei_targets: file.path().to_string_lossy().to_string(),
+            ..Default::default()
+        };
+
+        let targets = NucleiTargets::new(&config).unwrap();
+
+        targets
+            .add(&response("http://localhost/admin", 200))
+            .unwrap();
+
+        assert_eq!(
+            std::fs::read_to_string(file.path()).unwrap(),
+            "http://localhost/admin\n"
+        );
+
+        let mut wildcard = response("http://localhost/wild", 200);
+        wildcard.set_wildcard(true);
+
+        targets
+            .add(&response("http://localhost/admin", 200))
+            .unwrap();
+        targets.add(&wildcard).unwrap();
+        targets
+            .add(&response("http://localhost/login", 200))
+            .unwrap();
+
+        assert_eq!(
+            std::fs::read_to_string(file.path()).unwrap(),
+            "http://localhost/admin\nhttp://localhost/login\n"
+        );
+    }
+
+    #[test]
+    /// nothing is written when --nuclei-targets isn't used
+    fn nuclei_targets_unused() {
+        assert!(NucleiTargets::new(&Configuration::default()).is_none());
+    }
+}
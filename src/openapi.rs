@@ -0,0 +1,422 @@
+//! seeding a scan with the operations documented in an OpenAPI (3.x) or Swagger (2.0) spec,
+//! see --openapi
+//!
+//! each documented path and method is requested once, ahead of the wordlist. Path parameters
+//! are filled in, and query parameters, form parameters, and json bodies are built from the
+//! spec's examples, defaults, and enums (falling back to a placeholder of the right type)
+use anyhow::{bail, Result};
+use serde_json::{Map, Value};
+
+/// methods that can hold an operation in a path item
+const OPERATION_METHODS: [&str; 8] = [
+    "get", "put", "post", "delete", "options", "head", "patch", "trace",
+];
+
+/// where specs are commonly served from, tried in order when --openapi auto is used
+pub const SPEC_LOCATIONS: [&str; 8] = [
+    "openapi.json",
+    "swagger.json",
+    "v3/api-docs",
+    "v2/api-docs",
+    "api-docs",
+    "swagger/v1/swagger.json",
+    "api/openapi.json",
+    "api/swagger.json",
+];
+
+/// a single documented operation, ready to be requested against a target
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ApiOperation {
+    /// http method, uppercased
+    pub method: String,
+
+    /// path and query, relative to the target (i.e. v1/users/1?limit=10), with the spec's base
+    /// path already applied
+    pub path: String,
+
+    /// request body built from the spec, if the operation documents one
+    pub body: Option<Vec<u8>>,
+}
+
+/// parse the given spec into its operations; fails when it doesn't look like an OpenAPI or
+/// Swagger spec
+///
+/// only json specs are supported
+pub fn parse_spec(contents: &str) -> Result<Vec<ApiOperation>> {
+    let Ok(spec) = serde_json::from_str::<Value>(contents) else {
+        bail!("Expected an OpenAPI or Swagger spec in json format");
+    };
+
+    if spec.get("openapi").is_none() && spec.get("swagger").is_none() {
+        bail!("Expected an OpenAPI or Swagger spec, i.e. {{\"openapi\": \"3.0.0\", ...}}");
+    }
+
+    let base = base_path(&spec);
+    let mut operations = Vec::new();
+
+    let Some(paths) = spec.get("paths").and_then(Value::as_object) else {
+        return Ok(operations);
+    };
+
+    for (template, item) in paths {
+        let item = resolve(&spec, item);
+        let shared = item.get("parameters").and_then(Value::as_array);
+
+        for method in OPERATION_METHODS {
+            let Some(operation) = item.get(method) else {
+                continue;
+            };
+
+            // operation-level parameters override path-level ones of the same name and location
+            let mut parameters: Vec<&Value> = Vec::new();
+
+            for parameter in operation
+                .get("parameters")
+                .and_then(Value::as_array)
+                .into_iter()
+                .chain(shared)
+                .flatten()
+            {
+                let parameter = resolve(&spec, parameter);
+
+                let duplicate = parameters.iter().any(|existing| {
+                    existing.get("name") == parameter.get("name")
+                        && existing.get("in") == parameter.get("in")
+                });
+
+                if !duplicate {
+                    parameters.push(parameter);
+                }
+            }
+
+            operations.push(ApiOperation {
+                method: method.to_uppercase(),
+                path: fill_path(&base, template, &parameters),
+                body: request_body(&spec, operation, &parameters),
+            });
+        }
+    }
+
+    Ok(operations)
+}
+
+/// path that every operation is relative to, without leading or trailing slashes; the path of
+/// the first of servers (3.x), or basePath (2.0)
+fn base_path(spec: &Value) -> String {
+    let server = spec
+        .pointer("/servers/0/url")
+        .and_then(Value::as_str)
+        .map(|url| match url.split_once("://") {
+            // absolute; only the path is kept, the target decides where requests go
+            Some((_, rest)) => rest
+                .find('/')
+                .map(|slash| &rest[slash..])
+                .unwrap_or_default(),
+            None => url,
+        });
+
+    let base = server
+        .or_else(|| spec.get("basePath").and_then(Value::as_str))
+        .unwrap_or_default();
+
+    base.trim_matches('/').to_string()
+}
+
+/// follow a local $ref (i.e. #/components/parameters/limit), if the value is one
+fn resolve<'a>(spec: &'a Value, value: &'a Value) -> &'a Value {
+    match value.get("$ref").and_then(Value::as_str) {
+        Some(reference) => spec
+            .pointer(reference.trim_start_matches('#'))
+            .unwrap_or(value),
+        None => value,
+    }
+}
+
+/// the path template with path parameters filled in and query parameters appended
+fn fill_path(base: &str, template: &str, parameters: &[&Value]) -> String {
+    let mut path = template.trim_start_matches('/').to_string();
+    let mut query = Vec::new();
+
+    for parameter in parameters {
+        let Some(name) = parameter.get("name").and_then(Value::as_str) else {
+            continue;
+        };
+
+        match parameter.get("in").and_then(Value::as_str) {
+            Some("path") => {
+                path = path.replace(&format!("{{{name}}}"), &encode(&sample(parameter)));
+            }
+            Some("query") => query.push(format!("{}={}", encode(name), encode(&sample(parameter)))),
+            _ => {}
+        }
+    }
+
+    // undocumented path parameters still need a value
+    while let (Some(start), Some(end)) = (path.find('{'), path.find('}')) {
+        if end < start {
+            break;
+        }
+
+        path.replace_range(start..=end, "1");
+    }
+
+    let mut filled = if base.is_empty() {
+        path
+    } else {
+        format!("{base}/{path}")
+    };
+
+    if !query.is_empty() {
+        filled.push('?');
+        filled.push_str(&query.join("&"));
+    }
+
+    filled
+}
+
+/// body for the operation: the json example of its requestBody (3.x) or body parameter (2.0),
+/// or its form parameters, urlencoded
+fn request_body(spec: &Value, operation: &Value, parameters: &[&Value]) -> Option<Vec<u8>> {
+    let json_schema = operation
+        .get("requestBody")
+        .map(|body| resolve(spec, body))
+        .and_then(|body| body.pointer("/content/application~1json"))
+        .or_else(|| {
+            parameters
+                .iter()
+                .find(|parameter| parameter.get("in").and_then(Value::as_str) == Some("body"))
+                .copied()
+        });
+
+    if let Some(media) = json_schema {
+        let example = media
+            .get("example")
+            .or_else(|| media.pointer("/schema/example"))
+            .cloned()
+            .or_else(|| {
+                media
+                    .get("schema")
+                    .map(|schema| sample_json(spec, resolve(spec, schema), 0))
+            });
+
+        return example.and_then(|example| serde_json::to_vec(&example).ok());
+    }
+
+    let form: Vec<_> = parameters
+        .iter()
+        .filter(|parameter| parameter.get("in").and_then(Value::as_str) == Some("formData"))
+        .filter_map(|parameter| {
+            let name = parameter.get("name").and_then(Value::as_str)?;
+            Some(format!("{}={}", encode(name), encode(&sample(parameter))))
+        })
+        .collect();
+
+    if form.is_empty() {
+        None
+    } else {
+        Some(form.join("&").into_bytes())
+    }
+}
+
+/// a value for the given parameter: its example, default, or first enum value, from the
+/// parameter itself (2.0) or its schema (3.x); otherwise a placeholder of the right type
+fn sample(parameter: &Value) -> String {
+    let schema = parameter.get("schema").unwrap_or(parameter);
+
+    let documented = ["example", "default"]
+        .iter()
+        .find_map(|key| parameter.get(*key).or_else(|| schema.get(*key)))
+        .or_else(|| parameter.pointer("/enum/0"))
+        .or_else(|| schema.pointer("/enum/0"));
+
+    match documented {
+        Some(Value::String(value)) => value.clone(),
+        Some(value) => value.to_string(),
+        None => match schema.get("type").and_then(Value::as_str) {
+            Some("integer") | Some("number") => String::from("1"),
+            Some("boolean") => String::from("true"),
+            _ => String::from("test"),
+        },
+    }
+}
+
+/// a json value matching the given schema, built from examples where the spec has them; nested
+/// schemas are followed a few levels deep (schemas can refer to themselves)
+fn sample_json(spec: &Value, schema: &Value, depth: usize) -> Value {
+    if let Some(example) = schema.get("example").or_else(|| schema.get("default")) {
+        return example.clone();
+    }
+
+    if let Some(first) = schema.pointer("/enum/0") {
+        return first.clone();
+    }
+
+    match schema.get("type").and_then(Value::as_str) {
+        Some("integer") | Some("number") => Value::from(1),
+        Some("boolean") => Value::Bool(true),
+        Some("array") if depth < 4 => {
+            let items = schema
+                .get("items")
+                .map(|items| sample_json(spec, resolve(spec, items), depth + 1));
+
+            Value::Array(items.into_iter().collect())
+        }
+        Some("object") | None if depth < 4 => {
+            let mut object = Map::new();
+
+            for (name, property) in schema
+                .get("properties")
+                .and_then(Value::as_object)
+                .into_iter()
+                .flatten()
+            {
+                object.insert(
+                    name.clone(),
+                    sample_json(spec, resolve(spec, property), depth + 1),
+                );
+            }
+
+            Value::Object(object)
+        }
+        Some("string") => Value::from("test"),
+        _ => Value::Null,
+    }
+}
+
+/// percent-encode everything but unreserved characters
+fn encode(value: &str) -> String {
+    value
+        .bytes()
+        .map(|byte| match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => {
+                (byte as char).to_string()
+            }
+            _ => format!("%{byte:02X}"),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// an OpenAPI 3 spec with shared, referenced, and overridden parameters, and a json body
+    const OPENAPI: &str = r##"{
+  "openapi": "3.0.3",
+  "info": {"title": "test", "version": "1.0"},
+  "servers": [{"url": "https://api.example.com/v1/"}],
+  "paths": {
+    "/users": {
+      "get": {
+        "parameters": [
+          {"$ref": "#/components/parameters/limit"},
+          {"name": "q", "in": "query", "schema": {"type": "string", "example": "a b"}}
+        ]
+      },
+      "post": {
+        "requestBody": {
+          "content": {
+            "application/json": {
+              "schema": {"$ref": "#/components/schemas/User"}
+            }
+          }
+        }
+      }
+    },
+    "/users/{id}": {
+      "parameters": [{"name": "id", "in": "path", "required": true, "schema": {"type": "integer"}}],
+      "delete": {},
+      "get": {
+        "parameters": [{"name": "id", "in": "path", "schema": {"type": "string", "enum": ["me"]}}]
+      }
+    },
+    "/files/{name}": {"head": {}}
+  },
+  "components": {
+    "parameters": {
+      "limit": {"name": "limit", "in": "query", "schema": {"type": "integer", "default": 10}}
+    },
+    "schemas": {
+      "User": {
+        "type": "object",
+        "properties": {"name": {"type": "string", "example": "alice"}, "admin": {"type": "boolean"}}
+      }
+    }
+  }
+}"##;
+
+    /// a Swagger 2 spec with a base path, a body parameter, and form parameters
+    const SWAGGER: &str = r#"{
+  "swagger": "2.0",
+  "basePath": "/api",
+  "paths": {
+    "/login": {
+      "post": {
+        "parameters": [
+          {"name": "username", "in": "formData", "type": "string", "example": "admin"},
+          {"name": "remember", "in": "formData", "type": "boolean"}
+        ]
+      }
+    },
+    "/pets": {
+      "put": {
+        "parameters": [{"name": "pet", "in": "body", "schema": {"example": {"id": 7}}}]
+      }
+    }
+  }
+}"#;
+
+    #[test]
+    /// every operation is found, with parameters filled in and json bodies built from schemas
+    fn parse_spec_reads_openapi_operations() {
+        let operations = parse_spec(OPENAPI).unwrap();
+
+        let summary: Vec<_> = operations
+            .iter()
+            .map(|operation| format!("{} {}", operation.method, operation.path))
+            .collect();
+
+        assert_eq!(
+            summary,
+            vec![
+                "HEAD v1/files/1",
+                "GET v1/users?limit=10&q=a%20b",
+                "POST v1/users",
+                "GET v1/users/me",
+                "DELETE v1/users/1",
+            ]
+        );
+
+        assert_eq!(
+            operations[2].body.as_deref(),
+            Some(br#"{"admin":true,"name":"alice"}"#.as_slice())
+        );
+        assert!(operations[1].body.is_none());
+    }
+
+    #[test]
+    /// swagger 2 base paths, body parameters, and form parameters are all used
+    fn parse_spec_reads_swagger_operations() {
+        let operations = parse_spec(SWAGGER).unwrap();
+
+        assert_eq!(operations.len(), 2);
+        assert_eq!(operations[0].method, "POST");
+        assert_eq!(operations[0].path, "api/login");
+        assert_eq!(
+            operations[0].body.as_deref(),
+            Some(b"username=admin&remember=true".as_slice())
+        );
+        assert_eq!(operations[1].path, "api/pets");
+        assert_eq!(
+            operations[1].body.as_deref(),
+            Some(br#"{"id":7}"#.as_slice())
+        );
+    }
+
+    #[test]
+    /// things that aren't specs are rejected
+    fn parse_spec_rejects_other_documents() {
+        assert!(parse_spec("<html></html>").is_err());
+        assert!(parse_spec(r#"{"name": "package.json"}"#).is_err());
+    }
+}
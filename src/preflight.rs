@@ -0,0 +1,209 @@
+use std::{
+    fs::File,
+    io::{BufWriter, Write},
+};
+
+use anyhow::{Context, Result};
+use console::style;
+use serde::{Deserialize, Serialize};
+
+use crate::{statistics::StatError, traits::FeroxSerialize, utils::fmt_err};
+
+/// modes understood by --preflight
+///
+/// - warn: unreachable targets are skipped (default)
+/// - strict: any unreachable target stops the run
+/// - skip-tls-errors: targets failing due to tls errors are skipped, any other failure stops
+///   the run
+pub const PREFLIGHT_MODES: [&str; 3] = ["warn", "strict", "skip-tls-errors"];
+
+#[derive(Serialize, Deserialize, Default, Debug, Clone, PartialEq, Eq)]
+/// Outcome of the pre-scan connectivity check for a single target
+pub struct PreflightResult {
+    #[serde(rename = "type")]
+    /// Name of this type of struct, used for serialization, i.e. `{"type":"preflight"}`
+    pub(crate) kind: String,
+
+    /// The target that was checked
+    pub(crate) url: String,
+
+    /// whether or not the target responded
+    pub(crate) live: bool,
+
+    /// status code of the target's response, if it responded
+    pub(crate) status: Option<u16>,
+
+    /// number of requests made before the target responded or retries ran out
+    pub(crate) attempts: usize,
+
+    /// class of the last error, as given by `StatError::as_str`, if the target didn't respond
+    pub(crate) error: Option<String>,
+
+    /// the last error's message, if the target didn't respond
+    pub(crate) message: Option<String>,
+}
+
+/// implementation of PreflightResult
+impl PreflightResult {
+    /// create a PreflightResult for a target that responded with the given status code
+    pub fn live(url: &str, status: u16, attempts: usize) -> Self {
+        Self {
+            kind: String::from("preflight"),
+            url: url.to_string(),
+            live: true,
+            status: Some(status),
+            attempts,
+            ..Default::default()
+        }
+    }
+
+    /// create a PreflightResult for a target that never responded
+    pub fn dead(url: &str, error: StatError, message: &str, attempts: usize) -> Self {
+        Self {
+            kind: String::from("preflight"),
+            url: url.to_string(),
+            live: false,
+            attempts,
+            error: Some(error.as_str().to_string()),
+            message: Some(message.to_string()),
+            ..Default::default()
+        }
+    }
+
+    /// whether or not the target failed due to a tls error
+    pub fn is_tls_error(&self) -> bool {
+        self.error.as_deref() == Some(StatError::Tls.as_str())
+    }
+
+    /// whether or not, given the --preflight mode, this result should stop the run
+    pub fn is_fatal(&self, mode: &str) -> bool {
+        if self.live {
+            return false;
+        }
+
+        match mode {
+            "strict" => true,
+            "skip-tls-errors" => !self.is_tls_error(),
+            _ => false,
+        }
+    }
+}
+
+/// Implementation of PreflightResult
+impl FeroxSerialize for PreflightResult {
+    /// Create a string representation of the preflight result
+    ///
+    /// ex:
+    /// PRE     live https://localhost/ 200 (1 attempt)
+    /// PRE     dead https://otherhost/ tls (3 attempts)
+    fn as_str(&self) -> String {
+        let (status, detail) = if self.live {
+            (
+                style("live").green(),
+                self.status.unwrap_or_default().to_string(),
+            )
+        } else {
+            (style("dead").red(), self.error.clone().unwrap_or_default())
+        };
+
+        format!(
+            "{} {:>8} {} {} ({} attempt{})\n",
+            style("PRE").bg(console::Color::Cyan).black(),
+            status,
+            self.url,
+            detail,
+            self.attempts,
+            if self.attempts == 1 { "" } else { "s" }
+        )
+    }
+
+    /// Create an NDJSON representation of the preflight result
+    ///
+    /// (expanded for clarity)
+    /// ex:
+    /// {
+    ///   "type": "preflight",
+    ///   "url": "https://otherhost/",
+    ///   "live": false,
+    ///   "status": null,
+    ///   "attempts": 3,
+    ///   "error": "tls",
+    ///   "message": "error sending request for url (https://otherhost/)"
+    /// }\n
+    fn as_json(&self) -> Result<String> {
+        let mut json = serde_json::to_string(&self)
+            .with_context(|| fmt_err(&format!("Could not convert {} to JSON", self.url)))?;
+        json.push('\n');
+        Ok(json)
+    }
+}
+
+/// write every preflight result to --preflight-report as NDJSON, one target per line
+pub fn write_report(location: &str, results: &[PreflightResult]) -> Result<()> {
+    log::trace!("enter: write_report({}, {:?})", location, results);
+
+    let file =
+        File::create(location).with_context(|| fmt_err(&format!("Could not create {location}")))?;
+
+    let mut writer = BufWriter::new(file);
+
+    for result in results {
+        writer
+            .write_all(result.as_json()?.as_bytes())
+            .with_context(|| fmt_err(&format!("Could not write to {location}")))?;
+    }
+
+    writer
+        .flush()
+        .with_context(|| fmt_err(&format!("Could not write to {location}")))?;
+
+    log::trace!("exit: write_report");
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    /// live targets never stop the run, dead ones do depending on the mode and error
+    fn is_fatal_follows_preflight_mode() {
+        let live = PreflightResult::live("http://localhost/", 200, 1);
+        let tls = PreflightResult::dead("https://localhost/", StatError::Tls, "bad cert", 2);
+        let dns = PreflightResult::dead("http://nothere/", StatError::Dns, "no such host", 2);
+
+        for mode in PREFLIGHT_MODES {
+            assert!(!live.is_fatal(mode));
+        }
+
+        assert!(!tls.is_fatal("warn"));
+        assert!(!dns.is_fatal("warn"));
+        assert!(tls.is_fatal("strict"));
+        assert!(dns.is_fatal("strict"));
+        assert!(!tls.is_fatal("skip-tls-errors"));
+        assert!(dns.is_fatal("skip-tls-errors"));
+    }
+
+    #[test]
+    /// the report contains one json object per target
+    fn write_report_writes_ndjson() {
+        let tmp = tempfile::NamedTempFile::new().unwrap();
+        let location = tmp.path().to_str().unwrap();
+
+        let results = [
+            PreflightResult::live("http://localhost/", 200, 1),
+            PreflightResult::dead("http://nothere/", StatError::Dns, "no such host", 3),
+        ];
+
+        write_report(location, &results).unwrap();
+
+        let contents = std::fs::read_to_string(location).unwrap();
+        let parsed: Vec<PreflightResult> = contents
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+
+        assert_eq!(parsed, results);
+        assert_eq!(parsed[1].error.as_deref(), Some("dns"));
+    }
+}
@@ -1,14 +1,166 @@
-use indicatif::{MultiProgress, ProgressBar, ProgressDrawTarget, ProgressStyle};
+use std::{
+    sync::{
+        atomic::{AtomicBool, AtomicU16, AtomicU64, AtomicU8, Ordering},
+        Mutex,
+    },
+    time::{Duration, Instant},
+};
+
+use console::{user_attended, Term};
+use indicatif::{MultiProgress, ProgressBar, ProgressDrawTarget, ProgressStyle, WeakProgressBar};
 use lazy_static::lazy_static;
 
+use crate::theme::themed;
+
+/// default number of times per second that progress bars are redrawn
+pub const DEFAULT_BAR_REFRESH_HZ: u8 = 20;
+
+/// number of times per second that progress bars are redrawn, set via --bar-refresh-hz
+static BAR_REFRESH_HZ: AtomicU8 = AtomicU8::new(DEFAULT_BAR_REFRESH_HZ);
+
+/// whether progress bars have been disabled entirely, set via --no-bars
+static BARS_DISABLED: AtomicBool = AtomicBool::new(false);
+
+/// widest the `[####>---]` portion of a bar is ever drawn
+const MAX_BAR_WIDTH: u16 = 20;
+
+/// narrowest the `[####>---]` portion of a bar is ever drawn
+const MIN_BAR_WIDTH: u16 = 5;
+
+/// columns taken up by everything on a bar's line other than the bar itself (elapsed time,
+/// counts, rate, and a reasonably sized url)
+const BAR_RESERVED_COLUMNS: u16 = 80;
+
+/// current width of the `[####>---]` portion of each bar, based on the terminal's width
+static BAR_WIDTH: AtomicU16 = AtomicU16::new(MAX_BAR_WIDTH);
+
 lazy_static! {
     /// Global progress bar that houses other progress bars
-    pub static ref PROGRESS_BAR: MultiProgress = MultiProgress::with_draw_target(ProgressDrawTarget::stdout());
+    pub static ref PROGRESS_BAR: MultiProgress = MultiProgress::with_draw_target(draw_target());
 
     /// Global progress bar that is only used for printing messages that don't jack up other bars
     pub static ref PROGRESS_PRINTER: ProgressBar = add_bar("", 0, BarType::Hidden);
+
+    /// every visible bar along with its most recent style, so they can be restyled when the
+    /// terminal is resized
+    static ref STYLED_BARS: Mutex<Vec<(WeakProgressBar, BarType)>> = Mutex::new(Vec::new());
+}
+
+/// Set the number of times per second that `PROGRESS_BAR` is redrawn (minimum of 1)
+pub fn set_refresh_rate(hz: u8) {
+    BAR_REFRESH_HZ.store(hz.max(1), Ordering::Relaxed);
+    PROGRESS_BAR.set_draw_target(draw_target());
+}
+
+/// Disable progress bars for the remainder of the program's execution
+///
+/// bars created afterwards are hidden and never attached to `PROGRESS_BAR`, so they track their
+/// position without ever being drawn
+pub fn disable_bars() {
+    BARS_DISABLED.store(true, Ordering::Relaxed);
+    PROGRESS_BAR.set_draw_target(ProgressDrawTarget::hidden());
+}
+
+/// whether or not progress bars are drawn at all
+pub fn bars_enabled() -> bool {
+    !BARS_DISABLED.load(Ordering::Relaxed)
+}
+
+/// stdout draw target that respects the configured refresh rate (hidden when bars are disabled)
+pub fn draw_target() -> ProgressDrawTarget {
+    if !bars_enabled() {
+        return ProgressDrawTarget::hidden();
+    }
+
+    ProgressDrawTarget::stdout_with_hz(BAR_REFRESH_HZ.load(Ordering::Relaxed))
+}
+
+/// print a message above the progress bars, or straight to stdout when bars are disabled
+pub fn print_above_bars(msg: &str) {
+    let msg = themed(msg);
+
+    if bars_enabled() {
+        PROGRESS_PRINTER.println(msg);
+    } else if user_attended() {
+        println!("{msg}");
+    }
+}
+
+/// width of the `[####>---]` portion of a bar for a terminal that's `columns` wide
+fn bar_width(columns: u16) -> u16 {
+    columns
+        .saturating_sub(BAR_RESERVED_COLUMNS)
+        .clamp(MIN_BAR_WIDTH, MAX_BAR_WIDTH)
+}
+
+/// resize and redraw every bar to fit the terminal's current width
+///
+/// a resized terminal leaves the previous frame's (now re-wrapped) lines behind, so the bars
+/// are cleared and given a fresh draw target instead of waiting for new output to paper over
+/// the mess
+pub fn redraw_bars() {
+    if !bars_enabled() {
+        return;
+    }
+
+    let (_, columns) = Term::stdout().size();
+    BAR_WIDTH.store(bar_width(columns), Ordering::Relaxed);
+
+    let bars = match STYLED_BARS.lock() {
+        Ok(mut guard) => {
+            // drop bars that have since been dropped themselves
+            guard.retain(|(bar, _)| bar.upgrade().is_some());
+            guard.clone()
+        }
+        Err(_) => return,
+    };
+
+    for (bar, bar_type) in bars {
+        if let Some(bar) = bar.upgrade() {
+            bar.set_style(bar_style(bar_type));
+        }
+    }
+
+    let _ = PROGRESS_BAR.clear();
+    PROGRESS_BAR.set_draw_target(draw_target());
+}
+
+/// redraw the bars whenever the terminal is resized (SIGWINCH)
+///
+/// resizes typically arrive in bursts while a window is being dragged, so the redraw waits
+/// for the burst to settle
+#[cfg(unix)]
+pub async fn redraw_bars_on_resize() {
+    use tokio::{
+        signal::unix::{signal, SignalKind},
+        time::{sleep, Duration},
+    };
+
+    // bars created before the first resize should fit the terminal as well
+    let (_, columns) = Term::stdout().size();
+    BAR_WIDTH.store(bar_width(columns), Ordering::Relaxed);
+
+    let Ok(mut resizes) = signal(SignalKind::window_change()) else {
+        log::warn!("Could not listen for terminal resizes; bars won't be redrawn on resize");
+        return;
+    };
+
+    while resizes.recv().await.is_some() {
+        loop {
+            tokio::select! {
+                Some(_) = resizes.recv() => continue,
+                _ = sleep(Duration::from_millis(100)) => break,
+            }
+        }
+
+        redraw_bars();
+    }
 }
 
+/// terminal resizes aren't signalled on this platform; bars adapt on their next redraw
+#[cfg(not(unix))]
+pub async fn redraw_bars_on_resize() {}
+
 /// Types of ProgressBars that can be added to `PROGRESS_BAR`
 #[derive(Copy, Clone)]
 pub enum BarType {
@@ -31,6 +183,12 @@ pub enum BarType {
 /// Add an [indicatif::ProgressBar](https://docs.rs/indicatif/latest/indicatif/struct.ProgressBar.html)
 /// to the global [PROGRESS_BAR](../config/struct.PROGRESS_BAR.html)
 pub fn add_bar(prefix: &str, length: u64, bar_type: BarType) -> ProgressBar {
+    if !bars_enabled() {
+        return ProgressBar::hidden()
+            .with_prefix(prefix.to_string())
+            .with_length(length);
+    }
+
     let pb = ProgressBar::new(length).with_prefix(prefix.to_string());
 
     update_style(&pb, bar_type);
@@ -40,6 +198,21 @@ pub fn add_bar(prefix: &str, length: u64, bar_type: BarType) -> ProgressBar {
 
 /// Update the style of a progress bar based on the `BarType`
 pub fn update_style(bar: &ProgressBar, bar_type: BarType) {
+    bar.set_style(bar_style(bar_type));
+
+    if matches!(bar_type, BarType::Hidden) || !bars_enabled() {
+        return;
+    }
+
+    if let Ok(mut guard) = STYLED_BARS.lock() {
+        guard.push((bar.downgrade(), bar_type));
+    }
+}
+
+/// style for the given `BarType`, sized to the current `BAR_WIDTH`
+fn bar_style(bar_type: BarType) -> ProgressStyle {
+    let width = BAR_WIDTH.load(Ordering::Relaxed);
+
     let mut style = ProgressStyle::default_bar().progress_chars("#>-").with_key(
         "smoothed_per_sec",
         |state: &indicatif::ProgressState, w: &mut dyn std::fmt::Write| match (
@@ -61,21 +234,83 @@ pub fn update_style(bar: &ProgressBar, bar_type: BarType) {
     style = match bar_type {
         BarType::Hidden => style.template("").unwrap(),
         BarType::Default => style
-            .template("[{bar:.cyan/blue}] - {elapsed:<4} {pos:>7}/{len:7} {smoothed_per_sec:7} {prefix} {msg}")
+            .template(&format!("[{{bar:{width}.cyan/blue}}] - {{elapsed:<4}} {{pos:>7}}/{{len:7}} {{smoothed_per_sec:7}} {{prefix}} {{msg}}"))
             .unwrap(),
         BarType::Message => style
             .template(&format!(
-            "[{{bar:.cyan/blue}}] - {{elapsed:<4}} {{pos:>7}}/{{len:7}} {:7} {{prefix}} {{msg}}",
+            "[{{bar:{width}.cyan/blue}}] - {{elapsed:<4}} {{pos:>7}}/{{len:7}} {:7} {{prefix}} {{msg}}",
             "-"
         ))
             .unwrap(),
         BarType::Total => style
-            .template("[{bar:.yellow/blue}] - {elapsed:<4} {pos:>7}/{len:7} {eta:7} {msg}")
+            .template(&format!("[{{bar:{width}.yellow/blue}}] - {{elapsed:<4}} {{pos:>7}}/{{len:7}} {{eta:7}} {{msg}}"))
             .unwrap(),
         BarType::Quiet => style.template("Scanning: {prefix}").unwrap(),
     };
 
-    bar.set_style(style);
+    style
+}
+
+/// Wrapper around a progress bar that applies increments at most once per refresh interval
+/// (--bar-refresh-hz), instead of on every call; anything still pending is applied by `flush`
+/// or when the wrapper is dropped
+pub struct BatchedBar {
+    /// the wrapped bar
+    bar: ProgressBar,
+
+    /// increments received since the bar was last updated
+    pending: AtomicU64,
+
+    /// when the bar was last updated
+    flushed: Mutex<Instant>,
+
+    /// minimum amount of time between updates to the bar
+    interval: Duration,
+}
+
+impl BatchedBar {
+    /// wrap the given bar, using the refresh rate set by `set_refresh_rate`
+    pub fn new(bar: ProgressBar) -> Self {
+        let hz = BAR_REFRESH_HZ.load(Ordering::Relaxed).max(1);
+
+        Self {
+            bar,
+            pending: AtomicU64::new(0),
+            flushed: Mutex::new(Instant::now()),
+            interval: Duration::from_secs_f64(1.0 / f64::from(hz)),
+        }
+    }
+
+    /// record an increment, updating the bar if a refresh interval has passed since it was last
+    /// updated
+    pub fn inc(&self, delta: u64) {
+        self.pending.fetch_add(delta, Ordering::Relaxed);
+
+        // whoever holds the lock is already updating the bar
+        let Ok(mut flushed) = self.flushed.try_lock() else {
+            return;
+        };
+
+        if flushed.elapsed() >= self.interval {
+            *flushed = Instant::now();
+            self.flush();
+        }
+    }
+
+    /// apply any pending increments to the bar
+    pub fn flush(&self) {
+        let pending = self.pending.swap(0, Ordering::Relaxed);
+
+        if pending > 0 {
+            self.bar.inc(pending);
+        }
+    }
+}
+
+impl Drop for BatchedBar {
+    fn drop(&mut self) {
+        self.flush();
+    }
 }
 
 #[cfg(test)]
@@ -100,4 +335,45 @@ mod tests {
         assert!(p3.is_finished());
         assert!(p4.is_finished());
     }
+
+    #[test]
+    /// bars shrink on narrow terminals, but never past the min/max widths
+    fn bar_width_is_clamped() {
+        assert_eq!(bar_width(0), MIN_BAR_WIDTH);
+        assert_eq!(bar_width(BAR_RESERVED_COLUMNS + 10), 10);
+        assert_eq!(bar_width(500), MAX_BAR_WIDTH);
+    }
+
+    #[test]
+    /// a refresh rate of 0 would never redraw; it's bumped up to 1
+    fn set_refresh_rate_enforces_minimum() {
+        set_refresh_rate(0);
+        assert_eq!(BAR_REFRESH_HZ.load(Ordering::Relaxed), 1);
+
+        set_refresh_rate(DEFAULT_BAR_REFRESH_HZ);
+        assert_eq!(
+            BAR_REFRESH_HZ.load(Ordering::Relaxed),
+            DEFAULT_BAR_REFRESH_HZ
+        );
+    }
+
+    #[test]
+    /// increments made within a refresh interval are held until the bar is flushed or dropped
+    fn batched_bar_applies_increments_on_flush_and_drop() {
+        let bar = ProgressBar::hidden();
+        bar.set_length(10);
+
+        let batched = BatchedBar::new(bar.clone());
+        batched.inc(2);
+        batched.inc(3);
+
+        assert_eq!(bar.position(), 0);
+
+        batched.flush();
+        assert_eq!(bar.position(), 5);
+
+        batched.inc(1);
+        drop(batched);
+        assert_eq!(bar.position(), 6);
+    }
 }
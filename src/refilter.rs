@@ -0,0 +1,281 @@
+//! applying a new set of filters to a previous run's results, without scanning again, see
+//! --refilter
+use std::{
+    fs::File,
+    io::{stdout, BufWriter, Write},
+};
+
+use anyhow::{bail, Context, Result};
+use serde_json::Value;
+
+use crate::{
+    config::Configuration,
+    export::{self, format_meta},
+    filters::{FeroxFilters, LinesFilter, RegexFilter, SizeFilter, StatusCodeFilter, WordsFilter},
+    report,
+    response::FeroxResponse,
+    sarif::SarifLog,
+    scanner::RESPONSES,
+    statistics::Stats,
+    traits::FeroxSerialize,
+    utils::fmt_err,
+};
+
+/// read the responses from the --refilter file, drop those matched by the filters given on the
+/// command line, and write the rest to --output (or stdout), along with any --export-urls,
+/// --emit-ffuf-json, --report-html, --report-markdown, --report-xml, or --tree
+///
+/// --sarif and --csv change the format written to --output, the same as they do for a scan
+///
+/// returns the number of responses kept and the number read
+pub fn refilter(config: &Configuration) -> Result<(usize, usize)> {
+    log::trace!("enter: refilter({})", config.refilter);
+
+    let contents = std::fs::read_to_string(&config.refilter)
+        .with_context(|| fmt_err(&format!("Could not read {}", config.refilter)))?;
+
+    let responses = parse_responses(&contents);
+
+    if responses.is_empty() {
+        bail!("Did not find any responses in {}", config.refilter);
+    }
+
+    let filters = offline_filters(config)?;
+    let total = responses.len();
+
+    let kept: Vec<_> = responses
+        .into_iter()
+        .filter(|response| filters.explain(response).is_empty())
+        .collect();
+
+    let mut writer: Box<dyn Write> = if config.output.is_empty() {
+        Box::new(stdout())
+    } else {
+        let file = File::create(&config.output)
+            .with_context(|| fmt_err(&format!("Could not create {}", config.output)))?;
+        Box::new(BufWriter::new(file))
+    };
+
+    write_responses(config, &kept, &mut writer)?;
+    writer.flush()?;
+
+    let kept_count = kept.len();
+
+    // the reports are written from the same place a scan's are
+    for response in kept {
+        RESPONSES.insert(response);
+    }
+
+    // a state file's statistics are carried over into the html/markdown reports; --json output
+    // doesn't have them in a form that can be read back, so those reports show none
+    let stats = Stats::new(config.json);
+
+    if let Err(e) = stats.merge_from(&config.refilter) {
+        log::debug!("no statistics read from {}: {}", config.refilter, e);
+    }
+
+    export::export_urls(config)?;
+    export::emit_ffuf_json(config)?;
+    report::print_tree(config)?;
+    report::write_html_report(config, &stats)?;
+    report::write_markdown_report(config, &stats)?;
+    report::write_xml_report(config)?;
+
+    log::trace!("exit: refilter -> ({}, {})", kept_count, total);
+    Ok((kept_count, total))
+}
+
+/// write the kept responses in the format a scan would've written them to --output
+fn write_responses(
+    config: &Configuration,
+    responses: &[FeroxResponse],
+    writer: &mut dyn Write,
+) -> Result<()> {
+    if config.sarif {
+        let mut log = SarifLog::new(&config.run_id, &config.meta);
+
+        for response in responses {
+            log.add(response);
+        }
+
+        writer.write_all(log.to_json()?.as_bytes())?;
+        return Ok(());
+    }
+
+    if config.csv {
+        writer.write_all(format_meta(&config.meta).as_bytes())?;
+        writer.write_all(format!("{}\n", config.csv_columns.join(",")).as_bytes())?;
+
+        for row in responses
+            .iter()
+            .filter_map(|response| response.as_csv(&config.csv_columns))
+        {
+            writer.write_all(row.as_bytes())?;
+        }
+
+        return Ok(());
+    }
+
+    for response in responses {
+        let line = if config.json {
+            response.as_json()?
+        } else {
+            console::strip_ansi_codes(&response.as_str()).to_string()
+        };
+
+        writer.write_all(line.as_bytes())?;
+    }
+
+    Ok(())
+}
+
+/// responses from either a state file (--resume-from) or --json output; anything else in the
+/// file is skipped
+fn parse_responses(contents: &str) -> Vec<FeroxResponse> {
+    if let Ok(Value::Object(state)) = serde_json::from_str::<Value>(contents) {
+        // a state file is a single json object, with the responses in an array
+        return state
+            .get("responses")
+            .and_then(Value::as_array)
+            .into_iter()
+            .flatten()
+            .filter_map(|response| serde_json::from_value(response.clone()).ok())
+            .collect();
+    }
+
+    contents
+        .lines()
+        .filter_map(|line| serde_json::from_str::<Value>(line).ok())
+        .filter(|record| record["type"] == "response")
+        .filter_map(|record| serde_json::from_value(record).ok())
+        .collect()
+}
+
+/// the filters given on the command line that don't need to make requests; --filter-similar-to
+/// compares against a live page, so it's left out
+fn offline_filters(config: &Configuration) -> Result<FeroxFilters> {
+    let filters = FeroxFilters::default();
+
+    for code in &config.filter_status {
+        filters.push(Box::new(StatusCodeFilter { filter_code: *code }))?;
+    }
+
+    for lines in &config.filter_line_count {
+        filters.push(Box::new(LinesFilter { line_count: *lines }))?;
+    }
+
+    for words in &config.filter_word_count {
+        filters.push(Box::new(WordsFilter { word_count: *words }))?;
+    }
+
+    for size in &config.filter_size {
+        filters.push(Box::new(SizeFilter {
+            content_length: *size,
+        }))?;
+    }
+
+    for regex in &config.filter_regex {
+        filters.push(Box::new(RegexFilter::new(regex)?))?;
+    }
+
+    if !config.filter_similar.is_empty() {
+        log::warn!("--filter-similar-to needs to request its page, and isn't used by --refilter");
+    }
+
+    if filters.filters.read().map(|f| f.is_empty()).unwrap_or(true) {
+        bail!("--refilter needs at least one of -C, -N, -W, -S, or -X");
+    }
+
+    Ok(filters)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// a response record, as written by --json
+    fn record(url: &str, status: u16, content_length: u64) -> String {
+        format!(
+            r#"{{"type":"response","url":"{url}","original_url":"{url}","path":"/","wildcard":false,"status":{status},"method":"GET","content_length":{content_length},"line_count":1,"word_count":2,"headers":{{}},"extension":""}}"#
+        )
+    }
+
+    #[test]
+    /// response records are read from both --json output and state files
+    fn parse_responses_reads_ndjson_and_state_files() {
+        let ndjson = format!(
+            "{}\n{}\n{}\nnot json\n",
+            r#"{"type":"configuration","target_url":"http://localhost"}"#,
+            record("http://localhost/a", 200, 10),
+            record("http://localhost/b", 403, 20),
+        );
+
+        let responses = parse_responses(&ndjson);
+        assert_eq!(responses.len(), 2);
+        assert_eq!(responses[1].url().as_str(), "http://localhost/b");
+
+        let state = format!(
+            r#"{{"scans":[],"config":{{}},"responses":[{}]}}"#,
+            record("http://localhost/c", 200, 30)
+        );
+
+        let responses = parse_responses(&state);
+        assert_eq!(responses.len(), 1);
+        assert_eq!(responses[0].content_length(), 30);
+    }
+
+    #[test]
+    /// filters from the command line are applied, and at least one is required
+    fn offline_filters_match_given_filters() {
+        let config = Configuration {
+            filter_size: vec![10],
+            filter_status: vec![403],
+            ..Default::default()
+        };
+
+        let filters = offline_filters(&config).unwrap();
+
+        let responses = parse_responses(&format!(
+            "{}\n{}\n{}\n",
+            record("http://localhost/a", 200, 10),
+            record("http://localhost/b", 403, 20),
+            record("http://localhost/c", 200, 30),
+        ));
+
+        let kept: Vec<_> = responses
+            .iter()
+            .filter(|response| filters.explain(response).is_empty())
+            .map(|response| response.url().path().to_string())
+            .collect();
+
+        assert_eq!(kept, vec!["/c"]);
+
+        assert!(offline_filters(&Configuration::default()).is_err());
+    }
+
+    #[test]
+    /// --sarif and --csv change what the kept responses are written as
+    fn write_responses_honors_sarif_and_csv() {
+        let responses = parse_responses(&record("http://localhost/a", 200, 10));
+
+        let write = |config: Configuration| {
+            let mut written = Vec::new();
+            write_responses(&config, &responses, &mut written).unwrap();
+            String::from_utf8(written).unwrap()
+        };
+
+        let sarif = write(Configuration {
+            sarif: true,
+            ..Default::default()
+        });
+        let sarif: Value = serde_json::from_str(&sarif).unwrap();
+        assert_eq!(sarif["runs"][0]["results"].as_array().unwrap().len(), 1);
+
+        let csv = write(Configuration {
+            csv: true,
+            csv_columns: vec!["url".to_string(), "status".to_string()],
+            ..Default::default()
+        });
+        assert_eq!(csv, "url,status\nhttp://localhost/a,200\n");
+    }
+}
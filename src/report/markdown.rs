@@ -0,0 +1,267 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    fmt::Write as _,
+    fs::File,
+    io::{BufWriter, Write},
+};
+
+use anyhow::{bail, Context, Result};
+use reqwest::header::LOCATION;
+
+use crate::{
+    config::Configuration,
+    response::FeroxResponse,
+    scanner::RESPONSES,
+    statistics::Stats,
+    utils::{fmt_err, parent_directory},
+};
+
+use super::render_tree;
+
+/// response headers that tend to give away the technologies behind a target, in the order
+/// they're listed in the report
+const NOTABLE_HEADERS: [&str; 9] = [
+    "server",
+    "x-powered-by",
+    "x-aspnet-version",
+    "x-aspnetmvc-version",
+    "x-generator",
+    "x-backend-server",
+    "via",
+    "x-cache",
+    "access-control-allow-origin",
+];
+
+/// write a markdown summary of the scan's findings, grouped by target, to --report-markdown
+///
+/// called at the same point as `write_html_report`, once all responses have made their way
+/// into `RESPONSES` and `stats` is final
+pub fn write_markdown_report(config: &Configuration, stats: &Stats) -> Result<()> {
+    log::trace!("enter: write_markdown_report({})", config.report_markdown);
+
+    if config.report_markdown.is_empty() {
+        log::trace!("exit: write_markdown_report");
+        return Ok(());
+    }
+
+    let responses = match RESPONSES.responses.read() {
+        Ok(guard) => guard
+            .iter()
+            .filter(|response| !response.wildcard())
+            .cloned()
+            .collect::<Vec<_>>(),
+        Err(_) => bail!(fmt_err("Could not read discovered responses for report")),
+    };
+
+    let file = File::create(&config.report_markdown)
+        .with_context(|| fmt_err(&format!("Could not create {}", config.report_markdown)))?;
+
+    let mut writer = BufWriter::new(file);
+
+    writer
+        .write_all(
+            render_markdown(&config.run_id, &config.meta, stats.requests(), &responses).as_bytes(),
+        )
+        .and_then(|_| writer.flush())
+        .with_context(|| fmt_err(&format!("Could not write to {}", config.report_markdown)))?;
+
+    log::info!(
+        "wrote markdown report covering {} finding(s) to {}",
+        responses.len(),
+        config.report_markdown
+    );
+
+    log::trace!("exit: write_markdown_report");
+    Ok(())
+}
+
+/// the full report; one section per target (scheme://host:port), in sorted order
+fn render_markdown(
+    run_id: &str,
+    meta: &HashMap<String, String>,
+    requests: usize,
+    responses: &[FeroxResponse],
+) -> String {
+    let mut targets: BTreeMap<String, Vec<&FeroxResponse>> = BTreeMap::new();
+
+    for response in responses {
+        targets
+            .entry(response.url().origin().ascii_serialization())
+            .or_default()
+            .push(response);
+    }
+
+    let mut report = String::from("# feroxbuster report\n\n");
+
+    if !run_id.is_empty() {
+        let _ = writeln!(report, "- **Run ID:** `{run_id}`");
+    }
+
+    let mut pairs: Vec<_> = meta.iter().collect();
+    pairs.sort();
+
+    for (key, value) in pairs {
+        let _ = writeln!(report, "- **{key}:** {value}");
+    }
+
+    let _ = writeln!(report, "- **Requests:** {requests}");
+    let _ = writeln!(report, "- **Findings:** {}", responses.len());
+    let _ = writeln!(report, "- **Targets:** {}\n", targets.len());
+
+    if targets.is_empty() {
+        report.push_str("No findings.\n");
+    }
+
+    for (target, findings) in &targets {
+        render_target(&mut report, target, findings);
+    }
+
+    report
+}
+
+/// status breakdown, findings by directory, a tree of the findings, and notable headers for a
+/// single target
+fn render_target(report: &mut String, target: &str, findings: &[&FeroxResponse]) {
+    let _ = writeln!(report, "## {target}\n");
+
+    let mut statuses: BTreeMap<u16, usize> = BTreeMap::new();
+    let mut directories: BTreeMap<String, Vec<&FeroxResponse>> = BTreeMap::new();
+    let mut headers: BTreeMap<(usize, String), usize> = BTreeMap::new();
+
+    for response in findings {
+        *statuses.entry(response.status().as_u16()).or_default() += 1;
+
+        directories
+            .entry(parent_directory(response.url()))
+            .or_default()
+            .push(response);
+
+        for (position, name) in NOTABLE_HEADERS.iter().enumerate() {
+            if let Some(value) = response
+                .headers()
+                .get(*name)
+                .and_then(|value| value.to_str().ok())
+            {
+                *headers.entry((position, value.to_string())).or_default() += 1;
+            }
+        }
+    }
+
+    report.push_str("### Status Codes\n\n| Status | Count |\n| --- | ---: |\n");
+
+    for (status, count) in &statuses {
+        let _ = writeln!(report, "| {status} | {count} |");
+    }
+
+    report.push_str("\n### Findings\n\n");
+
+    for (directory, responses) in &directories {
+        let _ = writeln!(report, "#### `{directory}`\n");
+        report.push_str("| Status | Method | Lines | Words | Size | URL | Redirect |\n");
+        report.push_str("| --- | --- | ---: | ---: | ---: | --- | --- |\n");
+
+        for response in responses {
+            let location = response
+                .headers()
+                .get(LOCATION)
+                .and_then(|value| value.to_str().ok())
+                .unwrap_or_default();
+
+            let _ = writeln!(
+                report,
+                "| {} | {} | {} | {} | {} | {} | {} |",
+                response.status().as_u16(),
+                response.method(),
+                response.line_count(),
+                response.word_count(),
+                response.content_length(),
+                markdown_cell(response.url().as_str()),
+                markdown_cell(location),
+            );
+        }
+
+        report.push('\n');
+    }
+
+    let _ = writeln!(
+        report,
+        "### Tree\n\n```text\n{}```\n",
+        render_tree(findings.iter().copied())
+    );
+
+    if !headers.is_empty() {
+        report.push_str(
+            "### Notable Headers\n\n| Header | Value | Responses |\n| --- | --- | ---: |\n",
+        );
+
+        for ((position, value), count) in &headers {
+            let _ = writeln!(
+                report,
+                "| {} | {} | {count} |",
+                NOTABLE_HEADERS[*position],
+                markdown_cell(value)
+            );
+        }
+
+        report.push('\n');
+    }
+}
+
+/// escape a value so it stays within its table cell; empty values are left empty
+fn markdown_cell(value: &str) -> String {
+    if value.is_empty() {
+        return String::new();
+    }
+
+    format!("`{}`", value.replace('|', "\\|").replace('`', "'"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::response::fixtures::response;
+    use reqwest::header::HeaderValue;
+
+    #[test]
+    /// findings are split by target, then grouped by directory, with headers tallied
+    fn render_markdown_groups_by_target_and_directory() {
+        let responses = [
+            ("http://localhost/admin/login", 200, "nginx"),
+            ("http://localhost/admin/users", 403, "nginx"),
+            ("http://localhost/index.html", 200, "nginx"),
+            ("https://other.local/api|v1", 500, "Apache"),
+        ]
+        .map(|(url, status, server)| {
+            let mut response = response(url, status);
+            response
+                .headers_mut()
+                .insert("server", HeaderValue::from_static(server));
+            response
+        });
+
+        let meta = HashMap::from([(String::from("engagement"), String::from("1337"))]);
+        let report = render_markdown("abc", &meta, 40, &responses);
+
+        assert!(report.contains("- **Run ID:** `abc`"));
+        assert!(report.contains("- **engagement:** 1337"));
+        assert!(report.contains("- **Requests:** 40"));
+        assert!(report.contains("- **Targets:** 2"));
+        assert!(report.contains("## http://localhost\n"));
+        assert!(report.contains("## https://other.local\n"));
+        assert!(report.contains("#### `http://localhost/admin/`"));
+        assert!(report.contains("| 200 | 2 |"));
+        assert!(report.contains("| server | `nginx` | 3 |"));
+        assert!(report.contains("| server | `Apache` | 1 |"));
+        assert!(report.contains("`https://other.local/api\\|v1`"));
+        assert!(report.contains("```text\nhttp://localhost\n├── admin/\n│   ├── login [200]\n"));
+    }
+
+    #[test]
+    /// a scan without findings still produces a report
+    fn render_markdown_without_findings() {
+        let report = render_markdown("", &HashMap::new(), 10, &[]);
+
+        assert!(!report.contains("Run ID"));
+        assert!(report.contains("No findings."));
+    }
+}
@@ -0,0 +1,250 @@
+(function () {
+  "use strict";
+
+  var SVG_NS = "http://www.w3.org/2000/svg";
+  var data = JSON.parse(document.getElementById("report-data").textContent);
+
+  function el(tag, attrs, text) {
+    var node = document.createElement(tag);
+    Object.keys(attrs || {}).forEach(function (key) {
+      node.setAttribute(key, attrs[key]);
+    });
+    if (text !== undefined) {
+      node.textContent = text;
+    }
+    return node;
+  }
+
+  function svg(tag, attrs, text) {
+    var node = document.createElementNS(SVG_NS, tag);
+    Object.keys(attrs || {}).forEach(function (key) {
+      node.setAttribute(key, attrs[key]);
+    });
+    if (text !== undefined) {
+      node.textContent = text;
+    }
+    return node;
+  }
+
+  function statusClass(status) {
+    var family = Math.floor(status / 100);
+    return family >= 2 && family <= 5 ? "s" + family + "xx" : "sother";
+  }
+
+  function empty(container) {
+    container.appendChild(el("p", { class: "empty" }, "No findings"));
+  }
+
+  function renderSummary() {
+    var stats = data.stats || {};
+    var runtime = (stats.total_runtime || [])[0];
+    var findings = data.timeline.length;
+    var entries = [
+      ["Requests", stats.requests],
+      ["Errors", stats.errors],
+      ["Findings", findings],
+      ["Directories Scanned", stats.total_scans],
+      ["Filtered", (stats.responses_filtered || 0) + (stats.wildcards_filtered || 0)],
+      ["Runtime (secs)", runtime === undefined ? "-" : runtime.toFixed(1)]
+    ];
+    var container = document.getElementById("summary");
+
+    entries.forEach(function (entry) {
+      var stat = el("div", { class: "stat" });
+      stat.appendChild(el("div", { class: "value" }, String(entry[1] === undefined ? 0 : entry[1])));
+      stat.appendChild(el("div", { class: "label" }, entry[0]));
+      container.appendChild(stat);
+    });
+
+    document.getElementById("targets").textContent = (stats.targets || []).join(", ");
+
+    if (data.run_id) {
+      document.getElementById("run-id").textContent = "Run ID: " + data.run_id;
+    }
+
+    var meta = Object.keys(data.meta || {}).map(function (key) { return key + "=" + data.meta[key]; });
+
+    if (meta.length) {
+      document.getElementById("meta").textContent = meta.join(", ");
+    }
+  }
+
+  function renderStatuses() {
+    var container = document.getElementById("statuses");
+    var codes = Object.keys(data.statuses);
+
+    if (!codes.length) {
+      return empty(container);
+    }
+
+    var max = Math.max.apply(null, codes.map(function (code) { return data.statuses[code]; }));
+    var barHeight = 22;
+    var width = 800;
+    var labelWidth = 50;
+    var chart = svg("svg", { viewBox: "0 0 " + width + " " + codes.length * (barHeight + 6) });
+
+    codes.forEach(function (code, index) {
+      var count = data.statuses[code];
+      var y = index * (barHeight + 6);
+      var barWidth = Math.max(1, (count / max) * (width - labelWidth - 60));
+
+      chart.appendChild(svg("text", { x: 0, y: y + barHeight * 0.7 }, code));
+      chart.appendChild(svg("rect", {
+        x: labelWidth, y: y, width: barWidth, height: barHeight, class: statusClass(Number(code))
+      }));
+      chart.appendChild(svg("text", { x: labelWidth + barWidth + 6, y: y + barHeight * 0.7 }, String(count)));
+    });
+
+    container.appendChild(chart);
+  }
+
+  function renderTimeline() {
+    var container = document.getElementById("timeline");
+    var points = data.timeline;
+
+    if (!points.length) {
+      return empty(container);
+    }
+
+    var width = 800;
+    var height = 240;
+    var pad = 40;
+    var span = Math.max(points[points.length - 1].offset, 1);
+    var chart = svg("svg", { viewBox: "0 0 " + width + " " + height });
+
+    function x(offset) { return pad + (offset / span) * (width - 2 * pad); }
+    function y(count) { return height - pad - (count / points.length) * (height - 2 * pad); }
+
+    chart.appendChild(svg("line", { x1: pad, y1: height - pad, x2: width - pad, y2: height - pad, class: "axis" }));
+    chart.appendChild(svg("line", { x1: pad, y1: pad, x2: pad, y2: height - pad, class: "axis" }));
+    chart.appendChild(svg("text", { x: pad, y: height - pad + 16 }, "0s"));
+    chart.appendChild(svg("text", { x: width - pad - 30, y: height - pad + 16 }, span.toFixed(1) + "s"));
+    chart.appendChild(svg("text", { x: 4, y: pad + 4 }, String(points.length)));
+
+    var path = "M " + x(0) + " " + y(0);
+    points.forEach(function (point, index) {
+      path += " L " + x(point.offset) + " " + y(index) + " L " + x(point.offset) + " " + y(index + 1);
+    });
+    chart.appendChild(svg("path", { d: path, class: "line" }));
+
+    points.forEach(function (point, index) {
+      var dot = svg("circle", { cx: x(point.offset), cy: y(index + 1), r: 3, class: statusClass(point.status) });
+      dot.appendChild(svg("title", {}, point.status + " " + point.url + " (+" + point.offset.toFixed(2) + "s)"));
+      chart.appendChild(dot);
+    });
+
+    container.appendChild(chart);
+  }
+
+  // table whose rows are sorted by clicking a column's header, and filtered by a text box
+  // that matches against every cell in the row; `textColumn` holds strings and sorts ascending
+  // on first click, all other columns are numeric and sort descending on first click
+  function sortableTable(container, columns, rows, value, textColumn) {
+    if (!rows.length) {
+      return empty(container);
+    }
+
+    var filter = el("input", { type: "search", class: "filter", placeholder: "filter" });
+    var table = el("table");
+    var head = el("tr");
+    var body = el("tbody");
+    var sortKey = textColumn;
+    var ascending = true;
+
+    function fill() {
+      var needle = filter.value.toLowerCase();
+
+      rows.sort(function (a, b) {
+        var left = value(a, sortKey);
+        var right = value(b, sortKey);
+        var order = left < right ? -1 : left > right ? 1 : 0;
+        return ascending ? order : -order;
+      });
+
+      body.textContent = "";
+      rows.forEach(function (row) {
+        var cells = columns.map(function (column) { return String(value(row, column)); });
+
+        if (needle && !cells.some(function (cell) { return cell.toLowerCase().indexOf(needle) !== -1; })) {
+          return;
+        }
+
+        var tr = el("tr");
+        cells.forEach(function (cell, index) {
+          tr.appendChild(el("td", columns[index] === textColumn ? { class: "url" } : {}, cell));
+        });
+        body.appendChild(tr);
+      });
+    }
+
+    columns.forEach(function (column) {
+      var th = el("th", {}, column);
+      th.addEventListener("click", function () {
+        ascending = sortKey === column ? !ascending : column === textColumn;
+        sortKey = column;
+        fill();
+      });
+      head.appendChild(th);
+    });
+
+    filter.addEventListener("input", fill);
+
+    var thead = el("thead");
+    thead.appendChild(head);
+    table.appendChild(thead);
+    table.appendChild(body);
+    fill();
+
+    container.appendChild(filter);
+    container.appendChild(table);
+  }
+
+  function renderFindings() {
+    var columns = ["url", "status", "method", "lines", "words", "chars", "location"];
+
+    sortableTable(document.getElementById("findings"), columns, data.findings.slice(), function (row, column) {
+      return row[column];
+    }, "url");
+  }
+
+  function renderDirectories() {
+    var columns = ["directory", "findings"].concat(Object.keys(data.statuses));
+
+    sortableTable(document.getElementById("directories"), columns, data.directories.slice(), function (row, column) {
+      if (column === "directory" || column === "findings") {
+        return row[column];
+      }
+      return row.statuses[column] || 0;
+    }, "directory");
+  }
+
+  function renderTree() {
+    var container = document.getElementById("tree");
+
+    if (!data.tree) {
+      empty(container);
+      return;
+    }
+
+    container.appendChild(el("pre", { class: "tree" }, data.tree));
+  }
+
+  function renderConfiguration() {
+    var rows = Object.keys(data.config || {}).map(function (key) {
+      var setting = data.config[key];
+      return { setting: key, value: typeof setting === "string" ? setting : JSON.stringify(setting) };
+    });
+
+    sortableTable(document.getElementById("configuration"), ["setting", "value"], rows, function (row, column) {
+      return row[column];
+    }, "setting");
+  }
+
+  renderSummary();
+  renderStatuses();
+  renderTimeline();
+  renderFindings();
+  renderDirectories();
+  renderTree();
+  renderConfiguration();
+})();
@@ -0,0 +1,169 @@
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fmt::Write as _,
+};
+
+use crate::response::FeroxResponse;
+
+/// a single path segment in the tree, along with the status codes of any findings that ended
+/// there and the segments found beneath it
+#[derive(Debug, Default)]
+struct Node {
+    /// status codes of the findings at this path; empty for directories that were only
+    /// discovered by way of their children
+    statuses: BTreeSet<u16>,
+
+    /// whether a finding at this path ended in a slash
+    directory: bool,
+
+    /// segments beneath this one, in sorted order
+    children: BTreeMap<String, Node>,
+}
+
+/// implementation of Node
+impl Node {
+    /// walk the given segments, creating nodes as needed, and record the status on the last one
+    fn insert(&mut self, segments: &[&str], status: u16) {
+        match segments {
+            [] => {
+                self.statuses.insert(status);
+            }
+            [""] => {
+                // trailing slash, i.e. /admin/
+                self.directory = true;
+                self.statuses.insert(status);
+            }
+            [segment, rest @ ..] => self
+                .children
+                .entry(segment.to_string())
+                .or_default()
+                .insert(rest, status),
+        }
+    }
+
+    /// `name`, with a trailing slash for directories, followed by any status codes
+    fn label(&self, name: &str) -> String {
+        let slash = if self.directory || !self.children.is_empty() {
+            "/"
+        } else {
+            ""
+        };
+
+        format!("{name}{slash}{}", self.status_suffix())
+    }
+
+    /// the status codes seen at this path, i.e. ` [200, 301]`, or nothing if there weren't any
+    fn status_suffix(&self) -> String {
+        if self.statuses.is_empty() {
+            return String::new();
+        }
+
+        let statuses = self
+            .statuses
+            .iter()
+            .map(u16::to_string)
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        format!(" [{statuses}]")
+    }
+
+    /// write each child on its own line, drawing the branches that lead to it
+    fn render_children(&self, tree: &mut String, prefix: &str) {
+        let last = self.children.len().saturating_sub(1);
+
+        for (position, (name, child)) in self.children.iter().enumerate() {
+            let (branch, continuation) = if position == last {
+                ("└── ", "    ")
+            } else {
+                ("├── ", "│   ")
+            };
+
+            let _ = writeln!(tree, "{prefix}{branch}{}", child.label(name));
+
+            child.render_children(tree, &format!("{prefix}{continuation}"));
+        }
+    }
+}
+
+/// findings laid out like the output of `tree`, one tree per target (scheme://host:port), in
+/// sorted order; wildcard responses should be filtered out by the caller
+///
+/// ex:
+/// http://localhost [200]
+/// ├── admin/ [301]
+/// │   ├── login [200]
+/// │   └── users [403]
+/// └── index.html [200]
+pub fn render_tree<'a>(responses: impl IntoIterator<Item = &'a FeroxResponse>) -> String {
+    let mut targets: BTreeMap<String, Node> = BTreeMap::new();
+
+    for response in responses {
+        let segments = response
+            .url()
+            .path_segments()
+            .map(|segments| segments.collect::<Vec<_>>())
+            .unwrap_or_default();
+
+        // the root itself shows up as a single empty segment, same as a trailing slash
+        let segments = if segments == [""] {
+            &[][..]
+        } else {
+            &segments[..]
+        };
+
+        targets
+            .entry(response.url().origin().ascii_serialization())
+            .or_default()
+            .insert(segments, response.status().as_u16());
+    }
+
+    let mut tree = String::new();
+
+    for (target, root) in &targets {
+        let _ = writeln!(tree, "{target}{}", root.status_suffix());
+        root.render_children(&mut tree, "");
+    }
+
+    tree
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::response::fixtures::response;
+
+    #[test]
+    /// findings are nested under their directories, with intermediate directories filled in
+    fn render_tree_nests_findings_by_directory() {
+        let tree = render_tree(&[
+            response("http://localhost/", 200),
+            response("http://localhost/index.html", 200),
+            response("http://localhost/admin", 301),
+            response("http://localhost/admin/", 200),
+            response("http://localhost/admin/users", 403),
+            response("http://localhost/api/v1/health", 200),
+            response("https://other.local/login", 200),
+        ]);
+
+        assert_eq!(
+            tree,
+            "http://localhost [200]
+├── admin/ [200, 301]
+│   └── users [403]
+├── api/
+│   └── v1/
+│       └── health [200]
+└── index.html [200]
+https://other.local
+└── login [200]
+"
+        );
+    }
+
+    #[test]
+    /// no findings, no tree
+    fn render_tree_without_findings() {
+        assert!(render_tree(&[] as &[FeroxResponse]).is_empty());
+    }
+}
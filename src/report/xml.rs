@@ -0,0 +1,179 @@
+use std::{
+    collections::{BTreeSet, HashMap},
+    fmt::Write as _,
+    fs::File,
+    io::{BufWriter, Write},
+};
+
+use anyhow::{bail, Context, Result};
+
+use crate::{config::Configuration, response::FeroxResponse, scanner::RESPONSES, utils::fmt_err};
+
+/// write the scan's findings to --report-xml, using DirBuster's xml report schema
+///
+/// called at the same point as `write_markdown_report`, once all responses have made their way
+/// into `RESPONSES`
+pub fn write_xml_report(config: &Configuration) -> Result<()> {
+    log::trace!("enter: write_xml_report({})", config.report_xml);
+
+    if config.report_xml.is_empty() {
+        log::trace!("exit: write_xml_report");
+        return Ok(());
+    }
+
+    let responses = match RESPONSES.responses.read() {
+        Ok(guard) => guard
+            .iter()
+            .filter(|response| !response.wildcard())
+            .cloned()
+            .collect::<Vec<_>>(),
+        Err(_) => bail!(fmt_err("Could not read discovered responses for report")),
+    };
+
+    let file = File::create(&config.report_xml)
+        .with_context(|| fmt_err(&format!("Could not create {}", config.report_xml)))?;
+
+    let mut writer = BufWriter::new(file);
+
+    writer
+        .write_all(render_xml(&config.meta, &responses).as_bytes())
+        .and_then(|_| writer.flush())
+        .with_context(|| fmt_err(&format!("Could not write to {}", config.report_xml)))?;
+
+    log::info!(
+        "wrote xml report covering {} finding(s) to {}",
+        responses.len(),
+        config.report_xml
+    );
+
+    log::trace!("exit: write_xml_report");
+    Ok(())
+}
+
+/// the full report; one `Result` per finding, directories before files, as DirBuster orders them
+///
+/// DirBuster reports cover a single target, so `Path` is the path (and query) of the finding;
+/// when more than one target was scanned, `Path` is the full url instead, so findings from
+/// different targets can still be told apart
+///
+/// --meta pairs are written as `Meta` elements ahead of the results; DirBuster's schema has no
+/// place for them, and readers that only look for `Result` elements skip right over them
+fn render_xml(meta: &HashMap<String, String>, responses: &[FeroxResponse]) -> String {
+    let origins = responses
+        .iter()
+        .map(|response| response.url().origin().ascii_serialization())
+        .collect::<BTreeSet<_>>();
+
+    let mut results = responses
+        .iter()
+        .map(|response| {
+            let path = if origins.len() > 1 {
+                response.url().to_string()
+            } else {
+                match response.url().query() {
+                    Some(query) => format!("{}?{query}", response.url().path()),
+                    None => response.url().path().to_string(),
+                }
+            };
+
+            let kind = if response.is_directory() {
+                "Dir"
+            } else {
+                "File"
+            };
+
+            (kind, path, response.status().as_u16())
+        })
+        .collect::<Vec<_>>();
+
+    results.sort();
+    results.dedup();
+
+    let mut report =
+        String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<DirBusterResults>\n");
+
+    let mut pairs: Vec<_> = meta.iter().collect();
+    pairs.sort();
+
+    for (key, value) in pairs {
+        let _ = writeln!(
+            report,
+            "<Meta key=\"{}\">{}</Meta>",
+            xml_escape(key),
+            xml_escape(value)
+        );
+    }
+
+    for (kind, path, status) in results {
+        let _ = writeln!(
+            report,
+            "<Result type=\"{kind}\">\n<Path>{}</Path>\n<ResponseCode>{status}</ResponseCode>\n</Result>",
+            xml_escape(&path)
+        );
+    }
+
+    report.push_str("</DirBusterResults>\n");
+    report
+}
+
+/// escape the characters that can't appear as-is in xml text
+fn xml_escape(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::response::fixtures::response;
+
+    #[test]
+    /// a single target's findings are reported by path, directories first
+    fn render_xml_uses_paths_for_a_single_target() {
+        let report = render_xml(
+            &HashMap::new(),
+            &[
+                response("http://localhost/index.php?a=1&b=2", 200),
+                response("http://localhost/admin/", 200),
+            ],
+        );
+
+        assert!(
+            report.starts_with("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<DirBusterResults>")
+        );
+        assert!(report.ends_with("</DirBusterResults>\n"));
+
+        let dir = report
+            .find("<Result type=\"Dir\">\n<Path>/admin/</Path>\n<ResponseCode>200</ResponseCode>")
+            .unwrap();
+        let file = report
+            .find("<Result type=\"File\">\n<Path>/index.php?a=1&amp;b=2</Path>")
+            .unwrap();
+
+        assert!(dir < file);
+    }
+
+    #[test]
+    /// findings from more than one target keep their full url
+    fn render_xml_uses_urls_for_multiple_targets() {
+        let meta = HashMap::from([(String::from("ticket"), String::from("SEC-1 & <2>"))]);
+        let report = render_xml(
+            &meta,
+            &[
+                response("http://localhost/a", 200),
+                response("https://other.local/a", 403),
+            ],
+        );
+
+        assert!(report
+            .contains("<DirBusterResults>\n<Meta key=\"ticket\">SEC-1 &amp; &lt;2&gt;</Meta>\n"));
+
+        assert!(report.contains("<Path>http://localhost/a</Path>"));
+        assert!(report.contains("<Path>https://other.local/a</Path>"));
+        assert!(report.contains("<ResponseCode>403</ResponseCode>"));
+    }
+}
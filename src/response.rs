@@ -3,13 +3,18 @@ use std::{
     convert::{TryFrom, TryInto},
     fmt,
     str::FromStr,
-    sync::Arc,
+    sync::{
+        atomic::{AtomicBool, AtomicUsize, Ordering},
+        Arc, RwLock,
+    },
 };
 
 use anyhow::{Context, Result};
+use base64::{engine::general_purpose::STANDARD, Engine};
 use console::style;
+use encoding_rs::{Encoding, UTF_8};
 use reqwest::{
-    header::{HeaderMap, HeaderName, HeaderValue},
+    header::{HeaderMap, HeaderName, HeaderValue, CONTENT_TYPE},
     Method, Response, StatusCode, Url,
 };
 use serde::ser::SerializeStruct;
@@ -17,14 +22,92 @@ use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use serde_json::Value;
 
 use crate::{
-    config::OutputLevel,
+    config::{Configuration, OutputLevel},
     event_handlers::{Command, Handles},
+    nlp,
+    template::OUTPUT_TEMPLATE,
     traits::FeroxSerialize,
     url::FeroxUrl,
-    utils::{self, fmt_err, parse_url_with_raw_path, status_colorizer, timestamp},
-    CommandSender,
+    utils::{
+        self, csv_field, fmt_err, parse_url_with_raw_path, status_colorizer, timestamp, TraceId,
+    },
+    CommandSender, OUTPUT_SCHEMA_VERSION,
 };
 
+/// Maximum number of body bytes included in each NDJSON record (--json-include-body); set once
+/// at startup, 0 means bodies are left out
+pub static JSON_BODY_LIMIT: AtomicUsize = AtomicUsize::new(0);
+
+/// values replaced with `REDACTED` wherever they appear in a body included in NDJSON output;
+/// set once at startup, see `body_redactions`
+pub static JSON_BODY_REDACTIONS: RwLock<Vec<String>> = RwLock::new(Vec::new());
+
+/// stands in for a redacted value
+const REDACTED: &str = "[redacted]";
+
+/// the user's own secrets, which a response body may echo back: each --headers value, each
+/// cookie, and each --query value, along with the zap api key
+///
+/// these are kept out of the bodies written by --json-include-body, the same way the banner and
+/// state files keep out the secrets they'd otherwise show
+pub fn body_redactions(config: &Configuration) -> Vec<String> {
+    let mut secrets: Vec<String> = config.headers.values().cloned().collect();
+
+    if let Some(cookies) = config
+        .headers
+        .iter()
+        .find(|(name, _)| name.eq_ignore_ascii_case("cookie"))
+        .map(|(_, value)| value)
+    {
+        secrets.extend(
+            cookies
+                .split(';')
+                .filter_map(|cookie| cookie.split_once('='))
+                .map(|(_, value)| value.trim().to_string()),
+        );
+    }
+
+    secrets.extend(config.queries.iter().map(|(_, value)| value.clone()));
+    secrets.push(config.zap_api_key.clone());
+
+    secrets.retain(|secret| !secret.is_empty());
+
+    // longest first, so a value that contains another is replaced whole
+    secrets.sort_by_key(|secret| std::cmp::Reverse(secret.len()));
+    secrets.dedup();
+
+    secrets
+}
+
+/// replace each of the given secrets found in `bytes` with `REDACTED`
+fn redact_bytes(bytes: &[u8], secrets: &[String]) -> Vec<u8> {
+    let mut redacted = bytes.to_vec();
+
+    for secret in secrets {
+        let needle = secret.as_bytes();
+        let mut replaced = Vec::with_capacity(redacted.len());
+        let mut idx = 0;
+
+        while idx < redacted.len() {
+            if redacted[idx..].starts_with(needle) {
+                replaced.extend_from_slice(REDACTED.as_bytes());
+                idx += needle.len();
+            } else {
+                replaced.push(redacted[idx]);
+                idx += 1;
+            }
+        }
+
+        redacted = replaced;
+    }
+
+    redacted
+}
+
+/// Whether the wordlist entry behind each finding is shown in terminal output; set once at
+/// startup when --verbosity is used
+pub static SHOW_WORD: AtomicBool = AtomicBool::new(false);
+
 /// A `FeroxResponse`, derived from a `Response` to a submitted `Request`
 #[derive(Debug, Clone)]
 pub struct FeroxResponse {
@@ -66,6 +149,40 @@ pub struct FeroxResponse {
 
     /// Timestamp of when this response was received
     timestamp: f64,
+
+    /// Unique id of the request that generated this response, used to correlate findings with
+    /// debug logs, the replay proxy, and target-side logs
+    trace_id: String,
+
+    /// The original character encoding of the body, prior to being transcoded to UTF-8
+    encoding: String,
+
+    /// whether the connection was cut mid-body or the body's length didn't match the
+    /// Content-Length header; often a sign of WAF interference
+    truncated: bool,
+
+    /// raw bytes of a binary body, capped at `JSON_BODY_LIMIT`; only kept when
+    /// --json-include-body is used, since `text` can't faithfully represent them
+    binary_body: Option<Vec<u8>>,
+
+    /// best guess at the human language of the body, by ISO 639-1 code; only set when
+    /// --detect-language is used
+    language: Option<String>,
+
+    /// tags marking the finding as worth a closer look (ex: backup-file, 5xx); only set when
+    /// --tag-findings or --tag-rule is used
+    tags: Vec<String>,
+
+    /// the wordlist entry that produced this response; not set for responses that didn't come
+    /// from the wordlist (ex: extracted links)
+    word: Option<String>,
+
+    /// the extension appended to `word` to build the requested url, if any
+    applied_extension: Option<String>,
+
+    /// whether the response was a 304 to a conditional request made for --changed-since,
+    /// i.e. the content hasn't changed since the previous run
+    unchanged: bool,
 }
 
 /// implement Default trait for FeroxResponse
@@ -86,6 +203,15 @@ impl Default for FeroxResponse {
             output_level: Default::default(),
             extension: None,
             timestamp: timestamp(),
+            trace_id: String::new(),
+            encoding: String::new(),
+            truncated: false,
+            binary_body: None,
+            language: None,
+            tags: Vec::new(),
+            word: None,
+            applied_extension: None,
+            unchanged: false,
         }
     }
 }
@@ -147,6 +273,96 @@ impl FeroxResponse {
         self.timestamp
     }
 
+    /// Get the trace id of the request that generated this response
+    pub fn trace_id(&self) -> &str {
+        &self.trace_id
+    }
+
+    /// Get the original character encoding of the response body
+    pub fn encoding(&self) -> &str {
+        &self.encoding
+    }
+
+    /// Get whether or not the response body was truncated
+    pub fn truncated(&self) -> bool {
+        self.truncated
+    }
+
+    /// Get the detected language of the response body, if one was detected
+    pub fn language(&self) -> Option<&str> {
+        self.language.as_deref()
+    }
+
+    /// Get the tags applied to this response
+    pub fn tags(&self) -> &[String] {
+        &self.tags
+    }
+
+    /// set `tags` attribute
+    pub(crate) fn set_tags(&mut self, tags: Vec<String>) {
+        self.tags = tags;
+    }
+
+    /// Get the wordlist entry that produced this response, if it came from the wordlist
+    pub fn word(&self) -> Option<&str> {
+        self.word.as_deref()
+    }
+
+    /// Get the extension that was appended to the wordlist entry, if any
+    pub fn applied_extension(&self) -> Option<&str> {
+        self.applied_extension.as_deref()
+    }
+
+    /// Get whether the content hasn't changed since the run given to --changed-since
+    pub fn unchanged(&self) -> bool {
+        self.unchanged
+    }
+
+    /// set `unchanged` attribute
+    pub(crate) fn set_unchanged(&mut self, unchanged: bool) {
+        self.unchanged = unchanged;
+    }
+
+    /// set `word` and `applied_extension` attributes
+    pub(crate) fn set_word(&mut self, word: &str, extension: Option<&str>) {
+        self.word = Some(word.to_string());
+        self.applied_extension = extension.map(String::from);
+    }
+
+    /// body to include in NDJSON output, with the given secrets redacted and capped at `limit`
+    /// bytes
+    ///
+    /// returns the body, whether it's base64 encoded (binary bodies are), and whether it was
+    /// cut short to fit within the limit
+    fn json_body(&self, limit: usize, secrets: &[String]) -> (String, bool, bool) {
+        if let Some(bytes) = &self.binary_body {
+            let cut_short = (bytes.len() as u64) < self.content_length;
+            return (
+                STANDARD.encode(redact_bytes(bytes, secrets)),
+                true,
+                cut_short,
+            );
+        }
+
+        // redacted before it's cut, so that a secret straddling the limit isn't half-written
+        let mut text = self.text.clone();
+
+        for secret in secrets {
+            text = text.replace(secret.as_str(), REDACTED);
+        }
+
+        let mut end = text.len().min(limit);
+
+        while !text.is_char_boundary(end) {
+            end -= 1;
+        }
+
+        let cut_short = end < text.len();
+        text.truncate(end);
+
+        (text, false, cut_short)
+    }
+
     /// Set `FeroxResponse`'s `url` attribute, has no affect if an error occurs
     pub fn set_url(&mut self, url: &str) {
         match parse_url_with_raw_path(url) {
@@ -164,6 +380,18 @@ impl FeroxResponse {
         self.wildcard = is_wildcard;
     }
 
+    /// set `status` attribute
+    #[cfg(test)]
+    pub fn set_status(&mut self, status: StatusCode) {
+        self.status = status;
+    }
+
+    /// mutable access to the `headers` attribute
+    #[cfg(test)]
+    pub fn headers_mut(&mut self) -> &mut HeaderMap {
+        &mut self.headers
+    }
+
     /// set `text` attribute; update words/lines/content_length
     #[cfg(test)]
     pub fn set_text(&mut self, text: &str) {
@@ -216,7 +444,7 @@ impl FeroxResponse {
 
     /// Create a new `FeroxResponse` from the given `Response`
     pub async fn from(
-        response: Response,
+        mut response: Response,
         original_url: &str,
         method: &str,
         output_level: OutputLevel,
@@ -224,23 +452,57 @@ impl FeroxResponse {
         let url = response.url().clone();
         let status = response.status();
         let headers = response.headers().clone();
-        let content_length = response.content_length().unwrap_or(0);
+        let expected_length = response.content_length();
+        let content_length = expected_length.unwrap_or(0);
         let timestamp = timestamp();
-
-        // .text() consumes the response, must be called last
-        let text = response
-            .text()
-            .await
-            .with_context(|| "Could not parse body from response")
+        let trace_id = response
+            .extensions()
+            .get::<TraceId>()
+            .map(|trace| trace.0.clone())
             .unwrap_or_default();
 
+        // the body is read chunk by chunk, so that a connection cut mid-body still leaves us
+        // with what was received so far
+        let mut body = Vec::new();
+        let mut truncated = false;
+
+        loop {
+            match response.chunk().await {
+                Ok(Some(chunk)) => body.extend_from_slice(&chunk),
+                Ok(None) => break,
+                Err(e) => {
+                    log::warn!("Could not read full body of {}: {}", url, e);
+                    truncated = true;
+                    break;
+                }
+            }
+        }
+
+        // expected_length is only known when the body isn't being decompressed, in which case
+        // it's the value of the Content-Length header
+        if expected_length.is_some_and(|expected| expected != body.len() as u64) {
+            truncated = true;
+        }
+
+        // legacy charsets (Shift_JIS, windows-1251, etc) are transcoded to UTF-8 so that word
+        // counts, filters, and link extraction all operate on sane text
+        let (text, encoding) = decode_body(&headers, &body);
+
+        let body_limit = JSON_BODY_LIMIT.load(Ordering::Relaxed);
+
+        let binary_body = if body_limit > 0 && is_binary(&body, &text) {
+            Some(body[..body.len().min(body_limit)].to_vec())
+        } else {
+            None
+        };
+
         // in the event that the content_length was 0, we can try to get the length
         // of the body we just parsed. At worst, it's still 0; at best we've accounted
         // for sites that reply without a content-length header and yet still have
         // contents in the body.
         //
         // thanks to twitter use @f3rn0s for pointing out the possibility
-        let content_length = content_length.max(text.len() as u64);
+        let content_length = content_length.max(body.len() as u64);
 
         let line_count = text.lines().count();
         let word_count = text.lines().map(|s| s.split_whitespace().count()).sum();
@@ -259,9 +521,30 @@ impl FeroxResponse {
             wildcard: false,
             extension: None,
             timestamp,
+            trace_id,
+            encoding: encoding.to_string(),
+            truncated,
+            binary_body,
+            language: None,
+            tags: Vec::new(),
+            word: None,
+            applied_extension: None,
+            unchanged: false,
         }
     }
 
+    /// if --detect-language is used, make a best guess at the human language of the body, so
+    /// findings from different locales of an application can be told apart
+    pub(crate) fn detect_language(&mut self) {
+        log::trace!("enter: detect_language");
+
+        if self.binary_body.is_none() {
+            self.language = nlp::detect_language(&self.text).map(String::from);
+        }
+
+        log::trace!("exit: detect_language -> {:?}", self.language);
+    }
+
     /// if --collect-extensions is used, examine the response's url and grab the file's extension
     /// if one is available to be grabbed. If an extension is found, send it to the ScanHandler
     /// for further processing
@@ -424,6 +707,11 @@ impl FeroxResponse {
 impl FeroxSerialize for FeroxResponse {
     /// Simple wrapper around create_report_string
     fn as_str(&self) -> String {
+        if let Some(template) = OUTPUT_TEMPLATE.get() {
+            // --output-format replaces the normal report line entirely
+            return template.render(self);
+        }
+
         let lines = self.line_count().to_string();
         let words = self.word_count().to_string();
         let chars = self.content_length().to_string();
@@ -470,6 +758,59 @@ impl FeroxSerialize for FeroxResponse {
             }
         };
 
+        if self.truncated
+            && !matches!(
+                self.output_level,
+                OutputLevel::Silent | OutputLevel::SilentJSON
+            )
+        {
+            // cut-short bodies skew sizes and word counts, so they're called out
+            url_with_redirect.push_str(&format!(" {}", style("(truncated)").red()));
+        }
+
+        if self.unchanged
+            && !matches!(
+                self.output_level,
+                OutputLevel::Silent | OutputLevel::SilentJSON
+            )
+        {
+            url_with_redirect.push_str(&format!(" {}", style("(unchanged)").blue()));
+        }
+
+        if let Some(language) = &self.language {
+            if !matches!(
+                self.output_level,
+                OutputLevel::Silent | OutputLevel::SilentJSON
+            ) {
+                url_with_redirect.push_str(&format!(" {}", style(format!("[{language}]")).cyan()));
+            }
+        }
+
+        if !self.tags.is_empty()
+            && !matches!(
+                self.output_level,
+                OutputLevel::Silent | OutputLevel::SilentJSON
+            )
+        {
+            let tags: Vec<_> = self.tags.iter().map(|tag| format!("#{tag}")).collect();
+            url_with_redirect.push_str(&format!(" {}", style(tags.join(" ")).magenta()));
+        }
+
+        if let Some(word) = &self.word {
+            if SHOW_WORD.load(Ordering::Relaxed)
+                && !matches!(
+                    self.output_level,
+                    OutputLevel::Silent | OutputLevel::SilentJSON
+                )
+            {
+                let source = match &self.applied_extension {
+                    Some(extension) => format!("(word: {word} +.{extension})"),
+                    None => format!("(word: {word})"),
+                };
+                url_with_redirect.push_str(&format!(" {}", style(source).dim()));
+            }
+        }
+
         if self.wildcard && matches!(self.output_level, OutputLevel::Default | OutputLevel::Quiet) {
             // --silent was not used and response is a wildcard, special messages abound when
             // this is the case...
@@ -522,12 +863,21 @@ impl FeroxSerialize for FeroxResponse {
     /// ex:
     /// {
     ///    "type":"response",
+    ///    "schema_version":1,
     ///    "url":"https://localhost.com/images",
     ///    "path":"/images",
     ///    "status":301,
     ///    "content_length":179,
     ///    "line_count":10,
     ///    "word_count":16,
+    ///    "trace_id":"5d8ef2a5a9e04b6c8b8a4bb2e0bde4fb",
+    ///    "encoding":"UTF-8",
+    ///    "truncated":false,
+    ///    "unchanged":false,
+    ///    "language":"en",
+    ///    "tags":["directory-listing"],
+    ///    "word":"images",
+    ///    "applied_extension":"",
     ///    "headers":{
     ///       "x-content-type-options":"nosniff",
     ///       "strict-transport-security":"max-age=31536000; includeSubDomains",
@@ -545,23 +895,150 @@ impl FeroxSerialize for FeroxResponse {
     ///       "access-control-allow-origin":"https://localhost.com"
     ///    }
     /// }\n
+    ///
+    /// when --json-include-body is used, `body`, `body_base64`, and `body_truncated` fields are
+    /// added as well; they're only ever part of NDJSON output, never a state file
     fn as_json(&self) -> anyhow::Result<String> {
-        let mut json = serde_json::to_string(&self)
+        let record = JsonRecord {
+            response: self,
+            body_limit: JSON_BODY_LIMIT.load(Ordering::Relaxed),
+        };
+
+        let mut json = serde_json::to_string(&record)
             .with_context(|| fmt_err(&format!("Could not convert {} to JSON", self.url())))?;
         json.push('\n');
         Ok(json)
     }
+
+    /// Create a CSV row of the given columns (see `CSV_COLUMNS`), in the order given
+    ///
+    /// ex (all columns):
+    /// https://localhost.com/images,301,GET,10,16,179,/images/\n
+    fn as_csv(&self, columns: &[String]) -> Option<String> {
+        let fields: Vec<_> = columns
+            .iter()
+            .map(|column| match column.as_str() {
+                "url" => csv_field(self.url().as_str()),
+                "status" => self.status().as_str().to_string(),
+                "method" => csv_field(self.method().as_str()),
+                "lines" => self.line_count().to_string(),
+                "words" => self.word_count().to_string(),
+                "chars" => self.content_length().to_string(),
+                "location" => csv_field(
+                    self.headers()
+                        .get("Location")
+                        .and_then(|location| location.to_str().ok())
+                        .unwrap_or_default(),
+                ),
+                _ => String::new(),
+            })
+            .collect();
+
+        Some(format!("{}\n", fields.join(",")))
+    }
+}
+
+/// number of bytes at the start of a body that are searched for a `<meta>` charset declaration
+///
+/// ref: https://html.spec.whatwg.org/multipage/parsing.html#prescan-a-byte-stream-to-determine-its-encoding
+const META_CHARSET_PRESCAN_LENGTH: usize = 1024;
+
+/// pull the value of a `charset=` parameter out of the given (lowercased) text, if one exists
+fn charset_label(text: &str) -> Option<&str> {
+    let start = text.find("charset=")? + "charset=".len();
+
+    let label = text[start..]
+        .trim_start_matches(['"', '\'', ' '])
+        .split(|c: char| matches!(c, '"' | '\'' | ';' | '>' | '/' | ' '))
+        .next()?;
+
+    if label.is_empty() {
+        None
+    } else {
+        Some(label)
+    }
+}
+
+/// whether the body is binary rather than text; either it contains NUL bytes or it couldn't be
+/// decoded without replacement characters
+fn is_binary(body: &[u8], text: &str) -> bool {
+    body.contains(&0) || text.contains(char::REPLACEMENT_CHARACTER)
+}
+
+/// determine the body's character encoding and transcode it to UTF-8
+///
+/// precedence: byte order mark, then the Content-Type header's charset, then a `<meta>` charset
+/// declaration near the top of the document, and finally UTF-8 as a fallback
+fn decode_body(headers: &HeaderMap, body: &[u8]) -> (String, &'static str) {
+    let from_header = headers
+        .get(CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .map(|value| value.to_ascii_lowercase())
+        .and_then(|value| {
+            charset_label(&value).and_then(|label| Encoding::for_label(label.as_bytes()))
+        });
+
+    let from_meta = || {
+        let prescan = &body[..body.len().min(META_CHARSET_PRESCAN_LENGTH)];
+        let prescan = String::from_utf8_lossy(prescan).to_ascii_lowercase();
+
+        prescan
+            .find("<meta")
+            .and_then(|idx| charset_label(&prescan[idx..]))
+            .and_then(|label| Encoding::for_label(label.as_bytes()))
+    };
+
+    let encoding = from_header.or_else(from_meta).unwrap_or(UTF_8);
+
+    // decode performs BOM sniffing, a BOM overrides whatever encoding was declared
+    let (text, used, _) = encoding.decode(body);
+
+    (text.into_owned(), used.name())
+}
+
+/// a `FeroxResponse` as written to NDJSON output, which is the only place its body is
+/// included (--json-include-body)
+struct JsonRecord<'a> {
+    /// the response being written
+    response: &'a FeroxResponse,
+
+    /// maximum number of body bytes to include, 0 leaves the body out
+    body_limit: usize,
+}
+
+/// Serialize implementation for JsonRecord
+impl Serialize for JsonRecord<'_> {
+    /// the response's usual fields, followed by its body when one was asked for
+    fn serialize<S>(&self, serializer: S) -> anyhow::Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        self.response.serialize_fields(serializer, self.body_limit)
+    }
 }
 
 /// Serialize implementation for FeroxResponse
 impl Serialize for FeroxResponse {
-    /// Function that handles serialization of a FeroxResponse to NDJSON
+    /// Function that handles serialization of a FeroxResponse; bodies are left out, so they
+    /// never make it into state files
     fn serialize<S>(&self, serializer: S) -> anyhow::Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        self.serialize_fields(serializer, 0)
+    }
+}
+
+/// serialization helpers for FeroxResponse
+impl FeroxResponse {
+    /// serialize each of the response's fields, along with up to `body_limit` bytes of its body
+    /// (see `json_body`) when `body_limit` isn't 0
+    fn serialize_fields<S>(&self, serializer: S, body_limit: usize) -> Result<S::Ok, S::Error>
     where
         S: Serializer,
     {
         let mut headers = HashMap::new();
-        let mut state = serializer.serialize_struct("FeroxResponse", 8)?;
+        let mut state = serializer.serialize_struct("FeroxResponse", 16)?;
 
         // need to convert the HeaderMap to a HashMap in order to pass it to the serializer
         for (key, value) in &self.headers {
@@ -571,6 +1048,7 @@ impl Serialize for FeroxResponse {
         }
 
         state.serialize_field("type", "response")?;
+        state.serialize_field("schema_version", &OUTPUT_SCHEMA_VERSION)?;
         state.serialize_field("url", self.url.as_str())?;
         state.serialize_field("original_url", self.original_url.as_str())?;
         state.serialize_field("path", self.url.path())?;
@@ -586,6 +1064,30 @@ impl Serialize for FeroxResponse {
             self.extension.as_ref().unwrap_or(&String::new()),
         )?;
         state.serialize_field("timestamp", &self.timestamp)?;
+        state.serialize_field("trace_id", &self.trace_id)?;
+        state.serialize_field("encoding", &self.encoding)?;
+        state.serialize_field("truncated", &self.truncated)?;
+        state.serialize_field("unchanged", &self.unchanged)?;
+        state.serialize_field("language", self.language.as_ref().unwrap_or(&String::new()))?;
+        state.serialize_field("tags", &self.tags)?;
+        state.serialize_field("word", self.word.as_ref().unwrap_or(&String::new()))?;
+        state.serialize_field(
+            "applied_extension",
+            self.applied_extension.as_ref().unwrap_or(&String::new()),
+        )?;
+
+        if body_limit > 0 {
+            let secrets = JSON_BODY_REDACTIONS
+                .read()
+                .map(|secrets| secrets.clone())
+                .unwrap_or_default();
+
+            let (body, base64, cut_short) = self.json_body(body_limit, &secrets);
+
+            state.serialize_field("body", &body)?;
+            state.serialize_field("body_base64", &base64)?;
+            state.serialize_field("body_truncated", &cut_short)?;
+        }
 
         state.end()
     }
@@ -612,6 +1114,15 @@ impl<'de> Deserialize<'de> for FeroxResponse {
             word_count: 0,
             extension: None,
             timestamp: timestamp(),
+            trace_id: String::new(),
+            encoding: String::new(),
+            truncated: false,
+            binary_body: None,
+            language: None,
+            tags: Vec::new(),
+            word: None,
+            applied_extension: None,
+            unchanged: false,
         };
 
         let map: HashMap<String, Value> = HashMap::deserialize(deserializer)?;
@@ -690,6 +1201,57 @@ impl<'de> Deserialize<'de> for FeroxResponse {
                         response.timestamp = result;
                     }
                 }
+                "trace_id" => {
+                    if let Some(result) = value.as_str() {
+                        response.trace_id = result.to_string();
+                    }
+                }
+                "encoding" => {
+                    if let Some(result) = value.as_str() {
+                        response.encoding = result.to_string();
+                    }
+                }
+                "truncated" => {
+                    if let Some(result) = value.as_bool() {
+                        response.truncated = result;
+                    }
+                }
+                "unchanged" => {
+                    if let Some(result) = value.as_bool() {
+                        response.unchanged = result;
+                    }
+                }
+                "language" => {
+                    if let Some(result) = value.as_str().filter(|result| !result.is_empty()) {
+                        response.language = Some(result.to_string());
+                    }
+                }
+                "tags" => {
+                    if let Some(tags) = value.as_array() {
+                        response.tags = tags
+                            .iter()
+                            .filter_map(|tag| tag.as_str().map(String::from))
+                            .collect();
+                    }
+                }
+                "word" => {
+                    if let Some(result) = value.as_str().filter(|result| !result.is_empty()) {
+                        response.word = Some(result.to_string());
+                    }
+                }
+                "applied_extension" => {
+                    if let Some(result) = value.as_str().filter(|result| !result.is_empty()) {
+                        response.applied_extension = Some(result.to_string());
+                    }
+                }
+                "body" => {
+                    // binary bodies are base64 encoded and can't be used as text
+                    let base64 = map.get("body_base64").and_then(Value::as_bool);
+
+                    if let (Some(result), Some(false)) = (value.as_str(), base64) {
+                        response.text = result.to_string();
+                    }
+                }
                 _ => {}
             }
         }
@@ -698,6 +1260,21 @@ impl<'de> Deserialize<'de> for FeroxResponse {
     }
 }
 
+/// fixtures shared by the tests of modules that report on `FeroxResponse`s
+#[cfg(test)]
+pub(crate) mod fixtures {
+    use super::FeroxResponse;
+    use reqwest::StatusCode;
+
+    /// helper to build a response with the given url and status
+    pub(crate) fn response(url: &str, status: u16) -> FeroxResponse {
+        let mut response = FeroxResponse::default();
+        response.set_url(url);
+        response.set_status(StatusCode::from_u16(status).unwrap());
+        response
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -834,4 +1411,292 @@ mod tests {
 
         assert_eq!(response.extension, None);
     }
+
+    #[test]
+    /// charset declared in the content-type header is used to transcode the body
+    fn decode_body_uses_content_type_charset() {
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            CONTENT_TYPE,
+            HeaderValue::from_static("text/html; charset=windows-1251"),
+        );
+
+        // "привет" in windows-1251
+        let body = [0xef, 0xf0, 0xe8, 0xe2, 0xe5, 0xf2];
+
+        let (text, encoding) = decode_body(&headers, &body);
+
+        assert_eq!(text, "привет");
+        assert_eq!(encoding, "windows-1251");
+    }
+
+    #[test]
+    /// charset declared in a meta tag is used when the header doesn't declare one
+    fn decode_body_uses_meta_charset() {
+        let mut body = br#"<html><head><meta charset="Shift_JIS"></head><body>"#.to_vec();
+        // "日本" in Shift_JIS
+        body.extend_from_slice(&[0x93, 0xfa, 0x96, 0x7b]);
+        body.extend_from_slice(b"</body></html>");
+
+        let (text, encoding) = decode_body(&HeaderMap::new(), &body);
+
+        assert!(text.contains("日本"));
+        assert_eq!(encoding, "Shift_JIS");
+    }
+
+    #[test]
+    /// no declared charset falls back to UTF-8
+    fn decode_body_defaults_to_utf8() {
+        let (text, encoding) = decode_body(&HeaderMap::new(), "héllo".as_bytes());
+
+        assert_eq!(text, "héllo");
+        assert_eq!(encoding, "UTF-8");
+    }
+
+    #[test]
+    /// a response's trace id should survive a round trip through serialization
+    fn trace_id_is_serialized_and_deserialized() {
+        let response = FeroxResponse {
+            trace_id: String::from("5d8ef2a5a9e04b6c8b8a4bb2e0bde4fb"),
+            ..Default::default()
+        };
+
+        let json = response.as_json().unwrap();
+        assert!(json.contains(r#""trace_id":"5d8ef2a5a9e04b6c8b8a4bb2e0bde4fb""#));
+
+        let deserialized: FeroxResponse = serde_json::from_str(&json).unwrap();
+        assert_eq!(deserialized.trace_id(), "5d8ef2a5a9e04b6c8b8a4bb2e0bde4fb");
+    }
+
+    #[test]
+    /// truncated responses are called out in the report and survive a round trip through
+    /// serialization
+    fn truncated_response_is_reported_and_serialized() {
+        let response = FeroxResponse {
+            truncated: true,
+            ..Default::default()
+        };
+
+        let report = console::strip_ansi_codes(&response.as_str()).to_string();
+        assert!(report.contains("http://localhost/ (truncated)"));
+
+        let json = response.as_json().unwrap();
+        assert!(json.contains(r#""truncated":true"#));
+
+        let deserialized: FeroxResponse = serde_json::from_str(&json).unwrap();
+        assert!(deserialized.truncated());
+    }
+
+    #[test]
+    /// unchanged responses are called out in the report and survive a round trip through
+    /// serialization
+    fn unchanged_response_is_reported_and_serialized() {
+        let mut response = FeroxResponse::default();
+        response.set_unchanged(true);
+
+        let report = console::strip_ansi_codes(&response.as_str()).to_string();
+        assert!(report.contains("http://localhost/ (unchanged)"));
+
+        let json = response.as_json().unwrap();
+        assert!(json.contains(r#""unchanged":true"#));
+
+        let deserialized: FeroxResponse = serde_json::from_str(&json).unwrap();
+        assert!(deserialized.unchanged());
+    }
+
+    #[test]
+    /// a detected language is tagged in the report and survives a round trip through
+    /// serialization
+    fn detected_language_is_reported_and_serialized() {
+        let mut response = FeroxResponse::default();
+        response.set_text("<html><body><p>Die Mitarbeiter haben sich entschieden, dass wir die Sitzung auf nächste Woche verschieben. Ich glaube, das ist eine gute Entscheidung für unser Unternehmen.</p></body></html>");
+        response.detect_language();
+
+        assert_eq!(response.language(), Some("de"));
+
+        let report = console::strip_ansi_codes(&response.as_str()).to_string();
+        assert!(report.contains("http://localhost/ [de]"));
+
+        let json = response.as_json().unwrap();
+        assert!(json.contains(r#""language":"de""#));
+
+        let deserialized: FeroxResponse = serde_json::from_str(&json).unwrap();
+        assert_eq!(deserialized.language(), Some("de"));
+
+        // no guess is serialized as an empty string, and read back as no guess
+        let json = FeroxResponse::default().as_json().unwrap();
+        assert!(json.contains(r#""language":"""#));
+
+        let deserialized: FeroxResponse = serde_json::from_str(&json).unwrap();
+        assert_eq!(deserialized.language(), None);
+    }
+
+    #[test]
+    /// tags are shown after the url in the report and survive a round trip through json
+    fn tags_are_reported_and_serialized() {
+        let mut response = FeroxResponse::default();
+        response.set_tags(vec![String::from("backup-file"), String::from("5xx")]);
+
+        let report = console::strip_ansi_codes(&response.as_str()).to_string();
+        assert!(report.contains("http://localhost/ #backup-file #5xx"));
+
+        let json = response.as_json().unwrap();
+        assert!(json.contains(r#""tags":["backup-file","5xx"]"#));
+
+        let deserialized: FeroxResponse = serde_json::from_str(&json).unwrap();
+        assert_eq!(deserialized.tags(), ["backup-file", "5xx"]);
+
+        // untagged findings still carry an empty list, so consumers can rely on the field
+        let json = FeroxResponse::default().as_json().unwrap();
+        assert!(json.contains(r#""tags":[]"#));
+    }
+
+    #[test]
+    /// the originating wordlist entry and extension are shown when verbose and survive a round
+    /// trip through json
+    fn word_is_reported_and_serialized() {
+        let mut response = FeroxResponse::default();
+        response.set_url("http://localhost/admin.php");
+        response.set_word("admin", Some("php"));
+
+        SHOW_WORD.store(true, Ordering::Relaxed);
+        let report = console::strip_ansi_codes(&response.as_str()).to_string();
+        assert!(report.contains("http://localhost/admin.php (word: admin +.php)"));
+
+        let json = response.as_json().unwrap();
+        assert!(json.contains(r#""word":"admin","applied_extension":"php""#));
+
+        let deserialized: FeroxResponse = serde_json::from_str(&json).unwrap();
+        assert_eq!(deserialized.word(), Some("admin"));
+        assert_eq!(deserialized.applied_extension(), Some("php"));
+
+        // responses that didn't come from the wordlist carry empty strings
+        let json = FeroxResponse::default().as_json().unwrap();
+        assert!(json.contains(r#""word":"","applied_extension":"""#));
+
+        let deserialized: FeroxResponse = serde_json::from_str(&json).unwrap();
+        assert_eq!(deserialized.word(), None);
+        assert_eq!(deserialized.applied_extension(), None);
+    }
+
+    #[test]
+    /// text bodies are cut on a character boundary, binary bodies are base64 encoded
+    fn json_body_caps_text_and_encodes_binary() {
+        let response = FeroxResponse {
+            text: String::from("héllo"),
+            content_length: 6,
+            ..Default::default()
+        };
+
+        assert_eq!(response.json_body(2, &[]), (String::from("h"), false, true));
+        assert_eq!(
+            response.json_body(usize::MAX, &[]),
+            (String::from("héllo"), false, false)
+        );
+
+        let binary = FeroxResponse {
+            binary_body: Some(vec![0, 159, 146]),
+            content_length: 10,
+            ..Default::default()
+        };
+
+        assert_eq!(binary.json_body(3, &[]), (String::from("AJ+S"), true, true));
+
+        // secrets are redacted before the body is cut
+        let secrets = [String::from("hunter2")];
+        let echoed = FeroxResponse {
+            text: String::from("token=hunter2"),
+            content_length: 13,
+            ..Default::default()
+        };
+
+        assert_eq!(
+            echoed.json_body(10, &secrets),
+            (String::from("token=[red"), false, true)
+        );
+        assert_eq!(redact_bytes(b"\0hunter2\0", &secrets), b"\0[redacted]\0");
+    }
+
+    #[test]
+    /// bodies are only part of NDJSON records, never a plain serialization (i.e. state files)
+    fn body_is_only_serialized_into_json_records() {
+        let response = FeroxResponse {
+            text: String::from("secret body"),
+            content_length: 11,
+            ..Default::default()
+        };
+
+        let serialized = serde_json::to_string(&response).unwrap();
+        assert!(!serialized.contains("secret body"));
+        assert!(!serialized.contains(r#""body""#));
+
+        let record = JsonRecord {
+            response: &response,
+            body_limit: usize::MAX,
+        };
+
+        let serialized = serde_json::to_string(&record).unwrap();
+        assert!(serialized.contains(r#""body":"secret body","body_base64":false"#));
+    }
+
+    #[test]
+    /// header values, cookies, query values, and the zap api key are all redacted
+    fn body_redactions_collects_user_secrets() {
+        let mut config = Configuration::new().unwrap();
+        config
+            .headers
+            .insert(String::from("Authorization"), String::from("Bearer abc"));
+        config.headers.insert(
+            String::from("Cookie"),
+            String::from("session=s3ss; theme=dark"),
+        );
+        config.queries = vec![(String::from("token"), String::from("t0k3n"))];
+        config.zap_api_key = String::from("zapkey");
+
+        let secrets = body_redactions(&config);
+
+        for expected in [
+            "Bearer abc",
+            "session=s3ss; theme=dark",
+            "s3ss",
+            "dark",
+            "t0k3n",
+            "zapkey",
+        ] {
+            assert!(secrets.contains(&expected.to_string()), "{expected}");
+        }
+        assert!(is_binary(&[0, 1, 2], "\0\u{1}\u{2}"));
+        assert!(!is_binary(b"hello", "hello"));
+    }
+
+    #[test]
+    /// csv rows contain only the requested columns, in the requested order
+    fn as_csv_writes_requested_columns() {
+        let mut response = FeroxResponse {
+            text: String::from("one two\nthree"),
+            content_length: 13,
+            line_count: 2,
+            word_count: 3,
+            ..Default::default()
+        };
+        response.set_url("http://localhost/admin");
+        response.set_status(StatusCode::MOVED_PERMANENTLY);
+        response
+            .headers_mut()
+            .insert("Location", HeaderValue::from_static("/admin/?a=1,2"));
+
+        let all: Vec<_> = crate::CSV_COLUMNS.iter().map(|c| c.to_string()).collect();
+
+        assert_eq!(
+            response.as_csv(&all).unwrap(),
+            "http://localhost/admin,301,GET,2,3,13,\"/admin/?a=1,2\"\n"
+        );
+
+        let some = vec![String::from("status"), String::from("url")];
+
+        assert_eq!(
+            response.as_csv(&some).unwrap(),
+            "301,http://localhost/admin\n"
+        );
+    }
 }
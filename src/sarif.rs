@@ -0,0 +1,249 @@
+use std::collections::{BTreeMap, HashMap};
+
+use serde::Serialize;
+
+use crate::{response::FeroxResponse, VERSION};
+
+/// schema that every SARIF log written by --sarif conforms to
+const SARIF_SCHEMA: &str = "https://json.schemastore.org/sarif-2.1.0.json";
+
+/// version of SARIF written by --sarif
+const SARIF_VERSION: &str = "2.1.0";
+
+/// where to learn more about the tool that produced a SARIF log
+const INFORMATION_URI: &str = "https://github.com/epi052/feroxbuster";
+
+/// text wrapper used throughout SARIF (i.e. `{"text": "..."}`)
+#[derive(Serialize, Debug, Clone, PartialEq)]
+struct Message {
+    /// the message itself
+    text: String,
+}
+
+/// a rule, one per wordlist entry that led to a finding
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+struct Rule {
+    /// the wordlist entry
+    id: String,
+
+    /// short, human readable name of the rule
+    name: String,
+
+    /// what the rule represents
+    short_description: Message,
+}
+
+/// a single finding
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+struct SarifResult {
+    /// id of the `Rule` for the wordlist entry that found this result
+    rule_id: String,
+
+    /// index of the `Rule` in the driver's rules
+    rule_index: usize,
+
+    /// severity of the result
+    level: &'static str,
+
+    /// summary of the response
+    message: Message,
+
+    /// where the result was found
+    locations: Vec<serde_json::Value>,
+
+    /// the response's status, size, etc
+    properties: serde_json::Value,
+}
+
+/// Collects `FeroxResponse`s as SARIF 2.1.0 results, so that findings can be uploaded to code
+/// scanning dashboards (--sarif)
+///
+/// SARIF is a single json document, so results are held until the scan is over and the whole
+/// log is written at once
+#[derive(Debug, Default)]
+pub(crate) struct SarifLog {
+    /// id of the run that produced the log (`Configuration::run_id`)
+    run_id: String,
+
+    /// --meta pairs, written as the run's properties
+    meta: BTreeMap<String, String>,
+
+    /// one rule per wordlist entry, in the order they were first seen
+    rules: Vec<Rule>,
+
+    /// index into `rules` for each wordlist entry
+    rule_indices: HashMap<String, usize>,
+
+    /// every finding, in the order they were reported
+    results: Vec<SarifResult>,
+}
+
+/// implementation of SarifLog
+impl SarifLog {
+    /// create an empty log for the run with the given id and --meta pairs
+    pub(crate) fn new(run_id: &str, meta: &HashMap<String, String>) -> Self {
+        Self {
+            run_id: run_id.to_string(),
+            meta: meta.clone().into_iter().collect(),
+            ..Default::default()
+        }
+    }
+
+    /// add the given response to the log as a result
+    pub(crate) fn add(&mut self, response: &FeroxResponse) {
+        let word = wordlist_entry(response);
+
+        let rule_index = match self.rule_indices.get(&word) {
+            Some(index) => *index,
+            None => {
+                self.rules.push(Rule {
+                    id: word.clone(),
+                    name: String::from("DiscoveredResource"),
+                    short_description: Message {
+                        text: format!("Resource discovered by the wordlist entry '{word}'"),
+                    },
+                });
+                self.rule_indices.insert(word.clone(), self.rules.len() - 1);
+                self.rules.len() - 1
+            }
+        };
+
+        let status = response.status().as_u16();
+
+        self.results.push(SarifResult {
+            rule_id: word,
+            rule_index,
+            level: if status >= 500 { "warning" } else { "note" },
+            message: Message {
+                text: format!(
+                    "{} {} {} ({} bytes, {} words, {} lines)",
+                    status,
+                    response.method(),
+                    response.url(),
+                    response.content_length(),
+                    response.word_count(),
+                    response.line_count()
+                ),
+            },
+            locations: vec![serde_json::json!({
+                "physicalLocation": {
+                    "artifactLocation": { "uri": response.url().as_str() }
+                }
+            })],
+            properties: serde_json::json!({
+                "url": response.url().as_str(),
+                "method": response.method().as_str(),
+                "status": status,
+                "content_length": response.content_length(),
+                "word_count": response.word_count(),
+                "line_count": response.line_count(),
+            }),
+        });
+    }
+
+    /// number of results collected so far
+    pub(crate) fn len(&self) -> usize {
+        self.results.len()
+    }
+
+    /// the complete SARIF log
+    pub(crate) fn to_json(&self) -> serde_json::Result<String> {
+        let mut run = serde_json::json!({
+            "tool": {
+                "driver": {
+                    "name": "feroxbuster",
+                    "version": VERSION,
+                    "informationUri": INFORMATION_URI,
+                    "rules": self.rules,
+                }
+            },
+            "results": self.results,
+        });
+
+        if !self.run_id.is_empty() {
+            run["automationDetails"] = serde_json::json!({ "guid": self.run_id });
+        }
+
+        if !self.meta.is_empty() {
+            run["properties"] = serde_json::json!({ "meta": self.meta });
+        }
+
+        serde_json::to_string_pretty(&serde_json::json!({
+            "$schema": SARIF_SCHEMA,
+            "version": SARIF_VERSION,
+            "runs": [run]
+        }))
+    }
+}
+
+/// the wordlist entry that led to the response, taken from the last segment of its path
+///
+/// ex:
+/// http://localhost/admin/login.php -> login.php
+/// http://localhost/admin/ -> admin
+/// http://localhost/ -> /
+fn wordlist_entry(response: &FeroxResponse) -> String {
+    response
+        .url()
+        .path_segments()
+        .and_then(|segments| segments.rev().find(|segment| !segment.is_empty()))
+        .map(String::from)
+        .unwrap_or_else(|| String::from("/"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::response::fixtures::response;
+
+    #[test]
+    /// the last non-empty path segment is the wordlist entry
+    fn wordlist_entry_uses_last_segment() {
+        assert_eq!(
+            wordlist_entry(&response("http://localhost/admin/login.php", 200)),
+            "login.php"
+        );
+        assert_eq!(
+            wordlist_entry(&response("http://localhost/admin/", 301)),
+            "admin"
+        );
+        assert_eq!(wordlist_entry(&response("http://localhost/", 200)), "/");
+    }
+
+    #[test]
+    /// results share a rule when they come from the same wordlist entry
+    fn sarif_log_reuses_rules_per_wordlist_entry() {
+        let meta = HashMap::from([(String::from("engagement"), String::from("1337"))]);
+        let mut log = SarifLog::new("0b2f5a38-2d6c-4f0e-9c3e-6a1d3a3b8f7e", &meta);
+        log.add(&response("http://localhost/admin", 301));
+        log.add(&response("http://localhost/api/admin", 403));
+        log.add(&response("http://localhost/login", 500));
+
+        assert_eq!(log.len(), 3);
+
+        let sarif: serde_json::Value = serde_json::from_str(&log.to_json().unwrap()).unwrap();
+        let run = &sarif["runs"][0];
+
+        assert_eq!(sarif["version"], "2.1.0");
+        assert_eq!(run["tool"]["driver"]["name"], "feroxbuster");
+        assert_eq!(
+            run["automationDetails"]["guid"],
+            "0b2f5a38-2d6c-4f0e-9c3e-6a1d3a3b8f7e"
+        );
+        assert_eq!(run["properties"]["meta"]["engagement"], "1337");
+        assert_eq!(run["tool"]["driver"]["rules"].as_array().unwrap().len(), 2);
+
+        let results = run["results"].as_array().unwrap();
+        assert_eq!(results[0]["ruleId"], "admin");
+        assert_eq!(results[1]["ruleIndex"], 0);
+        assert_eq!(results[2]["ruleIndex"], 1);
+        assert_eq!(results[2]["level"], "warning");
+        assert_eq!(results[1]["properties"]["status"], 403);
+        assert_eq!(
+            results[1]["locations"][0]["physicalLocation"]["artifactLocation"]["uri"],
+            "http://localhost/api/admin"
+        );
+    }
+}
@@ -0,0 +1,47 @@
+use std::collections::BTreeSet;
+
+/// how far through the wordlist a directory scan has gotten
+///
+/// words complete out of order, since many requests are in-flight at once; the checkpoint only
+/// advances past a word once every word before it has completed too, so resuming from
+/// `position` never skips a word that wasn't requested
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct WordCheckpoint {
+    /// index into the full wordlist that this run of the scan started from
+    base: usize,
+
+    /// number of words, counted from `base`, that have all completed
+    next: usize,
+
+    /// words past `next` that have completed, relative to `base`
+    done: BTreeSet<usize>,
+}
+
+/// implementation of WordCheckpoint
+impl WordCheckpoint {
+    /// checkpoint for a scan that starts `base` words into the wordlist
+    pub fn new(base: usize) -> Self {
+        Self {
+            base,
+            ..Default::default()
+        }
+    }
+
+    /// mark the word at `index` (relative to where this run started) as completed
+    pub fn complete(&mut self, index: usize) {
+        if index < self.next {
+            return;
+        }
+
+        self.done.insert(index);
+
+        while self.done.remove(&self.next) {
+            self.next += 1;
+        }
+    }
+
+    /// index into the full wordlist that a resumed scan should start from
+    pub fn position(&self) -> usize {
+        self.base + self.next
+    }
+}
@@ -1,7 +1,8 @@
 use std::time::Duration;
 
 use crate::filters::filter_lookup;
-use crate::progress::PROGRESS_BAR;
+use crate::progress::{draw_target, PROGRESS_BAR};
+use crate::theme::{glyph, themed};
 use crate::traits::FeroxFilter;
 use console::{measure_text_width, pad_str, style, Alignment, Term};
 use indicatif::{HumanDuration, ProgressDrawTarget};
@@ -16,11 +17,39 @@ pub enum MenuCmd {
     /// user wants to cancel one or more active scans
     Cancel(Vec<usize>, bool),
 
+    /// user wants to cancel every scan against a host, given by name or by one of its scan ids
+    CancelHost(String, bool),
+
     /// user wants to create a new filter
     AddFilter(Box<dyn FeroxFilter>),
 
     /// user wants to remove one or more active filters
     RemoveFilter(Vec<usize>),
+
+    /// user wants to change the number of concurrent requests per scan
+    SetThreads(usize),
+
+    /// user wants to know which filters would suppress the response from the given url
+    TestFilters(String),
+}
+
+/// Snapshot of a paused scan, shown in the live panel at the top of the menu
+#[derive(Debug, Default)]
+pub(super) struct PauseStatus {
+    /// how long the scan has been running
+    pub(super) elapsed: Duration,
+
+    /// number of resources discovered so far
+    pub(super) findings: usize,
+
+    /// number of requests made so far
+    pub(super) requests: usize,
+
+    /// resident memory used by the process, in bytes, if it could be determined
+    pub(super) memory: Option<u64>,
+
+    /// (index, url, position, length) of each directory scan that hasn't been cancelled
+    pub(super) scans: Vec<(usize, String, u64, u64)>,
 }
 
 /// Data container for a command result to be used internally by the ferox_scanner
@@ -59,13 +88,13 @@ pub(super) struct Menu {
 impl Menu {
     /// Creates new Menu
     pub(super) fn new() -> Self {
-        let separator = "─".to_string();
+        let separator = glyph("─", "-").to_string();
 
         let name = format!(
             "{} {} {}",
-            "💀",
+            glyph("💀", "[x]"),
             style("Scan Management Menu").bright().yellow(),
-            "💀"
+            glyph("💀", "[x]")
         );
 
         let add_cmd = format!(
@@ -83,6 +112,14 @@ impl Menu {
             style("c").red(),
         );
 
+        let canx_host_cmd = format!(
+            "  {}[{}] [-f] SCAN_ID|HOST (ex: {} 3 or {} -f example.com)\n",
+            style("ch").red(),
+            style("ost").red(),
+            style("chost").red(),
+            style("ch").red(),
+        );
+
         let new_filter_cmd = format!(
             "  {}[{}] FILTER_TYPE FILTER_VALUE (ex: {} lines 40)\n",
             style("n").green(),
@@ -108,12 +145,29 @@ impl Menu {
             style("r").red(),
         );
 
+        let threads_cmd = format!(
+            "\n  {}[{}] NUM_THREADS (ex: {} 20)",
+            style("t").yellow(),
+            style("hreads").yellow(),
+            style("threads").yellow(),
+        );
+
+        let test_filters_cmd = format!(
+            "\n  {}[{}] URL (ex: {} http://localhost/admin)",
+            style("te").yellow(),
+            style("st-filters").yellow(),
+            style("test-filters").yellow(),
+        );
+
         let mut commands = format!("{}:\n", style("Commands").bright().blue());
         commands.push_str(&add_cmd);
         commands.push_str(&canx_cmd);
+        commands.push_str(&canx_host_cmd);
         commands.push_str(&new_filter_cmd);
         commands.push_str(&valid_filters);
         commands.push_str(&rm_filter_cmd);
+        commands.push_str(&threads_cmd);
+        commands.push_str(&test_filters_cmd);
 
         let longest = measure_text_width(&canx_cmd).max(measure_text_width(&name)) + 1;
 
@@ -151,10 +205,86 @@ impl Menu {
     /// print menu footer
     pub(super) fn print_eta(&self, eta: Duration) {
         let inner = format!("⏳ {} remaining ⏳", HumanDuration(eta));
+        let inner = themed(&inner);
         let padded_eta = pad_str(&inner, self.longest, Alignment::Center, None);
         self.println(&format!("{padded_eta}\n{}", self.border));
     }
 
+    /// build the lines of the paused-status panel; every line is padded to the width of the
+    /// menu so that a redraw fully overwrites the previous one
+    pub(super) fn pause_panel(&self, status: &PauseStatus) -> Vec<String> {
+        let memory = status
+            .memory
+            .map(|bytes| format!("{:.1} MiB", bytes as f64 / (1024.0 * 1024.0)))
+            .unwrap_or_else(|| String::from("n/a"));
+
+        let summary = format!(
+            "⏸  {} | {} elapsed | {}: {} | {}: {} | {}: {}",
+            style("paused").bright().yellow(),
+            HumanDuration(status.elapsed),
+            style("found").green(),
+            status.findings,
+            style("requests").blue(),
+            status.requests,
+            style("mem").cyan(),
+            memory
+        );
+        let summary = themed(&summary);
+
+        let mut lines = vec![pad_str(&summary, self.longest, Alignment::Center, None).to_string()];
+
+        for (index, url, position, length) in &status.scans {
+            let percent = if *length > 0 {
+                (*position * 100 / *length).min(100)
+            } else {
+                0
+            };
+
+            let line = format!("{index:3}: {percent:>3}% ({position}/{length}) {url}");
+            lines.push(pad_str(&line, self.longest, Alignment::Left, None).to_string());
+        }
+
+        lines.push(self.border.clone());
+        lines
+    }
+
+    /// print the paused-status panel
+    pub(super) fn print_pause_panel(&self, status: &PauseStatus) -> usize {
+        let lines = self.pause_panel(status);
+
+        for line in &lines {
+            self.println(line);
+        }
+
+        lines.len()
+    }
+
+    /// redraw the paused-status panel in place, starting at the given row, without disturbing
+    /// whatever the user is typing; exactly `num_lines` lines are drawn so the rest of the menu
+    /// stays where it is
+    pub(super) fn redraw_pause_panel(&self, status: &PauseStatus, row: usize, num_lines: usize) {
+        let mut lines = self.pause_panel(status);
+        let border = lines.pop().unwrap_or_default();
+
+        // keep the panel's original height; scans that started while paused aren't shown
+        lines.resize(num_lines.saturating_sub(1), " ".repeat(self.longest));
+        lines.push(border);
+
+        // save the cursor position (DECSC), draw, then restore it (DECRC)
+        self.term.write_str("\x1b7").unwrap_or_default();
+
+        for (offset, line) in lines.iter().enumerate() {
+            self.term
+                .move_cursor_to(0, row + offset)
+                .unwrap_or_default();
+            self.term.clear_line().unwrap_or_default();
+            self.term.write_str(line).unwrap_or_default();
+        }
+
+        self.term.write_str("\x1b8").unwrap_or_default();
+        self.term.flush().unwrap_or_default();
+    }
+
     /// set PROGRESS_BAR bar target to hidden
     pub(super) fn hide_progress_bars(&self) {
         PROGRESS_BAR.set_draw_target(ProgressDrawTarget::hidden());
@@ -162,7 +292,7 @@ impl Menu {
 
     /// set PROGRESS_BAR bar target to hidden
     pub(super) fn show_progress_bars(&self) {
-        PROGRESS_BAR.set_draw_target(ProgressDrawTarget::stdout());
+        PROGRESS_BAR.set_draw_target(draw_target());
     }
 
     /// Wrapper around console's Term::clear_screen and flush
@@ -173,7 +303,7 @@ impl Menu {
 
     /// Wrapper around console's Term::write_line
     pub(super) fn println(&self, msg: &str) {
-        self.term.write_line(msg).unwrap_or_default();
+        self.term.write_line(&themed(msg)).unwrap_or_default();
     }
 
     /// Helper for parsing a usize from a str
@@ -245,13 +375,33 @@ impl Menu {
 
         match line.chars().next().unwrap_or('_').to_ascii_lowercase() {
             'c' => {
-                // cancel command; start by determining if -f was used
-                let force = line.contains("-f");
+                // cancel command; start by determining if -f was used, only as a token of its
+                // own, so that hosts like my-files.example.com are left alone
+                let force = line.split_whitespace().any(|token| token == "-f");
+                let line = line
+                    .split_whitespace()
+                    .filter(|token| *token != "-f")
+                    .collect::<Vec<_>>()
+                    .join(" ");
+
+                let host_re = Regex::new(r"^[cC][hH][ostOST]*(\s|$)").unwrap();
+
+                if host_re.is_match(&line) {
+                    // cancel host command; what's left after removing c[h]ost is either a scan
+                    // id or a host
+                    let line = host_re.replace(&line, "");
+                    let selection = line.trim();
+
+                    if selection.is_empty() {
+                        return None;
+                    }
+
+                    return Some(MenuCmd::CancelHost(selection.to_string(), force));
+                }
 
                 // then remove c[ancel] from the command so it can be passed to the number
                 // splitter
                 let re = Regex::new(r"^[cC][ancelANCEL]*").unwrap();
-                let line = line.replace("-f", "");
                 let line = re.replace(&line, "").to_string();
 
                 Some(MenuCmd::Cancel(self.split_to_nums(&line), force))
@@ -297,6 +447,29 @@ impl Menu {
 
                 Some(MenuCmd::RemoveFilter(indices))
             }
+            't' if line[1..].starts_with(['e', 'E']) => {
+                // test filters command; remove te[st-filters] from the command, the rest should
+                // be a url
+                let re = Regex::new(r"^[tT][eE][stfilterSTFILTER-]*").unwrap();
+                let line = re.replace(line, "").trim().to_string();
+
+                if line.is_empty() {
+                    return None;
+                }
+
+                Some(MenuCmd::TestFilters(line))
+            }
+            't' => {
+                // threads command; remove t[hreads] from the command, the rest should be a
+                // single number
+                let re = Regex::new(r"^[tT][hreadsHREADS]*").unwrap();
+                let line = re.replace(line, "").to_string();
+
+                match self.str_to_usize(&line) {
+                    0 => None,
+                    threads => Some(MenuCmd::SetThreads(threads)),
+                }
+            }
             _ => {
                 // invalid input
                 None
@@ -304,6 +477,47 @@ impl Menu {
         }
     }
 
+    /// print which filters would suppress the response from `url`, then wait for the user to
+    /// acknowledge the results
+    ///
+    /// `filters` holds (filter id, filter, matched value) for each filter that matched
+    pub(super) fn print_filter_test(
+        &self,
+        url: &str,
+        response: &str,
+        filters: &[(usize, String, String)],
+    ) {
+        self.println(&format!("{}: {url}", style("Filter test").bright().blue()));
+        self.println(&format!("  response: {response}"));
+
+        if filters.is_empty() {
+            self.println(&format!(
+                "  {}",
+                style("not filtered; the response would be reported").green()
+            ));
+        }
+
+        for (id, filter, value) in filters {
+            self.println(&format!(
+                "  {} by {id:3}: {filter} (matched {value})",
+                style("filtered").red()
+            ));
+        }
+
+        self.print_border();
+        self.println("Press enter to return to the menu...");
+        self.term.read_line().unwrap_or_default();
+    }
+
+    /// Given a host, confirm with user that we should cancel all of its scans
+    pub(super) fn confirm_host_cancellation(&self, host: &str, num_scans: usize) -> char {
+        self.println(&format!(
+            "You sure you wanna cancel all {num_scans} active scan(s) against {host}? [Y/n]"
+        ));
+
+        self.term.read_char().unwrap_or('n')
+    }
+
     /// Given a url, confirm with user that we should cancel
     pub(super) fn confirm_cancellation(&self, url: &str) -> char {
         self.println(&format!(
@@ -314,6 +528,29 @@ impl Menu {
     }
 }
 
+/// resident memory used by this process, in bytes
+#[cfg(target_os = "linux")]
+pub(super) fn memory_usage() -> Option<u64> {
+    let status = std::fs::read_to_string("/proc/self/status").ok()?;
+
+    let kilobytes = status
+        .lines()
+        .find_map(|line| line.strip_prefix("VmRSS:"))?
+        .trim()
+        .trim_end_matches("kB")
+        .trim()
+        .parse::<u64>()
+        .ok()?;
+
+    Some(kilobytes * 1024)
+}
+
+/// resident memory used by this process, in bytes
+#[cfg(not(target_os = "linux"))]
+pub(super) fn memory_usage() -> Option<u64> {
+    None
+}
+
 /// Default implementation for Menu
 impl Default for Menu {
     /// return Menu::new as default
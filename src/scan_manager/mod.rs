@@ -1,3 +1,4 @@
+mod checkpoint;
 mod scan_container;
 mod response_container;
 mod scan;
@@ -8,6 +9,7 @@ mod state;
 #[cfg(test)]
 mod tests;
 
+pub use checkpoint::WordCheckpoint;
 use menu::Menu;
 pub use menu::{MenuCmd, MenuCmdResult};
 pub use order::ScanOrder;
@@ -15,4 +17,8 @@ pub use response_container::FeroxResponses;
 pub use scan::{FeroxScan, ScanStatus, ScanType};
 pub use scan_container::{FeroxScans, PAUSE_SCAN};
 pub use state::FeroxState;
-pub use utils::{resume_scan, start_max_time_thread};
+pub use utils::{
+    confirm_request_volume, confirm_resume, find_state_file, load_denylist_file,
+    request_volume_warning, resume_scan, start_denylist_watcher, start_max_time_thread,
+    suggested_split, DEFAULT_MAX_DIR_REQUESTS,
+};
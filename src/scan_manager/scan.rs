@@ -4,7 +4,7 @@ use crate::{
     event_handlers::Handles,
     progress::update_style,
     progress::{add_bar, BarType},
-    scan_manager::utils::determine_bar_type,
+    scan_manager::{scan_container::normalize_url, utils::determine_bar_type},
     scanner::PolicyTrigger,
 };
 use anyhow::Result;
@@ -13,7 +13,7 @@ use indicatif::ProgressBar;
 use serde::{ser::SerializeStruct, Deserialize, Deserializer, Serialize, Serializer};
 use serde_json::Value;
 use std::{
-    collections::HashMap,
+    collections::{HashMap, HashSet},
     fmt,
     sync::{Arc, Mutex},
     time::Instant,
@@ -45,7 +45,8 @@ pub struct FeroxScan {
     /// The URL that to be scanned
     pub(super) url: String,
 
-    /// A url used solely for comparison to other URLs
+    /// A url used solely for comparison to other URLs, see `normalize_url`; computed once, when
+    /// the scan is created
     pub(super) normalized_url: String,
 
     /// The type of scan
@@ -64,6 +65,14 @@ pub struct FeroxScan {
     /// deserialization: sets self.requests_made_so_far to this field
     pub(super) requests_made_so_far: u64,
 
+    /// Index into the wordlist that a previously saved scan got to, only used during
+    /// deserialization; None when the state file predates word-level checkpoints
+    pub(super) word_index: Option<u64>,
+
+    /// how far through the wordlist this scan has gotten, saved as `word_index`; None until the
+    /// scan starts
+    pub(super) checkpoint: Mutex<Option<WordCheckpoint>>,
+
     /// Status of this scan
     pub status: Mutex<ScanStatus>,
 
@@ -85,6 +94,12 @@ pub struct FeroxScan {
     /// tracker for total number of errors encountered by the FeroxScan instance
     pub(super) errors: AtomicUsize,
 
+    /// tracker for the number of responses this scan reported (i.e. that weren't filtered)
+    pub(super) findings: AtomicUsize,
+
+    /// which extensions have turned anything up in this directory (--adaptive-extensions)
+    pub(super) extension_probe: Mutex<ExtensionProbe>,
+
     /// tracker for the time at which this scan was started
     pub(super) start_time: Instant,
 
@@ -93,6 +108,23 @@ pub struct FeroxScan {
 
     /// handles object pointer
     pub(super) handles: Option<Arc<Handles>>,
+
+    /// bounds the number of this scan's requests that may be in-flight at once, only present
+    /// when --inflight-per-dir was used
+    pub(super) inflight: Option<Arc<sync::Semaphore>>,
+}
+
+/// evidence gathered about each extension within a single directory (--adaptive-extensions)
+#[derive(Debug, Default)]
+pub(super) struct ExtensionProbe {
+    /// number of completed requests made with each extension
+    requests: HashMap<String, usize>,
+
+    /// extensions that produced at least one reportable response
+    hits: HashSet<String>,
+
+    /// extensions that missed on every sampled word, and are no longer requested
+    dropped: HashSet<String>,
 }
 
 /// Default implementation for FeroxScan
@@ -108,6 +140,8 @@ impl Default for FeroxScan {
             handles: None,
             num_requests: 0,
             requests_made_so_far: 0,
+            word_index: None,
+            checkpoint: Mutex::new(None),
             scan_order: ScanOrder::Latest,
             url: String::new(),
             normalized_url: String::new(),
@@ -115,10 +149,13 @@ impl Default for FeroxScan {
             scan_type: ScanType::File,
             output_level: Default::default(),
             errors: Default::default(),
+            findings: Default::default(),
+            extension_probe: Default::default(),
             status_429s: Default::default(),
             status_403s: Default::default(),
             start_time: Instant::now(),
             visible: AtomicBool::new(true),
+            inflight: None,
         }
     }
 }
@@ -195,6 +232,46 @@ impl FeroxScan {
         self.requests_made_so_far
     }
 
+    /// getter for the wordlist index reached during previously saved scans (i.e. --resume-from
+    /// used); None if the state file didn't record one
+    pub fn word_index(&self) -> Option<u64> {
+        self.word_index
+    }
+
+    /// restart the scan's checkpoint from the given index into the wordlist
+    pub fn start_checkpoint(&self, base: usize) {
+        if let Ok(mut checkpoint) = self.checkpoint.lock() {
+            *checkpoint = Some(WordCheckpoint::new(base));
+        }
+    }
+
+    /// mark the word at `index` (relative to where this run of the scan started) as completed
+    pub fn complete_word(&self, index: usize) {
+        if let Ok(mut guard) = self.checkpoint.lock() {
+            if let Some(checkpoint) = guard.as_mut() {
+                checkpoint.complete(index);
+            }
+        }
+    }
+
+    /// index into the wordlist that a resumed scan should start from
+    ///
+    /// a scan that hasn't started yet keeps the index it was loaded with from a state file; None
+    /// when that state file didn't record one, leaving the offset to be worked out from
+    /// `requests_made_so_far`
+    pub fn checkpoint(&self) -> Option<u64> {
+        let started = self.checkpoint.lock().ok().and_then(|guard| {
+            guard
+                .as_ref()
+                .map(|checkpoint| checkpoint.position() as u64)
+        });
+
+        started.or(self.word_index).or_else(|| {
+            // nothing has been requested, so the scan starts from the top either way
+            (self.requests_made_so_far == 0).then_some(0)
+        })
+    }
+
     /// small wrapper to set the JoinHandle
     pub async fn set_task(&self, task: JoinHandle<()>) -> Result<()> {
         let mut guard = self.task.lock().await;
@@ -300,9 +377,14 @@ impl FeroxScan {
         visibility: bool,
         handles: Arc<Handles>,
     ) -> Arc<Self> {
+        let inflight = match handles.config.inflight_per_dir {
+            0 => None,
+            limit => Some(Arc::new(sync::Semaphore::new(limit))),
+        };
+
         Arc::new(Self {
             url: url.to_string(),
-            normalized_url: format!("{}/", url.trim_end_matches('/')),
+            normalized_url: normalize_url(url, handles.config.case_insensitive),
             scan_type,
             scan_order,
             num_requests,
@@ -310,10 +392,20 @@ impl FeroxScan {
             progress_bar: Mutex::new(pb),
             visible: AtomicBool::new(visibility),
             handles: Some(handles),
+            inflight,
             ..Default::default()
         })
     }
 
+    /// wait until fewer than --inflight-per-dir of this scan's requests are outstanding
+    ///
+    /// the returned permit should be held until the response has been read; None is returned
+    /// when no limit is imposed
+    pub async fn acquire_inflight(&self) -> Option<sync::OwnedSemaphorePermit> {
+        let inflight = self.inflight.as_ref()?;
+        inflight.clone().acquire_owned().await.ok()
+    }
+
     /// Mark the scan as complete and stop the scan's progress bar
     pub fn finish(&self, active_bars: usize) -> Result<()> {
         self.set_status(ScanStatus::Complete)?;
@@ -396,6 +488,48 @@ impl FeroxScan {
         self.errors.fetch_add(1, Ordering::Relaxed);
     }
 
+    /// increment the value in question by 1
+    pub(crate) fn add_finding(&self) {
+        self.findings.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// return the number of responses reported by this scan
+    pub fn findings(&self) -> usize {
+        self.findings.load(Ordering::Relaxed)
+    }
+
+    /// record the outcome of a request made with the given extension in this directory
+    /// (--adaptive-extensions)
+    ///
+    /// once `sample_size` requests have been made with an extension that never turned anything
+    /// up, the extension is dropped; true is returned when this call is the one that dropped it
+    pub fn record_extension_probe(&self, extension: &str, hit: bool, sample_size: usize) -> bool {
+        let Ok(mut probe) = self.extension_probe.lock() else {
+            return false;
+        };
+
+        if hit {
+            if !probe.hits.contains(extension) {
+                probe.hits.insert(extension.to_string());
+            }
+            return false;
+        }
+
+        let requests = if let Some(requests) = probe.requests.get_mut(extension) {
+            *requests += 1;
+            *requests
+        } else {
+            probe.requests.insert(extension.to_string(), 1);
+            1
+        };
+
+        if requests >= sample_size && !probe.hits.contains(extension) {
+            return probe.dropped.insert(extension.to_string());
+        }
+
+        false
+    }
+
     /// simple wrapper to call the appropriate getter based on the given PolicyTrigger
     pub fn num_errors(&self, trigger: PolicyTrigger) -> usize {
         match trigger {
@@ -471,7 +605,7 @@ impl Serialize for FeroxScan {
     where
         S: Serializer,
     {
-        let mut state = serializer.serialize_struct("FeroxScan", 6)?;
+        let mut state = serializer.serialize_struct("FeroxScan", 8)?;
 
         state.serialize_field("id", &self.id)?;
         state.serialize_field("url", &self.url)?;
@@ -480,6 +614,7 @@ impl Serialize for FeroxScan {
         state.serialize_field("status", &self.status)?;
         state.serialize_field("num_requests", &self.num_requests)?;
         state.serialize_field("requests_made_so_far", &self.requests())?;
+        state.serialize_field("word_index", &self.checkpoint())?;
 
         state.end()
     }
@@ -543,6 +678,11 @@ impl<'de> Deserialize<'de> for FeroxScan {
                         scan.requests_made_so_far = requests_made_so_far;
                     }
                 }
+                "word_index" => {
+                    if let Some(word_index) = value.as_u64() {
+                        scan.word_index = Some(word_index);
+                    }
+                }
                 _ => {}
             }
         }
@@ -596,13 +736,15 @@ impl Default for ScanStatus {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::config::Configuration;
     use std::thread::sleep;
     use tokio::time::Duration;
 
     #[test]
     /// ensure that num_errors returns the correct values for the given PolicyTrigger
     ///
-    /// covers tests for add_[403,429,error] and the related getters in addition to num_errors
+    /// covers tests for add_[403,429,error,finding] and the related getters in addition to
+    /// num_errors
     fn num_errors_returns_correct_values() {
         let scan = FeroxScan::new(
             "http://localhost",
@@ -625,6 +767,9 @@ mod tests {
         assert_eq!(scan.num_errors(PolicyTrigger::Errors), 1);
         assert_eq!(scan.num_errors(PolicyTrigger::Status403), 2);
         assert_eq!(scan.num_errors(PolicyTrigger::Status429), 3);
+
+        scan.add_finding();
+        assert_eq!(scan.findings(), 1);
     }
 
     #[test]
@@ -646,8 +791,13 @@ mod tests {
             status_403s: Default::default(),
             status_429s: Default::default(),
             errors: Default::default(),
+            findings: Default::default(),
+            extension_probe: Default::default(),
             start_time: Instant::now(),
             handles: None,
+            inflight: None,
+            word_index: None,
+            checkpoint: Default::default(),
         };
 
         let pb = scan.progress_bar();
@@ -717,4 +867,57 @@ mod tests {
         assert!(!scan.is_complete());
         assert!(!scan.is_cancelled());
     }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+    /// acquire_inflight should only hand out --inflight-per-dir permits at once
+    async fn acquire_inflight_respects_limit() {
+        let config = Configuration {
+            inflight_per_dir: 2,
+            ..Default::default()
+        };
+
+        let scan = FeroxScan::new(
+            "http://localhost",
+            ScanType::Directory,
+            ScanOrder::Latest,
+            1000,
+            OutputLevel::Default,
+            None,
+            true,
+            Arc::new(Handles::for_testing(None, Some(Arc::new(config))).0),
+        );
+
+        let first = scan.acquire_inflight().await;
+        let second = scan.acquire_inflight().await;
+
+        assert!(first.is_some());
+        assert!(second.is_some());
+
+        // limit reached, a third request has to wait
+        let third = tokio::time::timeout(Duration::from_millis(50), scan.acquire_inflight()).await;
+        assert!(third.is_err());
+
+        drop(first);
+
+        let third = tokio::time::timeout(Duration::from_millis(50), scan.acquire_inflight()).await;
+        assert!(third.unwrap().is_some());
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+    /// without --inflight-per-dir, acquire_inflight never waits
+    async fn acquire_inflight_without_limit() {
+        let scan = FeroxScan::new(
+            "http://localhost",
+            ScanType::Directory,
+            ScanOrder::Latest,
+            1000,
+            OutputLevel::Default,
+            None,
+            true,
+            Arc::new(Handles::for_testing(None, None).0),
+        );
+
+        assert!(scan.inflight.is_none());
+        assert!(scan.acquire_inflight().await.is_none());
+    }
 }
@@ -1,6 +1,7 @@
+use super::menu::{memory_usage, PauseStatus};
 use super::scan::ScanType;
 use super::*;
-use crate::event_handlers::Handles;
+use crate::event_handlers::{Handles, TermInputHandler};
 use crate::filters::{
     EmptyFilter, LinesFilter, RegexFilter, SimilarityFilter, SizeFilter, StatusCodeFilter,
     WildcardFilter, WordsFilter,
@@ -9,28 +10,30 @@ use crate::traits::FeroxFilter;
 use crate::Command::AddFilter;
 use crate::{
     banner::Banner,
-    config::OutputLevel,
-    progress::PROGRESS_PRINTER,
-    progress::{add_bar, BarType},
+    config::{Denier, OutputLevel},
+    progress::{add_bar, print_above_bars, BarType},
+    response::FeroxResponse,
     scan_manager::utils::determine_bar_type,
     scan_manager::{MenuCmd, MenuCmdResult},
     scanner::RESPONSES,
+    semaphore::DynamicSemaphore,
     traits::FeroxSerialize,
-    Command, SLEEP_DURATION,
+    utils::{make_request, parse_url_with_raw_path},
+    Command, DEFAULT_METHOD, SLEEP_DURATION,
 };
 use anyhow::Result;
 use console::style;
-use reqwest::StatusCode;
+use reqwest::{StatusCode, Url};
 use serde::{ser::SerializeSeq, Serialize, Serializer};
 use std::{
-    collections::HashSet,
+    collections::{HashMap, HashSet},
     convert::TryInto,
     fs::File,
     io::BufReader,
     ops::Index,
     sync::{
         atomic::{AtomicBool, AtomicUsize, Ordering},
-        Arc, Mutex, RwLock,
+        Arc, Mutex, RwLock, Weak,
     },
     thread::sleep,
 };
@@ -41,9 +44,89 @@ use tokio::time::{self, Duration};
 /// when pausing a scan
 static INTERACTIVE_BARRIER: AtomicUsize = AtomicUsize::new(0);
 
+/// How often the paused-status panel is redrawn while the scan management menu is open
+const PAUSE_PANEL_REFRESH: Duration = Duration::from_secs(1);
+
 /// Atomic boolean flag, used to determine whether or not a scan should pause or resume
 pub static PAUSE_SCAN: AtomicBool = AtomicBool::new(false);
 
+/// hostname of the given url; urls without a scheme (i.e. a bare host typed into the menu) are
+/// treated as http
+fn host_of(url: &str) -> Option<String> {
+    let parsed = if url.contains("://") {
+        Url::parse(url)
+    } else {
+        Url::parse(&format!("http://{url}"))
+    };
+
+    parsed.ok()?.host_str().map(|host| host.to_lowercase())
+}
+
+/// the given directory (which must end in a /) followed by each of its parents, closest first,
+/// each ending in a /
+fn directory_and_parents(directory: &str) -> impl Iterator<Item = &str> {
+    directory
+        .match_indices('/')
+        .rev()
+        .map(move |(index, _)| &directory[..=index])
+}
+
+/// normalize a url for comparison against the urls of existing scans
+///
+/// percent-encoded unreserved characters are decoded, the remaining percent-encodings have
+/// their hex digits uppercased, and the scheme and host are lowercased (RFC 3986 6.2.2). When
+/// `case_insensitive` is true, the entire url is lowercased. The result always ends in a /
+pub(super) fn normalize_url(url: &str, case_insensitive: bool) -> String {
+    let hex_value = |byte: u8| (byte as char).to_digit(16).map(|digit| digit as u8);
+
+    let bytes = url.trim_end_matches('/').as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len() + 1);
+    let mut index = 0;
+
+    while index < bytes.len() {
+        if bytes[index] == b'%' && index + 2 < bytes.len() {
+            if let (Some(high), Some(low)) =
+                (hex_value(bytes[index + 1]), hex_value(bytes[index + 2]))
+            {
+                let value = high << 4 | low;
+
+                if value.is_ascii_alphanumeric() || b"-._~".contains(&value) {
+                    decoded.push(value);
+                } else {
+                    decoded.push(b'%');
+                    decoded.extend(bytes[index + 1..index + 3].to_ascii_uppercase());
+                }
+
+                index += 3;
+                continue;
+            }
+        }
+
+        decoded.push(bytes[index]);
+        index += 1;
+    }
+
+    decoded.push(b'/');
+
+    // only ascii bytes were decoded, so the result is still valid utf-8
+    let mut normalized = String::from_utf8_lossy(&decoded).to_string();
+
+    if case_insensitive {
+        return normalized.to_lowercase();
+    }
+
+    if let Some(scheme_end) = normalized.find("://") {
+        let authority_end = normalized[scheme_end + 3..]
+            .find('/')
+            .map_or(normalized.len(), |end| scheme_end + 3 + end);
+
+        let authority = normalized[..authority_end].to_lowercase();
+        normalized.replace_range(..authority_end, &authority);
+    }
+
+    normalized
+}
+
 /// Container around a locked hashset of `FeroxScan`s, adds wrappers for insertion and searching
 #[derive(Debug, Default)]
 pub struct FeroxScans {
@@ -60,11 +143,53 @@ pub struct FeroxScans {
     /// whether or not the user passed --silent|--quiet on the command line
     output_level: OutputLevel,
 
+    /// output levels of targets read from stdin with a level prefix (i.e. `quiet http://...`);
+    /// keyed by the target's url (without a trailing slash)
+    target_output_levels: RwLock<HashMap<String, OutputLevel>>,
+
     /// vector of extensions discovered and collected during scans
     pub(crate) collected_extensions: RwLock<HashSet<String>>,
 
+    /// initial targets that were detected as single-page applications when --spa-mode is used
+    spa_targets: RwLock<Vec<Url>>,
+
+    /// per-directory decisions of whether or not to append / to requests when --auto-slash is
+    /// used; keyed by the directory's url (with a trailing slash)
+    slash_decisions: RwLock<HashMap<String, bool>>,
+
+    /// extensions dropped by --adaptive-extensions for each directory, keyed by the directory's
+    /// url (with a trailing slash); the evidence behind each drop is kept on the directory's
+    /// FeroxScan
+    dropped_extensions: RwLock<HashMap<String, HashSet<String>>>,
+
+    /// number of extensions dropped by --adaptive-extensions so far, used to tell when cached
+    /// results of `dropped_extensions_for` are stale
+    extension_drops: AtomicUsize,
+
+    /// hosts whose scans were cancelled from the menu; no new scans or extracted links are
+    /// added for them
+    cancelled_hosts: RwLock<HashSet<String>>,
+
+    /// origins (scheme://host:port) found to be fronted by an interstitial page; requests to
+    /// them are made with the --interstitial-bypass headers
+    interstitial_origins: RwLock<HashSet<String>>,
+
+    /// entries most recently read from --dont-scan-file
+    file_denylist: RwLock<Vec<Denier>>,
+
     /// stored value for Configuration.limit_bars
     bar_limit: usize,
+
+    /// stored value for Configuration.case_insensitive
+    case_insensitive: bool,
+
+    /// number of concurrent requests per scan, as set from the interactive menu; 0 means the
+    /// value from Configuration.threads is still in use
+    threads: AtomicUsize,
+
+    /// request limiters of all scans that have been started; used to change the number of
+    /// in-flight requests of running scans
+    limiters: Mutex<Vec<Weak<DynamicSemaphore>>>,
 }
 
 /// Serialize implementation for FeroxScans
@@ -97,10 +222,11 @@ impl Serialize for FeroxScans {
 /// Implementation of `FeroxScans`
 impl FeroxScans {
     /// given an OutputLevel, create a new FeroxScans object
-    pub fn new(output_level: OutputLevel, bar_limit: usize) -> Self {
+    pub fn new(output_level: OutputLevel, bar_limit: usize, case_insensitive: bool) -> Self {
         Self {
             output_level,
             bar_limit,
+            case_insensitive,
             ..Default::default()
         }
     }
@@ -160,6 +286,11 @@ impl FeroxScans {
                     // without the line below
                     deser_scan.output_level = self.output_level;
 
+                    // older state files only trimmed the trailing slash, and --case-insensitive
+                    // may differ between runs
+                    deser_scan.normalized_url =
+                        normalize_url(&deser_scan.url, self.case_insensitive);
+
                     self.insert(Arc::new(deser_scan));
                 }
             }
@@ -229,7 +360,7 @@ impl FeroxScans {
     /// on the given URL
     pub fn contains(&self, url: &str) -> bool {
         if let Ok(scans) = self.scans.read() {
-            let normalized = format!("{}/", url.trim_end_matches('/'));
+            let normalized = normalize_url(url, self.case_insensitive);
 
             for scan in scans.iter() {
                 if scan.normalized_url == normalized {
@@ -243,7 +374,7 @@ impl FeroxScans {
     /// Find and return a `FeroxScan` based on the given URL
     pub fn get_scan_by_url(&self, url: &str) -> Option<Arc<FeroxScan>> {
         if let Ok(guard) = self.scans.read() {
-            let normalized = format!("{}/", url.trim_end_matches('/'));
+            let normalized = normalize_url(url, self.case_insensitive);
 
             for scan in guard.iter() {
                 if scan.normalized_url == normalized {
@@ -411,6 +542,169 @@ impl FeroxScans {
         num_cancelled
     }
 
+    /// Given a scan id or a host, cancel every active scan against that host and keep any more
+    /// from being added
+    async fn cancel_host(&self, selection: &str, force: bool) -> usize {
+        let menu_pause_duration = Duration::from_millis(SLEEP_DURATION);
+
+        let url = match selection.parse::<usize>() {
+            Ok(num) => match self.scans.read() {
+                Ok(u_scans) if num < u_scans.len() => u_scans.index(num).url.clone(),
+                _ => {
+                    self.menu
+                        .println(&format!("The number {num} is not a valid choice."));
+                    sleep(menu_pause_duration);
+                    return 0;
+                }
+            },
+            Err(_) => selection.to_string(),
+        };
+
+        let Some(host) = host_of(&url) else {
+            self.menu
+                .println(&format!("Could not determine a host from {selection}"));
+            sleep(menu_pause_duration);
+            return 0;
+        };
+
+        let selected: Vec<_> = match self.scans.read() {
+            Ok(u_scans) => u_scans
+                .iter()
+                .filter(|scan| matches!(scan.scan_type, ScanType::Directory))
+                .filter(|scan| scan.is_active())
+                .filter(|scan| host_of(&scan.url).as_deref() == Some(host.as_str()))
+                .cloned()
+                .collect(),
+            Err(..) => return 0,
+        };
+
+        let input = if force {
+            'y'
+        } else {
+            self.menu.confirm_host_cancellation(&host, selected.len())
+        };
+
+        if input != 'y' && input != '\n' {
+            self.menu.println("Ok, doing nothing...");
+            sleep(menu_pause_duration);
+            return 0;
+        }
+
+        if let Ok(mut hosts) = self.cancelled_hosts.write() {
+            hosts.insert(host.clone());
+        }
+
+        let mut num_cancelled = 0_usize;
+
+        for scan in selected {
+            self.menu.println(&format!("Stopping {}...", scan.url));
+
+            let active_bars = self.number_of_bars();
+            scan.abort(active_bars)
+                .await
+                .unwrap_or_else(|e| log::warn!("Could not cancel task: {}", e));
+
+            if scan.is_not_started() {
+                // queued scans may not have a task to abort yet; marking them cancelled keeps
+                // them from being picked up later
+                scan.set_status(ScanStatus::Cancelled).unwrap_or_default();
+            }
+
+            let pb = scan.progress_bar();
+            num_cancelled +=
+                (pb.length().unwrap_or(0) as usize).saturating_sub(pb.position() as usize);
+        }
+
+        sleep(menu_pause_duration);
+
+        num_cancelled
+    }
+
+    /// replace the entries read from --dont-scan-file
+    pub fn set_file_denylist(&self, deniers: Vec<Denier>) {
+        log::trace!("enter: set_file_denylist({:?})", deniers);
+
+        if let Ok(mut denylist) = self.file_denylist.write() {
+            *denylist = deniers;
+        }
+
+        log::trace!("exit: set_file_denylist");
+    }
+
+    /// entries most recently read from --dont-scan-file
+    pub fn file_denylist(&self) -> Vec<Denier> {
+        self.file_denylist
+            .read()
+            .map(|denylist| denylist.clone())
+            .unwrap_or_default()
+    }
+
+    /// whether or not --dont-scan-file currently holds any entries
+    pub fn has_file_denylist(&self) -> bool {
+        self.file_denylist
+            .read()
+            .map(|denylist| !denylist.is_empty())
+            .unwrap_or(false)
+    }
+
+    /// cancel every running or queued directory scan whose url matches one of the given
+    /// entries; returns the urls of the cancelled scans, along with the number of requests they
+    /// had left, which need to come off of the overall progress bar
+    pub async fn cancel_denied_scans(&self, deniers: &[Denier]) -> (Vec<String>, usize) {
+        log::trace!("enter: cancel_denied_scans({:?})", deniers);
+
+        let denied: Vec<_> = self
+            .get_active_scans()
+            .into_iter()
+            .filter(|scan| {
+                parse_url_with_raw_path(scan.url().trim_end_matches('/'))
+                    .map(|url| deniers.iter().any(|denier| denier.matches(&url)))
+                    .unwrap_or(false)
+            })
+            .collect();
+
+        let mut cancelled = Vec::with_capacity(denied.len());
+        let mut num_cancelled = 0_usize;
+
+        for scan in denied {
+            let active_bars = self.number_of_bars();
+            scan.abort(active_bars)
+                .await
+                .unwrap_or_else(|e| log::warn!("Could not cancel task: {}", e));
+
+            if scan.is_not_started() {
+                // queued scans may not have a task to abort yet; marking them cancelled keeps
+                // them from being picked up later
+                scan.set_status(ScanStatus::Cancelled).unwrap_or_default();
+            }
+
+            let pb = scan.progress_bar();
+            num_cancelled +=
+                (pb.length().unwrap_or(0) as usize).saturating_sub(pb.position() as usize);
+
+            cancelled.push(scan.url().to_string());
+        }
+
+        log::trace!(
+            "exit: cancel_denied_scans -> ({:?}, {})",
+            cancelled,
+            num_cancelled
+        );
+        (cancelled, num_cancelled)
+    }
+
+    /// determine whether the given url belongs to a host that was cancelled from the menu
+    pub fn is_host_cancelled(&self, url: &str) -> bool {
+        let Some(host) = host_of(url) else {
+            return false;
+        };
+
+        self.cancelled_hosts
+            .read()
+            .map(|hosts| hosts.contains(&host))
+            .unwrap_or(false)
+    }
+
     fn display_filters(&self, handles: Arc<Handles>) {
         let mut printed = 0;
 
@@ -432,11 +726,119 @@ impl FeroxScans {
         }
     }
 
+    /// request the given url and show the user which of the active filters would suppress
+    /// its response, and why
+    async fn test_filters(&self, url: &str, handles: Arc<Handles>) {
+        log::trace!("enter: test_filters({}, {:?})", url, handles);
+
+        let method = handles
+            .config
+            .methods
+            .first()
+            .map_or(DEFAULT_METHOD, String::as_str);
+
+        let parsed = match Url::parse(url) {
+            Ok(parsed) => parsed,
+            Err(e) => {
+                self.menu
+                    .print_filter_test(url, &format!("invalid url: {e}"), &[]);
+                log::trace!("exit: test_filters");
+                return;
+            }
+        };
+
+        let response = make_request(
+            &handles.config.client,
+            &parsed,
+            method,
+            None,
+            handles.config.output_level,
+            &handles.config,
+            &handles.stats,
+        )
+        .await;
+
+        match response {
+            Ok(response) => {
+                let response =
+                    FeroxResponse::from(response, url, method, handles.config.output_level).await;
+
+                let summary = format!(
+                    "{} {} {}l {}w {}c",
+                    response.method(),
+                    response.status().as_u16(),
+                    response.line_count(),
+                    response.word_count(),
+                    response.content_length()
+                );
+
+                let filters = handles.filters.data.explain(&response);
+                self.menu.print_filter_test(url, &summary, &filters);
+            }
+            Err(e) => {
+                self.menu
+                    .print_filter_test(url, &format!("request failed: {e:#}"), &[]);
+            }
+        }
+
+        log::trace!("exit: test_filters");
+    }
+
+    /// gather a snapshot of the scan's progress for the paused-status panel
+    async fn pause_status(&self, handles: Arc<Handles>) -> PauseStatus {
+        let scans = {
+            // lock is dropped before awaiting anything below, keeping the future Send
+            let mut scans = Vec::new();
+
+            if let Ok(guard) = self.scans.read() {
+                for (i, scan) in guard.iter().enumerate() {
+                    if !matches!(scan.scan_type, ScanType::Directory) || scan.is_cancelled() {
+                        continue;
+                    }
+
+                    if let Ok(bar) = scan.progress_bar.lock() {
+                        let (position, length) = bar
+                            .as_ref()
+                            .map(|bar| (bar.position(), bar.length().unwrap_or(0)))
+                            .unwrap_or_default();
+
+                        scans.push((i, scan.url().to_string(), position, length));
+                    }
+                }
+            }
+
+            scans
+        };
+
+        let (tx, rx) = oneshot::channel::<Duration>();
+
+        let elapsed = if handles.stats.send(Command::QueryElapsed(tx)).is_ok() {
+            rx.await.unwrap_or_default()
+        } else {
+            Duration::default()
+        };
+
+        PauseStatus {
+            elapsed,
+            findings: handles.stats.data.resources_discovered(),
+            requests: handles.stats.data.requests(),
+            memory: memory_usage(),
+            scans,
+        }
+    }
+
     /// CLI menu that allows for interactive cancellation of recursed-into directories
     async fn interactive_menu(&self, handles: Arc<Handles>) -> Option<MenuCmdResult> {
         self.menu.hide_progress_bars();
         self.menu.clear_screen();
         self.menu.print_header();
+
+        // the live panel sits directly beneath the 3-line header
+        let panel_row = 3;
+        let panel_lines = self
+            .menu
+            .print_pause_panel(&self.pause_status(handles.clone()).await);
+
         let (tx, rx) = oneshot::channel::<Duration>();
         if handles.stats.send(Command::QueryOverallBarEta(tx)).is_ok() {
             if let Ok(y) = rx.await {
@@ -448,7 +850,27 @@ impl FeroxScans {
         self.display_filters(handles.clone());
         self.menu.print_footer();
 
-        let menu_cmd = if let Ok(line) = self.menu.term.read_line() {
+        // the user's input is read on a blocking thread, leaving this one free to keep the
+        // paused-status panel up to date in the meantime
+        let term = self.menu.term.clone();
+        let mut input = tokio::task::spawn_blocking(move || term.read_line());
+
+        let mut refresh = time::interval_at(
+            time::Instant::now() + PAUSE_PANEL_REFRESH,
+            PAUSE_PANEL_REFRESH,
+        );
+
+        let line = loop {
+            tokio::select! {
+                line = &mut input => break line,
+                _ = refresh.tick() => {
+                    let status = self.pause_status(handles.clone()).await;
+                    self.menu.redraw_pause_panel(&status, panel_row, panel_lines);
+                }
+            }
+        };
+
+        let menu_cmd = if let Ok(Ok(line)) = line {
             self.menu.get_command_input_from_user(&line)
         } else {
             None
@@ -458,8 +880,38 @@ impl FeroxScans {
             Some(MenuCmd::Cancel(indices, should_force)) => {
                 // cancel the things
                 let num_cancelled = self.cancel_scans(indices, should_force).await;
+
+                handles
+                    .flush_output("scan cancelled")
+                    .await
+                    .unwrap_or_else(|e| log::warn!("Could not flush output file: {}", e));
+
+                TermInputHandler::checkpoint_state(handles.clone(), "scan cancelled").await;
+
                 Some(MenuCmdResult::NumCancelled(num_cancelled))
             }
+            Some(MenuCmd::CancelHost(selection, should_force)) => {
+                let num_cancelled = self.cancel_host(&selection, should_force).await;
+
+                let reason = format!("scans of {selection} cancelled");
+
+                handles
+                    .flush_output(&reason)
+                    .await
+                    .unwrap_or_else(|e| log::warn!("Could not flush output file: {}", e));
+
+                TermInputHandler::checkpoint_state(handles.clone(), &reason).await;
+
+                Some(MenuCmdResult::NumCancelled(num_cancelled))
+            }
+            Some(MenuCmd::SetThreads(threads)) => {
+                self.set_threads(threads);
+                None
+            }
+            Some(MenuCmd::TestFilters(url)) => {
+                self.test_filters(&url, handles.clone()).await;
+                None
+            }
             Some(MenuCmd::AddUrl(url)) => Some(MenuCmdResult::Url(url)),
             Some(MenuCmd::AddFilter(filter)) => Some(MenuCmdResult::Filter(filter)),
             Some(MenuCmd::RemoveFilter(indices)) => {
@@ -520,7 +972,7 @@ impl FeroxScans {
                     // calling this from a resumed state)
                     response.output_level = self.output_level;
                 }
-                PROGRESS_PRINTER.println(response.as_str());
+                print_above_bars(&response.as_str());
             }
         }
     }
@@ -602,6 +1054,39 @@ impl FeroxScans {
         }
     }
 
+    /// number of concurrent requests per scan, as set from the interactive menu (0 if unchanged)
+    pub fn threads(&self) -> usize {
+        self.threads.load(Ordering::Relaxed)
+    }
+
+    /// keep track of a scan's request limiter so its capacity can be changed by `set_threads`
+    pub(crate) fn register_limiter(&self, limiter: &Arc<DynamicSemaphore>) {
+        if let Ok(mut limiters) = self.limiters.lock() {
+            // scans that have completed have dropped their limiter, no need to keep them around
+            limiters.retain(|limiter| limiter.strong_count() > 0);
+            limiters.push(Arc::downgrade(limiter));
+        }
+    }
+
+    /// change the number of concurrent requests for all running scans, as well as any scans
+    /// started afterwards
+    pub fn set_threads(&self, threads: usize) {
+        if threads == 0 {
+            log::warn!("number of threads must be greater than 0; ignoring");
+            return;
+        }
+
+        self.threads.store(threads, Ordering::Relaxed);
+
+        if let Ok(limiters) = self.limiters.lock() {
+            for limiter in limiters.iter().filter_map(Weak::upgrade) {
+                limiter.resize(threads);
+            }
+        }
+
+        log::info!("concurrent requests per scan set to {}", threads);
+    }
+
     /// set the bar length of FeroxScans
     pub fn set_bar_length(&self, bar_length: u64) {
         if let Ok(mut guard) = self.bar_length.lock() {
@@ -627,8 +1112,10 @@ impl FeroxScans {
             0
         };
 
+        let output_level = self.output_level_for(url);
+
         let active_bars = self.number_of_bars();
-        let bar_type = determine_bar_type(self.bar_limit, active_bars, self.output_level);
+        let bar_type = determine_bar_type(self.bar_limit, active_bars, output_level);
 
         let bar = match scan_type {
             ScanType::Directory => {
@@ -648,7 +1135,7 @@ impl FeroxScans {
             scan_type,
             scan_order,
             bar_length,
-            self.output_level,
+            output_level,
             bar,
             is_visible,
             handles,
@@ -795,16 +1282,213 @@ impl FeroxScans {
         log::trace!("exit: add_discovered_extension -> {}", extension_added);
         extension_added
     }
+
+    /// mark the given target as a single-page application
+    pub fn add_spa_target(&self, target: &str) {
+        log::trace!("enter: add_spa_target({})", target);
+
+        let Ok(parsed) = Url::parse(target) else {
+            log::warn!(
+                "could not parse {} as a url, not marking it as an SPA",
+                target
+            );
+            return;
+        };
+
+        if let Ok(mut targets) = self.spa_targets.write() {
+            if !targets.contains(&parsed) {
+                targets.push(parsed);
+            }
+        }
+
+        log::trace!("exit: add_spa_target");
+    }
+
+    /// override the output level of scans and findings beneath the given target
+    pub fn set_target_output_level(&self, target: &str, output_level: OutputLevel) {
+        log::trace!(
+            "enter: set_target_output_level({}, {:?})",
+            target,
+            output_level
+        );
+
+        if let Ok(mut levels) = self.target_output_levels.write() {
+            levels.insert(target.trim_end_matches('/').to_string(), output_level);
+        }
+
+        log::trace!("exit: set_target_output_level");
+    }
+
+    /// move the output level given for a bare hostname over to the url(s) its scheme probe
+    /// resolved it to, since the scans are keyed by those
+    pub fn rekey_target_output_level(&self, host: &str, targets: &[String]) {
+        log::trace!("enter: rekey_target_output_level({}, {:?})", host, targets);
+
+        if let Ok(mut levels) = self.target_output_levels.write() {
+            if let Some(output_level) = levels.remove(host.trim_end_matches('/')) {
+                for target in targets {
+                    levels.insert(target.trim_end_matches('/').to_string(), output_level);
+                }
+            }
+        }
+
+        log::trace!("exit: rekey_target_output_level");
+    }
+
+    /// the output level overriding the global one for the given target, if any
+    pub fn target_output_level(&self, target: &str) -> Option<OutputLevel> {
+        self.target_output_levels
+            .read()
+            .ok()?
+            .get(target.trim_end_matches('/'))
+            .copied()
+    }
+
+    /// output level to use for the given url; the level of the most specific target the url
+    /// lives beneath, or the global --quiet|--silent level when no target overrides it
+    pub fn output_level_for(&self, url: &str) -> OutputLevel {
+        if let Ok(levels) = self.target_output_levels.read() {
+            if let Some((_, level)) = levels
+                .iter()
+                .filter(|(target, _)| {
+                    // http://localhost shouldn't claim http://localhost.example
+                    url.strip_prefix(target.as_str())
+                        .is_some_and(|rest| rest.is_empty() || rest.starts_with(['/', '?']))
+                })
+                .max_by_key(|(target, _)| target.len())
+            {
+                return *level;
+            }
+        }
+
+        self.output_level
+    }
+
+    /// determine whether the given url lives beneath a target that was detected as a
+    /// single-page application
+    ///
+    /// the url must share the target's origin, and its path must be the target's path or sit
+    /// beneath it (http://localhost/app doesn't claim http://localhost/application)
+    pub fn is_spa_url(&self, url: &str) -> bool {
+        let Ok(parsed) = Url::parse(url) else {
+            return false;
+        };
+
+        let Ok(targets) = self.spa_targets.read() else {
+            return false;
+        };
+
+        targets.iter().any(|target| {
+            if target.origin() != parsed.origin() {
+                return false;
+            }
+
+            let prefix = target.path().trim_end_matches('/');
+
+            parsed
+                .path()
+                .strip_prefix(prefix)
+                .is_some_and(|rest| rest.is_empty() || rest.starts_with('/'))
+        })
+    }
+
+    /// mark the origin of the given url as fronted by an interstitial page
+    pub fn add_interstitial_origin(&self, url: &str) {
+        log::trace!("enter: add_interstitial_origin({})", url);
+
+        if let Ok(parsed) = Url::parse(url) {
+            if let Ok(mut origins) = self.interstitial_origins.write() {
+                origins.insert(parsed.origin().ascii_serialization());
+            }
+        }
+
+        log::trace!("exit: add_interstitial_origin");
+    }
+
+    /// determine whether requests to the given url should carry the --interstitial-bypass
+    /// headers
+    pub fn bypasses_interstitial(&self, url: &str) -> bool {
+        let Ok(parsed) = Url::parse(url) else {
+            return false;
+        };
+
+        self.interstitial_origins
+            .read()
+            .map(|origins| origins.contains(&parsed.origin().ascii_serialization()))
+            .unwrap_or(false)
+    }
+
+    /// record whether or not requests made beneath the given directory should have a / appended
+    pub fn set_add_slash(&self, directory: &str, add_slash: bool) {
+        log::trace!("enter: set_add_slash({}, {})", directory, add_slash);
+
+        if let Ok(mut decisions) = self.slash_decisions.write() {
+            decisions.insert(format!("{}/", directory.trim_end_matches('/')), add_slash);
+        }
+
+        log::trace!("exit: set_add_slash");
+    }
+
+    /// stop requesting the given extension in the given directory and everything beneath it
+    /// (--adaptive-extensions)
+    pub fn drop_extension(&self, directory: &str, extension: &str) {
+        if let Ok(mut dropped) = self.dropped_extensions.write() {
+            dropped
+                .entry(format!("{}/", directory.trim_end_matches('/')))
+                .or_default()
+                .insert(extension.to_string());
+
+            self.extension_drops.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    /// number of extensions dropped so far; results of `dropped_extensions_for` are only stale
+    /// once this changes
+    pub fn extension_drops(&self) -> usize {
+        self.extension_drops.load(Ordering::Relaxed)
+    }
+
+    /// extensions that are no longer requested beneath the given directory, because they were
+    /// dropped for it or for one of its parents (--adaptive-extensions)
+    pub fn dropped_extensions_for(&self, directory: &str) -> HashSet<String> {
+        let directory = format!("{}/", directory.trim_end_matches('/'));
+
+        let Ok(dropped) = self.dropped_extensions.read() else {
+            return HashSet::new();
+        };
+
+        directory_and_parents(&directory)
+            .filter_map(|probed| dropped.get(probed))
+            .flatten()
+            .cloned()
+            .collect()
+    }
+
+    /// determine whether a / should be appended to requests made beneath the given directory
+    ///
+    /// directories that haven't been tested inherit the decision made for their closest tested
+    /// parent; `None` is returned when no parent has been tested either
+    pub fn add_slash_for(&self, directory: &str) -> Option<bool> {
+        let directory = format!("{}/", directory.trim_end_matches('/'));
+
+        let Ok(decisions) = self.slash_decisions.read() else {
+            return None;
+        };
+
+        // one lookup per path segment, closest parent first, instead of walking every decision
+        directory_and_parents(&directory).find_map(|tested| decisions.get(tested).copied())
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::config::Configuration;
 
     #[test]
     /// unknown extension should be added to collected_extensions
     fn unknown_extension_is_added_to_collected_extensions() {
-        let scans = FeroxScans::new(OutputLevel::Default, 0);
+        let scans = FeroxScans::new(OutputLevel::Default, 0, false);
 
         assert_eq!(0, scans.collected_extensions.read().unwrap().len());
 
@@ -817,7 +1501,7 @@ mod tests {
     #[test]
     /// known extension should not be added to collected_extensions
     fn known_extension_is_added_to_collected_extensions() {
-        let scans = FeroxScans::new(OutputLevel::Default, 0);
+        let scans = FeroxScans::new(OutputLevel::Default, 0, false);
         scans
             .collected_extensions
             .write()
@@ -831,4 +1515,63 @@ mod tests {
         assert!(!added);
         assert_eq!(1, scans.collected_extensions.read().unwrap().len());
     }
+
+    #[test]
+    /// encoded unreserved characters are decoded, other encodings keep their meaning, and the
+    /// path's case is only folded when asked to
+    fn normalize_url_handles_encodings_and_case() {
+        assert_eq!(
+            normalize_url("HTTP://LocalHost/%7Eadmin/%41pi%2f/", false),
+            "http://localhost/~admin/Api%2F/"
+        );
+        assert_eq!(
+            normalize_url("http://localhost/Admin", true),
+            "http://localhost/admin/"
+        );
+        assert_eq!(
+            normalize_url("http://localhost/100%", false),
+            "http://localhost/100%/"
+        );
+    }
+
+    #[test]
+    /// equivalent urls are recognized as already being scanned
+    fn contains_matches_equivalent_urls() {
+        let scans = FeroxScans::new(OutputLevel::Default, 0, false);
+        let scan = FeroxScan::new(
+            "http://localhost/Admin",
+            ScanType::Directory,
+            ScanOrder::Latest,
+            0,
+            OutputLevel::Default,
+            None,
+            true,
+            Arc::new(Handles::for_testing(None, None).0),
+        );
+        scans.insert(scan);
+
+        assert!(scans.contains("http://localhost/%41dmin/"));
+        assert!(!scans.contains("http://localhost/admin"));
+
+        // both are built from the same configuration outside of tests
+        let config = Configuration {
+            case_insensitive: true,
+            ..Default::default()
+        };
+
+        let scans = FeroxScans::new(OutputLevel::Default, 0, true);
+        scans.insert(FeroxScan::new(
+            "http://localhost/Admin",
+            ScanType::Directory,
+            ScanOrder::Latest,
+            0,
+            OutputLevel::Default,
+            None,
+            true,
+            Arc::new(Handles::for_testing(None, Some(Arc::new(config))).0),
+        ));
+
+        assert!(scans.contains("http://localhost/admin"));
+        assert!(scans.get_scan_by_url("http://LOCALHOST/ADMIN/").is_some());
+    }
 }
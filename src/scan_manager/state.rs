@@ -3,7 +3,7 @@ use crate::filters::FeroxFilters;
 use crate::{config::Configuration, statistics::Stats, traits::FeroxSerialize, utils::fmt_err};
 use anyhow::{Context, Result};
 use serde::Serialize;
-use std::collections::HashSet;
+use std::collections::{BTreeMap, HashSet};
 use std::sync::Arc;
 
 /// Data container for (de)?serialization of multiple items
@@ -26,6 +26,10 @@ pub struct FeroxState {
 
     /// runtime filters, as they may differ from original config
     filters: Arc<FeroxFilters>,
+
+    /// --meta pairs, sorted by key; also part of `config`, but repeated here so that tools
+    /// reading a state file don't have to dig through the configuration for them
+    meta: BTreeMap<String, String>,
 }
 
 /// implementation of FeroxState
@@ -43,6 +47,8 @@ impl FeroxState {
             Err(_) => HashSet::new(),
         };
 
+        let meta = config.meta.clone().into_iter().collect();
+
         Self {
             scans,
             config,
@@ -50,6 +56,7 @@ impl FeroxState {
             statistics,
             collected_extensions,
             filters,
+            meta,
         }
     }
 }
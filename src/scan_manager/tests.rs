@@ -4,23 +4,262 @@ use crate::filters::{
     WordsFilter,
 };
 use crate::{
-    config::{Configuration, OutputLevel},
+    config::{Configuration, Denier, OutputLevel},
     event_handlers::Handles,
     response::FeroxResponse,
     scanner::RESPONSES,
+    semaphore::DynamicSemaphore,
     statistics::Stats,
     traits::FeroxSerialize,
+    wordlists::Wordlist,
     SLEEP_DURATION, VERSION,
 };
 use indicatif::ProgressBar;
 use predicates::prelude::*;
 use regex::Regex;
+use std::collections::HashSet;
 use std::sync::atomic::AtomicBool;
 use std::sync::{atomic::Ordering, Arc};
 use std::thread::sleep;
 use std::time::Instant;
 use tokio::time::{self, Duration};
 
+#[test]
+/// directories expected to take more than --max-dir-requests requests are warned about, unless
+/// the check is turned off
+fn request_volume_warning_respects_threshold() {
+    let handles = |max_dir_requests| {
+        let config = Configuration {
+            extensions: vec![String::from("php"), String::from("html")],
+            methods: vec![String::from("GET"), String::from("POST")],
+            max_dir_requests,
+            ..Default::default()
+        };
+
+        Handles::for_testing(None, Some(Arc::new(config))).0
+    };
+
+    // 1 word x (2 extensions + the bare word) x 2 methods
+    let warning = request_volume_warning(&handles(5)).unwrap();
+    assert!(warning.contains("expected to take 6 requests"));
+    assert!(warning.contains("more than --max-dir-requests 5"));
+
+    assert!(request_volume_warning(&handles(6)).is_none());
+    assert!(request_volume_warning(&handles(0)).is_none());
+}
+
+#[test]
+/// scans over --max-dir-requests are split into passes of fewer extensions when that's enough,
+/// and into a shorter wordlist otherwise
+fn suggested_split_prefers_extension_passes() {
+    let handles = |num_words: usize, extensions: &[&str], max_dir_requests| {
+        let config = Configuration {
+            extensions: extensions.iter().map(|ext| ext.to_string()).collect(),
+            max_dir_requests,
+            ..Default::default()
+        };
+
+        let mut handles = Handles::for_testing(None, Some(Arc::new(config))).0;
+        handles.wordlist = Arc::new(Wordlist::from(
+            (0..num_words)
+                .map(|word| word.to_string())
+                .collect::<Vec<_>>(),
+        ));
+        handles
+    };
+
+    // 10 words x (2 extensions + the bare word) per pass
+    assert_eq!(
+        suggested_split(&handles(10, &["php", "html", "js", "txt"], 30)).unwrap(),
+        "run 2 passes of at most 30 requests each: -x php,html | -x js,txt"
+    );
+
+    // a single extension per pass is still too many: 10 words x (2 extensions + the bare word)
+    // only fits 4 words at a time
+    assert_eq!(
+        suggested_split(&handles(10, &["php", "html"], 12)).unwrap(),
+        "split the wordlist into 3 parts of at most 4 words each"
+    );
+
+    assert!(suggested_split(&handles(1, &["php", "html"], 1)).is_none());
+}
+
+#[test]
+/// urls beneath a target marked as a single-page application are reported as such, everything
+/// else isn't
+fn ferox_scans_tracks_spa_targets() {
+    let scans = FeroxScans::default();
+
+    scans.add_spa_target("http://localhost/");
+
+    assert!(scans.is_spa_url("http://localhost/api/"));
+    assert!(scans.is_spa_url("http://localhost"));
+    assert!(scans.is_spa_url("HTTP://LOCALHOST:80/api/"));
+    assert!(!scans.is_spa_url("http://otherhost/api/"));
+    assert!(!scans.is_spa_url("http://localhost.example/api/"));
+    assert!(!scans.is_spa_url("http://localhost:8080/api/"));
+    assert!(!scans.is_spa_url("https://localhost/api/"));
+
+    scans.add_spa_target("http://otherhost/app");
+
+    assert!(scans.is_spa_url("http://otherhost/app"));
+    assert!(scans.is_spa_url("http://otherhost/app/js/"));
+    assert!(!scans.is_spa_url("http://otherhost/application/"));
+}
+
+#[test]
+/// urls beneath a target with its own output level use it, the most specific target wins, and
+/// everything else uses the global level
+fn ferox_scans_resolves_output_level_per_target() {
+    let scans = FeroxScans::new(OutputLevel::Quiet, 0, false);
+
+    scans.set_target_output_level("http://localhost/", OutputLevel::Silent);
+    scans.set_target_output_level("http://localhost/api", OutputLevel::Default);
+
+    assert_eq!(
+        scans.output_level_for("http://localhost/js/app.js"),
+        OutputLevel::Silent
+    );
+    assert_eq!(
+        scans.output_level_for("http://localhost/api/users"),
+        OutputLevel::Default
+    );
+    assert_eq!(
+        scans.output_level_for("http://localhost/apis"),
+        OutputLevel::Silent
+    );
+    assert_eq!(
+        scans.output_level_for("http://localhost.example/"),
+        OutputLevel::Quiet
+    );
+    assert_eq!(
+        scans.target_output_level("http://localhost"),
+        Some(OutputLevel::Silent)
+    );
+    assert_eq!(scans.target_output_level("http://otherhost"), None);
+}
+
+#[test]
+/// the level given for a bare hostname moves to the urls its scheme probe resolved it to
+fn ferox_scans_rekeys_output_level_of_bare_host() {
+    let scans = FeroxScans::new(OutputLevel::Default, 0, false);
+
+    scans.set_target_output_level("localhost", OutputLevel::Silent);
+    scans.rekey_target_output_level(
+        "localhost",
+        &[
+            "http://localhost".to_string(),
+            "https://localhost".to_string(),
+        ],
+    );
+
+    assert_eq!(scans.target_output_level("localhost"), None);
+    assert_eq!(
+        scans.output_level_for("http://localhost/js/app.js"),
+        OutputLevel::Silent
+    );
+    assert_eq!(
+        scans.output_level_for("https://localhost/js/app.js"),
+        OutputLevel::Silent
+    );
+
+    // nothing to move
+    scans.rekey_target_output_level("otherhost", &["http://otherhost".to_string()]);
+    assert_eq!(scans.target_output_level("http://otherhost"), None);
+}
+
+#[test]
+/// urls that share an origin with one marked as fronted by an interstitial page bypass it,
+/// other schemes, ports, and hosts don't
+fn ferox_scans_tracks_interstitial_origins() {
+    let scans = FeroxScans::default();
+
+    scans.add_interstitial_origin("https://localhost/");
+
+    assert!(scans.bypasses_interstitial("https://localhost/admin/login"));
+    assert!(scans.bypasses_interstitial("https://LOCALHOST:443/"));
+    assert!(!scans.bypasses_interstitial("http://localhost/admin"));
+    assert!(!scans.bypasses_interstitial("https://localhost:8443/admin"));
+    assert!(!scans.bypasses_interstitial("https://otherhost/"));
+}
+
+#[test]
+/// untested directories inherit the add-slash decision of their closest tested parent
+fn ferox_scans_add_slash_decisions_are_inherited() {
+    let scans = FeroxScans::default();
+
+    assert_eq!(scans.add_slash_for("http://localhost/api/"), None);
+
+    scans.set_add_slash("http://localhost/api", true);
+    scans.set_add_slash("http://localhost/api/v1/", false);
+
+    assert_eq!(scans.add_slash_for("http://localhost/api/"), Some(true));
+    assert_eq!(
+        scans.add_slash_for("http://localhost/api/docs/"),
+        Some(true)
+    );
+    assert_eq!(
+        scans.add_slash_for("http://localhost/api/v1/users"),
+        Some(false)
+    );
+    assert_eq!(scans.add_slash_for("http://localhost/apis/"), None);
+}
+
+#[test]
+/// extensions are only dropped after a full sample without hits
+fn ferox_scan_drops_extensions_without_hits() {
+    let scan = FeroxScan::default();
+
+    for _ in 0..2 {
+        assert!(!scan.record_extension_probe("php", false, 3));
+        assert!(!scan.record_extension_probe("js", false, 3));
+    }
+
+    scan.record_extension_probe("js", true, 3);
+
+    // third miss completes php's sample; js already hit, so it's kept
+    assert!(scan.record_extension_probe("php", false, 3));
+    assert!(!scan.record_extension_probe("js", false, 3));
+
+    // already dropped
+    assert!(!scan.record_extension_probe("php", false, 3));
+}
+
+#[test]
+/// dropped extensions apply beneath the directory they were dropped for, and each drop is
+/// counted so cached results can be refreshed
+fn ferox_scans_dropped_extensions_apply_beneath_directory() {
+    let scans = FeroxScans::default();
+
+    assert_eq!(scans.extension_drops(), 0);
+    assert!(scans
+        .dropped_extensions_for("http://localhost/api/")
+        .is_empty());
+
+    scans.drop_extension("http://localhost/api", "php");
+    scans.drop_extension("http://localhost/api/v1/", "js");
+
+    assert_eq!(scans.extension_drops(), 2);
+
+    let expected: HashSet<_> = [String::from("php")].into();
+
+    assert_eq!(
+        scans.dropped_extensions_for("http://localhost/api"),
+        expected
+    );
+
+    let expected: HashSet<_> = [String::from("php"), String::from("js")].into();
+
+    assert_eq!(
+        scans.dropped_extensions_for("http://localhost/api/v1/users"),
+        expected
+    );
+    assert!(scans.dropped_extensions_for("http://localhost/").is_empty());
+    assert!(scans
+        .dropped_extensions_for("http://localhost/apis/")
+        .is_empty());
+}
+
 #[test]
 /// test that ScanType's default is File
 fn default_scantype_is_file() {
@@ -298,6 +537,82 @@ fn ferox_scan_deserialize() {
     ));
     assert_eq!(fs_three.num_requests, 42);
     assert_eq!(fs.id, "057016a14769414aac9a7a62707598cb");
+    assert_eq!(fs.word_index(), None);
+
+    let fs_json_four = r#"{"id":"057016a14769414aac9a7a62707598cb","url":"https://spiritanimal.com","scan_type":"Directory","status":"Running","requests_made_so_far":500,"word_index":123}"#;
+    let fs_four: FeroxScan = serde_json::from_str(fs_json_four).unwrap();
+    assert_eq!(fs_four.word_index(), Some(123));
+}
+
+#[test]
+/// the checkpoint only moves past words once every word before them has completed
+fn word_checkpoint_tracks_contiguous_progress() {
+    let mut checkpoint = WordCheckpoint::new(100);
+    assert_eq!(checkpoint.position(), 100);
+
+    checkpoint.complete(1);
+    checkpoint.complete(2);
+    assert_eq!(checkpoint.position(), 100);
+
+    checkpoint.complete(0);
+    assert_eq!(checkpoint.position(), 103);
+
+    // already counted
+    checkpoint.complete(1);
+    checkpoint.complete(4);
+    assert_eq!(checkpoint.position(), 103);
+
+    checkpoint.complete(3);
+    assert_eq!(checkpoint.position(), 105);
+}
+
+#[test]
+/// a scan's checkpoint is saved as its word index, relative to the full wordlist
+fn ferox_scan_serializes_checkpoint() {
+    let fs = FeroxScan::new(
+        "https://spiritanimal.com",
+        ScanType::Directory,
+        ScanOrder::Latest,
+        0,
+        OutputLevel::Default,
+        None,
+        true,
+        Arc::new(Handles::for_testing(None, None).0),
+    );
+
+    fs.start_checkpoint(10);
+    fs.complete_word(0);
+    fs.complete_word(1);
+    fs.complete_word(3);
+
+    assert_eq!(fs.checkpoint(), Some(12));
+    assert!(serde_json::to_string(&*fs)
+        .unwrap()
+        .contains(r#""word_index":12"#));
+}
+
+#[test]
+/// a scan loaded from a state file, but not yet restarted, saves the word index it was loaded
+/// with instead of starting over
+fn ferox_scan_unstarted_round_trips_word_index() {
+    let json = r#"{"id":"057016a14769414aac9a7a62707598cb","url":"https://spiritanimal.com","scan_type":"Directory","status":"Cancelled","num_requests":4000,"requests_made_so_far":500,"word_index":123}"#;
+    let scan: FeroxScan = serde_json::from_str(json).unwrap();
+
+    assert_eq!(scan.checkpoint(), Some(123));
+
+    let resaved: FeroxScan = serde_json::from_str(&serde_json::to_string(&scan).unwrap()).unwrap();
+    assert_eq!(resaved.word_index(), Some(123));
+    assert_eq!(resaved.requests_made_so_far(), 500);
+
+    // older state files without a word index keep relying on requests_made_so_far
+    let json = r#"{"id":"057016a14769414aac9a7a62707598cb","url":"https://spiritanimal.com","scan_type":"Directory","status":"Cancelled","num_requests":4000,"requests_made_so_far":500}"#;
+    let scan: FeroxScan = serde_json::from_str(json).unwrap();
+
+    assert_eq!(scan.checkpoint(), None);
+
+    let resaved: FeroxScan = serde_json::from_str(&serde_json::to_string(&scan).unwrap()).unwrap();
+    assert_eq!(resaved.word_index(), None);
+    assert_eq!(resaved.requests_made_so_far(), 500);
 }
 
 #[test]
@@ -314,7 +629,7 @@ fn ferox_scan_serialize() {
         Arc::new(Handles::for_testing(None, None).0),
     );
     let fs_json = format!(
-        r#"{{"id":"{}","url":"https://spiritanimal.com","normalized_url":"https://spiritanimal.com/","scan_type":"Directory","status":"NotStarted","num_requests":0,"requests_made_so_far":0}}"#,
+        r#"{{"id":"{}","url":"https://spiritanimal.com","normalized_url":"https://spiritanimal.com/","scan_type":"Directory","status":"NotStarted","num_requests":0,"requests_made_so_far":0,"word_index":0}}"#,
         fs.id
     );
     assert_eq!(fs_json, serde_json::to_string(&*fs).unwrap());
@@ -335,7 +650,7 @@ fn ferox_scans_serialize() {
     );
     let ferox_scans = FeroxScans::default();
     let ferox_scans_json = format!(
-        r#"[{{"id":"{}","url":"https://spiritanimal.com","normalized_url":"https://spiritanimal.com/","scan_type":"Directory","status":"NotStarted","num_requests":0,"requests_made_so_far":0}}]"#,
+        r#"[{{"id":"{}","url":"https://spiritanimal.com","normalized_url":"https://spiritanimal.com/","scan_type":"Directory","status":"NotStarted","num_requests":0,"requests_made_so_far":0,"word_index":0}}]"#,
         ferox_scan.id
     );
     ferox_scans.scans.write().unwrap().push(ferox_scan);
@@ -437,6 +752,7 @@ fn feroxstates_feroxserialize_implementation() {
         .push(Box::new(RegexFilter {
             raw_string: ".*".to_string(),
             compiled: Regex::new(".*").unwrap(),
+            ..Default::default()
         }))
         .unwrap();
     filters
@@ -618,6 +934,11 @@ fn feroxscan_display() {
         task: tokio::sync::Mutex::new(None),
         progress_bar: std::sync::Mutex::new(None),
         errors: Default::default(),
+        findings: Default::default(),
+        extension_probe: Default::default(),
+        inflight: None,
+        word_index: None,
+        checkpoint: Default::default(),
     };
 
     let not_started = format!("{scan}");
@@ -667,7 +988,12 @@ async fn ferox_scan_abort() {
         }))),
         progress_bar: std::sync::Mutex::new(None),
         errors: Default::default(),
+        findings: Default::default(),
+        extension_probe: Default::default(),
         handles: Some(Arc::new(Handles::for_testing(None, None).0)),
+        inflight: None,
+        word_index: None,
+        checkpoint: Default::default(),
     };
 
     scan.abort(0).await.unwrap();
@@ -697,6 +1023,41 @@ fn menu_print_header_and_footer() {
     menu.show_progress_bars();
 }
 
+#[test]
+/// the paused-status panel has a summary line, a line per scan, and a border, all padded to the
+/// same width
+fn menu_pause_panel_shows_summary_and_scans() {
+    let menu = Menu::new();
+
+    let status = menu::PauseStatus {
+        elapsed: Duration::from_secs(65),
+        findings: 12,
+        requests: 3400,
+        memory: Some(42 * 1024 * 1024),
+        scans: vec![
+            (0, String::from("http://localhost/"), 50, 200),
+            (3, String::from("http://localhost/api/"), 0, 0),
+        ],
+    };
+
+    let lines: Vec<_> = menu
+        .pause_panel(&status)
+        .iter()
+        .map(|line| console::strip_ansi_codes(line).to_string())
+        .collect();
+
+    assert_eq!(lines.len(), 4);
+    assert!(lines[0].contains("paused"));
+    assert!(lines[0].contains("found: 12"));
+    assert!(lines[0].contains("requests: 3400"));
+    assert!(lines[0].contains("mem: 42.0 MiB"));
+    assert!(lines[1].contains("0:  25% (50/200) http://localhost/"));
+    assert!(lines[2].contains("3:   0% (0/0) http://localhost/api/"));
+
+    let width = console::measure_text_width(&lines[1]);
+    assert_eq!(console::measure_text_width(&lines[2]), width);
+}
+
 /// ensure command parsing from user input results int he correct MenuCmd returned
 #[test]
 fn menu_get_command_input_from_user_returns_cancel() {
@@ -722,6 +1083,106 @@ fn menu_get_command_input_from_user_returns_cancel() {
     }
 }
 
+/// ensure command parsing from user input results int he correct MenuCmd returned
+#[test]
+fn menu_get_command_input_from_user_returns_cancel_host() {
+    let menu = Menu::new();
+
+    let result = menu.get_command_input_from_user("chost -f example.com\n");
+    assert!(matches!(result, Some(MenuCmd::CancelHost(host, true)) if host == "example.com"));
+
+    let result = menu.get_command_input_from_user("ch 3\n");
+    assert!(matches!(result, Some(MenuCmd::CancelHost(id, false)) if id == "3"));
+
+    // -f only counts as a flag on its own, never as part of a host
+    let result = menu.get_command_input_from_user("chost my-files.example.com\n");
+    assert!(
+        matches!(result, Some(MenuCmd::CancelHost(host, false)) if host == "my-files.example.com")
+    );
+
+    let result = menu.get_command_input_from_user("ch my-files.example.com -f\n");
+    assert!(
+        matches!(result, Some(MenuCmd::CancelHost(host, true)) if host == "my-files.example.com")
+    );
+
+    assert!(menu.get_command_input_from_user("ch\n").is_none());
+}
+
+/// ensure command parsing from user input results int he correct MenuCmd returned
+#[test]
+fn menu_get_command_input_from_user_returns_test_filters() {
+    let menu = Menu::new();
+
+    for cmd in ["test-filters", "TEST-FILTERS", "te", "test"] {
+        let result = menu.get_command_input_from_user(&format!("{cmd} http://localhost/a\n"));
+        assert!(matches!(result, Some(MenuCmd::TestFilters(url)) if url == "http://localhost/a"));
+    }
+
+    // threads still parses, and a missing url is rejected
+    let result = menu.get_command_input_from_user("threads 20\n");
+    assert!(matches!(result, Some(MenuCmd::SetThreads(20))));
+    assert!(menu.get_command_input_from_user("te\n").is_none());
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+/// once a host is cancelled, every url on that host is reported as cancelled, regardless of
+/// scheme or port
+async fn ferox_scans_cancel_host_marks_host_cancelled() {
+    let scans = FeroxScans::default();
+
+    assert!(!scans.is_host_cancelled("http://localhost/api/"));
+
+    scans.cancel_host("LOCALHOST", true).await;
+
+    assert!(scans.is_host_cancelled("http://localhost/api/"));
+    assert!(scans.is_host_cancelled("https://localhost:8443/"));
+    assert!(!scans.is_host_cancelled("http://otherhost/"));
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+/// scans that fall under a newly added --dont-scan-file entry are cancelled, even when they're
+/// only queued, while scans outside of it are left alone
+async fn ferox_scans_cancel_denied_scans_cancels_matching_scans() {
+    let scans = FeroxScans::default();
+    let handles = Arc::new(Handles::for_testing(None, None).0);
+
+    for url in ["http://localhost/admin/", "http://localhost/api/"] {
+        scans.insert(FeroxScan::new(
+            url,
+            ScanType::Directory,
+            ScanOrder::Latest,
+            0,
+            OutputLevel::Default,
+            None,
+            true,
+            handles.clone(),
+        ));
+    }
+
+    let deniers = vec![Denier::parse("http://localhost/admin").unwrap()];
+
+    let (cancelled, num_cancelled) = scans.cancel_denied_scans(&deniers).await;
+
+    assert_eq!(cancelled, vec!["http://localhost/admin/".to_string()]);
+    assert_eq!(
+        num_cancelled,
+        scans
+            .get_scan_by_url("http://localhost/admin/")
+            .unwrap()
+            .progress_bar()
+            .length()
+            .unwrap_or(0) as usize
+    );
+    assert!(!scans
+        .get_scan_by_url("http://localhost/admin/")
+        .unwrap()
+        .is_active());
+    assert!(scans
+        .get_scan_by_url("http://localhost/api/")
+        .unwrap()
+        .is_active());
+}
+
 /// ensure command parsing from user input results int he correct MenuCmd returned
 #[test]
 fn menu_get_command_input_from_user_returns_add() {
@@ -744,6 +1205,39 @@ fn menu_get_command_input_from_user_returns_add() {
     }
 }
 
+/// ensure command parsing from user input results int he correct MenuCmd returned
+#[test]
+fn menu_get_command_input_from_user_returns_set_threads() {
+    let menu = Menu::new();
+
+    for cmd in ["threads", "Threads", "t", "T"] {
+        let result = menu.get_command_input_from_user(&format!("{cmd} 20\n"));
+        assert!(matches!(result, Some(MenuCmd::SetThreads(20))));
+    }
+
+    assert!(menu.get_command_input_from_user("threads 0\n").is_none());
+    assert!(menu.get_command_input_from_user("threads\n").is_none());
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+/// set_threads resizes the limiters of running scans and is remembered for future scans
+async fn ferox_scans_set_threads_resizes_registered_limiters() {
+    let scans = FeroxScans::default();
+    let limiter = Arc::new(DynamicSemaphore::new(50));
+
+    assert_eq!(scans.threads(), 0);
+
+    scans.register_limiter(&limiter);
+    scans.set_threads(10);
+
+    assert_eq!(scans.threads(), 10);
+    assert_eq!(limiter.capacity(), 10);
+
+    // 0 is ignored
+    scans.set_threads(0);
+    assert_eq!(scans.threads(), 10);
+}
+
 #[test]
 /// ensure spaces are trimmed and numbers are returned from split_to_nums
 fn split_to_nums_is_correct() {
@@ -845,3 +1339,22 @@ fn get_base_scan_by_url_finds_correct_scan_with_trailing_slash() {
         scan.id
     );
 }
+
+#[test]
+/// --notify-webhook carries its own credentials, so it's never written to a state file
+fn feroxstates_omit_notify_webhook() {
+    let config = Configuration {
+        notify_webhook: String::from("https://hooks.example.com/super-secret-path"),
+        ..Configuration::new().unwrap()
+    };
+
+    let ferox_state = FeroxState::new(
+        Arc::new(FeroxScans::default()),
+        Arc::new(config),
+        &RESPONSES,
+        Arc::new(Stats::new(false)),
+        Arc::new(FeroxFilters::default()),
+    );
+
+    assert!(!ferox_state.as_json().unwrap().contains("super-secret-path"));
+}
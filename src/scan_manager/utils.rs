@@ -1,15 +1,30 @@
 #[cfg(not(test))]
 use crate::event_handlers::TermInputHandler;
 use crate::{
-    config::{Configuration, OutputLevel},
-    event_handlers::Handles,
+    config::{parse_denylist, Configuration, OutputLevel},
+    event_handlers::{Command, Handles},
     parser::TIMESPEC_REGEX,
     progress::BarType,
-    scan_manager::scan::Visibility,
+    scan_manager::{scan::Visibility, PAUSE_SCAN},
     scanner::RESPONSES,
+    theme::themed,
+    utils::slugify_filename,
 };
 
-use std::{fs::File, io::BufReader, sync::Arc};
+use anyhow::{Context, Result};
+use console::style;
+use serde::{
+    de::{self, IgnoredAny, MapAccess, Visitor},
+    Deserialize, Deserializer as _,
+};
+use std::{
+    fmt,
+    fs::{read_dir, File},
+    io::{stderr, stdin, BufRead, BufReader, IsTerminal, Write},
+    path::{Path, PathBuf},
+    sync::{atomic::Ordering, Arc},
+    time::{Duration, SystemTime},
+};
 use tokio::time;
 
 /// Given a string representing some number of seconds, minutes, hours, or days, convert
@@ -57,6 +72,81 @@ pub async fn start_max_time_thread(handles: Arc<Handles>) {
     );
 }
 
+/// how often --dont-scan-file is re-read while the scan runs
+const DENYLIST_POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+/// read the entries from --dont-scan-file and hand them to FeroxScans, where they're checked
+/// alongside --dont-scan
+pub fn load_denylist_file(handles: Arc<Handles>) -> Result<()> {
+    log::trace!(
+        "enter: load_denylist_file({})",
+        handles.config.dont_scan_file
+    );
+
+    let path = &handles.config.dont_scan_file;
+
+    let contents =
+        std::fs::read_to_string(path).with_context(|| format!("Could not read {path}"))?;
+    let deniers = parse_denylist(&contents).with_context(|| format!("Could not parse {path}"))?;
+
+    handles.ferox_scans()?.set_file_denylist(deniers);
+
+    log::trace!("exit: load_denylist_file");
+    Ok(())
+}
+
+/// re-read --dont-scan-file every couple of seconds; whenever its contents change, the new
+/// entries are sent to the scan handler, which swaps them in and cancels any scans they deny
+///
+/// a file that can't be read or parsed leaves the previous entries in place, so a half-finished
+/// edit doesn't open the scan back up
+pub async fn start_denylist_watcher(handles: Arc<Handles>) {
+    log::trace!(
+        "enter: start_denylist_watcher({})",
+        handles.config.dont_scan_file
+    );
+
+    let path = handles.config.dont_scan_file.clone();
+
+    let mut previous = std::fs::read_to_string(&path).unwrap_or_default();
+    let mut interval = time::interval(DENYLIST_POLL_INTERVAL);
+
+    loop {
+        interval.tick().await;
+
+        let contents = match std::fs::read_to_string(&path) {
+            Ok(contents) => contents,
+            Err(e) => {
+                log::debug!("could not read {}: {}", path, e);
+                continue;
+            }
+        };
+
+        if contents == previous {
+            continue;
+        }
+
+        previous = contents;
+
+        match parse_denylist(&previous) {
+            Ok(deniers) => {
+                log::info!("{} changed, {} entries loaded", path, deniers.len());
+
+                if handles
+                    .send_scan_command(Command::UpdateDenylist(deniers))
+                    .is_err()
+                {
+                    // scan handler is gone, nothing left to update
+                    break;
+                }
+            }
+            Err(e) => log::warn!("Ignoring changes to {}: {:?}", path, e),
+        }
+    }
+
+    log::trace!("exit: start_denylist_watcher");
+}
+
 /// Primary logic used to load a Configuration from disk and populate the appropriate data
 /// structures
 pub fn resume_scan(filename: &str) -> Configuration {
@@ -96,6 +186,277 @@ pub fn resume_scan(filename: &str) -> Configuration {
     config
 }
 
+/// the only part of a state file's configuration needed to match it to a target
+#[derive(Deserialize)]
+struct ConfigTarget {
+    /// the target the saved scan was started against
+    #[serde(default)]
+    target_url: String,
+}
+
+/// visits the top level of a state file, stopping as soon as its `config` has been read
+struct StateFileTarget<'a>(&'a mut Option<String>);
+
+/// implementation of Visitor for StateFileTarget
+impl<'de> Visitor<'de> for StateFileTarget<'_> {
+    type Value = ();
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a feroxbuster state file")
+    }
+
+    fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        while let Some(key) = map.next_key::<String>()? {
+            if key != "config" {
+                map.next_value::<IgnoredAny>()?;
+                continue;
+            }
+
+            let config: ConfigTarget = map.next_value()?;
+            *self.0 = Some(config.target_url);
+
+            // the responses that follow can make up most of the file; erroring out is the only
+            // way to stop the deserializer short of reading them
+            return Err(de::Error::custom("stopped after the config"));
+        }
+
+        Ok(())
+    }
+}
+
+/// read the target url from the configuration saved in the given state file, without reading
+/// any further into the file than the configuration
+fn state_file_target(path: &Path) -> Option<String> {
+    let file = File::open(path).ok()?;
+    let mut target = None;
+
+    let mut deserializer = serde_json::Deserializer::from_reader(BufReader::new(file));
+    let _ = deserializer.deserialize_map(StateFileTarget(&mut target));
+
+    target
+}
+
+/// search the given directories for the most recently written state file whose saved
+/// configuration targets `target_url`; files `max_age` or older are ignored
+///
+/// state files are named by `slugify_filename`, which is lossy, so candidates are matched by
+/// name first and then confirmed against the target url in their serialized configuration
+pub fn find_state_file(target_url: &str, dirs: &[PathBuf], max_age: Duration) -> Option<PathBuf> {
+    log::trace!(
+        "enter: find_state_file({}, {:?}, {:?})",
+        target_url,
+        dirs,
+        max_age
+    );
+
+    // everything ahead of the timestamp that slugify_filename appends
+    let name = slugify_filename(target_url, "ferox", "state");
+    let prefix = match name.rsplit_once('-') {
+        Some((prefix, _)) => format!("{prefix}-"),
+        None => name,
+    };
+    let now = SystemTime::now();
+
+    let mut newest: Option<(SystemTime, PathBuf)> = None;
+
+    for dir in dirs {
+        let Ok(entries) = read_dir(dir) else {
+            continue;
+        };
+
+        for entry in entries.flatten() {
+            let name = entry.file_name().to_string_lossy().to_string();
+
+            if !name.starts_with(&prefix) || !name.ends_with(".state") {
+                continue;
+            }
+
+            let Ok(modified) = entry.metadata().and_then(|metadata| metadata.modified()) else {
+                continue;
+            };
+
+            let age = now.duration_since(modified).unwrap_or_default();
+
+            if age >= max_age || newest.as_ref().is_some_and(|(time, _)| *time >= modified) {
+                continue;
+            }
+
+            let path = entry.path();
+
+            if state_file_target(&path).as_deref() == Some(target_url) {
+                newest = Some((modified, path));
+            }
+        }
+    }
+
+    let result = newest.map(|(_, path)| path);
+
+    log::trace!("exit: find_state_file -> {:?}", result);
+    result
+}
+
+/// default for --max-dir-requests
+pub const DEFAULT_MAX_DIR_REQUESTS: usize = 1_000_000;
+
+/// warning about the number of requests each directory is expected to take, when that number is
+/// more than --max-dir-requests; None when it isn't, or the check is turned off
+pub fn request_volume_warning(handles: &Handles) -> Option<String> {
+    let threshold = handles.config.max_dir_requests;
+    let expected = handles.expected_num_requests_per_dir();
+
+    if threshold == 0 || expected <= threshold {
+        return None;
+    }
+
+    let mut breakdown = format!(
+        "{} word(s) x {} request(s) per word",
+        handles.wordlist.len(),
+        handles.expected_num_requests_multiplier()
+    );
+
+    let paired = handles.expected_num_paired_requests();
+
+    if paired > 0 {
+        breakdown.push_str(&format!(" + {paired} extension-paired request(s)"));
+    }
+
+    Some(format!(
+        "Each directory is expected to take {expected} requests ({breakdown}; {} extension(s), {} method(s)), more than --max-dir-requests {threshold}",
+        handles.config.extensions.len(),
+        handles.config.methods.len().max(1)
+    ))
+}
+
+/// a way to split a scan that's over --max-dir-requests into runs that aren't: passes with fewer
+/// -x extensions each when that's enough, otherwise a shorter wordlist; None when not even a
+/// single word fits
+///
+/// the split is only suggested, feroxbuster doesn't run the passes itself
+pub fn suggested_split(handles: &Handles) -> Option<String> {
+    let threshold = handles.config.max_dir_requests;
+    let extensions = &handles.config.extensions;
+    let paired = handles.expected_num_paired_requests();
+    let per_extension =
+        handles.config.methods.len().max(1) * handles.num_collected_extensions().max(1);
+
+    // requests each directory takes in a pass with the given number of extensions; the bare
+    // word and the extension-paired wordlists are requested in every pass
+    let pass = |num_extensions: usize| {
+        handles.wordlist.len() * (num_extensions + 1) * per_extension + paired
+    };
+
+    if let Some(per_pass) = (1..extensions.len())
+        .rev()
+        .find(|num_extensions| pass(*num_extensions) <= threshold)
+    {
+        let passes = extensions
+            .chunks(per_pass)
+            .map(|chunk| format!("-x {}", chunk.join(",")))
+            .collect::<Vec<_>>();
+
+        return Some(format!(
+            "run {} passes of at most {} requests each: {}",
+            passes.len(),
+            pass(per_pass),
+            passes.join(" | ")
+        ));
+    }
+
+    let max_words = threshold.saturating_sub(paired) / handles.expected_num_requests_multiplier();
+
+    if max_words == 0 {
+        return None;
+    }
+
+    Some(format!(
+        "split the wordlist into {} parts of at most {max_words} words each",
+        handles.wordlist.len().div_ceil(max_words)
+    ))
+}
+
+/// warn when each directory is expected to take more than --max-dir-requests requests and, when
+/// stdin is a terminal, ask the user whether or not to go ahead with the scan; the warning
+/// includes a `suggested_split` when there is one
+///
+/// returns false only when the user declined
+pub fn confirm_request_volume(handles: Arc<Handles>) -> bool {
+    log::trace!("enter: confirm_request_volume({:?})", handles);
+
+    let Some(warning) = request_volume_warning(&handles) else {
+        log::trace!("exit: confirm_request_volume -> true");
+        return true;
+    };
+
+    let warning = match suggested_split(&handles) {
+        Some(split) => format!("{warning}; to stay under it, {split}"),
+        None => format!("{warning}; reduce -x/-m or the wordlist to shrink it"),
+    };
+
+    log::warn!("{}", warning);
+
+    let silent = matches!(handles.config.output_level, OutputLevel::Silent);
+
+    if silent || !stdin().is_terminal() {
+        // nobody to ask; the scan goes ahead
+        if !silent {
+            eprintln!("{}", themed(&format!("🧮 {warning}")));
+        }
+
+        log::trace!("exit: confirm_request_volume -> true");
+        return true;
+    }
+
+    // keep the enter handler from reading stdin while the user answers
+    PAUSE_SCAN.store(true, Ordering::Release);
+
+    let prompt = format!("🧮 {}; continue anyway? [y/N] ", style(&warning).yellow());
+
+    eprint!("{}", themed(&prompt));
+    stderr().flush().unwrap_or_default();
+
+    let mut answer = String::new();
+    let confirmed = stdin().lock().read_line(&mut answer).is_ok()
+        && matches!(answer.trim().to_lowercase().as_str(), "y" | "yes");
+
+    PAUSE_SCAN.store(false, Ordering::Release);
+
+    log::trace!("exit: confirm_request_volume -> {}", confirmed);
+    confirmed
+}
+
+/// ask the user whether or not to resume the scan saved in the given state file; only prompts
+/// when stdin is a terminal, otherwise a fresh scan is started
+pub fn confirm_resume(target_url: &str, path: &Path) -> bool {
+    if !stdin().is_terminal() {
+        log::info!(
+            "found a state file for {} at {}; use --resume-from or --auto-resume to resume it",
+            target_url,
+            path.display()
+        );
+        return false;
+    }
+
+    let prompt = format!(
+        "💾 Found a state file for {} ({}); resume the saved scan? [y/N] ",
+        target_url,
+        style(path.display()).cyan()
+    );
+
+    eprint!("{}", themed(&prompt));
+    stderr().flush().unwrap_or_default();
+
+    let mut answer = String::new();
+
+    if stdin().lock().read_line(&mut answer).is_err() {
+        return false;
+    }
+
+    matches!(answer.trim().to_lowercase().as_str(), "y" | "yes")
+}
+
 /// determine the type of progress bar to display
 /// takes both --limit-bars and output-level (--quiet|--silent|etc)
 /// into account to arrive at a `BarType`
@@ -170,4 +531,72 @@ mod tests {
         let bar_type = determine_bar_type(0, 1, OutputLevel::SilentJSON);
         assert!(matches!(bar_type, BarType::Hidden));
     }
+
+    /// write a minimal state file for the given target to the given path
+    fn write_state_file(path: &Path, target_url: &str) {
+        let state = serde_json::json!({"config": {"target_url": target_url}, "scans": []});
+        std::fs::write(path, state.to_string()).unwrap();
+    }
+
+    #[test]
+    /// find_state_file should only return recent state files whose saved target matches
+    fn find_state_file_matches_target_and_age() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let other = tempfile::TempDir::new().unwrap();
+        let dirs = [tmp.path().to_path_buf(), other.path().to_path_buf()];
+        let week = Duration::from_secs(60 * 60 * 24 * 7);
+
+        assert!(find_state_file("http://localhost/", &dirs, week).is_none());
+
+        // same slug, different target
+        write_state_file(
+            &tmp.path().join("ferox-http_localhost_-1606947491.state"),
+            "http://localhost.",
+        );
+        // matching target, but not a state file
+        write_state_file(
+            &tmp.path().join("ferox-http_localhost_-1606947491.log"),
+            "http://localhost/",
+        );
+        // not json
+        std::fs::write(
+            tmp.path().join("ferox-http_localhost_-1606947492.state"),
+            "{",
+        )
+        .unwrap();
+
+        assert!(find_state_file("http://localhost/", &dirs, week).is_none());
+
+        let expected = other.path().join("ferox-http_localhost_-1606947493.state");
+        write_state_file(&expected, "http://localhost/");
+
+        assert_eq!(
+            find_state_file("http://localhost/", &dirs, week),
+            Some(expected)
+        );
+
+        // too old
+        assert!(find_state_file("http://localhost/", &dirs, Duration::ZERO).is_none());
+    }
+
+    #[test]
+    /// only the state file's config is read, whatever follows it doesn't matter
+    fn state_file_target_stops_after_config() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let path = tmp.path().join("ferox-http_localhost_-1606947491.state");
+
+        std::fs::write(
+            &path,
+            r#"{"scans":[{"id":"1"}],"config":{"type":"configuration","target_url":"http://localhost/"},"responses":[{"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            state_file_target(&path).as_deref(),
+            Some("http://localhost/")
+        );
+
+        std::fs::write(&path, r#"{"scans":[],"responses":[]}"#).unwrap();
+        assert_eq!(state_file_target(&path), None);
+    }
 }
@@ -1,15 +1,15 @@
 use std::fmt::Write as _;
 use std::sync::atomic::AtomicBool;
-use std::{ops::Deref, sync::atomic::Ordering, sync::Arc, time::Instant};
+use std::{collections::HashSet, sync::atomic::Ordering, sync::Arc, time::Instant};
 
 use anyhow::{bail, Result};
 use console::style;
 use futures::{stream, StreamExt};
 use indicatif::ProgressBar;
 use lazy_static::lazy_static;
-use tokio::sync::Semaphore;
+use tokio::sync::oneshot;
 
-use crate::filters::{create_similarity_filter, EmptyFilter, SimilarityFilter};
+use crate::filters::{create_similarity_filter, EmptyFilter, SimilarityFilter, SIM_HASHER};
 use crate::heuristics::WildcardResult;
 use crate::Command::AddFilter;
 use crate::{
@@ -19,17 +19,23 @@ use crate::{
     },
     extractor::{ExtractionTarget, ExtractorBuilder},
     heuristics,
-    scan_manager::{FeroxResponses, FeroxScans, MenuCmdResult, ScanOrder, ScanStatus, PAUSE_SCAN},
+    nlp::preprocess,
+    progress::BatchedBar,
+    scan_manager::{
+        FeroxResponses, FeroxScan, FeroxScans, MenuCmdResult, ScanOrder, ScanStatus, PAUSE_SCAN,
+    },
     scanner::requester::TF_IDF,
+    semaphore::{DynamicSemaphore, ScanLimiter},
     statistics::{
         StatError::Other,
-        StatField::{DirScanTimes, TotalExpected},
+        StatField::{DirScanTimes, QueuedScans, TotalExpected},
     },
-    utils::fmt_err,
-    Command,
+    utils::{fmt_err, parse_url_with_raw_path},
+    wordlists::Wordlist,
+    Command, SPA_API_WORDS,
 };
 
-use super::requester::Requester;
+use super::{requester::Requester, utils::constrain_collected_words};
 
 lazy_static! {
     /// Vector of FeroxResponse objects
@@ -37,6 +43,37 @@ lazy_static! {
     // todo consider removing this
 }
 
+/// counts a scan as queued for as long as it's alive, so the count is given back even if the
+/// waiting scan's task is aborted
+struct QueuedScan<'a> {
+    /// handles used to update the queued scan count
+    handles: &'a Handles,
+}
+
+/// implementation of QueuedScan
+impl<'a> QueuedScan<'a> {
+    /// count a new queued scan
+    fn new(handles: &'a Handles) -> Self {
+        handles
+            .stats
+            .send(AddToUsizeField(QueuedScans, 1))
+            .unwrap_or_else(|e| log::warn!("Could not update queued scans: {}", e));
+
+        Self { handles }
+    }
+}
+
+/// Drop implementation for QueuedScan
+impl Drop for QueuedScan<'_> {
+    /// the scan is no longer queued, whether it got its permit or was aborted
+    fn drop(&mut self) {
+        self.handles
+            .stats
+            .send(SubtractFromUsizeField(QueuedScans, 1))
+            .unwrap_or_else(|e| log::warn!("Could not update queued scans: {}", e));
+    }
+}
+
 /// check to see if `pause_flag` is set to true. when true; enter a busy loop that only exits
 /// by setting PAUSE_SCAN back to false
 async fn check_for_user_input(
@@ -118,10 +155,10 @@ pub struct FeroxScanner {
     order: ScanOrder,
 
     /// wordlist that's already been read from disk
-    wordlist: Arc<Vec<String>>,
+    wordlist: Arc<Wordlist>,
 
     /// limiter that restricts the number of active FeroxScanners
-    scan_limiter: Arc<Semaphore>,
+    scan_limiter: Arc<ScanLimiter>,
 }
 
 /// FeroxScanner implementation
@@ -130,8 +167,8 @@ impl FeroxScanner {
     pub fn new(
         target_url: &str,
         order: ScanOrder,
-        wordlist: Arc<Vec<String>>,
-        scan_limiter: Arc<Semaphore>,
+        wordlist: Arc<Wordlist>,
+        scan_limiter: Arc<ScanLimiter>,
         handles: Arc<Handles>,
     ) -> Self {
         Self {
@@ -143,47 +180,163 @@ impl FeroxScanner {
         }
     }
 
+    /// key that this scan waits on the scan limiter under; every scan shares a single key (first
+    /// come, first served) unless --interleave-targets was used, in which case scans are keyed by
+    /// their target's origin
+    fn limiter_key(&self) -> String {
+        if !self.handles.config.interleave_targets {
+            return String::new();
+        }
+
+        parse_url_with_raw_path(&self.target_url)
+            .map(|url| url.origin().ascii_serialization())
+            .unwrap_or_else(|_| self.target_url.clone())
+    }
+
+    /// hand the directories this scan turned up, held back by --recurse-threshold until its
+    /// findings were all in, back to the ScanHandler; waits until they've been decided on
+    async fn release_deferred_recursion(&self) -> Result<()> {
+        if self.handles.config.recurse_threshold == 0 {
+            return Ok(());
+        }
+
+        self.handles
+            .send_scan_command(Command::ScanFinished(self.target_url.clone()))?;
+
+        let (tx, rx) = oneshot::channel::<bool>();
+        self.handles.send_scan_command(Command::Sync(tx))?;
+        rx.await?;
+
+        Ok(())
+    }
+
     /// produces and awaits tasks (mp of mpsc); responsible for making requests
+    ///
+    /// `leading_words` are requested ahead of `looping_words`; when `checkpoint` is given,
+    /// completed words from `looping_words` advance the scan's wordlist checkpoint
     async fn stream_requests(
         &self,
-        looping_words: Arc<Vec<String>>,
+        leading_words: Vec<String>,
+        looping_words: Arc<Wordlist>,
         progress_bar: ProgressBar,
         scanned_urls: Arc<FeroxScans>,
         requester: Arc<Requester>,
+        checkpoint: Option<Arc<FeroxScan>>,
     ) {
         log::trace!("enter: stream_requests(params too verbose to print)");
 
-        let producers = stream::iter(looping_words.deref().to_owned())
-            .map(|word| {
-                let pb = progress_bar.clone(); // progress bar is an Arc around internal state
-                let scanned_urls_clone = scanned_urls.clone();
-                let requester_clone = requester.clone();
-                let handles_clone = self.handles.clone();
-                (
-                    tokio::spawn(async move {
-                        // for every word in the wordlist, check to see if user has pressed enter
-                        // in order to go into the interactive menu
-                        check_for_user_input(&PAUSE_SCAN, scanned_urls_clone, handles_clone).await;
-
-                        // after checking for user input, send the request
-                        requester_clone
-                            .request(&word)
-                            .await
-                            .unwrap_or_else(|e| log::warn!("Requester encountered an error: {}", e))
-                    }),
-                    pb,
-                )
-            })
-            .for_each_concurrent(self.handles.config.threads, |(resp, bar)| async move {
-                match resp.await {
-                    Ok(_) => {
-                        let increment_len = self.handles.expected_num_requests_multiplier() as u64;
-                        bar.inc(increment_len);
+        // the number of in-flight requests is governed by a semaphore instead of a fixed
+        // concurrency limit, so that it can be changed mid-scan from the interactive menu
+        let threads = match scanned_urls.threads() {
+            0 => self.handles.config.threads,
+            threads => threads,
+        };
+
+        let limiter = Arc::new(DynamicSemaphore::new(threads));
+        scanned_urls.register_limiter(&limiter);
+
+        // words paired with a single extension (-x ext:FILE) are requested after the main wordlist,
+        // and only with their paired extension
+        let paired_words =
+            self.handles
+                .extension_wordlists
+                .iter()
+                .flat_map(|(extension, words)| {
+                    words
+                        .iter()
+                        .map(move |word| (word.to_string(), Some(extension.to_owned())))
+                });
+
+        let paired_increment = self.handles.config.methods.len().max(1) as u64;
+
+        let cancellation = self.handles.cancellation_token();
+
+        // completed words are tallied and applied to the scan's bar at most once per refresh
+        // interval, the same as the overall bar
+        let batched_bar = BatchedBar::new(progress_bar.clone());
+        let batched_bar = &batched_bar;
+
+        // words are only copied out of the shared wordlist as their requests are made
+        let producers = stream::iter(leading_words.into_iter().map(|word| (word, None, None)))
+            .chain(stream::iter(
+                looping_words
+                    .iter()
+                    .enumerate()
+                    .map(|(index, word)| (word.to_string(), None, Some(index))),
+            ))
+            .chain(stream::iter(
+                paired_words.map(|(word, extension)| (word, extension, None)),
+            ))
+            // once cancelled, no new requests are made; those already in flight still finish
+            .take_until(cancellation.cancelled())
+            .then(
+                |(word, extension, index): (String, Option<String>, Option<usize>)| {
+                    let scanned_urls_clone = scanned_urls.clone();
+                    let requester_clone = requester.clone();
+                    let handles_clone = self.handles.clone();
+                    let limiter_clone = limiter.clone();
+                    let is_paired = extension.is_some();
+
+                    async move {
+                        // a request isn't spawned until a permit is available
+                        let permit = limiter_clone.acquire().await.ok();
+
+                        (
+                            tokio::spawn(async move {
+                                // held until the request completes
+                                let _permit = permit;
+
+                                // for every word in the wordlist, check to see if user has pressed
+                                // enter in order to go into the interactive menu
+                                check_for_user_input(
+                                    &PAUSE_SCAN,
+                                    scanned_urls_clone,
+                                    handles_clone,
+                                )
+                                .await;
+
+                                // after checking for user input, send the request
+                                let result = match extension {
+                                    Some(extension) => {
+                                        requester_clone
+                                            .request_with_extension(&word, &extension)
+                                            .await
+                                    }
+                                    None => requester_clone.request(&word).await,
+                                };
+
+                                result.unwrap_or_else(|e| {
+                                    log::warn!("Requester encountered an error: {}", e)
+                                })
+                            }),
+                            is_paired,
+                            index,
+                        )
                     }
-                    Err(e) => {
-                        log::warn!("error awaiting a response: {}", e);
-                        self.handles.stats.send(AddError(Other)).unwrap_or_default();
-                        std::process::exit(1);
+                },
+            )
+            .for_each_concurrent(None, |(resp, is_paired, index)| {
+                let checkpoint = checkpoint.as_ref();
+
+                async move {
+                    match resp.await {
+                        Ok(_) => {
+                            let increment_len = if is_paired {
+                                paired_increment
+                            } else {
+                                self.handles.expected_num_requests_multiplier() as u64
+                            };
+                            batched_bar.inc(increment_len);
+
+                            if let (Some(scan), Some(index)) = (checkpoint, index) {
+                                scan.complete_word(index);
+                            }
+                        }
+                        Err(e) => {
+                            log::warn!("error awaiting a response: {}", e);
+                            self.handles.stats.send(AddError(Other)).unwrap_or_default();
+                            std::process::exit(1);
+                        }
                     }
                 }
             });
@@ -191,6 +344,7 @@ impl FeroxScanner {
         // await tx tasks
         log::trace!("awaiting scan producers");
         producers.await;
+        batched_bar.flush();
         log::trace!("done awaiting scan producers");
         log::trace!("exit: stream_requests");
     }
@@ -236,21 +390,53 @@ impl FeroxScanner {
 
         let progress_bar = ferox_scan.progress_bar();
 
-        // When acquire is called and the semaphore has remaining permits, the function immediately
+        // When acquire is called and the limiter has remaining permits, the function immediately
         // returns a permit. However, if no remaining permits are available, acquire (asynchronously)
         // waits until an outstanding permit is dropped, at which point, the freed permit is assigned
-        // to the caller.
-        let _permit = self.scan_limiter.acquire().await;
+        // to the next caller in line (see `ScanLimiter` for how that line is ordered).
+        //
+        // scans that have to wait are counted as queued, so the overall bar can show why nothing
+        // seems to be happening
+        let _permit = match self.scan_limiter.try_acquire() {
+            Some(permit) => Ok(permit),
+            None => {
+                let _queued = QueuedScan::new(&self.handles);
+                self.scan_limiter.acquire(&self.limiter_key()).await
+            }
+        };
+
+        if self.handles.is_cancelled() {
+            // queued scans (and any sent after cancellation) are left unfinished, so that
+            // they're picked back up by --resume-from
+            log::trace!("exit: scan_url (cancelled)");
+            return Ok(());
+        }
 
         if self.handles.config.scan_limit > 0 {
             scan_timer = Instant::now();
             progress_bar.reset();
         }
 
+        // api-centric words to prepend to the wordlist when the target is a single-page app
+        let mut spa_words: Vec<String> = Vec::new();
+
         {
             // heuristics test block:
             let test = heuristics::HeuristicTests::new(self.handles.clone());
 
+            if matches!(self.order, ScanOrder::Initial) {
+                // an interstitial page in front of the target skews every other heuristic, so
+                // it's detected first; once found, all requests that follow (baselines
+                // included) are made with the bypass headers
+                if let Err(e) = test.detect_interstitial(&self.target_url).await {
+                    log::warn!(
+                        "Could not check {} for an interstitial page: {}",
+                        self.target_url,
+                        e
+                    );
+                }
+            }
+
             if let Ok(Some(dirlist_result)) = test.directory_listing(&self.target_url).await {
                 // at this point, we have a DirListingType, and it's not the None variant
                 // which means we found directory listing based on the heuristic; now we need
@@ -307,6 +493,8 @@ impl FeroxScanner {
                     progress_bar.reset_eta();
                     progress_bar.finish_with_message(message);
 
+                    self.release_deferred_recursion().await?;
+
                     if self.handles.config.limit_bars > 0 {
                         let scans = self.handles.ferox_scans()?;
                         let num_bars = scans.number_of_bars();
@@ -320,6 +508,58 @@ impl FeroxScanner {
                 }
             }
 
+            if matches!(self.order, ScanOrder::Initial) {
+                // only initial targets are checked for a catch-all app shell; anything found
+                // beneath a single-page application is handled by the recursion limits in
+                // event_handlers/scans.rs
+                if let Ok(Some(app_shell)) = test.detect_spa(&self.target_url).await {
+                    self.handles.ferox_scans()?.add_spa_target(&self.target_url);
+
+                    if !self.handles.config.dont_filter {
+                        let hash = SIM_HASHER.create_signature(preprocess(app_shell.text()).iter());
+
+                        let sim_filter = SimilarityFilter {
+                            hash,
+                            original_url: app_shell.url().to_string(),
+                        };
+
+                        self.handles.filters.send(AddFilter(Box::new(sim_filter)))?;
+                    }
+
+                    let message = format!(
+                        "=> {} detected! {} recursion",
+                        style("SPA").blue().bright(),
+                        style("reduced").yellow()
+                    );
+                    progress_bar.set_message(message);
+
+                    // a single pass over the wordlist, crossing off the api words it already has
+                    let mut missing: HashSet<&str> = SPA_API_WORDS.into_iter().collect();
+
+                    for word in self.wordlist.iter() {
+                        if missing.remove(word) && missing.is_empty() {
+                            break;
+                        }
+                    }
+
+                    spa_words = SPA_API_WORDS
+                        .into_iter()
+                        .filter(|word| missing.contains(word))
+                        .map(String::from)
+                        .collect();
+                }
+            }
+
+            // with --auto-slash, learn whether this directory needs a / appended to its requests
+            // before the 404 detection below builds its requests
+            if let Err(e) = test.detect_slash_redirects(&self.target_url).await {
+                log::warn!(
+                    "Could not determine slash behavior of {}: {}",
+                    self.target_url,
+                    e
+                );
+            }
+
             // now that we haven't found a directory listing, we'll attempt to derive whatever
             // the server is using to respond to resources that don't exist (could be a
             // traditional 404, or a custom response)
@@ -346,21 +586,43 @@ impl FeroxScanner {
             }
         }
 
-        // Arc clones to be passed around to the various scans
-        let looping_words = self.wordlist.clone();
-
         let requester = Arc::new(Requester::from(self, ferox_scan.clone())?);
 
+        if !spa_words.is_empty() {
+            // single-page application detected; api paths are requested ahead of the wordlist
+            // since the app shell masks whatever the wordlist would otherwise find
+            let num_new_requests =
+                spa_words.len() * self.handles.expected_num_requests_multiplier();
+
+            progress_bar.inc_length(num_new_requests as u64);
+
+            self.handles
+                .stats
+                .send(AddToUsizeField(TotalExpected, num_new_requests))
+                .unwrap_or_default();
+        }
+
+        // spa words are requested ahead of the wordlist without being copied into it, and
+        // don't count towards the checkpoint
         self.stream_requests(
-            looping_words.clone(),
+            spa_words,
+            self.wordlist.clone(),
             progress_bar.clone(),
             scanned_urls.clone(),
             requester.clone(),
+            Some(ferox_scan.clone()),
         )
         .await;
 
+        if self.handles.is_cancelled() {
+            // the checkpoint has the words that were requested, the scan itself isn't finished
+            log::trace!("exit: scan_url (cancelled)");
+            return Ok(());
+        }
+
         if self.handles.config.collect_words {
-            let new_words = TF_IDF.read().unwrap().all_words();
+            let new_words =
+                constrain_collected_words(TF_IDF.read().unwrap().all_words(), &self.handles.config);
             let new_words_len = new_words.len();
 
             let cur_length = progress_bar.length().unwrap_or(0);
@@ -380,14 +642,21 @@ impl FeroxScanner {
             );
 
             self.stream_requests(
+                Vec::new(),
                 Arc::new(new_words),
                 progress_bar.clone(),
                 scanned_urls.clone(),
                 requester.clone(),
+                None,
             )
             .await;
         }
 
+        requester.clear_current_word();
+
+        // links held back by --extract-priority low get their turn now that the wordlist is done
+        extraction_tasks.push(requester.request_deferred_links().await?);
+
         self.handles.stats.send(AddToF64Field(
             DirScanTimes,
             scan_timer.elapsed().as_secs_f64(),
@@ -397,6 +666,8 @@ impl FeroxScanner {
             _ = handle.await;
         }
 
+        self.release_deferred_recursion().await?;
+
         if self.handles.config.limit_bars > 0 {
             let scans = self.handles.ferox_scans()?;
             let num_bars = scans.number_of_bars();
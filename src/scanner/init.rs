@@ -1,14 +1,55 @@
 use crate::{
-    event_handlers::{Command::AddToUsizeField, Handles},
+    config::OutputLevel,
+    event_handlers::{
+        Command::{AddToUsizeField, WriteGraphql},
+        Handles,
+    },
+    extractor::ServiceDescription,
+    graphql::{
+        is_graphql_response, GraphqlSchema, GRAPHQL_LOCATIONS, INTROSPECTION_QUERY, PROBE_QUERY,
+    },
+    heuristics::HeuristicTests,
+    nlp::{Document, PreprocessOptions, STOP_WORD_LANGUAGES},
+    openapi::{parse_spec, ApiOperation, SPEC_LOCATIONS},
+    progress::PROGRESS_PRINTER,
+    response::FeroxResponse,
+    scanner::requester::TF_IDF,
+    skip_fail,
     statistics::StatField::ExpectedPerScan,
+    traits::FeroxSerialize,
+    utils::{
+        ferox_print, fmt_err, has_denylist, logged_request, parse_url_with_raw_path,
+        send_try_recursion_command, should_deny_url,
+    },
+    DEFAULT_METHOD,
+};
+use anyhow::{Context, Result};
+use futures::{stream, StreamExt};
+use reqwest::{header::CONTENT_TYPE, Response, StatusCode, Url};
+use std::{
+    collections::{BTreeSet, HashSet, VecDeque},
+    convert::TryInto,
+    fs::File,
+    io::{BufRead, BufReader},
+    sync::Arc,
 };
-use anyhow::Result;
-use std::{convert::TryInto, sync::Arc};
 
 /// Perform steps necessary to run scans that only need to be performed once (warming up the
 /// engine, as it were)
-pub async fn initialize(num_words: usize, handles: Arc<Handles>) -> Result<()> {
-    log::trace!("enter: initialize({}, {:?})", num_words, handles);
+///
+/// returns the parent directories of any confirmed --known-urls, which should be queued for
+/// recursion once the initial targets are known to the scan handler
+pub async fn initialize(
+    num_words: usize,
+    targets: &[String],
+    handles: Arc<Handles>,
+) -> Result<Vec<String>> {
+    log::trace!(
+        "enter: initialize({}, {:?}, {:?})",
+        num_words,
+        targets,
+        handles
+    );
 
     // number of requests only needs to be calculated once, and then can be reused
     let num_reqs_expected: u64 = handles.expected_num_requests_per_dir().try_into()?;
@@ -24,6 +65,691 @@ pub async fn initialize(num_words: usize, handles: Arc<Handles>) -> Result<()> {
         .stats
         .send(AddToUsizeField(ExpectedPerScan, num_reqs_expected as usize))?;
 
-    log::trace!("exit: initialize");
+    if handles.config.collect_words {
+        // --word-languages is validated by the parser, but values from a config file aren't
+        for language in &handles.config.word_languages {
+            if !STOP_WORD_LANGUAGES.contains(&language.as_str()) {
+                log::warn!(
+                    "no stop words are known for {}, expected one of {:?}",
+                    language,
+                    STOP_WORD_LANGUAGES
+                );
+            }
+        }
+
+        // words are collected as soon as --known-urls are seeded below, so the model needs to
+        // know how to pre-process documents before then
+        if let Ok(mut guard) = TF_IDF.write() {
+            guard.set_options(PreprocessOptions {
+                languages: handles.config.word_languages.clone(),
+                transliterate: handles.config.transliterate,
+            });
+        }
+    }
+
+    if !handles.config.imported_urls.is_empty() {
+        // endpoints already known from --import-burp are requested once, below; marking them
+        // as seen keeps link extraction from requesting them all over again
+        let imported = handles.config.imported_urls.iter().cloned().collect();
+        handles.seen_links.insert_new(&imported);
+    }
+
+    let parents = if handles.config.known_urls.is_empty() && handles.config.imported_urls.is_empty()
+    {
+        Vec::new()
+    } else {
+        seed_known_urls(targets, handles.clone()).await?
+    };
+
+    log::trace!("exit: initialize -> {:?}", parents);
+    Ok(parents)
+}
+
+/// queue the parent directories returned by `initialize` for recursion
+///
+/// each parent is requested, so that the scan handler decides on recursion from the
+/// directory's actual response, the same as it does for directories found by the wordlist
+///
+/// must be called after the initial targets have been sent to the scan handler
+pub async fn queue_known_parents(
+    parents: Vec<String>,
+    targets: &[String],
+    handles: Arc<Handles>,
+) -> Result<()> {
+    log::trace!("enter: queue_known_parents({:?}, {:?})", parents, targets);
+
+    if handles.config.no_recursion {
+        log::trace!("exit: queue_known_parents -> no_recursion is true");
+        return Ok(());
+    }
+
+    let mut urls = Vec::new();
+
+    for parent in parents {
+        urls.push(skip_fail!(parse_url_with_raw_path(&parent)));
+    }
+
+    let urls = allowed_urls(urls, targets, &handles);
+
+    for response in request_all(urls, handles.clone()).await {
+        send_try_recursion_command(handles.clone(), response).await?;
+    }
+
+    log::trace!("exit: queue_known_parents");
     Ok(())
 }
+
+/// drop any of the given urls that the scanner itself wouldn't request: those that aren't on the
+/// same host as one of the targets, and those caught by --dont-scan / --dont-scan-file
+fn allowed_urls(urls: Vec<Url>, targets: &[String], handles: &Arc<Handles>) -> Vec<Url> {
+    let targets: Vec<_> = targets
+        .iter()
+        .filter_map(|target| parse_url_with_raw_path(target).ok())
+        .collect();
+
+    let should_test_deny = has_denylist(handles);
+
+    urls.into_iter()
+        .filter(|url| {
+            if !targets.iter().any(|target| target.host() == url.host()) {
+                log::info!("{} isn't part of any target, skipping", url);
+                return false;
+            }
+
+            if should_test_deny && should_deny_url(url, handles.clone()).unwrap_or(false) {
+                log::info!("{} matched --dont-scan, skipping", url);
+                return false;
+            }
+
+            true
+        })
+        .collect()
+}
+
+/// request each of the given urls, --threads at a time; urls that couldn't be requested are
+/// left out of the returned responses
+async fn request_all(urls: Vec<Url>, handles: Arc<Handles>) -> Vec<FeroxResponse> {
+    stream::iter(urls)
+        .map(|url| {
+            let handles = handles.clone();
+
+            async move {
+                let response = logged_request(&url, DEFAULT_METHOD, None, handles.clone())
+                    .await
+                    .map_err(|e| log::warn!("{}", fmt_err(&format!("{e}; skipping..."))))
+                    .ok()?;
+
+                Some(
+                    FeroxResponse::from(
+                        response,
+                        url.as_str(),
+                        DEFAULT_METHOD,
+                        handles.config.output_level,
+                    )
+                    .await,
+                )
+            }
+        })
+        .buffer_unordered(handles.config.threads.max(1))
+        .filter_map(|response| async move { response })
+        .collect()
+        .await
+}
+
+/// request each url listed in --known-urls (or found by --import-burp) ahead of the wordlist,
+/// confirming it still exists
+///
+/// confirmed urls are reported like any other finding, feed extension and word collection
+/// (when enabled), and contribute their parent directories to the returned list
+///
+/// known wsdl/wadl documents are reported as well, and the endpoints they declare on the same
+/// host are requested as if they'd been listed too
+async fn seed_known_urls(targets: &[String], handles: Arc<Handles>) -> Result<Vec<String>> {
+    log::trace!(
+        "enter: seed_known_urls({}, {:?})",
+        handles.config.known_urls,
+        targets
+    );
+
+    let mut lines = VecDeque::new();
+
+    if !handles.config.known_urls.is_empty() {
+        let file = File::open(&handles.config.known_urls)
+            .with_context(|| format!("Could not open {}", handles.config.known_urls))?;
+
+        for line in BufReader::new(file).lines() {
+            lines.push_back(skip_fail!(line));
+        }
+    }
+
+    lines.extend(handles.config.imported_urls.iter().cloned());
+
+    let mut parents = BTreeSet::new();
+    let mut requested = HashSet::new();
+    let mut baselined = HashSet::new();
+    let test = HeuristicTests::new(handles.clone());
+
+    // each pass requests everything queued so far; endpoints declared by wsdl/wadl documents
+    // found along the way make up the next pass
+    while !lines.is_empty() {
+        let mut urls = Vec::new();
+
+        for line in lines.drain(..) {
+            let line = line.trim();
+
+            if line.is_empty() || line.starts_with('#') || !requested.insert(line.to_string()) {
+                continue;
+            }
+
+            urls.push(skip_fail!(parse_url_with_raw_path(line)));
+        }
+
+        // known urls are held to the same scope as everything else the scanner requests
+        let urls = allowed_urls(urls, targets, &handles);
+
+        // known urls are requested before any directory scan has had the chance to auto-filter
+        // its 404-like responses, so the directories they live in are baselined first
+        for directory in urls.iter().filter_map(|url| url.join(".").ok()) {
+            if baselined.insert(directory.to_string()) {
+                if let Err(e) = test.detect_404_like_responses(directory.as_str()).await {
+                    log::warn!("Could not baseline {}: {}", directory, e);
+                }
+            }
+        }
+
+        handles.filters.sync().await?;
+
+        for ferox_response in request_all(urls, handles.clone()).await {
+            seed_known_response(ferox_response, &mut parents, &mut lines, handles.clone())?;
+        }
+    }
+
+    let parents = parents.into_iter().collect();
+
+    log::trace!("exit: seed_known_urls -> {:?}", parents);
+    Ok(parents)
+}
+
+/// report a response to one of --known-urls, unless the url no longer exists or was filtered;
+/// its parent directories are added to `parents`, and the endpoints of a wsdl/wadl document to
+/// `lines`
+fn seed_known_response(
+    mut ferox_response: FeroxResponse,
+    parents: &mut BTreeSet<String>,
+    lines: &mut VecDeque<String>,
+    handles: Arc<Handles>,
+) -> Result<()> {
+    let url = ferox_response.url().clone();
+
+    if matches!(
+        *ferox_response.status(),
+        StatusCode::NOT_FOUND | StatusCode::GONE
+    ) || handles
+        .filters
+        .data
+        .should_filter_response(&ferox_response, handles.stats.tx.clone())
+    {
+        log::info!("known url no longer exists or was filtered: {}", url);
+        return Ok(());
+    }
+
+    parents.extend(parent_directories(ferox_response.url()));
+
+    if handles.config.collect_extensions {
+        ferox_response.parse_extension(handles.clone())?;
+    }
+
+    if handles.config.detect_language {
+        ferox_response.detect_language();
+    }
+
+    if handles.config.collect_words {
+        if let Ok(mut guard) = TF_IDF.write() {
+            if let Some(doc) = Document::from_html(ferox_response.text(), guard.options()) {
+                let origin = ferox_response.url().origin().ascii_serialization();
+                guard.add_document_from(doc, &origin);
+                guard.calculate_tf_idf_scores();
+            }
+        }
+    }
+
+    if handles.config.extract_links {
+        // a known wsdl/wadl has its endpoints requested the same as any other known url
+        if let Some(description) =
+            ServiceDescription::parse(ferox_response.url(), ferox_response.text())
+        {
+            description.report(&handles);
+
+            lines.extend(
+                description
+                    .requestable_endpoints()
+                    .filter(|endpoint| {
+                        parse_url_with_raw_path(endpoint)
+                            .is_ok_and(|endpoint| endpoint.host() == ferox_response.url().host())
+                    })
+                    .map(String::from),
+            );
+        }
+    }
+
+    if let Err(e) = ferox_response.send_report(handles.output.tx.clone()) {
+        log::warn!("Could not send FeroxResponse to output handler: {}", e);
+    }
+
+    Ok(())
+}
+
+/// request each operation documented in the --openapi spec against each target, ahead of the
+/// wordlist; with --openapi auto, each target is first checked for a spec at the usual
+/// locations (see `SPEC_LOCATIONS`)
+///
+/// operations that don't come back as a 404/405/410 (and aren't filtered) are reported like any
+/// other finding, and contribute their parent directories to the returned list
+pub async fn seed_openapi(targets: &[String], handles: Arc<Handles>) -> Result<Vec<String>> {
+    log::trace!(
+        "enter: seed_openapi({:?}, {})",
+        targets,
+        handles.config.openapi
+    );
+
+    if handles.config.openapi.is_empty() {
+        log::trace!("exit: seed_openapi -> []");
+        return Ok(Vec::new());
+    }
+
+    let given = if handles.config.openapi == "auto" {
+        None
+    } else {
+        Some(load_spec(&handles).await?)
+    };
+
+    let mut parents = BTreeSet::new();
+
+    for target in targets {
+        // operations are relative to the target, which needs to end in a slash to be joined
+        let base = if target.ends_with('/') {
+            target.to_owned()
+        } else {
+            format!("{target}/")
+        };
+
+        let base = skip_fail!(parse_url_with_raw_path(&base));
+
+        let operations = match &given {
+            Some(operations) => operations.clone(),
+            None => match discover_spec(&base, handles.clone()).await {
+                Some(operations) => operations,
+                None => {
+                    log::info!("no OpenAPI / Swagger spec found for {}", base);
+                    continue;
+                }
+            },
+        };
+
+        let urls = operations
+            .iter()
+            .filter_map(|operation| base.join(&operation.path).ok())
+            .map(|url| url.to_string())
+            .collect();
+
+        // keeps link extraction from requesting documented endpoints all over again
+        handles.seen_links.insert_new(&urls);
+
+        for operation in operations {
+            let url = skip_fail!(base.join(&operation.path));
+
+            let response = skip_fail!(
+                logged_request(
+                    &url,
+                    &operation.method,
+                    operation.body.as_deref(),
+                    handles.clone()
+                )
+                .await
+            );
+
+            let ferox_response = FeroxResponse::from(
+                response,
+                url.as_str(),
+                &operation.method,
+                handles.config.output_level,
+            )
+            .await;
+
+            if matches!(
+                *ferox_response.status(),
+                StatusCode::NOT_FOUND | StatusCode::METHOD_NOT_ALLOWED | StatusCode::GONE
+            ) || handles
+                .filters
+                .data
+                .should_filter_response(&ferox_response, handles.stats.tx.clone())
+            {
+                continue;
+            }
+
+            parents.extend(parent_directories(ferox_response.url()));
+
+            if let Err(e) = ferox_response.send_report(handles.output.tx.clone()) {
+                log::warn!("Could not send FeroxResponse to output handler: {}", e);
+            }
+        }
+    }
+
+    let parents = parents.into_iter().collect();
+
+    log::trace!("exit: seed_openapi -> {:?}", parents);
+    Ok(parents)
+}
+
+/// read the operations from the spec given to --openapi, either a local file or a url
+async fn load_spec(handles: &Handles) -> Result<Vec<ApiOperation>> {
+    let location = &handles.config.openapi;
+
+    let contents = if location.starts_with("http") {
+        handles
+            .config
+            .client
+            .get(location)
+            .send()
+            .await
+            .and_then(|response| response.error_for_status())
+            .with_context(|| fmt_err(&format!("Could not download {location}")))?
+            .text()
+            .await?
+    } else {
+        std::fs::read_to_string(location)
+            .with_context(|| fmt_err(&format!("Could not read {location}")))?
+    };
+
+    parse_spec(&contents).with_context(|| fmt_err(&format!("Could not parse {location}")))
+}
+
+/// look for a spec at each of `SPEC_LOCATIONS` beneath the target, returning the operations of
+/// the first one found; the spec itself is reported as a finding
+async fn discover_spec(base: &Url, handles: Arc<Handles>) -> Option<Vec<ApiOperation>> {
+    for location in SPEC_LOCATIONS {
+        let Ok(url) = base.join(location) else {
+            continue;
+        };
+
+        let Ok(response) = logged_request(&url, DEFAULT_METHOD, None, handles.clone()).await else {
+            continue;
+        };
+
+        if !response.status().is_success() {
+            continue;
+        }
+
+        let ferox_response = FeroxResponse::from(
+            response,
+            url.as_str(),
+            DEFAULT_METHOD,
+            handles.config.output_level,
+        )
+        .await;
+
+        if let Ok(operations) = parse_spec(ferox_response.text()) {
+            log::info!(
+                "found a spec with {} operation(s) at {}",
+                operations.len(),
+                url
+            );
+
+            if let Err(e) = ferox_response.send_report(handles.output.tx.clone()) {
+                log::warn!("Could not send FeroxResponse to output handler: {}", e);
+            }
+
+            return Some(operations);
+        }
+    }
+
+    None
+}
+
+/// mark each --import-crawl url beneath one of the targets as already seen, so that link
+/// extraction doesn't request what the crawler already did
+///
+/// returns the parent directories of those urls, which should be queued for recursion along
+/// with the parents from `initialize`
+pub fn seed_crawl(targets: &[String], handles: Arc<Handles>) -> Vec<String> {
+    log::trace!("enter: seed_crawl({:?})", targets);
+
+    if handles.config.crawled_urls.is_empty() {
+        log::trace!("exit: seed_crawl -> []");
+        return Vec::new();
+    }
+
+    let bases: Vec<String> = targets
+        .iter()
+        .filter_map(|target| parse_url_with_raw_path(target).ok())
+        .map(|url| {
+            let base = url.to_string();
+
+            if base.ends_with('/') {
+                base
+            } else {
+                format!("{base}/")
+            }
+        })
+        .collect();
+
+    let mut crawled = HashSet::new();
+    let mut parents = BTreeSet::new();
+
+    for url in &handles.config.crawled_urls {
+        let Some(base) = bases.iter().find(|base| url.starts_with(base.as_str())) else {
+            continue;
+        };
+
+        let parsed = skip_fail!(parse_url_with_raw_path(url));
+
+        parents.extend(
+            parent_directories(&parsed)
+                .into_iter()
+                .filter(|parent| parent.len() > base.len()),
+        );
+
+        crawled.insert(url.to_owned());
+    }
+
+    log::info!(
+        "{} of {} crawled url(s) belong to a target",
+        crawled.len(),
+        handles.config.crawled_urls.len()
+    );
+
+    handles.seen_links.insert_new(&crawled);
+
+    let parents = parents.into_iter().collect();
+
+    log::trace!("exit: seed_crawl -> {:?}", parents);
+    parents
+}
+
+/// look for a GraphQL endpoint beneath each target at the usual locations (see
+/// `GRAPHQL_LOCATIONS`), and report the operations each one found exposes through introspection
+///
+/// endpoints are reported like any other finding; when --collect-words is used, operation names
+/// are added to the collected words
+pub async fn discover_graphql(targets: &[String], handles: Arc<Handles>) -> Result<()> {
+    log::trace!("enter: discover_graphql({:?})", targets);
+
+    if !handles.config.graphql {
+        log::trace!("exit: discover_graphql -> graphql is false");
+        return Ok(());
+    }
+
+    for target in targets {
+        let base = if target.ends_with('/') {
+            target.to_owned()
+        } else {
+            format!("{target}/")
+        };
+
+        let base = skip_fail!(parse_url_with_raw_path(&base));
+
+        for location in GRAPHQL_LOCATIONS {
+            let url = skip_fail!(base.join(location));
+
+            let Ok(response) = graphql_request(&url, PROBE_QUERY, &handles).await else {
+                continue;
+            };
+
+            let ferox_response =
+                FeroxResponse::from(response, url.as_str(), "POST", handles.config.output_level)
+                    .await;
+
+            if !is_graphql_response(ferox_response.text()) {
+                continue;
+            }
+
+            log::info!("found a GraphQL endpoint at {}", url);
+
+            if let Err(e) = ferox_response.send_report(handles.output.tx.clone()) {
+                log::warn!("Could not send FeroxResponse to output handler: {}", e);
+            }
+
+            let introspected = match graphql_request(&url, INTROSPECTION_QUERY, &handles).await {
+                Ok(response) => response.text().await.unwrap_or_default(),
+                Err(_) => String::new(),
+            };
+
+            let schema = GraphqlSchema::from_introspection(url.as_str(), &introspected)
+                .unwrap_or_else(|_| GraphqlSchema::without_introspection(url.as_str()));
+
+            if handles.config.collect_words && schema.operations().next().is_some() {
+                if let Ok(mut guard) = TF_IDF.write() {
+                    let names: Vec<_> = schema.operations().map(String::as_str).collect();
+                    let doc = Document::new(&names.join(" "), guard.options());
+                    guard.add_document_from(doc, &url.origin().ascii_serialization());
+                    guard.calculate_tf_idf_scores();
+                }
+            }
+
+            if matches!(
+                handles.config.output_level,
+                OutputLevel::Default | OutputLevel::Quiet
+            ) {
+                ferox_print(&schema.as_str(), &PROGRESS_PRINTER);
+            }
+
+            handles
+                .output
+                .tx_file
+                .send(WriteGraphql(Box::new(schema)))
+                .unwrap_or_default();
+
+            // one endpoint per target is enough; the others are almost always aliases
+            break;
+        }
+    }
+
+    log::trace!("exit: discover_graphql");
+    Ok(())
+}
+
+/// POST the given GraphQL query (already json encoded) to the url
+async fn graphql_request(url: &Url, query: &str, handles: &Handles) -> Result<Response> {
+    let response = handles
+        .config
+        .client
+        .post(url.clone())
+        .header(CONTENT_TYPE, "application/json")
+        .body(query.to_string())
+        .send()
+        .await?;
+
+    Ok(response)
+}
+
+/// given a url, return each of its parent directories, excluding the web root
+///
+/// ex: http://localhost/a/b/c.php -> [http://localhost/a/, http://localhost/a/b/]
+fn parent_directories(url: &Url) -> Vec<String> {
+    let mut parents = Vec::new();
+
+    let Some(segments) = url.path_segments() else {
+        return parents;
+    };
+
+    let segments: Vec<_> = segments.collect();
+
+    // the last segment is the resource itself (or empty when the url ends in a slash)
+    let mut path = String::from("/");
+
+    for segment in segments.iter().take(segments.len().saturating_sub(1)) {
+        if segment.is_empty() {
+            continue;
+        }
+
+        path.push_str(segment);
+        path.push('/');
+
+        if let Ok(parent) = url.join(&path) {
+            parents.push(parent.to_string());
+        }
+    }
+
+    parents
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Configuration;
+
+    #[test]
+    /// each parent directory is returned, the web root and the resource itself are not
+    fn parent_directories_returns_each_parent() {
+        let url = Url::parse("http://localhost/a/b/c.php").unwrap();
+
+        assert_eq!(
+            parent_directories(&url),
+            vec![
+                String::from("http://localhost/a/"),
+                String::from("http://localhost/a/b/")
+            ]
+        );
+    }
+
+    #[test]
+    /// a url ending in a slash is itself a directory and is returned along with its parents
+    fn parent_directories_handles_trailing_slash() {
+        let url = Url::parse("http://localhost/a/b/").unwrap();
+
+        assert_eq!(
+            parent_directories(&url),
+            vec![
+                String::from("http://localhost/a/"),
+                String::from("http://localhost/a/b/")
+            ]
+        );
+
+        let root = Url::parse("http://localhost/index.html").unwrap();
+        assert!(parent_directories(&root).is_empty());
+    }
+
+    #[test]
+    /// seeded urls on another host or caught by --dont-scan are dropped
+    fn allowed_urls_enforces_scope_and_denylist() {
+        let mut config = Configuration::new().unwrap();
+        config.url_denylist = vec![Url::parse("http://localhost/denied").unwrap()];
+
+        let handles = Arc::new(Handles::for_testing(None, Some(Arc::new(config))).0);
+        let targets = [String::from("http://localhost/")];
+
+        let urls = [
+            "http://localhost/a/b.php",
+            "http://elsewhere.com/a/b.php",
+            "http://localhost/denied/b.php",
+        ]
+        .iter()
+        .map(|url| Url::parse(url).unwrap())
+        .collect();
+
+        assert_eq!(
+            allowed_urls(urls, &targets, &handles),
+            vec![Url::parse("http://localhost/a/b.php").unwrap()]
+        );
+    }
+}
@@ -8,5 +8,6 @@ mod policy_data;
 mod requester;
 
 pub use self::ferox_scanner::{FeroxScanner, RESPONSES};
-pub use self::init::initialize;
+pub use self::init::{discover_graphql, initialize, queue_known_parents, seed_crawl, seed_openapi};
+pub(crate) use self::requester::TF_IDF;
 pub use self::utils::PolicyTrigger;
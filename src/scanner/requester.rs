@@ -6,12 +6,14 @@ use std::{
         atomic::{AtomicBool, Ordering},
         Arc, Mutex,
     },
+    time::Instant,
 };
 
 use anyhow::Result;
 use console::style;
 use lazy_static::lazy_static;
 use leaky_bucket::RateLimiter;
+use reqwest::{StatusCode, Url};
 use tokio::{
     sync::RwLock,
     time::{sleep, Duration},
@@ -19,23 +21,38 @@ use tokio::{
 
 use crate::{
     atomic_load, atomic_store,
-    config::RequesterPolicy,
+    config::{OutputLevel, RequesterPolicy},
     event_handlers::{
-        Command::{AddError, SubtractFromUsizeField},
-        Handles,
+        Command::{AddError, AddToUsizeField, SubtractFromUsizeField},
+        Handles, TermInputHandler,
     },
     extractor::{ExtractionTarget, ExtractorBuilder},
     nlp::{Document, TfIdf},
+    progress::PROGRESS_PRINTER,
     response::FeroxResponse,
     scan_manager::{FeroxScan, ScanStatus},
-    statistics::{StatError::Other, StatField::TotalExpected},
+    statistics::{
+        StatError::Other,
+        StatField::{TotalExpected, TruncatedResponses},
+    },
+    theme::{glyph, themed},
     url::FeroxUrl,
-    utils::{logged_request, send_try_recursion_command, should_deny_url},
-    HIGH_ERROR_RATIO,
+    utils::{
+        ferox_print, has_denylist, logged_request, send_try_recursion_command, should_deny_url,
+    },
+    CURRENT_WORD_INTERVAL, HIGH_ERROR_RATIO, SLOW_SCAN_RATE,
 };
 
 use super::{policy_data::PolicyData, FeroxScanner, PolicyTrigger};
 
+/// prefix of a progress bar message showing the word currently being requested
+const CURRENT_WORD_PREFIX: &str = "=> 🐢 ";
+
+/// `CURRENT_WORD_PREFIX`, or its ascii-safe equivalent when --ascii is in effect
+fn current_word_prefix() -> &'static str {
+    glyph(CURRENT_WORD_PREFIX, "=> ")
+}
+
 lazy_static! {
     /// make sure to note that this is a std rwlock and not tokio
     pub(crate) static ref TF_IDF: Arc<sync::RwLock<TfIdf>> = Arc::new(sync::RwLock::new(TfIdf::new()));
@@ -58,11 +75,16 @@ pub(super) struct Requester {
     /// FeroxScan associated with the creation of this Requester
     ferox_scan: Arc<FeroxScan>,
 
-    /// cache of previously seen links gotten via link extraction. since the requester is passed
+    /// cache of previously seen links gotten via link extraction, only used when the cache
+    /// shared by every scan (`Handles::seen_links`) can't be. since the requester is passed
     /// around as an arc, and seen_links needs to be mutable, putting it behind a lock for
     /// interior mutability, similar to the tuning_lock below
     seen_links: RwLock<HashSet<String>>,
 
+    /// links found via extraction that are held until the wordlist is done, when
+    /// --extract-priority low is used; see `request_deferred_links`
+    deferred_links: Mutex<HashSet<String>>,
+
     /// simple lock to control access to tuning to a single thread (per-scan)
     ///
     /// need a usize to determine the number of consecutive non-error calls that a requester has
@@ -70,6 +92,14 @@ pub(super) struct Requester {
     /// the need for a counter)
     tuning_lock: Mutex<usize>,
 
+    /// when the word currently being requested was last shown in the progress bar, used to
+    /// throttle updates on slow scans
+    word_shown: Mutex<Option<Instant>>,
+
+    /// extensions dropped by --adaptive-extensions for this directory, paired with the number
+    /// of drops (`FeroxScans::extension_drops`) made when they were gathered
+    dropped_extensions: sync::RwLock<(usize, Arc<HashSet<String>>)>,
+
     policy_triggered: AtomicBool,
 }
 
@@ -94,14 +124,39 @@ impl Requester {
             ferox_scan,
             policy_data,
             seen_links: RwLock::new(HashSet::<String>::new()),
+            deferred_links: Mutex::new(HashSet::new()),
             rate_limiter: RwLock::new(rate_limiter),
             handles: scanner.handles.clone(),
             target_url: scanner.target_url.to_owned(),
             tuning_lock: Mutex::new(0),
+            word_shown: Mutex::new(None),
+            dropped_extensions: Default::default(),
             policy_triggered: AtomicBool::new(false),
         })
     }
 
+    /// request the links held back by --extract-priority low; called once the scan's wordlist
+    /// is done
+    pub async fn request_deferred_links(&self) -> Result<Option<tokio::task::JoinHandle<()>>> {
+        let links = match self.deferred_links.lock() {
+            Ok(mut deferred) => std::mem::take(&mut *deferred),
+            Err(_) => return Ok(None),
+        };
+
+        log::trace!("enter: request_deferred_links({})", links.len());
+
+        let mut extractor = ExtractorBuilder::default()
+            .target(ExtractionTarget::ResponseBody)
+            .url(self.ferox_scan.url())
+            .handles(self.handles.clone())
+            .build()?;
+
+        let task = extractor.request_links(links).await?;
+
+        log::trace!("exit: request_deferred_links");
+        Ok(task)
+    }
+
     /// build a RateLimiter, given a rate limit (as requests per second)
     fn build_a_bucket(limit: usize) -> Result<RateLimiter> {
         let refill = max((limit as f64 / 10.0).round() as usize, 1); // minimum of 1 per second
@@ -215,9 +270,9 @@ impl Requester {
 
                     let styled_direction = style("reduced").red();
 
-                    self.ferox_scan
-                        .progress_bar()
-                        .set_message(format!("=> 🚦 {styled_direction} scan speed",));
+                    self.ferox_scan.progress_bar().set_message(
+                        themed(&format!("=> 🚦 {styled_direction} scan speed")).into_owned(),
+                    );
                 }
                 self.policy_data.set_errors(scan_errors);
             } else {
@@ -228,9 +283,9 @@ impl Requester {
 
                 let styled_direction = style("increased").green();
 
-                self.ferox_scan
-                    .progress_bar()
-                    .set_message(format!("=> 🚦 {styled_direction} scan speed",));
+                self.ferox_scan.progress_bar().set_message(
+                    themed(&format!("=> 🚦 {styled_direction} scan speed")).into_owned(),
+                );
             }
         }
 
@@ -239,7 +294,7 @@ impl Requester {
             atomic_store!(self.policy_data.remove_limit, false);
             self.ferox_scan
                 .progress_bar()
-                .set_message("=> 🚦 removed rate limiter 🚀");
+                .set_message(themed("=> 🚦 removed rate limiter 🚀").into_owned());
         } else if create_limiter {
             // create_limiter is really just used for unit testing situations, it's true anytime
             // during actual execution
@@ -286,7 +341,7 @@ impl Requester {
             self.set_rate_limiter(Some(new_limit)).await?;
             self.ferox_scan
                 .progress_bar()
-                .set_message(format!("=> 🚦 set rate limit ({new_limit}/s)"));
+                .set_message(themed(&format!("=> 🚦 set rate limit ({new_limit}/s)")).into_owned());
         }
 
         self.adjust_limit(trigger, true).await?;
@@ -328,11 +383,12 @@ impl Requester {
 
             let styled_trigger = style(format!("{trigger:?}")).red();
 
-            pb.set_message(format!(
+            let message = format!(
                 "=> 💀 too many {} ({}) 💀 bailing",
                 styled_trigger,
                 self.ferox_scan.num_errors(trigger),
-            ));
+            );
+            pb.set_message(themed(&message).into_owned());
 
             // update the overall scan bar by subtracting the number of skipped requests from
             // the total
@@ -340,26 +396,225 @@ impl Requester {
                 .stats
                 .send(SubtractFromUsizeField(TotalExpected, num_skipped))
                 .unwrap_or_else(|e| log::warn!("Could not update overall scan bar: {}", e));
+
+            // whatever was found before bailing shouldn't be lost if the process dies soon after
+            let reason = format!("auto-bail on {}", self.target_url);
+
+            self.handles
+                .flush_output(&reason)
+                .await
+                .unwrap_or_else(|e| log::warn!("Could not flush output file: {}", e));
+
+            TermInputHandler::checkpoint_state(self.handles.clone(), &reason).await;
         }
 
         Ok(())
     }
 
+    /// on slow scans, show the word being requested in the scan's progress bar
+    ///
+    /// updates happen at most once per `CURRENT_WORD_INTERVAL` and never replace a message that
+    /// was set for some other reason (tuning, bailing, etc...)
+    fn show_current_word(&self, word: &str) {
+        // another request is already updating the bar, no need to wait on it
+        let Ok(mut shown) = self.word_shown.try_lock() else {
+            return;
+        };
+
+        let interval = Duration::from_millis(CURRENT_WORD_INTERVAL);
+
+        if matches!(*shown, Some(at) if at.elapsed() < interval) {
+            return;
+        }
+
+        *shown = Some(Instant::now());
+
+        let pb = self.ferox_scan.progress_bar();
+        let elapsed = pb.elapsed();
+
+        if elapsed < interval {
+            // too early to tell how fast the scan is
+            return;
+        }
+
+        let message = pb.message();
+
+        if !message.is_empty() && !message.starts_with(current_word_prefix()) {
+            return;
+        }
+
+        let rate = pb.position() as f64 / elapsed.as_secs_f64();
+
+        if rate < SLOW_SCAN_RATE {
+            pb.set_message(format!("{}{word}", current_word_prefix()));
+        } else if !message.is_empty() {
+            // the scan sped back up
+            pb.set_message("");
+        }
+    }
+
+    /// remove the word shown by `show_current_word`, if any, from the scan's progress bar
+    pub fn clear_current_word(&self) {
+        let pb = self.ferox_scan.progress_bar();
+
+        if pb.message().starts_with(current_word_prefix()) {
+            pb.set_message("");
+        }
+    }
+
     /// Wrapper for make_request
     ///
     /// Attempts recursion when appropriate and sends Responses to the output handler for processing
     pub async fn request(&self, word: &str) -> Result<()> {
         log::trace!("enter: request({})", word);
 
+        self.show_current_word(word);
+
         let collected = self.handles.collected_extensions();
+        let num_extensions = self.handles.config.extensions.len() + collected.len();
 
         let urls = FeroxUrl::from_string(&self.target_url, self.handles.clone())
-            .formatted_urls(word, collected)?;
+            .formatted_urls_with_extensions(word, collected, &self.dropped_extensions())?;
+
+        let num_skipped = (num_extensions + 1).saturating_sub(urls.len());
+
+        if self.handles.config.adaptive_extensions > 0 && num_skipped > 0 {
+            // extensions dropped by --adaptive-extensions were still counted in the expected
+            // number of requests
+            self.handles
+                .stats
+                .send(SubtractFromUsizeField(
+                    TotalExpected,
+                    num_skipped * self.handles.config.methods.len(),
+                ))
+                .unwrap_or_default();
+        }
 
-        let should_test_deny = !self.handles.config.url_denylist.is_empty()
-            || !self.handles.config.regex_denylist.is_empty();
+        self.request_urls(word, urls, true).await?;
 
-        for url in urls {
+        log::trace!("exit: request");
+        Ok(())
+    }
+
+    /// Wrapper for make_request, used for words from a wordlist paired with a single extension
+    /// (-x ext:FILE); only the word + extension is requested
+    pub async fn request_with_extension(&self, word: &str, extension: &str) -> Result<()> {
+        log::trace!("enter: request_with_extension({}, {})", word, extension);
+
+        self.show_current_word(word);
+
+        let url = FeroxUrl::from_string(&self.target_url, self.handles.clone())
+            .format(word, Some(extension))?;
+
+        // paired wordlists were chosen for their extension, so they're never adaptively dropped
+        self.request_urls(word, vec![(url, Some(extension.to_string()))], false)
+            .await?;
+
+        log::trace!("exit: request_with_extension");
+        Ok(())
+    }
+
+    /// links from `links` that this scan hasn't already extracted; the per-scan fallback for
+    /// when the cache shared by every scan can't be used
+    async fn unseen_links(&self, links: &HashSet<String>) -> HashSet<String> {
+        let new_links: HashSet<_>;
+
+        {
+            // gain and quickly drop the read lock on seen_links, using it while unlocked
+            // to determine if there are any new links to process
+            let read_links = self.seen_links.read().await;
+            new_links = links.difference(&read_links).cloned().collect();
+        }
+
+        if !new_links.is_empty() {
+            // using is_empty instead of direct iteration to acquire the write lock behind
+            // some kind of less expensive gate (and not in a loop, obv)
+            let mut write_links = self.seen_links.write().await;
+            for new_link in &new_links {
+                write_links.insert(new_link.to_owned());
+            }
+        }
+
+        new_links
+    }
+
+    /// extensions that --adaptive-extensions stopped requesting in this directory (or a parent
+    /// directory); only gathered again once another extension has been dropped somewhere
+    fn dropped_extensions(&self) -> Arc<HashSet<String>> {
+        if self.handles.config.adaptive_extensions == 0 {
+            return Default::default();
+        }
+
+        let Ok(scans) = self.handles.ferox_scans() else {
+            return Default::default();
+        };
+
+        let drops = scans.extension_drops();
+
+        if let Ok(cached) = self.dropped_extensions.read() {
+            if cached.0 == drops {
+                return cached.1.clone();
+            }
+        }
+
+        let dropped = Arc::new(scans.dropped_extensions_for(&self.target_url));
+
+        if let Ok(mut cached) = self.dropped_extensions.write() {
+            *cached = (drops, dropped.clone());
+        }
+
+        dropped
+    }
+
+    /// record whether a request made with the given extension turned anything up, for
+    /// --adaptive-extensions; a hit is any response that would be reported
+    fn record_extension_probe(&self, extension: &str, response: &FeroxResponse, filtered: bool) {
+        let config = &self.handles.config;
+
+        let status = response.status().as_u16();
+
+        let reportable = if !config.filter_status.is_empty() {
+            !config.filter_status.contains(&status)
+        } else {
+            config.status_codes.contains(&status)
+        };
+
+        let dropped = self.ferox_scan.record_extension_probe(
+            extension,
+            !filtered && reportable,
+            config.adaptive_extensions,
+        );
+
+        if dropped {
+            if let Ok(scans) = self.handles.ferox_scans() {
+                scans.drop_extension(&self.target_url, extension);
+            }
+
+            log::info!(
+                "dropping .{} beneath {}: no hits in {} sampled requests",
+                extension,
+                self.target_url,
+                config.adaptive_extensions
+            );
+        }
+    }
+
+    /// make a request to each of the given urls (once per method)
+    ///
+    /// urls made with an extension are paired with it, so --adaptive-extensions can track
+    /// which extensions turn anything up (when `adaptive` is set), and every response is tagged
+    /// with the `word` and extension that produced it
+    ///
+    /// Attempts recursion when appropriate and sends Responses to the output handler for processing
+    async fn request_urls(
+        &self,
+        word: &str,
+        urls: Vec<(Url, Option<String>)>,
+        adaptive: bool,
+    ) -> Result<()> {
+        let should_test_deny = has_denylist(&self.handles);
+
+        for (url, extension) in urls {
             for method in self.handles.config.methods.iter() {
                 // auto_tune is true, or rate_limit was set (mutually exclusive to user)
                 // and a rate_limiter has been created
@@ -387,6 +642,10 @@ impl Requester {
                     Some(self.handles.config.data.as_slice())
                 };
 
+                // held until the response's body has been read, bounding the number of this
+                // directory's outstanding requests when --inflight-per-dir is used
+                let inflight = self.ferox_scan.acquire_inflight().await;
+
                 let response =
                     logged_request(&url, method.as_str(), data, self.handles.clone()).await?;
 
@@ -423,6 +682,54 @@ impl Requester {
                 )
                 .await;
 
+                if ferox_response.truncated() {
+                    self.handles
+                        .stats
+                        .send(AddToUsizeField(TruncatedResponses, 1))
+                        .unwrap_or_default();
+
+                    if self.handles.config.retry_truncated {
+                        // a second, complete response replaces the truncated one; otherwise the
+                        // truncated response is reported as-is
+                        log::debug!("retrying truncated response from {}", url);
+
+                        // the retry wasn't part of the expected total, and would otherwise run
+                        // the overall bar past its end
+                        self.handles
+                            .stats
+                            .send(AddToUsizeField(TotalExpected, 1))
+                            .unwrap_or_default();
+
+                        if let Ok(retried) =
+                            logged_request(&url, method.as_str(), data, self.handles.clone()).await
+                        {
+                            let retried = FeroxResponse::from(
+                                retried,
+                                &self.target_url,
+                                method,
+                                self.handles.config.output_level,
+                            )
+                            .await;
+
+                            if !retried.truncated() {
+                                ferox_response = retried;
+                            }
+                        }
+                    }
+                }
+
+                drop(inflight);
+
+                ferox_response.set_word(word, extension.as_deref());
+
+                if ferox_response.status() == &StatusCode::NOT_MODIFIED
+                    && self.handles.config.validators.contains_key(url.as_str())
+                {
+                    // --changed-since made the request conditional, and the server says the
+                    // content is the same as it was during the previous run
+                    ferox_response.set_unchanged(true);
+                }
+
                 // do recursion if appropriate
                 if !self.handles.config.no_recursion && !self.handles.config.force_recursion {
                     // to support --force-recursion, we want to limit recursive calls to only
@@ -436,15 +743,38 @@ impl Requester {
 
                 // purposefully doing recursion before filtering. the thought process is that
                 // even though this particular url is filtered, subsequent urls may not
-                if self
+                let filtered = self
                     .handles
                     .filters
                     .data
-                    .should_filter_response(&ferox_response, self.handles.stats.tx.clone())
-                {
+                    .should_filter_response(&ferox_response, self.handles.stats.tx.clone());
+
+                if let Some(extension) = &extension {
+                    if adaptive && self.handles.config.adaptive_extensions > 0 {
+                        self.record_extension_probe(extension, &ferox_response, filtered);
+                    }
+                }
+
+                if filtered {
+                    if let Some(warning) =
+                        self.handles.filters.data.wildcard_warning(&ferox_response)
+                    {
+                        log::warn!("{}", console::strip_ansi_codes(&warning));
+
+                        if matches!(
+                            self.handles.config.output_level,
+                            OutputLevel::Default | OutputLevel::Quiet
+                        ) {
+                            ferox_print(&warning, &PROGRESS_PRINTER);
+                        }
+                    }
+
                     continue;
                 }
 
+                // counted towards --recurse-threshold for the directories this scan turns up
+                self.ferox_scan.add_finding();
+
                 if !self.handles.config.no_recursion && self.handles.config.force_recursion {
                     // in this branch, we're saying that both recursion AND force recursion
                     // are turned on. It comes after should_filter_response, so those cases
@@ -476,10 +806,17 @@ impl Requester {
                     ferox_response.parse_extension(self.handles.clone())?;
                 }
 
+                if self.handles.config.detect_language {
+                    ferox_response.detect_language();
+                }
+
                 if self.handles.config.collect_words {
                     if let Ok(mut guard) = TF_IDF.write() {
-                        if let Some(doc) = Document::from_html(ferox_response.text()) {
-                            guard.add_document(doc);
+                        if let Some(doc) =
+                            Document::from_html(ferox_response.text(), guard.options())
+                        {
+                            let origin = ferox_response.url().origin().ascii_serialization();
+                            guard.add_document_from(doc, &origin);
                             if guard.num_documents() % 12 == 0
                                 || (guard.num_documents() < 5 && guard.num_documents() % 2 == 0)
                             {
@@ -497,31 +834,25 @@ impl Requester {
                         .url(self.ferox_scan.url())
                         .build()?;
 
-                    let new_links: HashSet<_>;
-
                     let result = extractor.extract().await?;
 
-                    {
-                        // gain and quickly drop the read lock on seen_links, using it while unlocked
-                        // to determine if there are any new links to process
-                        let read_links = self.seen_links.read().await;
-                        new_links = result.difference(&read_links).cloned().collect();
-                    }
+                    let new_links = match self.handles.seen_links.insert_new(&result) {
+                        Some(new_links) => new_links,
+                        None => self.unseen_links(&result).await,
+                    };
 
                     if !new_links.is_empty() {
-                        // using is_empty instead of direct iteration to acquire the write lock behind
-                        // some kind of less expensive gate (and not in a loop, obv)
-                        let mut write_links = self.seen_links.write().await;
-                        for new_link in &new_links {
-                            write_links.insert(new_link.to_owned());
-                        }
-                    }
-
-                    if !new_links.is_empty() {
-                        let extraction_task = extractor.request_links(new_links).await?;
+                        if self.handles.config.extract_priority == "low" {
+                            // crawling waits its turn, the wordlist isn't held up by it
+                            if let Ok(mut deferred) = self.deferred_links.lock() {
+                                deferred.extend(new_links);
+                            }
+                        } else {
+                            let extraction_task = extractor.request_links(new_links).await?;
 
-                        if let Some(task) = extraction_task {
-                            _ = task.await;
+                            if let Some(task) = extraction_task {
+                                _ = task.await;
+                            }
                         }
                     }
                 }
@@ -533,15 +864,12 @@ impl Requester {
             }
         }
 
-        log::trace!("exit: request");
         Ok(())
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use std::time::Instant;
-
     use reqwest::StatusCode;
 
     use crate::{
@@ -552,6 +880,7 @@ mod tests {
         filters,
         scan_manager::{ScanOrder, ScanType},
         statistics::StatError,
+        wordlists::Wordlist,
     };
 
     use super::*;
@@ -565,7 +894,7 @@ mod tests {
         let (filters_task, filters_handle) = FiltersHandler::initialize();
         let (out_task, out_handle) =
             TermOutHandler::initialize(configuration.clone(), stats_handle.tx.clone());
-        let wordlist = Arc::new(vec![String::from("this_is_a_test")]);
+        let wordlist = Arc::new(Wordlist::from(vec![String::from("this_is_a_test")]));
 
         let handles = Arc::new(Handles::new(
             stats_handle,
@@ -573,6 +902,7 @@ mod tests {
             out_handle,
             configuration.clone(),
             wordlist,
+            Vec::new(),
         ));
 
         let (scan_task, scan_handle) = ScanHandler::initialize(handles.clone());
@@ -699,6 +1029,7 @@ mod tests {
             target_url: "http://localhost".to_string(),
             seen_links: RwLock::new(HashSet::<String>::new()),
             tuning_lock: Mutex::new(0),
+            word_shown: Mutex::new(None),
             ferox_scan: Arc::new(FeroxScan::default()),
             rate_limiter: RwLock::new(None),
             policy_data: Default::default(),
@@ -727,6 +1058,7 @@ mod tests {
             handles,
             seen_links: RwLock::new(HashSet::<String>::new()),
             tuning_lock: Mutex::new(0),
+            word_shown: Mutex::new(None),
             ferox_scan: ferox_scan.clone(),
             target_url: "http://localhost".to_string(),
             rate_limiter: RwLock::new(None),
@@ -753,6 +1085,7 @@ mod tests {
             handles,
             seen_links: RwLock::new(HashSet::<String>::new()),
             tuning_lock: Mutex::new(0),
+            word_shown: Mutex::new(None),
             ferox_scan: ferox_scan.clone(),
             target_url: "http://localhost".to_string(),
             rate_limiter: RwLock::new(None),
@@ -794,6 +1127,7 @@ mod tests {
             handles,
             seen_links: RwLock::new(HashSet::<String>::new()),
             tuning_lock: Mutex::new(0),
+            word_shown: Mutex::new(None),
             ferox_scan: ferox_scan.clone(),
             target_url: "http://localhost".to_string(),
             rate_limiter: RwLock::new(None),
@@ -850,6 +1184,7 @@ mod tests {
             handles,
             seen_links: RwLock::new(HashSet::<String>::new()),
             tuning_lock: Mutex::new(0),
+            word_shown: Mutex::new(None),
             ferox_scan: req_clone,
             target_url: "http://one/one/stuff.php".to_string(),
             rate_limiter: RwLock::new(None),
@@ -885,6 +1220,7 @@ mod tests {
             handles,
             seen_links: RwLock::new(HashSet::<String>::new()),
             tuning_lock: Mutex::new(0),
+            word_shown: Mutex::new(None),
             ferox_scan: Arc::new(FeroxScan::default()),
             target_url: "http://one/one/stuff.php".to_string(),
             rate_limiter: RwLock::new(None),
@@ -908,6 +1244,7 @@ mod tests {
             handles,
             seen_links: RwLock::new(HashSet::<String>::new()),
             tuning_lock: Mutex::new(0),
+            word_shown: Mutex::new(None),
             ferox_scan: Arc::new(FeroxScan::default()),
             target_url: "http://localhost".to_string(),
             rate_limiter: RwLock::new(None),
@@ -932,6 +1269,7 @@ mod tests {
             handles,
             seen_links: RwLock::new(HashSet::<String>::new()),
             tuning_lock: Mutex::new(0),
+            word_shown: Mutex::new(None),
             ferox_scan: Arc::new(FeroxScan::default()),
             target_url: "http://localhost".to_string(),
             rate_limiter: RwLock::new(None),
@@ -963,6 +1301,7 @@ mod tests {
             handles,
             seen_links: RwLock::new(HashSet::<String>::new()),
             tuning_lock: Mutex::new(0),
+            word_shown: Mutex::new(None),
             ferox_scan: Arc::new(FeroxScan::default()),
             target_url: "http://localhost".to_string(),
             rate_limiter: RwLock::new(None),
@@ -1002,6 +1341,7 @@ mod tests {
             handles,
             seen_links: RwLock::new(HashSet::<String>::new()),
             tuning_lock: Mutex::new(0),
+            word_shown: Mutex::new(None),
             ferox_scan: Arc::new(scan),
             target_url: "http://localhost".to_string(),
             rate_limiter: RwLock::new(Some(limiter)),
@@ -1040,6 +1380,7 @@ mod tests {
             handles,
             seen_links: RwLock::new(HashSet::<String>::new()),
             tuning_lock: Mutex::new(0),
+            word_shown: Mutex::new(None),
             ferox_scan: Arc::new(scan),
             target_url: "http://localhost".to_string(),
             rate_limiter: RwLock::new(None),
@@ -1069,6 +1410,7 @@ mod tests {
             handles,
             seen_links: RwLock::new(HashSet::<String>::new()),
             tuning_lock: Mutex::new(0),
+            word_shown: Mutex::new(None),
             ferox_scan: Arc::new(FeroxScan::default()),
             target_url: "http://localhost".to_string(),
             rate_limiter: RwLock::new(None),
@@ -1113,6 +1455,7 @@ mod tests {
             handles,
             seen_links: RwLock::new(HashSet::<String>::new()),
             tuning_lock: Mutex::new(0),
+            word_shown: Mutex::new(None),
             ferox_scan: Arc::new(FeroxScan::default()),
             target_url: "http://localhost".to_string(),
             rate_limiter: RwLock::new(Some(limiter)),
@@ -1159,6 +1502,7 @@ mod tests {
             handles,
             seen_links: RwLock::new(HashSet::<String>::new()),
             tuning_lock: Mutex::new(0),
+            word_shown: Mutex::new(None),
             ferox_scan: scan.clone(),
             target_url: "http://localhost".to_string(),
             rate_limiter: RwLock::new(Some(limiter)),
@@ -1187,4 +1531,56 @@ mod tests {
         scan.finish(0).unwrap();
         assert!(start.elapsed().as_millis() >= 2000);
     }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+    /// show_current_word should only show words on slow scans, at most once per interval, and
+    /// never over another message
+    async fn show_current_word_on_slow_scans() {
+        let (handles, _) = setup_requester_test(None).await;
+
+        let requester = Requester {
+            handles,
+            target_url: "http://localhost".to_string(),
+            seen_links: RwLock::new(HashSet::<String>::new()),
+            tuning_lock: Mutex::new(0),
+            word_shown: Mutex::new(None),
+            ferox_scan: Arc::new(FeroxScan::default()),
+            rate_limiter: RwLock::new(None),
+            policy_data: Default::default(),
+            policy_triggered: AtomicBool::new(false),
+        };
+
+        let pb = requester.ferox_scan.progress_bar();
+
+        // too early to know the scan's speed
+        requester.show_current_word("first");
+        assert_eq!(pb.message(), "");
+
+        sleep(Duration::from_millis(CURRENT_WORD_INTERVAL + 100)).await;
+
+        // no requests made in over a second, i.e. a slow scan
+        requester.show_current_word("second");
+        assert_eq!(pb.message(), format!("{CURRENT_WORD_PREFIX}second"));
+
+        // throttled
+        requester.show_current_word("third");
+        assert_eq!(pb.message(), format!("{CURRENT_WORD_PREFIX}second"));
+
+        // other messages aren't replaced
+        pb.set_message("=> 🚦 set rate limit (1/s)");
+        *requester.word_shown.lock().unwrap() = None;
+        requester.show_current_word("fourth");
+        assert_eq!(pb.message(), "=> 🚦 set rate limit (1/s)");
+
+        requester.clear_current_word();
+        assert_eq!(pb.message(), "=> 🚦 set rate limit (1/s)");
+
+        pb.set_message("");
+        *requester.word_shown.lock().unwrap() = None;
+        requester.show_current_word("fifth");
+        assert_eq!(pb.message(), format!("{CURRENT_WORD_PREFIX}fifth"));
+
+        requester.clear_current_word();
+        assert_eq!(pb.message(), "");
+    }
 }
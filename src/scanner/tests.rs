@@ -1,21 +1,23 @@
 use std::sync::Arc;
 
-use tokio::sync::Semaphore;
-
 use crate::{
-    config::OutputLevel,
+    config::{Configuration, OutputLevel},
     event_handlers::Handles,
     scan_manager::{FeroxScans, ScanOrder},
+    semaphore::ScanLimiter,
+    statistics::StatField,
+    Command,
 };
 
+use super::utils::constrain_collected_words;
 use super::*;
 
 #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
 #[should_panic]
 /// try to hit struct field coverage of FileOutHandler
 async fn get_scan_by_url_bails_on_unfound_url() {
-    let sem = Semaphore::new(10);
-    let urls = FeroxScans::new(OutputLevel::Default, 0);
+    let sem = ScanLimiter::new(10);
+    let urls = FeroxScans::new(OutputLevel::Default, 0, false);
 
     let scanner = FeroxScanner::new(
         "http://localhost",
@@ -26,3 +28,114 @@ async fn get_scan_by_url_bails_on_unfound_url() {
     );
     scanner.scan_url().await.unwrap();
 }
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+/// once the run is cancelled, scans return without finishing, so --resume-from picks them back up
+async fn scan_url_leaves_scan_unfinished_when_cancelled() {
+    let urls = Arc::new(FeroxScans::new(OutputLevel::Default, 0, false));
+    let handles = Arc::new(Handles::for_testing(Some(urls.clone()), None).0);
+
+    let (_, scan) =
+        urls.add_directory_scan("http://localhost", ScanOrder::Initial, handles.clone());
+
+    handles.cancellation_token().cancel();
+    assert!(handles.is_cancelled());
+
+    let scanner = FeroxScanner::new(
+        "http://localhost/",
+        ScanOrder::Initial,
+        Arc::new(Default::default()),
+        Arc::new(ScanLimiter::new(10)),
+        handles,
+    );
+
+    scanner.scan_url().await.unwrap();
+    assert!(!scan.is_complete());
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+/// a scan that's aborted while waiting on the scan limiter is no longer counted as queued
+async fn scan_url_releases_queued_scan_when_aborted() {
+    let urls = Arc::new(FeroxScans::new(OutputLevel::Default, 0, false));
+    let (handles, mut rx) = Handles::for_testing(Some(urls.clone()), None);
+    let handles = Arc::new(handles);
+
+    urls.add_directory_scan("http://localhost", ScanOrder::Initial, handles.clone());
+
+    let limiter = Arc::new(ScanLimiter::new(1));
+    let _held = limiter.try_acquire().unwrap();
+
+    let scanner = FeroxScanner::new(
+        "http://localhost/",
+        ScanOrder::Initial,
+        Arc::new(Default::default()),
+        limiter,
+        handles,
+    );
+
+    let task = tokio::spawn(async move { scanner.scan_url().await });
+    tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+    task.abort();
+    assert!(task.await.unwrap_err().is_cancelled());
+
+    let (mut was_queued, mut queued) = (false, 0);
+
+    while let Ok(cmd) = rx.try_recv() {
+        match cmd {
+            Command::AddToUsizeField(StatField::QueuedScans, value) => {
+                was_queued = true;
+                queued += value
+            }
+            Command::SubtractFromUsizeField(StatField::QueuedScans, value) => queued -= value,
+            _ => {}
+        }
+    }
+
+    assert!(was_queued);
+    assert_eq!(queued, 0);
+}
+
+/// helper to build a list of owned words
+fn words(words: &[&str]) -> Vec<String> {
+    words.iter().map(|word| word.to_string()).collect()
+}
+
+#[test]
+/// no constraints leaves collected words untouched
+fn constrain_collected_words_defaults_keep_everything() {
+    let collected = words(&["admin", "v2", "x", "données", "api_key", "2024"]);
+
+    assert_eq!(
+        constrain_collected_words(collected.clone(), &Configuration::default()),
+        collected
+    );
+}
+
+#[test]
+/// length and charset constraints are applied before the limit, which keeps the first words
+fn constrain_collected_words_applies_constraints() {
+    let collected = words(&["admin", "v2", "x", "données", "api_key", "2024", "backup"]);
+
+    let config = Configuration {
+        collected_word_min_len: 2,
+        collected_word_max_len: 7,
+        collected_word_charset: String::from("alpha"),
+        ..Default::default()
+    };
+
+    assert_eq!(
+        constrain_collected_words(collected.clone(), &config),
+        ["admin", "données", "backup"]
+    );
+
+    let config = Configuration {
+        collected_word_charset: String::from("alnum"),
+        collected_word_limit: 3,
+        ..Default::default()
+    };
+
+    assert_eq!(
+        constrain_collected_words(collected, &config),
+        ["admin", "v2", "x"]
+    );
+}
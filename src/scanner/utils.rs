@@ -1,3 +1,5 @@
+use crate::config::Configuration;
+
 #[derive(Copy, Clone, PartialEq, Eq, Debug)]
 /// represents different situations where different criteria can trigger auto-tune/bail behavior
 pub enum PolicyTrigger {
@@ -13,3 +15,30 @@ pub enum PolicyTrigger {
     /// dummy error for upward rate adjustment
     TryAdjustUp,
 }
+
+/// apply the --collected-word-* constraints to the words gathered by --collect-words
+///
+/// expects words ordered highest scoring first, so that --collected-word-limit keeps the best
+/// candidates
+pub(super) fn constrain_collected_words(words: Vec<String>, config: &Configuration) -> Vec<String> {
+    let limit = match config.collected_word_limit {
+        0 => usize::MAX,
+        limit => limit,
+    };
+
+    words
+        .into_iter()
+        .filter(|word| {
+            let length = word.chars().count();
+
+            length >= config.collected_word_min_len
+                && (config.collected_word_max_len == 0 || length <= config.collected_word_max_len)
+        })
+        .filter(|word| match config.collected_word_charset.as_str() {
+            "alpha" => word.chars().all(char::is_alphabetic),
+            "alnum" => word.chars().all(char::is_alphanumeric),
+            _ => true,
+        })
+        .take(limit)
+        .collect()
+}
@@ -0,0 +1,239 @@
+use std::fmt;
+
+use console::style;
+use reqwest::{
+    header::{LOCATION, STRICT_TRANSPORT_SECURITY},
+    Response, Url,
+};
+
+/// ports that conventionally speak plain http
+const HTTP_PORTS: [u16; 5] = [80, 8000, 8008, 8080, 8888];
+
+/// ports that conventionally speak https
+const HTTPS_PORTS: [u16; 3] = [443, 8443, 9443];
+
+/// the scheme a bare hostname's explicit port conventionally speaks, if it's a well-known one
+///
+/// ex:
+/// example.com:8080 -> Some("http")
+/// example.com:8443 -> Some("https")
+/// example.com -> None
+pub(crate) fn preferred_scheme(host: &str) -> Option<&'static str> {
+    // a non-special scheme keeps default ports (i.e. :80) around, where http:// would drop them
+    let port = Url::parse(&format!("ferox://{host}")).ok()?.port()?;
+
+    if HTTP_PORTS.contains(&port) {
+        Some("http")
+    } else if HTTPS_PORTS.contains(&port) {
+        Some("https")
+    } else {
+        None
+    }
+}
+
+/// What was learned about a bare hostname (i.e. --url example.com) by requesting it over both
+/// http and https
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct SchemeProbe {
+    /// the hostname, as given by the user
+    pub(crate) host: String,
+
+    /// whether or not the host responded over http
+    pub(crate) http_live: bool,
+
+    /// whether or not the host responded over https
+    pub(crate) https_live: bool,
+
+    /// whether or not http requests are redirected to https
+    pub(crate) upgrades_to_https: bool,
+
+    /// value of the `Strict-Transport-Security` header served over https, if any
+    pub(crate) hsts: Option<String>,
+
+    /// scheme that wasn't requested because the host's port is known to speak the other one
+    /// (--smart-schemes)
+    pub(crate) skipped: Option<&'static str>,
+}
+
+/// implementation of SchemeProbe
+impl SchemeProbe {
+    /// create a new SchemeProbe from the responses (if any) to the http and https requests
+    pub(crate) fn new(host: &str, http: Option<&Response>, https: Option<&Response>) -> Self {
+        let upgrades_to_https = http.is_some_and(|response| {
+            // redirects are either followed (-r), leaving us at the final url, or not, leaving a
+            // Location header to inspect
+            let location = response
+                .headers()
+                .get(LOCATION)
+                .and_then(|value| value.to_str().ok())
+                .unwrap_or_default()
+                .to_lowercase();
+
+            response.url().scheme() == "https"
+                || (response.status().is_redirection() && location.starts_with("https://"))
+        });
+
+        let hsts = https.and_then(|response| {
+            response
+                .headers()
+                .get(STRICT_TRANSPORT_SECURITY)
+                .map(|value| String::from_utf8_lossy(value.as_bytes()).to_string())
+        });
+
+        Self {
+            host: host.to_string(),
+            http_live: http.is_some(),
+            https_live: https.is_some(),
+            upgrades_to_https,
+            hsts,
+            skipped: None,
+        }
+    }
+
+    /// urls that should be scanned for this host
+    ///
+    /// `protocol` (--protocol) breaks the tie when both schemes are live and http doesn't
+    /// redirect to https, and is used when neither scheme is live, so that the connectivity
+    /// check can report the failure. When `both_schemes` is true, every live scheme is scanned
+    pub(crate) fn targets(&self, protocol: &str, both_schemes: bool) -> Vec<String> {
+        let url = |scheme: &str| format!("{scheme}://{}", self.host);
+
+        let schemes = match (self.http_live, self.https_live) {
+            (false, false) => vec![protocol],
+            (true, false) => vec!["http"],
+            (false, true) => vec!["https"],
+            (true, true) if both_schemes => vec!["https", "http"],
+            (true, true) if self.upgrades_to_https => vec!["https"],
+            (true, true) => vec![protocol],
+        };
+
+        schemes.into_iter().map(url).collect()
+    }
+}
+
+/// Display implementation for SchemeProbe
+impl fmt::Display for SchemeProbe {
+    /// formatter for Display
+    ///
+    /// ex:
+    /// example.com => http: live (redirects to https) | https: live (hsts: max-age=31536000)
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let status = |scheme: &str, live: bool| {
+            if self.skipped == Some(scheme) {
+                style("skipped").dim().to_string()
+            } else if live {
+                style("live").green().to_string()
+            } else {
+                style("down").red().to_string()
+            }
+        };
+
+        let mut http = status("http", self.http_live);
+
+        if self.upgrades_to_https {
+            http.push_str(" (redirects to https)");
+        }
+
+        let mut https = status("https", self.https_live);
+
+        if let Some(hsts) = &self.hsts {
+            https.push_str(&format!(" (hsts: {hsts})"));
+        }
+
+        write!(
+            f,
+            "{} => http: {} | https: {}",
+            style(&self.host).bright().yellow(),
+            http,
+            https
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// helper to build a probe for localhost with the given liveness
+    fn probe(http_live: bool, https_live: bool, upgrades_to_https: bool) -> SchemeProbe {
+        SchemeProbe {
+            host: String::from("localhost"),
+            http_live,
+            https_live,
+            upgrades_to_https,
+            hsts: None,
+            skipped: None,
+        }
+    }
+
+    #[test]
+    /// the only live scheme is picked, regardless of --protocol
+    fn targets_prefers_the_live_scheme() {
+        assert_eq!(
+            probe(true, false, false).targets("https", false),
+            vec!["http://localhost"]
+        );
+        assert_eq!(
+            probe(false, true, false).targets("http", false),
+            vec!["https://localhost"]
+        );
+        assert_eq!(
+            probe(false, false, false).targets("https", true),
+            vec!["https://localhost"]
+        );
+    }
+
+    #[test]
+    /// when both schemes are live, an upgrade redirect wins over --protocol, and --both-schemes
+    /// scans both
+    fn targets_with_both_schemes_live() {
+        assert_eq!(
+            probe(true, true, true).targets("http", false),
+            vec!["https://localhost"]
+        );
+        assert_eq!(
+            probe(true, true, false).targets("http", false),
+            vec!["http://localhost"]
+        );
+        assert_eq!(
+            probe(true, true, true).targets("http", true),
+            vec!["https://localhost", "http://localhost"]
+        );
+    }
+
+    #[test]
+    /// display shows liveness, upgrades, and hsts
+    fn display_summarizes_probe() {
+        let mut probe = probe(true, true, true);
+        probe.hsts = Some(String::from("max-age=31536000"));
+
+        let shown = console::strip_ansi_codes(&probe.to_string()).to_string();
+
+        assert_eq!(
+            shown,
+            "localhost => http: live (redirects to https) | https: live (hsts: max-age=31536000)"
+        );
+    }
+
+    #[test]
+    /// display marks a scheme skipped by --smart-schemes as such, rather than down
+    fn display_shows_skipped_scheme() {
+        let mut probe = probe(true, false, false);
+        probe.skipped = Some("https");
+
+        let shown = console::strip_ansi_codes(&probe.to_string()).to_string();
+
+        assert_eq!(shown, "localhost => http: live | https: skipped");
+    }
+
+    #[test]
+    /// well-known ports map to the scheme they conventionally speak
+    fn preferred_scheme_uses_well_known_ports() {
+        assert_eq!(preferred_scheme("localhost:80"), Some("http"));
+        assert_eq!(preferred_scheme("localhost:8080"), Some("http"));
+        assert_eq!(preferred_scheme("localhost:443"), Some("https"));
+        assert_eq!(preferred_scheme("localhost:8443/admin"), Some("https"));
+        assert_eq!(preferred_scheme("localhost:3000"), None);
+        assert_eq!(preferred_scheme("localhost"), None);
+    }
+}
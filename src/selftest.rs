@@ -0,0 +1,402 @@
+//! --self-test; scans a small site served from within the process, whose content is known ahead
+//! of time, and checks what was reported against what should've been
+//!
+//! the site has real content (a redirected directory, nested files, a 403, a rate limited path)
+//! alongside the usual traps: every unknown path is a soft-404 (200 with a "not found" page), and
+//! /api/ is a wildcard directory. The user's other flags are kept, so the check doubles as a dry
+//! run of their filter / flag combination
+use std::{collections::BTreeSet, env::temp_dir, fs, net::SocketAddr, path::PathBuf, sync::Arc};
+
+use anyhow::{bail, Context, Result};
+use console::style;
+use tokio::{
+    io::{AsyncBufReadExt, AsyncWriteExt, BufReader},
+    net::{TcpListener, TcpStream},
+    task::JoinHandle,
+};
+
+use crate::{config::Configuration, scanner::RESPONSES, utils::fmt_err};
+
+/// wordlist used for the self-test; a mix of paths that exist and paths that don't
+const SELF_TEST_WORDS: [&str; 10] = [
+    "admin",
+    "users",
+    "login",
+    "backup.zip",
+    "server-status",
+    "limited",
+    "api",
+    "nothing-here",
+    "old",
+    "test",
+];
+
+/// paths of the mock site that hold real content, along with their status codes; these should
+/// all be reported, and nothing else should
+const EXPECTED_FINDINGS: [(&str, u16); 8] = [
+    ("/admin", 301),
+    ("/admin/users", 200),
+    ("/login", 200),
+    ("/backup.zip", 200),
+    ("/server-status", 403),
+    ("/limited", 429),
+    ("/api", 301),
+    ("/api/users", 200),
+];
+
+/// body served for every path that doesn't exist, with a 200
+const SOFT_404_BODY: &str =
+    "<html><head><title>Oops</title></head><body><h1>Sorry, that page doesn't exist</h1></body></html>";
+
+/// body served for every unknown path under /api/, with a 200
+const API_WILDCARD_BODY: &str = r#"{"status":"ok","data":[]}"#;
+
+/// a mock site running on a random local port, torn down when dropped
+#[derive(Debug)]
+pub struct MockSite {
+    /// address the site is listening on
+    address: SocketAddr,
+
+    /// task accepting connections
+    server: JoinHandle<()>,
+
+    /// wordlist written for the self-test scan
+    wordlist: PathBuf,
+}
+
+/// implementation of MockSite
+impl MockSite {
+    /// start serving the mock site and write the self-test wordlist
+    pub async fn start() -> Result<Self> {
+        log::trace!("enter: MockSite::start");
+
+        let listener = TcpListener::bind("127.0.0.1:0")
+            .await
+            .with_context(|| fmt_err("Could not start the self-test server"))?;
+
+        let address = listener.local_addr()?;
+
+        let server = tokio::spawn(async move {
+            while let Ok((stream, _)) = listener.accept().await {
+                tokio::spawn(async move {
+                    if let Err(e) = serve(stream).await {
+                        log::debug!("self-test server: {}", e);
+                    }
+                });
+            }
+        });
+
+        let wordlist = temp_dir().join(format!("ferox-self-test-{}.txt", address.port()));
+
+        fs::write(&wordlist, SELF_TEST_WORDS.join("\n"))
+            .with_context(|| fmt_err(&format!("Could not write {}", wordlist.display())))?;
+
+        let site = Self {
+            address,
+            server,
+            wordlist,
+        };
+
+        log::trace!("exit: MockSite::start -> {:?}", site);
+        Ok(site)
+    }
+
+    /// base url of the mock site
+    pub fn url(&self) -> String {
+        format!("http://{}/", self.address)
+    }
+
+    /// copy of the given configuration, pointed at the mock site
+    ///
+    /// targets, wordlists, and seeds from the command line are swapped out, and anything that
+    /// would write the self-test's results to the user's files (or elsewhere) is turned off
+    pub fn configure(&self, config: &Configuration) -> Arc<Configuration> {
+        let mut config = config.clone();
+
+        config.target_url = self.url();
+        config.stdin = false;
+        config.parallel = 0;
+        config.resume_from.clear();
+        config.wordlist = self.wordlist.to_string_lossy().to_string();
+        config.fetch_wordlist.clear();
+        config.archive_seed.clear();
+        config.extension_wordlists.clear();
+        config.known_urls.clear();
+        config.openapi.clear();
+        config.graphql = false;
+        config.imported_targets.clear();
+        config.imported_urls.clear();
+        config.import_crawl.clear();
+        config.crawled_urls.clear();
+        config.changed_since.clear();
+        config.validators.clear();
+
+        config.save_state = false;
+        config.output.clear();
+        config.output_dir.clear();
+        config.banner_json.clear();
+        config.export_urls.clear();
+        config.nuclei_targets.clear();
+        config.report_html.clear();
+        config.report_markdown.clear();
+        config.report_xml.clear();
+        config.emit_ffuf_json.clear();
+        config.save_collected_words.clear();
+        config.campaign.clear();
+        config.notify_webhook.clear();
+        config.zap_api_url.clear();
+        config.replay_proxy.clear();
+        config.replay_client = None;
+
+        Arc::new(config)
+    }
+
+    /// compare what the scan reported against the site's real content, printing the outcome of
+    /// each check; fails when something was missed or a trap was reported
+    pub fn verify(&self) -> Result<()> {
+        log::trace!("enter: verify");
+
+        let reported = match RESPONSES.responses.read() {
+            Ok(guard) => guard
+                .iter()
+                .filter(|response| !response.wildcard())
+                .map(|response| {
+                    (
+                        response.url().path().trim_end_matches('/').to_string(),
+                        response.status().as_u16(),
+                    )
+                })
+                .collect::<BTreeSet<_>>(),
+            Err(_) => bail!(fmt_err("Could not read the self-test's findings")),
+        };
+
+        let outcome = check_findings(&reported);
+
+        println!();
+        println!("Self-test results ({})", self.url());
+
+        for (path, status) in EXPECTED_FINDINGS {
+            if outcome.missed.contains(&(path, status)) {
+                println!("  {} {path} [{status}] was not reported", style("✘").red());
+            } else {
+                println!("  {} {path} [{status}]", style("✔").green());
+            }
+        }
+
+        for (path, status) in &outcome.unexpected {
+            println!(
+                "  {} {path} [{status}] was reported, but isn't real content",
+                style("✘").red()
+            );
+        }
+
+        if !outcome.missed.is_empty() || !outcome.unexpected.is_empty() {
+            bail!(
+                "Self-test failed: {} missed, {} false positive(s); check the flags used above",
+                outcome.missed.len(),
+                outcome.unexpected.len()
+            );
+        }
+
+        println!("Self-test passed");
+
+        log::trace!("exit: verify");
+        Ok(())
+    }
+}
+
+/// stop serving and remove the wordlist once the self-test is over
+impl Drop for MockSite {
+    fn drop(&mut self) {
+        self.server.abort();
+        let _ = fs::remove_file(&self.wordlist);
+    }
+}
+
+/// findings that should've been reported but weren't, and those reported that shouldn't have been
+#[derive(Debug, Default, PartialEq)]
+struct Outcome {
+    /// expected findings that weren't reported
+    missed: Vec<(&'static str, u16)>,
+
+    /// reported findings that aren't real content (soft-404s, wildcards, the site's root aside)
+    unexpected: Vec<(String, u16)>,
+}
+
+/// compare the reported (path, status) pairs against `EXPECTED_FINDINGS`; trailing slashes are
+/// expected to have been trimmed already
+fn check_findings(reported: &BTreeSet<(String, u16)>) -> Outcome {
+    let missed = EXPECTED_FINDINGS
+        .into_iter()
+        .filter(|(path, status)| !reported.contains(&(path.to_string(), *status)))
+        .collect();
+
+    let unexpected = reported
+        .iter()
+        .filter(|(path, status)| {
+            // the root, and directories found by following a redirect, are fine to report
+            !path.is_empty()
+                && !EXPECTED_FINDINGS.contains(&(path.as_str(), *status))
+                && !(*status == 200 && ["/admin", "/api"].contains(&path.as_str()))
+        })
+        .cloned()
+        .collect();
+
+    Outcome { missed, unexpected }
+}
+
+/// status, extra headers, and body for the given path on the mock site
+fn route(path: &str) -> (u16, Vec<(&'static str, String)>, String) {
+    let path = path.split(['?', '#']).next().unwrap_or_default();
+
+    match path {
+        "/" => (
+            200,
+            Vec::new(),
+            String::from("<html><body><h1>feroxbuster self-test</h1></body></html>"),
+        ),
+        "/admin" | "/api" => (301, vec![("Location", format!("{path}/"))], String::new()),
+        "/admin/" => (
+            200,
+            Vec::new(),
+            String::from("<html><body><h1>Admin</h1><p>Welcome back</p></body></html>"),
+        ),
+        "/admin/users" => (200, Vec::new(), String::from("alice\nbob\ncarol\n")),
+        "/login" => (
+            200,
+            Vec::new(),
+            String::from(
+                r#"<html><body><form method="post"><input name="username"><input name="password" type="password"></form></body></html>"#,
+            ),
+        ),
+        "/backup.zip" => (
+            200,
+            Vec::new(),
+            String::from("PK\u{3}\u{4}not-really-a-zip"),
+        ),
+        "/server-status" => (403, Vec::new(), String::from("Forbidden")),
+        "/limited" => (
+            429,
+            vec![("Retry-After", String::from("1"))],
+            String::from("Too Many Requests"),
+        ),
+        "/api/users" => (
+            200,
+            Vec::new(),
+            String::from(
+                r#"{"status":"ok","data":[{"id":1,"name":"alice"},{"id":2,"name":"bob"}]}"#,
+            ),
+        ),
+        _ if path.starts_with("/api/") => (200, Vec::new(), String::from(API_WILDCARD_BODY)),
+        _ => (200, Vec::new(), String::from(SOFT_404_BODY)),
+    }
+}
+
+/// answer a single request on the given connection, then close it
+async fn serve(stream: TcpStream) -> Result<()> {
+    let mut reader = BufReader::new(stream);
+
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line).await?;
+
+    // the rest of the request's headers aren't needed, but are read so the client doesn't see
+    // the connection reset while it's still writing them
+    loop {
+        let mut line = String::new();
+
+        if reader.read_line(&mut line).await? == 0 || line.trim().is_empty() {
+            break;
+        }
+    }
+
+    let mut parts = request_line.split_whitespace();
+    let method = parts.next().unwrap_or_default();
+    let path = parts.next().unwrap_or("/");
+
+    let (status, headers, body) = route(path);
+
+    let mut response = format!(
+        "HTTP/1.1 {status} {}\r\nContent-Length: {}\r\nContent-Type: text/html\r\nConnection: close\r\n",
+        reason(status),
+        body.len()
+    );
+
+    for (name, value) in headers {
+        response.push_str(&format!("{name}: {value}\r\n"));
+    }
+
+    response.push_str("\r\n");
+
+    if method != "HEAD" {
+        response.push_str(&body);
+    }
+
+    let stream = reader.get_mut();
+    stream.write_all(response.as_bytes()).await?;
+    stream.shutdown().await?;
+
+    Ok(())
+}
+
+/// reason phrase for the status codes served by the mock site
+fn reason(status: u16) -> &'static str {
+    match status {
+        200 => "OK",
+        301 => "Moved Permanently",
+        403 => "Forbidden",
+        429 => "Too Many Requests",
+        _ => "",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    /// known paths are served as-is, unknown paths are soft-404s, and /api/ is a wildcard
+    fn route_serves_known_content_and_traps() {
+        assert_eq!(route("/admin").0, 301);
+        assert_eq!(
+            route("/admin").1,
+            vec![("Location", String::from("/admin/"))]
+        );
+        assert_eq!(route("/server-status?x=1").0, 403);
+        assert_eq!(route("/limited").0, 429);
+        assert_eq!(
+            route("/nothing-here"),
+            (200, Vec::new(), SOFT_404_BODY.into())
+        );
+        assert_eq!(route("/admin/old"), (200, Vec::new(), SOFT_404_BODY.into()));
+        assert_eq!(
+            route("/api/old"),
+            (200, Vec::new(), API_WILDCARD_BODY.into())
+        );
+        assert_ne!(route("/api/users").2, API_WILDCARD_BODY);
+    }
+
+    #[test]
+    /// misses and false positives are both called out; the root and followed redirects aren't
+    fn check_findings_reports_misses_and_false_positives() {
+        let mut reported: BTreeSet<_> = EXPECTED_FINDINGS
+            .iter()
+            .map(|(path, status)| (path.to_string(), *status))
+            .collect();
+
+        reported.insert((String::new(), 200));
+        reported.insert((String::from("/admin"), 200));
+
+        assert_eq!(check_findings(&reported), Outcome::default());
+
+        reported.remove(&(String::from("/login"), 200));
+        reported.insert((String::from("/old"), 200));
+
+        assert_eq!(
+            check_findings(&reported),
+            Outcome {
+                missed: vec![("/login", 200)],
+                unexpected: vec![(String::from("/old"), 200)],
+            }
+        );
+    }
+}
@@ -0,0 +1,402 @@
+use std::{
+    collections::{HashMap, VecDeque},
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc, Mutex,
+    },
+};
+
+use anyhow::{bail, Result};
+use tokio::sync::{oneshot, OwnedSemaphorePermit, Semaphore};
+
+/// Semaphore whose number of permits can be raised or lowered while permits are held
+///
+/// used to govern the number of in-flight requests for a single directory scan; the capacity can
+/// be changed mid-scan from the interactive menu
+#[derive(Debug)]
+pub(crate) struct DynamicSemaphore {
+    /// underlying semaphore from which permits are acquired
+    inner: Arc<Semaphore>,
+
+    /// total number of permits the semaphore should hold, whether acquired or available
+    capacity: AtomicUsize,
+
+    /// number of permits that still need to be removed after a reduction in capacity; these
+    /// are taken from permits as they're released, since acquired permits can't be revoked
+    pending_removal: AtomicUsize,
+
+    /// serializes calls to `resize`
+    resize_lock: Mutex<()>,
+}
+
+/// A permit acquired from a `DynamicSemaphore`, returned to the semaphore when dropped (unless
+/// the semaphore's capacity was lowered in the meantime)
+#[derive(Debug)]
+pub(crate) struct DynamicPermit {
+    /// semaphore that issued the permit
+    semaphore: Arc<DynamicSemaphore>,
+
+    /// the actual permit, only ever `None` after drop has begun
+    permit: Option<OwnedSemaphorePermit>,
+}
+
+/// implementation of DynamicSemaphore
+impl DynamicSemaphore {
+    /// create a new DynamicSemaphore with the given number of permits (minimum of 1)
+    pub(crate) fn new(capacity: usize) -> Self {
+        let capacity = capacity.max(1);
+
+        Self {
+            inner: Arc::new(Semaphore::new(capacity)),
+            capacity: AtomicUsize::new(capacity),
+            pending_removal: AtomicUsize::new(0),
+            resize_lock: Mutex::new(()),
+        }
+    }
+
+    /// current capacity of the semaphore
+    pub(crate) fn capacity(&self) -> usize {
+        self.capacity.load(Ordering::Relaxed)
+    }
+
+    /// wait for a permit to become available
+    pub(crate) async fn acquire(self: &Arc<Self>) -> Result<DynamicPermit> {
+        let permit = self.inner.clone().acquire_owned().await?;
+
+        Ok(DynamicPermit {
+            semaphore: self.clone(),
+            permit: Some(permit),
+        })
+    }
+
+    /// change the capacity of the semaphore (minimum of 1)
+    ///
+    /// when growing, new permits are available immediately; when shrinking, available permits
+    /// are removed immediately and any remainder is removed as held permits are released
+    pub(crate) fn resize(&self, new_capacity: usize) {
+        let new_capacity = new_capacity.max(1);
+
+        let Ok(_guard) = self.resize_lock.lock() else {
+            log::warn!("Could not acquire resize lock, semaphore was not resized");
+            return;
+        };
+
+        let current = self.capacity.swap(new_capacity, Ordering::SeqCst);
+
+        if new_capacity > current {
+            let mut grow_by = new_capacity - current;
+
+            // permits that were scheduled for removal, but haven't been released yet, can
+            // simply be kept instead
+            let cancelled = self
+                .pending_removal
+                .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |pending| {
+                    Some(pending.saturating_sub(grow_by))
+                })
+                .map(|pending| pending.min(grow_by))
+                .unwrap_or_default();
+
+            grow_by -= cancelled;
+
+            self.inner.add_permits(grow_by);
+        } else if new_capacity < current {
+            let shrink_by = current - new_capacity;
+            let forgotten = self.inner.forget_permits(shrink_by);
+
+            self.pending_removal
+                .fetch_add(shrink_by - forgotten, Ordering::SeqCst);
+        }
+    }
+}
+
+/// implementation of Drop for DynamicPermit
+impl Drop for DynamicPermit {
+    /// return the permit to its semaphore, or discard it if the semaphore has shrunk
+    fn drop(&mut self) {
+        let Some(permit) = self.permit.take() else {
+            return;
+        };
+
+        let should_forget = self
+            .semaphore
+            .pending_removal
+            .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |pending| {
+                pending.checked_sub(1)
+            })
+            .is_ok();
+
+        if should_forget {
+            permit.forget();
+        }
+    }
+}
+
+/// Limits the number of concurrent scans (--scan-limit), handing out freed permits either in
+/// the order they were asked for, or round-robin across targets (--interleave-targets)
+///
+/// a scan waiting on a permit is queued under its target; when a permit is released, it goes
+/// to the first scan queued under the target whose turn it is, and that target moves to the
+/// back of the line. With a single key for every scan, that's plain first come, first served
+#[derive(Debug)]
+pub(crate) struct ScanLimiter {
+    /// permits available and the scans waiting on them
+    state: Mutex<LimiterState>,
+}
+
+/// mutable state of a `ScanLimiter`
+#[derive(Debug, Default)]
+struct LimiterState {
+    /// permits that aren't held by any scan
+    available: usize,
+
+    /// once closed, waiting scans are turned away and new scans don't wait
+    closed: bool,
+
+    /// targets with scans waiting, in the order they'll be served
+    turns: VecDeque<String>,
+
+    /// scans waiting on a permit, by target; a permit is handed over through the sender
+    waiting: HashMap<String, VecDeque<oneshot::Sender<ScanPermit>>>,
+}
+
+/// A permit acquired from a `ScanLimiter`, handed to the next waiting scan when dropped
+#[derive(Debug)]
+pub(crate) struct ScanPermit {
+    /// limiter that issued the permit
+    limiter: Arc<ScanLimiter>,
+}
+
+/// implementation of ScanLimiter
+impl ScanLimiter {
+    /// create a new ScanLimiter with the given number of permits; 0 means no limit
+    pub(crate) fn new(limit: usize) -> Self {
+        let available = if limit == 0 { usize::MAX } else { limit };
+
+        Self {
+            state: Mutex::new(LimiterState {
+                available,
+                ..Default::default()
+            }),
+        }
+    }
+
+    /// take a permit, if one is available and no other scan is waiting
+    pub(crate) fn try_acquire(self: &Arc<Self>) -> Option<ScanPermit> {
+        let mut state = self.state.lock().ok()?;
+
+        if state.available == 0 || !state.turns.is_empty() {
+            return None;
+        }
+
+        state.available -= 1;
+
+        Some(ScanPermit {
+            limiter: self.clone(),
+        })
+    }
+
+    /// wait for a permit, queued under `key` (ex: the scan's target)
+    pub(crate) async fn acquire(self: &Arc<Self>, key: &str) -> Result<ScanPermit> {
+        let receiver = {
+            let Ok(mut state) = self.state.lock() else {
+                bail!("Could not lock scan limiter");
+            };
+
+            if state.closed {
+                bail!("scan limiter is closed");
+            }
+
+            if state.available > 0 && state.turns.is_empty() {
+                state.available -= 1;
+
+                return Ok(ScanPermit {
+                    limiter: self.clone(),
+                });
+            }
+
+            let (sender, receiver) = oneshot::channel();
+
+            let queue = state.waiting.entry(key.to_string()).or_default();
+            queue.push_back(sender);
+
+            if queue.len() == 1 {
+                // first scan waiting for this target, which gets in line for a turn
+                state.turns.push_back(key.to_string());
+            }
+
+            receiver
+        };
+
+        Ok(receiver.await?)
+    }
+
+    /// turn away every waiting scan, and stop limiting new ones
+    pub(crate) fn close(&self) {
+        if let Ok(mut state) = self.state.lock() {
+            state.closed = true;
+            state.available = usize::MAX;
+            state.turns.clear();
+            state.waiting.clear(); // dropped senders wake their scans with an error
+        }
+    }
+
+    /// hand a released permit to the next waiting scan, or make it available
+    fn release(self: &Arc<Self>) {
+        let Ok(mut state) = self.state.lock() else {
+            return;
+        };
+
+        while let Some(key) = state.turns.pop_front() {
+            let Some(queue) = state.waiting.get_mut(&key) else {
+                continue;
+            };
+
+            let sender = queue.pop_front();
+
+            if queue.is_empty() {
+                state.waiting.remove(&key);
+            } else {
+                // more scans are waiting under this target; back of the line
+                state.turns.push_back(key);
+            }
+
+            let Some(sender) = sender else {
+                continue;
+            };
+
+            let permit = ScanPermit {
+                limiter: self.clone(),
+            };
+
+            match sender.send(permit) {
+                Ok(()) => return,
+                // the scan stopped waiting (cancelled); dropping the permit here would release
+                // it again while the lock is held, so it's forgotten and the next scan is tried
+                Err(permit) => std::mem::forget(permit),
+            }
+        }
+
+        state.available = state.available.saturating_add(1);
+    }
+}
+
+/// implementation of Drop for ScanPermit
+impl Drop for ScanPermit {
+    /// give the permit to the next scan in line
+    fn drop(&mut self) {
+        self.limiter.release();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+    /// growing the semaphore makes new permits available immediately
+    async fn dynamic_semaphore_grows() {
+        let semaphore = Arc::new(DynamicSemaphore::new(1));
+        let _first = semaphore.acquire().await.unwrap();
+
+        assert_eq!(semaphore.inner.available_permits(), 0);
+
+        semaphore.resize(3);
+
+        assert_eq!(semaphore.capacity(), 3);
+        assert_eq!(semaphore.inner.available_permits(), 2);
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+    /// shrinking the semaphore below the number of held permits removes permits as they're
+    /// released
+    async fn dynamic_semaphore_shrinks_as_permits_are_released() {
+        let semaphore = Arc::new(DynamicSemaphore::new(3));
+
+        let first = semaphore.acquire().await.unwrap();
+        let second = semaphore.acquire().await.unwrap();
+
+        semaphore.resize(1);
+
+        // the one available permit was removed right away, one more is still owed
+        assert_eq!(semaphore.inner.available_permits(), 0);
+        assert_eq!(semaphore.pending_removal.load(Ordering::SeqCst), 1);
+
+        drop(first);
+        assert_eq!(semaphore.inner.available_permits(), 0);
+
+        drop(second);
+        assert_eq!(semaphore.inner.available_permits(), 1);
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+    /// growing after a shrink cancels out permits that are still owed
+    async fn dynamic_semaphore_grow_cancels_pending_removal() {
+        let semaphore = Arc::new(DynamicSemaphore::new(2));
+
+        let first = semaphore.acquire().await.unwrap();
+        let second = semaphore.acquire().await.unwrap();
+
+        semaphore.resize(1);
+        assert_eq!(semaphore.pending_removal.load(Ordering::SeqCst), 1);
+
+        semaphore.resize(2);
+        assert_eq!(semaphore.pending_removal.load(Ordering::SeqCst), 0);
+
+        drop(first);
+        drop(second);
+        assert_eq!(semaphore.inner.available_permits(), 2);
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+    /// freed permits go to each waiting target in turn, rather than in the order asked for
+    async fn scan_limiter_serves_targets_round_robin() {
+        let limiter = Arc::new(ScanLimiter::new(1));
+        let held = limiter.try_acquire().unwrap();
+
+        let order = Arc::new(Mutex::new(Vec::new()));
+        let mut tasks = Vec::new();
+
+        // three scans for one target queue up before the other target's only scan
+        for (i, key) in ["a", "a", "a", "b"].into_iter().enumerate() {
+            let limiter = limiter.clone();
+            let order = order.clone();
+
+            tasks.push(tokio::spawn(async move {
+                let _permit = limiter.acquire(key).await.unwrap();
+                order.lock().unwrap().push(format!("{key}{i}"));
+            }));
+
+            // let each task get in line before the next one is spawned
+            tokio::time::sleep(std::time::Duration::from_millis(25)).await;
+        }
+
+        assert!(limiter.try_acquire().is_none());
+
+        drop(held);
+
+        for task in tasks {
+            task.await.unwrap();
+        }
+
+        assert_eq!(*order.lock().unwrap(), ["a0", "b3", "a1", "a2"]);
+        assert!(limiter.try_acquire().is_some());
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+    /// closing the limiter wakes waiting scans and stops limiting
+    async fn scan_limiter_close_releases_waiters() {
+        let limiter = Arc::new(ScanLimiter::new(1));
+        let _held = limiter.try_acquire().unwrap();
+
+        let waiting = {
+            let limiter = limiter.clone();
+            tokio::spawn(async move { limiter.acquire("a").await.is_err() })
+        };
+
+        tokio::time::sleep(std::time::Duration::from_millis(25)).await;
+        limiter.close();
+
+        assert!(waiting.await.unwrap());
+        assert!(limiter.try_acquire().is_some());
+    }
+}
@@ -0,0 +1,136 @@
+use std::{
+    collections::{hash_map::DefaultHasher, HashMap},
+    hash::{Hash, Hasher},
+    sync::Mutex,
+    thread,
+};
+
+use reqwest::StatusCode;
+
+use super::StatError;
+use crate::STATS_BATCH_SIZE;
+
+/// request outcomes tallied since they were last applied to `Stats`
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct StatsBatch {
+    /// number of responses received, by status code
+    pub(crate) statuses: HashMap<StatusCode, usize>,
+
+    /// number of failed requests, by kind of failure
+    pub(crate) errors: HashMap<StatError, usize>,
+}
+
+/// implementation of StatsBatch
+impl StatsBatch {
+    /// tally a response with the given status code
+    pub fn add_status(&mut self, status: StatusCode) {
+        *self.statuses.entry(status).or_default() += 1;
+    }
+
+    /// tally a failed request
+    pub fn add_error(&mut self, error: StatError) {
+        *self.errors.entry(error).or_default() += 1;
+    }
+
+    /// fold another batch's tallies into this one
+    pub fn merge(&mut self, other: StatsBatch) {
+        for (status, count) in other.statuses {
+            *self.statuses.entry(status).or_default() += count;
+        }
+
+        for (error, count) in other.errors {
+            *self.errors.entry(error).or_default() += count;
+        }
+    }
+
+    /// number of requests tallied
+    pub fn len(&self) -> usize {
+        self.statuses.values().sum::<usize>() + self.errors.values().sum::<usize>()
+    }
+
+    /// whether or not any requests have been tallied
+    pub fn is_empty(&self) -> bool {
+        self.statuses.is_empty() && self.errors.is_empty()
+    }
+}
+
+/// `StatsBatch`es shared between request workers and the stats handler
+///
+/// each worker thread tallies into its own shard, so workers rarely contend with one another.
+/// A shard is handed back to its worker to be sent once it holds `STATS_BATCH_SIZE` requests,
+/// and the stats handler drains whatever's left in every shard on each of its ticks, so tallies
+/// are never stale for longer than a bar refresh
+#[derive(Debug)]
+pub struct StatsBatches {
+    /// one batch per shard, a worker's shard is picked by its thread's id
+    shards: Vec<Mutex<StatsBatch>>,
+}
+
+/// implementation of StatsBatches
+impl StatsBatches {
+    /// create a shard for each of the machine's available cores
+    pub fn new() -> Self {
+        let count = thread::available_parallelism().map_or(4, |count| count.get());
+
+        Self {
+            shards: (0..count)
+                .map(|_| Mutex::new(StatsBatch::default()))
+                .collect(),
+        }
+    }
+
+    /// the current thread's shard
+    fn shard(&self) -> &Mutex<StatsBatch> {
+        let mut hasher = DefaultHasher::new();
+        thread::current().id().hash(&mut hasher);
+
+        &self.shards[hasher.finish() as usize % self.shards.len()]
+    }
+
+    /// apply `update` to the current thread's shard, returning the shard's batch when it's full
+    fn update<F>(&self, update: F) -> Option<StatsBatch>
+    where
+        F: FnOnce(&mut StatsBatch),
+    {
+        let mut batch = self.shard().lock().ok()?;
+
+        update(&mut batch);
+
+        if batch.len() >= STATS_BATCH_SIZE {
+            Some(std::mem::take(&mut *batch))
+        } else {
+            None
+        }
+    }
+
+    /// tally a response with the given status code; returns a full batch that needs to be sent
+    pub fn add_status(&self, status: StatusCode) -> Option<StatsBatch> {
+        self.update(|batch| batch.add_status(status))
+    }
+
+    /// tally a failed request; returns a full batch that needs to be sent
+    pub fn add_error(&self, error: StatError) -> Option<StatsBatch> {
+        self.update(|batch| batch.add_error(error))
+    }
+
+    /// empty every shard, returning their combined tallies
+    pub fn drain(&self) -> StatsBatch {
+        let mut drained = StatsBatch::default();
+
+        for shard in &self.shards {
+            if let Ok(mut batch) = shard.lock() {
+                drained.merge(std::mem::take(&mut *batch));
+            }
+        }
+
+        drained
+    }
+}
+
+/// implementation of Default for StatsBatches
+impl Default for StatsBatches {
+    /// see `StatsBatches::new`
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -1,5 +1,5 @@
 use std::{
-    collections::HashMap,
+    collections::{BTreeMap, HashMap},
     convert::TryFrom,
     fs::File,
     io::BufReader,
@@ -17,9 +17,10 @@ use serde_json::Value;
 use crate::{
     traits::FeroxSerialize,
     utils::{fmt_err, open_file, write_to},
+    OUTPUT_SCHEMA_VERSION,
 };
 
-use super::{error::StatError, field::StatField};
+use super::{batch::StatsBatch, error::StatError, field::StatField};
 
 /// Data collection of statistics related to a scan
 #[derive(Default, Debug)]
@@ -69,6 +70,18 @@ pub struct Stats {
     /// response bodies and robots.txt as of v1.11.0
     links_extracted: AtomicUsize,
 
+    /// tracker for number of requests made for extracted links; already counted by `requests`,
+    /// kept separately so crawling and the wordlist can be told apart
+    extracted_requests: AtomicUsize,
+
+    /// tracker for number of hostnames resolved by the os when `--dns-cache-size` is used, i.e.
+    /// lookups that weren't answered by the cache
+    dns_lookups: AtomicUsize,
+
+    /// tracker for number of hostname lookups answered by the cache when `--dns-cache-size` is
+    /// used
+    dns_cache_hits: AtomicUsize,
+
     /// tracker for number of extensions discovered when `--collect-extensions` is used; sources
     /// are response bodies
     extensions_collected: AtomicUsize,
@@ -112,6 +125,10 @@ pub struct Stats {
     /// tracker for number of files found
     resources_discovered: AtomicUsize,
 
+    /// number of directory scans waiting on --scan-limit before they can start; only reflects
+    /// the current run, so it's never loaded from a state file
+    queued_scans: AtomicUsize,
+
     /// tracker for number of errors triggered during URL formatting
     url_format_errors: AtomicUsize,
 
@@ -124,6 +141,31 @@ pub struct Stats {
     /// tracker for number of errors related to the request used
     request_errors: AtomicUsize,
 
+    /// tracker for number of errors resolving a target's hostname
+    dns_errors: AtomicUsize,
+
+    /// tracker for number of errors negotiating TLS
+    tls_errors: AtomicUsize,
+
+    /// tracker for number of connections reset/aborted by the target
+    reset_errors: AtomicUsize,
+
+    /// tracker for number of responses that exceeded the client's size limits
+    too_large_errors: AtomicUsize,
+
+    /// tracker for number of response bodies that couldn't be read/decoded
+    decode_errors: AtomicUsize,
+
+    /// tracker for number of requests that couldn't be sent through the replay proxy
+    replay_errors: AtomicUsize,
+
+    /// tracker for number of responses whose body was cut short or didn't match its
+    /// Content-Length
+    truncated_responses: AtomicUsize,
+
+    /// tracker for errors seen per target (scheme://host:port), broken down by error class
+    target_errors: Mutex<BTreeMap<String, BTreeMap<String, usize>>>,
+
     /// tracker for each directory's total scan time in seconds as a float
     directory_scan_times: Mutex<Vec<f64>>,
 
@@ -158,9 +200,10 @@ impl Serialize for Stats {
     where
         S: Serializer,
     {
-        let mut state = serializer.serialize_struct("Stats", 32)?;
+        let mut state = serializer.serialize_struct("Stats", 41)?;
 
         state.serialize_field("type", &self.kind)?;
+        state.serialize_field("schema_version", &OUTPUT_SCHEMA_VERSION)?;
         state.serialize_field("timeouts", &atomic_load!(self.timeouts))?;
         state.serialize_field("requests", &atomic_load!(self.requests))?;
         state.serialize_field("expected_per_scan", &atomic_load!(self.expected_per_scan))?;
@@ -173,6 +216,9 @@ impl Serialize for Stats {
         state.serialize_field("total_scans", &atomic_load!(self.total_scans))?;
         state.serialize_field("initial_targets", &atomic_load!(self.initial_targets))?;
         state.serialize_field("links_extracted", &atomic_load!(self.links_extracted))?;
+        state.serialize_field("extracted_requests", &atomic_load!(self.extracted_requests))?;
+        state.serialize_field("dns_lookups", &atomic_load!(self.dns_lookups))?;
+        state.serialize_field("dns_cache_hits", &atomic_load!(self.dns_cache_hits))?;
         state.serialize_field(
             "extensions_collected",
             &atomic_load!(self.extensions_collected),
@@ -193,10 +239,22 @@ impl Serialize for Stats {
             "resources_discovered",
             &atomic_load!(self.resources_discovered),
         )?;
+        state.serialize_field("queued_scans", &atomic_load!(self.queued_scans))?;
         state.serialize_field("url_format_errors", &atomic_load!(self.url_format_errors))?;
         state.serialize_field("redirection_errors", &atomic_load!(self.redirection_errors))?;
         state.serialize_field("connection_errors", &atomic_load!(self.connection_errors))?;
         state.serialize_field("request_errors", &atomic_load!(self.request_errors))?;
+        state.serialize_field("dns_errors", &atomic_load!(self.dns_errors))?;
+        state.serialize_field("tls_errors", &atomic_load!(self.tls_errors))?;
+        state.serialize_field("reset_errors", &atomic_load!(self.reset_errors))?;
+        state.serialize_field("too_large_errors", &atomic_load!(self.too_large_errors))?;
+        state.serialize_field("decode_errors", &atomic_load!(self.decode_errors))?;
+        state.serialize_field("replay_errors", &atomic_load!(self.replay_errors))?;
+        state.serialize_field(
+            "truncated_responses",
+            &atomic_load!(self.truncated_responses),
+        )?;
+        state.serialize_field("target_errors", &self.target_errors)?;
         state.serialize_field("directory_scan_times", &self.directory_scan_times)?;
         state.serialize_field("total_runtime", &self.total_runtime)?;
         state.serialize_field("targets", &self.targets)?;
@@ -302,6 +360,27 @@ impl<'a> Deserialize<'a> for Stats {
                         }
                     }
                 }
+                "extracted_requests" => {
+                    if let Some(num) = value.as_u64() {
+                        if let Ok(parsed) = usize::try_from(num) {
+                            atomic_increment!(stats.extracted_requests, parsed);
+                        }
+                    }
+                }
+                "dns_lookups" => {
+                    if let Some(num) = value.as_u64() {
+                        if let Ok(parsed) = usize::try_from(num) {
+                            atomic_increment!(stats.dns_lookups, parsed);
+                        }
+                    }
+                }
+                "dns_cache_hits" => {
+                    if let Some(num) = value.as_u64() {
+                        if let Ok(parsed) = usize::try_from(num) {
+                            atomic_increment!(stats.dns_cache_hits, parsed);
+                        }
+                    }
+                }
                 "extensions_collected" => {
                     if let Some(num) = value.as_u64() {
                         if let Ok(parsed) = usize::try_from(num) {
@@ -428,6 +507,76 @@ impl<'a> Deserialize<'a> for Stats {
                         }
                     }
                 }
+                "dns_errors" => {
+                    if let Some(num) = value.as_u64() {
+                        if let Ok(parsed) = usize::try_from(num) {
+                            atomic_increment!(stats.dns_errors, parsed);
+                        }
+                    }
+                }
+                "tls_errors" => {
+                    if let Some(num) = value.as_u64() {
+                        if let Ok(parsed) = usize::try_from(num) {
+                            atomic_increment!(stats.tls_errors, parsed);
+                        }
+                    }
+                }
+                "reset_errors" => {
+                    if let Some(num) = value.as_u64() {
+                        if let Ok(parsed) = usize::try_from(num) {
+                            atomic_increment!(stats.reset_errors, parsed);
+                        }
+                    }
+                }
+                "too_large_errors" => {
+                    if let Some(num) = value.as_u64() {
+                        if let Ok(parsed) = usize::try_from(num) {
+                            atomic_increment!(stats.too_large_errors, parsed);
+                        }
+                    }
+                }
+                "decode_errors" => {
+                    if let Some(num) = value.as_u64() {
+                        if let Ok(parsed) = usize::try_from(num) {
+                            atomic_increment!(stats.decode_errors, parsed);
+                        }
+                    }
+                }
+                "replay_errors" => {
+                    if let Some(num) = value.as_u64() {
+                        if let Ok(parsed) = usize::try_from(num) {
+                            atomic_increment!(stats.replay_errors, parsed);
+                        }
+                    }
+                }
+                "truncated_responses" => {
+                    if let Some(num) = value.as_u64() {
+                        if let Ok(parsed) = usize::try_from(num) {
+                            atomic_increment!(stats.truncated_responses, parsed);
+                        }
+                    }
+                }
+                "target_errors" => {
+                    if let Some(targets) = value.as_object() {
+                        if let Ok(mut guard) = stats.target_errors.lock() {
+                            for (target, classes) in targets {
+                                let Some(classes) = classes.as_object() else {
+                                    continue;
+                                };
+
+                                let entry = guard.entry(target.to_string()).or_default();
+
+                                for (class, count) in classes {
+                                    if let Some(parsed) =
+                                        count.as_u64().and_then(|n| usize::try_from(n).ok())
+                                    {
+                                        *entry.entry(class.to_string()).or_default() += parsed;
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
                 "directory_scan_times" => {
                     if let Some(arr) = value.as_array() {
                         for val in arr {
@@ -492,6 +641,11 @@ impl Stats {
         atomic_load!(self.resources_discovered)
     }
 
+    /// public getter for queued_scans
+    pub fn queued_scans(&self) -> usize {
+        atomic_load!(self.queued_scans)
+    }
+
     /// public getter for errors
     pub fn errors(&self) -> usize {
         atomic_load!(self.errors)
@@ -517,18 +671,39 @@ impl Stats {
         atomic_load!(self.initial_targets)
     }
 
+    /// public getter for requests
+    pub fn requests(&self) -> usize {
+        atomic_load!(self.requests)
+    }
+
     /// increment `requests` field by one
     pub fn add_request(&self) {
         atomic_increment!(self.requests);
     }
 
     /// given an `Instant` update total runtime
-    fn update_runtime(&self, seconds: f64) {
+    pub(crate) fn update_runtime(&self, seconds: f64) {
         if let Ok(mut runtime) = self.total_runtime.lock() {
             runtime[0] = seconds;
         }
     }
 
+    /// total runtime of the scan, in seconds, as of the last save or the stats handler's exit
+    pub fn total_runtime(&self) -> f64 {
+        self.total_runtime
+            .lock()
+            .map(|runtime| runtime[0])
+            .unwrap_or_default()
+    }
+
+    /// the initial targets that were passed in to the scan
+    pub fn targets(&self) -> Vec<String> {
+        self.targets
+            .lock()
+            .map(|targets| targets.clone())
+            .unwrap_or_default()
+    }
+
     /// update targets with the given vector of strings
     pub fn update_targets(&self, targets: Vec<String>) {
         if let Ok(mut locked_targets) = self.targets.lock() {
@@ -572,10 +747,53 @@ impl Stats {
             StatError::Request => {
                 atomic_increment!(self.request_errors);
             }
+            StatError::Dns => {
+                atomic_increment!(self.dns_errors);
+            }
+            StatError::Tls => {
+                atomic_increment!(self.tls_errors);
+            }
+            StatError::Reset => {
+                atomic_increment!(self.reset_errors);
+            }
+            StatError::TooLarge => {
+                atomic_increment!(self.too_large_errors);
+            }
+            StatError::Decode => {
+                atomic_increment!(self.decode_errors);
+            }
             _ => {} // no need to hit Other as we always increment self.errors anyway
         }
     }
 
+    /// Record an error of the given class against the given target (scheme://host:port)
+    ///
+    /// does not affect any of the overall counters; those are updated by `add_error`
+    pub fn add_target_error(&self, target: &str, error: StatError) {
+        if let Ok(mut guard) = self.target_errors.lock() {
+            *guard
+                .entry(target.to_string())
+                .or_default()
+                .entry(error.as_str().to_string())
+                .or_default() += 1;
+        }
+    }
+
+    /// Apply every request outcome tallied in the given `StatsBatch`
+    pub fn add_batch(&self, batch: &StatsBatch) {
+        for (status, count) in &batch.statuses {
+            for _ in 0..*count {
+                self.add_status_code(*status);
+            }
+        }
+
+        for (error, count) in &batch.errors {
+            for _ in 0..*count {
+                self.add_error(*error);
+            }
+        }
+    }
+
     /// Inspect the given `StatusCode` and increment the appropriate fields
     ///
     /// Implies incrementing:
@@ -641,8 +859,14 @@ impl Stats {
 
     /// subtract a value from the given field
     pub fn subtract_from_usize_field(&self, field: StatField, value: usize) {
-        if let StatField::TotalExpected = field {
-            self.total_expected.fetch_sub(value, Ordering::Relaxed);
+        match field {
+            StatField::TotalExpected => {
+                self.total_expected.fetch_sub(value, Ordering::Relaxed);
+            }
+            StatField::QueuedScans => {
+                self.queued_scans.fetch_sub(value, Ordering::Relaxed);
+            }
+            _ => {}
         }
     }
 
@@ -665,6 +889,15 @@ impl Stats {
             StatField::LinksExtracted => {
                 atomic_increment!(self.links_extracted, value);
             }
+            StatField::ExtractedRequests => {
+                atomic_increment!(self.extracted_requests, value);
+            }
+            StatField::DnsLookups => {
+                atomic_increment!(self.dns_lookups, value);
+            }
+            StatField::DnsCacheHits => {
+                atomic_increment!(self.dns_cache_hits, value);
+            }
             StatField::ExtensionsCollected => {
                 atomic_increment!(self.extensions_collected, value);
             }
@@ -681,6 +914,15 @@ impl Stats {
             StatField::InitialTargets => {
                 atomic_increment!(self.initial_targets, value);
             }
+            StatField::ReplayErrors => {
+                atomic_increment!(self.replay_errors, value);
+            }
+            StatField::TruncatedResponses => {
+                atomic_increment!(self.truncated_responses, value);
+            }
+            StatField::QueuedScans => {
+                atomic_increment!(self.queued_scans, value);
+            }
             _ => {} // f64 fields
         }
     }
@@ -704,6 +946,12 @@ impl Stats {
             atomic_increment!(self.client_errors, atomic_load!(d_stats.client_errors));
             atomic_increment!(self.server_errors, atomic_load!(d_stats.server_errors));
             atomic_increment!(self.links_extracted, atomic_load!(d_stats.links_extracted));
+            atomic_increment!(
+                self.extracted_requests,
+                atomic_load!(d_stats.extracted_requests)
+            );
+            atomic_increment!(self.dns_lookups, atomic_load!(d_stats.dns_lookups));
+            atomic_increment!(self.dns_cache_hits, atomic_load!(d_stats.dns_cache_hits));
             atomic_increment!(
                 self.extensions_collected,
                 atomic_load!(d_stats.extensions_collected)
@@ -743,6 +991,31 @@ impl Stats {
                 atomic_load!(d_stats.redirection_errors)
             );
             atomic_increment!(self.request_errors, atomic_load!(d_stats.request_errors));
+            atomic_increment!(self.dns_errors, atomic_load!(d_stats.dns_errors));
+            atomic_increment!(self.tls_errors, atomic_load!(d_stats.tls_errors));
+            atomic_increment!(self.reset_errors, atomic_load!(d_stats.reset_errors));
+            atomic_increment!(
+                self.too_large_errors,
+                atomic_load!(d_stats.too_large_errors)
+            );
+            atomic_increment!(self.decode_errors, atomic_load!(d_stats.decode_errors));
+            atomic_increment!(self.replay_errors, atomic_load!(d_stats.replay_errors));
+            atomic_increment!(
+                self.truncated_responses,
+                atomic_load!(d_stats.truncated_responses)
+            );
+
+            if let (Ok(mut ours), Ok(theirs)) =
+                (self.target_errors.lock(), d_stats.target_errors.lock())
+            {
+                for (target, classes) in theirs.iter() {
+                    let entry = ours.entry(target.to_string()).or_default();
+
+                    for (class, count) in classes {
+                        *entry.entry(class.to_string()).or_default() += count;
+                    }
+                }
+            }
 
             if let Ok(scan_times) = d_stats.directory_scan_times.lock() {
                 for scan_time in scan_times.iter() {
@@ -779,6 +1052,49 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+    /// outcomes recorded through a batched handle are all applied by the time the handler
+    /// exits, whether or not a batch filled up and was sent
+    async fn statistics_handler_applies_batched_outcomes() {
+        use crate::traits::StatsRecorder;
+
+        let (task, handle) = setup_stats_test();
+
+        for _ in 0..crate::STATS_BATCH_SIZE + 3 {
+            handle.add_status(reqwest::StatusCode::FORBIDDEN);
+        }
+
+        handle.add_error(StatError::Timeout);
+
+        teardown_stats_test(handle.tx.clone(), task).await;
+
+        let expected = crate::STATS_BATCH_SIZE + 3;
+
+        assert_eq!(handle.data.status_403s.load(Ordering::Relaxed), expected);
+        assert_eq!(handle.data.timeouts.load(Ordering::Relaxed), 1);
+        assert_eq!(handle.data.requests.load(Ordering::Relaxed), expected + 1);
+    }
+
+    #[test]
+    /// a shard's batch is only handed back once it's full, and drain empties every shard
+    fn stats_batches_fill_and_drain() {
+        let batches = StatsBatches::new();
+
+        for _ in 0..crate::STATS_BATCH_SIZE - 1 {
+            assert!(batches.add_status(reqwest::StatusCode::OK).is_none());
+        }
+
+        let full = batches.add_error(StatError::Dns).unwrap();
+        assert_eq!(full.len(), crate::STATS_BATCH_SIZE);
+        assert_eq!(full.errors.get(&StatError::Dns), Some(&1));
+
+        batches.add_status(reqwest::StatusCode::OK);
+
+        let drained = batches.drain();
+        assert_eq!(drained.len(), 1);
+        assert!(batches.drain().is_empty());
+    }
+
     #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
     /// when sent StatCommand::AddRequest, stats object should reflect the change
     ///
@@ -842,6 +1158,30 @@ mod tests {
         assert_eq!(stats.timeouts.load(Ordering::Relaxed), 4);
     }
 
+    #[test]
+    /// classified errors increment their own counter along with the overall error count, while
+    /// add_target_error only tracks the per-target breakdown
+    fn stats_increments_classified_errors() {
+        let config = Configuration::new().unwrap();
+        let stats = Stats::new(config.json);
+
+        stats.add_error(StatError::Dns);
+        stats.add_error(StatError::Tls);
+        stats.add_error(StatError::Reset);
+        stats.add_target_error("https://localhost", StatError::Reset);
+        stats.add_target_error("https://localhost", StatError::Reset);
+        stats.add_target_error("http://localhost:8080", StatError::Dns);
+
+        assert_eq!(stats.errors.load(Ordering::Relaxed), 3);
+        assert_eq!(stats.dns_errors.load(Ordering::Relaxed), 1);
+        assert_eq!(stats.tls_errors.load(Ordering::Relaxed), 1);
+        assert_eq!(stats.reset_errors.load(Ordering::Relaxed), 1);
+
+        let target_errors = stats.target_errors.lock().unwrap();
+        assert_eq!(target_errors["https://localhost"]["reset"], 2);
+        assert_eq!(target_errors["http://localhost:8080"]["dns"], 1);
+    }
+
     #[test]
     /// when Stats::update_usize_field receives StatField::WildcardsFiltered, it should increment
     /// the following:
@@ -875,10 +1215,27 @@ mod tests {
         assert_eq!(stats.responses_filtered.load(Ordering::Relaxed), 3);
     }
 
+    #[test]
+    /// queued scans are added when a scan starts waiting and subtracted once it starts
+    fn stats_tracks_queued_scans() {
+        let config = Configuration::new().unwrap();
+        let stats = Stats::new(config.json);
+
+        stats.update_usize_field(StatField::QueuedScans, 1);
+        stats.update_usize_field(StatField::QueuedScans, 1);
+        assert_eq!(stats.queued_scans(), 2);
+
+        stats.subtract_from_usize_field(StatField::QueuedScans, 1);
+        assert_eq!(stats.queued_scans(), 1);
+
+        let json = serde_json::to_string(&stats).unwrap();
+        assert!(json.contains(r#""queued_scans":1"#));
+    }
+
     #[test]
     /// Stats::merge_from should properly increment expected fields and ignore others
     fn stats_merge_from_alters_correct_fields() {
-        let contents = r#"{"statistics":{"type":"statistics","timeouts":1,"requests":9207,"expected_per_scan":707,"total_expected":9191,"errors":3,"successes":720,"redirects":13,"client_errors":8474,"server_errors":2,"total_scans":13,"initial_targets":1,"links_extracted":51,"extensions_collected":4,"status_403s":3,"status_200s":720,"status_301s":12,"status_302s":1,"status_401s":4,"status_429s":2,"status_500s":5,"status_503s":9,"status_504s":6,"status_508s":7,"wildcards_filtered":707,"responses_filtered":707,"resources_discovered":27,"directory_scan_times":[2.211973078,1.989015505,1.898675839,3.9714468910000003,4.938152838,5.256073528,6.021986595,6.065740734,6.42633762,7.095142125,7.336982137,5.319785619,4.843649778],"total_runtime":[11.556575456000001],"url_format_errors":17,"redirection_errors":12,"connection_errors":21,"request_errors":4}}"#;
+        let contents = r#"{"statistics":{"type":"statistics","timeouts":1,"requests":9207,"expected_per_scan":707,"total_expected":9191,"errors":3,"successes":720,"redirects":13,"client_errors":8474,"server_errors":2,"total_scans":13,"initial_targets":1,"links_extracted":51,"extracted_requests":48,"dns_lookups":3,"dns_cache_hits":97,"extensions_collected":4,"status_403s":3,"status_200s":720,"status_301s":12,"status_302s":1,"status_401s":4,"status_429s":2,"status_500s":5,"status_503s":9,"status_504s":6,"status_508s":7,"wildcards_filtered":707,"responses_filtered":707,"resources_discovered":27,"directory_scan_times":[2.211973078,1.989015505,1.898675839,3.9714468910000003,4.938152838,5.256073528,6.021986595,6.065740734,6.42633762,7.095142125,7.336982137,5.319785619,4.843649778],"total_runtime":[11.556575456000001],"url_format_errors":17,"redirection_errors":12,"connection_errors":21,"request_errors":4,"dns_errors":5,"tls_errors":6,"reset_errors":7,"too_large_errors":8,"decode_errors":9,"replay_errors":10,"truncated_responses":11,"target_errors":{"https://localhost":{"dns":5,"tls":6}}}}"#;
         let config = Configuration::new().unwrap();
         let stats = Stats::new(config.json);
 
@@ -919,6 +1276,20 @@ mod tests {
         assert_eq!(atomic_load!(stats.redirection_errors), 12);
         assert_eq!(atomic_load!(stats.connection_errors), 21);
         assert_eq!(atomic_load!(stats.request_errors), 4);
+        assert_eq!(atomic_load!(stats.dns_errors), 5);
+        assert_eq!(atomic_load!(stats.tls_errors), 6);
+        assert_eq!(atomic_load!(stats.reset_errors), 7);
+        assert_eq!(atomic_load!(stats.too_large_errors), 8);
+        assert_eq!(atomic_load!(stats.decode_errors), 9);
+        assert_eq!(atomic_load!(stats.replay_errors), 10);
+        assert_eq!(atomic_load!(stats.truncated_responses), 11);
+        assert_eq!(atomic_load!(stats.extracted_requests), 48);
+        assert_eq!(atomic_load!(stats.dns_lookups), 3);
+        assert_eq!(atomic_load!(stats.dns_cache_hits), 97);
+        assert_eq!(
+            stats.target_errors.lock().unwrap()["https://localhost"]["tls"],
+            6
+        );
         assert_eq!(stats.directory_scan_times.lock().unwrap().len(), 13);
         for scan in stats.directory_scan_times.lock().unwrap().iter() {
             assert!(scan.max(0.0) > 0.0); // all scans are non-zero
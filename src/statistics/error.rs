@@ -1,4 +1,13 @@
-#[derive(Debug, Copy, Clone)]
+use std::{error::Error, io::ErrorKind};
+
+use anyhow::Context;
+use console::style;
+use serde::{Deserialize, Serialize};
+
+use crate::traits::FeroxSerialize;
+use crate::utils::fmt_err;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
 /// Enum variants used to inform the `StatCommand` protocol what `Stats` fields should be updated
 pub enum StatError {
     /// Represents a timeout error
@@ -16,6 +25,236 @@ pub enum StatError {
     /// Represents an error resulting from the client's request
     Request,
 
+    /// Represents a failure to resolve the target's hostname
+    Dns,
+
+    /// Represents a failure to negotiate TLS with the target (handshake, certificates, etc)
+    Tls,
+
+    /// Represents a connection that was reset, aborted, or closed early by the target
+    Reset,
+
+    /// Represents a response whose headers or body exceeded the client's limits
+    TooLarge,
+
+    /// Represents a response body that couldn't be read or decoded
+    Decode,
+
     /// Represents any other error not explicitly defined above
     Other,
 }
+
+/// implementation of StatError
+impl StatError {
+    /// short name of the error class, used in stats and error events
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            StatError::Timeout => "timeout",
+            StatError::UrlFormat => "url-format",
+            StatError::Redirection => "redirection",
+            StatError::Connection => "connect",
+            StatError::Request => "request",
+            StatError::Dns => "dns",
+            StatError::Tls => "tls",
+            StatError::Reset => "reset",
+            StatError::TooLarge => "too-large",
+            StatError::Decode => "decode",
+            StatError::Other => "other",
+        }
+    }
+}
+
+/// classify a failed request based on the error returned by reqwest
+impl From<&reqwest::Error> for StatError {
+    fn from(error: &reqwest::Error) -> Self {
+        if error.is_timeout() {
+            return StatError::Timeout;
+        }
+
+        if error.is_redirect() {
+            return StatError::Redirection;
+        }
+
+        // reqwest's own classification is coarse (a dns failure and a tls failure are both
+        // connect errors), so the underlying cause is checked before falling back to it. the
+        // top-level error is skipped, as its message contains the (user-controlled) url
+        if let Some(classified) = error.source().and_then(classify_source) {
+            return classified;
+        }
+
+        if error.is_decode() || error.is_body() {
+            StatError::Decode
+        } else if error.is_connect() {
+            StatError::Connection
+        } else if error.is_request() {
+            StatError::Request
+        } else {
+            StatError::Other
+        }
+    }
+}
+
+/// walk an error's chain of sources, looking for a more specific root cause than what's exposed
+/// by the top-level error
+pub(super) fn classify_source(error: &(dyn Error + 'static)) -> Option<StatError> {
+    let mut current = Some(error);
+
+    while let Some(err) = current {
+        if let Some(io_error) = err.downcast_ref::<std::io::Error>() {
+            match io_error.kind() {
+                ErrorKind::ConnectionReset
+                | ErrorKind::ConnectionAborted
+                | ErrorKind::BrokenPipe
+                | ErrorKind::UnexpectedEof => return Some(StatError::Reset),
+                ErrorKind::TimedOut => return Some(StatError::Timeout),
+                _ => {}
+            }
+        }
+
+        let message = err.to_string().to_lowercase();
+
+        if message.contains("dns error")
+            || message.contains("failed to lookup address")
+            || message.contains("name or service not known")
+            || message.contains("no such host")
+        {
+            return Some(StatError::Dns);
+        }
+
+        if message.contains("certificate")
+            || message.contains("tls")
+            || message.contains("ssl")
+            || message.contains("handshake")
+        {
+            return Some(StatError::Tls);
+        }
+
+        if message.contains("too large") || message.contains("too long") {
+            return Some(StatError::TooLarge);
+        }
+
+        current = err.source();
+    }
+
+    None
+}
+
+#[derive(Serialize, Deserialize, Default, Debug, Clone, PartialEq, Eq)]
+/// A single failed request, written to the output file when --error-events is used
+pub struct ErrorEvent {
+    #[serde(rename = "type")]
+    /// Name of this type of struct, used for serialization, i.e. `{"type":"error"}`
+    pub(crate) kind: String,
+
+    /// The url that failed
+    pub(crate) url: String,
+
+    /// The HTTP method used
+    pub(crate) method: String,
+
+    /// class of the error, as given by `StatError::as_str`
+    pub(crate) error: String,
+
+    /// the full error message
+    pub(crate) message: String,
+}
+
+/// implementation of ErrorEvent
+impl ErrorEvent {
+    /// create a new ErrorEvent from the given url, method, error class, and message
+    pub fn new(url: &str, method: &str, error: StatError, message: &str) -> Self {
+        Self {
+            kind: String::from("error"),
+            url: url.to_string(),
+            method: method.to_string(),
+            error: error.as_str().to_string(),
+            message: message.to_string(),
+        }
+    }
+}
+
+/// Implementation of ErrorEvent
+impl FeroxSerialize for ErrorEvent {
+    /// Create a string representation of the error event
+    ///
+    /// ex:
+    /// ERR      GET timeout https://localhost/slow (operation timed out)
+    fn as_str(&self) -> String {
+        format!(
+            "{} {:>8} {} {} ({})\n",
+            style("ERR").bg(console::Color::Red).black(),
+            self.method,
+            style(&self.error).red(),
+            self.url,
+            style(&self.message).dim(),
+        )
+    }
+
+    /// Create an NDJSON representation of the error event
+    ///
+    /// (expanded for clarity)
+    /// ex:
+    /// {
+    ///   "type": "error",
+    ///   "url": "https://localhost/slow",
+    ///   "method": "GET",
+    ///   "error": "timeout",
+    ///   "message": "error sending request for url (https://localhost/slow)"
+    /// }\n
+    fn as_json(&self) -> anyhow::Result<String> {
+        let mut json = serde_json::to_string(&self)
+            .with_context(|| fmt_err(&format!("Could not convert {} to JSON", self.url)))?;
+        json.push('\n');
+        Ok(json)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io;
+
+    #[test]
+    /// the source chain is inspected for io error kinds and well-known messages
+    fn classify_source_finds_root_cause() {
+        let reset = io::Error::from(ErrorKind::ConnectionReset);
+        assert_eq!(classify_source(&reset), Some(StatError::Reset));
+
+        let dns = io::Error::new(
+            ErrorKind::Other,
+            "dns error: failed to lookup address information",
+        );
+        assert_eq!(classify_source(&dns), Some(StatError::Dns));
+
+        let tls = io::Error::new(ErrorKind::Other, "invalid peer certificate: UnknownIssuer");
+        assert_eq!(classify_source(&tls), Some(StatError::Tls));
+
+        let too_large = io::Error::new(ErrorKind::Other, "message head is too large");
+        assert_eq!(classify_source(&too_large), Some(StatError::TooLarge));
+
+        let unknown = io::Error::new(ErrorKind::Other, "something else entirely");
+        assert_eq!(classify_source(&unknown), None);
+    }
+
+    #[test]
+    /// test as_str and as_json methods of ErrorEvent
+    fn error_event_serializes() {
+        let event = ErrorEvent::new(
+            "https://localhost/slow",
+            "GET",
+            StatError::TooLarge,
+            "message head is too large",
+        );
+
+        let event_str = console::strip_ansi_codes(&event.as_str()).to_string();
+        assert!(event_str.contains("too-large https://localhost/slow"));
+        assert!(event_str.ends_with('\n'));
+
+        let json = event.as_json().unwrap();
+        assert!(json.contains(r#""type":"error""#));
+        assert!(json.contains(r#""error":"too-large""#));
+
+        let deserialized: ErrorEvent = serde_json::from_str(&json).unwrap();
+        assert_eq!(deserialized, event);
+    }
+}
@@ -13,6 +13,15 @@ pub enum StatField {
     /// Translates to `links_extracted`
     LinksExtracted,
 
+    /// Translates to `extracted_requests`; requests made for links found via extraction
+    ExtractedRequests,
+
+    /// Translates to `dns_lookups`; hostnames resolved by the os while --dns-cache-size is used
+    DnsLookups,
+
+    /// Translates to `dns_cache_hits`; lookups answered by the --dns-cache-size cache
+    DnsCacheHits,
+
     /// Translates to `extensions_collected`
     ExtensionsCollected,
 
@@ -31,6 +40,16 @@ pub enum StatField {
     /// Translates to `initial_targets`
     InitialTargets,
 
+    /// Translates to `replay_errors`
+    ReplayErrors,
+
+    /// Translates to `truncated_responses`
+    TruncatedResponses,
+
+    /// Translates to `queued_scans`; decremented via `SubtractFromUsizeField` once a queued
+    /// scan starts
+    QueuedScans,
+
     /// Translates to `directory_scan_times`; assumes a single append to the vector
     DirScanTimes,
 }
@@ -1,3 +1,4 @@
+mod batch;
 mod error;
 mod macros;
 mod container;
@@ -5,8 +6,9 @@ mod field;
 #[cfg(test)]
 mod tests;
 
+pub use self::batch::{StatsBatch, StatsBatches};
 pub use self::container::Stats;
-pub use self::error::StatError;
+pub use self::error::{ErrorEvent, StatError};
 pub use self::field::StatField;
 
 #[cfg(test)]
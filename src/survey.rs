@@ -0,0 +1,149 @@
+use std::collections::BTreeMap;
+
+use anyhow::Context;
+use console::style;
+use serde::{Deserialize, Serialize};
+
+use crate::traits::FeroxSerialize;
+use crate::utils::fmt_err;
+
+/// security-relevant response headers collected by --survey-headers
+pub(crate) const SECURITY_HEADERS: [&str; 9] = [
+    "content-security-policy",
+    "strict-transport-security",
+    "x-frame-options",
+    "x-content-type-options",
+    "referrer-policy",
+    "permissions-policy",
+    "cross-origin-opener-policy",
+    "cross-origin-resource-policy",
+    "cross-origin-embedder-policy",
+];
+
+#[derive(Serialize, Deserialize, Default, Debug, Clone, PartialEq, Eq)]
+/// Results of a one-shot, per-host survey of security headers and TRACE availability
+pub struct HeaderSurvey {
+    #[serde(rename = "type")]
+    /// Name of this type of struct, used for serialization, i.e. `{"type":"header_survey"}`
+    pub(crate) kind: String,
+
+    /// The url that was surveyed
+    pub(crate) url: String,
+
+    /// security headers that were present in the response, mapped to their values
+    pub(crate) present: BTreeMap<String, String>,
+
+    /// security headers that were absent from the response
+    pub(crate) missing: Vec<String>,
+
+    /// methods advertised by the `Allow` header of an OPTIONS request
+    pub(crate) allowed_methods: Vec<String>,
+
+    /// whether or not the server reflected a TRACE request
+    pub(crate) trace_enabled: bool,
+}
+
+/// Implementation of HeaderSurvey
+impl FeroxSerialize for HeaderSurvey {
+    /// Create a string representation of the survey
+    ///
+    /// ex:
+    /// SRV https://localhost/ hsts: max-age=31536000 | missing: content-security-policy | TRACE enabled
+    fn as_str(&self) -> String {
+        let mut parts = Vec::new();
+
+        for (name, value) in &self.present {
+            parts.push(format!("{name}: {value}"));
+        }
+
+        if !self.missing.is_empty() {
+            parts.push(format!(
+                "{}: {}",
+                style("missing").yellow(),
+                self.missing.join(", ")
+            ));
+        }
+
+        if !self.allowed_methods.is_empty() {
+            parts.push(format!("allow: {}", self.allowed_methods.join(", ")));
+        }
+
+        if self.trace_enabled {
+            parts.push(style("TRACE enabled").red().to_string());
+        }
+
+        format!(
+            "{} {} {}\n",
+            style("SRV").bg(console::Color::Blue).black(),
+            self.url,
+            parts.join(" | ")
+        )
+    }
+
+    /// Create an NDJSON representation of the survey
+    ///
+    /// (expanded for clarity)
+    /// ex:
+    /// {
+    ///   "type": "header_survey",
+    ///   "url": "https://localhost/",
+    ///   "present": {"strict-transport-security": "max-age=31536000"},
+    ///   "missing": ["content-security-policy", "x-frame-options"],
+    ///   "allowed_methods": ["GET", "HEAD", "OPTIONS", "TRACE"],
+    ///   "trace_enabled": true
+    /// }\n
+    fn as_json(&self) -> anyhow::Result<String> {
+        let mut json = serde_json::to_string(&self)
+            .with_context(|| fmt_err(&format!("Could not convert {} to JSON", self.url)))?;
+        json.push('\n');
+        Ok(json)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn survey() -> HeaderSurvey {
+        let mut present = BTreeMap::new();
+        present.insert(
+            String::from("strict-transport-security"),
+            String::from("max-age=31536000"),
+        );
+
+        HeaderSurvey {
+            kind: String::from("header_survey"),
+            url: String::from("https://localhost/"),
+            present,
+            missing: vec![String::from("content-security-policy")],
+            allowed_methods: vec![String::from("GET"), String::from("TRACE")],
+            trace_enabled: true,
+        }
+    }
+
+    #[test]
+    /// test as_str method of HeaderSurvey
+    fn header_survey_as_str_returns_string_with_newline() {
+        let survey_str = console::strip_ansi_codes(&survey().as_str()).to_string();
+
+        assert!(survey_str.starts_with("SRV https://localhost/"));
+        assert!(survey_str.contains("strict-transport-security: max-age=31536000"));
+        assert!(survey_str.contains("missing: content-security-policy"));
+        assert!(survey_str.contains("allow: GET, TRACE"));
+        assert!(survey_str.contains("TRACE enabled"));
+        assert!(survey_str.ends_with('\n'));
+    }
+
+    #[test]
+    /// test as_json method of HeaderSurvey
+    fn header_survey_as_json_round_trips() {
+        let original = survey();
+        let json = original.as_json().unwrap();
+
+        assert!(json.contains(r#""type":"header_survey""#));
+        assert!(json.ends_with('\n'));
+
+        let deserialized: HeaderSurvey = serde_json::from_str(&json).unwrap();
+        assert_eq!(deserialized, original);
+    }
+}
@@ -0,0 +1,253 @@
+//! tags that mark findings worth a closer look, so the interesting ones can be picked out of a
+//! large set of results during triage
+//!
+//! a handful of rules are built in (see `BUILTIN_TAGS`); more can be added with
+//! --tag-rule NAME=REGEX, which tags any finding whose url or body matches the regex
+use anyhow::{bail, Result};
+use lazy_static::lazy_static;
+use regex::Regex;
+use reqwest::StatusCode;
+use scraper::Html;
+
+use crate::{config::Configuration, heuristics::directory_listing_type, response::FeroxResponse};
+
+/// names of the built-in tags, in the order they're applied
+pub const BUILTIN_TAGS: [&str; 4] = ["5xx", "auth-endpoint", "backup-file", "directory-listing"];
+
+lazy_static! {
+    /// path segments that commonly belong to login / sso / token endpoints
+    static ref AUTH_SEGMENT: Regex = Regex::new(
+        r"(?i)^(log[-_]?in|log[-_]?on|sign[-_]?in|auth|authenticate|authori[sz]e|oauth2?|sso|saml2?|openid(-connect)?|cas|token)(\.\w+)?$"
+    )
+    .unwrap();
+}
+
+/// a user-supplied --tag-rule; findings whose url or body match `regex` are tagged with `name`
+#[derive(Debug, Clone)]
+pub struct TagRule {
+    /// tag added to matching findings
+    name: String,
+
+    /// pattern matched against a finding's url and body
+    regex: Regex,
+}
+
+/// implementation of TagRule
+impl TagRule {
+    /// parse a NAME=REGEX pair, as given to --tag-rule
+    pub fn parse(rule: &str) -> Result<Self> {
+        let Some((name, pattern)) = rule.split_once('=') else {
+            bail!("expected NAME=REGEX, got {rule}");
+        };
+
+        let name = name.trim();
+
+        if name.is_empty() || name.contains(char::is_whitespace) || name.contains(',') {
+            bail!("tag names can't be empty or contain whitespace or commas, got '{name}'");
+        }
+
+        Ok(Self {
+            name: name.to_string(),
+            regex: Regex::new(pattern)?,
+        })
+    }
+
+    /// whether the given response's url or body matches this rule
+    fn matches(&self, response: &FeroxResponse) -> bool {
+        self.regex.is_match(response.url().as_str()) || self.regex.is_match(response.text())
+    }
+}
+
+/// applies the built-in and user-supplied tag rules to findings
+#[derive(Debug, Clone)]
+pub(crate) struct Tagger {
+    /// suffixes that mark a url as a backup of another file; same as --backup-extensions
+    backup_extensions: Vec<String>,
+
+    /// rules given with --tag-rule
+    rules: Vec<TagRule>,
+}
+
+/// implementation of Tagger
+impl Tagger {
+    /// create a Tagger from the given config; None when neither --tag-findings nor --tag-rule
+    /// was used
+    ///
+    /// rules that don't parse were already reported during config parsing, so they're skipped
+    /// here
+    pub(crate) fn new(config: &Configuration) -> Option<Self> {
+        if !config.tag_findings && config.tag_rules.is_empty() {
+            return None;
+        }
+
+        let rules = config
+            .tag_rules
+            .iter()
+            .filter_map(|rule| match TagRule::parse(rule) {
+                Ok(rule) => Some(rule),
+                Err(e) => {
+                    log::warn!("Skipping --tag-rule {}: {}", rule, e);
+                    None
+                }
+            })
+            .collect();
+
+        Some(Self {
+            backup_extensions: config.backup_extensions.clone(),
+            rules,
+        })
+    }
+
+    /// every tag that applies to the given response; built-in tags come first, followed by the
+    /// --tag-rule tags in the order they were given
+    pub(crate) fn tags(&self, response: &FeroxResponse) -> Vec<String> {
+        log::trace!("enter: tags({})", response.url());
+
+        let mut tags = Vec::new();
+
+        if response.status().is_server_error() {
+            tags.push(BUILTIN_TAGS[0].to_string());
+        }
+
+        if is_auth_endpoint(response) {
+            tags.push(BUILTIN_TAGS[1].to_string());
+        }
+
+        if self.is_backup_file(response) {
+            tags.push(BUILTIN_TAGS[2].to_string());
+        }
+
+        if is_directory_listing(response) {
+            tags.push(BUILTIN_TAGS[3].to_string());
+        }
+
+        for rule in &self.rules {
+            if !tags.contains(&rule.name) && rule.matches(response) {
+                tags.push(rule.name.clone());
+            }
+        }
+
+        log::trace!("exit: tags -> {:?}", tags);
+        tags
+    }
+
+    /// whether the url's last path segment ends in one of the --backup-extensions
+    fn is_backup_file(&self, response: &FeroxResponse) -> bool {
+        let Some(segment) = response
+            .url()
+            .path_segments()
+            .and_then(|mut segments| segments.next_back())
+        else {
+            return false;
+        };
+
+        self.backup_extensions
+            .iter()
+            .any(|extension| segment.len() > extension.len() && segment.ends_with(extension))
+    }
+}
+
+/// whether the response asks for credentials, or its url looks like a login / sso / token
+/// endpoint
+fn is_auth_endpoint(response: &FeroxResponse) -> bool {
+    let status = *response.status();
+
+    if status == StatusCode::UNAUTHORIZED
+        || status == StatusCode::PROXY_AUTHENTICATION_REQUIRED
+        || response.headers().contains_key("www-authenticate")
+    {
+        return true;
+    }
+
+    response
+        .url()
+        .path_segments()
+        .is_some_and(|mut segments| segments.any(|segment| AUTH_SEGMENT.is_match(segment)))
+}
+
+/// whether the response is a server-generated directory listing
+fn is_directory_listing(response: &FeroxResponse) -> bool {
+    // parsing html is comparatively expensive, skip bodies that can't have a <title>
+    if !response.text().to_ascii_lowercase().contains("<title") {
+        return false;
+    }
+
+    directory_listing_type(&Html::parse_document(response.text())).is_some()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::response::fixtures::response;
+
+    /// tagger with tagging turned on and the given --tag-rule values
+    fn tagger(rules: &[&str]) -> Tagger {
+        let mut config = Configuration::new().unwrap();
+        config.tag_findings = true;
+        config.tag_rules = rules.iter().map(|rule| rule.to_string()).collect();
+        Tagger::new(&config).unwrap()
+    }
+
+    #[test]
+    /// each built-in rule tags the findings it's meant to, and nothing else
+    fn tagger_applies_builtin_tags() {
+        let tagger = tagger(&[]);
+
+        let cases = [
+            ("http://localhost/api/users", 500, "", vec!["5xx"]),
+            ("http://localhost/login", 200, "", vec!["auth-endpoint"]),
+            ("http://localhost/admin", 401, "", vec!["auth-endpoint"]),
+            (
+                "http://localhost/index.php.bak",
+                200,
+                "",
+                vec!["backup-file"],
+            ),
+            (
+                "http://localhost/files/",
+                200,
+                "<html><title>Index of /files</title></html>",
+                vec!["directory-listing"],
+            ),
+            (
+                "http://localhost/about",
+                200,
+                "<title>About</title>",
+                vec![],
+            ),
+        ];
+
+        for (url, status, body, expected) in cases {
+            let mut response = response(url, status);
+            response.set_text(body);
+
+            assert_eq!(tagger.tags(&response), expected, "{url}");
+        }
+    }
+
+    #[test]
+    /// --tag-rule tags follow the built-in ones, match on url or body, and aren't repeated
+    fn tagger_applies_user_rules_after_builtin_tags() {
+        let tagger = tagger(&["secret=(?i)api[_-]?key", "admin=/admin", "5xx=/admin"]);
+
+        let mut response = response("http://localhost/admin/config", 503);
+        response.set_text("API_KEY=abc123");
+
+        let tags = tagger.tags(&response);
+
+        assert_eq!(tags, vec!["5xx", "secret", "admin"]);
+    }
+
+    #[test]
+    /// tagging is off unless asked for, and malformed rules are rejected
+    fn tagger_is_opt_in_and_rules_are_validated() {
+        let config = Configuration::new().unwrap();
+        assert!(Tagger::new(&config).is_none());
+
+        assert!(TagRule::parse("no-equals-sign").is_err());
+        assert!(TagRule::parse("=/admin").is_err());
+        assert!(TagRule::parse("two words=/admin").is_err());
+        assert!(TagRule::parse("broken=[unclosed").is_err());
+        assert!(TagRule::parse("admin=/admin").is_ok());
+    }
+}
@@ -0,0 +1,221 @@
+//! user-defined line format for findings (--output-format), so results can be shaped into
+//! whatever downstream tools expect without piping them through awk
+//!
+//! templates use handlebars-style placeholders, i.e. `{{status}} {{url}}`; see `PLACEHOLDERS`
+//! for the full list. `\t`, `\n`, and `\\` are understood as escapes, since tabs are awkward to
+//! type on the command line
+use std::sync::OnceLock;
+
+use anyhow::{bail, Result};
+
+use crate::response::FeroxResponse;
+
+/// the --output-format template in use, if any; set once at startup
+pub static OUTPUT_TEMPLATE: OnceLock<OutputTemplate> = OnceLock::new();
+
+/// names that can appear between `{{` and `}}`; `header.NAME` is also accepted, for any NAME
+pub const PLACEHOLDERS: [&str; 11] = [
+    "status", "method", "url", "path", "lines", "words", "chars", "headers", "redirect", "tags",
+    "language",
+];
+
+/// one piece of a parsed template
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Part {
+    /// text that's written as-is
+    Literal(String),
+
+    /// one of the `PLACEHOLDERS`
+    Field(String),
+
+    /// `{{header.NAME}}`, the value of the named response header
+    Header(String),
+}
+
+/// a parsed --output-format template
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OutputTemplate {
+    /// literal text and placeholders, in order
+    parts: Vec<Part>,
+}
+
+/// implementation of OutputTemplate
+impl OutputTemplate {
+    /// parse the given template, failing on unknown or unterminated placeholders
+    pub fn parse(template: &str) -> Result<Self> {
+        let template = unescape(template);
+
+        let mut parts = Vec::new();
+        let mut rest = template.as_str();
+
+        while let Some(start) = rest.find("{{") {
+            if start > 0 {
+                parts.push(Part::Literal(rest[..start].to_string()));
+            }
+
+            let Some(end) = rest[start..].find("}}") else {
+                bail!("unterminated placeholder in {template}");
+            };
+
+            let name = rest[start + 2..start + end].trim();
+
+            if let Some(header) = name.strip_prefix("header.") {
+                if header.is_empty() {
+                    bail!("expected a header name after {{{{header.}}}}");
+                }
+                parts.push(Part::Header(header.to_string()));
+            } else if PLACEHOLDERS.contains(&name) {
+                parts.push(Part::Field(name.to_string()));
+            } else {
+                bail!(
+                    "unknown placeholder {{{{{name}}}}}, expected one of {} or header.NAME",
+                    PLACEHOLDERS.join(", ")
+                );
+            }
+
+            rest = &rest[start + end + 2..];
+        }
+
+        if !rest.is_empty() {
+            parts.push(Part::Literal(rest.to_string()));
+        }
+
+        Ok(Self { parts })
+    }
+
+    /// fill in the template with the given response's details; the result ends in a newline, like
+    /// the normal report lines do
+    pub fn render(&self, response: &FeroxResponse) -> String {
+        let mut line = String::new();
+
+        for part in &self.parts {
+            match part {
+                Part::Literal(text) => line.push_str(text),
+                Part::Header(name) => line.push_str(&header_value(response, name)),
+                Part::Field(name) => line.push_str(&field_value(response, name)),
+            }
+        }
+
+        line.push('\n');
+        line
+    }
+}
+
+/// the value of a `PLACEHOLDERS` entry for the given response
+fn field_value(response: &FeroxResponse, name: &str) -> String {
+    match name {
+        "status" => response.status().as_str().to_string(),
+        "method" => response.method().as_str().to_string(),
+        "url" => response.url().to_string(),
+        "path" => response.url().path().to_string(),
+        "lines" => response.line_count().to_string(),
+        "words" => response.word_count().to_string(),
+        "chars" => response.content_length().to_string(),
+        "headers" => response
+            .headers()
+            .iter()
+            .map(|(name, value)| format!("{}: {}", name, value.to_str().unwrap_or_default()))
+            .collect::<Vec<_>>()
+            .join("; "),
+        "redirect" => header_value(response, "location"),
+        "tags" => response.tags().join(","),
+        "language" => response.language().unwrap_or_default().to_string(),
+        _ => String::new(), // names are checked when the template is parsed
+    }
+}
+
+/// the value of the named header, or an empty string when it's missing or isn't valid text;
+/// repeated headers are joined with a comma
+fn header_value(response: &FeroxResponse, name: &str) -> String {
+    response
+        .headers()
+        .get_all(name)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+/// replace `\t`, `\n`, and `\\` with the characters they stand for; any other backslash is kept
+fn unescape(template: &str) -> String {
+    let mut unescaped = String::with_capacity(template.len());
+    let mut chars = template.chars();
+
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            unescaped.push(c);
+            continue;
+        }
+
+        match chars.next() {
+            Some('t') => unescaped.push('\t'),
+            Some('n') => unescaped.push('\n'),
+            Some('\\') => unescaped.push('\\'),
+            Some(other) => {
+                unescaped.push('\\');
+                unescaped.push(other);
+            }
+            None => unescaped.push('\\'),
+        }
+    }
+
+    unescaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::response::fixtures::response;
+    use reqwest::header::HeaderValue;
+
+    /// a 302 to /login with a couple of headers and a two-line body
+    fn login_redirect() -> FeroxResponse {
+        let mut response = response("http://localhost/admin", 302);
+        response.set_text("one two\nthree");
+        response
+            .headers_mut()
+            .insert("location", HeaderValue::from_static("/login"));
+        response
+            .headers_mut()
+            .insert("server", HeaderValue::from_static("nginx"));
+        response
+    }
+
+    #[test]
+    /// placeholders are filled in, escapes are honored, and literal text is kept
+    fn output_template_renders_placeholders() {
+        let template = OutputTemplate::parse(
+            r"{{status}}\t{{ method }}\t{{url}}\t{{path}}\t{{lines}} {{words}} {{chars}}\t{{redirect}}\t{{header.Server}}",
+        )
+        .unwrap();
+
+        assert_eq!(
+            template.render(&login_redirect()),
+            "302\tGET\thttp://localhost/admin\t/admin\t2 3 13\t/login\tnginx\n"
+        );
+    }
+
+    #[test]
+    /// missing headers and unset fields render as empty strings
+    fn output_template_renders_missing_values_as_empty() {
+        let template =
+            OutputTemplate::parse("[{{header.x-missing}}][{{tags}}][{{language}}]").unwrap();
+
+        assert_eq!(template.render(&login_redirect()), "[][][]\n");
+
+        let template = OutputTemplate::parse("{{headers}}").unwrap();
+        assert_eq!(
+            template.render(&login_redirect()),
+            "location: /login; server: nginx\n"
+        );
+    }
+
+    #[test]
+    /// unknown, empty, and unterminated placeholders are rejected
+    fn output_template_rejects_bad_placeholders() {
+        assert!(OutputTemplate::parse("{{size}}").is_err());
+        assert!(OutputTemplate::parse("{{header.}}").is_err());
+        assert!(OutputTemplate::parse("{{url").is_err());
+        assert!(OutputTemplate::parse("just text").is_ok());
+    }
+}
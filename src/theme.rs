@@ -0,0 +1,147 @@
+//! ascii-safe fallbacks for the emoji and box-drawing characters used in terminal output
+//!
+//! plain ttys and some windows consoles can't draw either, which turns the banner, menu, and
+//! status messages into mojibake. When --ascii is used, or the terminal doesn't look like it
+//! can handle emoji, everything printed to the terminal goes through `themed`
+use std::{
+    borrow::Cow,
+    sync::atomic::{AtomicBool, Ordering},
+};
+
+use console::Term;
+
+/// whether terminal output should stick to ascii; set once at startup
+static ASCII: AtomicBool = AtomicBool::new(false);
+
+/// switch terminal output to (or from) the ascii-safe theme
+pub fn set_ascii(enabled: bool) {
+    ASCII.store(enabled, Ordering::Relaxed);
+}
+
+/// whether terminal output is using the ascii-safe theme
+pub fn ascii() -> bool {
+    ASCII.load(Ordering::Relaxed)
+}
+
+/// whether stderr is a terminal that can't be trusted with emoji (non-UTF-8 locale on unix,
+/// legacy console on windows)
+///
+/// output that isn't going to a terminal is left alone, since it's usually being saved or
+/// piped somewhere that handles unicode just fine
+pub fn terminal_lacks_unicode() -> bool {
+    let term = Term::stderr();
+    term.is_term() && !term.features().wants_emoji()
+}
+
+/// `unicode` normally, or `fallback` when the ascii-safe theme is in use
+pub fn glyph<'a>(unicode: &'a str, fallback: &'a str) -> &'a str {
+    if ascii() {
+        fallback
+    } else {
+        unicode
+    }
+}
+
+/// the given text, made ascii-safe when the ascii-safe theme is in use; see `asciify`
+pub fn themed(text: &str) -> Cow<'_, str> {
+    if ascii() {
+        Cow::Owned(asciify(text))
+    } else {
+        Cow::Borrowed(text)
+    }
+}
+
+/// replace box-drawing characters and status emoji with ascii look-alikes, and drop any other
+/// emoji along with the space that separated it from the surrounding text
+pub fn asciify(text: &str) -> String {
+    let mut asciified = String::with_capacity(text.len());
+    let mut chars = text.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if let Some(replacement) = replacement(c) {
+            asciified.push_str(replacement);
+            continue;
+        }
+
+        if !is_emoji(c) {
+            asciified.push(c);
+            continue;
+        }
+
+        // emoji are decorative; don't leave a doubled or dangling space where one used to be
+        if asciified.is_empty() || asciified.ends_with(' ') {
+            match chars.peek() {
+                Some(' ') => {
+                    chars.next();
+                }
+                None => {
+                    asciified.pop();
+                }
+                _ => {}
+            }
+        }
+    }
+
+    asciified
+}
+
+/// ascii stand-in for characters that carry meaning, rather than just decoration
+fn replacement(c: char) -> Option<&'static str> {
+    match c {
+        '─' | '━' | '═' => Some("-"),
+        '│' | '┃' | '║' => Some("|"),
+        '✅' => Some("[+]"),
+        '❌' => Some("[-]"),
+        '🚨' => Some("[!]"),
+        '💀' => Some("[x]"),
+        '™' => Some(""),
+        // remaining box-drawing corners and junctions
+        c if ('\u{2500}'..='\u{257F}').contains(&c) => Some("+"),
+        _ => None,
+    }
+}
+
+/// whether the given character is part of an emoji (or similar pictograph)
+fn is_emoji(c: char) -> bool {
+    matches!(
+        c,
+        '\u{2300}'..='\u{23FF}'       // miscellaneous technical (⏳, ⏸)
+            | '\u{2600}'..='\u{27BF}' // miscellaneous symbols and dingbats
+            | '\u{2B00}'..='\u{2BFF}' // miscellaneous symbols and arrows
+            | '\u{1F000}'..='\u{1FAFF}'
+            | '\u{FE0F}'              // emoji presentation selector
+            | '\u{200D}' // zero width joiner
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    /// box-drawing characters keep their width, and status emoji become ascii markers
+    fn asciify_replaces_box_drawing_and_status_emoji() {
+        assert_eq!(asciify("───┬───"), "---+---");
+        assert_eq!(asciify(" 🎯  Target │ x"), "  Target | x");
+        assert_eq!(
+            asciify("🚨 Caught ctrl+c 🚨 saving"),
+            "[!] Caught ctrl+c [!] saving"
+        );
+        assert_eq!(asciify("❌❌ Could not save"), "[-][-] Could not save");
+    }
+
+    #[test]
+    /// decorative emoji are dropped without leaving doubled or trailing spaces behind
+    fn asciify_drops_decorative_emoji() {
+        assert_eq!(
+            asciify("=> 🚦 removed rate limiter 🚀"),
+            "=> removed rate limiter"
+        );
+        assert_eq!(asciify("⏳ 5 minutes remaining ⏳"), "5 minutes remaining");
+        assert_eq!(asciify("🛡️ CDN/WAF"), "CDN/WAF");
+        assert_eq!(
+            asciify("http://localhost/ünïcödé"),
+            "http://localhost/ünïcödé"
+        );
+    }
+}
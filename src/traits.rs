@@ -4,9 +4,11 @@ use crate::filters::{
     WordsFilter,
 };
 use crate::response::FeroxResponse;
+use crate::statistics::StatError;
 use crate::utils::status_colorizer;
 use anyhow::Result;
 use crossterm::style::{style, Stylize};
+use reqwest::StatusCode;
 use serde::Serialize;
 use std::any::Any;
 use std::fmt::{self, Debug, Display, Formatter};
@@ -26,6 +28,25 @@ pub trait FeroxFilter: Debug + Send + Sync {
 
     /// gives us `other` as Any in box_eq
     fn as_any(&self) -> &dyn Any;
+
+    /// relative cost of running this filter against a response; cheaper filters are run first
+    fn cost(&self) -> FilterCost {
+        FilterCost::Moderate
+    }
+}
+
+/// relative cost of evaluating a `FeroxFilter`, used to order the filter chain so that a response
+/// is rejected by the cheapest applicable filter
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub enum FilterCost {
+    /// compares precomputed values, i.e. status code or content length
+    Cheap,
+
+    /// does a small amount of work per response
+    Moderate,
+
+    /// examines the entire response body, i.e. regex or similarity hashing
+    Expensive,
 }
 
 impl Display for dyn FeroxFilter {
@@ -108,4 +129,38 @@ pub trait FeroxSerialize: Serialize {
 
     /// Return an NDJSON representation of the object
     fn as_json(&self) -> Result<String>;
+
+    /// Return a CSV row containing the given columns, or None if the implementor isn't part of
+    /// CSV output (only findings are written as rows)
+    fn as_csv(&self, _columns: &[String]) -> Option<String> {
+        None
+    }
+}
+
+/// StatsRecorder trait; represents the different ways the outcome of a request can make its
+/// way to the stats handler (sent right away, or tallied and sent in batches)
+pub trait StatsRecorder: Debug {
+    /// Record a response with the given status code
+    fn add_status(&self, status: StatusCode);
+
+    /// Record a failed request
+    fn add_error(&self, error: StatError);
+
+    /// Record a failed request against the given target (scheme://host:port)
+    fn add_target_error(&self, target: String, error: StatError);
+}
+
+/// implementation of StatsRecorder for references to implementors, so handles can be lent out
+impl<T: StatsRecorder + ?Sized> StatsRecorder for &T {
+    fn add_status(&self, status: StatusCode) {
+        (**self).add_status(status)
+    }
+
+    fn add_error(&self, error: StatError) {
+        (**self).add_error(error)
+    }
+
+    fn add_target_error(&self, target: String, error: StatError) {
+        (**self).add_target_error(target, error)
+    }
 }
@@ -0,0 +1,388 @@
+use std::collections::HashMap;
+use std::env::{
+    self,
+    consts::{ARCH, OS},
+};
+use std::fs;
+use std::path::Path;
+
+use anyhow::{bail, Context, Result};
+use minisign_verify::{PublicKey, Signature};
+use reqwest::header::ACCEPT;
+use self_update::{
+    backends::github::Update, cargo_crate_version, update::ReleaseAsset, ArchiveKind, Compression,
+    Download, Extract, Status,
+};
+use sha2::{Digest, Sha256};
+use uuid::Uuid;
+
+/// name of the release asset that holds the sha256 checksums of all other release assets
+pub const CHECKSUMS_ASSET: &str = "SHA256SUMS";
+
+/// name of the release asset that holds the minisign signature of `CHECKSUMS_ASSET`
+pub const SIGNATURE_ASSET: &str = "SHA256SUMS.minisig";
+
+/// minisign public key used to verify `SIGNATURE_ASSET`, embedded at build time by setting
+/// `FEROX_UPDATE_PUBKEY`; when present, a valid signature is required in order to update
+const UPDATE_PUBLIC_KEY: Option<&str> = option_env!("FEROX_UPDATE_PUBKEY");
+
+/// name of the binary contained within each release archive
+const BIN_NAME: &str = "feroxbuster";
+
+/// parse the contents of a `sha256sum`-style checksums file into a map of filename => hash
+///
+/// expected line format is `<hex digest>  <filename>`, where the filename may be prefixed
+/// with `*` (binary mode)
+pub fn parse_checksums(contents: &str) -> HashMap<String, String> {
+    log::trace!("enter: parse_checksums({})", contents);
+
+    let mut checksums = HashMap::new();
+
+    for line in contents.lines() {
+        let mut parts = line.split_whitespace();
+
+        if let (Some(digest), Some(filename)) = (parts.next(), parts.next()) {
+            checksums.insert(
+                filename.trim_start_matches('*').to_string(),
+                digest.to_lowercase(),
+            );
+        }
+    }
+
+    log::trace!("exit: parse_checksums -> {:?}", checksums);
+    checksums
+}
+
+/// return the lowercase hex-encoded sha256 digest of the given bytes
+pub fn sha256_hex(bytes: &[u8]) -> String {
+    Sha256::digest(bytes)
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect()
+}
+
+/// ensure the sha256 digest of `contents` matches the entry for `filename` in `checksums`
+///
+/// fails closed: a missing entry is treated the same as a mismatch
+pub fn verify_checksum(
+    filename: &str,
+    contents: &[u8],
+    checksums: &HashMap<String, String>,
+) -> Result<()> {
+    log::trace!("enter: verify_checksum({}, {:?})", filename, checksums);
+
+    let Some(expected) = checksums.get(filename) else {
+        bail!("{CHECKSUMS_ASSET} has no entry for {filename}; refusing to update");
+    };
+
+    let actual = sha256_hex(contents);
+
+    if actual != *expected {
+        bail!(
+            "checksum mismatch for {filename} (expected {expected}, got {actual}); refusing to update"
+        );
+    }
+
+    log::trace!("exit: verify_checksum");
+    Ok(())
+}
+
+/// verify the minisign `signature` over `checksums` when a public key was embedded at build time
+///
+/// when no key is embedded, verification is skipped and only checksums are enforced
+pub fn verify_signature(checksums: &[u8], signature: Option<&str>) -> Result<()> {
+    log::trace!("enter: verify_signature({:?})", signature);
+
+    let Some(encoded_key) = UPDATE_PUBLIC_KEY else {
+        log::info!("no update signing key embedded in this build, skipping signature check");
+        log::trace!("exit: verify_signature");
+        return Ok(());
+    };
+
+    let Some(signature) = signature else {
+        bail!("release is missing {SIGNATURE_ASSET}; refusing to update");
+    };
+
+    let public_key = PublicKey::from_base64(encoded_key)
+        .map_err(|e| anyhow::anyhow!("invalid embedded update signing key: {e}"))?;
+
+    let signature = Signature::decode(signature)
+        .map_err(|e| anyhow::anyhow!("could not decode {SIGNATURE_ASSET}: {e}"))?;
+
+    public_key
+        .verify(checksums, &signature, false)
+        .map_err(|e| {
+            anyhow::anyhow!(
+                "signature verification of {CHECKSUMS_ASSET} failed: {e}; refusing to update"
+            )
+        })?;
+
+    log::trace!("exit: verify_signature");
+    Ok(())
+}
+
+/// download the given release asset into memory
+fn download_asset(asset: &ReleaseAsset, show_progress: bool) -> Result<Vec<u8>> {
+    log::trace!("enter: download_asset({:?}, {})", asset, show_progress);
+
+    let mut contents = Vec::new();
+
+    Download::from_url(&asset.download_url)
+        .set_header(ACCEPT, "application/octet-stream".parse()?)
+        .show_progress(show_progress)
+        .download_to(&mut contents)
+        .with_context(|| format!("Could not download {}", asset.name))?;
+
+    log::trace!("exit: download_asset -> {} bytes", contents.len());
+    Ok(contents)
+}
+
+/// extract the feroxbuster binary from a (previously verified) release archive and replace the
+/// currently running executable with it
+pub fn install_archive(archive_name: &str, contents: &[u8]) -> Result<()> {
+    log::trace!("enter: install_archive({})", archive_name);
+
+    let archive_kind = if archive_name.ends_with(".zip") {
+        ArchiveKind::Zip
+    } else if archive_name.ends_with(".tar.gz") || archive_name.ends_with(".tgz") {
+        ArchiveKind::Tar(Some(Compression::Gz))
+    } else {
+        bail!("unsupported release archive format: {archive_name}");
+    };
+
+    let tmp_dir =
+        env::temp_dir().join(format!("feroxbuster-update-{}", Uuid::new_v4().as_simple()));
+
+    fs::create_dir_all(&tmp_dir)
+        .with_context(|| format!("Could not create {}", tmp_dir.display()))?;
+
+    let result = extract_and_replace(&tmp_dir, archive_name, archive_kind, contents);
+
+    fs::remove_dir_all(&tmp_dir).unwrap_or_default();
+
+    log::trace!("exit: install_archive");
+    result
+}
+
+/// helper for `install_archive`, split out so the temporary directory is always cleaned up
+fn extract_and_replace(
+    tmp_dir: &Path,
+    archive_name: &str,
+    archive_kind: ArchiveKind,
+    contents: &[u8],
+) -> Result<()> {
+    let archive_path = tmp_dir.join(archive_name);
+
+    fs::write(&archive_path, contents)
+        .with_context(|| format!("Could not write {}", archive_path.display()))?;
+
+    let bin_name = if OS == "windows" {
+        format!("{BIN_NAME}.exe")
+    } else {
+        BIN_NAME.to_string()
+    };
+
+    Extract::from_source(&archive_path)
+        .archive(archive_kind)
+        .extract_file(tmp_dir, &bin_name)
+        .with_context(|| format!("Could not extract {bin_name} from {archive_name}"))?;
+
+    let new_exe = tmp_dir.join(&bin_name);
+
+    self_update::self_replace::self_replace(&new_exe)
+        .with_context(|| "Could not replace the running executable")?;
+
+    Ok(())
+}
+
+/// check github for a newer release and, if one exists, download it, verify it against the
+/// published checksums (and signature, when configured), then install it
+///
+/// blocking; callers in an async context should use `spawn_blocking`
+pub fn update_app() -> Result<Status> {
+    log::trace!("enter: update_app");
+
+    let target = format!("{ARCH}-{OS}");
+    let current_version = cargo_crate_version!();
+
+    let updater = Update::configure()
+        .repo_owner("epi052")
+        .repo_name("feroxbuster")
+        .bin_name(BIN_NAME)
+        .target(&target)
+        .current_version(current_version)
+        .build()?;
+
+    let release = updater.get_latest_release()?;
+
+    if !self_update::version::bump_is_greater(current_version, &release.version)? {
+        log::trace!("exit: update_app -> up to date");
+        return Ok(Status::UpToDate(current_version.to_string()));
+    }
+
+    let Some(archive) = release.asset_for(&target, None) else {
+        bail!(
+            "No release asset found for {target} in v{}",
+            release.version
+        );
+    };
+
+    let Some(checksums_asset) = release
+        .assets
+        .iter()
+        .find(|asset| asset.name == CHECKSUMS_ASSET)
+    else {
+        bail!(
+            "v{} doesn't publish {CHECKSUMS_ASSET}; refusing to install an unverified binary",
+            release.version
+        );
+    };
+
+    let checksums = download_asset(checksums_asset, false)?;
+
+    let signature = match release
+        .assets
+        .iter()
+        .find(|asset| asset.name == SIGNATURE_ASSET)
+    {
+        Some(asset) => Some(String::from_utf8(download_asset(asset, false)?)?),
+        None => None,
+    };
+
+    verify_signature(&checksums, signature.as_deref())?;
+
+    let contents = download_asset(&archive, true)?;
+
+    verify_checksum(
+        &archive.name,
+        &contents,
+        &parse_checksums(&String::from_utf8_lossy(&checksums)),
+    )?;
+
+    install_archive(&archive.name, &contents)?;
+
+    log::trace!("exit: update_app -> {}", release.version);
+    Ok(Status::Updated(release.version))
+}
+
+/// verify and install a locally provided release archive, for environments that can't reach
+/// github
+///
+/// `SHA256SUMS` (and `SHA256SUMS.minisig`, when a signing key is embedded) are expected to live
+/// in the same directory as the archive
+pub fn update_from_archive(archive_path: &str) -> Result<()> {
+    log::trace!("enter: update_from_archive({})", archive_path);
+
+    let archive = Path::new(archive_path);
+
+    let Some(archive_name) = archive.file_name().and_then(|name| name.to_str()) else {
+        bail!("Could not determine the file name of {archive_path}");
+    };
+
+    let directory = archive.parent().unwrap_or_else(|| Path::new("."));
+
+    let checksums_path = directory.join(CHECKSUMS_ASSET);
+
+    let checksums = fs::read(&checksums_path).with_context(|| {
+        format!(
+            "Could not read {}; refusing to install an unverified binary",
+            checksums_path.display()
+        )
+    })?;
+
+    let signature = fs::read_to_string(directory.join(SIGNATURE_ASSET)).ok();
+
+    verify_signature(&checksums, signature.as_deref())?;
+
+    let contents = fs::read(archive).with_context(|| format!("Could not read {archive_path}"))?;
+
+    verify_checksum(
+        archive_name,
+        &contents,
+        &parse_checksums(&String::from_utf8_lossy(&checksums)),
+    )?;
+
+    install_archive(archive_name, &contents)?;
+
+    log::trace!("exit: update_from_archive");
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    /// sha256sum output, including binary-mode markers, is parsed into filename => digest
+    fn parse_checksums_handles_text_and_binary_mode() {
+        let contents = "ABCDEF0123  x86_64-linux-feroxbuster.tar.gz\n\
+                        0123abcdef *x86_64-windows-feroxbuster.exe.zip\n\
+                        \n";
+
+        let checksums = parse_checksums(contents);
+
+        assert_eq!(checksums.len(), 2);
+        assert_eq!(
+            checksums["x86_64-linux-feroxbuster.tar.gz"],
+            String::from("abcdef0123")
+        );
+        assert_eq!(
+            checksums["x86_64-windows-feroxbuster.exe.zip"],
+            String::from("0123abcdef")
+        );
+    }
+
+    #[test]
+    /// known-answer test for the sha256 helper
+    fn sha256_hex_produces_expected_digest() {
+        assert_eq!(
+            sha256_hex(b""),
+            "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855"
+        );
+    }
+
+    #[test]
+    /// matching digests pass, mismatched or missing digests fail closed
+    fn verify_checksum_fails_closed() {
+        let mut checksums = HashMap::new();
+        checksums.insert(
+            String::from("archive.tar.gz"),
+            String::from("e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855"),
+        );
+
+        assert!(verify_checksum("archive.tar.gz", b"", &checksums).is_ok());
+        assert!(verify_checksum("archive.tar.gz", b"tampered", &checksums).is_err());
+        assert!(verify_checksum("other.tar.gz", b"", &checksums).is_err());
+    }
+
+    #[test]
+    /// an archive without a SHA256SUMS file next to it is rejected before anything is installed
+    fn update_from_archive_requires_checksums() {
+        let tmp_dir = tempfile::TempDir::new().unwrap();
+        let archive = tmp_dir.path().join("x86_64-linux-feroxbuster.tar.gz");
+        fs::write(&archive, b"not really an archive").unwrap();
+
+        let result = update_from_archive(archive.to_str().unwrap());
+
+        assert!(result.is_err());
+        assert!(result.unwrap_err().to_string().contains(CHECKSUMS_ASSET));
+    }
+
+    #[test]
+    /// an archive whose digest doesn't match the SHA256SUMS entry is rejected
+    fn update_from_archive_rejects_checksum_mismatch() {
+        let tmp_dir = tempfile::TempDir::new().unwrap();
+        let archive = tmp_dir.path().join("x86_64-linux-feroxbuster.tar.gz");
+        fs::write(&archive, b"not really an archive").unwrap();
+        fs::write(
+            tmp_dir.path().join(CHECKSUMS_ASSET),
+            format!("{}  x86_64-linux-feroxbuster.tar.gz\n", sha256_hex(b"")),
+        )
+        .unwrap();
+
+        let result = update_from_archive(archive.to_str().unwrap());
+
+        assert!(result.is_err());
+        assert!(result.unwrap_err().to_string().contains("mismatch"));
+    }
+}
@@ -44,11 +44,28 @@ impl FeroxUrl {
         word: &str,
         collected_extensions: HashSet<String>,
     ) -> Result<Vec<Url>> {
-        log::trace!("enter: formatted_urls({})", word);
+        let urls =
+            self.formatted_urls_with_extensions(word, collected_extensions, &HashSet::new())?;
+
+        Ok(urls.into_iter().map(|(url, _)| url).collect())
+    }
+
+    /// Same as `formatted_urls`, except each Url is paired with the extension used to create
+    /// it (None for base_url + word)
+    ///
+    /// extensions in `dropped` (those --adaptive-extensions stopped requesting in this
+    /// directory) are skipped
+    pub fn formatted_urls_with_extensions(
+        &self,
+        word: &str,
+        collected_extensions: HashSet<String>,
+        dropped: &HashSet<String>,
+    ) -> Result<Vec<(Url, Option<String>)>> {
+        log::trace!("enter: formatted_urls_with_extensions({})", word);
 
         let mut urls = vec![];
 
-        let slash = if self.handles.config.add_slash {
+        let slash = if self.should_add_slash() {
             Some("/")
         } else {
             None
@@ -56,7 +73,7 @@ impl FeroxUrl {
 
         match self.format(word, slash) {
             // default request, i.e. no extension
-            Ok(url) => urls.push(url),
+            Ok(url) => urls.push((url, None)),
             Err(_) => self.handles.stats.send(AddError(UrlFormat))?,
         }
 
@@ -67,16 +84,40 @@ impl FeroxUrl {
             .iter()
             .chain(collected_extensions.iter())
         {
+            if dropped.contains(ext) {
+                continue;
+            }
+
             match self.format(word, Some(ext)) {
                 // any extensions passed in
-                Ok(url) => urls.push(url),
+                Ok(url) => urls.push((url, Some(ext.to_owned()))),
                 Err(_) => self.handles.stats.send(AddError(UrlFormat))?,
             }
         }
-        log::trace!("exit: formatted_urls -> {:?}", urls);
+        log::trace!("exit: formatted_urls_with_extensions -> {:?}", urls);
         Ok(urls)
     }
 
+    /// Determine whether a / should be appended to words requested beneath this url
+    ///
+    /// always true with --add-slash; with --auto-slash, the decision made by the slash
+    /// heuristic for this directory (or its closest tested parent) is used
+    pub fn should_add_slash(&self) -> bool {
+        if self.handles.config.add_slash {
+            return true;
+        }
+
+        if !self.handles.config.auto_slash {
+            return false;
+        }
+
+        self.handles
+            .ferox_scans()
+            .ok()
+            .and_then(|scans| scans.add_slash_for(&self.normalize()))
+            .unwrap_or(false)
+    }
+
     /// Simple helper to generate a `Url`
     ///
     /// Errors during parsing `url` or joining `word` are propagated up the call stack
@@ -12,26 +12,37 @@ use std::{
     time::Duration,
     time::{SystemTime, UNIX_EPOCH},
 };
-use tokio::sync::{mpsc::UnboundedSender, oneshot};
+use tokio::sync::oneshot;
+use uuid::Uuid;
 
 use crate::{
     config::Configuration,
-    config::OutputLevel,
-    event_handlers::{
-        Command::{self, AddError, AddStatus},
-        Handles,
-    },
-    progress::PROGRESS_PRINTER,
+    config::{Denier, OutputLevel},
+    event_handlers::{Command, Handles},
+    progress::{bars_enabled, PROGRESS_PRINTER},
     response::FeroxResponse,
-    send_command,
-    statistics::StatError::{Connection, Other, Redirection, Request, Timeout},
-    traits::FeroxSerialize,
+    statistics::{ErrorEvent, StatError},
+    theme::themed,
+    traits::{FeroxSerialize, StatsRecorder},
     USER_AGENTS,
 };
 
 /// simple counter for grabbing 'random' user agents
 static mut USER_AGENT_CTR: usize = 0;
 
+/// Header used to pass a request's trace id along to the replay proxy
+pub const TRACE_HEADER: &str = "X-Ferox-Trace";
+
+/// Per-request trace id; stashed in a `Response`'s extensions by `make_request` so that it
+/// survives the conversion into a `FeroxResponse`
+#[derive(Debug, Clone)]
+pub(crate) struct TraceId(pub(crate) String);
+
+/// simple wrapper to generate a new trace id, formatted as lowercase without hyphens
+pub fn trace_id() -> String {
+    Uuid::new_v4().as_simple().to_string()
+}
+
 /// Given the path to a file, open the file in append mode (create it if it doesn't exist) and
 /// return a reference to the buffered file
 pub fn open_file(filename: &str) -> Result<BufWriter<fs::File>> {
@@ -117,12 +128,20 @@ pub fn module_colorizer(modname: &str) -> String {
 /// If a terminal is not attached, `msg` is printed to stdout, with its ansi
 /// color codes stripped.
 ///
+/// When progress bars are disabled (--no-bars), `msg` is printed to stdout as-is.
+///
 /// additionally, provides a location for future printing options (no color, etc) to be handled
 pub fn ferox_print(msg: &str, bar: &ProgressBar) {
+    let msg = themed(msg);
+
     if user_attended() {
-        bar.println(msg);
+        if bars_enabled() {
+            bar.println(msg);
+        } else {
+            println!("{msg}");
+        }
     } else {
-        let stripped = strip_ansi_codes(msg);
+        let stripped = strip_ansi_codes(&msg);
         println!("{stripped}");
     }
 }
@@ -135,13 +154,27 @@ pub async fn logged_request(
     data: Option<&[u8]>,
     handles: Arc<Handles>,
 ) -> Result<Response> {
-    let client = &handles.config.client;
+    let scans = handles.ferox_scans()?;
+
+    // hosts found to be fronted by an interstitial page get the --interstitial-bypass headers
+    let client = match &handles.config.interstitial_client {
+        Some(bypass) if scans.bypasses_interstitial(url.as_str()) => bypass,
+        _ => &handles.config.client,
+    };
     let level = handles.config.output_level;
-    let tx_stats = handles.stats.tx.clone();
 
-    let response = make_request(client, url, method, data, level, &handles.config, tx_stats).await;
+    // outcomes of scan requests are batched by the stats handle, see `StatsBatches`
+    let response = make_request(
+        client,
+        url,
+        method,
+        data,
+        level,
+        &handles.config,
+        &handles.stats,
+    )
+    .await;
 
-    let scans = handles.ferox_scans()?;
     match response {
         Ok(resp) => {
             match resp.status() {
@@ -155,26 +188,75 @@ pub async fn logged_request(
         Err(e) => {
             log::warn!("err: {:?}", e);
             scans.increment_error(url.as_str());
+
+            if handles.config.error_events {
+                if let Some(request_error) = e.downcast_ref::<reqwest::Error>() {
+                    let event = ErrorEvent::new(
+                        url.as_str(),
+                        method,
+                        StatError::from(request_error),
+                        &format!("{e:#}"),
+                    );
+
+                    handles
+                        .output
+                        .tx_file
+                        .send(Command::WriteError(Box::new(event)))
+                        .unwrap_or_default();
+                }
+            }
+
             bail!(e)
         }
     }
 }
 
-/// Initiate request to the given `Url` using `Client`
+/// Initiate request to the given `Url` using `Client`; a new trace id is generated for the request
 pub async fn make_request(
+    client: &Client,
+    url: &Url,
+    method: &str,
+    data: Option<&[u8]>,
+    output_level: OutputLevel,
+    config: &Configuration,
+    tx_stats: impl StatsRecorder,
+) -> Result<Response> {
+    make_traced_request(
+        client,
+        url,
+        method,
+        data,
+        output_level,
+        config,
+        tx_stats,
+        None,
+    )
+    .await
+}
+
+/// Initiate request to the given `Url` using `Client`
+///
+/// when `trace` is `Some`, the given trace id is reused (used when replaying a finding through
+/// the replay proxy). Otherwise, a new trace id is generated. Either way, the trace id is sent
+/// along in the `X-Ferox-Trace` header and attached to the returned `Response`, so that the
+/// original request and its replay can be matched up in the target's and the proxy's logs
+#[allow(clippy::too_many_arguments)]
+pub async fn make_traced_request(
     client: &Client,
     url: &Url,
     method: &str,
     mut data: Option<&[u8]>,
     output_level: OutputLevel,
     config: &Configuration,
-    tx_stats: UnboundedSender<Command>,
+    tx_stats: impl StatsRecorder,
+    trace: Option<&str>,
 ) -> Result<Response> {
     log::trace!(
-        "enter: make_request(Configuration::Client, {}, {:?}, {:?})",
+        "enter: make_traced_request(Configuration::Client, {}, {:?}, {:?}, {:?})",
         url,
         output_level,
-        tx_stats
+        tx_stats,
+        trace
     );
     let tmp_workaround: Option<&[u8]> = Some(&[0xd_u8, 0xa]); // \r\n
 
@@ -215,13 +297,37 @@ pub async fn make_request(
         request = request.header("User-Agent", user_agent);
     }
 
+    if let Some(validators) = config.validators.get(url.as_str()) {
+        // --changed-since; only safe methods can be made conditional
+        if ["GET", "HEAD"].contains(&method.to_ascii_uppercase().as_str()) {
+            if let Some(etag) = &validators.etag {
+                request = request.header("If-None-Match", etag);
+            }
+
+            if let Some(last_modified) = &validators.last_modified {
+                request = request.header("If-Modified-Since", last_modified);
+            }
+        }
+    }
+
+    let trace_id = match trace {
+        Some(existing) => existing.to_string(),
+        None => trace_id(),
+    };
+
+    request = request.header(TRACE_HEADER, &trace_id);
+
+    log::debug!("[{}] {} {}", trace_id, method, url);
+
     match request.send().await {
         Err(e) => {
-            log::trace!("exit: make_request -> {}", e);
+            log::trace!("exit: make_traced_request -> {}", e);
+
+            let kind = StatError::from(&e);
 
-            if e.is_timeout() {
-                send_command!(tx_stats, AddError(Timeout));
-            } else if e.is_redirect() {
+            tx_stats.add_target_error(url.origin().ascii_serialization(), kind);
+
+            if kind == StatError::Redirection {
                 if let Some(last_redirect) = e.url() {
                     // get where we were headed (last_redirect) and where we came from (url)
                     let fancy_message = format!(
@@ -246,24 +352,19 @@ pub async fn make_request(
                         output_level,
                     );
 
-                    send_command!(tx_stats, AddError(Redirection));
-
                     ferox_print(&report, &PROGRESS_PRINTER)
                 };
-            } else if e.is_connect() {
-                send_command!(tx_stats, AddError(Connection));
-            } else if e.is_request() {
-                send_command!(tx_stats, AddError(Request));
-            } else {
-                send_command!(tx_stats, AddError(Other));
             }
 
-            log::warn!("Error while making request: {}", e);
-            bail!("{}", e)
+            tx_stats.add_error(kind);
+
+            log::warn!("[{}] Error while making request: {}", trace_id, e);
+            Err(e.into())
         }
-        Ok(resp) => {
-            log::trace!("exit: make_request -> {:?}", resp);
-            send_command!(tx_stats, AddStatus(resp.status()));
+        Ok(mut resp) => {
+            log::trace!("exit: make_traced_request -> {:?}", resp);
+            tx_stats.add_status(resp.status());
+            resp.extensions_mut().insert(TraceId(trace_id));
             Ok(resp)
         }
     }
@@ -350,13 +451,13 @@ pub fn set_open_file_limit(limit: u64) -> bool {
     false
 }
 
-/// Given a string and a reference to a locked buffered file, write the contents and flush
-/// the buffer to disk.
-pub fn write_to<T>(
+/// Given a string and a reference to a locked buffered file, write the contents to the buffer
+/// without flushing it; returns the number of bytes written
+pub fn buffer_to<T>(
     value: &T,
     file: &mut io::BufWriter<fs::File>,
     convert_to_json: bool,
-) -> Result<()>
+) -> Result<usize>
 where
     T: FeroxSerialize,
 {
@@ -373,7 +474,21 @@ where
 
     let contents = strip_ansi_codes(&contents);
 
-    let written = file.write(contents.as_bytes())?;
+    Ok(file.write(contents.as_bytes())?)
+}
+
+/// Given a string and a reference to a locked buffered file, write the contents and flush
+/// the buffer to disk.
+pub fn write_to<T>(
+    value: &T,
+    file: &mut io::BufWriter<fs::File>,
+    convert_to_json: bool,
+) -> Result<()>
+where
+    T: FeroxSerialize,
+{
+    // same as buffer_to, no logging in here
+    let written = buffer_to(value, file, convert_to_json)?;
 
     if written > 0 {
         // this function is used within async functions/loops, so i'm flushing so that in
@@ -494,8 +609,18 @@ fn should_deny_regex(url_to_test: &Url, denier: &Regex) -> bool {
     result
 }
 
+/// whether there are any --dont-scan or --dont-scan-file entries to check urls against
+pub fn has_denylist(handles: &Handles) -> bool {
+    !handles.config.url_denylist.is_empty()
+        || !handles.config.regex_denylist.is_empty()
+        || handles
+            .ferox_scans()
+            .map(|scans| scans.has_file_denylist())
+            .unwrap_or(false)
+}
+
 /// determines whether or not a given url should be denied based on the user-supplied --dont-scan
-/// flag
+/// and --dont-scan-file flags
 pub fn should_deny_url(url: &Url, handles: Arc<Handles>) -> Result<bool> {
     log::trace!(
         "enter: should_deny_url({}, {:?}, {:?})",
@@ -524,6 +649,21 @@ pub fn should_deny_url(url: &Url, handles: Arc<Handles>) -> Result<bool> {
         }
     }
 
+    // entries from --dont-scan-file can change mid-scan, so they're read from FeroxScans
+    // instead of the config
+    for denier in handles.ferox_scans()?.file_denylist() {
+        let should_deny = match &denier {
+            Denier::Url(absolute) => {
+                should_deny_absolute(&normed_url, absolute, handles.clone()).unwrap_or(false)
+            }
+            Denier::Regex(regex) => should_deny_regex(&normed_url, regex),
+        };
+
+        if should_deny {
+            return Ok(true);
+        }
+    }
+
     // made it to the end of the deny lists unscathed, return false, indicating we should not deny
     // this particular url
     log::trace!("exit: should_deny_url -> false");
@@ -556,6 +696,144 @@ pub fn slugify_filename(url: &str, prefix: &str, suffix: &str) -> String {
     filename
 }
 
+/// placeholders understood by `render_output_template`
+pub const OUTPUT_PLACEHOLDERS: [&str; 6] = [
+    "{target}",
+    "{host}",
+    "{date}",
+    "{time}",
+    "{timestamp}",
+    "{run_id}",
+];
+
+/// whether or not the given path contains any of the `OUTPUT_PLACEHOLDERS`
+pub fn is_output_template(path: &str) -> bool {
+    OUTPUT_PLACEHOLDERS
+        .iter()
+        .any(|placeholder| path.contains(placeholder))
+}
+
+/// resolve the placeholders in an --output path for a scan of the given target (or "stdin")
+///
+/// - `{target}`: slugified target, ex: http_localhost_admin
+/// - `{host}`: target's host (and port, if given), ex: localhost_8080
+/// - `{date}`: current date (UTC), ex: 2024-06-30
+/// - `{time}`: current time (UTC), ex: 134502
+/// - `{timestamp}`: current unix timestamp, ex: 1719755102
+/// - `{run_id}`: the run's unique id
+///
+/// ex: ferox-{host}-{date}.json -> ferox-localhost_8080-2024-06-30.json
+pub fn render_output_template(template: &str, target: &str, run_id: &str) -> String {
+    if !is_output_template(template) {
+        return template.to_string();
+    }
+
+    let secs = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_else(|_| Duration::from_secs(0))
+        .as_secs();
+
+    // 2024-06-30T13:45:02Z
+    let now = utc_rfc3339(secs);
+
+    let host = Url::parse(target)
+        .ok()
+        .and_then(|url| {
+            url.host_str().map(|host| match url.port() {
+                Some(port) => format!("{host}_{port}"),
+                None => host.to_string(),
+            })
+        })
+        .unwrap_or_else(|| target.to_string());
+
+    let slug = |value: &str| {
+        value
+            .replace("://", "_")
+            .replace(['/', '.', ':', '?', '&', '='], "_")
+            .trim_end_matches('_')
+            .to_string()
+    };
+
+    template
+        .replace("{target}", &slug(target))
+        .replace("{host}", &slug(&host))
+        .replace("{date}", &now[..10])
+        .replace("{time}", &now[11..19].replace(':', ""))
+        .replace("{timestamp}", &secs.to_string())
+        .replace("{run_id}", run_id)
+}
+
+/// (year, month, day) of the given number of days since the unix epoch
+///
+/// ref: https://howardhinnant.github.io/date_algorithms.html#civil_from_days
+fn civil_date(days: u64) -> (u64, u64, u64) {
+    let days = days + 719_468;
+    let era = days / 146_097;
+    let day_of_era = days % 146_097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month_index + 2) / 5 + 1;
+    let month = if month_index < 10 {
+        month_index + 3
+    } else {
+        month_index - 9
+    };
+    let year = year_of_era + era * 400 + u64::from(month <= 2);
+
+    (year, month, day)
+}
+
+/// the given unix timestamp as rfc3339 in utc, i.e. 2024-06-30T12:00:00Z
+pub(crate) fn utc_rfc3339(secs: u64) -> String {
+    let (year, month, day) = civil_date(secs / 86_400);
+    let time_of_day = secs % 86_400;
+
+    format!(
+        "{year:04}-{month:02}-{day:02}T{:02}:{:02}:{:02}Z",
+        time_of_day / 3600,
+        time_of_day % 3600 / 60,
+        time_of_day % 60
+    )
+}
+
+/// name of the state file saved for the given target (or "stdin") during the run with the given
+/// id
+///
+/// ex: ferox-http_telsa_com-1606947491-0b2f5a38-2d6c-4f0e-9c3e-6a1d3a3b8f7e.state
+pub fn state_filename(target: &str, run_id: &str) -> String {
+    let filename = slugify_filename(target, "ferox", "state");
+
+    if run_id.is_empty() {
+        return filename;
+    }
+
+    format!("{}-{run_id}.state", filename.trim_end_matches(".state"))
+}
+
+/// quote a csv field if it contains a delimiter, quote, or newline
+pub(crate) fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+/// the directory a url was found in
+///
+/// ex:
+/// http://localhost/admin/login -> http://localhost/admin/
+/// http://localhost/admin/ -> http://localhost/
+pub fn parent_directory(url: &Url) -> String {
+    let relative = if url.path().ends_with('/') { ".." } else { "." };
+
+    url.join(relative)
+        .map(|parent| parent.to_string())
+        .unwrap_or_else(|_| url.to_string())
+}
+
 /// This function takes a url string and returns a `url::Url`
 ///
 /// It is primarily used to detect url paths that `url::Url::parse` will
@@ -750,6 +1028,106 @@ mod tests {
     use crate::config::Configuration;
     use crate::scan_manager::{FeroxScans, ScanOrder};
 
+    #[test]
+    /// state files are named after the target, with the run id appended when there is one
+    fn state_filename_includes_run_id() {
+        let with_id = state_filename("http://localhost", "abc-123");
+        assert!(with_id.starts_with("ferox-http_localhost-"));
+        assert!(with_id.ends_with("-abc-123.state"));
+
+        let without_id = state_filename("stdin", "");
+        assert!(without_id.starts_with("ferox-stdin-"));
+        assert!(without_id.ends_with(".state"));
+        assert!(!without_id.ends_with("-.state"));
+    }
+
+    #[test]
+    /// parent_directory strips the last path segment, whether or not it's a directory
+    fn parent_directory_strips_last_segment() {
+        let file = Url::parse("http://localhost/admin/login").unwrap();
+        let dir = Url::parse("http://localhost/admin/").unwrap();
+        let root = Url::parse("http://localhost/").unwrap();
+
+        assert_eq!(parent_directory(&file), "http://localhost/admin/");
+        assert_eq!(parent_directory(&dir), "http://localhost/");
+        assert_eq!(parent_directory(&root), "http://localhost/");
+    }
+
+    #[test]
+    /// days since the epoch are converted to the right calendar dates
+    fn civil_date_handles_epoch_and_leap_years() {
+        assert_eq!(civil_date(0), (1970, 1, 1));
+        assert_eq!(civil_date(11_016), (2000, 2, 29));
+        assert_eq!(civil_date(19_905), (2024, 7, 1));
+    }
+
+    #[test]
+    /// timestamps are formatted as rfc3339 in utc
+    fn utc_rfc3339_formats_date_and_time() {
+        assert_eq!(utc_rfc3339(0), "1970-01-01T00:00:00Z");
+        assert_eq!(utc_rfc3339(1_719_755_102), "2024-06-30T13:45:02Z");
+    }
+
+    #[test]
+    /// placeholders are resolved from the target and run id, other paths are left alone
+    fn render_output_template_resolves_placeholders() {
+        let target = "http://localhost:8080/admin";
+
+        assert_eq!(
+            render_output_template("results.txt", target, "abc"),
+            "results.txt"
+        );
+        assert_eq!(
+            render_output_template("ferox-{target}.json", target, "abc"),
+            "ferox-http_localhost_8080_admin.json"
+        );
+        assert_eq!(
+            render_output_template("/tmp/{host}-{run_id}.txt", target, "abc"),
+            "/tmp/localhost_8080-abc.txt"
+        );
+        assert_eq!(
+            render_output_template("{host}.txt", "stdin", "abc"),
+            "stdin.txt"
+        );
+
+        let dated = render_output_template("{date}_{time}_{timestamp}", target, "");
+        let parts: Vec<_> = dated.split('_').collect();
+
+        assert_eq!(parts[0].len(), 10);
+        assert_eq!(parts[1].len(), 6);
+        assert!(parts[2].parse::<u64>().is_ok());
+        assert!(!is_output_template(&dated));
+    }
+
+    #[test]
+    /// csv_field only quotes when necessary
+    fn csv_field_quotes_when_needed() {
+        assert_eq!(csv_field("plain"), "plain");
+        assert_eq!(csv_field("a,b"), "\"a,b\"");
+        assert_eq!(csv_field("say \"hi\""), "\"say \"\"hi\"\"\"");
+    }
+
+    #[test]
+    /// buffer_to leaves contents in the buffer, write_to flushes them to disk
+    fn buffer_to_does_not_flush_but_write_to_does() {
+        let tmp = tempfile::NamedTempFile::new().unwrap();
+        let location = tmp.path().to_str().unwrap();
+        let mut file = open_file(location).unwrap();
+
+        let mut response = FeroxResponse::default();
+        response.set_url("http://localhost/stuff");
+
+        let written = buffer_to(&response, &mut file, true).unwrap();
+
+        assert!(written > 0);
+        assert!(fs::read_to_string(location).unwrap().is_empty());
+
+        write_to(&response, &mut file, true).unwrap();
+
+        let contents = fs::read_to_string(location).unwrap();
+        assert_eq!(contents.lines().count(), 2);
+    }
+
     #[test]
     /// parse_url_with_raw_path with javascript:// should not throw an unimplemented! error
     fn utils_parse_url_with_raw_path_javascript() {
@@ -1233,4 +1611,35 @@ mod tests {
         assert!(!should_deny_url(&tested_https_url, handles.clone()).unwrap());
         assert!(should_deny_url(&tested_http_url, handles).unwrap());
     }
+
+    #[test]
+    /// provide a --dont-scan-file style denylist through FeroxScans instead of the config
+    /// expect urls matching its entries to be denied, and has_denylist to notice the entries
+    fn should_deny_url_blocks_urls_from_file_denylist() {
+        let scan_url = "https://testdomain.com/";
+        let tested_denied_url = Url::parse("https://testdomain.com/denied/").unwrap();
+        let tested_logout_url = Url::parse("https://testdomain.com/api/logout").unwrap();
+        let tested_allowed_url = Url::parse("https://testdomain.com/allowed/").unwrap();
+
+        let scans = Arc::new(FeroxScans::default());
+        scans.add_directory_scan(
+            scan_url,
+            ScanOrder::Initial,
+            Arc::new(Handles::for_testing(None, None).0),
+        );
+
+        let config = Arc::new(Configuration::new().unwrap());
+        let handles = Arc::new(Handles::for_testing(Some(scans.clone()), Some(config)).0);
+
+        assert!(!has_denylist(&handles));
+
+        scans.set_file_denylist(
+            crate::config::parse_denylist("https://testdomain.com/denied\n/logout$").unwrap(),
+        );
+
+        assert!(has_denylist(&handles));
+        assert!(should_deny_url(&tested_denied_url, handles.clone()).unwrap());
+        assert!(should_deny_url(&tested_logout_url, handles.clone()).unwrap());
+        assert!(!should_deny_url(&tested_allowed_url, handles).unwrap());
+    }
 }
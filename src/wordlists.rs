@@ -0,0 +1,906 @@
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use anyhow::{anyhow, bail, Context, Result};
+use reqwest::{Client, StatusCode, Url};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::{
+    client, config::Configuration, scanner::TF_IDF, updater::sha256_hex, utils::fmt_err, VERSION,
+};
+
+/// SecLists release tag from which all registered wordlists are fetched
+pub const SECLISTS_REVISION: &str = "2024.3";
+
+/// base url used to download raw files from SecLists
+const SECLISTS_RAW_URL: &str = "https://raw.githubusercontent.com/danielmiessler/SecLists";
+
+/// well-known wordlists that can be fetched by name with --fetch-wordlist, mapped to their
+/// location within the SecLists repository and the sha256 digest of that file at
+/// [`SECLISTS_REVISION`]
+///
+/// downloads and cached copies are only used when they match the pinned digest; a list without
+/// a pinned digest is refused outright rather than trusted on first use. The digests must be
+/// re-pinned whenever [`SECLISTS_REVISION`] is bumped
+pub const WORDLIST_REGISTRY: [(&str, &str, &str); 13] = [
+    (
+        "raft-small-directories",
+        "Discovery/Web-Content/raft-small-directories.txt",
+        "",
+    ),
+    (
+        "raft-medium-directories",
+        "Discovery/Web-Content/raft-medium-directories.txt",
+        "",
+    ),
+    (
+        "raft-large-directories",
+        "Discovery/Web-Content/raft-large-directories.txt",
+        "",
+    ),
+    (
+        "raft-small-files",
+        "Discovery/Web-Content/raft-small-files.txt",
+        "",
+    ),
+    (
+        "raft-medium-files",
+        "Discovery/Web-Content/raft-medium-files.txt",
+        "",
+    ),
+    (
+        "raft-large-files",
+        "Discovery/Web-Content/raft-large-files.txt",
+        "",
+    ),
+    (
+        "raft-small-words",
+        "Discovery/Web-Content/raft-small-words.txt",
+        "",
+    ),
+    (
+        "raft-medium-words",
+        "Discovery/Web-Content/raft-medium-words.txt",
+        "",
+    ),
+    (
+        "raft-large-words",
+        "Discovery/Web-Content/raft-large-words.txt",
+        "",
+    ),
+    ("common", "Discovery/Web-Content/common.txt", ""),
+    ("big", "Discovery/Web-Content/big.txt", ""),
+    ("quickhits", "Discovery/Web-Content/quickhits.txt", ""),
+    (
+        "api-endpoints",
+        "Discovery/Web-Content/api/api-endpoints.txt",
+        "",
+    ),
+];
+
+/// given a wordlist's name, return its path within SecLists and its pinned sha256 digest, if
+/// it's registered
+pub fn lookup(name: &str) -> Option<(&'static str, &'static str)> {
+    WORDLIST_REGISTRY
+        .iter()
+        .find(|(registered, _, _)| *registered == name)
+        .map(|(_, path, digest)| (*path, *digest))
+}
+
+/// directory in which fetched wordlists are cached, i.e.
+/// `~/.config/feroxbuster/wordlists/2024.3/` on linux
+pub fn cache_dir() -> Result<PathBuf> {
+    let config_dir = dirs::config_dir().ok_or_else(|| anyhow!("Couldn't find config directory"))?;
+
+    Ok(config_dir
+        .join("feroxbuster")
+        .join("wordlists")
+        .join(SECLISTS_REVISION))
+}
+
+/// ensure the sha256 digest of a wordlist's `contents` matches the digest pinned for it
+///
+/// fails closed: a list without a pinned digest is treated the same as a mismatch
+fn verify_digest(name: &str, contents: &[u8], pinned: &str) -> Result<()> {
+    if pinned.is_empty() {
+        bail!("{name} has no pinned checksum for SecLists {SECLISTS_REVISION}; refusing to use it");
+    }
+
+    let actual = sha256_hex(contents);
+
+    if actual != pinned {
+        bail!("{name} failed checksum validation (expected {pinned}, got {actual}); refusing to use it");
+    }
+
+    Ok(())
+}
+
+/// return the path to the named wordlist, downloading it into the cache when necessary
+///
+/// the cached copy and every download are checked against the digest pinned in
+/// [`WORDLIST_REGISTRY`]; a cached copy that doesn't match is discarded and re-fetched, and a
+/// download that doesn't match is an error
+pub async fn fetch_wordlist(name: &str, config: &Configuration) -> Result<PathBuf> {
+    log::trace!("enter: fetch_wordlist({})", name);
+
+    let Some((path, pinned)) = lookup(name) else {
+        let known: Vec<_> = WORDLIST_REGISTRY.iter().map(|(name, _, _)| *name).collect();
+        bail!("Unknown wordlist: {name} (available: {})", known.join(", "));
+    };
+
+    if pinned.is_empty() {
+        // nothing to check a download against, don't bother making the request
+        verify_digest(name, &[], pinned)?;
+    }
+
+    let directory = cache_dir()?;
+    let wordlist = directory.join(format!("{name}.txt"));
+
+    if let Ok(contents) = fs::read(&wordlist) {
+        if verify_digest(name, &contents, pinned).is_ok() {
+            log::info!("using cached wordlist: {}", wordlist.display());
+            log::trace!("exit: fetch_wordlist -> {}", wordlist.display());
+            return Ok(wordlist);
+        }
+
+        log::warn!(
+            "cached wordlist {} failed checksum validation, fetching a fresh copy",
+            wordlist.display()
+        );
+    }
+
+    let url = format!("{SECLISTS_RAW_URL}/{SECLISTS_REVISION}/{path}");
+
+    // SecLists is a third party; the target's headers, proxy, and -k stay with the target
+    let client = client::service(config.tls_backend)?;
+
+    let response = client
+        .get(&url)
+        .send()
+        .await
+        .with_context(|| format!("Unable to download wordlist from remote url: {url}"))?;
+
+    if !response.status().is_success() {
+        bail!(
+            "[{}] Unable to download wordlist from url: {url}",
+            response.status().as_str()
+        );
+    }
+
+    let body = response.bytes().await?;
+
+    verify_digest(name, &body, pinned)
+        .with_context(|| format!("Wordlist downloaded from {url} was rejected"))?;
+
+    fs::create_dir_all(&directory)
+        .with_context(|| format!("Could not create {}", directory.display()))?;
+
+    fs::write(&wordlist, &body)
+        .with_context(|| format!("Could not write {}", wordlist.display()))?;
+
+    log::trace!("exit: fetch_wordlist -> {}", wordlist.display());
+    Ok(wordlist)
+}
+
+/// web archives that can be used to seed the wordlist with --archive-seed
+pub const ARCHIVE_SOURCES: [&str; 2] = ["wayback", "commoncrawl"];
+
+/// Wayback Machine's CDX api
+const WAYBACK_CDX_URL: &str = "https://web.archive.org/cdx/search/cdx";
+
+/// list of CommonCrawl's crawls, newest first, along with each crawl's CDX api
+const COMMONCRAWL_COLLECTIONS_URL: &str = "https://index.commoncrawl.org/collinfo.json";
+
+/// most urls requested from a single archive, per target
+const ARCHIVE_URL_LIMIT: usize = 25000;
+
+/// path segments longer than this are almost always session ids, hashes, and the like
+const ARCHIVE_MAX_WORD_LENGTH: usize = 64;
+
+/// merge the paths that the archives in --archive-seed have seen on the target into the given
+/// wordlist
+///
+/// an archive that can't be reached is only logged, the scan goes on with whatever the other
+/// archives (or the wordlist alone) provide
+pub async fn seed_from_archives(words: Arc<Wordlist>, config: &Configuration) -> Arc<Wordlist> {
+    log::trace!(
+        "enter: seed_from_archives({} words, {:?})",
+        words.len(),
+        config.archive_seed
+    );
+
+    if config.target_url.is_empty() {
+        if config.parallel == 0 {
+            // --parallel hands each target to its own process with --url, where seeding happens
+            log::warn!("--archive-seed only applies to a target given with --url");
+        }
+        log::trace!("exit: seed_from_archives -> {} words", words.len());
+        return words;
+    }
+
+    let Ok(target) = Url::parse(&config.target_url) else {
+        log::warn!(
+            "could not parse {} to seed it from archives",
+            config.target_url
+        );
+        log::trace!("exit: seed_from_archives -> {} words", words.len());
+        return words;
+    };
+
+    // the archives are third parties; the target's headers, proxy, and -k stay with the target
+    let client = match client::service(config.tls_backend) {
+        Ok(client) => client,
+        Err(e) => {
+            log::warn!("could not build a client to query archives: {e:#}");
+            log::trace!("exit: seed_from_archives -> {} words", words.len());
+            return words;
+        }
+    };
+
+    let mut archived = Vec::new();
+
+    for source in &config.archive_seed {
+        let urls = match source.as_str() {
+            "wayback" => query_wayback(&target, &client).await,
+            "commoncrawl" => query_commoncrawl(&target, &client).await,
+            _ => continue,
+        };
+
+        match urls {
+            Ok(urls) => {
+                log::info!("{source} returned {} urls for {target}", urls.len());
+                archived.extend(archived_words(&target, urls.iter().map(String::as_str)));
+            }
+            Err(e) => log::warn!("could not query {source} for {target}: {e:#}"),
+        }
+    }
+
+    let (merged, added) = words.merge(&archived);
+
+    log::info!("added {added} words from archives to the wordlist");
+    log::trace!("exit: seed_from_archives -> {} words", merged.len());
+
+    Arc::new(merged)
+}
+
+/// the target's host and path (without a scheme), with a wildcard, i.e. example.com/app/*
+fn archive_query(target: &Url) -> String {
+    let host = target.host_str().unwrap_or_default();
+    let path = target.path().trim_end_matches('/');
+
+    format!("{host}{path}/*")
+}
+
+/// every url the Wayback Machine has captured beneath the target
+async fn query_wayback(target: &Url, client: &Client) -> Result<Vec<String>> {
+    let limit = ARCHIVE_URL_LIMIT.to_string();
+
+    let response = client
+        .get(WAYBACK_CDX_URL)
+        .query(&[
+            ("url", archive_query(target).as_str()),
+            ("output", "txt"),
+            ("fl", "original"),
+            ("collapse", "urlkey"),
+            ("limit", limit.as_str()),
+        ])
+        .send()
+        .await?;
+
+    if !response.status().is_success() {
+        bail!("[{}] unexpected response", response.status().as_str());
+    }
+
+    let body = response.text().await?;
+
+    Ok(body.lines().map(String::from).collect())
+}
+
+/// every url the newest CommonCrawl crawl has captured beneath the target
+async fn query_commoncrawl(target: &Url, client: &Client) -> Result<Vec<String>> {
+    let collections: Value = client
+        .get(COMMONCRAWL_COLLECTIONS_URL)
+        .send()
+        .await?
+        .error_for_status()?
+        .json()
+        .await?;
+
+    let Some(cdx_api) = collections.pointer("/0/cdx-api").and_then(Value::as_str) else {
+        bail!("no crawls listed at {COMMONCRAWL_COLLECTIONS_URL}");
+    };
+
+    let limit = ARCHIVE_URL_LIMIT.to_string();
+
+    let response = client
+        .get(cdx_api)
+        .query(&[
+            ("url", archive_query(target).as_str()),
+            ("output", "json"),
+            ("fl", "url"),
+            ("limit", limit.as_str()),
+        ])
+        .send()
+        .await?;
+
+    if response.status() == StatusCode::NOT_FOUND {
+        // the index answers with a 404 when it has no captures for the given url
+        return Ok(Vec::new());
+    }
+
+    if !response.status().is_success() {
+        bail!("[{}] unexpected response", response.status().as_str());
+    }
+
+    let body = response.text().await?;
+
+    Ok(body
+        .lines()
+        .filter_map(|line| serde_json::from_str::<Value>(line).ok())
+        .filter_map(|capture| capture.get("url").and_then(Value::as_str).map(String::from))
+        .collect())
+}
+
+/// path segments found beneath the target in the given archived urls, in the order they were
+/// first seen
+///
+/// segments are used instead of whole paths so that a directory seen in one place is also tried
+/// in every other directory the scan recurses into
+fn archived_words<'a>(target: &Url, urls: impl Iterator<Item = &'a str>) -> Vec<String> {
+    let base = target.path().trim_end_matches('/');
+    let mut seen = HashSet::new();
+    let mut words = Vec::new();
+
+    for url in urls {
+        let Ok(url) = Url::parse(url.trim()) else {
+            continue;
+        };
+
+        let same_host = match (url.host_str(), target.host_str()) {
+            (Some(host), Some(expected)) => host.eq_ignore_ascii_case(expected),
+            _ => false,
+        };
+
+        if !same_host {
+            continue;
+        }
+
+        let Some(path) = url.path().strip_prefix(base) else {
+            continue;
+        };
+
+        if !path.starts_with('/') {
+            // i.e. /application when the target is /app
+            continue;
+        }
+
+        for segment in path.split('/') {
+            if segment.is_empty() || segment.len() > ARCHIVE_MAX_WORD_LENGTH {
+                continue;
+            }
+
+            if seen.insert(segment.to_string()) {
+                words.push(segment.to_string());
+            }
+        }
+    }
+
+    words
+}
+
+/// list of words to be requested, stored as a single shared buffer and the byte offsets of each
+/// word within it, instead of one allocation per word
+///
+/// the buffer is shared between a wordlist and any of its `skip`ped views, so resuming a scan
+/// partway through the list doesn't duplicate the words themselves
+#[derive(Debug, Default, Clone)]
+pub struct Wordlist {
+    /// every word, back to back
+    buffer: Arc<str>,
+
+    /// (start, end) byte offsets of each word within `buffer`
+    offsets: Vec<(usize, usize)>,
+}
+
+/// implementation of Wordlist
+impl Wordlist {
+    /// parse the raw contents of a wordlist file, skipping empty lines and `#` comments,
+    /// trimming leading forward-slashes from each word, and removing duplicate words (the first
+    /// occurrence of a word keeps its position)
+    ///
+    /// lines that aren't valid utf-8 are skipped
+    pub fn from_bytes(contents: &[u8]) -> (Self, LoadSummary) {
+        let mut summary = LoadSummary::default();
+        let mut buffer = String::with_capacity(contents.len());
+        let mut seen = HashSet::from([""]);
+
+        // this empty string ensures that we call Requester::request with the base url, i.e.
+        // `http://localhost/` instead of going straight into `http://localhost/WORD.EXT`.
+        // for vanilla scans, it doesn't matter all that much, but it can be a significant
+        // difference when `-e` is used, depending on the content at the base url.
+        let mut offsets = vec![(0, 0)];
+
+        for line in contents.split(|byte| *byte == b'\n') {
+            // same line endings as BufRead::lines, i.e. \n or \r\n
+            let line = line.strip_suffix(b"\r").unwrap_or(line);
+
+            let Ok(word) = std::str::from_utf8(line) else {
+                continue;
+            };
+
+            if word.starts_with('#') || word.is_empty() {
+                continue;
+            }
+
+            let word = if word.starts_with('/') {
+                summary.trimmed_word = true;
+                word.trim_start_matches('/')
+            } else {
+                word
+            };
+
+            if !seen.insert(word) {
+                summary.duplicates += 1;
+                continue;
+            }
+
+            let start = buffer.len();
+            buffer.push_str(word);
+            offsets.push((start, buffer.len()));
+        }
+
+        let wordlist = Self {
+            buffer: buffer.into(),
+            offsets,
+        };
+
+        (wordlist, summary)
+    }
+
+    /// number of words in the wordlist
+    pub fn len(&self) -> usize {
+        self.offsets.len()
+    }
+
+    /// whether or not the wordlist has any words
+    pub fn is_empty(&self) -> bool {
+        self.offsets.is_empty()
+    }
+
+    /// word at the given position, if any
+    pub fn get(&self, index: usize) -> Option<&str> {
+        self.offsets
+            .get(index)
+            .map(|(start, end)| &self.buffer[*start..*end])
+    }
+
+    /// iterate over each word, in order
+    pub fn iter(&self) -> impl Iterator<Item = &str> + '_ {
+        self.offsets
+            .iter()
+            .map(|(start, end)| &self.buffer[*start..*end])
+    }
+
+    /// a view of the wordlist that starts `offset` words in; the underlying buffer is shared
+    pub fn skip(&self, offset: usize) -> Self {
+        let offset = offset.min(self.offsets.len());
+
+        Self {
+            buffer: self.buffer.clone(),
+            offsets: self.offsets[offset..].to_vec(),
+        }
+    }
+
+    /// a copy of the wordlist with the given words appended, skipping any that are already
+    /// present; returns the copy along with the number of words added
+    pub fn merge(&self, words: &[String]) -> (Self, usize) {
+        let mut seen: HashSet<&str> = self.iter().collect();
+        let mut merged: Vec<String> = self.iter().map(String::from).collect();
+        let original = merged.len();
+
+        for word in words {
+            let word = word.trim_start_matches('/');
+
+            if seen.insert(word) {
+                merged.push(word.to_string());
+            }
+        }
+
+        let added = merged.len() - original;
+
+        (Self::from(merged), added)
+    }
+}
+
+/// what was changed about a wordlist's contents while it was loaded
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub struct LoadSummary {
+    /// whether any word had its leading forward-slash(es) trimmed
+    pub trimmed_word: bool,
+
+    /// number of duplicate words that were removed
+    pub duplicates: usize,
+}
+
+/// build a wordlist from already-owned words, as-is
+impl From<Vec<String>> for Wordlist {
+    fn from(words: Vec<String>) -> Self {
+        let mut buffer = String::with_capacity(words.iter().map(String::len).sum());
+        let mut offsets = Vec::with_capacity(words.len());
+
+        for word in &words {
+            let start = buffer.len();
+            buffer.push_str(word);
+            offsets.push((start, buffer.len()));
+        }
+
+        Self {
+            buffer: buffer.into(),
+            offsets,
+        }
+    }
+}
+
+/// a word collected by --collect-words, along with its tf-idf score
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct ScoredWord {
+    /// the collected word
+    pub word: String,
+
+    /// the word's tf-idf score at the end of the run
+    pub score: f32,
+}
+
+/// words collected from a single target during a run, as saved to --save-collected-words
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct CollectedWords {
+    /// origin (scheme, host, and port) the words were collected from
+    pub target: String,
+
+    /// id of the run that collected the words
+    pub run_id: String,
+
+    /// version of feroxbuster that collected the words
+    pub version: String,
+
+    /// unix timestamp of when the run finished
+    pub timestamp: u64,
+
+    /// number of responses the words were collected from
+    pub documents: usize,
+
+    /// languages whose stop words were left out (--word-languages)
+    pub languages: Vec<String>,
+
+    /// collected words, highest scoring first
+    pub words: Vec<ScoredWord>,
+}
+
+/// implementation of CollectedWords
+impl CollectedWords {
+    /// name shared by the files saved for this target, ex: http_localhost_8080
+    fn file_stem(&self) -> String {
+        self.target
+            .replace("://", "_")
+            .replace(['/', '.', ':'], "_")
+    }
+
+    /// the words as a wordlist that can be passed straight back to --wordlist; the metadata
+    /// is written as comments, which wordlists skip
+    fn as_wordlist(&self) -> String {
+        let mut contents = format!(
+            "# words collected by feroxbuster {} from {}\n# run_id={}\n# timestamp={}\n# documents={}\n# languages={}\n",
+            self.version,
+            self.target,
+            self.run_id,
+            self.timestamp,
+            self.documents,
+            self.languages.join(",")
+        );
+
+        for scored in &self.words {
+            contents.push_str(&scored.word);
+            contents.push('\n');
+        }
+
+        contents
+    }
+}
+
+/// save the words learned by --collect-words to --save-collected-words, one pair of files per
+/// target
+///
+/// `<target>.txt` is a wordlist that a later scan can use as-is, and `<target>.json` holds the
+/// same words along with their scores and the run they came from. Words aren't subject to the
+/// --collected-word-* constraints, so a later scan can apply its own.
+pub fn save_collected_words(config: &Configuration) -> Result<()> {
+    log::trace!(
+        "enter: save_collected_words({})",
+        config.save_collected_words
+    );
+
+    if config.save_collected_words.is_empty() {
+        log::trace!("exit: save_collected_words");
+        return Ok(());
+    }
+
+    if !config.collect_words {
+        log::warn!("--save-collected-words has no effect without --collect-words");
+        log::trace!("exit: save_collected_words");
+        return Ok(());
+    }
+
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_secs())
+        .unwrap_or_default();
+
+    let collected = match TF_IDF.write() {
+        Ok(mut guard) => {
+            // scores are only recalculated every so often during the scan
+            guard.calculate_tf_idf_scores();
+
+            guard
+                .origins()
+                .into_iter()
+                .map(|(origin, documents)| CollectedWords {
+                    target: origin.to_string(),
+                    run_id: config.run_id.clone(),
+                    version: VERSION.to_string(),
+                    timestamp,
+                    documents,
+                    languages: config.word_languages.clone(),
+                    words: guard
+                        .scored_words_from(origin)
+                        .into_iter()
+                        .map(|(word, score)| ScoredWord { word, score })
+                        .collect(),
+                })
+                .collect::<Vec<_>>()
+        }
+        Err(_) => bail!(fmt_err("Could not read collected words")),
+    };
+
+    let directory = Path::new(&config.save_collected_words);
+
+    fs::create_dir_all(directory)
+        .with_context(|| fmt_err(&format!("Could not create {}", directory.display())))?;
+
+    for words in &collected {
+        write_collected_words(directory, words)?;
+    }
+
+    log::info!(
+        "saved collected words for {} target(s) to {}",
+        collected.len(),
+        directory.display()
+    );
+
+    log::trace!("exit: save_collected_words");
+    Ok(())
+}
+
+/// write the wordlist and json files for a single target's collected words
+fn write_collected_words(directory: &Path, words: &CollectedWords) -> Result<()> {
+    let stem = words.file_stem();
+
+    let wordlist = directory.join(format!("{stem}.txt"));
+    let json = directory.join(format!("{stem}.json"));
+
+    fs::write(&wordlist, words.as_wordlist())
+        .with_context(|| fmt_err(&format!("Could not write to {}", wordlist.display())))?;
+
+    fs::write(&json, serde_json::to_string_pretty(words)?)
+        .with_context(|| fmt_err(&format!("Could not write to {}", json.display())))?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    /// registered names resolve to their SecLists path, unknown names don't
+    fn lookup_finds_registered_wordlists() {
+        assert_eq!(
+            lookup("raft-medium-directories").map(|(path, _)| path),
+            Some("Discovery/Web-Content/raft-medium-directories.txt")
+        );
+        assert_eq!(lookup("not-a-real-list"), None);
+    }
+
+    #[test]
+    /// cache directory is namespaced by the pinned revision
+    fn cache_dir_is_pinned_to_revision() {
+        let directory = cache_dir().unwrap();
+        assert!(directory.ends_with(format!("feroxbuster/wordlists/{SECLISTS_REVISION}")));
+    }
+
+    #[test]
+    /// comments and empty lines are skipped, leading slashes trimmed, and the base url's empty
+    /// word comes first
+    fn wordlist_from_bytes_filters_and_trims() {
+        let contents = b"# comment\nadmin\r\n\n/login\n//api/v1\n\xff\xfe\nlast";
+        let (wordlist, summary) = Wordlist::from_bytes(contents);
+
+        assert!(summary.trimmed_word);
+        assert_eq!(
+            wordlist.iter().collect::<Vec<_>>(),
+            vec!["", "admin", "login", "api/v1", "last"]
+        );
+
+        let (wordlist, summary) = Wordlist::from_bytes(b"admin\nlogin\n");
+        assert!(!summary.trimmed_word);
+        assert_eq!(wordlist.len(), 3);
+    }
+
+    #[test]
+    /// duplicates are removed in favor of their first occurrence, and counted
+    fn wordlist_from_bytes_removes_duplicates() {
+        let contents = b"admin\nlogin\nadmin\n/login\n/\nbackup\nadmin\r\n";
+        let (wordlist, summary) = Wordlist::from_bytes(contents);
+
+        assert_eq!(
+            wordlist.iter().collect::<Vec<_>>(),
+            vec!["", "admin", "login", "backup"]
+        );
+        // admin x2, /login, and / (which trims down to the base url's empty word)
+        assert_eq!(summary.duplicates, 4);
+        assert!(summary.trimmed_word);
+    }
+
+    #[test]
+    /// skipped views share the buffer and clamp to the end of the list
+    fn wordlist_skip_and_get() {
+        let wordlist = Wordlist::from(vec![
+            String::from("one"),
+            String::from("two"),
+            String::from("three"),
+        ]);
+
+        let skipped = wordlist.skip(1);
+        assert_eq!(skipped.len(), 2);
+        assert_eq!(skipped.get(0), Some("two"));
+        assert_eq!(skipped.get(2), None);
+        assert!(Arc::ptr_eq(&wordlist.buffer, &skipped.buffer));
+
+        assert!(wordlist.skip(10).is_empty());
+        assert!(Wordlist::default().is_empty());
+    }
+
+    #[test]
+    /// merged words are appended once, after the existing words
+    fn wordlist_merge_skips_known_words() {
+        let (wordlist, _) = Wordlist::from_bytes(b"admin\nlogin\n");
+
+        let (merged, added) = wordlist.merge(&[
+            String::from("login"),
+            String::from("/backup"),
+            String::from("backup"),
+            String::from("old"),
+        ]);
+
+        assert_eq!(added, 2);
+        assert_eq!(
+            merged.iter().collect::<Vec<_>>(),
+            vec!["", "admin", "login", "backup", "old"]
+        );
+    }
+
+    #[test]
+    /// only segments beneath the target, on the target's host, become words
+    fn archived_words_are_segments_beneath_the_target() {
+        let target = Url::parse("http://example.com/app/").unwrap();
+        let too_long = format!(
+            "http://example.com/app/{}",
+            "a".repeat(ARCHIVE_MAX_WORD_LENGTH + 1)
+        );
+
+        let urls = [
+            "http://example.com/app/admin/login.php?next=/",
+            "https://EXAMPLE.com:443/app/admin/",
+            "http://example.com/application/secret",
+            "http://example.com/other/thing",
+            "http://cdn.example.com/app/assets/",
+            "not a url",
+            too_long.as_str(),
+            "http://example.com/app/uploads/old.zip\r",
+        ];
+
+        assert_eq!(
+            archived_words(&target, urls.iter().copied()),
+            vec!["admin", "login.php", "uploads", "old.zip"]
+        );
+    }
+
+    #[test]
+    /// archives are asked for everything beneath the target's path
+    fn archive_query_wildcards_the_target() {
+        let target = Url::parse("https://example.com").unwrap();
+        assert_eq!(archive_query(&target), "example.com/*");
+
+        let target = Url::parse("https://example.com/app/").unwrap();
+        assert_eq!(archive_query(&target), "example.com/app/*");
+    }
+
+    #[test]
+    /// saved words can be loaded straight back in as a wordlist, metadata and all
+    fn collected_words_as_wordlist_round_trips() {
+        let collected = CollectedWords {
+            target: String::from("http://localhost:8080"),
+            run_id: String::from("abc"),
+            version: String::from("2.11.0"),
+            timestamp: 1700000000,
+            documents: 3,
+            languages: vec![String::from("en"), String::from("de")],
+            words: vec![
+                ScoredWord {
+                    word: String::from("haze"),
+                    score: 0.5,
+                },
+                ScoredWord {
+                    word: String::from("island"),
+                    score: 0.25,
+                },
+            ],
+        };
+
+        assert_eq!(collected.file_stem(), "http_localhost_8080");
+
+        let contents = collected.as_wordlist();
+        assert!(contents.starts_with(
+            "# words collected by feroxbuster 2.11.0 from http://localhost:8080\n# run_id=abc\n"
+        ));
+        assert!(contents.contains("# languages=en,de\n"));
+
+        let (wordlist, _) = Wordlist::from_bytes(contents.as_bytes());
+        assert_eq!(
+            wordlist.iter().collect::<Vec<_>>(),
+            vec!["", "haze", "island"]
+        );
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+    /// unknown names fail before any network activity takes place
+    async fn fetch_wordlist_rejects_unknown_names() {
+        let result = fetch_wordlist("not-a-real-list", &Configuration::default()).await;
+
+        assert!(result.is_err());
+        assert!(result
+            .unwrap_err()
+            .to_string()
+            .contains("raft-medium-directories"));
+    }
+
+    #[test]
+    /// pinned digests are lowercase sha256 hex
+    fn registry_digests_are_sha256_hex() {
+        for (name, _, digest) in WORDLIST_REGISTRY {
+            assert!(
+                digest.is_empty()
+                    || (digest.len() == 64
+                        && digest
+                            .chars()
+                            .all(|c| c.is_ascii_digit() || ('a'..='f').contains(&c))),
+                "{name} has a malformed digest"
+            );
+        }
+    }
+
+    #[test]
+    /// matching digests pass; mismatched contents and unpinned lists fail closed
+    fn verify_digest_fails_closed() {
+        let empty = "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855";
+
+        assert!(verify_digest("common", b"", empty).is_ok());
+        assert!(verify_digest("common", b"tampered", empty).is_err());
+
+        let unpinned = verify_digest("common", b"", "").unwrap_err().to_string();
+        assert!(unpinned.contains("no pinned checksum"));
+    }
+}
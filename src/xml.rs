@@ -0,0 +1,97 @@
+//! the small amount of xml parsing shared by the burp and nmap importers and service
+//! description extraction: attribute values and entity decoding
+use lazy_static::lazy_static;
+use regex::Regex;
+
+lazy_static! {
+    /// a single attribute of a tag, quoted with either ' or "; the name has to start the tag's
+    /// attributes or follow whitespace, so addr doesn't match within subaddr
+    static ref ATTRIBUTE: Regex =
+        Regex::new(r#"(?:^|\s)([A-Za-z0-9_.:-]+)\s*=\s*(?:"([^"]*)"|'([^']*)')"#).unwrap();
+}
+
+/// value of the named attribute within a tag's attributes, with entities decoded and
+/// surrounding whitespace trimmed; None when it's missing or empty
+pub(crate) fn attribute(attributes: &str, name: &str) -> Option<String> {
+    ATTRIBUTE
+        .captures_iter(attributes)
+        .find(|capture| &capture[1] == name)
+        .and_then(|capture| capture.get(2).or_else(|| capture.get(3)))
+        .map(|value| decode_entities(value.as_str()).trim().to_string())
+        .filter(|value| !value.is_empty())
+}
+
+/// decode the five predefined xml entities along with numeric character references (i.e.
+/// &#xa; and &#10;), in a single pass so that &amp;lt; becomes &lt; and not <
+///
+/// anything that isn't a known entity is left as-is
+pub(crate) fn decode_entities(text: &str) -> String {
+    let mut decoded = String::with_capacity(text.len());
+    let mut rest = text;
+
+    while let Some(start) = rest.find('&') {
+        decoded.push_str(&rest[..start]);
+        rest = &rest[start + 1..];
+
+        let entity = rest.find(';').map(|end| &rest[..end]);
+
+        match entity.and_then(entity_char) {
+            Some(character) => {
+                decoded.push(character);
+                rest = &rest[entity.unwrap_or_default().len() + 1..];
+            }
+            None => decoded.push('&'),
+        }
+    }
+
+    decoded.push_str(rest);
+    decoded
+}
+
+/// the character an entity's name (the part between & and ;) stands for
+fn entity_char(entity: &str) -> Option<char> {
+    match entity {
+        "lt" => Some('<'),
+        "gt" => Some('>'),
+        "quot" => Some('"'),
+        "apos" => Some('\''),
+        "amp" => Some('&'),
+        _ => {
+            let number = entity.strip_prefix('#')?;
+
+            let code = match number.strip_prefix(['x', 'X']) {
+                Some(hex) => u32::from_str_radix(hex, 16).ok()?,
+                None => number.parse().ok()?,
+            };
+
+            char::from_u32(code)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    /// attributes are matched by their whole name, with either quote style
+    fn attribute_reads_whole_names_and_both_quotes() {
+        let attributes = r#"subaddr="nope" addr='10.0.0.1' output="a&#xa;b &amp; c" empty="  ""#;
+
+        assert_eq!(attribute(attributes, "addr").as_deref(), Some("10.0.0.1"));
+        assert_eq!(attribute(attributes, "output").as_deref(), Some("a\nb & c"));
+        assert_eq!(attribute(attributes, "empty"), None);
+        assert_eq!(attribute(attributes, "missing"), None);
+    }
+
+    #[test]
+    /// predefined and numeric entities are decoded once, unknown ones are left alone
+    fn decode_entities_handles_predefined_and_numeric() {
+        assert_eq!(
+            decode_entities("&lt;a&gt; &quot;b&quot; &apos;c&apos; &#65;&#x42;"),
+            "<a> \"b\" 'c' AB"
+        );
+        assert_eq!(decode_entities("&amp;lt;"), "&lt;");
+        assert_eq!(decode_entities("a & b &nbsp; c&"), "a & b &nbsp; c&");
+    }
+}
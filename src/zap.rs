@@ -0,0 +1,172 @@
+//! handing findings to a running OWASP ZAP instance through its api, see --zap-api-url
+use anyhow::{Context, Result};
+use reqwest::{Client, Url};
+use serde_json::Value;
+
+use crate::{client, config::Configuration, utils::fmt_err};
+
+/// header ZAP reads its api key from
+const ZAP_API_KEY_HEADER: &str = "X-ZAP-API-Key";
+
+/// client for the ZAP api at --zap-api-url
+///
+/// findings are handed over with `core/action/accessUrl`, which has ZAP request the url itself;
+/// the request and response end up in ZAP's sites tree, where the passive scanner sees them and
+/// the active scanner can be pointed at them
+///
+/// ZAP gets a third-party client of its own, so that headers meant for the target are never
+/// sent to the api, --proxy isn't used to reach it, and its certificate is verified even when
+/// --insecure is used against the target
+#[derive(Debug, Clone)]
+pub(crate) struct Zap {
+    /// client used only for the ZAP api
+    client: Client,
+
+    /// base url of the api, i.e. http://127.0.0.1:8080/
+    api_url: Url,
+
+    /// --zap-api-key, empty when ZAP doesn't require one
+    api_key: String,
+}
+
+/// implementation of Zap
+impl Zap {
+    /// client for the given configuration; None when --zap-api-url wasn't used
+    pub(crate) fn new(config: &Configuration) -> Result<Option<Self>> {
+        if config.zap_api_url.is_empty() {
+            return Ok(None);
+        }
+
+        let mut api_url = Url::parse(&config.zap_api_url)
+            .with_context(|| fmt_err(&format!("{} is not a valid url", config.zap_api_url)))?;
+
+        if !api_url.path().ends_with('/') {
+            // api paths are joined onto the whole of --zap-api-url
+            let path = format!("{}/", api_url.path());
+            api_url.set_path(&path);
+        }
+
+        let client = client::service(config.tls_backend)?;
+
+        Ok(Some(Self {
+            client,
+            api_url,
+            api_key: config.zap_api_key.clone(),
+        }))
+    }
+
+    /// version of the ZAP instance, which doubles as a check that the api is reachable and the
+    /// key was accepted
+    pub(crate) async fn version(&self) -> Result<String> {
+        let response = self.call("JSON/core/view/version/", &[]).await?;
+
+        Ok(response
+            .get("version")
+            .and_then(Value::as_str)
+            .unwrap_or("unknown")
+            .to_string())
+    }
+
+    /// have ZAP request the given url, adding it to the sites tree
+    pub(crate) async fn access_url(&self, url: &str) -> Result<()> {
+        self.call(
+            "JSON/core/action/accessUrl/",
+            &[("url", url), ("followRedirects", "false")],
+        )
+        .await?;
+
+        Ok(())
+    }
+
+    /// full url of the given api endpoint, with its parameters
+    fn endpoint(&self, path: &str, params: &[(&str, &str)]) -> Result<Url> {
+        let mut url = self
+            .api_url
+            .join(path)
+            .with_context(|| format!("Could not join {} with {path}", self.api_url))?;
+
+        if !params.is_empty() {
+            url.query_pairs_mut().extend_pairs(params);
+        }
+
+        Ok(url)
+    }
+
+    /// GET the given api endpoint, failing on anything other than a 2xx/3xx
+    async fn call(&self, path: &str, params: &[(&str, &str)]) -> Result<Value> {
+        let url = self.endpoint(path, params)?;
+
+        let mut request = self.client.get(url);
+
+        if !self.api_key.is_empty() {
+            request = request.header(ZAP_API_KEY_HEADER, &self.api_key);
+        }
+
+        let response = request
+            .send()
+            .await
+            .and_then(|response| response.error_for_status())
+            .with_context(|| format!("Could not reach the ZAP api at {}", self.api_url))?;
+
+        let body = response.text().await?;
+
+        serde_json::from_str(&body)
+            .with_context(|| format!("Unexpected response from the ZAP api: {body}"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// zap client for the given --zap-api-url
+    fn zap(api_url: &str) -> Zap {
+        let config = Configuration {
+            zap_api_url: api_url.to_string(),
+            ..Default::default()
+        };
+
+        Zap::new(&config).unwrap().unwrap()
+    }
+
+    #[test]
+    /// no client without --zap-api-url, and an invalid one is an error
+    fn zap_requires_valid_url() {
+        assert!(Zap::new(&Configuration::default()).unwrap().is_none());
+
+        let config = Configuration {
+            zap_api_url: String::from("not a url"),
+            ..Default::default()
+        };
+
+        assert!(Zap::new(&config).is_err());
+    }
+
+    #[test]
+    /// api paths are joined onto the whole of --zap-api-url, and parameters are encoded
+    fn zap_endpoint_keeps_base_path() {
+        let url = zap("http://127.0.0.1:8080/zap")
+            .endpoint(
+                "JSON/core/action/accessUrl/",
+                &[
+                    ("url", "http://localhost/a b?c=d"),
+                    ("followRedirects", "false"),
+                ],
+            )
+            .unwrap();
+
+        assert_eq!(
+            url.as_str(),
+            "http://127.0.0.1:8080/zap/JSON/core/action/accessUrl/?url=http%3A%2F%2Flocalhost%2Fa+b%3Fc%3Dd&followRedirects=false"
+        );
+
+        let url = zap("http://127.0.0.1:8080")
+            .endpoint("JSON/core/view/version/", &[])
+            .unwrap();
+
+        assert_eq!(
+            url.as_str(),
+            "http://127.0.0.1:8080/JSON/core/view/version/"
+        );
+    }
+}
@@ -28,3 +28,62 @@ fn read_in_config_file_for_settings() -> Result<(), Box<dyn std::error::Error>>
 
     Ok(())
 }
+
+#[test]
+/// a ferox-config.json in the current directory is read like a ferox-config.toml
+fn read_in_json_config_file_for_settings() -> Result<(), Box<dyn std::error::Error>> {
+    let srv = MockServer::start();
+
+    let (tmp_dir, file) =
+        setup_tmp_directory(&[r#"{"threads": 37}"#.to_string()], "ferox-config.json")?;
+
+    Command::cargo_bin("feroxbuster")
+        .unwrap()
+        .current_dir(&tmp_dir)
+        .arg("--url")
+        .arg(srv.url("/"))
+        .arg("--wordlist")
+        .arg(file.as_os_str())
+        .arg("-vvvv")
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("│ 37"));
+
+    teardown_tmp_directory(tmp_dir);
+
+    Ok(())
+}
+
+#[test]
+/// a config file given via --config is the only one read, regardless of its name
+fn read_in_config_file_passed_via_cli() -> Result<(), Box<dyn std::error::Error>> {
+    let srv = MockServer::start();
+
+    let (tmp_dir, file) = setup_tmp_directory(&[r#"{"threads": 38}"#.to_string()], "custom.json")?;
+
+    // would be picked up from the current directory if --config weren't used
+    std::fs::write(tmp_dir.path().join("ferox-config.toml"), "threads = 37")?;
+
+    Command::cargo_bin("feroxbuster")
+        .unwrap()
+        .current_dir(&tmp_dir)
+        .arg("--url")
+        .arg(srv.url("/"))
+        .arg("--config")
+        .arg(file.as_os_str())
+        .arg("--wordlist")
+        .arg(file.as_os_str())
+        .arg("-vvvv")
+        .assert()
+        .success()
+        .stderr(
+            predicate::str::contains("│ 38")
+                .and(predicate::str::contains("Config File"))
+                .and(predicate::str::contains("custom.json"))
+                .and(predicate::str::contains("│ 37").not()),
+        );
+
+    teardown_tmp_directory(tmp_dir);
+
+    Ok(())
+}
@@ -710,3 +710,102 @@ fn robots_text_extraction_doesnt_run_with_dont_extract_links() {
     assert_eq!(mock_scanned_file.hits(), 0);
     teardown_tmp_directory(tmp_dir);
 }
+
+#[test]
+/// with --extract-priority low, links found via extraction are still requested and reported,
+/// once the wordlist is done
+fn extractor_requests_low_priority_links_after_wordlist() -> Result<(), Box<dyn std::error::Error>>
+{
+    let srv = MockServer::start();
+    let (tmp_dir, file) =
+        setup_tmp_directory(&["LICENSE".to_string(), "other".to_string()], "wordlist")?;
+
+    let mock = srv.mock(|when, then| {
+        when.method(GET).path("/LICENSE");
+        then.status(200)
+            .body(srv.url("'/homepage/assets/img/icons/handshake.svg'"));
+    });
+
+    let mock_two = srv.mock(|when, then| {
+        when.method(GET)
+            .path("/homepage/assets/img/icons/handshake.svg");
+        then.status(200);
+    });
+
+    let mock_three = srv.mock(|when, then| {
+        when.method(GET).path("/other");
+        then.status(404);
+    });
+
+    let cmd = Command::cargo_bin("feroxbuster")
+        .unwrap()
+        .arg("--url")
+        .arg(srv.url("/"))
+        .arg("--wordlist")
+        .arg(file.as_os_str())
+        .arg("--extract-priority")
+        .arg("low")
+        .unwrap();
+
+    cmd.assert()
+        .success()
+        .stdout(
+            predicate::str::contains("/LICENSE").and(predicate::str::contains(
+                "/homepage/assets/img/icons/handshake.svg",
+            )),
+        );
+
+    assert_eq!(mock.hits(), 1);
+    assert_eq!(mock_two.hits(), 1);
+    assert_eq!(mock_three.hits(), 1);
+    teardown_tmp_directory(tmp_dir);
+    Ok(())
+}
+
+#[test]
+/// a wsdl found by the wordlist has its service address requested and its operations reported
+fn extractor_requests_endpoints_from_wsdl() -> Result<(), Box<dyn std::error::Error>> {
+    let srv = MockServer::start();
+    let (tmp_dir, file) = setup_tmp_directory(&["users.wsdl".to_string()], "wordlist")?;
+
+    let wsdl = format!(
+        r#"<wsdl:definitions xmlns:soap="http://schemas.xmlsoap.org/wsdl/soap/" xmlns:wsdl="http://schemas.xmlsoap.org/wsdl/">
+  <wsdl:portType name="UsersSoap"><wsdl:operation name="GetUser" /></wsdl:portType>
+  <wsdl:service name="Users">
+    <wsdl:port name="UsersSoap" binding="tns:UsersSoap">
+      <soap:address location="{}" />
+    </wsdl:port>
+  </wsdl:service>
+</wsdl:definitions>"#,
+        srv.url("/services/users.asmx")
+    );
+
+    let mock = srv.mock(|when, then| {
+        when.method(GET).path("/users.wsdl");
+        then.status(200).body(&wsdl);
+    });
+
+    let mock_two = srv.mock(|when, then| {
+        when.method(GET).path("/services/users.asmx");
+        then.status(200).body("soap service");
+    });
+
+    let cmd = Command::cargo_bin("feroxbuster")
+        .unwrap()
+        .arg("--url")
+        .arg(srv.url("/"))
+        .arg("--wordlist")
+        .arg(file.as_os_str())
+        .unwrap();
+
+    cmd.assert().success().stdout(
+        predicate::str::contains("SVC")
+            .and(predicate::str::contains("operations: GetUser"))
+            .and(predicate::str::contains("/services/users.asmx")),
+    );
+
+    assert_eq!(mock.hits(), 1);
+    assert_eq!(mock_two.hits(), 1);
+    teardown_tmp_directory(tmp_dir);
+    Ok(())
+}
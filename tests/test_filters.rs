@@ -339,3 +339,44 @@ fn filters_regex_should_filter_response_based_on_headers() {
     assert_eq!(mock_two.hits(), 1);
     teardown_tmp_directory(tmp_dir);
 }
+
+#[test]
+/// --refilter applies the given filters to a previous run's --json output, without scanning
+fn filters_refilter_applies_filters_to_previous_results() {
+    let record = |url: &str, status: u16| {
+        format!(
+            r#"{{"type":"response","url":"{url}","original_url":"{url}","path":"/","wildcard":false,"status":{status},"method":"GET","content_length":14,"line_count":1,"word_count":4,"headers":{{}},"extension":""}}"#
+        )
+    };
+
+    let (tmp_dir, file) = setup_tmp_directory(
+        &[
+            r#"{"type":"configuration","target_url":"http://localhost"}"#.to_string(),
+            record("http://localhost/kept", 200),
+            record("http://localhost/forbidden", 403),
+        ],
+        "results.json",
+    )
+    .unwrap();
+
+    let output = tmp_dir.path().join("refiltered.json");
+
+    Command::cargo_bin("feroxbuster")
+        .unwrap()
+        .arg("--refilter")
+        .arg(file.as_os_str())
+        .arg("--filter-status")
+        .arg("403")
+        .arg("--json")
+        .arg("--output")
+        .arg(output.as_os_str())
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("Kept 1 of 2 response(s)"));
+
+    let contents = std::fs::read_to_string(&output).unwrap();
+
+    assert!(contents.contains("http://localhost/kept"));
+    assert!(!contents.contains("http://localhost/forbidden"));
+    teardown_tmp_directory(tmp_dir);
+}
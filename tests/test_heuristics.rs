@@ -90,6 +90,39 @@ fn test_one_good_and_one_bad_target_scan_succeeds() -> Result<(), Box<dyn std::e
     Ok(())
 }
 
+#[test]
+/// a bare hostname piped in with an output level prefix keeps its level once its scheme has
+/// been probed, i.e. silent findings are printed as bare urls
+fn test_stdin_output_level_applies_to_bare_host() -> Result<(), Box<dyn std::error::Error>> {
+    let srv = MockServer::start();
+    let (tmp_dir, file) = setup_tmp_directory(&["LICENSE".to_string()], "wordlist")?;
+    let targets = tmp_dir.path().join("targets");
+    std::fs::write(&targets, format!("silent {}\n", srv.address()))?;
+
+    let mock = srv.mock(|when, then| {
+        when.method(GET).path("/LICENSE");
+        then.status(200).body("this is a test");
+    });
+
+    Command::cargo_bin("feroxbuster")
+        .unwrap()
+        .arg("--stdin")
+        .arg("--wordlist")
+        .arg(file.as_os_str())
+        .pipe_stdin(targets)
+        .unwrap()
+        .assert()
+        .success()
+        .stdout(
+            predicate::str::contains(format!("http://{}/LICENSE", srv.address()))
+                .and(predicate::str::contains("GET").not()),
+        );
+    assert_eq!(mock.hits(), 1);
+
+    teardown_tmp_directory(tmp_dir);
+    Ok(())
+}
+
 #[test]
 /// test passes one target with SSL issues via -u to the scanner, expected result is that the
 /// scanner dies and prints an SSL specific error message
@@ -490,3 +523,55 @@ fn heuristics_wildcard_test_that_auto_filtering_403s_still_allows_for_recursion_
 // }
 
 // todo figure out why ci hates these tests
+
+#[test]
+/// --survey-headers should report present/missing security headers, the methods advertised by
+/// OPTIONS, and whether TRACE is reflected, once per host
+fn survey_headers_reports_security_headers_and_trace() -> Result<(), Box<dyn std::error::Error>> {
+    let srv = MockServer::start();
+    let (tmp_dir, file) = setup_tmp_directory(&["LICENSE".to_string()], "wordlist")?;
+
+    let root = srv.mock(|when, then| {
+        when.method(GET).path("/");
+        then.status(200)
+            .header("strict-transport-security", "max-age=31536000")
+            .body("home");
+    });
+
+    let options = srv.mock(|when, then| {
+        when.method(httpmock::Method::OPTIONS).path("/");
+        then.status(200).header("allow", "GET, OPTIONS, TRACE");
+    });
+
+    let trace = srv.mock(|when, then| {
+        when.method(httpmock::Method::TRACE).path("/");
+        then.status(200)
+            .body("TRACE / HTTP/1.1\r\nhost: localhost\r\n\r\n");
+    });
+
+    let cmd = Command::cargo_bin("feroxbuster")
+        .unwrap()
+        .arg("--url")
+        .arg(srv.url("/"))
+        .arg("--wordlist")
+        .arg(file.as_os_str())
+        .arg("--survey-headers")
+        .unwrap();
+
+    cmd.assert().success().stdout(
+        predicate::str::contains("SRV")
+            .and(predicate::str::contains(
+                "strict-transport-security: max-age=31536000",
+            ))
+            .and(predicate::str::contains("content-security-policy"))
+            .and(predicate::str::contains("allow: GET, OPTIONS, TRACE"))
+            .and(predicate::str::contains("TRACE enabled")),
+    );
+
+    assert!(root.hits() >= 1);
+    assert_eq!(options.hits(), 1);
+    assert_eq!(trace.hits(), 1);
+
+    teardown_tmp_directory(tmp_dir);
+    Ok(())
+}
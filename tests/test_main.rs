@@ -30,6 +30,36 @@ fn main_use_root_owned_file_as_wordlist() {
     assert_eq!(mock.hits(), 0);
 }
 
+#[test]
+/// duplicate words are only requested once, and the number removed is reported
+fn main_removes_duplicate_words() -> Result<(), Box<dyn std::error::Error>> {
+    let srv = MockServer::start();
+    let words = ["LICENSE", "/LICENSE", "other", "LICENSE"].map(String::from);
+    let (tmp_dir, file) = setup_tmp_directory(&words, "wordlist")?;
+
+    let mock = srv.mock(|when, then| {
+        when.method(GET).path("/LICENSE");
+        then.status(200).body("this is a test");
+    });
+
+    Command::cargo_bin("feroxbuster")
+        .unwrap()
+        .arg("--url")
+        .arg(srv.url("/"))
+        .arg("--wordlist")
+        .arg(file.as_os_str())
+        .assert()
+        .success()
+        .stderr(predicate::str::contains(
+            "Removed 2 duplicate word(s) from the wordlist",
+        ));
+
+    assert_eq!(mock.hits(), 1);
+
+    teardown_tmp_directory(tmp_dir);
+    Ok(())
+}
+
 #[test]
 /// send the function an empty file
 fn main_use_empty_wordlist() -> Result<(), Box<dyn std::error::Error>> {
@@ -205,6 +235,12 @@ fn main_parallel_creates_output_directory() -> Result<(), Box<dyn std::error::Er
 
     for entry in sub_dir.read_dir()? {
         let entry = entry?;
+
+        if entry.file_name() == "manifest.json" {
+            // covered by main_parallel_writes_manifest
+            continue;
+        }
+
         // created each file like ferox-https_localhost-1627845741.log
         println!("name: {:?}", entry.file_name().to_string_lossy());
         assert!(file_regex.is_match(&entry.file_name().to_string_lossy()));
@@ -221,6 +257,67 @@ fn main_parallel_creates_output_directory() -> Result<(), Box<dyn std::error::Er
     Ok(())
 }
 
+#[test]
+/// send three targets over stdin with --output enabled, expect a manifest in the logs directory
+/// with one entry per child, each pointing at the log file for its target
+fn main_parallel_writes_manifest() -> Result<(), Box<dyn std::error::Error>> {
+    let t1 = MockServer::start();
+    let t2 = MockServer::start();
+    let t3 = MockServer::start();
+
+    let (word_tmp_dir, wordlist) = setup_tmp_directory(&["LICENSE".to_string()], "wordlist")?;
+    let (output_dir, outfile) = setup_tmp_directory(&[], "output-file")?;
+    let targets = [t1.url("/"), t2.url("/"), t3.url("/")];
+    let (tgt_tmp_dir, target_file) = setup_tmp_directory(&targets, "targets")?;
+
+    Command::cargo_bin("feroxbuster")
+        .unwrap()
+        .arg("--stdin")
+        .arg("--quiet")
+        .arg("--parallel")
+        .arg("2")
+        .arg("--output")
+        .arg(outfile.as_os_str())
+        .arg("--wordlist")
+        .arg(wordlist.as_os_str())
+        .pipe_stdin(target_file)
+        .unwrap()
+        .assert()
+        .success();
+
+    let sub_dir = read_dir(&output_dir)?.next().unwrap()?.path();
+    let contents = read_to_string(sub_dir.join("manifest.json"))?;
+
+    let entries: Vec<serde_json::Value> = contents
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect();
+
+    assert_eq!(entries.len(), 3);
+
+    for target in &targets {
+        let entry = entries
+            .iter()
+            .find(|entry| entry["target"] == target.as_str())
+            .unwrap();
+
+        assert_eq!(entry["type"], "child");
+        assert!(entry["pid"].as_u64().unwrap() > 0);
+        assert_eq!(entry["exit_status"], 0);
+
+        // the recorded output file is the one the child actually wrote to
+        let output = entry["output"].as_str().unwrap();
+        assert!(output.starts_with(&*sub_dir.to_string_lossy()));
+        assert!(std::path::Path::new(output).exists());
+    }
+
+    teardown_tmp_directory(word_tmp_dir);
+    teardown_tmp_directory(tgt_tmp_dir);
+    teardown_tmp_directory(output_dir);
+
+    Ok(())
+}
+
 #[test]
 /// download a wordlist from a url
 fn main_download_wordlist_from_url() -> Result<(), Box<dyn std::error::Error>> {
@@ -263,3 +360,73 @@ fn main_download_wordlist_from_url() -> Result<(), Box<dyn std::error::Error>> {
 
     Ok(())
 }
+
+#[test]
+/// --self-test scans the built-in mock site and reports that everything checked out
+fn main_self_test_passes() {
+    Command::cargo_bin("feroxbuster")
+        .unwrap()
+        .arg("--self-test")
+        .assert()
+        .success()
+        .stdout(
+            predicate::str::contains("/admin/users [200]")
+                .and(predicate::str::contains("/server-status [403]"))
+                .and(predicate::str::contains("Self-test passed")),
+        );
+}
+
+#[test]
+/// --self-test fails when the flags given filter out real content
+fn main_self_test_fails_when_content_is_filtered() {
+    Command::cargo_bin("feroxbuster")
+        .unwrap()
+        .arg("--self-test")
+        .arg("--filter-status")
+        .arg("403")
+        .assert()
+        .failure()
+        .stdout(predicate::str::contains(
+            "/server-status [403] was not reported",
+        ))
+        .stderr(predicate::str::contains("Self-test failed: 1 missed"));
+}
+
+#[test]
+/// a directory expected to take more than --max-dir-requests requests is warned about, along
+/// with a split that stays under it; without a terminal to ask on, the scan still goes ahead
+fn main_warns_when_directory_exceeds_max_dir_requests() -> Result<(), Box<dyn std::error::Error>> {
+    let srv = MockServer::start();
+    let words = [String::from("LICENSE"), String::from("admin")];
+    let (tmp_dir, file) = setup_tmp_directory(&words, "wordlist")?;
+
+    let mock = srv.mock(|when, then| {
+        when.method(GET).path("/LICENSE");
+        then.status(200).body("this is a test");
+    });
+
+    Command::cargo_bin("feroxbuster")
+        .unwrap()
+        .arg("--url")
+        .arg(srv.url("/"))
+        .arg("--wordlist")
+        .arg(file.as_os_str())
+        .arg("--extensions")
+        .arg("php,html")
+        .arg("--max-dir-requests")
+        .arg("4")
+        .assert()
+        .success()
+        .stderr(
+            predicate::str::contains("Each directory is expected to take")
+                .and(predicate::str::contains("more than --max-dir-requests 4"))
+                .and(predicate::str::contains(
+                    "to stay under it, run 2 passes of at most 4 requests each: -x php | -x html",
+                )),
+        );
+
+    assert_eq!(mock.hits(), 1);
+
+    teardown_tmp_directory(tmp_dir);
+    Ok(())
+}
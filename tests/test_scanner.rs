@@ -1,6 +1,6 @@
 mod utils;
 use assert_cmd::prelude::*;
-use httpmock::Method::GET;
+use httpmock::Method::{DELETE, GET, POST};
 use httpmock::MockServer;
 use predicates::prelude::*;
 use std::thread::sleep;
@@ -39,6 +39,172 @@ fn scanner_single_request_scan() -> Result<(), Box<dyn std::error::Error>> {
     Ok(())
 }
 
+#[test]
+/// --output-format replaces the normal line for each finding, both on stdout and in --output
+fn scanner_prints_findings_with_output_format() -> Result<(), Box<dyn std::error::Error>> {
+    let srv = MockServer::start();
+    let (tmp_dir, file) = setup_tmp_directory(&["LICENSE".to_string()], "wordlist")?;
+    let outfile = tmp_dir.path().join("output");
+
+    let mock = srv.mock(|when, then| {
+        when.method(GET).path("/LICENSE");
+        then.status(200)
+            .header("server", "ferox-test")
+            .body("this is a test");
+    });
+
+    let cmd = Command::cargo_bin("feroxbuster")
+        .unwrap()
+        .arg("--url")
+        .arg(srv.url("/"))
+        .arg("--wordlist")
+        .arg(file.as_os_str())
+        .arg("--output-format")
+        .arg(r"{{status}}\t{{path}}\t{{words}}\t{{header.Server}}")
+        .arg("--output")
+        .arg(outfile.as_os_str())
+        .unwrap();
+
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("200\t/LICENSE\t4\tferox-test\n"));
+
+    let contents = std::fs::read_to_string(outfile)?;
+    assert!(contents.contains("200\t/LICENSE\t4\tferox-test\n"));
+
+    assert_eq!(mock.hits(), 1);
+    teardown_tmp_directory(tmp_dir);
+    Ok(())
+}
+
+#[test]
+/// with --tree, findings are printed as a tree once the scan completes
+fn scanner_prints_tree_of_findings() -> Result<(), Box<dyn std::error::Error>> {
+    let srv = MockServer::start();
+    let (tmp_dir, file) = setup_tmp_directory(&["LICENSE".to_string()], "wordlist")?;
+
+    let mock = srv.mock(|when, then| {
+        when.method(GET).path("/LICENSE");
+        then.status(200).body("this is a test");
+    });
+
+    let cmd = Command::cargo_bin("feroxbuster")
+        .unwrap()
+        .arg("--url")
+        .arg(srv.url("/"))
+        .arg("--wordlist")
+        .arg(file.as_os_str())
+        .arg("--tree")
+        .unwrap();
+
+    cmd.assert().success().stdout(
+        predicate::str::contains(srv.url("")).and(predicate::str::contains("└── LICENSE [200]")),
+    );
+
+    assert_eq!(mock.hits(), 1);
+    teardown_tmp_directory(tmp_dir);
+    Ok(())
+}
+
+#[test]
+/// with --adaptive-extensions, an extension that misses on every sampled word stops being
+/// requested for the rest of the directory, while the bare words are still requested
+fn scanner_adaptive_extensions_drop_missing_extension() -> Result<(), Box<dyn std::error::Error>> {
+    let srv = MockServer::start();
+    let words: Vec<_> = ["one", "two", "three", "four"]
+        .iter()
+        .map(|word| word.to_string())
+        .collect();
+    let (tmp_dir, file) = setup_tmp_directory(&words, "wordlist")?;
+
+    let sampled = srv.mock(|when, then| {
+        when.method(GET).path("/one.php");
+        then.status(404);
+    });
+
+    let dropped = srv.mock(|when, then| {
+        when.method(GET).path("/four.php");
+        then.status(404);
+    });
+
+    let bare = srv.mock(|when, then| {
+        when.method(GET).path("/four");
+        then.status(200).body("this is a test");
+    });
+
+    Command::cargo_bin("feroxbuster")
+        .unwrap()
+        .arg("--url")
+        .arg(srv.url("/"))
+        .arg("--wordlist")
+        .arg(file.as_os_str())
+        .arg("-x")
+        .arg("php")
+        .arg("--adaptive-extensions")
+        .arg("2")
+        .arg("--threads")
+        .arg("1")
+        .arg("--dont-filter")
+        .unwrap()
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("/four"));
+
+    assert_eq!(sampled.hits(), 1);
+    assert_eq!(dropped.hits(), 0);
+    assert_eq!(bare.hits(), 1);
+
+    teardown_tmp_directory(tmp_dir);
+    Ok(())
+}
+
+#[test]
+/// words from a wordlist paired with an extension (-x php:FILE) are only requested with that
+/// extension, and the paired extension isn't applied to the main wordlist
+fn scanner_extension_paired_wordlist() -> Result<(), Box<dyn std::error::Error>> {
+    let srv = MockServer::start();
+    let (tmp_dir, file) = setup_tmp_directory(&["LICENSE".to_string()], "wordlist")?;
+    let paired = tmp_dir.path().join("paired");
+    std::fs::write(&paired, "admin\n")?;
+
+    let paired_mock = srv.mock(|when, then| {
+        when.method(GET).path("/admin.php");
+        then.status(200).body("this is a test");
+    });
+
+    let bare_mock = srv.mock(|when, then| {
+        when.method(GET).path("/admin");
+        then.status(200).body("this is a test");
+    });
+
+    let unpaired_mock = srv.mock(|when, then| {
+        when.method(GET).path("/LICENSE.php");
+        then.status(200).body("this is a test");
+    });
+
+    let cmd = Command::cargo_bin("feroxbuster")
+        .unwrap()
+        .arg("--url")
+        .arg(srv.url("/"))
+        .arg("--wordlist")
+        .arg(file.as_os_str())
+        .arg("-x")
+        .arg(format!("php:{}", paired.display()))
+        .arg("-vvvv")
+        .unwrap();
+
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("/admin.php"))
+        .stderr(predicate::str::contains("Extension Wordlist"));
+
+    assert_eq!(paired_mock.hits(), 1);
+    assert_eq!(bare_mock.hits(), 0);
+    assert_eq!(unpaired_mock.hits(), 0);
+    teardown_tmp_directory(tmp_dir);
+    Ok(())
+}
+
 #[test]
 /// send a valid request, follow redirects into new directories, expect 301/200 responses
 fn scanner_recursive_request_scan() -> Result<(), Box<dyn std::error::Error>> {
@@ -165,6 +331,51 @@ fn scanner_recursive_request_scan_using_only_success_responses(
     Ok(())
 }
 
+#[test]
+/// --recurse-threshold counts every finding of the parent scan, including those found after the
+/// directory itself
+fn scanner_recurse_threshold_waits_for_parent_scan() -> Result<(), Box<dyn std::error::Error>> {
+    for (threshold, expected_hits) in [("2", 1), ("3", 0)] {
+        let srv = MockServer::start();
+        let urls = ["js/".to_string(), "file.js".to_string()];
+        let (tmp_dir, file) = setup_tmp_directory(&urls, "wordlist")?;
+
+        srv.mock(|when, then| {
+            when.method(GET).path("/js/");
+            then.status(200);
+        });
+
+        srv.mock(|when, then| {
+            when.method(GET).path("/file.js");
+            then.status(200).body("this is a test");
+        });
+
+        let js_file_mock = srv.mock(|when, then| {
+            when.method(GET).path("/js/file.js");
+            then.status(200).body("this is a test");
+        });
+
+        Command::cargo_bin("feroxbuster")
+            .unwrap()
+            .arg("--url")
+            .arg(srv.url("/"))
+            .arg("--wordlist")
+            .arg(file.as_os_str())
+            .arg("-t")
+            .arg("1")
+            .arg("--recurse-threshold")
+            .arg(threshold)
+            .assert()
+            .success();
+
+        assert_eq!(js_file_mock.hits(), expected_hits);
+
+        teardown_tmp_directory(tmp_dir);
+    }
+
+    Ok(())
+}
+
 #[test]
 /// send a single valid request, get a response, and write it to disk
 fn scanner_single_request_scan_with_file_output() -> Result<(), Box<dyn std::error::Error>> {
@@ -385,6 +596,100 @@ fn scanner_single_request_replayed_to_proxy() -> Result<(), Box<dyn std::error::
     Ok(())
 }
 
+#[test]
+/// the original request and its replay both carry the finding's trace id
+fn scanner_sends_trace_id_with_original_and_replayed_request(
+) -> Result<(), Box<dyn std::error::Error>> {
+    let srv = MockServer::start();
+    let proxy = MockServer::start();
+    let (tmp_dir, file) = setup_tmp_directory(&["LICENSE".to_string()], "wordlist")?;
+
+    let mock = srv.mock(|when, then| {
+        when.method(GET)
+            .path("/LICENSE")
+            .header_exists("X-Ferox-Trace");
+        then.status(200).body("this is a test");
+    });
+
+    let mock_two = proxy.mock(|when, then| {
+        when.method(GET)
+            .path("/LICENSE")
+            .header_exists("X-Ferox-Trace");
+        then.status(200).body("this is a test");
+    });
+
+    Command::cargo_bin("feroxbuster")
+        .unwrap()
+        .arg("--url")
+        .arg(srv.url("/"))
+        .arg("--wordlist")
+        .arg(file.as_os_str())
+        .arg("--replay-proxy")
+        .arg(format!("http://{}", proxy.address()))
+        .arg("--replay-codes")
+        .arg("200")
+        .assert()
+        .success();
+
+    assert_eq!(mock.hits(), 1);
+    assert_eq!(mock_two.hits(), 1);
+    teardown_tmp_directory(tmp_dir);
+    Ok(())
+}
+
+#[test]
+/// send a single valid request, expect a 200 response that then gets handed to the zap api,
+/// along with the api key
+fn scanner_single_request_sent_to_zap() -> Result<(), Box<dyn std::error::Error>> {
+    let srv = MockServer::start();
+    let zap = MockServer::start();
+    let (tmp_dir, file) = setup_tmp_directory(&["LICENSE".to_string()], "wordlist")?;
+
+    let mock = srv.mock(|when, then| {
+        when.method(GET).path("/LICENSE");
+        then.status(200).body("this is a test");
+    });
+
+    let version = zap.mock(|when, then| {
+        when.method(GET)
+            .path("/JSON/core/view/version/")
+            .header("X-ZAP-API-Key", "changeme");
+        then.status(200).body(r#"{"version":"2.15.0"}"#);
+    });
+
+    let access_url = zap.mock(|when, then| {
+        when.method(GET)
+            .path("/JSON/core/action/accessUrl/")
+            .query_param("url", srv.url("/LICENSE"))
+            .query_param("followRedirects", "false")
+            .header("X-ZAP-API-Key", "changeme");
+        then.status(200).body(r#"{"Result":"OK"}"#);
+    });
+
+    let cmd = Command::cargo_bin("feroxbuster")
+        .unwrap()
+        .arg("--url")
+        .arg(srv.url("/"))
+        .arg("--wordlist")
+        .arg(file.as_os_str())
+        .arg("--zap-api-url")
+        .arg(zap.url("/"))
+        .arg("--zap-api-key")
+        .arg("changeme")
+        .unwrap();
+
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("/LICENSE").and(predicate::str::contains("200")))
+        .stderr(predicate::str::contains("ZAP API"));
+
+    assert_eq!(mock.hits(), 1);
+    assert_eq!(version.hits(), 1);
+    assert_eq!(access_url.hits(), 1);
+    teardown_tmp_directory(tmp_dir);
+    Ok(())
+}
+
 #[test]
 /// send a single valid request, filter the size of the response, expect one out of 2 urls
 fn scanner_single_request_scan_with_filtered_result() -> Result<(), Box<dyn std::error::Error>> {
@@ -512,6 +817,81 @@ fn scanner_single_request_scan_with_debug_logging_as_json() {
     teardown_tmp_directory(tmp_dir);
 }
 
+#[test]
+/// send a single valid request, get a response, and write it to disk as a SARIF log
+fn scanner_single_request_scan_with_sarif_output() {
+    let srv = MockServer::start();
+    let (tmp_dir, file) = setup_tmp_directory(&["LICENSE".to_string()], "wordlist").unwrap();
+
+    let mock = srv.mock(|when, then| {
+        when.method(GET).path("/LICENSE");
+        then.status(200).body("this is a test");
+    });
+
+    let outfile = tmp_dir.path().join("results.sarif");
+
+    Command::cargo_bin("feroxbuster")
+        .unwrap()
+        .arg("--url")
+        .arg(srv.url("/"))
+        .arg("--wordlist")
+        .arg(file.as_os_str())
+        .arg("--output")
+        .arg(outfile.as_os_str())
+        .arg("--sarif")
+        .unwrap();
+
+    let contents = std::fs::read_to_string(outfile).unwrap();
+    let sarif: serde_json::Value = serde_json::from_str(&contents).unwrap();
+    let results = sarif["runs"][0]["results"].as_array().unwrap();
+
+    assert_eq!(sarif["version"], "2.1.0");
+    assert_eq!(results.len(), 1);
+    assert_eq!(results[0]["ruleId"], "LICENSE");
+    assert_eq!(results[0]["properties"]["url"], srv.url("/LICENSE"));
+    assert_eq!(results[0]["properties"]["status"], 200);
+
+    assert_eq!(mock.hits(), 1);
+    teardown_tmp_directory(tmp_dir);
+}
+
+#[test]
+/// send a single valid request with --csv, expect a header row and a row for the finding
+fn scanner_single_request_scan_with_csv_output() {
+    let srv = MockServer::start();
+    let (tmp_dir, file) = setup_tmp_directory(&["LICENSE".to_string()], "wordlist").unwrap();
+
+    let mock = srv.mock(|when, then| {
+        when.method(GET).path("/LICENSE");
+        then.status(200).body("this is a test");
+    });
+
+    let outfile = tmp_dir.path().join("results.csv");
+
+    Command::cargo_bin("feroxbuster")
+        .unwrap()
+        .arg("--url")
+        .arg(srv.url("/"))
+        .arg("--wordlist")
+        .arg(file.as_os_str())
+        .arg("--output")
+        .arg(outfile.as_os_str())
+        .arg("--csv")
+        .arg("--csv-columns")
+        .arg("url,status,chars")
+        .unwrap();
+
+    let contents = std::fs::read_to_string(outfile).unwrap();
+    let lines: Vec<_> = contents.lines().collect();
+
+    assert_eq!(lines.len(), 2);
+    assert_eq!(lines[0], "url,status,chars");
+    assert_eq!(lines[1], format!("{},200,14", srv.url("/LICENSE")));
+
+    assert_eq!(mock.hits(), 1);
+    teardown_tmp_directory(tmp_dir);
+}
+
 #[test]
 /// send a single valid request, filter the response by regex, expect one out of 2 urls
 fn scanner_single_request_scan_with_regex_filtered_result() {
@@ -921,3 +1301,491 @@ fn scanner_forced_recursion_ignores_normal_redirect_logic() -> Result<(), Box<dy
 
     Ok(())
 }
+
+#[test]
+/// --known-urls entries are requested before the wordlist, reported when they still exist, and
+/// those of their parent directories that respond like directories are recursed into
+fn scanner_seeds_scan_with_known_urls() -> Result<(), Box<dyn std::error::Error>> {
+    let srv = MockServer::start();
+    let (tmp_dir, file) = setup_tmp_directory(&["LICENSE".to_string()], "wordlist")?;
+
+    let known = tmp_dir.path().join("known-urls");
+    std::fs::write(
+        &known,
+        format!(
+            "# previously discovered\n{}\n{}\n",
+            srv.url("/app/static/main.js"),
+            srv.url("/gone.php")
+        ),
+    )?;
+
+    let main_js = srv.mock(|when, then| {
+        when.method(GET).path("/app/static/main.js");
+        then.status(200).body("console.log('still here');");
+    });
+
+    let gone = srv.mock(|when, then| {
+        when.method(GET).path("/gone.php");
+        then.status(404);
+    });
+
+    let static_dir = srv.mock(|when, then| {
+        when.method(GET).path("/app/static/");
+        then.status(403);
+    });
+
+    let recursed = srv.mock(|when, then| {
+        when.method(GET).path("/app/static/LICENSE");
+        then.status(200).body("this is a test");
+    });
+
+    let cmd = Command::cargo_bin("feroxbuster")
+        .unwrap()
+        .arg("--url")
+        .arg(srv.url("/"))
+        .arg("--wordlist")
+        .arg(file.as_os_str())
+        .arg("--known-urls")
+        .arg(known.as_os_str())
+        .unwrap();
+
+    cmd.assert().success().stdout(
+        predicate::str::contains("/app/static/main.js")
+            .and(predicate::str::contains("/app/static/LICENSE"))
+            .and(predicate::str::contains("/gone.php").not()),
+    );
+
+    assert_eq!(main_js.hits(), 1);
+    assert_eq!(gone.hits(), 1);
+    assert!(static_dir.hits() >= 1);
+    assert_eq!(recursed.hits(), 1);
+    teardown_tmp_directory(tmp_dir);
+    Ok(())
+}
+
+#[test]
+/// --import-burp turns the export's host into the target, sends its headers along with every
+/// request, and seeds the scan with its known-good urls
+fn scanner_seeds_scan_with_burp_import() -> Result<(), Box<dyn std::error::Error>> {
+    let srv = MockServer::start();
+    let (tmp_dir, file) = setup_tmp_directory(&["LICENSE".to_string()], "wordlist")?;
+
+    let export = tmp_dir.path().join("burp.xml");
+    std::fs::write(
+        &export,
+        format!(
+            r#"<?xml version="1.0"?>
+<items burpVersion="2024.3.1" exportTime="Mon Apr 01 12:00:00 UTC 2024">
+  <item>
+    <url><![CDATA[{}]]></url>
+    <request base64="false"><![CDATA[GET /app/main.js HTTP/1.1
+Host: {}
+X-Burp: imported
+Cookie: session=abc
+
+]]></request>
+    <status>200</status>
+  </item>
+</items>
+"#,
+            srv.url("/app/main.js"),
+            srv.address()
+        ),
+    )?;
+
+    let main_js = srv.mock(|when, then| {
+        when.method(GET)
+            .path("/app/main.js")
+            .header("x-burp", "imported")
+            .header("cookie", "session=abc");
+        then.status(200).body("console.log('still here');");
+    });
+
+    let license = srv.mock(|when, then| {
+        when.method(GET)
+            .path("/LICENSE")
+            .header("x-burp", "imported");
+        then.status(200).body("this is a test");
+    });
+
+    srv.mock(|when, then| {
+        when.method(GET).path("/app/");
+        then.status(403);
+    });
+
+    let recursed = srv.mock(|when, then| {
+        when.method(GET).path("/app/LICENSE");
+        then.status(200).body("this is a test");
+    });
+
+    let cmd = Command::cargo_bin("feroxbuster")
+        .unwrap()
+        .arg("--import-burp")
+        .arg(export.as_os_str())
+        .arg("--wordlist")
+        .arg(file.as_os_str())
+        .unwrap();
+
+    cmd.assert().success().stdout(
+        predicate::str::contains("/app/main.js")
+            .and(predicate::str::contains("/app/LICENSE"))
+            .and(predicate::str::contains("/LICENSE")),
+    );
+
+    assert_eq!(main_js.hits(), 1);
+    assert_eq!(license.hits(), 1);
+    assert_eq!(recursed.hits(), 1);
+    teardown_tmp_directory(tmp_dir);
+    Ok(())
+}
+
+#[test]
+/// test that findings carry the wordlist entry and extension behind them in json output, and
+/// show them in the terminal when -v is used
+fn scanner_reports_originating_word_and_extension() -> Result<(), Box<dyn std::error::Error>> {
+    let srv = MockServer::start();
+    let (tmp_dir, file) = setup_tmp_directory(&["LICENSE".to_string()], "wordlist")?;
+    let outfile = tmp_dir.path().join("output");
+
+    let mock = srv.mock(|when, then| {
+        when.method(GET).path("/LICENSE.txt");
+        then.status(200).body("this is a test");
+    });
+
+    let cmd = Command::cargo_bin("feroxbuster")
+        .unwrap()
+        .arg("--url")
+        .arg(srv.url("/"))
+        .arg("--wordlist")
+        .arg(file.as_os_str())
+        .arg("--extensions")
+        .arg("txt")
+        .arg("--json")
+        .arg("--output")
+        .arg(outfile.as_os_str())
+        .arg("-v")
+        .unwrap();
+
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("(word: LICENSE +.txt)"));
+
+    let contents = std::fs::read_to_string(outfile)?;
+    assert!(contents.contains(r#""word":"LICENSE","applied_extension":"txt""#));
+
+    assert_eq!(mock.hits(), 1);
+    teardown_tmp_directory(tmp_dir);
+    Ok(())
+}
+
+#[test]
+/// test that discovered urls are appended to --nuclei-targets, one per line
+fn scanner_writes_nuclei_targets() -> Result<(), Box<dyn std::error::Error>> {
+    let srv = MockServer::start();
+    let (tmp_dir, file) = setup_tmp_directory(&["LICENSE".to_string()], "wordlist")?;
+    let targets = tmp_dir.path().join("nuclei-targets");
+
+    let mock = srv.mock(|when, then| {
+        when.method(GET).path("/LICENSE");
+        then.status(200).body("this is a test");
+    });
+
+    let cmd = Command::cargo_bin("feroxbuster")
+        .unwrap()
+        .arg("--url")
+        .arg(srv.url("/"))
+        .arg("--wordlist")
+        .arg(file.as_os_str())
+        .arg("--nuclei-targets")
+        .arg(targets.as_os_str())
+        .unwrap();
+
+    cmd.assert().success();
+
+    let contents = std::fs::read_to_string(targets)?;
+    assert_eq!(contents, format!("{}\n", srv.url("/LICENSE")));
+
+    assert_eq!(mock.hits(), 1);
+    teardown_tmp_directory(tmp_dir);
+    Ok(())
+}
+
+#[test]
+/// test that findings from a previous run are requested conditionally with --changed-since, and
+/// that 304s are reported as unchanged
+fn scanner_reports_unchanged_findings_with_changed_since() -> Result<(), Box<dyn std::error::Error>>
+{
+    let srv = MockServer::start();
+    let (tmp_dir, file) =
+        setup_tmp_directory(&["LICENSE".to_string(), "README".to_string()], "wordlist")?;
+
+    let previous = tmp_dir.path().join("previous.json");
+    std::fs::write(
+        &previous,
+        format!(
+            "{}\n{}\n",
+            r#"{"type":"metadata","schema_version":1}"#,
+            serde_json::json!({
+                "type": "response",
+                "url": srv.url("/LICENSE"),
+                "wildcard": false,
+                "headers": {"etag": "\"abc\"", "last-modified": "Wed, 21 Oct 2015 07:28:00 GMT"},
+            })
+        ),
+    )?;
+
+    let license = srv.mock(|when, then| {
+        when.method(GET)
+            .path("/LICENSE")
+            .header("if-none-match", "\"abc\"")
+            .header("if-modified-since", "Wed, 21 Oct 2015 07:28:00 GMT");
+        then.status(304);
+    });
+
+    let readme = srv.mock(|when, then| {
+        when.method(GET).path("/README");
+        then.status(200).body("this is a test");
+    });
+
+    let cmd = Command::cargo_bin("feroxbuster")
+        .unwrap()
+        .arg("--url")
+        .arg(srv.url("/"))
+        .arg("--wordlist")
+        .arg(file.as_os_str())
+        .arg("--changed-since")
+        .arg(previous.as_os_str())
+        .unwrap();
+
+    cmd.assert().success().stdout(
+        predicate::str::contains("/LICENSE (unchanged)")
+            .and(predicate::str::contains("/README"))
+            .and(predicate::str::contains("/README (unchanged)").not()),
+    );
+
+    assert_eq!(license.hits(), 1);
+    assert_eq!(readme.hits(), 1);
+    teardown_tmp_directory(tmp_dir);
+    Ok(())
+}
+
+#[test]
+/// test that open http services from --nmap-xml become targets, including non-standard ports
+fn scanner_scans_targets_from_nmap_xml() -> Result<(), Box<dyn std::error::Error>> {
+    let srv = MockServer::start();
+    let (tmp_dir, file) = setup_tmp_directory(&["LICENSE".to_string()], "wordlist")?;
+
+    let scan = tmp_dir.path().join("scan.xml");
+    std::fs::write(
+        &scan,
+        format!(
+            r#"<?xml version="1.0" encoding="UTF-8"?>
+<nmaprun scanner="nmap" args="nmap -sV -oX scan.xml 127.0.0.1" version="7.94">
+<host><status state="up" reason="localhost-response"/>
+<address addr="127.0.0.1" addrtype="ipv4"/>
+<hostnames/>
+<ports>
+<port protocol="tcp" portid="{}"><state state="open" reason="syn-ack"/><service name="http" product="httpmock"/></port>
+<port protocol="tcp" portid="22"><state state="open" reason="syn-ack"/><service name="ssh"/></port>
+</ports>
+</host>
+</nmaprun>
+"#,
+            srv.port()
+        ),
+    )?;
+
+    let mock = srv.mock(|when, then| {
+        when.method(GET).path("/LICENSE");
+        then.status(200).body("this is a test");
+    });
+
+    let cmd = Command::cargo_bin("feroxbuster")
+        .unwrap()
+        .arg("--nmap-xml")
+        .arg(scan.as_os_str())
+        .arg("--wordlist")
+        .arg(file.as_os_str())
+        .unwrap();
+
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains(format!(
+            "http://127.0.0.1:{}/LICENSE",
+            srv.port()
+        )));
+
+    assert_eq!(mock.hits(), 1);
+    teardown_tmp_directory(tmp_dir);
+    Ok(())
+}
+
+#[test]
+/// test that directories from --import-crawl are recursed into, while the crawled pages
+/// themselves aren't requested again, even when found via extraction
+fn scanner_seeds_recursion_from_imported_crawl() -> Result<(), Box<dyn std::error::Error>> {
+    let srv = MockServer::start();
+    let (tmp_dir, file) = setup_tmp_directory(&["LICENSE".to_string()], "wordlist")?;
+
+    let crawl = tmp_dir.path().join("crawl.jsonl");
+    std::fs::write(
+        &crawl,
+        format!(
+            r#"{{"request":{{"method":"GET","endpoint":"{}"}},"response":{{"status_code":200}}}}
+{{"type":"url","output":"http://elsewhere.example/other/page.html","status":200}}
+"#,
+            srv.url("/crawled/page.html")
+        ),
+    )?;
+
+    let mock = srv.mock(|when, then| {
+        when.method(GET).path("/LICENSE");
+        then.status(200).body(srv.url("/crawled/page.html"));
+    });
+
+    srv.mock(|when, then| {
+        when.method(GET).path("/crawled/");
+        then.status(403);
+    });
+
+    let mock_two = srv.mock(|when, then| {
+        when.method(GET).path("/crawled/LICENSE");
+        then.status(200).body("this is a test");
+    });
+
+    let mock_three = srv.mock(|when, then| {
+        when.method(GET).path("/crawled/page.html");
+        then.status(200).body("crawled already");
+    });
+
+    let cmd = Command::cargo_bin("feroxbuster")
+        .unwrap()
+        .arg("--url")
+        .arg(srv.url("/"))
+        .arg("--import-crawl")
+        .arg(crawl.as_os_str())
+        .arg("--wordlist")
+        .arg(file.as_os_str())
+        .unwrap();
+
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("/crawled/LICENSE"));
+
+    assert_eq!(mock.hits(), 1);
+    assert_eq!(mock_two.hits(), 1);
+    assert_eq!(mock_three.hits(), 0);
+    teardown_tmp_directory(tmp_dir);
+    Ok(())
+}
+
+#[test]
+/// test that --openapi auto finds a spec on the target and requests each documented operation,
+/// with its parameters filled in
+fn scanner_seeds_scan_with_discovered_openapi_spec() -> Result<(), Box<dyn std::error::Error>> {
+    let srv = MockServer::start();
+    let (tmp_dir, file) = setup_tmp_directory(&["LICENSE".to_string()], "wordlist")?;
+
+    let spec = srv.mock(|when, then| {
+        when.method(GET).path("/openapi.json");
+        then.status(200).body(
+            r#"{
+  "openapi": "3.0.0",
+  "servers": [{"url": "/v1"}],
+  "paths": {
+    "/users/{id}": {
+      "get": {"parameters": [{"name": "id", "in": "path", "schema": {"type": "integer", "example": 42}}]},
+      "delete": {"parameters": [{"name": "id", "in": "path", "schema": {"type": "integer"}}]}
+    }
+  }
+}"#,
+        );
+    });
+
+    let get_user = srv.mock(|when, then| {
+        when.method(GET).path("/v1/users/42");
+        then.status(200).body("{\"id\": 42}");
+    });
+
+    let delete_user = srv.mock(|when, then| {
+        when.method(DELETE).path("/v1/users/1");
+        then.status(204);
+    });
+
+    let cmd = Command::cargo_bin("feroxbuster")
+        .unwrap()
+        .arg("--url")
+        .arg(srv.url("/"))
+        .arg("--wordlist")
+        .arg(file.as_os_str())
+        .arg("--openapi")
+        .arg("auto")
+        .arg("--no-recursion")
+        .unwrap();
+
+    cmd.assert().success().stdout(
+        predicate::str::contains("/openapi.json")
+            .and(predicate::str::contains("/v1/users/42"))
+            .and(predicate::str::contains("/v1/users/1")),
+    );
+
+    assert_eq!(spec.hits(), 1);
+    assert_eq!(get_user.hits(), 1);
+    assert_eq!(delete_user.hits(), 1);
+    teardown_tmp_directory(tmp_dir);
+    Ok(())
+}
+
+#[test]
+/// test that --graphql finds an endpoint on the target and reports the operations found through
+/// introspection
+fn scanner_reports_graphql_operations() -> Result<(), Box<dyn std::error::Error>> {
+    let srv = MockServer::start();
+    let (tmp_dir, file) = setup_tmp_directory(&["LICENSE".to_string()], "wordlist")?;
+
+    let probe = srv.mock(|when, then| {
+        when.method(POST)
+            .path("/api/graphql")
+            .body_contains("__typename");
+        then.status(200)
+            .body(r#"{"data": {"__typename": "Query"}}"#);
+    });
+
+    let introspection = srv.mock(|when, then| {
+        when.method(POST)
+            .path("/api/graphql")
+            .body_contains("__schema");
+        then.status(200).body(
+            r#"{"data": {"__schema": {
+  "queryType": {"name": "Query"},
+  "mutationType": {"name": "Mutation"},
+  "subscriptionType": null,
+  "types": [
+    {"name": "Query", "fields": [{"name": "currentUser"}]},
+    {"name": "Mutation", "fields": [{"name": "resetPassword"}]}
+  ]
+}}}"#,
+        );
+    });
+
+    let cmd = Command::cargo_bin("feroxbuster")
+        .unwrap()
+        .arg("--url")
+        .arg(srv.url("/"))
+        .arg("--wordlist")
+        .arg(file.as_os_str())
+        .arg("--graphql")
+        .arg("--no-recursion")
+        .unwrap();
+
+    cmd.assert().success().stdout(
+        predicate::str::contains("/api/graphql")
+            .and(predicate::str::contains("queries: currentUser"))
+            .and(predicate::str::contains("mutations: resetPassword")),
+    );
+
+    assert_eq!(probe.hits(), 1);
+    assert_eq!(introspection.hits(), 1);
+    teardown_tmp_directory(tmp_dir);
+    Ok(())
+}